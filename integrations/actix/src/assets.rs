@@ -0,0 +1,76 @@
+//! Serves the files of the site, with URLs that change whenever they do.
+//!
+//! [`hashed_assets`] returns a route that serves the files in the
+//! [`site_root`](LeptosOptions::site_root). It hashes the contents of each
+//! file when it is created, and the app's pages then load the files in their
+//! `<head>`, like its JS, WASM and CSS, with the hash in their URLs (see
+//! [`AssetManifest`]). Those are cached by the browser for a year, as they
+//! never change, while HTML files and files requested without the hash are
+//! revalidated with their `ETag` every time:
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
+//!     .default_service(hashed_assets(&leptos_options)?)
+//! ```
+//!
+//! Requests for files that do not exist are answered with `404 Not Found`.
+
+use actix_web::{
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+        Method,
+    },
+    web, HttpRequest, HttpResponse, Route,
+};
+use leptos::LeptosOptions;
+pub use leptos_integration_utils::{Asset, AssetManifest};
+use std::{io, sync::Arc};
+
+/// Returns a route that serves the files in the `site_root`, after hashing
+/// them and [installing](AssetManifest::install) the manifest, so that the
+/// pages of the app load them with the hashes in their URLs. See the
+/// [module docs](self).
+pub fn hashed_assets(options: &LeptosOptions) -> io::Result<Route> {
+    let manifest = AssetManifest::build(options)?.install();
+    Ok(web::to(move |req: HttpRequest| {
+        let manifest = Arc::clone(&manifest);
+        async move {
+            let asset = [Method::GET, Method::HEAD]
+                .contains(req.method())
+                .then(|| manifest.resolve(req.path(), Some(req.query_string())))
+                .flatten();
+            match asset {
+                Some(asset) => serve(&req, asset).await,
+                None => HttpResponse::NotFound().finish(),
+            }
+        }
+    }))
+}
+
+async fn serve(req: &HttpRequest, asset: Asset) -> HttpResponse {
+    let mut res = HttpResponse::Ok();
+    res.insert_header((CACHE_CONTROL, asset.cache_control))
+        .insert_header((CONTENT_TYPE, asset.content_type));
+    if let Some(etag) = &asset.etag {
+        res.insert_header((ETAG, etag.as_str()));
+        let matches = req
+            .headers()
+            .get_all(IF_NONE_MATCH)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|tag| tag.trim() == etag || tag.trim() == "*");
+        if matches {
+            return res
+                .status(actix_web::http::StatusCode::NOT_MODIFIED)
+                .finish();
+        }
+    }
+    match tokio::fs::read(&asset.file).await {
+        Ok(body) => res.body(body),
+        Err(e) => {
+            tracing::error!("failed to read {}: {e}", asset.file.display());
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
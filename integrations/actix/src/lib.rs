@@ -2,10 +2,32 @@
 
 //! Provides functions to easily integrate Leptos with Actix.
 //!
+//! ## Features
+//! - `nonce`: activates Leptos features that automatically provide a CSP
+//!   [`Nonce`](leptos::nonce::Nonce) via context
+//! - `csrf`: protects server functions from cross-site request forgery
+//! - `websocket`: supports server functions that communicate over a WebSocket
+//!
+//! ## Serving several apps
+//! Several Leptos apps, each with its own routes, WASM bundle, and [`LeptosOptions`], can be
+//! served by one server under different paths, e.g., while migrating from one to another.
+//! Give each app its own `site_base_path`, `output_name`, and `server_fn_prefix`, use the
+//! same base path as the `base` of its `<Router/>` and the same prefix in the `#[server]`
+//! macros of its server functions, and add the routes of each:
+//! ```rust,ignore
+//! App::new()
+//!     .leptos_routes(shop_options, generate_route_list(shop::App), shop::App)
+//!     .leptos_routes(blog_options, generate_route_list(blog::App), blog::App)
+//! ```
+//! Each app then only registers and runs its own server functions.
+//!
 //! For more details on how to use the integrations, see the
 //! [`examples`](https://github.com/leptos-rs/leptos/tree/main/examples)
 //! directory in the Leptos repository.
 
+pub mod assets;
+pub mod security;
+
 use actix_http::header::{HeaderName, HeaderValue, ACCEPT};
 use actix_web::{
     body::BoxBody,
@@ -17,20 +39,35 @@ use actix_web::{
 use futures::{Stream, StreamExt};
 use http::StatusCode;
 use leptos::{
+    deadline::provide_render_deadline,
+    leptos_server::{
+        provide_server_response,
+        request::{provide_server_request, RequestContext, ServerRequest},
+        warn_if_response_sent, ResponseUpdate,
+    },
     ssr::render_to_stream_with_prefix_undisposed_with_context_and_block_replacement,
     *,
 };
-use leptos_integration_utils::{build_async_response, html_parts_separated};
+use leptos_integration_utils::{
+    build_async_response, content_security_policy, html_stream,
+    is_app_server_fn, with_site_base_path,
+};
 use leptos_meta::*;
 use leptos_router::*;
 use parking_lot::RwLock;
 use regex::Regex;
+#[cfg(feature = "websocket")]
+use send_wrapper::SendWrapper;
+#[cfg(feature = "websocket")]
+use server_fn::websocket::UpgradeContext;
 use server_fn::{redirect::REDIRECT_HEADER, request::actix::ActixRequest};
 use std::{
     fmt::{Debug, Display},
     future::Future,
+    net::SocketAddr,
     pin::Pin,
     sync::Arc,
+    time::Duration,
 };
 #[cfg(debug_assertions)]
 use tracing::instrument;
@@ -62,6 +99,15 @@ impl ResponseParts {
 }
 
 /// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
+///
+/// Server functions can also do this without depending on this integration, with
+/// [`set_response_status`], [`insert_response_header`], [`append_response_header`],
+/// and [`set_cookie`].
+///
+/// When a page is streamed, the status code and headers are sent with its shell, so they can
+/// be changed by any component rendered before then, including within `<Suspense/>` fragments
+/// that block the shell. Changes made after the shell has been sent, like within a fragment
+/// streamed in after it, are ignored, with a warning in debug builds.
 #[derive(Debug, Clone, Default)]
 pub struct ResponseOptions(pub Arc<RwLock<ResponseParts>>);
 
@@ -73,6 +119,9 @@ impl ResponseOptions {
     }
     /// Set the status of the returned Response.
     pub fn set_status(&self, status: StatusCode) {
+        if warn_if_response_sent(ResponseUpdate::Status(status.as_u16())) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.status = Some(status);
@@ -83,6 +132,12 @@ impl ResponseOptions {
         key: header::HeaderName,
         value: header::HeaderValue,
     ) {
+        if warn_if_response_sent(ResponseUpdate::InsertHeader(
+            key.as_str(),
+            value.to_str().unwrap_or_default(),
+        )) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.headers.insert(key, value);
@@ -93,12 +148,71 @@ impl ResponseOptions {
         key: header::HeaderName,
         value: header::HeaderValue,
     ) {
+        if warn_if_response_sent(ResponseUpdate::AppendHeader(
+            key.as_str(),
+            value.to_str().unwrap_or_default(),
+        )) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.headers.append(key, value);
     }
 }
 
+// provides the `ResponseOptions`, and lets server functions change them without
+// depending on this integration, with `leptos::set_response_status` etc.
+fn provide_response_options(res_options: ResponseOptions) {
+    provide_context(res_options.clone());
+    provide_server_response(move |update| match update {
+        ResponseUpdate::Status(status) => {
+            let status =
+                StatusCode::from_u16(status).map_err(|e| e.to_string())?;
+            res_options.set_status(status);
+            Ok(())
+        }
+        ResponseUpdate::InsertHeader(name, value) => {
+            let (name, value) = header_from_str(name, value)?;
+            res_options.insert_header(name, value);
+            Ok(())
+        }
+        ResponseUpdate::AppendHeader(name, value) => {
+            let (name, value) = header_from_str(name, value)?;
+            res_options.append_header(name, value);
+            Ok(())
+        }
+    })
+}
+
+fn header_from_str(
+    name: &str,
+    value: &str,
+) -> Result<(header::HeaderName, header::HeaderValue), String> {
+    let name = header::HeaderName::from_bytes(name.as_bytes())
+        .map_err(|e| format!("invalid header name {name:?}: {e}"))?;
+    let value = header::HeaderValue::from_str(value)
+        .map_err(|e| format!("invalid header value {value:?}: {e}"))?;
+    Ok((name, value))
+}
+
+/// Sets a `Content-Security-Policy` header on the response that allows the
+/// inline scripts and styles rendered by Leptos, which carry the
+/// [`Nonce`](leptos::nonce::Nonce) of the response, and blocks any others.
+/// This can be called in the app, like in its root component, to apply a
+/// strict policy to every page.
+///
+/// This requires the `nonce` feature, without which it does nothing.
+pub fn set_content_security_policy(options: &LeptosOptions) {
+    if let (Some(csp), Some(res)) = (
+        content_security_policy(options),
+        use_context::<ResponseOptions>(),
+    ) {
+        if let Ok(csp) = HeaderValue::from_str(&csp) {
+            res.insert_header(header::CONTENT_SECURITY_POLICY, csp);
+        }
+    }
+}
+
 /// Provides an easy way to redirect the user from within a server function.
 ///
 /// This sets the `Location` header to the URL given.
@@ -152,6 +266,42 @@ pub fn redirect(path: &str) {
     }
 }
 
+/// Provides a value as context for the request, from an Actix middleware, so
+/// that the components rendered for it and the server functions it calls can
+/// use it with [`use_context`] or [`expect_context`], like a database pool or
+/// the session of the user. See [`RequestContext`].
+///
+/// ```rust,ignore
+/// let app = App::new()
+///     .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
+///     .wrap_fn(|req, srv| {
+///         let session = load_session(req.headers());
+///         leptos_actix::provide_request_context(&req, session);
+///         srv.call(req)
+///     });
+/// ```
+pub fn provide_request_context<T>(req: &impl HttpMessage, value: T)
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let mut extensions = req.extensions_mut();
+    match extensions.get_mut::<RequestContext>() {
+        Some(context) => context.insert(value),
+        None => {
+            let mut context = RequestContext::new();
+            context.insert(value);
+            extensions.insert(context);
+        }
+    }
+}
+
+// provides the values that middleware has provided for the request
+fn provide_request_contexts(req: &HttpRequest) {
+    if let Some(context) = req.extensions().get::<RequestContext>() {
+        context.provide();
+    }
+}
+
 /// An Actix [struct@Route](actix_web::Route) that listens for a `POST` request with
 /// Leptos server function arguments in the body, runs the server function if found,
 /// and returns the resulting [HttpResponse].
@@ -223,47 +373,51 @@ pub fn handle_server_fns_with_context(
 
             let path = req.path();
             if let Some(mut service) =
-                server_fn::actix::get_server_fn_service(path)
+                server_fn::actix::get_server_fn_service(path, req.method())
             {
-                let runtime = create_runtime();
+                #[cfg(feature = "websocket")]
+                provide_upgrade_context(&req, additional_context.clone());
 
                 // Add additional info to the context of the server function
-                additional_context();
-                provide_context(req.clone());
                 let res_parts = ResponseOptions::default();
-                provide_context(res_parts.clone());
-
-                let mut res = service
-                    .0
-                    .run(ActixRequest::from((req, payload)))
-                    .await
-                    .take();
-
-                // Override StatusCode if it was set in a Resource or Element
-                if let Some(status) = res_parts.0.read().status {
-                    *res.status_mut() = status;
-                }
-
-                // Use provided ResponseParts headers if they exist
-                let headers = res.headers_mut();
-                let mut res_parts = res_parts.0.write();
-
-                // Location is set to redirect to Referer in the server handler handler by default,
-                // but it can only have a single value
-                //
-                // if we have used redirect() we will end up appending this second Location value
-                // to the first one, which will cause an invalid response
-                // see https://github.com/leptos-rs/leptos/issues/2506
-                for location in res_parts.headers.remove(header::LOCATION) {
-                    headers.insert(header::LOCATION, location);
-                }
-                for (k, v) in std::mem::take(&mut res_parts.headers) {
-                    headers.append(k, v);
-                }
+                run_server_fn(additional_context, || {
+                    provide_server_fn_contexts(&req);
+                    provide_response_options(res_parts.clone());
+                    async move {
+                        let mut res = service
+                            .0
+                            .run(ActixRequest::from((req, payload)))
+                            .await
+                            .take();
+
+                        // Override StatusCode if it was set in a Resource or Element
+                        if let Some(status) = res_parts.0.read().status {
+                            *res.status_mut() = status;
+                        }
 
-                // clean up the scope
-                runtime.dispose();
-                res
+                        // Use provided ResponseParts headers if they exist
+                        let headers = res.headers_mut();
+                        let mut res_parts = res_parts.0.write();
+
+                        // Location is set to redirect to Referer in the server handler handler by default,
+                        // but it can only have a single value
+                        //
+                        // if we have used redirect() we will end up appending this second Location value
+                        // to the first one, which will cause an invalid response
+                        // see https://github.com/leptos-rs/leptos/issues/2506
+                        for location in
+                            res_parts.headers.remove(header::LOCATION)
+                        {
+                            headers.insert(header::LOCATION, location);
+                        }
+                        for (k, v) in std::mem::take(&mut res_parts.headers) {
+                            headers.append(k, v);
+                        }
+                        drop(res_parts);
+                        res
+                    }
+                })
+                .await
             } else {
                 HttpResponse::BadRequest().body(format!(
                     "Could not find a server function at the route {:?}. \
@@ -282,6 +436,34 @@ pub fn handle_server_fns_with_context(
     })
 }
 
+// provides the request to a server function, along with the context that
+// middleware added to it
+fn provide_server_fn_contexts(req: &HttpRequest) {
+    provide_server_request(ActixRequestParts(req.clone()));
+    provide_request_contexts(req);
+    provide_context(req.clone());
+}
+
+// runs a WebSocket server function, once its connection is open, in the
+// context of the request that opened it, like other server functions
+#[cfg(feature = "websocket")]
+fn provide_upgrade_context(
+    req: &HttpRequest,
+    additional_context: impl Fn() + 'static + Clone + Send,
+) {
+    // the connection is handled on the thread of the request
+    let upgraded = SendWrapper::new(req.clone());
+    req.extensions_mut().insert(UpgradeContext::new(move |run| {
+        Box::pin(run_in_new_runtime(
+            move || {
+                additional_context();
+                provide_server_fn_contexts(&upgraded);
+            },
+            run,
+        ))
+    }));
+}
+
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application. The stream
 /// will include fallback content for any `<Suspense/>` nodes, and be immediately interactive,
@@ -555,8 +737,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let path = leptos_corrected_path(&req, &options);
                 move || {
-                    provide_contexts(&req, res_options);
+                    provide_contexts(&req, path, res_options);
                     (app_fn)().into_view()
                 }
             };
@@ -612,8 +795,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let path = leptos_corrected_path(&req, &options);
                 move || {
-                    provide_contexts(&req, res_options);
+                    provide_contexts(&req, path, res_options);
                     (app_fn)().into_view()
                 }
             };
@@ -664,8 +848,9 @@ where
             let app = {
                 let app_fn = app_fn.clone();
                 let res_options = res_options.clone();
+                let path = leptos_corrected_path(&req, &options);
                 move || {
-                    provide_contexts(&req, res_options);
+                    provide_contexts(&req, path, res_options);
                     (app_fn)().into_view()
                 }
             };
@@ -689,26 +874,103 @@ where
 }
 
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
-fn provide_contexts(req: &HttpRequest, res_options: ResponseOptions) {
-    let path = leptos_corrected_path(req);
+fn provide_contexts(
+    req: &HttpRequest,
+    path: String,
+    res_options: ResponseOptions,
+) {
+    provide_server_contexts(
+        path,
+        ActixAdapter {
+            req: req.clone(),
+            res_options,
+        },
+    );
+}
 
-    let integration = ServerIntegration { path };
-    provide_context(RouterIntegrationContext::new(integration));
-    provide_context(MetaContext::new());
-    provide_context(res_options);
-    provide_context(req.clone());
-    provide_server_redirect(redirect);
-    #[cfg(feature = "nonce")]
-    leptos::nonce::provide_nonce();
+// the parts of rendering a page that depend on Actix
+struct ActixAdapter {
+    req: HttpRequest,
+    res_options: ResponseOptions,
+}
+
+impl ServerAdapter for ActixAdapter {
+    fn provide_contexts(&self) {
+        provide_response_options(self.res_options.clone());
+        #[cfg(feature = "csrf")]
+        issue_csrf_token(&self.req, &self.res_options);
+        provide_server_request(ActixRequestParts(self.req.clone()));
+        provide_request_contexts(&self.req);
+        provide_context(self.req.clone());
+        if let Some(headers) =
+            self.req.extensions().get::<security::SecurityHeaders>()
+        {
+            provide_context(headers.clone());
+        }
+    }
+
+    fn set_status(&self, status: u16) {
+        if let Ok(status) = StatusCode::from_u16(status) {
+            self.res_options.set_status(status);
+        }
+    }
+
+    fn redirect(&self, path: &str) {
+        redirect(path);
+    }
+}
+
+// the request, from which `leptos::request::extract` extracts values
+struct ActixRequestParts(HttpRequest);
+
+impl ServerRequest for ActixRequestParts {
+    fn method(&self) -> &str {
+        self.0.method().as_str()
+    }
+
+    fn uri(&self) -> String {
+        let uri = self.0.uri();
+        uri.path_and_query()
+            .map(|uri| uri.to_string())
+            .unwrap_or_else(|| uri.path().to_string())
+    }
+
+    fn headers(&self) -> Vec<(&str, &str)> {
+        self.0
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str(), value.to_str().ok()?))
+            })
+            .collect()
+    }
+
+    fn client_addr(&self) -> Option<SocketAddr> {
+        self.0.peer_addr()
+    }
+}
+
+// sets the CSRF cookie on the page, if the request does not carry one yet
+#[cfg(feature = "csrf")]
+fn issue_csrf_token(req: &HttpRequest, res_options: &ResponseOptions) {
+    let cookies = req
+        .headers()
+        .get_all(header::COOKIE)
+        .filter_map(|value| value.to_str().ok());
+    if let Some(cookie) = server_fn::csrf::issue(cookies)
+        .and_then(|cookie| HeaderValue::from_str(&cookie).ok())
+    {
+        res_options.append_header(header::SET_COOKIE, cookie);
+    }
 }
 
-fn leptos_corrected_path(req: &HttpRequest) -> String {
-    let path = req.path();
+fn leptos_corrected_path(req: &HttpRequest, options: &LeptosOptions) -> String {
+    let path = with_site_base_path(options, req.path());
     let query = req.query_string();
     if query.is_empty() {
-        "http://leptos".to_string() + path
+        "http://leptos".to_string() + &path
     } else {
-        "http://leptos".to_string() + path + "?" + query
+        "http://leptos".to_string() + &path + "?" + query
     }
 }
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
@@ -719,6 +981,11 @@ async fn stream_app(
     additional_context: impl Fn() + 'static + Clone + Send,
     replace_blocks: bool,
 ) -> HttpResponse<BoxBody> {
+    let render_timeout = Duration::from_millis(options.render_timeout_ms);
+    let app = move || {
+        provide_render_deadline(render_timeout, tokio::time::sleep);
+        app()
+    };
     let (stream, runtime) =
         render_to_stream_with_prefix_undisposed_with_context_and_block_replacement(
             app,
@@ -757,44 +1024,17 @@ async fn build_stream_response(
     stream: impl Stream<Item = String> + 'static,
     runtime: RuntimeId,
 ) -> HttpResponse {
-    let mut stream = Box::pin(stream);
-
-    // wait for any blocking resources to load before pulling metadata
-    let first_app_chunk = stream.next().await.unwrap_or_default();
-
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>().as_ref());
-
-    let mut stream = Box::pin(
-        futures::stream::once(async move { head.clone() })
-            .chain(
-                futures::stream::once(async move { first_app_chunk })
-                    .chain(stream),
-            )
-            .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>),
-    );
-
-    // Get the first and second in the stream, which renders the app shell, and thus allows Resources to run
-
-    let first_chunk = stream.next().await;
-
-    let second_chunk = stream.next().await;
+    // renders the app shell, and thus allows Resources to run
+    let complete_stream =
+        html_stream(options, stream, runtime, tokio::time::sleep)
+            .await
+            .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>);
 
     let res_options = res_options.0.read();
 
     let (status, headers) = (res_options.status, res_options.headers.clone());
     let status = status.unwrap_or_default();
 
-    let complete_stream =
-        futures::stream::iter([first_chunk.unwrap(), second_chunk.unwrap()])
-            .chain(stream)
-            .chain(
-                futures::stream::once(async move {
-                    runtime.dispose();
-                    tail.to_string()
-                })
-                .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>),
-            );
     let mut res = HttpResponse::Ok()
         .content_type("text/html")
         .streaming(complete_stream);
@@ -914,73 +1154,20 @@ pub fn generate_route_list_with_exclusions_and_ssg_and_context<IV>(
 where
     IV: IntoView + 'static,
 {
-    let (mut routes, static_data_map) =
-        leptos_router::generate_route_list_inner_with_context(
-            app_fn,
-            additional_context,
-        );
-
     // Actix's Router doesn't follow Leptos's
     // Match `*` or `*someword` to replace with replace it with "/{tail.*}
     let wildcard_re = Regex::new(r"\*.*").unwrap();
     // Match `:some_word` but only capture `some_word` in the groups to replace with `{some_word}`
     let capture_re = Regex::new(r":((?:[^.,/]+)+)[^/]?").unwrap();
 
-    // Empty strings screw with Actix pathing, they need to be "/"
-    routes = routes
-        .into_iter()
-        .map(|listing| {
-            let path = listing.path();
-            if path.is_empty() {
-                return RouteListing::new(
-                    "/".to_string(),
-                    listing.path(),
-                    listing.mode(),
-                    listing.methods(),
-                    listing.static_mode(),
-                );
-            }
-            RouteListing::new(
-                listing.path(),
-                listing.path(),
-                listing.mode(),
-                listing.methods(),
-                listing.static_mode(),
-            )
-        })
-        .map(|listing| {
-            let path = wildcard_re
-                .replace_all(listing.path(), "{tail:.*}")
-                .to_string();
-            let path = capture_re.replace_all(&path, "{$1}").to_string();
-            RouteListing::new(
-                path,
-                listing.path(),
-                listing.mode(),
-                listing.methods(),
-                listing.static_mode(),
-            )
-        })
-        .collect::<Vec<_>>();
-
-    (
-        if routes.is_empty() {
-            vec![RouteListing::new(
-                "/",
-                "",
-                Default::default(),
-                [Method::Get],
-                None,
-            )]
-        } else {
-            // Routes to exclude from auto generation
-            if let Some(excluded_routes) = excluded_routes {
-                routes
-                    .retain(|p| !excluded_routes.iter().any(|e| e == p.path()))
-            }
-            routes
+    generate_route_list_with_format(
+        app_fn,
+        excluded_routes,
+        additional_context,
+        |path| {
+            let path = wildcard_re.replace_all(path, "{tail:.*}");
+            capture_re.replace_all(&path, "{$1}").to_string()
         },
-        static_data_map,
     )
 }
 
@@ -1095,7 +1282,7 @@ where
     IV: IntoView + 'static,
 {
     match mode {
-        StaticMode::Incremental => {
+        StaticMode::Incremental | StaticMode::Revalidate(_) => {
             let handler = move |req: HttpRequest| {
                 Box::pin({
                     let options = options.clone();
@@ -1223,7 +1410,9 @@ where
         let mut router = self;
 
         // register server functions first to allow for wildcard route in Leptos's Router
-        for (path, _) in server_fn::actix::server_fn_paths() {
+        for (path, _) in server_fn::actix::server_fn_paths()
+            .filter(|(path, _)| is_app_server_fn(&options, path))
+        {
             let additional_context = additional_context.clone();
             let handler = handle_server_fns_with_context(additional_context);
             router = router.route(path, handler);
@@ -1326,7 +1515,9 @@ impl LeptosRoutes for &mut ServiceConfig {
         let mut router = self;
 
         // register server functions first to allow for wildcard route in Leptos's Router
-        for (path, _) in server_fn::actix::server_fn_paths() {
+        for (path, _) in server_fn::actix::server_fn_paths()
+            .filter(|(path, _)| is_app_server_fn(&options, path))
+        {
             let additional_context = additional_context.clone();
             let handler = handle_server_fns_with_context(additional_context);
             router = router.route(path, handler);
@@ -0,0 +1,115 @@
+//! Adds headers that harden the responses of the app.
+//!
+//! [`SecurityHeadersMiddleware`] adds the [`SecurityHeaders`] to every
+//! response that does not already have them, including the responses of
+//! server functions and static files:
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .leptos_routes(leptos_options.to_owned(), routes.to_owned(), App)
+//!     .service(Files::new("/", site_root))
+//!     .wrap(SecurityHeadersMiddleware::new(
+//!         SecurityHeaders::new().frame_ancestors(Some("'self'")),
+//!     ))
+//! ```
+//!
+//! With the `nonce` feature, each page rendered by the app is given a
+//! `Content-Security-Policy` that allows its inline scripts and styles by
+//! their nonce, and blocks any others.
+
+use actix_http::header::{HeaderName, HeaderValue};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage,
+};
+use futures::future::{self, LocalBoxFuture, Ready};
+pub use leptos_integration_utils::SecurityHeaders;
+use std::sync::Arc;
+
+/// Middleware that adds the [`SecurityHeaders`] to every response. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersMiddleware {
+    config: SecurityHeaders,
+    headers: Arc<[(HeaderName, HeaderValue)]>,
+}
+
+impl Default for SecurityHeadersMiddleware {
+    fn default() -> Self {
+        Self::new(SecurityHeaders::default())
+    }
+}
+
+impl SecurityHeadersMiddleware {
+    /// Adds the given headers to every response.
+    pub fn new(config: SecurityHeaders) -> Self {
+        let headers = config
+            .headers()
+            .into_iter()
+            .filter_map(|(name, value)| match HeaderValue::from_str(&value) {
+                Ok(value) => Some((HeaderName::from_static(name), value)),
+                Err(e) => {
+                    tracing::warn!("invalid {name} header {value:?}: {e}");
+                    None
+                }
+            })
+            .collect();
+        Self { config, headers }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for SecurityHeadersMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>
+        + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = SecurityHeadersService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ok(SecurityHeadersService {
+            middleware: self.clone(),
+            service,
+        })
+    }
+}
+
+/// The service created by the [`SecurityHeadersMiddleware`].
+#[derive(Debug)]
+pub struct SecurityHeadersService<S> {
+    middleware: SecurityHeadersMiddleware,
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for SecurityHeadersService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>
+        + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        // lets a page that is rendered set the policy for its nonce
+        req.extensions_mut().insert(self.middleware.config.clone());
+        let headers = Arc::clone(&self.middleware.headers);
+        let res = self.service.call(req);
+        Box::pin(async move {
+            let mut res = res.await?;
+            for (name, value) in headers.iter() {
+                if !res.headers().contains_key(name) {
+                    res.headers_mut().insert(name.clone(), value.clone());
+                }
+            }
+            Ok(res)
+        })
+    }
+}
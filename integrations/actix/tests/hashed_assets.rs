@@ -0,0 +1,73 @@
+use actix_web::{test, App};
+use leptos::LeptosOptions;
+use leptos_actix::assets::{hashed_assets, AssetManifest};
+use std::{fs, path::PathBuf};
+
+fn site_root() -> PathBuf {
+    let root = std::env::temp_dir()
+        .join(format!("leptos_actix_hashed_assets_{}", std::process::id()));
+    fs::create_dir_all(root.join("pkg")).unwrap();
+    fs::write(root.join("pkg/app.css"), "body { color: red; }").unwrap();
+    fs::write(root.join("index.html"), "<h1>Hello</h1>").unwrap();
+    root
+}
+
+#[actix_web::test]
+async fn serves_hashed_files() {
+    let root = site_root();
+    let options = LeptosOptions::builder()
+        .output_name("app")
+        .site_root(root.to_str().unwrap())
+        .build();
+    let app = test::init_service(
+        App::new().default_service(hashed_assets(&options).unwrap()),
+    )
+    .await;
+    let manifest = AssetManifest::installed(&options).unwrap();
+    let hashed = manifest.hashed_url("/pkg/app.css").unwrap();
+    assert_eq!(
+        manifest.rewrite(r#"<link rel="stylesheet" href="/pkg/app.css">"#),
+        format!(r#"<link rel="stylesheet" href="{hashed}">"#)
+    );
+
+    let req = test::TestRequest::get().uri(&hashed).to_request();
+    let res = test::call_service(&app, req).await;
+    let header = |res: &actix_web::dev::ServiceResponse, name| {
+        res.headers()
+            .get(name)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    };
+    assert!(res.status().is_success());
+    assert_eq!(
+        header(&res, "cache-control"),
+        "public, max-age=31536000, immutable"
+    );
+    assert_eq!(header(&res, "content-type"), "text/css");
+    let etag = header(&res, "etag");
+    assert_eq!(test::read_body(res).await, "body { color: red; }");
+
+    // without the hash, or with another one, the file is revalidated
+    let req = test::TestRequest::get()
+        .uri("/pkg/app.css?v=0")
+        .insert_header(("if-none-match", etag.as_str()))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 304);
+    assert_eq!(header(&res, "cache-control"), "no-cache");
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(header(&res, "cache-control"), "no-cache");
+    assert_eq!(test::read_body(res).await, "<h1>Hello</h1>");
+
+    for uri in ["/missing.css", "/../index.html", "/pkg/%2e%2e/index.html"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), 404, "{uri}");
+    }
+
+    fs::remove_dir_all(root).unwrap();
+}
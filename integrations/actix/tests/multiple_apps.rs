@@ -0,0 +1,96 @@
+use actix_web::{test, App};
+use leptos::{
+    server_fn::{
+        request::actix::ActixRequest, response::actix::ActixResponse, ServerFn,
+    },
+    *,
+};
+use leptos_actix::{generate_route_list, LeptosRoutes};
+use leptos_router::{Route, Router, Routes};
+
+// other integrations may be enabled alongside Actix (as in a workspace build),
+// so the server functions name their request and response types
+#[server(prefix = "/shop/api", req = ActixRequest, res = ActixResponse)]
+pub async fn shop_name() -> Result<String, ServerFnError> {
+    Ok("shop".into())
+}
+
+#[server(prefix = "/blog/api", req = ActixRequest, res = ActixResponse)]
+pub async fn blog_name() -> Result<String, ServerFnError> {
+    Ok("blog".into())
+}
+
+#[component]
+fn ShopApp() -> impl IntoView {
+    view! {
+        <Router base="/shop" id=1>
+            <Routes>
+                <Route path="" view=|| view! { <h1>"Shop"</h1> }/>
+            </Routes>
+        </Router>
+    }
+}
+
+#[component]
+fn BlogApp() -> impl IntoView {
+    view! {
+        <Router base="/blog" id=2>
+            <Routes>
+                <Route path="" view=|| view! { <h1>"Blog"</h1> }/>
+            </Routes>
+        </Router>
+    }
+}
+
+fn options(name: &str) -> LeptosOptions {
+    LeptosOptions::builder()
+        .output_name(name)
+        .site_base_path(format!("/{name}"))
+        .server_fn_prefix(format!("/{name}/api"))
+        .build()
+}
+
+#[actix_web::test]
+async fn each_app_handles_its_own_server_fns() {
+    let app = test::init_service(
+        App::new()
+            .leptos_routes(
+                options("shop"),
+                generate_route_list(ShopApp),
+                ShopApp,
+            )
+            .leptos_routes(
+                options("blog"),
+                generate_route_list(BlogApp),
+                BlogApp,
+            ),
+    )
+    .await;
+
+    for (path, name) in [(ShopName::PATH, "shop"), (BlogName::PATH, "blog")] {
+        let req = test::TestRequest::post().uri(path).to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(body, format!("{name:?}"));
+    }
+
+    for (path, name) in [("/shop", "shop"), ("/blog", "blog")] {
+        let req = test::TestRequest::get().uri(path).to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains(&format!("/{name}/pkg/{name}.js")), "{body}");
+    }
+}
+
+#[actix_web::test]
+async fn an_app_does_not_register_the_server_fns_of_another() {
+    let app = test::init_service(App::new().leptos_routes(
+        options("shop"),
+        generate_route_list(ShopApp),
+        ShopApp,
+    ))
+    .await;
+
+    let req = test::TestRequest::post().uri(BlogName::PATH).to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 404);
+}
@@ -0,0 +1,58 @@
+use actix_web::{test, web, App, HttpResponse};
+use leptos_actix::security::{SecurityHeaders, SecurityHeadersMiddleware};
+
+#[actix_web::test]
+async fn adds_default_headers() {
+    let app = test::init_service(
+        App::new()
+            .wrap(SecurityHeadersMiddleware::default())
+            .route("/", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+    let res =
+        test::call_service(&app, test::TestRequest::get().to_request()).await;
+    let header = |name| res.headers().get(name).unwrap().to_str().unwrap();
+
+    assert_eq!(
+        header("strict-transport-security"),
+        "max-age=63072000; includeSubDomains"
+    );
+    assert_eq!(header("x-content-type-options"), "nosniff");
+    assert_eq!(header("referrer-policy"), "strict-origin-when-cross-origin");
+    assert_eq!(
+        header("content-security-policy"),
+        "base-uri 'self'; object-src 'none'; frame-ancestors 'none'"
+    );
+}
+
+#[actix_web::test]
+async fn keeps_headers_set_by_the_handler() {
+    let app = test::init_service(
+        App::new()
+            .wrap(SecurityHeadersMiddleware::new(
+                SecurityHeaders::new()
+                    .hsts(None)
+                    .frame_ancestors(Some("'self'"))
+                    .csp_directive("object-src", "'self'"),
+            ))
+            .route(
+                "/",
+                web::get().to(|| async {
+                    HttpResponse::Ok()
+                        .insert_header(("referrer-policy", "no-referrer"))
+                        .finish()
+                }),
+            ),
+    )
+    .await;
+    let res =
+        test::call_service(&app, test::TestRequest::get().to_request()).await;
+    let header = |name| res.headers().get(name).map(|v| v.to_str().unwrap());
+
+    assert_eq!(header("strict-transport-security"), None);
+    assert_eq!(header("referrer-policy"), Some("no-referrer"));
+    assert_eq!(
+        header("content-security-policy"),
+        Some("base-uri 'self'; frame-ancestors 'self'; object-src 'self'")
+    );
+}
@@ -0,0 +1,66 @@
+use actix_web::{test, App, HttpRequest};
+use futures::{stream, StreamExt};
+use leptos::{
+    server_fn::{
+        codec::{Streaming, StreamingText, TextStream},
+        request::actix::ActixRequest,
+        response::actix::ActixResponse,
+        ServerFn,
+    },
+    *,
+};
+use leptos_actix::handle_server_fns;
+
+// other integrations may be enabled alongside Actix (as in a workspace build),
+// so the server functions name their request and response types
+#[server(output = StreamingText, req = ActixRequest, res = ActixResponse)]
+pub async fn greetings() -> Result<TextStream, ServerFnError> {
+    let req = expect_context::<HttpRequest>();
+    let name = req.query_string().to_string();
+    Ok(TextStream::new(
+        stream::iter(["hello ", "goodbye "])
+            .map(move |greeting| Ok(format!("{greeting}{name}\n"))),
+    ))
+}
+
+#[server(input = Streaming, req = ActixRequest, res = ActixResponse)]
+pub async fn count_bytes(
+    data: server_fn::codec::ByteStream,
+) -> Result<usize, ServerFnError> {
+    let mut data = data.into_inner();
+    let mut len = 0;
+    while let Some(chunk) = data.next().await {
+        len += chunk?.len();
+    }
+    Ok(len)
+}
+
+#[actix_web::test]
+async fn streams_the_output_of_a_server_fn() {
+    let app = test::init_service(
+        App::new().route("/api/{tail:.*}", handle_server_fns()),
+    )
+    .await;
+    let req = test::TestRequest::post()
+        .uri(&format!("{}?world", Greetings::PATH))
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "hello world\ngoodbye world\n");
+}
+
+#[actix_web::test]
+async fn streams_the_input_of_a_server_fn() {
+    let app = test::init_service(
+        App::new().route("/api/{tail:.*}", handle_server_fns()),
+    )
+    .await;
+    let req = test::TestRequest::post()
+        .uri(CountBytes::PATH)
+        .insert_header(("content-type", "application/octet-stream"))
+        .set_payload("0123456789")
+        .to_request();
+    let body = test::call_and_read_body(&app, req).await;
+
+    assert_eq!(body, "10");
+}
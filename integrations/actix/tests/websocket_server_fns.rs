@@ -0,0 +1,60 @@
+use actix_web::{App, HttpRequest, HttpServer};
+use futures::{SinkExt, StreamExt};
+use leptos::{
+    server_fn::{
+        request::actix::ActixRequest, response::actix::ActixResponse,
+        websocket::WebsocketChannel, ServerFn,
+    },
+    *,
+};
+use leptos_actix::handle_server_fns_with_context;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+#[derive(Clone)]
+struct Prefix(&'static str);
+
+// other integrations may be enabled alongside Actix (as in a workspace build),
+// so the server functions name their request and response types
+#[server(protocol = "websocket", req = ActixRequest, res = ActixResponse)]
+pub async fn shout(
+    channel: WebsocketChannel<String, String>,
+) -> Result<(), ServerFnError> {
+    // the request and additional context are available once it is upgraded
+    let Prefix(prefix) = expect_context::<Prefix>();
+    let name = expect_context::<HttpRequest>().query_string().to_string();
+    let (mut tx, mut rx) = channel.split();
+    while let Some(message) = rx.next().await.transpose()? {
+        if message == "fail" {
+            return Err(ServerFnError::new("failed"));
+        }
+        tx.send(format!("{prefix}{}, {name}", message.to_uppercase()))
+            .await?;
+    }
+    Ok(())
+}
+
+#[actix_web::test]
+async fn runs_a_websocket_server_fn_in_the_context_of_its_request() {
+    let server = HttpServer::new(|| {
+        App::new().route(
+            "/api/{tail:.*}",
+            handle_server_fns_with_context(|| provide_context(Prefix("> "))),
+        )
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let addr = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+
+    let url = format!("ws://{addr}{}?world", Shout::PATH);
+    let (mut socket, _) = connect_async(url).await.unwrap();
+    socket.send(Message::text("\"hello\"")).await.unwrap();
+    let reply = socket.next().await.unwrap().unwrap();
+    assert_eq!(reply.into_text().unwrap(), "\"> HELLO, world\"");
+
+    // errors are sent as the last frame
+    socket.send(Message::text("\"fail\"")).await.unwrap();
+    let reply = socket.next().await.unwrap().unwrap();
+    assert!(reply.into_text().unwrap().starts_with('!'));
+}
@@ -0,0 +1,182 @@
+//! Serves the files of the site, with URLs that change whenever they do.
+//!
+//! [`HashedAssets`] is a [`Service`] that serves the files in the
+//! [`site_root`](LeptosOptions::site_root). It hashes the contents of each
+//! file when it is created, and the app's pages then load the files in their
+//! `<head>`, like its JS, WASM and CSS, with the hash in their URLs (see
+//! [`AssetManifest`]). Those are cached by the browser for a year, as they
+//! never change, while HTML files and files requested without the hash are
+//! revalidated with their `ETag` every time:
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .leptos_routes(&leptos_options, routes, App)
+//!     .fallback_service(HashedAssets::new(&leptos_options)?)
+//!     .with_state(leptos_options);
+//! ```
+//!
+//! Requests for files that do not exist are answered with `404 Not Found`,
+//! or passed to another service with [`HashedAssets::not_found_service`], like
+//! a handler that renders the app's not-found page.
+
+use axum::{
+    body::Body,
+    http::{
+        header::{
+            CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH,
+        },
+        HeaderValue, Method, Request, Response, StatusCode,
+    },
+};
+use leptos::LeptosOptions;
+pub use leptos_integration_utils::{Asset, AssetManifest};
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+type NotFound = Arc<
+    dyn Fn(
+            Request<Body>,
+        ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// A [`Service`] that serves the files of the site, with URLs that change
+/// whenever they do. See the [module docs](self).
+#[derive(Clone)]
+pub struct HashedAssets {
+    manifest: Arc<AssetManifest>,
+    not_found: Option<NotFound>,
+}
+
+impl std::fmt::Debug for HashedAssets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HashedAssets")
+            .field("manifest", &self.manifest)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HashedAssets {
+    /// Hashes the files in the `site_root`, and
+    /// [installs](AssetManifest::install) the manifest, so that the pages of
+    /// the app load them with the hashes in their URLs.
+    pub fn new(options: &LeptosOptions) -> io::Result<Self> {
+        Ok(Self {
+            manifest: AssetManifest::build(options)?.install(),
+            not_found: None,
+        })
+    }
+
+    /// Passes requests for files that do not exist to `service`.
+    pub fn not_found_service<S>(mut self, service: S) -> Self
+    where
+        S: Service<
+                Request<Body>,
+                Response = Response<Body>,
+                Error = Infallible,
+            > + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Future: Send,
+    {
+        self.not_found = Some(Arc::new(move |req| {
+            let mut service = service.clone();
+            Box::pin(async move {
+                match service.call(req).await {
+                    Ok(res) => res,
+                    Err(e) => match e {},
+                }
+            })
+        }));
+        self
+    }
+}
+
+impl Service<Request<Body>> for HashedAssets {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<
+        Box<dyn Future<Output = Result<Response<Body>, Infallible>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let asset = [Method::GET, Method::HEAD]
+            .contains(req.method())
+            .then(|| self.manifest.resolve(req.uri().path(), req.uri().query()))
+            .flatten();
+        let not_found = self.not_found.clone();
+        Box::pin(async move {
+            let Some(asset) = asset else {
+                return Ok(match not_found {
+                    Some(not_found) => not_found(req).await,
+                    None => status(StatusCode::NOT_FOUND),
+                });
+            };
+            Ok(serve(req, asset).await)
+        })
+    }
+}
+
+async fn serve(req: Request<Body>, asset: Asset) -> Response<Body> {
+    let mut res = Response::builder()
+        .header(CACHE_CONTROL, asset.cache_control)
+        .header(
+            CONTENT_TYPE,
+            HeaderValue::from_str(&asset.content_type).unwrap_or(
+                HeaderValue::from_static("application/octet-stream"),
+            ),
+        );
+    if let Some(etag) = &asset.etag {
+        res = res.header(ETAG, etag);
+        let matches = req
+            .headers()
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|tag| tag.trim() == etag || tag.trim() == "*");
+        if matches {
+            return res
+                .status(StatusCode::NOT_MODIFIED)
+                .body(Body::empty())
+                .unwrap_or_else(|_| status(StatusCode::NOT_MODIFIED));
+        }
+    }
+    let body = match tokio::fs::read(&asset.file).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("failed to read {}: {e}", asset.file.display());
+            return status(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    res = res.header(CONTENT_LENGTH, body.len());
+    let body = if req.method() == Method::HEAD {
+        Body::empty()
+    } else {
+        Body::from(body)
+    };
+    res.body(body)
+        .unwrap_or_else(|_| status(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut res = Response::new(Body::empty());
+    *res.status_mut() = status;
+    res
+}
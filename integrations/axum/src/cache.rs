@@ -0,0 +1,601 @@
+//! Caches whole server-rendered responses, so that pages that are the same
+//! for every visitor are not rendered again for every request.
+//!
+//! [`ResponseCache`] is a [`Layer`] that stores each cacheable response in a
+//! [`CacheStore`], keyed by its URL and the values of the request headers it
+//! [varies](ResponseCache::vary) by, and serves it from the store until it
+//! expires:
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .leptos_routes(&leptos_options, routes, App)
+//!     .layer(
+//!         ResponseCache::new(Duration::from_secs(60)).vary(ACCEPT_LANGUAGE),
+//!     )
+//!     .with_state(leptos_options);
+//! ```
+//!
+//! Every cached response is given an `ETag`, which is a hash of its body, and
+//! a `Last-Modified` date, which is when it was rendered. Requests with a
+//! matching `If-None-Match` (or `If-Modified-Since`) header are answered with
+//! `304 Not Modified` and no body.
+//!
+//! By default, only `GET` requests without a `Cookie` or `Authorization`
+//! header are cached, as other requests may be rendered for a particular
+//! user; this can be changed with [`ResponseCache::cacheable`]. Responses are
+//! only stored if they are `200 OK`, do not set a cookie, and do not have a
+//! `Cache-Control` header with `no-store` or `private`. As a cacheable
+//! response is rendered in full before it is sent, it is not streamed.
+//!
+//! With the `csrf` feature, the CSRF cookie is not taken into account: a
+//! request whose only cookie is the CSRF cookie is cached, and the cookie is
+//! removed from the responses that are stored, so that visitors don't share a
+//! token. The browser client sets the cookie itself when there is none.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{
+        header::{
+            HeaderName, HeaderValue, AUTHORIZATION, CACHE_CONTROL, COOKIE,
+            ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, SET_COOKIE,
+            VARY,
+        },
+        request::Parts,
+        HeaderMap, Method, Request, Response, StatusCode,
+    },
+};
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    future::{self, Future},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A response stored in a [`CacheStore`].
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response, including its `ETag` and `Last-Modified`
+    /// headers.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+impl CachedResponse {
+    fn new(status: StatusCode, mut headers: HeaderMap, body: Bytes) -> Self {
+        let etag = format!("\"{:016x}\"", xxhash_rust::xxh64::xxh64(&body, 0));
+        let last_modified = httpdate::fmt_http_date(SystemTime::now());
+        // both are always valid header values
+        if let Ok(etag) = HeaderValue::from_str(&etag) {
+            headers.insert(ETAG, etag);
+        }
+        if let Ok(last_modified) = HeaderValue::from_str(&last_modified) {
+            headers.insert(LAST_MODIFIED, last_modified);
+        }
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    // whether the client already has this response, from the conditional
+    // headers of its request
+    fn is_fresh(&self, req: &HeaderMap) -> bool {
+        if let Some(if_none_match) = req.get(IF_NONE_MATCH) {
+            let (Some(etag), Ok(if_none_match)) =
+                (self.headers.get(ETAG), if_none_match.to_str())
+            else {
+                return false;
+            };
+            let etag = etag.as_bytes();
+            return if_none_match.split(',').any(|tag| {
+                let tag = tag.trim();
+                tag == "*" || tag.trim_start_matches("W/").as_bytes() == etag
+            });
+        }
+        let parse = |value: Option<&HeaderValue>| {
+            httpdate::parse_http_date(value?.to_str().ok()?).ok()
+        };
+        match (
+            parse(self.headers.get(LAST_MODIFIED)),
+            parse(req.get(IF_MODIFIED_SINCE)),
+        ) {
+            (Some(last_modified), Some(since)) => last_modified <= since,
+            _ => false,
+        }
+    }
+
+    fn respond(self, req: &HeaderMap) -> Response<Body> {
+        if self.is_fresh(req) {
+            let mut res = Response::new(Body::empty());
+            *res.status_mut() = StatusCode::NOT_MODIFIED;
+            for name in [ETAG, LAST_MODIFIED, VARY, CACHE_CONTROL] {
+                if let Some(value) = self.headers.get(&name) {
+                    res.headers_mut().insert(name, value.clone());
+                }
+            }
+            return res;
+        }
+        let mut res = Response::new(Body::from(self.body));
+        *res.status_mut() = self.status;
+        *res.headers_mut() = self.headers;
+        res
+    }
+}
+
+/// Stores the responses cached by a [`ResponseCache`].
+pub trait CacheStore: Send + Sync + 'static {
+    /// Returns the response stored for the given key, if it has not expired.
+    fn get(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = Option<CachedResponse>> + Send>>;
+
+    /// Stores a response for the given key, until it expires after `ttl`.
+    fn set(
+        &self,
+        key: String,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+/// A [`CacheStore`] that keeps responses in memory, so each server process
+/// has its own cache.
+#[derive(Debug, Default)]
+pub struct MemoryCacheStore {
+    responses: RwLock<HashMap<String, (Instant, CachedResponse)>>,
+}
+
+// the number of responses above which expired ones are removed
+const MAX_RESPONSES: usize = 10_000;
+
+impl CacheStore for MemoryCacheStore {
+    fn get(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = Option<CachedResponse>> + Send>> {
+        let response = self
+            .responses
+            .read()
+            .get(key)
+            .filter(|(expires, _)| *expires > Instant::now())
+            .map(|(_, response)| response.clone());
+        Box::pin(future::ready(response))
+    }
+
+    fn set(
+        &self,
+        key: String,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        let now = Instant::now();
+        let mut responses = self.responses.write();
+        if responses.len() > MAX_RESPONSES {
+            responses.retain(|_, (expires, _)| *expires > now);
+        }
+        responses.insert(key, (now + ttl, response));
+        Box::pin(future::ready(()))
+    }
+}
+
+type Cacheable = Arc<dyn Fn(&Parts) -> bool + Send + Sync>;
+
+/// A [`Layer`] that caches server-rendered responses. See the
+/// [module docs](self).
+#[derive(Clone)]
+pub struct ResponseCache {
+    ttl: Duration,
+    vary: Vec<HeaderName>,
+    store: Arc<dyn CacheStore>,
+    cacheable: Cacheable,
+}
+
+impl ResponseCache {
+    /// Caches responses in memory for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            vary: Vec::new(),
+            store: Arc::new(MemoryCacheStore::default()),
+            cacheable: Arc::new(|req| {
+                req.method == Method::GET
+                    && req
+                        .headers
+                        .get_all(COOKIE)
+                        .iter()
+                        .filter_map(|value| value.to_str().ok())
+                        .flat_map(|value| value.split(';'))
+                        .all(is_csrf_cookie)
+                    && !req.headers.contains_key(AUTHORIZATION)
+            }),
+        }
+    }
+
+    /// Stores responses in the given [`CacheStore`], in place of memory.
+    pub fn store(mut self, store: impl CacheStore) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Caches a separate response for each value of the given request
+    /// header, like `Accept-Language`, which is listed in the `Vary` header
+    /// of the responses.
+    pub fn vary(mut self, header: HeaderName) -> Self {
+        self.vary.push(header);
+        self
+    }
+
+    /// Sets the function that decides whether the response to a request can
+    /// be cached, in place of the default described in the
+    /// [module docs](self).
+    pub fn cacheable(
+        mut self,
+        cacheable: impl Fn(&Parts) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.cacheable = Arc::new(cacheable);
+        self
+    }
+
+    fn key(&self, req: &Parts) -> String {
+        let mut key = req.uri.to_string();
+        for name in &self.vary {
+            let value = req.headers.get(name).map(HeaderValue::as_bytes);
+            _ = write!(
+                key,
+                "|{}",
+                String::from_utf8_lossy(value.unwrap_or(&[]))
+            );
+        }
+        key
+    }
+
+    fn vary_header(&self) -> Option<HeaderValue> {
+        if self.vary.is_empty() {
+            return None;
+        }
+        let names = self
+            .vary
+            .iter()
+            .map(HeaderName::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        HeaderValue::from_str(&names).ok()
+    }
+}
+
+// whether a cookie, as in `name=value`, is the CSRF cookie, which is the
+// same for every page a visitor requests
+#[cfg(feature = "csrf")]
+fn is_csrf_cookie(cookie: &str) -> bool {
+    cookie.split_once('=').map(|(name, _)| name.trim())
+        == Some(server_fn::csrf::COOKIE_NAME)
+}
+
+#[cfg(not(feature = "csrf"))]
+fn is_csrf_cookie(_cookie: &str) -> bool {
+    false
+}
+
+fn sets_csrf_cookie(value: &HeaderValue) -> bool {
+    value.to_str().map(is_csrf_cookie).unwrap_or(false)
+}
+
+fn remove_csrf_cookies(headers: &mut HeaderMap) {
+    let cookies = headers
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter(|value| !sets_csrf_cookie(value))
+        .cloned()
+        .collect::<Vec<_>>();
+    headers.remove(SET_COOKIE);
+    for cookie in cookies {
+        headers.append(SET_COOKIE, cookie);
+    }
+}
+
+// whether a response may be shared with other visitors, once the CSRF
+// cookie is removed from it
+fn is_storable(res: &axum::http::response::Parts) -> bool {
+    let cache_control = res
+        .headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim);
+    res.status == StatusCode::OK
+        && res.headers.get_all(SET_COOKIE).iter().all(sets_csrf_cookie)
+        && !cache_control
+            .into_iter()
+            .any(|directive| directive == "no-store" || directive == "private")
+}
+
+impl<S> Layer<S> for ResponseCache {
+    type Service = ResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseCacheService {
+            cache: self.clone(),
+            inner,
+        }
+    }
+}
+
+/// The [`Service`] created by the [`ResponseCache`] layer.
+#[derive(Clone)]
+pub struct ResponseCacheService<S> {
+    cache: ResponseCache,
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for ResponseCacheService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Response<Body>, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let (parts, body) = req.into_parts();
+        if !(self.cache.cacheable)(&parts) {
+            return Box::pin(self.inner.call(Request::from_parts(parts, body)));
+        }
+        let key = self.cache.key(&parts);
+        let headers = parts.headers.clone();
+        let cache = self.cache.clone();
+        // the service that was polled ready is the one that is called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            if let Some(cached) = cache.store.get(&key).await {
+                return Ok(cached.respond(&headers));
+            }
+            let res = inner.call(Request::from_parts(parts, body)).await?;
+            let (mut res, body) = res.into_parts();
+            if !is_storable(&res) {
+                return Ok(Response::from_parts(res, body));
+            }
+            let body = match axum::body::to_bytes(body, usize::MAX).await {
+                Ok(body) => body,
+                Err(e) => {
+                    tracing::error!("error rendering cached response: {e}");
+                    let mut res = Response::new(Body::empty());
+                    *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                    return Ok(res);
+                }
+            };
+            remove_csrf_cookies(&mut res.headers);
+            if let Some(vary) = cache.vary_header() {
+                res.headers.append(VARY, vary);
+            }
+            let cached = CachedResponse::new(res.status, res.headers, body);
+            cache.store.set(key, cached.clone(), cache.ttl).await;
+            Ok(cached.respond(&headers))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::header::ACCEPT_LANGUAGE;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    // renders the same page every time, counting how often it is rendered
+    #[derive(Clone, Default)]
+    struct Page {
+        renders: Arc<AtomicUsize>,
+        set_cookie: Option<&'static str>,
+    }
+
+    impl Service<Request<Body>> for Page {
+        type Response = Response<Body>;
+        type Error = Infallible;
+        type Future = future::Ready<Result<Response<Body>, Infallible>>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: Request<Body>) -> Self::Future {
+            self.renders.fetch_add(1, Ordering::SeqCst);
+            let mut res = Response::new(Body::from("<p>Hello</p>"));
+            if let Some(cookie) = self.set_cookie {
+                res.headers_mut()
+                    .insert(SET_COOKIE, HeaderValue::from_static(cookie));
+            }
+            future::ready(Ok(res))
+        }
+    }
+
+    fn get(headers: &[(HeaderName, &str)]) -> Request<Body> {
+        let mut req = Request::get("/page").body(Body::empty()).unwrap();
+        for (name, value) in headers {
+            req.headers_mut()
+                .append(name, HeaderValue::from_str(value).unwrap());
+        }
+        req
+    }
+
+    async fn send(
+        service: &mut ResponseCacheService<Page>,
+        req: Request<Body>,
+    ) -> (Response<()>, Bytes) {
+        let res = service.call(req).await.unwrap();
+        let (parts, body) = res.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (Response::from_parts(parts, ()), body)
+    }
+
+    fn renders(service: &ResponseCacheService<Page>) -> usize {
+        service.inner.renders.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn etag_answers_not_modified() {
+        let mut service =
+            ResponseCache::new(Duration::from_secs(60)).layer(Page::default());
+
+        let (res, body) = send(&mut service, get(&[])).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body, "<p>Hello</p>");
+        let etag = res.headers()[ETAG].to_str().unwrap().to_string();
+
+        let (res, body) = send(&mut service, get(&[])).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body, "<p>Hello</p>");
+        assert_eq!(renders(&service), 1);
+
+        let weak = format!("W/{etag}");
+        for tag in [etag.as_str(), "\"other\", *", weak.as_str()] {
+            let (res, body) =
+                send(&mut service, get(&[(IF_NONE_MATCH, tag)])).await;
+            assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+            assert!(body.is_empty());
+            assert_eq!(res.headers()[ETAG], etag.as_str());
+        }
+
+        let (res, _) =
+            send(&mut service, get(&[(IF_NONE_MATCH, "\"other\"")])).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(renders(&service), 1);
+    }
+
+    #[tokio::test]
+    async fn if_modified_since_answers_not_modified() {
+        let mut service =
+            ResponseCache::new(Duration::from_secs(60)).layer(Page::default());
+
+        let (res, _) = send(&mut service, get(&[])).await;
+        let last_modified =
+            res.headers()[LAST_MODIFIED].to_str().unwrap().to_string();
+        let (res, _) = send(
+            &mut service,
+            get(&[(IF_MODIFIED_SINCE, last_modified.as_str())]),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+
+        let earlier = httpdate::fmt_http_date(
+            httpdate::parse_http_date(&last_modified).unwrap()
+                - Duration::from_secs(60),
+        );
+        let (res, body) =
+            send(&mut service, get(&[(IF_MODIFIED_SINCE, earlier.as_str())]))
+                .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body, "<p>Hello</p>");
+        assert_eq!(renders(&service), 1);
+    }
+
+    #[tokio::test]
+    async fn vary_keys_separate_responses() {
+        let mut service = ResponseCache::new(Duration::from_secs(60))
+            .vary(ACCEPT_LANGUAGE)
+            .layer(Page::default());
+
+        let (res, _) =
+            send(&mut service, get(&[(ACCEPT_LANGUAGE, "en")])).await;
+        assert_eq!(res.headers()[VARY], "accept-language");
+        send(&mut service, get(&[(ACCEPT_LANGUAGE, "fr")])).await;
+        assert_eq!(renders(&service), 2);
+
+        send(&mut service, get(&[(ACCEPT_LANGUAGE, "en")])).await;
+        send(&mut service, get(&[(ACCEPT_LANGUAGE, "fr")])).await;
+        assert_eq!(renders(&service), 2);
+
+        send(&mut service, get(&[])).await;
+        assert_eq!(renders(&service), 3);
+    }
+
+    #[tokio::test]
+    async fn expired_responses_are_rendered_again() {
+        let mut service =
+            ResponseCache::new(Duration::ZERO).layer(Page::default());
+
+        send(&mut service, get(&[])).await;
+        send(&mut service, get(&[])).await;
+        assert_eq!(renders(&service), 2);
+    }
+
+    #[tokio::test]
+    async fn private_requests_and_responses_are_not_cached() {
+        let mut service =
+            ResponseCache::new(Duration::from_secs(60)).layer(Page::default());
+        for _ in 0..2 {
+            send(&mut service, get(&[(COOKIE, "session=abc")])).await;
+            send(&mut service, get(&[(AUTHORIZATION, "Bearer abc")])).await;
+        }
+        assert_eq!(renders(&service), 4);
+
+        let mut service =
+            ResponseCache::new(Duration::from_secs(60)).layer(Page {
+                set_cookie: Some("session=abc; Path=/"),
+                ..Default::default()
+            });
+        for _ in 0..2 {
+            let (res, _) = send(&mut service, get(&[])).await;
+            assert_eq!(res.headers()[SET_COOKIE], "session=abc; Path=/");
+        }
+        assert_eq!(renders(&service), 2);
+    }
+
+    #[cfg(feature = "csrf")]
+    #[tokio::test]
+    async fn csrf_cookie_does_not_prevent_caching() {
+        let mut service =
+            ResponseCache::new(Duration::from_secs(60)).layer(Page {
+                set_cookie: Some("leptos_csrf=token; Path=/; SameSite=Strict"),
+                ..Default::default()
+            });
+
+        // the token is not stored and shared with other visitors
+        let (res, _) = send(&mut service, get(&[])).await;
+        assert!(!res.headers().contains_key(SET_COOKIE));
+
+        // nor does a request that only carries the token need its own page
+        let (res, body) =
+            send(&mut service, get(&[(COOKIE, "leptos_csrf=token")])).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body, "<p>Hello</p>");
+        assert_eq!(renders(&service), 1);
+
+        send(
+            &mut service,
+            get(&[(COOKIE, "leptos_csrf=token; session=abc")]),
+        )
+        .await;
+        assert_eq!(renders(&service), 2);
+    }
+}
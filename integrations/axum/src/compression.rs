@@ -0,0 +1,322 @@
+//! Compresses responses without holding back their streamed chunks.
+//!
+//! Compression layers like the one in `tower-http` compress the body of a
+//! response in blocks, and only send a block once it is full, so the shell of
+//! a page and each `<Suspense/>` fragment streamed after it sit in the
+//! compressor until enough of the page has been rendered. [`Compression`] is a
+//! [`Layer`] that instead flushes the compressor after every chunk of the
+//! body, so each chunk is sent as soon as it has been rendered, at the cost of
+//! a slightly larger response:
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .leptos_routes(&leptos_options, routes, App)
+//!     .layer(Compression::new())
+//!     .with_state(leptos_options);
+//! ```
+//!
+//! Responses are compressed with Brotli or gzip, whichever the client prefers
+//! in its `Accept-Encoding` header, with Brotli chosen if it accepts both
+//! equally. Only text, like HTML, JSON, JavaScript and SVG, and WebAssembly
+//! are compressed, and responses that are already compressed are left as
+//! they are. So are small responses, which compression would make larger:
+//! responses whose length is known to be less than the
+//! [minimum size](Compression::min_size), and streamed responses whose whole
+//! body fits in a first chunk of less than it.
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    http::{
+        header::{
+            HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH,
+            CONTENT_TYPE, VARY,
+        },
+        HeaderMap, Request, Response,
+    },
+};
+use flate2::write::GzEncoder;
+use futures::{future, stream, StreamExt, TryStreamExt};
+use std::{
+    future::Future,
+    io::{self, Write},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+// the default minimum size, below which responses are not compressed
+const MIN_SIZE: usize = 32;
+
+// the quality of Brotli compression, from 0 to 11: higher qualities are
+// too slow to compress each chunk as soon as it is rendered
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+
+/// An encoding in which responses can be compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Brotli, sent as `br`.
+    Brotli,
+    /// gzip.
+    Gzip,
+}
+
+impl Encoding {
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    // the encoding the client prefers, of those in `accepted`
+    fn negotiate(req: &HeaderMap, accepted: &[Encoding]) -> Option<Self> {
+        req.get_all(ACCEPT_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|encoding| {
+                let mut parts = encoding.split(';').map(str::trim);
+                let name = parts.next()?;
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                let index = accepted
+                    .iter()
+                    .position(|e| e.name().eq_ignore_ascii_case(name))?;
+                (quality > 0.0).then_some((index, quality))
+            })
+            // of those accepted equally, the first in `accepted` is preferred
+            .fold(None, |best: Option<(usize, f32)>, (index, q)| match best {
+                Some((best_index, best_q))
+                    if best_q > q || (best_q == q && best_index < index) =>
+                {
+                    best
+                }
+                _ => Some((index, q)),
+            })
+            .map(|(index, _)| accepted[index])
+    }
+}
+
+enum Encoder {
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+    Gzip(GzEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Brotli => {
+                Encoder::Brotli(Box::new(brotli::CompressorWriter::new(
+                    Vec::new(),
+                    4096,
+                    BROTLI_QUALITY,
+                    BROTLI_WINDOW,
+                )))
+            }
+            Encoding::Gzip => Encoder::Gzip(GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::default(),
+            )),
+        }
+    }
+
+    // compresses a chunk, and flushes it so that it can be decompressed
+    // without waiting for the rest of the body
+    fn compress(&mut self, chunk: &[u8]) -> io::Result<Bytes> {
+        if chunk.is_empty() {
+            return Ok(Bytes::new());
+        }
+        let output = match self {
+            Encoder::Brotli(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+            Encoder::Gzip(encoder) => {
+                encoder.write_all(chunk)?;
+                encoder.flush()?;
+                encoder.get_mut()
+            }
+        };
+        Ok(std::mem::take(output).into())
+    }
+
+    fn finish(self) -> io::Result<Bytes> {
+        let output = match self {
+            Encoder::Brotli(encoder) => encoder.into_inner(),
+            Encoder::Gzip(encoder) => encoder.finish()?,
+        };
+        Ok(output.into())
+    }
+}
+
+// whether a response of this type is worth compressing
+fn is_compressible(res: &HeaderMap) -> bool {
+    let Some(content_type) =
+        res.get(CONTENT_TYPE).and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let content_type = content_type.to_ascii_lowercase();
+    content_type.starts_with("text/")
+        || ["json", "javascript", "xml", "wasm"]
+            .iter()
+            .any(|kind| content_type.contains(kind))
+}
+
+/// A [`Layer`] that compresses responses, flushing each chunk as soon as it
+/// has been rendered. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Compression {
+    encodings: Vec<Encoding>,
+    min_size: usize,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compression {
+    /// Compresses responses with Brotli or gzip, if they are at least 32
+    /// bytes long.
+    pub fn new() -> Self {
+        Self {
+            encodings: vec![Encoding::Brotli, Encoding::Gzip],
+            min_size: MIN_SIZE,
+        }
+    }
+
+    /// Sets the encodings in which responses can be compressed, in order of
+    /// preference when the client accepts several equally.
+    pub fn encodings(
+        mut self,
+        encodings: impl IntoIterator<Item = Encoding>,
+    ) -> Self {
+        self.encodings = encodings.into_iter().collect();
+        self
+    }
+
+    /// Sets the size in bytes below which responses are not compressed.
+    ///
+    /// A streamed response is compressed unless its whole body is sent in a
+    /// first chunk smaller than this: once it is being compressed, every
+    /// chunk is, however small.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+}
+
+impl<S> Layer<S> for Compression {
+    type Service = CompressionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CompressionService {
+            compression: self.clone(),
+            inner,
+        }
+    }
+}
+
+/// The [`Service`] created by the [`Compression`] layer.
+#[derive(Debug, Clone)]
+pub struct CompressionService<S> {
+    compression: Compression,
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for CompressionService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Response<Body>, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let encoding =
+            Encoding::negotiate(req.headers(), &self.compression.encodings);
+        let min_size = self.compression.min_size;
+        let res = self.inner.call(req);
+        Box::pin(async move {
+            let res = res.await?;
+            let Some(encoding) = encoding else {
+                return Ok(res);
+            };
+            if res.headers().contains_key(CONTENT_ENCODING)
+                || !is_compressible(res.headers())
+                || res
+                    .body()
+                    .size_hint()
+                    .exact()
+                    .is_some_and(|len| len < min_size as u64)
+            {
+                return Ok(res);
+            }
+            let (mut parts, body) = res.into_parts();
+            let mut body = body.into_data_stream();
+
+            // a response whose whole body is a small first chunk is sent as
+            // it is, which is only known once the next chunk is polled
+            let mut first = Vec::new();
+            first.extend(body.next().await);
+            let is_small =
+                matches!(&first[..], [Ok(chunk)] if chunk.len() < min_size);
+            if is_small {
+                first.extend(body.next().await);
+            }
+            if first.is_empty() || (is_small && first.len() == 1) {
+                let body = stream::iter(first).chain(body);
+                return Ok(Response::from_parts(
+                    parts,
+                    Body::from_stream(body),
+                ));
+            }
+
+            parts.headers.remove(CONTENT_LENGTH);
+            parts.headers.insert(
+                CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.name()),
+            );
+            parts
+                .headers
+                .append(VARY, HeaderValue::from_static("accept-encoding"));
+            let body =
+                stream::iter(first).chain(body).map_err(io::Error::other);
+            let body = stream::unfold(
+                (body, Some(Encoder::new(encoding))),
+                |(mut body, mut encoder)| async move {
+                    let chunk = match body.next().await {
+                        Some(Ok(chunk)) => encoder.as_mut()?.compress(&chunk),
+                        Some(Err(e)) => {
+                            encoder.take()?;
+                            Err(e)
+                        }
+                        None => encoder.take()?.finish(),
+                    };
+                    Some((chunk, (body, encoder)))
+                },
+            )
+            .try_filter(|chunk| future::ready(!chunk.is_empty()));
+            Ok(Response::from_parts(parts, Body::from_stream(body)))
+        })
+    }
+}
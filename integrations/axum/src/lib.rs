@@ -15,8 +15,26 @@
 //! - `wasm`: with `default-features = false`, supports running in a JS Fetch-based
 //!   environment
 //! - `nonce`: activates Leptos features that automatically provide a CSP [`Nonce`](leptos::nonce::Nonce) via context
+//! - `websocket`: supports server functions that communicate over a WebSocket
 //! - `experimental-islands`: activates Leptos [islands mode](https://leptos-rs.github.io/leptos/islands.html)
 //!
+//! ## Serving several apps
+//! Several Leptos apps, each with its own routes, WASM bundle, and [`LeptosOptions`], can be
+//! served by one server under different paths, e.g., while migrating from one to another.
+//! Give each app its own `site_base_path`, `output_name`, and `server_fn_prefix`, use the
+//! same base path as the `base` of its `<Router/>` and the same prefix in the `#[server]`
+//! macros of its server functions, and merge their routers:
+//! ```rust,ignore
+//! let shop = Router::new()
+//!     .leptos_routes(&shop_options, generate_route_list(shop::App), shop::App)
+//!     .with_state(shop_options);
+//! let blog = Router::new()
+//!     .leptos_routes(&blog_options, generate_route_list(blog::App), blog::App)
+//!     .with_state(blog_options);
+//! let app = shop.merge(blog);
+//! ```
+//! Each app then only registers and runs its own server functions.
+//!
 //! ### Important Note
 //! Prior to 0.5, using `default-features = false` on `leptos_axum` simply did nothing. Now, it actively
 //! disables features necessary to support the normal native/Tokio runtime environment we create. This can
@@ -33,6 +51,12 @@
 //! [`examples`](https://github.com/leptos-rs/leptos/tree/main/examples)
 //! directory in the Leptos repository.
 
+#[cfg(feature = "default")]
+pub mod assets;
+pub mod cache;
+pub mod compression;
+pub mod security;
+
 use axum::{
     body::{Body, Bytes},
     extract::{FromRef, FromRequestParts, MatchedPath},
@@ -48,17 +72,33 @@ use futures::{
     channel::mpsc::{Receiver, Sender},
     Future, SinkExt, Stream, StreamExt,
 };
-use leptos::{ssr::*, *};
-use leptos_integration_utils::{build_async_response, html_parts_separated};
-use leptos_meta::{generate_head_metadata_separated, MetaContext};
+use leptos::{
+    leptos_server::{
+        provide_server_response,
+        request::{provide_server_request, RequestContext, ServerRequest},
+        warn_if_response_sent, ResponseUpdate,
+    },
+    ssr::*,
+    *,
+};
+use leptos_integration_utils::{
+    build_async_response, content_security_policy, html_stream,
+    is_app_server_fn, with_site_base_path,
+};
+use leptos_meta::generate_head_metadata_separated;
 use leptos_router::*;
 use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
+#[cfg(feature = "websocket")]
+use server_fn::websocket::UpgradeContext;
 use server_fn::{
     error::{NoCustomError, ServerFnErrorSerde},
     redirect::REDIRECT_HEADER,
 };
-use std::{fmt::Debug, io, pin::Pin, sync::Arc, thread::available_parallelism};
+use std::{
+    fmt::Debug, io, net::SocketAddr, pin::Pin, sync::Arc,
+    thread::available_parallelism,
+};
 use tokio_util::task::LocalPoolHandle;
 use tracing::Instrument;
 
@@ -83,6 +123,15 @@ impl ResponseParts {
 
 /// Allows you to override details of the HTTP response like the status code and add Headers/Cookies.
 ///
+/// Server functions can also do this without depending on this integration, with
+/// [`set_response_status`], [`insert_response_header`], [`append_response_header`],
+/// and [`set_cookie`].
+///
+/// When a page is streamed, the status code and headers are sent with its shell, so they can
+/// be changed by any component rendered before then, including within `<Suspense/>` fragments
+/// that block the shell. Changes made after the shell has been sent, like within a fragment
+/// streamed in after it, are ignored, with a warning in debug builds.
+///
 /// `ResponseOptions` is provided via context when you use most of the handlers provided in this
 /// crate, including [`.leptos_routes`](LeptosRoutes::leptos_routes),
 /// [`.leptos_routes_with_context`](LeptosRoutes::leptos_routes_with_context), [`handle_server_fns`], etc.
@@ -107,24 +156,92 @@ impl ResponseOptions {
     }
     /// Set the status of the returned Response.
     pub fn set_status(&self, status: StatusCode) {
+        if warn_if_response_sent(ResponseUpdate::Status(status.as_u16())) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.status = Some(status);
     }
     /// Insert a header, overwriting any previous value with the same key.
     pub fn insert_header(&self, key: HeaderName, value: HeaderValue) {
+        if warn_if_response_sent(ResponseUpdate::InsertHeader(
+            key.as_str(),
+            value.to_str().unwrap_or_default(),
+        )) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.headers.insert(key, value);
     }
     /// Append a header, leaving any header with the same key intact.
     pub fn append_header(&self, key: HeaderName, value: HeaderValue) {
+        if warn_if_response_sent(ResponseUpdate::AppendHeader(
+            key.as_str(),
+            value.to_str().unwrap_or_default(),
+        )) {
+            return;
+        }
         let mut writeable = self.0.write();
         let res_parts = &mut *writeable;
         res_parts.headers.append(key, value);
     }
 }
 
+// provides the `ResponseOptions`, and lets server functions change them without
+// depending on this integration, with `leptos::set_response_status` etc.
+fn provide_response_options(res_options: ResponseOptions) {
+    provide_context(res_options.clone());
+    provide_server_response(move |update| match update {
+        ResponseUpdate::Status(status) => {
+            let status =
+                StatusCode::from_u16(status).map_err(|e| e.to_string())?;
+            res_options.set_status(status);
+            Ok(())
+        }
+        ResponseUpdate::InsertHeader(name, value) => {
+            let (name, value) = header_from_str(name, value)?;
+            res_options.insert_header(name, value);
+            Ok(())
+        }
+        ResponseUpdate::AppendHeader(name, value) => {
+            let (name, value) = header_from_str(name, value)?;
+            res_options.append_header(name, value);
+            Ok(())
+        }
+    })
+}
+
+fn header_from_str(
+    name: &str,
+    value: &str,
+) -> Result<(HeaderName, HeaderValue), String> {
+    let name = HeaderName::from_bytes(name.as_bytes())
+        .map_err(|e| format!("invalid header name {name:?}: {e}"))?;
+    let value = HeaderValue::from_str(value)
+        .map_err(|e| format!("invalid header value {value:?}: {e}"))?;
+    Ok((name, value))
+}
+
+/// Sets a `Content-Security-Policy` header on the response that allows the
+/// inline scripts and styles rendered by Leptos, which carry the
+/// [`Nonce`](leptos::nonce::Nonce) of the response, and blocks any others.
+/// This can be called in the app, like in its root component, to apply a
+/// strict policy to every page.
+///
+/// This requires the `nonce` feature, without which it does nothing.
+pub fn set_content_security_policy(options: &LeptosOptions) {
+    if let (Some(csp), Some(res)) = (
+        content_security_policy(options),
+        use_context::<ResponseOptions>(),
+    ) {
+        if let Ok(csp) = HeaderValue::from_str(&csp) {
+            res.insert_header(header::CONTENT_SECURITY_POLICY, csp);
+        }
+    }
+}
+
 /// Provides an easy way to redirect the user from within a server function. Mimicking the Remix `redirect()`,
 /// it sets a StatusCode of 302 and a LOCATION header with the provided value.
 /// If looking to redirect from the client, `leptos_router::use_navigate()` should be used instead
@@ -166,6 +283,45 @@ pub fn redirect(path: &str) {
     }
 }
 
+/// Provides a value as context for the request, from an Axum middleware, so
+/// that the components rendered for it and the server functions it calls can
+/// use it with [`use_context`] or [`expect_context`], like a database pool or
+/// the session of the user. See [`RequestContext`].
+///
+/// ```rust,ignore
+/// async fn auth(mut req: Request, next: Next) -> Response {
+///     let session = load_session(req.headers()).await;
+///     leptos_axum::provide_request_context(&mut req, session);
+///     next.run(req).await
+/// }
+///
+/// let app = Router::new()
+///     .leptos_routes(&leptos_options, routes, App)
+///     .layer(axum::middleware::from_fn(auth))
+///     .with_state(leptos_options);
+/// ```
+pub fn provide_request_context<B, T>(req: &mut Request<B>, value: T)
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let extensions = req.extensions_mut();
+    match extensions.get_mut::<RequestContext>() {
+        Some(context) => context.insert(value),
+        None => {
+            let mut context = RequestContext::new();
+            context.insert(value);
+            extensions.insert(context);
+        }
+    }
+}
+
+// provides the values that middleware has provided for the request
+fn provide_request_contexts(parts: &Parts) {
+    if let Some(context) = parts.extensions.get::<RequestContext>() {
+        context.provide();
+    }
+}
+
 /// Decomposes an HTTP request into its parts, allowing you to read its headers
 /// and other data without consuming the body. Creates a new Request from the
 /// original parts for further processing
@@ -237,6 +393,43 @@ macro_rules! spawn_task {
     };
 }
 
+// provides the request to a server function, along with the context that
+// middleware added to it
+fn provide_server_fn_contexts(parts: Parts) {
+    provide_server_request(AxumRequest(parts.clone()));
+    provide_request_contexts(&parts);
+    provide_context(parts);
+}
+
+// runs a WebSocket server function, once its connection is open, in the
+// context of the request that opened it, like other server functions
+#[cfg(feature = "websocket")]
+fn with_upgrade_context(
+    mut req: Request<Body>,
+    parts: Parts,
+    additional_context: impl Fn() + 'static + Clone + Send,
+) -> Request<Body> {
+    req.extensions_mut().insert(UpgradeContext::new(move |run| {
+        Box::pin(async move {
+            // the runtime has to be polled on the thread it was created on
+            let (tx, rx) = futures::channel::oneshot::channel();
+            spawn_task!(async move {
+                run_in_new_runtime(
+                    move || {
+                        additional_context();
+                        provide_server_fn_contexts(parts);
+                    },
+                    run,
+                )
+                .await;
+                _ = tx.send(());
+            });
+            _ = rx.await;
+        })
+    }));
+    req
+}
+
 /// An Axum handlers to listens for a request with Leptos server function arguments in the body,
 /// run the server function if found, and return the resulting [`Response`].
 ///
@@ -282,60 +475,69 @@ async fn handle_server_fns_inner(
         let _guard = current_span.enter();
 
         let path = req.uri().path().to_string();
+        let method = req.method().clone();
         let (req, parts) = generate_request_and_parts(req);
+        #[cfg(feature = "websocket")]
+        let req = with_upgrade_context(
+            req,
+            parts.clone(),
+            additional_context.clone(),
+        );
 
         let res = if let Some(mut service) =
-            server_fn::axum::get_server_fn_service(&path)
+            server_fn::axum::get_server_fn_service(&path, &method)
         {
-            let runtime = create_runtime();
-
-            additional_context();
-            provide_context(parts);
-            provide_context(ResponseOptions::default());
-
-            // store Accepts and Referer in case we need them for redirect (below)
-            let accepts_html = req
-                .headers()
-                .get(ACCEPT)
-                .and_then(|v| v.to_str().ok())
-                .map(|v| v.contains("text/html"))
-                .unwrap_or(false);
-            let referrer = req.headers().get(REFERER).cloned();
-
-            // actually run the server fn
-            let mut res = service.run(req).await;
-
-            // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
-            // Location set, then redirect to Referer
-            if accepts_html {
-                if let Some(referrer) = referrer {
-                    let has_location = res.headers().get(LOCATION).is_some();
-                    if !has_location {
-                        *res.status_mut() = StatusCode::FOUND;
-                        res.headers_mut().insert(LOCATION, referrer);
+            let res = run_server_fn(additional_context, || {
+                provide_server_fn_contexts(parts);
+                provide_response_options(ResponseOptions::default());
+
+                async move {
+                    // store Accepts and Referer in case we need them for redirect (below)
+                    let accepts_html = req
+                        .headers()
+                        .get(ACCEPT)
+                        .and_then(|v| v.to_str().ok())
+                        .map(|v| v.contains("text/html"))
+                        .unwrap_or(false);
+                    let referrer = req.headers().get(REFERER).cloned();
+
+                    // actually run the server fn
+                    let mut res = service.run(req).await;
+
+                    // if it accepts text/html (i.e., is a plain form post) and doesn't already have a
+                    // Location set, then redirect to Referer
+                    if accepts_html {
+                        if let Some(referrer) = referrer {
+                            let has_location =
+                                res.headers().get(LOCATION).is_some();
+                            if !has_location {
+                                *res.status_mut() = StatusCode::FOUND;
+                                res.headers_mut().insert(LOCATION, referrer);
+                            }
+                        }
                     }
-                }
-            }
 
-            // update response as needed
-            if let Some(res_options) = use_context::<ResponseOptions>() {
-                let res_options_inner = res_options.0.read();
-                let (status, mut res_headers) = (
-                    res_options_inner.status,
-                    res_options_inner.headers.clone(),
-                );
+                    // update response as needed
+                    if let Some(res_options) = use_context::<ResponseOptions>()
+                    {
+                        let res_options_inner = res_options.0.read();
+                        let (status, mut res_headers) = (
+                            res_options_inner.status,
+                            res_options_inner.headers.clone(),
+                        );
 
-                // apply status code and headers if used changed them
-                if let Some(status) = status {
-                    *res.status_mut() = status;
+                        // apply status code and headers if used changed them
+                        if let Some(status) = status {
+                            *res.status_mut() = status;
+                        }
+                        res.headers_mut().extend(res_headers.drain());
+                    } else {
+                        eprintln!("Failed to find ResponseOptions for {path}");
+                    }
+                    res
                 }
-                res.headers_mut().extend(res_headers.drain());
-            } else {
-                eprintln!("Failed to find ResponseOptions for {path}");
-            }
-
-            // clean up the scope
-            runtime.dispose();
+            })
+            .await;
             Ok(res)
         } else {
             Response::builder().status(StatusCode::BAD_REQUEST).body(
@@ -375,7 +577,7 @@ pub type PinnedHtmlStream =
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application.
 ///
-/// The provides a [MetaContext] and a [RouterIntegrationContext] to app’s context before
+/// The provides a [MetaContext](leptos_meta::MetaContext) and a [RouterIntegrationContext] to app’s context before
 /// rendering it, and includes any meta tags injected using [leptos_meta].
 ///
 /// The HTML stream is rendered using [render_to_stream](leptos::ssr::render_to_stream), and
@@ -465,7 +667,7 @@ where
 /// This stream will pause at each `<Suspense/>` node and wait for it to resolve before
 /// sending down its HTML. The app will become interactive once it has fully loaded.
 ///
-/// The provides a [MetaContext] and a [RouterIntegrationContext] to app’s context before
+/// The provides a [MetaContext](leptos_meta::MetaContext) and a [RouterIntegrationContext] to app’s context before
 /// rendering it, and includes any meta tags injected using [leptos_meta].
 ///
 /// The HTML stream is rendered using [render_to_stream_in_order], and includes everything described in
@@ -698,10 +900,13 @@ where
                     // if http, it returns a relative path. Adding .path() seems to make it explicitly return the relative uri
                     let path = req.uri().path_and_query().unwrap().as_str();
 
+                    let path = with_site_base_path(&options, path);
                     let full_path = format!("http://leptos.dev{path}");
                     let (_, req_parts) = generate_request_and_parts(req);
+                    let render_timeout = options.render_timeout_ms;
                     move || {
                         provide_contexts(full_path, req_parts, default_res_options);
+                        provide_render_deadline(render_timeout);
                         app_fn().into_view()
                     }
                 };
@@ -713,9 +918,8 @@ where
                         replace_blocks
                     );
 
-                    forward_stream(&options, res_options2, bundle, tx).await;
-
-                    runtime.dispose();
+                    forward_stream(&options, res_options2, bundle, runtime, tx)
+                        .await;
             }.instrument(current_span));
 
             generate_response(res_options3, rx)
@@ -765,36 +969,51 @@ async fn generate_response(
     }
     res
 }
+// the timer used to coalesce the chunks of streamed responses
+#[cfg(feature = "default")]
+fn sleep(duration: std::time::Duration) -> impl Future<Output = ()> {
+    tokio::time::sleep(duration)
+}
+
+// without a tokio runtime, only chunks that are already ready are coalesced
+#[cfg(not(feature = "default"))]
+fn sleep(_: std::time::Duration) -> impl Future<Output = ()> {
+    futures::future::ready(())
+}
+
+// the deadline for rendering a page streamed out of order, which is only set
+// with a tokio runtime to time it
+fn provide_render_deadline(render_timeout_ms: u64) {
+    #[cfg(feature = "default")]
+    leptos::deadline::provide_render_deadline(
+        std::time::Duration::from_millis(render_timeout_ms),
+        sleep,
+    );
+    #[cfg(not(feature = "default"))]
+    let _ = render_timeout_ms;
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 async fn forward_stream(
     options: &LeptosOptions,
     res_options2: ResponseOptions,
     bundle: impl Stream<Item = String> + 'static,
+    runtime: RuntimeId,
     mut tx: Sender<String>,
 ) {
-    let mut shell = Box::pin(bundle);
-    let first_app_chunk = shell.next().await.unwrap_or_default();
-
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>().as_ref());
-
-    _ = tx.send(head).await;
+    let mut html = Box::pin(html_stream(options, bundle, runtime, sleep).await);
 
-    _ = tx.send(first_app_chunk).await;
-
-    while let Some(fragment) = shell.next().await {
-        _ = tx.send(fragment).await;
-    }
-
-    _ = tx.send(tail.to_string()).await;
-
-    // Extract the value of ResponseOptions from here
+    // Extract the value of ResponseOptions from here, now that the shell has
+    // been rendered
     let res_options = use_context::<ResponseOptions>().unwrap();
 
     let new_res_parts = res_options.0.read().clone();
 
-    let mut writable = res_options2.0.write();
-    *writable = new_res_parts;
+    *res_options2.0.write() = new_res_parts;
+
+    while let Some(chunk) = html.next().await {
+        _ = tx.send(chunk).await;
+    }
 
     tx.close_channel();
 }
@@ -855,6 +1074,7 @@ where
                 // if http, it returns a relative path. Adding .path() seems to make it explicitly return the relative uri
                 let path = req.uri().path_and_query().unwrap().as_str();
 
+                let path = with_site_base_path(&options, path);
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::mpsc::channel(8);
@@ -876,9 +1096,8 @@ where
                             add_context,
                         );
 
-                    forward_stream(&options, res_options2, bundle, tx).await;
-
-                    runtime.dispose();
+                    forward_stream(&options, res_options2, bundle, runtime, tx)
+                        .await;
                 }.instrument(current_span));
 
                 generate_response(res_options3, rx).await
@@ -893,21 +1112,110 @@ fn provide_contexts(
     parts: Parts,
     default_res_options: ResponseOptions,
 ) {
-    let integration = ServerIntegration { path };
-    provide_context(RouterIntegrationContext::new(integration));
-    provide_context(MetaContext::new());
-    provide_context(parts);
-    provide_context(default_res_options);
-    provide_server_redirect(redirect);
-    #[cfg(feature = "nonce")]
-    leptos::nonce::provide_nonce();
+    provide_server_contexts(
+        path,
+        AxumAdapter {
+            parts,
+            res_options: default_res_options,
+        },
+    );
+}
+
+// the parts of rendering a page that depend on Axum
+struct AxumAdapter {
+    parts: Parts,
+    res_options: ResponseOptions,
+}
+
+impl ServerAdapter for AxumAdapter {
+    fn provide_contexts(&self) {
+        #[cfg(feature = "csrf")]
+        issue_csrf_token(&self.parts, &self.res_options);
+        provide_server_request(AxumRequest(self.parts.clone()));
+        provide_request_contexts(&self.parts);
+        provide_context(self.parts.clone());
+        provide_response_options(self.res_options.clone());
+        if let Some(headers) =
+            self.parts.extensions.get::<security::SecurityHeaders>()
+        {
+            provide_context(headers.clone());
+        }
+    }
+
+    fn set_status(&self, status: u16) {
+        if let Ok(status) = StatusCode::from_u16(status) {
+            self.res_options.set_status(status);
+        }
+    }
+
+    fn redirect(&self, path: &str) {
+        redirect(path);
+    }
+}
+
+// the request, from which `leptos::request::extract` extracts values
+struct AxumRequest(Parts);
+
+impl ServerRequest for AxumRequest {
+    fn method(&self) -> &str {
+        self.0.method.as_str()
+    }
+
+    fn uri(&self) -> String {
+        self.0
+            .uri
+            .path_and_query()
+            .map(|uri| uri.to_string())
+            .unwrap_or_else(|| self.0.uri.path().to_string())
+    }
+
+    fn headers(&self) -> Vec<(&str, &str)> {
+        self.0
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str(), value.to_str().ok()?))
+            })
+            .collect()
+    }
+
+    // known if the app is served with `into_make_service_with_connect_info`
+    #[cfg(feature = "default")]
+    fn client_addr(&self) -> Option<SocketAddr> {
+        use axum::extract::ConnectInfo;
+
+        self.0
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr)
+    }
+
+    #[cfg(not(feature = "default"))]
+    fn client_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+}
+
+// sets the CSRF cookie on the page, if the request does not carry one yet
+#[cfg(feature = "csrf")]
+fn issue_csrf_token(parts: &Parts, res_options: &ResponseOptions) {
+    let cookies = parts
+        .headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+    if let Some(cookie) = server_fn::csrf::issue(cookies)
+        .and_then(|cookie| HeaderValue::from_str(&cookie).ok())
+    {
+        res_options.append_header(header::SET_COOKIE, cookie);
+    }
 }
 
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
 /// to route it using [leptos_router], asynchronously rendering an HTML page after all
 /// `async` [Resource](leptos::Resource)s have loaded.
 ///
-/// The provides a [MetaContext] and a [RouterIntegrationContext] to app’s context before
+/// The provides a [MetaContext](leptos_meta::MetaContext) and a [RouterIntegrationContext] to app’s context before
 /// rendering it, and includes any meta tags injected using [leptos_meta].
 ///
 /// The HTML stream is rendered using [render_to_string_async], and includes everything described in
@@ -1027,6 +1335,7 @@ where
                 // if http, it returns a relative path. Adding .path() seems to make it explicitly return the relative uri
                 let path = req.uri().path_and_query().unwrap().as_str();
 
+                let path = with_site_base_path(&options, path);
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::oneshot::channel();
@@ -1156,6 +1465,7 @@ where
                 // if http, it returns a relative path. Adding .path() seems to make it explicitly return the relative uri
                 let path = req.uri().path_and_query().unwrap().as_str();
 
+                let path = with_site_base_path(&options, path);
                 let full_path = format!("http://leptos.dev{path}");
 
                 let (tx, rx) = futures::channel::oneshot::channel();
@@ -1310,48 +1620,12 @@ pub fn generate_route_list_with_exclusions_and_ssg_and_context<IV>(
 where
     IV: IntoView + 'static,
 {
-    let (routes, static_data_map) =
-        leptos_router::generate_route_list_inner_with_context(
-            app_fn,
-            additional_context,
-        );
     // Axum's Router defines Root routes as "/" not ""
-    let mut routes = routes
-        .into_iter()
-        .map(|listing| {
-            let path = listing.path();
-            if path.is_empty() {
-                RouteListing::new(
-                    "/".to_string(),
-                    listing.path(),
-                    listing.mode(),
-                    listing.methods(),
-                    listing.static_mode(),
-                )
-            } else {
-                listing
-            }
-        })
-        .collect::<Vec<_>>();
-
-    (
-        if routes.is_empty() {
-            vec![RouteListing::new(
-                "/",
-                "",
-                Default::default(),
-                [leptos_router::Method::Get],
-                None,
-            )]
-        } else {
-            // Routes to exclude from auto generation
-            if let Some(excluded_routes) = excluded_routes {
-                routes
-                    .retain(|p| !excluded_routes.iter().any(|e| e == p.path()))
-            }
-            routes
-        },
-        static_data_map,
+    generate_route_list_with_format(
+        app_fn,
+        excluded_routes,
+        additional_context,
+        str::to_string,
     )
 }
 
@@ -1488,6 +1762,70 @@ where
     })
 }
 
+/// Marks the static page for a concrete path (e.g., `/blog/hello-world`) as
+/// stale, so that it is built again in the background on its next request.
+///
+/// Until the new page has been built, requests are still served the stale
+/// page. This works for routes in any [`StaticMode`], and is a way to update
+/// their pages when the data they were built from has changed, without
+/// making the next request wait for them to be rendered.
+#[cfg(feature = "default")]
+pub fn revalidate(path: &str) {
+    REVALIDATION.lock().stale.insert(path.to_string());
+}
+
+#[cfg(feature = "default")]
+#[derive(Default)]
+struct Revalidation {
+    // paths marked with `revalidate`
+    stale: std::collections::HashSet<String>,
+    // paths whose pages are being built again
+    running: std::collections::HashSet<String>,
+}
+
+#[cfg(feature = "default")]
+static REVALIDATION: once_cell::sync::Lazy<parking_lot::Mutex<Revalidation>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+// Removes the path from the running revalidations once it is dropped, even if
+// building the page fails.
+#[cfg(feature = "default")]
+struct RevalidationGuard(String);
+
+#[cfg(feature = "default")]
+impl Drop for RevalidationGuard {
+    fn drop(&mut self) {
+        REVALIDATION.lock().running.remove(&self.0);
+    }
+}
+
+// Starts building the page for the path again, if it has been marked as stale
+// or is older than the `ttl`, and is not already being built.
+#[cfg(feature = "default")]
+async fn begin_revalidation(
+    path: &str,
+    file_path: &str,
+    ttl: Option<std::time::Duration>,
+) -> Option<RevalidationGuard> {
+    let expired = match ttl {
+        Some(ttl) => tokio::fs::metadata(file_path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= ttl),
+        None => false,
+    };
+    let mut revalidation = REVALIDATION.lock();
+    let stale = revalidation.stale.contains(path);
+    if !(expired || stale) || revalidation.running.contains(path) {
+        return None;
+    }
+    revalidation.stale.remove(path);
+    revalidation.running.insert(path.to_string());
+    Some(RevalidationGuard(path.to_string()))
+}
+
 #[cfg(feature = "default")]
 fn static_route<IV, S>(
     router: axum::Router<S>,
@@ -1502,94 +1840,74 @@ where
     IV: IntoView + 'static,
     S: Clone + Send + Sync + 'static,
 {
-    match mode {
-        StaticMode::Incremental => {
-            let handler = move |req: Request<Body>| {
-                Box::pin({
-                    let path = req.uri().path().to_string();
-                    let options = options.clone();
-                    let app_fn = app_fn.clone();
-                    let additional_context = additional_context.clone();
-
-                    async move {
-                        let (tx, rx) = futures::channel::oneshot::channel();
-                        spawn_task!(async move {
-                            let res = incremental_static_route(
-                                tokio::fs::read_to_string(static_file_path(
-                                    &options, &path,
-                                ))
-                                .await,
-                            );
-                            let res = handle_static_response(
-                                path.clone(),
-                                options,
-                                app_fn,
-                                additional_context,
-                                res,
-                            )
-                            .await;
-
-                            let _ = tx.send(res);
-                        });
-                        rx.await.expect("to complete HTML rendering")
-                    }
-                })
-            };
-            router.route(
-                path,
-                match method {
-                    leptos_router::Method::Get => get(handler),
-                    leptos_router::Method::Post => post(handler),
-                    leptos_router::Method::Put => put(handler),
-                    leptos_router::Method::Delete => delete(handler),
-                    leptos_router::Method::Patch => patch(handler),
-                },
-            )
-        }
-        StaticMode::Upfront => {
-            let handler = move |req: Request<Body>| {
-                Box::pin({
-                    let path = req.uri().path().to_string();
-                    let options = options.clone();
-                    let app_fn = app_fn.clone();
-                    let additional_context = additional_context.clone();
-
-                    async move {
-                        let (tx, rx) = futures::channel::oneshot::channel();
-                        spawn_task!(async move {
-                            let res = upfront_static_route(
-                                tokio::fs::read_to_string(static_file_path(
-                                    &options, &path,
-                                ))
-                                .await,
-                            );
-                            let res = handle_static_response(
-                                path.clone(),
-                                options,
-                                app_fn,
-                                additional_context,
-                                res,
-                            )
-                            .await;
-
-                            let _ = tx.send(res);
-                        });
-                        rx.await.expect("to complete HTML rendering")
+    let (static_response, ttl): (fn(_) -> _, _) = match mode {
+        StaticMode::Upfront => (upfront_static_route, None),
+        StaticMode::Incremental => (incremental_static_route, None),
+        StaticMode::Revalidate(ttl) => (incremental_static_route, Some(ttl)),
+    };
+    let handler = move |req: Request<Body>| {
+        Box::pin({
+            let path = req.uri().path().to_string();
+            let options = options.clone();
+            let app_fn = app_fn.clone();
+            let additional_context = additional_context.clone();
+
+            async move {
+                let (tx, rx) = futures::channel::oneshot::channel();
+                spawn_task!(async move {
+                    let file_path = static_file_path(&options, &path);
+                    let page = tokio::fs::read_to_string(&file_path).await;
+                    let revalidation = match page {
+                        Ok(_) => {
+                            begin_revalidation(&path, &file_path, ttl).await
+                        }
+                        Err(_) => None,
+                    };
+                    let res = handle_static_response(
+                        path.clone(),
+                        options.clone(),
+                        app_fn.clone(),
+                        additional_context.clone(),
+                        static_response(page),
+                    )
+                    .await;
+
+                    let _ = tx.send(res);
+
+                    // the stale page has been sent, so the new one is built
+                    // without holding up the response
+                    if let Some(_revalidation) = revalidation {
+                        let res = render_dynamic(
+                            &path,
+                            &options,
+                            app_fn.clone(),
+                            additional_context.clone(),
+                        )
+                        .await;
+                        handle_static_response(
+                            path,
+                            options,
+                            app_fn,
+                            additional_context,
+                            res,
+                        )
+                        .await;
                     }
-                })
-            };
-            router.route(
-                path,
-                match method {
-                    leptos_router::Method::Get => get(handler),
-                    leptos_router::Method::Post => post(handler),
-                    leptos_router::Method::Put => put(handler),
-                    leptos_router::Method::Delete => delete(handler),
-                    leptos_router::Method::Patch => patch(handler),
-                },
-            )
-        }
-    }
+                });
+                rx.await.expect("to complete HTML rendering")
+            }
+        })
+    };
+    router.route(
+        path,
+        match method {
+            leptos_router::Method::Get => get(handler),
+            leptos_router::Method::Post => post(handler),
+            leptos_router::Method::Put => put(handler),
+            leptos_router::Method::Delete => delete(handler),
+            leptos_router::Method::Patch => patch(handler),
+        },
+    )
 }
 
 /// The default implementation of `LeptosRoutes` which takes in a list of paths, and dispatches GET requests
@@ -1634,13 +1952,30 @@ where
         let mut router = self;
 
         // register server functions first to allow for wildcard router path
-        for (path, method) in server_fn::axum::server_fn_paths() {
+        let leptos_options = LeptosOptions::from_ref(options);
+        for (path, method) in server_fn::axum::server_fn_paths()
+            .filter(|(path, _)| is_app_server_fn(&leptos_options, path))
+        {
             let cx_with_state = cx_with_state.clone();
             let handler = move |req: Request<Body>| async move {
                 handle_server_fns_with_context(cx_with_state, req).await
             };
+            // path parameters are written as `{name}`, but as `:name` in Axum
+            let path = path
+                .split('/')
+                .map(|segment| {
+                    match segment
+                        .strip_prefix('{')
+                        .and_then(|name| name.strip_suffix('}'))
+                    {
+                        Some(name) => format!(":{name}"),
+                        None => segment.to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
             router = router.route(
-                path,
+                &path,
                 match method {
                     Method::GET => get(handler),
                     Method::POST => post(handler),
@@ -0,0 +1,121 @@
+//! Adds headers that harden the responses of the app.
+//!
+//! [`SecurityHeadersLayer`] adds the [`SecurityHeaders`] to every response
+//! that does not already have them, including the responses of server
+//! functions and static files:
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .leptos_routes(&leptos_options, routes, App)
+//!     .fallback(file_and_error_handler)
+//!     .layer(SecurityHeadersLayer::new(
+//!         SecurityHeaders::new().frame_ancestors(Some("'self'")),
+//!     ))
+//!     .with_state(leptos_options);
+//! ```
+//!
+//! With the `nonce` feature, each page rendered by the app is given a
+//! `Content-Security-Policy` that allows its inline scripts and styles by
+//! their nonce, and blocks any others.
+
+use axum::{
+    body::Body,
+    http::{HeaderName, HeaderValue, Request, Response},
+};
+pub use leptos_integration_utils::SecurityHeaders;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A [`Layer`] that adds the [`SecurityHeaders`] to every response. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersLayer {
+    config: SecurityHeaders,
+    headers: Arc<[(HeaderName, HeaderValue)]>,
+}
+
+impl Default for SecurityHeadersLayer {
+    fn default() -> Self {
+        Self::new(SecurityHeaders::default())
+    }
+}
+
+impl SecurityHeadersLayer {
+    /// Adds the given headers to every response.
+    pub fn new(config: SecurityHeaders) -> Self {
+        let headers = config
+            .headers()
+            .into_iter()
+            .filter_map(|(name, value)| match HeaderValue::from_str(&value) {
+                Ok(value) => Some((HeaderName::from_static(name), value)),
+                Err(e) => {
+                    tracing::warn!("invalid {name} header {value:?}: {e}");
+                    None
+                }
+            })
+            .collect();
+        Self { config, headers }
+    }
+}
+
+impl<S> Layer<S> for SecurityHeadersLayer {
+    type Service = SecurityHeadersService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SecurityHeadersService {
+            layer: self.clone(),
+            inner,
+        }
+    }
+}
+
+/// The [`Service`] created by the [`SecurityHeadersLayer`].
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersService<S> {
+    layer: SecurityHeadersLayer,
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for SecurityHeadersService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Response<Body>, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        // lets a page that is rendered set the policy for its nonce
+        req.extensions_mut().insert(self.layer.config.clone());
+        let headers = Arc::clone(&self.layer.headers);
+        let res = self.inner.call(req);
+        Box::pin(async move {
+            let mut res = res.await?;
+            for (name, value) in headers.iter() {
+                if !res.headers().contains_key(name) {
+                    res.headers_mut().insert(name.clone(), value.clone());
+                }
+            }
+            Ok(res)
+        })
+    }
+}
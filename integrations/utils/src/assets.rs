@@ -0,0 +1,216 @@
+use crate::site_base_path;
+use leptos_config::LeptosOptions;
+use percent_encoding::percent_decode_str;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    env, fs, io,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+static INSTALLED: RwLock<Vec<Arc<AssetManifest>>> = RwLock::new(Vec::new());
+
+/// The `Cache-Control` of a file requested with the hash of its contents in
+/// its URL, which never changes.
+pub const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// The `Cache-Control` of HTML files, and of files requested without the hash
+/// of their contents, which are revalidated with their `ETag` before they are
+/// reused.
+pub const NO_CACHE: &str = "no-cache";
+
+/// The files in the [`site_root`](LeptosOptions::site_root), with a hash of
+/// the contents of each, so that they can be served with URLs that change
+/// whenever they do.
+///
+/// Once the manifest has been [installed](AssetManifest::install), the URLs
+/// of the files in the `<head>` of each page, like the app's JS, WASM and CSS
+/// and the stylesheets added with `leptos_meta`, are given the hash of the
+/// file as a `v` query parameter, like `/pkg/app.css?v=3f2a9c1e0b4d5a67`.
+/// [`resolve`](AssetManifest::resolve) finds the file to serve for a request,
+/// which can be cached for a year when its URL has the current hash, and is
+/// otherwise revalidated with its `ETag` before it is reused.
+///
+/// When running with `cargo leptos watch`, the files change while the server
+/// runs, so they are not hashed, and are always revalidated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetManifest {
+    root: PathBuf,
+    base: String,
+    hashes: HashMap<String, String>,
+}
+
+/// A file in the `site_root` to send in response to a request, found by
+/// [`AssetManifest::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asset {
+    /// The path of the file.
+    pub file: PathBuf,
+    /// The `Content-Type` of the file, guessed from its extension.
+    pub content_type: String,
+    /// The `ETag` of the file, which is the hash of its contents, if it has
+    /// been hashed.
+    pub etag: Option<String>,
+    /// The `Cache-Control` of the response: [`IMMUTABLE`] or [`NO_CACHE`].
+    pub cache_control: &'static str,
+}
+
+impl AssetManifest {
+    /// Hashes the contents of every file in the `site_root`.
+    pub fn build(options: &LeptosOptions) -> io::Result<Self> {
+        let mut manifest = Self {
+            root: PathBuf::from(&options.site_root),
+            base: site_base_path(options),
+            hashes: HashMap::new(),
+        };
+        if env::var("LEPTOS_WATCH").is_err() {
+            let root = manifest.root.clone();
+            manifest.hash_dir(&root, &root)?;
+        }
+        Ok(manifest)
+    }
+
+    fn hash_dir(&mut self, root: &Path, dir: &Path) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.hash_dir(root, &path)?;
+                continue;
+            }
+            let Some(relative) = path
+                .strip_prefix(root)
+                .ok()
+                .and_then(|relative| relative.to_str())
+            else {
+                continue;
+            };
+            let url = format!("{}/{}", self.base, relative.replace('\\', "/"));
+            let hash = xxhash_rust::xxh64::xxh64(&fs::read(&path)?, 0);
+            self.hashes.insert(url, format!("{hash:016x}"));
+        }
+        Ok(())
+    }
+
+    /// Makes this the manifest with which the `<head>` of each page of the
+    /// app served from its `site_root` and `site_base_path` is rewritten.
+    ///
+    /// Each app served by the same server can install its own manifest.
+    pub fn install(self) -> Arc<Self> {
+        let manifest = Arc::new(self);
+        let mut installed =
+            INSTALLED.write().unwrap_or_else(|e| e.into_inner());
+        installed.retain(|other| {
+            other.root != manifest.root || other.base != manifest.base
+        });
+        installed.push(Arc::clone(&manifest));
+        manifest
+    }
+
+    /// The manifest that has been [installed](AssetManifest::install) for the
+    /// app with the given options, if any.
+    pub fn installed(options: &LeptosOptions) -> Option<Arc<Self>> {
+        let root = Path::new(&options.site_root);
+        let base = site_base_path(options);
+        INSTALLED
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|manifest| manifest.root == root && manifest.base == base)
+            .cloned()
+    }
+
+    /// The hash of the contents of the file at the given URL path, like
+    /// `/pkg/app.css`.
+    pub fn hash(&self, path: &str) -> Option<&str> {
+        self.hashes.get(path).map(String::as_str)
+    }
+
+    /// The URL of the file at the given path with the hash of its contents,
+    /// like `/pkg/app.css?v=3f2a9c1e0b4d5a67`, if it has been hashed.
+    pub fn hashed_url(&self, path: &str) -> Option<String> {
+        self.hash(path).map(|hash| format!("{path}?v={hash}"))
+    }
+
+    /// Adds the hash of each file to the quoted URLs of files in `html`, like
+    /// `href="/pkg/app.css"` or `import('/pkg/app.js')`.
+    pub fn rewrite<'a>(&self, html: &'a str) -> Cow<'a, str> {
+        if self.hashes.is_empty() {
+            return Cow::Borrowed(html);
+        }
+        let mut rewritten = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(start) = rest.find(['"', '\'']) {
+            let quote = &rest[start..start + 1];
+            let (before, after) = rest.split_at(start + 1);
+            rewritten.push_str(before);
+            rest = after;
+            if !rest.starts_with('/') {
+                continue;
+            }
+            let Some(end) = rest.find(quote) else {
+                break;
+            };
+            let url = &rest[..end];
+            match self.hashed_url(url) {
+                Some(hashed) => rewritten.push_str(&hashed),
+                None => rewritten.push_str(url),
+            }
+            rest = &rest[end..];
+        }
+        rewritten.push_str(rest);
+        Cow::Owned(rewritten)
+    }
+
+    /// Finds the file to serve for a request for `path`, with the given query
+    /// string, or `None` if there is none. A path that ends with `/` is served
+    /// the `index.html` in that directory.
+    pub fn resolve(&self, path: &str, query: Option<&str>) -> Option<Asset> {
+        let path = percent_decode_str(path).decode_utf8().ok()?.into_owned();
+        let path = if path.ends_with('/') {
+            format!("{path}index.html")
+        } else {
+            path
+        };
+        let relative = path
+            .strip_prefix(&self.base)
+            .filter(|relative| relative.starts_with('/'))?
+            .trim_start_matches('/');
+
+        // files are only served from within the site root
+        let relative = Path::new(relative);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        let file = self.root.join(relative);
+        if !file.is_file() {
+            return None;
+        }
+
+        let content_type = mime_guess::from_path(&file)
+            .first_or_octet_stream()
+            .to_string();
+        let hash = self.hash(&path);
+        let requested_hash = query
+            .into_iter()
+            .flat_map(|q| q.split('&'))
+            .find_map(|param| param.strip_prefix("v="));
+        let cache_control = if content_type != "text/html"
+            && hash.is_some()
+            && hash == requested_hash
+        {
+            IMMUTABLE
+        } else {
+            NO_CACHE
+        };
+        Some(Asset {
+            file,
+            content_type,
+            etag: hash.map(|hash| format!("\"{hash}\"")),
+            cache_control,
+        })
+    }
+}
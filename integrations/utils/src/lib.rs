@@ -1,11 +1,24 @@
-use futures::{Stream, StreamExt};
-use leptos::{nonce::use_nonce, use_context, RuntimeId};
+use futures::{
+    future::{self, Either},
+    Future, Stream, StreamExt,
+};
+use leptos::{
+    error_pages::{log_unhandled_errors, take_error_page},
+    leptos_server::{append_response_header, mark_response_sent},
+    nonce::use_nonce,
+    provide_context, run_as_child, use_context, RuntimeId, SharedContext,
+};
 use leptos_config::LeptosOptions;
 use leptos_meta::MetaContext;
-use std::{borrow::Cow, collections::HashMap, env, fs};
+use std::{borrow::Cow, collections::HashMap, env, fs, time::Duration};
 
 extern crate tracing;
 
+mod assets;
+mod security;
+pub use assets::*;
+pub use security::*;
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 fn autoreload(nonce_str: &str, options: &LeptosOptions) -> String {
     let reload_port = match options.reload_external_port {
@@ -51,19 +64,106 @@ fn autoreload(nonce_str: &str, options: &LeptosOptions) -> String {
     }
 }
 
+/// Returns a `Content-Security-Policy` for the current response, which allows
+/// the inline scripts and styles that carry its [`Nonce`](leptos::nonce::Nonce)
+/// (see [`Nonce::content_security_policy`](leptos::nonce::Nonce::content_security_policy)),
+/// or `None` if no nonce has been provided.
+///
+/// When running with `cargo leptos watch`, it also allows the connection to
+/// the live-reload websocket.
+pub fn content_security_policy(options: &LeptosOptions) -> Option<String> {
+    let mut csp = use_nonce()?.content_security_policy();
+    if env::var("LEPTOS_WATCH").is_ok() {
+        let port = options.reload_external_port.unwrap_or(options.reload_port);
+        let protocol = match options.reload_ws_protocol {
+            leptos_config::ReloadWSProtocol::WS => "ws",
+            leptos_config::ReloadWSProtocol::WSS => "wss",
+        };
+        csp.push_str(&format!("; connect-src 'self' {protocol}://*:{port}"));
+    }
+    Some(csp)
+}
+
+/// Returns the configured [`site_base_path`](LeptosOptions::site_base_path),
+/// normalized to begin with a `/` and not end with one, or `""` if the app is
+/// served from the root.
+pub fn site_base_path(options: &LeptosOptions) -> String {
+    let base = options.site_base_path.trim_matches('/');
+    if base.is_empty() {
+        String::new()
+    } else {
+        format!("/{base}")
+    }
+}
+
+/// Adds the configured [`site_base_path`](LeptosOptions::site_base_path) to
+/// the start of the path of a request, unless it already begins with it.
+///
+/// This allows the router to match the same paths whether the server sees the
+/// full path, or the base path has been stripped by a reverse proxy or by
+/// mounting the app as a nested service.
+pub fn with_site_base_path<'a>(
+    options: &LeptosOptions,
+    path: &'a str,
+) -> Cow<'a, str> {
+    let base = site_base_path(options);
+    let has_base = base.is_empty()
+        || path.strip_prefix(&base).is_some_and(|rest| {
+            rest.is_empty() || rest.starts_with(['/', '?', '#'])
+        });
+    if has_base {
+        Cow::Borrowed(path)
+    } else if path.starts_with('/') {
+        Cow::Owned(format!("{base}{path}"))
+    } else {
+        Cow::Owned(format!("{base}/{path}"))
+    }
+}
+
+/// Returns whether the server function at `path` belongs to the app, because
+/// its path begins with the configured
+/// [`server_fn_prefix`](LeptosOptions::server_fn_prefix).
+///
+/// When several apps are served by the same server, this keeps the routes of
+/// each app from registering the server functions of the others.
+pub fn is_app_server_fn(options: &LeptosOptions, path: &str) -> bool {
+    let prefix = options.server_fn_prefix.trim_end_matches('/');
+    prefix.is_empty()
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Returns the path, or URL, from which the browser loads the files in the
+/// [`site_pkg_dir`](LeptosOptions::site_pkg_dir), like the app's WASM, JS, and
+/// CSS.
+///
+/// This is the `CDN_PKG_PATH` environment variable, if it is set when the
+/// server is run, or else the pkg dir under the
+/// [`asset_prefix`](LeptosOptions::asset_prefix), if there is one, or the
+/// `CDN_PKG_PATH` set when the server was built, or the pkg dir under the
+/// [`site_base_path`](LeptosOptions::site_base_path).
+pub fn site_pkg_path(options: &LeptosOptions) -> String {
+    if let Ok(path) = env::var("CDN_PKG_PATH") {
+        return path;
+    }
+    let pkg_dir = options.site_pkg_dir.trim_matches('/');
+    let prefix = options.asset_prefix.trim_end_matches('/');
+    if !prefix.is_empty() {
+        return format!("{prefix}/{pkg_dir}");
+    }
+    match option_env!("CDN_PKG_PATH") {
+        Some(path) => path.to_string(),
+        None => format!("{}/{pkg_dir}", site_base_path(options)),
+    }
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 pub fn html_parts_separated(
     options: &LeptosOptions,
     meta: Option<&MetaContext>,
 ) -> (String, &'static str) {
-    // First check runtime env, then build time, then default:
-    let pkg_path = match std::env::var("CDN_PKG_PATH").ok().map(Cow::from) {
-        Some(path) => path,
-        None => match option_env!("CDN_PKG_PATH").map(Cow::from) {
-            Some(path) => path,
-            None => format!("/{}", options.site_pkg_dir).into(),
-        },
-    };
+    let pkg_path = site_pkg_path(options);
     let output_name = &options.output_name;
     let nonce = use_nonce();
     let nonce = nonce
@@ -96,10 +196,21 @@ pub fn html_parts_separated(
           }
           mod.hydrate();
         }"# */
+        // lazy islands are hydrated when first interacted with, before the
+        // event reaches their handlers
         r#"() => {       
+            const events = ["pointerover", "pointerdown", "touchstart", "focusin", "keydown", "input", "change", "submit", "click"];
             for (let e of document.querySelectorAll("leptos-island")) {
                 let l = e.dataset.component;
-                mod["_island_" + l](e);
+                if ("lazy" in e.dataset) {
+                    let hydrate = () => {
+                        for (let ev of events) e.removeEventListener(ev, hydrate, true);
+                        mod["_island_" + l](e);
+                    };
+                    for (let ev of events) e.addEventListener(ev, hydrate, true);
+                } else {
+                    mod["_island_" + l](e);
+                }
             }
             mod.hydrate();
         }
@@ -111,19 +222,30 @@ pub fn html_parts_separated(
 
     let (js_hash, wasm_hash, css_hash) = get_hashes(options);
 
-    let head = head.replace(
+    let mut head = head.replace(
         &format!("{output_name}.css"),
         &format!("{output_name}{css_hash}.css"),
     );
+    // the app's own links to the pkg dir, like its stylesheet, are loaded
+    // from the same place as the WASM and JS
+    let local_pkg_path = format!(
+        "{}/{}",
+        site_base_path(options),
+        options.site_pkg_dir.trim_matches('/')
+    );
+    if pkg_path != local_pkg_path {
+        head = head.replace(
+            &format!("href=\"{local_pkg_path}/"),
+            &format!("href=\"{pkg_path}/"),
+        );
+    }
 
-    let head = format!(
-        r#"<!DOCTYPE html>
-            <html{html_metadata}>
-                <head>
-                    <meta charset="utf-8"/>
-                    <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                    {head}
-                    <link rel="modulepreload" href="{pkg_path}/{output_name}{js_hash}.js"{nonce}>
+    // pages that are not hydrated do not load the app in the browser
+    let hydration = if SharedContext::is_server_only() {
+        String::new()
+    } else {
+        format!(
+            r#"<link rel="modulepreload" href="{pkg_path}/{output_name}{js_hash}.js"{nonce}>
                     <link rel="preload" href="{pkg_path}/{wasm_output_name}{wasm_hash}.wasm" as="fetch" type="application/wasm" crossorigin=""{nonce}>
                     <script type="module"{nonce}>
                         function idle(c) {{
@@ -139,10 +261,26 @@ pub fn html_parts_separated(
                                     mod.default('{pkg_path}/{wasm_output_name}{wasm_hash}.wasm').then({import_callback});
                                 }})
                         }});
-                    </script>
+                    </script>"#
+        )
+    };
+
+    let head = format!(
+        r#"<!DOCTYPE html>
+            <html{html_metadata}>
+                <head>
+                    <meta charset="utf-8"/>
+                    <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                    {head}
+                    {hydration}
                     {leptos_autoreload}
                 </head>"#
     );
+    // the files of the site are loaded with the hashes of their contents
+    let head = match AssetManifest::installed(options) {
+        Some(manifest) => manifest.rewrite(&head).into_owned(),
+        None => head,
+    };
     let tail = "</body></html>";
     (head, tail)
 }
@@ -187,6 +325,138 @@ fn get_hashes(options: &LeptosOptions) -> (String, String, String) {
     )
 }
 
+/// Renders the [error page](leptos::error_pages) that replaces the current
+/// page, if the app has one for it, as a whole HTML document, and sets the
+/// status code of the response.
+///
+/// This should be called once the shell of the app has been rendered, and
+/// before any of it has been sent, as [`html_stream`] and
+/// [`build_async_response`] do.
+pub fn render_error_page(options: &LeptosOptions) -> Option<String> {
+    render_error_page_parts(options).map(|(head, body)| head + &body)
+}
+
+// the error page, split into its `<head>` and the rest of the document
+fn render_error_page_parts(
+    options: &LeptosOptions,
+) -> Option<(String, String)> {
+    let page = take_error_page()?;
+    let options = options.clone();
+    Some(run_as_child(move || {
+        // the page has its own metadata, and is not hydrated
+        let meta = MetaContext::new();
+        provide_context(meta.clone());
+        SharedContext::set_server_only();
+        let body = page().render_to_string();
+        let (head, tail) = html_parts_separated(&options, Some(&meta));
+        let body_meta = meta.body.as_string().unwrap_or_default();
+        (head, format!("<body{body_meta}>{body}{tail}"))
+    }))
+}
+
+/// Combines the chunks of a streamed HTML response that are ready within
+/// [`stream_coalesce_ms`](LeptosOptions::stream_coalesce_ms) of each other, up
+/// to [`stream_max_buffer`](LeptosOptions::stream_max_buffer) bytes, so that
+/// `<Suspense/>` fragments that resolve close together are sent as one chunk.
+///
+/// `sleep` returns a future that completes after the given duration, using
+/// the timer of the server's runtime.
+pub fn coalesce_stream<F>(
+    stream: impl Stream<Item = String> + 'static,
+    options: &LeptosOptions,
+    sleep: impl Fn(Duration) -> F + 'static,
+) -> impl Stream<Item = String>
+where
+    F: Future<Output = ()>,
+{
+    let window = Duration::from_millis(options.stream_coalesce_ms);
+    let max_buffer = options.stream_max_buffer;
+    let stream = Box::pin(stream.fuse());
+    futures::stream::unfold((stream, sleep), move |(mut stream, sleep)| {
+        async move {
+            let mut buf = stream.next().await?;
+            if !window.is_zero() {
+                // the window starts once the first chunk is ready
+                let mut deadline = std::pin::pin!(sleep(window));
+                while buf.len() < max_buffer {
+                    match future::select(stream.next(), deadline.as_mut()).await
+                    {
+                        Either::Left((Some(chunk), _)) => buf.push_str(&chunk),
+                        _ => break,
+                    }
+                }
+            }
+            Some((buf, (stream, sleep)))
+        }
+    })
+}
+
+/// Turns the stream of a streamed app into the stream of a whole HTML
+/// document, which begins with the `<head>` and ends with the closing tags.
+///
+/// This waits for the first chunk of the app, the shell, to be rendered, so
+/// that the `<head>` includes the metadata set while rendering it. Once it
+/// resolves, the status code and headers set while rendering the shell can be
+/// applied to the response, and later changes to them are ignored (see
+/// [`mark_response_sent`]). The chunks that follow are
+/// [coalesced](coalesce_stream) with the `sleep` timer, and `runtime` is
+/// disposed of once the app has been rendered in full.
+#[tracing::instrument(level = "trace", fields(error), skip_all)]
+pub async fn html_stream<F>(
+    options: &LeptosOptions,
+    stream: impl Stream<Item = String> + 'static,
+    runtime: RuntimeId,
+    sleep: impl Fn(Duration) -> F + 'static,
+) -> impl Stream<Item = String>
+where
+    F: Future<Output = ()>,
+{
+    let mut stream = Box::pin(stream);
+
+    // wait for any blocking resources to load before pulling metadata
+    let first_app_chunk = stream.next().await.unwrap_or_default();
+    set_page_content_security_policy(options);
+
+    // the shell is replaced by an error page before it is sent, and the
+    // runtime is kept until then, as the response options are read from it
+    if let Some((head, body)) = render_error_page_parts(options) {
+        mark_response_sent();
+        return futures::stream::once(future::ready(head))
+            .chain(futures::stream::once(async move {
+                runtime.dispose();
+                body
+            }))
+            .right_stream();
+    }
+
+    let meta = use_context::<MetaContext>();
+    set_preload_link_header(meta.as_ref());
+    let (head, tail) = html_parts_separated(options, meta.as_ref());
+
+    // the integration sends the status code and headers with the shell, so
+    // the components rendered after it can no longer change them
+    mark_response_sent();
+
+    futures::stream::iter([head, first_app_chunk])
+        .chain(coalesce_stream(stream, options, sleep))
+        .chain(futures::stream::once(async move {
+            // other pages may have been rendered on this thread since
+            leptos::set_current_runtime(runtime);
+            log_unhandled_errors();
+            runtime.dispose();
+            tail.to_string()
+        }))
+        .left_stream()
+}
+
+// lists the resources the page preloads in a `Link` header, which is sent
+// before the body, and which proxies can turn into 103 Early Hints
+fn set_preload_link_header(meta: Option<&MetaContext>) {
+    if let Some(link) = meta.and_then(|meta| meta.preloads.link_header()) {
+        _ = append_response_header("link", &link);
+    }
+}
+
 #[tracing::instrument(level = "trace", fields(error), skip_all)]
 pub async fn build_async_response(
     stream: impl Stream<Item = String> + 'static,
@@ -198,12 +468,19 @@ pub async fn build_async_response(
     while let Some(chunk) = stream.next().await {
         buf.push_str(&chunk);
     }
+    set_page_content_security_policy(options);
 
-    let (head, tail) =
-        html_parts_separated(options, use_context::<MetaContext>().as_ref());
+    if let Some(page) = render_error_page(options) {
+        runtime.dispose();
+        return page;
+    }
+    log_unhandled_errors();
 
-    // in async, we load the meta content *now*, after the suspenses have resolved
     let meta = use_context::<MetaContext>();
+    set_preload_link_header(meta.as_ref());
+    let (head, tail) = html_parts_separated(options, meta.as_ref());
+
+    // in async, we load the meta content *now*, after the suspenses have resolved
     let body_meta = meta
         .as_ref()
         .and_then(|meta| meta.body.as_string())
@@ -0,0 +1,174 @@
+use crate::content_security_policy;
+use leptos::{insert_response_header, use_context};
+use leptos_config::LeptosOptions;
+
+/// Headers that harden the responses of a Leptos app, added by the
+/// `SecurityHeadersLayer` of `leptos_axum`, or the `SecurityHeadersMiddleware`
+/// of `leptos_actix`.
+///
+/// By default, these are:
+/// - `Strict-Transport-Security: max-age=63072000; includeSubDomains`
+/// - `X-Content-Type-Options: nosniff`
+/// - `Referrer-Policy: strict-origin-when-cross-origin`
+/// - a `Content-Security-Policy` with `frame-ancestors 'none'`, so that the
+///   site cannot be embedded in another, as well as `base-uri 'self'` and
+///   `object-src 'none'`
+///
+/// With the `nonce` feature, the policy of each page also allows only the
+/// inline scripts and styles that carry the [`Nonce`](leptos::nonce::Nonce)
+/// of the response, which includes every one rendered by Leptos (see
+/// [`content_security_policy`]). Without it, inline scripts cannot be
+/// restricted, as the ones that load the data of resources differ from one
+/// response to the next, so they are not.
+///
+/// Each header is only added if the response does not already have it, so a
+/// handler can still set its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeaders {
+    hsts: Option<String>,
+    content_type_options: bool,
+    referrer_policy: Option<String>,
+    frame_ancestors: Option<String>,
+    content_security_policy: bool,
+    directives: Vec<(String, String)>,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecurityHeaders {
+    /// The default headers, described [above](SecurityHeaders).
+    pub fn new() -> Self {
+        Self {
+            hsts: Some("max-age=63072000; includeSubDomains".into()),
+            content_type_options: true,
+            referrer_policy: Some("strict-origin-when-cross-origin".into()),
+            frame_ancestors: Some("'none'".into()),
+            content_security_policy: true,
+            directives: Vec::new(),
+        }
+    }
+
+    /// Sets the value of the `Strict-Transport-Security` header, like
+    /// `max-age=31536000; includeSubDomains; preload`, or leaves it out if
+    /// `None`, like when the site is not only served over HTTPS.
+    pub fn hsts(mut self, value: Option<&str>) -> Self {
+        self.hsts = value.map(Into::into);
+        self
+    }
+
+    /// Sets whether to add `X-Content-Type-Options: nosniff`.
+    pub fn content_type_options(mut self, nosniff: bool) -> Self {
+        self.content_type_options = nosniff;
+        self
+    }
+
+    /// Sets the value of the `Referrer-Policy` header, or leaves it out if
+    /// `None`.
+    pub fn referrer_policy(mut self, value: Option<&str>) -> Self {
+        self.referrer_policy = value.map(Into::into);
+        self
+    }
+
+    /// Sets the sources that may embed the site, like `'self'` or
+    /// `'self' https://example.com`, in the `frame-ancestors` directive of the
+    /// `Content-Security-Policy`, or leaves it out if `None`.
+    pub fn frame_ancestors(mut self, sources: Option<&str>) -> Self {
+        self.frame_ancestors = sources.map(Into::into);
+        self
+    }
+
+    /// Sets whether to add a `Content-Security-Policy`.
+    pub fn content_security_policy(mut self, enabled: bool) -> Self {
+        self.content_security_policy = enabled;
+        self
+    }
+
+    /// Adds a directive to the `Content-Security-Policy`, like
+    /// `connect-src` with `'self' https://api.example.com`, replacing any
+    /// directive with the same name.
+    pub fn csp_directive(mut self, name: &str, value: &str) -> Self {
+        self.directives
+            .retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.directives.push((name.into(), value.into()));
+        self
+    }
+
+    /// The headers to add to every response, as lowercase names and their
+    /// values. The `Content-Security-Policy` is the one for responses that
+    /// are not pages rendered with a nonce, which do not restrict scripts.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(hsts) = &self.hsts {
+            headers.push(("strict-transport-security", hsts.clone()));
+        }
+        if self.content_type_options {
+            headers.push(("x-content-type-options", "nosniff".into()));
+        }
+        if let Some(policy) = &self.referrer_policy {
+            headers.push(("referrer-policy", policy.clone()));
+        }
+        if self.content_security_policy {
+            headers.push(("content-security-policy", self.policy("")));
+        }
+        headers
+    }
+
+    /// The `Content-Security-Policy` of the page being rendered, which allows
+    /// the inline scripts and styles that carry its nonce, or `None` if it
+    /// has none.
+    pub fn page_content_security_policy(
+        &self,
+        options: &LeptosOptions,
+    ) -> Option<String> {
+        if !self.content_security_policy {
+            return None;
+        }
+        content_security_policy(options).map(|csp| self.policy(&csp))
+    }
+
+    // adds the configured directives to `csp`, replacing those with the same
+    // name
+    fn policy(&self, csp: &str) -> String {
+        let mut directives = csp
+            .split(';')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| {
+                let (name, value) =
+                    directive.split_once(' ').unwrap_or((directive, ""));
+                (name.to_string(), value.to_string())
+            })
+            .collect::<Vec<_>>();
+        let defaults = [
+            ("base-uri", Some("'self'")),
+            ("object-src", Some("'none'")),
+            ("frame-ancestors", self.frame_ancestors.as_deref()),
+        ];
+        let defaults = defaults.into_iter().filter_map(|(name, value)| {
+            Some((name.to_string(), value?.to_string()))
+        });
+        for (name, value) in defaults.chain(self.directives.iter().cloned()) {
+            directives.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+            directives.push((name, value));
+        }
+        directives
+            .into_iter()
+            .map(|(name, value)| format!("{name} {value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+// sets the policy of the page being rendered, if the integration has
+// provided the `SecurityHeaders` of the request
+pub(crate) fn set_page_content_security_policy(options: &LeptosOptions) {
+    if let Some(csp) = use_context::<SecurityHeaders>()
+        .and_then(|headers| headers.page_content_security_policy(options))
+    {
+        _ = insert_response_header("content-security-policy", &csp);
+    }
+}
@@ -0,0 +1,59 @@
+use leptos_config::LeptosOptions;
+use leptos_integration_utils::AssetManifest;
+use std::{fs, path::PathBuf};
+
+fn manifest(name: &str) -> (AssetManifest, PathBuf) {
+    let root = std::env::temp_dir().join(format!(
+        "leptos_integration_utils_{name}_{}",
+        std::process::id()
+    ));
+    let site_root = root.join("site");
+    fs::create_dir_all(site_root.join("pkg")).unwrap();
+    fs::write(site_root.join("pkg/app.css"), "body { color: red; }").unwrap();
+    fs::write(site_root.join("pkg/100%.txt"), "all of it").unwrap();
+    fs::write(root.join("secret.txt"), "hunter2").unwrap();
+    let options = LeptosOptions::builder()
+        .output_name("app")
+        .site_root(site_root.to_str().unwrap())
+        .build();
+    (AssetManifest::build(&options).unwrap(), root)
+}
+
+#[test]
+fn resolves_percent_encoded_paths() {
+    let (manifest, root) = manifest("encoded");
+    let asset = manifest.resolve("/pkg/app%2Ecss", None).unwrap();
+    assert_eq!(asset.file, root.join("site/pkg/app.css"));
+    assert_eq!(asset.content_type, "text/css");
+    assert!(manifest.resolve("/pkg%2Fapp.css", None).is_some());
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn keeps_invalid_or_truncated_escapes() {
+    let (manifest, root) = manifest("invalid");
+    assert!(manifest.resolve("/pkg/100%.txt", None).is_some());
+    assert!(manifest.resolve("/pkg/100%25.txt", None).is_some());
+    assert!(manifest.resolve("/pkg/app.css%", None).is_none());
+    assert!(manifest.resolve("/pkg/app.css%2", None).is_none());
+    assert!(manifest.resolve("/pkg/app%zzcss", None).is_none());
+    // not valid UTF-8 once decoded
+    assert!(manifest.resolve("/pkg/app%FF.css", None).is_none());
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn does_not_serve_files_outside_the_site_root() {
+    let (manifest, root) = manifest("traversal");
+    assert!(manifest.resolve("/../secret.txt", None).is_none());
+    assert!(manifest.resolve("/%2e%2e/secret.txt", None).is_none());
+    assert!(manifest.resolve("/%2E%2E%2Fsecret.txt", None).is_none());
+    assert!(manifest
+        .resolve("/pkg/%2e%2e/%2e%2e/secret.txt", None)
+        .is_none());
+    assert!(manifest
+        .resolve("/pkg/..%2F..%2Fsecret.txt", None)
+        .is_none());
+    assert!(manifest.resolve("/%2Fetc%2Fpasswd", None).is_none());
+    fs::remove_dir_all(root).unwrap();
+}
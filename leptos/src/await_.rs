@@ -1,10 +1,12 @@
-use crate::Suspense;
-use leptos_dom::IntoView;
+use crate::{Suspense, ViewFn};
+use leptos_dom::{IntoView, View};
 use leptos_macro::{component, view};
 use leptos_reactive::{
     create_blocking_resource, create_local_resource, create_resource,
-    store_value, Serializable,
+    create_signal, spawn_local, store_value, with_owner, Owner, Serializable,
+    SignalGet, SignalSet,
 };
+use std::future::Future;
 
 #[component]
 /// Allows you to inline the data loading for an `async` block or
@@ -120,3 +122,34 @@ where
         </Suspense>
     }
 }
+
+/// Renders the view returned by the body of an `async` component, showing the
+/// `fallback` until it has loaded.
+///
+/// As a view can't be serialized, the body only runs in the browser, once,
+/// when the component is created. The server renders the `fallback`, which is
+/// also what the browser renders first, so that hydration matches.
+#[doc(hidden)]
+pub fn async_component<V>(
+    fallback: ViewFn,
+    view: impl Future<Output = V> + 'static,
+) -> impl IntoView
+where
+    V: IntoView + 'static,
+{
+    let (loaded, set_loaded) = create_signal(None::<View>);
+    if cfg!(not(feature = "ssr")) {
+        let owner = Owner::current();
+        spawn_local(async move {
+            let view = view.await;
+            // the view is created after the component has returned, so it is
+            // created in the component's owner
+            set_loaded.set(Some(match owner {
+                Some(owner) => with_owner(owner, || view.into_view()),
+                None => view.into_view(),
+            }));
+        });
+    }
+
+    move || loaded.get().unwrap_or_else(|| fallback.run())
+}
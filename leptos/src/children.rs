@@ -13,6 +13,11 @@ pub type ChildrenFn = Rc<dyn Fn() -> Fragment>;
 /// more than once, but may mutate the children.
 pub type ChildrenFnMut = Box<dyn FnMut() -> Fragment>;
 
+/// A type for the `children` property on slots that are rendered with a
+/// value, which is bound with `let:` where the slot is used, and can be
+/// called more than once.
+pub type TypedChildrenFn<T> = Rc<dyn Fn(T) -> Fragment>;
+
 // This is to still support components that accept `Box<dyn Fn() -> Fragment>` as a children.
 type BoxedChildrenFn = Box<dyn Fn() -> Fragment>;
 
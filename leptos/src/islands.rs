@@ -0,0 +1,62 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Display;
+
+/// Defines how the props of an [`island`](crate::island) are serialized into
+/// the HTML rendered on the server, and deserialized again when the island is
+/// hydrated in the browser.
+///
+/// By default, props are serialized as JSON with [`JsonIslandSerializer`]. An
+/// island can use another format with `#[island(serializer = MySerializer)]`,
+/// for example to use a more compact encoding or to compress large props. The
+/// serialized props are stored in an HTML attribute, so they must be a string.
+///
+/// ```rust,ignore
+/// pub struct Base64Json;
+///
+/// impl IslandSerializer for Base64Json {
+///     type Error = String;
+///
+///     fn serialize<T: Serialize>(props: &T) -> Result<String, Self::Error> {
+///         let json = serde_json::to_vec(props).map_err(|e| e.to_string())?;
+///         Ok(BASE64_STANDARD.encode(json))
+///     }
+///
+///     fn deserialize<T: DeserializeOwned>(
+///         data: &str,
+///     ) -> Result<T, Self::Error> {
+///         let json = BASE64_STANDARD.decode(data).map_err(|e| e.to_string())?;
+///         serde_json::from_slice(&json).map_err(|e| e.to_string())
+///     }
+/// }
+///
+/// #[island(serializer = Base64Json)]
+/// pub fn Chart(points: Vec<(f64, f64)>) -> impl IntoView {
+///     // ...
+/// }
+/// ```
+pub trait IslandSerializer {
+    /// The error returned if the props cannot be serialized or deserialized.
+    type Error: Display;
+
+    /// Serializes the props of an island on the server.
+    fn serialize<T: Serialize>(props: &T) -> Result<String, Self::Error>;
+
+    /// Deserializes the props of an island in the browser.
+    fn deserialize<T: DeserializeOwned>(data: &str) -> Result<T, Self::Error>;
+}
+
+/// Serializes the props of islands as JSON. This is the default
+/// [`IslandSerializer`].
+pub struct JsonIslandSerializer;
+
+impl IslandSerializer for JsonIslandSerializer {
+    type Error = serde_json::Error;
+
+    fn serialize<T: Serialize>(props: &T) -> Result<String, Self::Error> {
+        serde_json::to_string(props)
+    }
+
+    fn deserialize<T: DeserializeOwned>(data: &str) -> Result<T, Self::Error> {
+        serde_json::from_str(data)
+    }
+}
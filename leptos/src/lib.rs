@@ -69,7 +69,8 @@
 //!   - `async` interop: [`create_resource`] and [`Resource`] for loading data using `async` functions,
 //!     and [`create_action`] and [`Action`] to mutate data or imperatively call `async` functions.
 //!   - reactions: [`create_effect`]
-//! - **Templating/Views**: the [`view`] macro
+//! - **Templating/Views**: the [`view`] macro, and the [`component`] macro, whose `async` components
+//!   take a `fallback` prop that is rendered until they have loaded
 //! - **Routing**: the [`leptos_router`](https://docs.rs/leptos_router/latest/leptos_router/) crate
 //! - **Server Functions**: the [`server`](crate::leptos_server) macro, [`create_action`], and [`create_server_action`]
 //!
@@ -89,7 +90,10 @@
 //! - `tracing` Adds additional support for [`tracing`](https://docs.rs/tracing/latest/tracing/) to components.
 //! - `default-tls` Use default native TLS support. (Only applies when using server functions with a non-WASM client like a desktop app.)
 //! - `rustls` Use `rustls`. (Only applies when using server functions with a non-WASM client like a desktop app.)
-//! - `template_macro` Enables the [`template!`](leptos_macro::template) macro, which offers faster DOM node creation for some use cases in `csr`.
+//! - `template_macro` Enables the cached `<template>` of the [`template!`](leptos_macro::template) macro for views that
+//!   are not fully static, which offers faster DOM node creation for some use cases in `csr`.
+//! - `testing` Keeps views up to date and runs timers on a virtual clock without a browser, for the
+//!   headless renderer used by [`leptos_test`](https://docs.rs/leptos_test).
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in. You should only enable one of these per build target,
@@ -153,7 +157,9 @@ pub mod ssr {
     pub use leptos_dom::{ssr::*, ssr_in_order::*};
 }
 pub use leptos_dom::{
-    self, create_node_ref, document, ev,
+    self, create_node_ref, deadline, document, error_pages,
+    drag_and_drop::{use_draggable, use_drop_zone},
+    ev,
     helpers::{
         event_target, event_target_checked, event_target_value,
         request_animation_frame, request_animation_frame_with_handle,
@@ -163,6 +169,7 @@ pub use leptos_dom::{
     },
     html,
     html::Binding,
+    layout::{use_element_size, use_window_scroll, use_window_size},
     math, mount_to, mount_to_body, nonce, svg, window, Attribute, Class,
     CollectView, Errors, EventHandlerFn, Fragment, HtmlElement, IntoAttribute,
     IntoClass, IntoProperty, IntoStyle, IntoView, NodeRef, Property, View,
@@ -176,11 +183,10 @@ pub mod logging {
 pub mod error {
     pub use server_fn::error::{Error, Result};
 }
-#[cfg(all(target_arch = "wasm32", feature = "template_macro"))]
-pub use leptos_macro::template;
-#[cfg(not(all(target_arch = "wasm32", feature = "template_macro")))]
-pub use leptos_macro::view as template;
-pub use leptos_macro::{component, island, slice, slot, view, Params};
+pub use leptos_macro::{
+    component, island, slice, slot, template, view, Params, QueryParams,
+    TypedRoute,
+};
 cfg_if::cfg_if!(
     if #[cfg(feature="spin")] {
         pub use leptos_spin_macro::server;
@@ -190,9 +196,11 @@ cfg_if::cfg_if!(
 );
 pub use leptos_reactive::*;
 pub use leptos_server::{
-    self, create_action, create_multi_action, create_server_action,
-    create_server_multi_action, Action, MultiAction, ServerFnError,
-    ServerFnErrorErr,
+    self, append_response_header, create_action, create_multi_action,
+    create_optimistic, create_server_action, create_server_fn_resource,
+    create_server_multi_action, insert_response_header, request, set_cookie,
+    set_response_status, sync_cookies, use_cookie, use_cookie_with, Action,
+    Cookie, MultiAction, SameSite, ServerFnError, ServerFnErrorErr,
 };
 pub use server_fn::{self, ServerFn as _};
 mod error_boundary;
@@ -205,6 +213,10 @@ pub use animated_show::*;
 pub use for_loop::*;
 pub use provider::*;
 #[cfg(feature = "experimental-islands")]
+mod islands;
+#[cfg(feature = "experimental-islands")]
+pub use islands::*;
+#[cfg(feature = "experimental-islands")]
 pub use serde;
 #[cfg(feature = "experimental-islands")]
 pub use serde_json;
@@ -273,6 +285,12 @@ pub trait Props {
     fn builder() -> Self::Builder;
 }
 
+#[doc(hidden)]
+pub trait SpreadProps {
+    type Spread;
+    fn spread(self) -> Self::Spread;
+}
+
 #[doc(hidden)]
 pub trait DynAttrs {
     fn dyn_attrs(self, _args: Vec<(&'static str, Attribute)>) -> Self
@@ -344,6 +362,14 @@ pub fn component_props_builder<P: PropsOrNoPropsBuilder>(
     <P as PropsOrNoPropsBuilder>::builder_or_not()
 }
 
+#[doc(hidden)]
+pub fn component_props_spread<P: SpreadProps>(
+    _f: &impl Component<P>,
+    props: P,
+) -> <P as SpreadProps>::Spread {
+    props.spread()
+}
+
 #[doc(hidden)]
 pub fn component_view<P>(f: impl ComponentConstructor<P>, props: P) -> View {
     f.construct(props)
@@ -64,12 +64,20 @@ pub fn Suspense<V>(
     /// Returns a fallback UI that will be shown while `async` [`Resource`](leptos_reactive::Resource)s are still loading. By default this is the empty view.
     #[prop(optional, into)]
     fallback: ViewFn,
+    /// Whether this boundary is streamed in order, even when the page is streamed out of order.
+    /// The HTML that follows it is held back until it has resolved, and it is sent in place of
+    /// its fallback, rather than replacing the fallback once it has loaded. This is useful for
+    /// content that should not shift once it appears, at the cost of delaying what comes after.
+    #[prop(optional)]
+    in_order: bool,
     /// Children will be displayed once all `async` [`Resource`](leptos_reactive::Resource)s have resolved.
     children: Rc<dyn Fn() -> V>,
 ) -> impl IntoView
 where
     V: IntoView + 'static,
 {
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let _ = in_order;
     #[cfg(all(
         feature = "experimental-islands",
         not(any(feature = "csr", feature = "hydrate"))
@@ -162,6 +170,12 @@ where
                         HydrationCtx::continue_from(current_id);
                         let runtime = leptos_reactive::current_runtime();
 
+                        if in_order {
+                            SharedContext::register_in_order_fragment(
+                                current_id.to_string(),
+                            );
+                        }
+
                         SharedContext::register_suspense(
                             context,
                             &current_id.to_string(),
@@ -76,6 +76,10 @@ pub fn Transition(
     /// or not pending (`false`).
     #[prop(optional, into)]
     set_pending: Option<SignalSetter<bool>>,
+    /// Whether this boundary is streamed in order, even when the page is streamed out of
+    /// order. See [`Suspense`](crate::Suspense).
+    #[prop(optional)]
+    in_order: bool,
     /// Will be displayed once all resources have resolved.
     children: Box<dyn Fn() -> Fragment>,
 ) -> impl IntoView {
@@ -87,6 +91,7 @@ pub fn Transition(
 
     crate::Suspense(
         crate::SuspenseProps::builder()
+            .in_order(in_order)
             .fallback({
                 let prev_child = Rc::clone(&prev_children);
                 move || {
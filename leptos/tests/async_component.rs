@@ -0,0 +1,43 @@
+//! Run with `wasm-pack test --headless --firefox -- --features hydrate`.
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+async fn Greeting(name: &'static str) -> impl IntoView {
+    let name = async { name }.await;
+    view! { <p>"Hello, " {name} "!"</p> }
+}
+
+// resolves once the tasks spawned so far have run
+async fn tick() {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    spawn_local(async move {
+        _ = tx.send(());
+    });
+    _ = rx.await;
+}
+
+#[wasm_bindgen_test]
+async fn renders_the_fallback_until_loaded() {
+    let runtime = create_runtime();
+    let el = view! {
+        <main>
+            <Greeting name="Leptos" fallback=|| view! { <p>"Loading..."</p> }/>
+        </main>
+    };
+    let el: &web_sys::HtmlElement = &el;
+    document().body().unwrap().append_child(el).unwrap();
+
+    // the first render matches the fallback that the server renders
+    assert_eq!(el.text_content().unwrap(), "Loading...");
+
+    tick().await;
+    assert_eq!(el.text_content().unwrap(), "Hello, Leptos!");
+
+    el.remove();
+    runtime.dispose();
+}
@@ -206,3 +206,440 @@ fn ssr_option() {
 
     runtime.dispose();
 }
+
+#[cfg(all(feature = "experimental-islands", feature = "ssr"))]
+#[test]
+fn ssr_lazy_island() {
+    use leptos::*;
+
+    #[island(lazy)]
+    fn Counter(initial_value: i32) -> impl IntoView {
+        let (value, set_value) = create_signal(initial_value);
+        view! {
+            <button on:click=move |_| set_value.update(|value| *value += 1)>
+                {value}
+            </button>
+        }
+    }
+
+    let runtime = create_runtime();
+    let rendered = view! { <Counter initial_value=1/> }
+        .into_view()
+        .render_to_string();
+
+    assert!(rendered.starts_with(
+        "<leptos-island data-component=\"Counter\" data-hkc=\"0-0-0-0\" \
+         data-props=\"{&quot;initial_value&quot;:1}\" data-lazy"
+    ));
+    runtime.dispose();
+}
+
+#[cfg(all(feature = "experimental-islands", feature = "ssr"))]
+#[test]
+fn ssr_island_props_serializer_and_context() {
+    use leptos::{
+        serde::{de::DeserializeOwned, Serialize},
+        *,
+    };
+    use std::rc::Rc;
+
+    struct Reversed;
+
+    impl IslandSerializer for Reversed {
+        type Error = serde_json::Error;
+
+        fn serialize<T: Serialize>(props: &T) -> Result<String, Self::Error> {
+            serde_json::to_string(props).map(|s| s.chars().rev().collect())
+        }
+
+        fn deserialize<T: DeserializeOwned>(
+            data: &str,
+        ) -> Result<T, Self::Error> {
+            serde_json::from_str(&data.chars().rev().collect::<String>())
+        }
+    }
+
+    #[derive(Clone)]
+    struct Theme(Rc<str>);
+
+    #[island(serializer = Reversed)]
+    fn Greeting(name: String, #[prop(context)] theme: Theme) -> impl IntoView {
+        view! { <p class=theme.0.to_string()>{name}</p> }
+    }
+
+    let runtime = create_runtime();
+
+    provide_context(Theme("dark".into()));
+    let rendered = view! { <Greeting name="Ann".to_string()/> }
+        .into_view()
+        .render_to_string();
+    assert!(
+        rendered.contains("data-props=\"}&quot;nnA&quot;:&quot;eman&quot;{\"")
+    );
+    assert!(rendered.contains("dark\">Ann</p>"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_control_flow() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let (count, set_count) = create_signal(0);
+    let items = vec!["a", "b"];
+    let view = move || {
+        let items = items.clone();
+        view! {
+            <div>
+                {move if count.get() > 0 {
+                    view! { <p>"Some"</p> }
+                }}
+                {move match count.get() {
+                    0 => "Zero",
+                    _ => "More",
+                }}
+                <ul>
+                    {move for item in items.clone() {
+                        view! { <li>{item}</li> }
+                    }}
+                </ul>
+            </div>
+        }
+        .into_view()
+        .render_to_string()
+    };
+
+    let rendered = view();
+    assert!(!rendered.contains("Some"));
+    assert!(rendered.contains("Zero"));
+    assert!(rendered.contains(">a</li>") && rendered.contains(">b</li>"));
+
+    set_count.set(1);
+    let rendered = view();
+    assert!(rendered.contains("Some</p>"));
+    assert!(rendered.contains("More"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_control_flow_plain_blocks() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    // without `move`, a block is ordinary Rust that runs once, so it can
+    // move the values it captures
+    let data = vec![String::from("moved")];
+    let flag = true;
+    let rendered = view! {
+        <div>
+            {if flag { data.into_view() } else { ().into_view() }}
+        </div>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains("moved"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_control_flow_nested_branches() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+    let (count, set_count) = create_signal(0);
+    let (ready, set_ready) = create_signal(false);
+    let view = move || {
+        view! {
+            <div>
+                {move if count.get() == 0 {
+                    view! { <p>"Zero"</p> }.into_view()
+                } else if count.get() < 10 {
+                    view! {
+                        <p>
+                            {move match ready.get() {
+                                true => view! { <b>"Ready"</b> },
+                                false => view! { <b>"Waiting"</b> },
+                            }}
+                        </p>
+                    }
+                    .into_view()
+                } else {
+                    "Many".into_view()
+                }}
+            </div>
+        }
+        .into_view()
+        .render_to_string()
+    };
+
+    assert!(view().contains("Zero"));
+    set_count.set(1);
+    assert!(view().contains("Waiting"));
+    set_ready.set(true);
+    assert!(view().contains("Ready"));
+    set_count.set(10);
+    let rendered = view();
+    assert!(rendered.contains("Many") && !rendered.contains("Ready"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_control_flow_keyed_for() {
+    use leptos::*;
+
+    #[derive(Clone)]
+    struct Todo {
+        id: usize,
+        title: &'static str,
+    }
+
+    let runtime = create_runtime();
+    let (todos, _) = create_signal(vec![
+        Todo {
+            id: 0,
+            title: "Write",
+        },
+        Todo {
+            id: 1,
+            title: "Test",
+        },
+    ]);
+    let render = |view: View| view.render_to_string().to_string();
+    let keyed = render(
+        view! {
+            <ul>
+                {move for todo in todos.get() {
+                    key = todo.id;
+                    view! { <li>{todo.title}</li> }
+                }}
+            </ul>
+        }
+        .into_view(),
+    );
+    let unkeyed = render(
+        view! {
+            <ul>
+                {move for todo in todos.get() {
+                    view! { <li>{todo.title}</li> }
+                }}
+            </ul>
+        }
+        .into_view(),
+    );
+
+    for rendered in [&keyed, &unkeyed] {
+        assert!(rendered.contains(">Write</li>"));
+        assert!(rendered.contains(">Test</li>"));
+    }
+    // only the keyed loop is rendered by `<For/>`, which marks its items
+    if cfg!(debug_assertions) {
+        assert!(keyed.contains("leptos-each-start"));
+        assert!(!unkeyed.contains("leptos-each-start"));
+    }
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_spread_props() {
+    use leptos::*;
+
+    #[component]
+    fn UserCard(
+        #[prop(into)] name: String,
+        #[prop(optional)] role: Option<&'static str>,
+        #[prop(default = false)] admin: bool,
+    ) -> impl IntoView {
+        view! {
+            <p>{name}" "{role.unwrap_or("guest")}" "{admin}</p>
+        }
+    }
+
+    #[component]
+    fn Wrapper(props: UserCardProps) -> impl IntoView {
+        view! { <UserCard ..props admin=true/> }
+    }
+
+    let runtime = create_runtime();
+
+    let props = UserCardProps::builder().name("Ann").role("editor").build();
+    let rendered = view! { <UserCard ..props name="Bob"/> }
+        .into_view()
+        .render_to_string();
+    assert!(rendered.contains(">Bob editor false</p>"));
+
+    let props = UserCardProps::builder().name("Ann").build();
+    let rendered = view! { <Wrapper props/> }.into_view().render_to_string();
+    assert!(rendered.contains(">Ann guest true</p>"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_dynamic_tag() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+
+    let tag = "h2";
+    let rendered = view! {
+        <div>
+            <{tag} class="title">"Hello"</{tag}>
+        </div>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains("<h2"));
+    assert!(rendered.contains("class=\"title\""));
+    assert!(rendered.contains(">Hello</h2>"));
+
+    let tag = String::from("br");
+    let rendered = view! { <{tag}/> }.into_view().render_to_string();
+    assert!(rendered.contains("<br") && !rendered.contains("</br>"));
+
+    let tag = "script><p";
+    let rendered = view! { <{tag}>"x"</{tag}> }.into_view().render_to_string();
+    assert!(rendered.contains("<div") && !rendered.contains("<script"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_class_and_style_objects() {
+    use leptos::*;
+    use std::collections::BTreeMap;
+
+    let runtime = create_runtime();
+
+    let (is_active, _) = create_signal(true);
+    let rendered = view! {
+        <div
+            class="card"
+            class:{ active: is_active.get(), "text-lg": true, hidden: false }
+            style:{ color: "red", "font-size": format!("{}px", 12) }
+        ></div>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains("class=\"card active text-lg"));
+    assert!(!rendered.contains("hidden"));
+    assert!(rendered.contains("color: red;"));
+    assert!(rendered.contains("font-size: 12px;"));
+
+    let rendered = view! {
+        <p class=(move || {
+            BTreeMap::from([("active", is_active.get()), ("disabled", false)])
+        })></p>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains("active\"") && !rendered.contains("disabled"));
+
+    runtime.dispose();
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_static_template() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+
+    let rendered = view! {
+        <main>
+            {template! {
+                <section class="hero">
+                    <h1>"Fast & " "static"</h1>
+                    <svg viewBox="0 0 10 10"><circle cx=5 cy=5 r=4/></svg>
+                    <br/>
+                </section>
+            }}
+        </main>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains("<section data-hk="));
+    assert!(rendered.contains(
+        " class=\"hero\"><h1>Fast &amp; static</h1><svg viewBox=\"0 0 10 \
+         10\"><circle cx=\"5\" cy=\"5\" r=\"4\"/></svg><br></section>"
+    ));
+
+    runtime.dispose();
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_async_component() {
+    use leptos::*;
+
+    #[component]
+    async fn Greeting(name: &'static str) -> impl IntoView {
+        let name = async { name }.await;
+        view! { <p>"Hello, " {name} "!"</p> }
+    }
+
+    let app = || {
+        view! {
+            <main>
+                <Greeting name="Leptos" fallback=|| view! { <p>"Loading..."</p> }/>
+            </main>
+        }
+        .into_view()
+    };
+
+    // the body only runs in the browser, so the server renders the fallback,
+    // which is what the browser renders first when it hydrates the page
+    let runtime = create_runtime();
+    let rendered = app().render_to_string().to_string();
+    runtime.dispose();
+    let local = tokio::task::LocalSet::new();
+    let rendered_async = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(local.run_until(ssr::render_to_string_async(app)));
+
+    for rendered in [rendered, rendered_async] {
+        assert!(rendered.contains(">Loading...</p>"));
+        assert!(!rendered.contains("Hello"));
+    }
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_two_way_bindings() {
+    use leptos::*;
+
+    let runtime = create_runtime();
+
+    let name = create_rw_signal(String::from("\"Leptos\""));
+    let agreed = create_rw_signal(true);
+    let choice = create_rw_signal(String::from("b"));
+
+    let rendered = view! {
+        <form>
+            <input type="text" bind:value=name/>
+            <input type="checkbox" bind:checked=agreed/>
+            <input type="radio" value="a" bind:group=choice/>
+            <input type="radio" value="b" bind:group=choice/>
+        </form>
+    }
+    .into_view()
+    .render_to_string();
+    assert!(rendered.contains(" type=\"text\" value=\"&quot;Leptos&quot;\""));
+    assert!(rendered.contains(" type=\"checkbox\" checked"));
+    assert!(rendered.contains(" value=\"a\" data-hk"));
+    assert!(rendered.contains(" value=\"b\" checked"));
+
+    runtime.dispose();
+}
@@ -36,6 +36,31 @@ pub struct LeptosOptions {
     #[builder(setter(into), default=default_site_pkg_dir())]
     #[serde(default = "default_site_pkg_dir")]
     pub site_pkg_dir: String,
+    /// The path under which the app is served, like `/app`, when it is not mounted at the root
+    /// of its domain, e.g., behind a reverse proxy. Defaults to `""`.
+    /// The server integrations prefix the paths of the WASM and JS files with it, and add it to
+    /// the path of incoming requests that do not already include it, so it should match the
+    /// `base` of the app's `<Router/>`.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub site_base_path: String,
+    /// The URL from which the WASM, JS, and CSS files in the `site_pkg_dir` are served, when they
+    /// are not served by the app itself, like `https://cdn.example.com/my-app`. Defaults to
+    /// `""`. The server integrations load them from `{asset_prefix}/{site_pkg_dir}` instead of
+    /// from the `site_base_path`, so that the emitted HTML does not need to be rewritten when
+    /// the files are uploaded to a CDN, along with the hashes in their names if `hash_files`
+    /// is set.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub asset_prefix: String,
+    /// The prefix of the paths of this app's server functions, like `/shop/api`, when several
+    /// Leptos apps are served by the same server. Defaults to `""`, which includes every
+    /// server function. The server integrations only register and run the server functions
+    /// whose paths begin with it, so that each app handles its own, which should be given
+    /// this prefix in the `#[server]` macro.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub server_fn_prefix: String,
     /// Used to configure the running environment of Leptos. Can be used to load dev constants and keys v prod, or change
     /// things based on the deployment environment
     /// I recommend passing in the result of `env::var("LEPTOS_ENV")`
@@ -76,6 +101,28 @@ pub struct LeptosOptions {
     #[builder(default = default_hash_files())]
     #[serde(default = "default_hash_files")]
     pub hash_files: bool,
+    /// The most bytes of streamed HTML that are held back while waiting for more `<Suspense/>`
+    /// fragments to resolve (see [`stream_coalesce_ms`](Self::stream_coalesce_ms)) before they
+    /// are flushed to the client. Defaults to `16384`.
+    #[builder(default = default_stream_max_buffer())]
+    #[serde(default = "default_stream_max_buffer")]
+    pub stream_max_buffer: usize,
+    /// How long, in milliseconds, the server integrations wait for more `<Suspense/>` fragments
+    /// to resolve after one has, when streaming HTML, so that fragments that resolve close
+    /// together are sent in a single chunk rather than in many small ones.
+    /// The first chunk, with the app shell, is always sent right away. Defaults to `0`, which
+    /// sends each fragment as soon as it resolves.
+    #[builder(default)]
+    #[serde(default)]
+    pub stream_coalesce_ms: u64,
+    /// How long, in milliseconds, the server integrations wait for `<Suspense/>` fragments to
+    /// resolve when streaming HTML out of order, after the shell has been rendered. Once it has
+    /// passed, the fallbacks of the fragments that have not resolved are sent, and they are
+    /// loaded in the browser instead. It can be changed for a page with
+    /// `leptos::deadline::set_render_timeout`. Defaults to `0`, which waits for every fragment.
+    #[builder(default)]
+    #[serde(default)]
+    pub render_timeout_ms: u64,
 }
 
 impl LeptosOptions {
@@ -99,6 +146,9 @@ impl LeptosOptions {
             output_name,
             site_root: env_w_default("LEPTOS_SITE_ROOT", "target/site")?,
             site_pkg_dir: env_w_default("LEPTOS_SITE_PKG_DIR", "pkg")?,
+            site_base_path: env_w_default("LEPTOS_SITE_BASE_PATH", "")?,
+            asset_prefix: env_w_default("LEPTOS_ASSET_PREFIX", "")?,
+            server_fn_prefix: env_w_default("LEPTOS_SERVER_FN_PREFIX", "")?,
             env: env_from_str(env_w_default("LEPTOS_ENV", "DEV")?.as_str())?,
             site_addr: env_w_default("LEPTOS_SITE_ADDR", "127.0.0.1:3000")?
                 .parse()?,
@@ -116,6 +166,18 @@ impl LeptosOptions {
             not_found_path: env_w_default("LEPTOS_NOT_FOUND_PATH", "/404")?,
             hash_file: env_w_default("LEPTOS_HASH_FILE_NAME", "hash.txt")?,
             hash_files: env_w_default("LEPTOS_HASH_FILES", "false")?.parse()?,
+            stream_max_buffer: env_w_default(
+                "LEPTOS_STREAM_MAX_BUFFER",
+                "16384",
+            )?
+            .parse()?,
+            stream_coalesce_ms: env_w_default(
+                "LEPTOS_STREAM_COALESCE_MS",
+                "0",
+            )?
+            .parse()?,
+            render_timeout_ms: env_w_default("LEPTOS_RENDER_TIMEOUT_MS", "0")?
+                .parse()?,
         })
     }
 }
@@ -162,6 +224,10 @@ fn default_hash_files() -> bool {
     false
 }
 
+fn default_stream_max_buffer() -> usize {
+    16384
+}
+
 fn env_wo_default(key: &str) -> Result<Option<String>, LeptosConfigError> {
     match std::env::var(key) {
         Ok(val) => Ok(Some(val)),
@@ -183,6 +183,8 @@ async fn get_config_from_env() {
             ("LEPTOS_SITE_ADDR", Some("0.0.0.0:80")),
             ("LEPTOS_RELOAD_PORT", Some("8080")),
             ("LEPTOS_RELOAD_EXTERNAL_PORT", Some("8080")),
+            ("LEPTOS_ASSET_PREFIX", Some("https://cdn.example.com")),
+            ("LEPTOS_SERVER_FN_PREFIX", Some("/shop/api")),
         ],
         async { get_configuration(None).await.unwrap().leptos_options },
     )
@@ -191,6 +193,8 @@ async fn get_config_from_env() {
     assert_eq!(config.output_name, "app-test");
     assert_eq!(config.site_root, "my_target/site");
     assert_eq!(config.site_pkg_dir, "my_pkg");
+    assert_eq!(config.asset_prefix, "https://cdn.example.com");
+    assert_eq!(config.server_fn_prefix, "/shop/api");
     assert_eq!(
         config.site_addr,
         SocketAddr::from_str("0.0.0.0:80").unwrap()
@@ -228,6 +232,9 @@ fn leptos_options_builder_default() {
     assert_eq!(conf.output_name, "app-test");
     assert!(matches!(conf.env, Env::DEV));
     assert_eq!(conf.site_pkg_dir, "pkg");
+    assert_eq!(conf.site_base_path, "");
+    assert_eq!(conf.asset_prefix, "");
+    assert_eq!(conf.server_fn_prefix, "");
     assert_eq!(conf.site_root, ".");
     assert_eq!(
         conf.site_addr,
@@ -235,6 +242,9 @@ fn leptos_options_builder_default() {
     );
     assert_eq!(conf.reload_port, 3001);
     assert_eq!(conf.reload_external_port, None);
+    assert_eq!(conf.stream_max_buffer, 16384);
+    assert_eq!(conf.stream_coalesce_ms, 0);
+    assert_eq!(conf.render_timeout_ms, 0);
 }
 
 #[test]
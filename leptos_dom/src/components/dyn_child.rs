@@ -23,6 +23,12 @@ pub struct DynChildRepr {
     closing: Comment,
     #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
     pub(crate) id: Option<HydrationKey>,
+    /// Re-renders the child when it is read after its signals have changed.
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    pub(crate) refresh: Option<leptos_reactive::Memo<()>>,
 }
 
 impl fmt::Debug for DynChildRepr {
@@ -116,6 +122,11 @@ impl DynChildRepr {
             closing: markers.0,
             #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
             id,
+            #[cfg(all(
+                feature = "testing",
+                not(all(target_arch = "wasm32", feature = "web"))
+            ))]
+            refresh: None,
         }
     }
 }
@@ -380,13 +391,37 @@ where
                 },
             );
 
-            #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+            #[cfg(not(any(
+                feature = "testing",
+                all(target_arch = "wasm32", feature = "web")
+            )))]
             {
                 let new_child = child_fn().into_view();
 
                 **child.borrow_mut() = Some(new_child);
             }
 
+            // the headless testing renderer keeps the child up to date
+            #[cfg(all(
+                feature = "testing",
+                not(all(target_arch = "wasm32", feature = "web"))
+            ))]
+            let component = {
+                use leptos_reactive::SignalWithUntracked;
+
+                let refresh = leptos_reactive::create_memo(move |_| {
+                    let new_child = child_fn().into_view();
+
+                    **child.borrow_mut() = Some(new_child);
+                });
+                refresh.with_untracked(|_| {});
+
+                DynChildRepr {
+                    refresh: Some(refresh),
+                    ..component
+                }
+            };
+
             component
         }
 
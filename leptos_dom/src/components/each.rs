@@ -56,6 +56,12 @@ pub struct EachRepr {
     closing: Comment,
     #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
     pub(crate) id: Option<HydrationKey>,
+    /// Re-renders the list when it is read after its signals have changed.
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    pub(crate) refresh: Option<leptos_reactive::Memo<()>>,
 }
 
 impl fmt::Debug for EachRepr {
@@ -117,6 +123,11 @@ impl Default for EachRepr {
             closing: markers.0,
             #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
             id,
+            #[cfg(all(
+                feature = "testing",
+                not(all(target_arch = "wasm32", feature = "web"))
+            ))]
+            refresh: None,
         }
     }
 }
@@ -391,7 +402,10 @@ where
             key_fn,
         } = self;
 
-        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        #[cfg(not(any(
+            feature = "testing",
+            all(target_arch = "wasm32", feature = "web")
+        )))]
         let _ = key_fn;
 
         let component = EachRepr::default();
@@ -501,7 +515,10 @@ where
             },
         );
 
-        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        #[cfg(not(any(
+            feature = "testing",
+            all(target_arch = "wasm32", feature = "web")
+        )))]
         {
             *component.children.borrow_mut() = (items_fn)()
                 .into_iter()
@@ -512,6 +529,61 @@ where
                 .collect();
         }
 
+        // the headless testing renderer keeps the rows up to date, reusing
+        // the rows whose keys were already rendered
+        #[cfg(all(
+            feature = "testing",
+            not(all(target_arch = "wasm32", feature = "web"))
+        ))]
+        let component = {
+            use leptos_reactive::{
+                create_memo, with_owner, Owner, SignalWithUntracked,
+            };
+            use std::collections::HashMap;
+
+            let owner = Owner::current();
+            let children = component.children.clone();
+            let keys = RefCell::new(Vec::<K>::new());
+            let refresh = create_memo(move |_| {
+                let mut prev = keys
+                    .take()
+                    .into_iter()
+                    .zip(children.take())
+                    .filter_map(|(key, item)| Some((key, item?)))
+                    .collect::<HashMap<_, _>>();
+                let mut new_keys = Vec::new();
+                let new_children = items_fn()
+                    .into_iter()
+                    .map(|item| {
+                        let key = key_fn(&item);
+                        let each_item =
+                            prev.remove(&key).unwrap_or_else(|| {
+                                // rows are owned by the `Each`, not by this memo,
+                                // so they are not disposed when it runs again
+                                let new_item = || {
+                                    let (child, disposer) = each_fn(item);
+                                    EachItem::new(disposer, child.into_view())
+                                };
+                                match owner {
+                                    Some(owner) => with_owner(owner, new_item),
+                                    None => new_item(),
+                                }
+                            });
+                        new_keys.push(key);
+                        Some(each_item)
+                    })
+                    .collect();
+                *children.borrow_mut() = new_children;
+                *keys.borrow_mut() = new_keys;
+            });
+            refresh.with_untracked(|_| {});
+
+            EachRepr {
+                refresh: Some(refresh),
+                ..component
+            }
+        };
+
         View::CoreComponent(CoreComponent::Each(component))
     }
 }
@@ -0,0 +1,99 @@
+//! A deadline for rendering a page on the server, so that one slow resource
+//! does not hold the whole response back.
+//!
+//! When the page is streamed out of order, the server waits for the
+//! `<Suspense/>` fragments that block the shell (those with blocking resources,
+//! or that are streamed `in_order`) before sending it, and keeps the response
+//! open until every other fragment has resolved. Once the deadline passes, it
+//! stops waiting: the fallback of each fragment that has not resolved is sent
+//! in its place, and its resources are loaded in the browser instead, as if
+//! they had been created with `create_local_resource`. The resources that had
+//! not resolved are logged as a warning.
+//!
+//! The server integrations set the default deadline of every page from the
+//! `render_timeout_ms` of the `LeptosOptions`. A component can set a
+//! different one for the page it is rendered in, like a route that calls a
+//! slow upstream service:
+//!
+//! ```rust,ignore
+//! #[component]
+//! pub fn Dashboard() -> impl IntoView {
+//!     set_render_timeout(Duration::from_millis(500));
+//!     let stats = create_resource(|| (), |_| fetch_stats());
+//!     view! {
+//!         <Suspense fallback=|| "Loading…">
+//!             {move || stats.get().map(|stats| view! { <Stats stats/> })}
+//!         </Suspense>
+//!     }
+//! }
+//! ```
+//!
+//! The deadline does not apply to pages that are rendered in order or
+//! asynchronously, nor to pages that are not hydrated, which are always
+//! rendered in full.
+
+use leptos_reactive::{provide_context, use_context};
+use std::{cell::Cell, fmt, future::Future, pin::Pin, rc::Rc, time::Duration};
+
+type Sleep = Rc<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()>>>>;
+
+/// The deadline for rendering the current page on the server, provided by
+/// the server integration. See the [module docs](self).
+#[derive(Clone)]
+pub struct RenderDeadline {
+    timeout: Rc<Cell<Option<Duration>>>,
+    sleep: Sleep,
+}
+
+impl fmt::Debug for RenderDeadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderDeadline")
+            .field("timeout", &self.timeout.get())
+            .finish()
+    }
+}
+
+impl RenderDeadline {
+    /// How long the page may take to render, if it has a deadline.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.get()
+    }
+
+    // the future that completes once the deadline has passed, starting now
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    pub(crate) fn start(&self) -> Option<Pin<Box<dyn Future<Output = ()>>>> {
+        self.timeout.get().map(|timeout| (self.sleep)(timeout))
+    }
+}
+
+/// Sets the deadline for rendering the current page, which is `timeout`
+/// after the shell has been rendered. A timeout of zero renders the page in
+/// full, however long it takes.
+///
+/// `sleep` returns a future that completes after the given duration, using
+/// the timer of the server's runtime. This is provided by the server
+/// integrations, and can be changed for a page with [`set_render_timeout`].
+pub fn provide_render_deadline<F>(
+    timeout: Duration,
+    sleep: impl Fn(Duration) -> F + 'static,
+) where
+    F: Future<Output = ()> + 'static,
+{
+    provide_context(RenderDeadline {
+        timeout: Rc::new(Cell::new(Some(timeout).filter(|t| !t.is_zero()))),
+        sleep: Rc::new(move |duration| Box::pin(sleep(duration))),
+    });
+}
+
+/// Sets how long the current page may take to render on the server before
+/// the `<Suspense/>` fragments that have not resolved are loaded in the
+/// browser instead, in place of the default of the server integration. A
+/// timeout of zero renders the page in full. See the [module docs](self).
+///
+/// This should be called while rendering the shell, like in the component of
+/// a route. Does nothing in the browser.
+pub fn set_render_timeout(timeout: Duration) {
+    if let Some(deadline) = use_context::<RenderDeadline>() {
+        deadline.timeout.set(Some(timeout).filter(|t| !t.is_zero()));
+    }
+}
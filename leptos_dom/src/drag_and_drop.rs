@@ -0,0 +1,405 @@
+//! Reactive drag-and-drop, built on top of [directives](crate::Directive).
+//!
+//! [`use_draggable`] and [`use_drop_zone`] each return some reactive state
+//! and a directive that can be attached to an element with `use:`. The value
+//! being dragged is an ordinary Rust value: a drop zone created with
+//! `use_drop_zone::<T>` only reacts to draggables created with
+//! `use_draggable::<T>`, and receives the dragged `T` directly.
+//!
+//! Both directives also support the keyboard. A focused draggable is picked
+//! up with <kbd>Enter</kbd> or <kbd>Space</kbd>, then dropped by pressing
+//! <kbd>Enter</kbd> or <kbd>Space</kbd> on a focused drop zone. Pressing
+//! <kbd>Escape</kbd> cancels the drag.
+//!
+//! ```
+//! use leptos::{
+//!     leptos_dom::drag_and_drop::{use_draggable, use_drop_zone},
+//!     *,
+//! };
+//!
+//! #[derive(Clone)]
+//! struct Card {
+//!     id: usize,
+//! }
+//!
+//! #[component]
+//! fn DraggableCard(card: Card) -> impl IntoView {
+//!     let (state, drag) = use_draggable::<Card>();
+//!     let id = card.id;
+//!
+//!     view! {
+//!       <li use:drag=card class:dragging=move || state.is_dragging.get()>
+//!         {id}
+//!       </li>
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Column() -> impl IntoView {
+//!     let (cards, set_cards) = create_signal(Vec::<Card>::new());
+//!     let (zone, drop) =
+//!         use_drop_zone(move |card: Card| set_cards.update(|c| c.push(card)));
+//!
+//!     view! {
+//!       <ul use:drop class:over=move || zone.is_over.get()>
+//!         <For
+//!           each=move || cards.get()
+//!           key=|card| card.id
+//!           children=|card| view! { <DraggableCard card/> }
+//!         />
+//!       </ul>
+//!     }
+//! }
+//! ```
+
+use crate::{ev, html::AnyElement, HtmlElement};
+use leptos_reactive::{
+    create_signal, store_value, ReadSignal, SignalGet, SignalSet, StoredValue,
+};
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+/// The reactive state of a draggable element, as returned by
+/// [`use_draggable`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DragState {
+    /// Whether the element is currently being dragged, either with the
+    /// pointer or with the keyboard.
+    pub is_dragging: ReadSignal<bool>,
+    /// The most recent pointer position during a drag, in client
+    /// coordinates. This is `None` before the first drag, and while dragging
+    /// with the keyboard.
+    pub position: ReadSignal<Option<(f64, f64)>>,
+}
+
+/// The reactive state of a drop zone, as returned by [`use_drop_zone`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DropZoneState {
+    /// Whether a compatible payload is currently being dragged over the
+    /// drop zone.
+    pub is_over: ReadSignal<bool>,
+    /// Whether a compatible payload is being dragged anywhere on the page.
+    /// This is only updated for drags that start after the drop zone is
+    /// created.
+    pub can_drop: ReadSignal<bool>,
+}
+
+type DragListener = Rc<dyn Fn(bool)>;
+
+struct ActiveDrag {
+    payload: Box<dyn Any>,
+    finish: Rc<dyn Fn()>,
+}
+
+thread_local! {
+    static ACTIVE_DRAG: RefCell<Option<ActiveDrag>> = const { RefCell::new(None) };
+    static DRAG_LISTENERS: RefCell<Vec<DragListener>> = const { RefCell::new(Vec::new()) };
+}
+
+fn start_drag<T: 'static>(payload: T, finish: Rc<dyn Fn()>) {
+    cancel_drag();
+    ACTIVE_DRAG.with(|active| {
+        *active.borrow_mut() = Some(ActiveDrag {
+            payload: Box::new(payload),
+            finish,
+        })
+    });
+    notify_listeners(true);
+}
+
+fn is_dragging_type<T: 'static>() -> bool {
+    ACTIVE_DRAG.with(|active| {
+        active
+            .borrow()
+            .as_ref()
+            .map(|drag| drag.payload.is::<T>())
+            .unwrap_or(false)
+    })
+}
+
+fn take_payload<T: 'static>() -> Option<T> {
+    let drag = ACTIVE_DRAG.with(|active| {
+        let mut active = active.borrow_mut();
+        if active.as_ref()?.payload.is::<T>() {
+            active.take()
+        } else {
+            None
+        }
+    })?;
+    (drag.finish)();
+    notify_listeners(false);
+    drag.payload.downcast::<T>().ok().map(|payload| *payload)
+}
+
+fn cancel_drag() {
+    if let Some(drag) = ACTIVE_DRAG.with(|active| active.borrow_mut().take()) {
+        (drag.finish)();
+        notify_listeners(false);
+    }
+}
+
+fn notify_listeners(dragging: bool) {
+    let listeners = DRAG_LISTENERS.with(|l| l.borrow().clone());
+    for listener in listeners {
+        listener(dragging);
+    }
+}
+
+fn is_activation_key(key: &str) -> bool {
+    matches!(key, "Enter" | " ")
+}
+
+fn drop_effect(can_drop: bool) -> &'static str {
+    if can_drop {
+        "move"
+    } else {
+        "none"
+    }
+}
+
+/// Creates a draggable directive that carries a payload of type `T`.
+///
+/// Attach the returned directive with `use:`, passing the payload as its
+/// value, e.g., `<li use:drag=item.clone()>`. The element is made draggable
+/// and focusable, and its `aria-grabbed` attribute reflects the drag state.
+///
+/// The returned [`DragState`] is shared by every element the directive is
+/// attached to, so in a list, create one draggable per item (for example, in
+/// the component that renders a single row).
+pub fn use_draggable<T>() -> (
+    DragState,
+    impl Fn(HtmlElement<AnyElement>, T) + Copy + 'static,
+)
+where
+    T: Clone + 'static,
+{
+    let (is_dragging, set_is_dragging) = create_signal(false);
+    let (position, set_position) = create_signal(None);
+
+    let directive = move |el: HtmlElement<AnyElement>, payload: T| {
+        let finish: Rc<dyn Fn()> = Rc::new(move || set_is_dragging.set(false));
+        let payload = store_value(payload);
+
+        _ = el
+            .attr("draggable", "true")
+            .attr("tabindex", "0")
+            .attr("aria-grabbed", move || is_dragging.get().to_string())
+            .on(ev::dragstart, {
+                let finish = Rc::clone(&finish);
+                move |ev: web_sys::DragEvent| {
+                    if let Some(data) = ev.data_transfer() {
+                        // some browsers won't start a drag without data
+                        _ = data.set_data("text/plain", "");
+                        data.set_effect_allowed("move");
+                    }
+                    start_drag(payload.get_value(), Rc::clone(&finish));
+                    set_is_dragging.set(true);
+                    set_position.set(Some((
+                        ev.client_x() as f64,
+                        ev.client_y() as f64,
+                    )));
+                }
+            })
+            .on(ev::drag, move |ev: web_sys::DragEvent| {
+                // the final `drag` event often reports (0, 0)
+                if ev.client_x() != 0 || ev.client_y() != 0 {
+                    set_position.set(Some((
+                        ev.client_x() as f64,
+                        ev.client_y() as f64,
+                    )));
+                }
+            })
+            .on(ev::dragend, move |_| {
+                // if the drop was handled, the drag has already finished
+                cancel_drag();
+                set_is_dragging.set(false);
+            })
+            .on(ev::keydown, move |ev: web_sys::KeyboardEvent| {
+                let key = ev.key();
+                if is_activation_key(&key) {
+                    ev.prevent_default();
+                    // a drop zone that contains the draggable would otherwise
+                    // drop the payload as soon as it is picked up
+                    ev.stop_propagation();
+                    start_drag(payload.get_value(), Rc::clone(&finish));
+                    set_is_dragging.set(true);
+                } else if key == "Escape" {
+                    cancel_drag();
+                }
+            });
+    };
+
+    (
+        DragState {
+            is_dragging,
+            position,
+        },
+        directive,
+    )
+}
+
+/// Creates a drop zone directive that accepts payloads of type `T`.
+///
+/// Attach the returned directive to an element with `use:`. When a
+/// draggable created with [`use_draggable::<T>`](use_draggable) is dropped
+/// onto it, either with the pointer or with the keyboard, `on_drop` is
+/// called with the dragged payload. Drags of any other payload type are
+/// ignored.
+pub fn use_drop_zone<T>(
+    on_drop: impl Fn(T) + 'static,
+) -> (
+    DropZoneState,
+    impl Fn(HtmlElement<AnyElement>) + Copy + 'static,
+)
+where
+    T: 'static,
+{
+    let (is_over, set_is_over) = create_signal(false);
+    let (can_drop, set_can_drop) = create_signal(false);
+    let on_drop: StoredValue<Rc<dyn Fn(T)>> = store_value(Rc::new(on_drop));
+
+    let listener: DragListener = Rc::new(move |dragging| {
+        let compatible = dragging && is_dragging_type::<T>();
+        set_can_drop.set(compatible);
+        if !compatible {
+            set_is_over.set(false);
+        }
+    });
+    DRAG_LISTENERS.with(|l| l.borrow_mut().push(Rc::clone(&listener)));
+    leptos_reactive::on_cleanup(move || {
+        DRAG_LISTENERS
+            .with(|l| l.borrow_mut().retain(|l| !Rc::ptr_eq(l, &listener)))
+    });
+
+    let drop_payload = move || {
+        set_is_over.set(false);
+        if let Some(payload) = take_payload::<T>() {
+            on_drop.with_value(|on_drop| on_drop(payload));
+        }
+    };
+
+    let directive = move |el: HtmlElement<AnyElement>| {
+        _ = el
+            .attr("tabindex", "0")
+            .attr("aria-dropeffect", move || drop_effect(can_drop.get()))
+            .on(ev::dragenter, move |ev: web_sys::DragEvent| {
+                if is_dragging_type::<T>() {
+                    ev.prevent_default();
+                    set_is_over.set(true);
+                }
+            })
+            .on(ev::dragover, move |ev: web_sys::DragEvent| {
+                if is_dragging_type::<T>() {
+                    // allows the drop
+                    ev.prevent_default();
+                    if let Some(data) = ev.data_transfer() {
+                        data.set_drop_effect("move");
+                    }
+                }
+            })
+            .on(ev::dragleave, move |_| set_is_over.set(false))
+            .on(ev::drop, move |ev: web_sys::DragEvent| {
+                if is_dragging_type::<T>() {
+                    ev.prevent_default();
+                    drop_payload();
+                }
+            })
+            .on(ev::focus, move |_| {
+                if is_dragging_type::<T>() {
+                    set_is_over.set(true);
+                }
+            })
+            .on(ev::blur, move |_| set_is_over.set(false))
+            .on(ev::keydown, move |ev: web_sys::KeyboardEvent| {
+                let key = ev.key();
+                if is_activation_key(&key) && is_dragging_type::<T>() {
+                    ev.prevent_default();
+                    drop_payload();
+                } else if key == "Escape" {
+                    set_is_over.set(false);
+                    cancel_drag();
+                }
+            });
+    };
+
+    (DropZoneState { is_over, can_drop }, directive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos_reactive::{create_runtime, SignalGetUntracked};
+    use std::cell::Cell;
+
+    fn counter() -> (Rc<Cell<usize>>, Rc<dyn Fn()>) {
+        let count = Rc::new(Cell::new(0));
+        let finish: Rc<dyn Fn()> = Rc::new({
+            let count = Rc::clone(&count);
+            move || count.set(count.get() + 1)
+        });
+        (count, finish)
+    }
+
+    #[test]
+    fn payload_is_taken_as_its_own_type() {
+        let (finished, finish) = counter();
+
+        start_drag(String::from("card"), finish);
+        assert!(is_dragging_type::<String>());
+        assert!(!is_dragging_type::<u32>());
+
+        // other types leave the drag in place
+        assert_eq!(take_payload::<u32>(), None);
+        assert_eq!(finished.get(), 0);
+
+        assert_eq!(take_payload::<String>().as_deref(), Some("card"));
+        assert_eq!(finished.get(), 1);
+        assert!(!is_dragging_type::<String>());
+        assert_eq!(take_payload::<String>(), None);
+    }
+
+    #[test]
+    fn starting_a_drag_cancels_the_previous_one() {
+        let (first, finish_first) = counter();
+        let (second, finish_second) = counter();
+
+        start_drag(1_u32, finish_first);
+        start_drag(2_u32, finish_second);
+        assert_eq!(first.get(), 1);
+        assert_eq!(second.get(), 0);
+        assert_eq!(take_payload::<u32>(), Some(2));
+
+        start_drag(3_u32, Rc::new(|| {}));
+        cancel_drag();
+        assert!(!is_dragging_type::<u32>());
+        assert_eq!(take_payload::<u32>(), None);
+    }
+
+    #[test]
+    fn keys_and_drop_effects() {
+        assert!(is_activation_key("Enter"));
+        assert!(is_activation_key(" "));
+        assert!(!is_activation_key("Escape"));
+        assert!(!is_activation_key("a"));
+
+        assert_eq!(drop_effect(true), "move");
+        assert_eq!(drop_effect(false), "none");
+    }
+
+    #[test]
+    fn drop_zones_only_accept_their_own_type() {
+        let runtime = create_runtime();
+        let (numbers, _) = use_drop_zone(|_: u32| {});
+        let (strings, _) = use_drop_zone(|_: String| {});
+
+        start_drag(1_u32, Rc::new(|| {}));
+        assert!(numbers.can_drop.get_untracked());
+        assert!(!strings.can_drop.get_untracked());
+
+        cancel_drag();
+        assert!(!numbers.can_drop.get_untracked());
+        assert!(!strings.can_drop.get_untracked());
+
+        runtime.dispose();
+        DRAG_LISTENERS.with(|l| l.borrow_mut().clear());
+    }
+}
@@ -0,0 +1,174 @@
+//! The pages that the server integrations render in place of the app, when
+//! the URL does not match any of its routes or it renders errors that are not
+//! caught by any `<ErrorBoundary/>`.
+//!
+//! The app sets them while it is rendered on the server with
+//! [`set_error_pages`], usually in its root component. Each is rendered as a
+//! whole page, with its own `<head>` metadata, and sent with the status code
+//! for it. These pages are not hydrated, as they are not the app's views.
+//!
+//! ```rust,ignore
+//! #[component]
+//! pub fn App() -> impl IntoView {
+//!     set_error_pages(
+//!         ErrorPages::new()
+//!             .not_found(|| view! { <Title text="Not Found"/><h1>"Not Found"</h1> })
+//!             .error(|errors: Errors| view! {
+//!                 <h1>"Something went wrong"</h1>
+//!                 <p>{errors.into_iter().count()} " errors"</p>
+//!             }),
+//!     );
+//!     view! { <Router>/* ... */</Router> }
+//! }
+//! ```
+//!
+//! Whether the page should be replaced is checked once the first chunk of the
+//! response, with the shell of the app, has been rendered, and before it is
+//! sent. The response can no longer be changed once it has begun, so errors
+//! rendered by `<Suspense/>` fragments streamed after the shell are only
+//! logged: routes that need them to be handled in this way should be rendered
+//! with `SsrMode::Async`, or catch them with an `<ErrorBoundary/>`.
+
+use crate::{Errors, IntoView, View};
+use leptos_reactive::{
+    create_rw_signal, provide_context, use_context, RwSignal, SignalSet,
+    SignalWithUntracked,
+};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+/// The pages rendered in place of the app when it cannot render the requested
+/// page. See the [module docs](self).
+#[derive(Clone, Default)]
+pub struct ErrorPages {
+    not_found: Option<Rc<dyn Fn() -> View>>,
+    error: Option<Rc<dyn Fn(Errors) -> View>>,
+}
+
+impl fmt::Debug for ErrorPages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorPages")
+            .field("not_found", &self.not_found.is_some())
+            .field("error", &self.error.is_some())
+            .finish()
+    }
+}
+
+impl ErrorPages {
+    /// Creates a set of error pages, none of which are rendered until they
+    /// are set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the page rendered, with a `404` status, when the URL does not
+    /// match any of the app's routes.
+    pub fn not_found<IV: IntoView>(
+        mut self,
+        view: impl Fn() -> IV + 'static,
+    ) -> Self {
+        self.not_found = Some(Rc::new(move || view().into_view()));
+        self
+    }
+
+    /// Sets the page rendered, with a `500` status, when the app renders
+    /// errors that are not caught by any `<ErrorBoundary/>`.
+    pub fn error<IV: IntoView>(
+        mut self,
+        view: impl Fn(Errors) -> IV + 'static,
+    ) -> Self {
+        self.error = Some(Rc::new(move |errors| view(errors).into_view()));
+        self
+    }
+}
+
+#[derive(Clone)]
+struct ErrorPagesState {
+    pages: Rc<RefCell<Option<ErrorPages>>>,
+    not_found: Rc<Cell<bool>>,
+    // the errors that are not caught by any `<ErrorBoundary/>`, which are
+    // rendered into the nearest `RwSignal<Errors>`
+    errors: RwSignal<Errors>,
+    set_status: Rc<dyn Fn(u16)>,
+}
+
+/// Provides the state in which the error pages of the current request are
+/// set, along with the function that sets the status code of its response.
+///
+/// This is called by the server integrations before the app is rendered.
+pub fn provide_error_pages_state(set_status: impl Fn(u16) + 'static) {
+    let errors = create_rw_signal(Errors::default());
+    provide_context(errors);
+    provide_context(ErrorPagesState {
+        pages: Default::default(),
+        not_found: Default::default(),
+        errors,
+        set_status: Rc::new(set_status),
+    });
+}
+
+/// Sets the pages that the server integration renders in place of the app
+/// when it cannot render the requested page. See the [module docs](self).
+///
+/// Does nothing in the browser, or when not rendered by a server integration.
+pub fn set_error_pages(pages: ErrorPages) {
+    if let Some(state) = use_context::<ErrorPagesState>() {
+        *state.pages.borrow_mut() = Some(pages);
+    }
+}
+
+/// Marks the requested page as not found, which is rendered as the
+/// [`not_found`](ErrorPages::not_found) page, if there is one.
+///
+/// This is called by the router when the URL does not match any route.
+pub fn set_not_found() {
+    if let Some(state) = use_context::<ErrorPagesState>() {
+        state.not_found.set(true);
+    }
+}
+
+/// Returns the error page that replaces the current page, if there is one,
+/// and sets the status code of the response for it. The errors it renders
+/// are taken, so that they are not [logged](log_unhandled_errors).
+///
+/// The page is rendered when the returned function is called, so that it can
+/// be given its own context, like its `<head>` metadata.
+///
+/// This is called by the server integrations once the shell of the app has
+/// been rendered, and before any of it has been sent.
+pub fn take_error_page() -> Option<Box<dyn FnOnce() -> View>> {
+    let state = use_context::<ErrorPagesState>()?;
+    let pages = state.pages.borrow().clone()?;
+    let errors = state.errors.with_untracked(Clone::clone);
+    let (status, page): (_, Box<dyn FnOnce() -> View>) =
+        match (pages.error, pages.not_found) {
+            (Some(error), _) if !errors.is_empty() => {
+                state.errors.set(Errors::default());
+                (500, Box::new(move || error(errors)))
+            }
+            (_, Some(not_found)) if state.not_found.get() => {
+                (404, Box::new(move || not_found()))
+            }
+            _ => return None,
+        };
+    (state.set_status)(status);
+    Some(page)
+}
+
+/// Logs the errors that were not caught by any `<ErrorBoundary/>`, and were
+/// not rendered as an error page.
+pub fn log_unhandled_errors() {
+    let Some(state) = use_context::<ErrorPagesState>() else {
+        return;
+    };
+    state.errors.with_untracked(|errors| {
+        for (_, error) in errors.iter() {
+            tracing::error!(
+                "error not caught by any <ErrorBoundary/>: {error}"
+            );
+        }
+    });
+}
@@ -28,6 +28,15 @@ pub trait EventDescriptor: Clone {
     fn options(&self) -> &Option<web_sys::AddEventListenerOptions> {
         &None
     }
+
+    /// Converts an event dispatched by the headless testing renderer into
+    /// the event type, if possible.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn from_event(event: web_sys::Event) -> Option<Self::EventType> {
+        _ = event;
+        None
+    }
 }
 
 /// Overrides the [`EventDescriptor::BUBBLES`] value to always return
@@ -49,6 +58,11 @@ impl<Ev: EventDescriptor> EventDescriptor for undelegated<Ev> {
         self.0.event_delegation_key()
     }
 
+    #[inline(always)]
+    fn from_event(event: web_sys::Event) -> Option<Self::EventType> {
+        Ev::from_event(event)
+    }
+
     const BUBBLES: bool = false;
 }
 
@@ -390,6 +404,11 @@ macro_rules! generate_event_types {
           }
 
           const BUBBLES: bool = true $(&& generate_event_types!($does_not_bubble))?;
+
+          #[inline(always)]
+          fn from_event(event: web_sys::Event) -> Option<Self::EventType> {
+            Some(::wasm_bindgen::JsCast::unchecked_into(event))
+          }
         }
       )*
 
@@ -70,6 +70,13 @@ pub fn event_target_value<T>(event: &T) -> String
 where
     T: JsCast,
 {
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    if let Some(target) = crate::testing::event_target() {
+        return target.value;
+    }
     event
         .unchecked_ref::<web_sys::Event>()
         .target()
@@ -82,6 +89,13 @@ where
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
 pub fn event_target_checked(ev: &web_sys::Event) -> bool {
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    if let Some(target) = crate::testing::event_target() {
+        return target.checked;
+    }
     ev.target()
         .unwrap()
         .unchecked_into::<web_sys::HtmlInputElement>()
@@ -215,6 +229,13 @@ impl TimeoutHandle {
     /// Cancels the timeout to which this refers.
     /// See [`clearTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/clearTimeout)
     pub fn clear(&self) {
+        #[cfg(all(
+            feature = "testing",
+            not(all(target_arch = "wasm32", feature = "web"))
+        ))]
+        return crate::testing::clear_timer(self.0);
+
+        #[allow(unreachable_code)]
         window().clear_timeout_with_handle(self.0);
     }
 }
@@ -262,6 +283,24 @@ pub fn set_timeout_with_handle(
             .map(TimeoutHandle)
     }
 
+    // without a browser, tests drive timeouts with a virtual clock
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    return {
+        let mut cb = Some(cb);
+        let callback = move || {
+            if let Some(cb) = cb.take() {
+                cb();
+            }
+        };
+        Ok(TimeoutHandle(crate::testing::set_timer(
+            callback, duration, false,
+        )))
+    };
+
+    #[allow(unreachable_code)]
     st(closure_once(cb), duration)
 }
 
@@ -346,6 +385,13 @@ impl IntervalHandle {
     /// Cancels the repeating event to which this refers.
     /// See [`clearInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/clearInterval)
     pub fn clear(&self) {
+        #[cfg(all(
+            feature = "testing",
+            not(all(target_arch = "wasm32", feature = "web"))
+        ))]
+        return crate::testing::clear_timer(self.0);
+
+        #[allow(unreachable_code)]
         window().clear_interval_with_handle(self.0);
     }
 }
@@ -400,6 +446,16 @@ pub fn set_interval_with_handle(
             .map(IntervalHandle)
     }
 
+    // without a browser, tests drive intervals with a virtual clock
+    #[cfg(all(
+        feature = "testing",
+        not(all(target_arch = "wasm32", feature = "web"))
+    ))]
+    return Ok(IntervalHandle(crate::testing::set_timer(
+        cb, duration, true,
+    )));
+
+    #[allow(unreachable_code)]
     si(Box::new(cb), duration)
 }
 
@@ -70,7 +70,7 @@ use crate::{
     },
     Directive, Element, Fragment, IntoView, NodeRef, Text, View,
 };
-use leptos_reactive::{create_effect, Oco};
+use leptos_reactive::{create_effect, Oco, SignalGet, SignalSet};
 use std::{fmt, rc::Rc};
 
 /// Trait which allows creating an element tag.
@@ -266,6 +266,13 @@ impl ElementDescriptor for Custom {
         self.name.clone()
     }
 
+    #[inline(always)]
+    fn is_void(&self) -> bool {
+        // a custom element is never void, but an element created with
+        // `dynamic` may be
+        VOID_ELEMENTS.contains(&self.name.as_str())
+    }
+
     #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
     #[inline(always)]
     fn hydration_id(&self) -> &Option<HydrationKey> {
@@ -306,6 +313,8 @@ cfg_if! {
         pub(crate) element: El,
         pub(crate) attrs: SmallVec<[(Oco<'static, str>, Oco<'static, str>); 4]>,
         pub(crate) children: ElementChildren,
+        #[cfg(feature = "testing")]
+        pub(crate) testing: Box<crate::testing::ElementTesting>,
         #[cfg(debug_assertions)]
         pub(crate) view_marker: Option<String>
     }
@@ -409,6 +418,8 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
               attrs: smallvec![],
               children: Default::default(),
               element,
+              #[cfg(feature = "testing")]
+              testing: Default::default(),
               #[cfg(debug_assertions)]
               view_marker: None
             }
@@ -426,6 +437,8 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             attrs: smallvec![],
             children: ElementChildren::Chunks(chunks.into_iter().collect()),
             element,
+            #[cfg(feature = "testing")]
+            testing: Default::default(),
             #[cfg(debug_assertions)]
             view_marker: None,
         }
@@ -468,6 +481,8 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
               attrs,
               children,
               element,
+              #[cfg(feature = "testing")]
+              testing,
               #[cfg(debug_assertions)]
               view_marker
             } = self;
@@ -476,6 +491,8 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
               attrs,
               children,
+              #[cfg(feature = "testing")]
+              testing,
               element: AnyElement {
                 name: element.name(),
                 is_void: element.is_void(),
@@ -548,7 +565,7 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             };
 
             let this = self.clone();
-            let el = self.element.as_ref().clone();
+            let el: web_sys::HtmlElement = self.element.as_ref().clone();
 
             wasm_bindgen_futures::spawn_local(async move {
                 while !crate::document().body().unwrap().contains(Some(&el)) {
@@ -654,6 +671,10 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             let mut this = self;
 
             let mut attr = attr.into_attribute();
+            #[cfg(feature = "testing")]
+            if let Attribute::Fn(f) = &attr {
+                this.testing.attrs.push((name.clone(), Rc::clone(f)));
+            }
             while let Attribute::Fn(f) = attr {
                 attr = f();
             }
@@ -868,6 +889,13 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
 
             let include = match class {
                 Class::Value(include) => include,
+                #[cfg(feature = "testing")]
+                Class::Fn(f) => {
+                    let f: Rc<dyn Fn() -> bool> = f.into();
+                    this.testing.classes.push((name.clone(), Rc::clone(&f)));
+                    f()
+                }
+                #[cfg(not(feature = "testing"))]
                 Class::Fn(f) => f(),
             };
 
@@ -1176,7 +1204,25 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
             self
         }
 
-        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        #[cfg(all(
+            feature = "testing",
+            not(all(target_arch = "wasm32", feature = "web"))
+        ))]
+        {
+            let mut this = self;
+            this.testing.add_listener(event.name(), move |ev| {
+                if let Some(ev) = E::from_event(ev) {
+                    event_handler(ev);
+                }
+            });
+
+            this
+        }
+
+        #[cfg(not(any(
+            feature = "testing",
+            all(target_arch = "wasm32", feature = "web")
+        )))]
         {
             _ = event;
             _ = event_handler;
@@ -1218,6 +1264,147 @@ impl<El: ElementDescriptor + 'static> HtmlElement<El> {
         }
     }
 
+    /// Binds the `value` of an `<input>`, `<textarea>` or `<select>` to a
+    /// signal: the element shows the value of the signal, and the signal is
+    /// updated when the user edits the element. While the user is composing
+    /// text with an input method editor (IME), the signal is only updated once
+    /// the composition has ended.
+    ///
+    /// This is what `bind:value=signal` expands to in the `view` macro.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use leptos::*;
+    /// # let runtime = create_runtime();
+    /// let name = create_rw_signal(String::new());
+    /// html::input().attr("type", "text").bind_value(name)
+    /// # ;
+    /// # runtime.dispose();
+    /// ```
+    #[track_caller]
+    pub fn bind_value<S>(self, signal: S) -> Self
+    where
+        S: SignalGet<Value = String>
+            + SignalSet<Value = String>
+            + Clone
+            + 'static,
+    {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let value = signal.clone();
+            let on_compositionend = signal.clone();
+            self.prop("value", move || value.get())
+                .on(crate::ev::input, move |ev| {
+                    let is_composing = ev
+                        .dyn_ref::<web_sys::InputEvent>()
+                        .map(|ev| ev.is_composing())
+                        .unwrap_or(false);
+                    if !is_composing {
+                        signal.set(crate::helpers::event_target_value(&ev));
+                    }
+                })
+                .on(crate::ev::compositionend, move |ev| {
+                    on_compositionend
+                        .set(crate::helpers::event_target_value(&ev));
+                })
+        }
+
+        // the listener is only kept by the headless testing renderer
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            let value = signal.clone();
+            self.attr("value", move || value.get()).on(
+                crate::ev::input,
+                move |ev| {
+                    signal.set(crate::helpers::event_target_value(&ev));
+                },
+            )
+        }
+    }
+
+    /// Binds the `checked` state of a checkbox to a signal: the checkbox is
+    /// checked when the signal is `true`, and the signal is updated when the
+    /// user checks or unchecks it.
+    ///
+    /// This is what `bind:checked=signal` expands to in the `view` macro.
+    #[track_caller]
+    pub fn bind_checked<S>(self, signal: S) -> Self
+    where
+        S: SignalGet<Value = bool> + SignalSet<Value = bool> + Clone + 'static,
+    {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let checked = signal.clone();
+            self.prop("checked", move || checked.get()).on(
+                crate::ev::change,
+                move |ev| {
+                    signal.set(crate::helpers::event_target_checked(&ev));
+                },
+            )
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            let checked = signal.clone();
+            self.attr("checked", move || checked.get()).on(
+                crate::ev::change,
+                move |ev| {
+                    signal.set(crate::helpers::event_target_checked(&ev));
+                },
+            )
+        }
+    }
+
+    /// Binds a radio button to a signal that holds the `value` of the selected
+    /// radio button of its group: the radio button is checked when the signal
+    /// is equal to its `value`, and the signal is set to its `value` when the
+    /// user selects it.
+    ///
+    /// The `value` attribute must be set before calling this method. This is
+    /// what `bind:group=signal` expands to in the `view` macro.
+    #[track_caller]
+    pub fn bind_group<S>(self, signal: S) -> Self
+    where
+        S: SignalGet<Value = String>
+            + SignalSet<Value = String>
+            + Clone
+            + 'static,
+    {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        {
+            let el: web_sys::HtmlElement = self.element.as_ref().clone();
+            let checked = signal.clone();
+            self.prop("checked", move || {
+                checked.get()
+                    == el.unchecked_ref::<web_sys::HtmlInputElement>().value()
+            })
+            .on(crate::ev::change, move |ev| {
+                if crate::helpers::event_target_checked(&ev) {
+                    signal.set(crate::helpers::event_target_value(&ev));
+                }
+            })
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            let value = self
+                .attrs
+                .iter()
+                .find(|(name, _)| name == "value")
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default();
+            let checked = signal.clone();
+            self.attr("checked", move || checked.get() == value).on(
+                crate::ev::change,
+                move |ev| {
+                    if crate::helpers::event_target_checked(&ev) {
+                        signal.set(crate::helpers::event_target_value(&ev));
+                    }
+                },
+            )
+        }
+    }
+
     /// Adds a child to this element.
     #[track_caller]
     pub fn child(self, child: impl IntoView) -> Self {
@@ -1344,6 +1531,8 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
                 element,
                 mut attrs,
                 children,
+                #[cfg(feature = "testing")]
+                testing,
                 #[cfg(debug_assertions)]
                 view_marker,
                 ..
@@ -1359,6 +1548,10 @@ impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
 
             element.attrs = attrs;
             element.children = children;
+            #[cfg(feature = "testing")]
+            {
+                element.testing = testing;
+            }
 
             #[cfg(debug_assertions)]
             {
@@ -1392,6 +1585,101 @@ pub fn custom<El: ElementDescriptor>(el: El) -> HtmlElement<Custom> {
     })
 }
 
+// https://developer.mozilla.org/en-US/docs/Glossary/Void_element
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
+
+/// Creates an element whose tag name is only known at runtime, such as
+/// `<{tag}>` in the `view` macro.
+///
+/// The tag name can be anything that implements `AsRef<str>`, like a
+/// `String` or an enum of the allowed tags. As it may come from untrusted
+/// content, a name that is not a valid tag name is rendered as a `<div>`.
+pub fn dynamic(tag: impl AsRef<str>) -> HtmlElement<Custom> {
+    let tag = tag.as_ref();
+    let is_valid = tag.starts_with(|c: char| c.is_ascii_alphabetic())
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let tag = if is_valid {
+        tag.to_ascii_lowercase()
+    } else {
+        crate::debug_warn!(
+            "<{tag}> is not a valid tag name, so a <div> is rendered instead"
+        );
+        "div".to_string()
+    };
+    custom(Custom::new(tag))
+}
+
+/// Creates an element from HTML that is entirely known at compile time, which
+/// is how the `template!` macro renders a view that is fully static.
+///
+/// `html` is the outer HTML of a single `<{tag}>` element. On the server it is
+/// rendered as is. In the browser, it is parsed into a `<template>` once, and
+/// each element is a deep clone of it; when hydrating, the element rendered by
+/// the server is used with its children as they are.
+#[doc(hidden)]
+pub fn static_template(
+    tag: &'static str,
+    html: &'static str,
+) -> HtmlElement<AnyElement> {
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        use std::{cell::RefCell, collections::HashMap};
+        use wasm_bindgen::JsCast;
+
+        thread_local! {
+            // keyed by the address of the HTML, which is unique to each use
+            // of the macro
+            static TEMPLATES: RefCell<HashMap<usize, web_sys::HtmlTemplateElement>> =
+                Default::default();
+        }
+
+        if HydrationCtx::is_hydrating() {
+            return custom(Custom::new(tag)).into_any();
+        }
+
+        // keeps the hydration keys of the elements after this one in sync
+        // with the server, which gives this one a key as well
+        _ = HydrationCtx::id();
+        let element = TEMPLATES.with(|templates| {
+            templates
+                .borrow_mut()
+                .entry(html.as_ptr() as usize)
+                .or_insert_with(|| {
+                    let template =
+                        crate::document().create_element("template").unwrap();
+                    template.set_inner_html(html);
+                    template.unchecked_into()
+                })
+                .content()
+                .first_child()
+                .unwrap()
+                .clone_node_with_deep(true)
+                .unwrap()
+        });
+        custom(Custom {
+            name: tag.into(),
+            element: element.unchecked_into(),
+        })
+        .into_any()
+      } else {
+        let element = Custom::new(tag);
+        let html = format!(
+            "<{tag}{}{}",
+            element
+                .id
+                .map(|id| format!(" data-hk=\"{id}\""))
+                .unwrap_or_default(),
+            &html[tag.len() + 1..]
+        );
+        HtmlElement::from_chunks(element, [StringOrView::String(html.into())])
+            .into_any()
+      }
+    }
+}
+
 /// Creates a text node.
 #[inline(always)]
 pub fn text(text: impl Into<Oco<'static, str>>) -> Text {
@@ -0,0 +1,320 @@
+//! Reactive signals that track the layout of the window and of individual
+//! elements.
+//!
+//! Every signal created here starts out at `0.0`, both on the server and
+//! in the browser, and is only filled in once the app is running on the
+//! client. This means they can be read during server rendering and
+//! hydration without causing a mismatch. Updates are throttled to at most
+//! one per animation frame, and all listeners and observers are removed
+//! when the owning reactive scope is disposed.
+
+use crate::{html::ElementDescriptor, NodeRef};
+use leptos_reactive::{
+    create_effect, create_signal, on_cleanup, ReadSignal, SignalSet,
+};
+
+/// The current scroll position of the window, as returned by
+/// [`use_window_scroll`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowScroll {
+    /// The number of pixels the document has been scrolled horizontally,
+    /// i.e., [`window.scrollX`](https://developer.mozilla.org/en-US/docs/Web/API/Window/scrollX).
+    pub x: ReadSignal<f64>,
+    /// The number of pixels the document has been scrolled vertically,
+    /// i.e., [`window.scrollY`](https://developer.mozilla.org/en-US/docs/Web/API/Window/scrollY).
+    pub y: ReadSignal<f64>,
+}
+
+/// The current dimensions of the window or of an element, as returned by
+/// [`use_window_size`] and [`use_element_size`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Size {
+    /// The width, in CSS pixels.
+    pub width: ReadSignal<f64>,
+    /// The height, in CSS pixels.
+    pub height: ReadSignal<f64>,
+}
+
+/// Tracks the scroll position of the window.
+///
+/// ```
+/// use leptos::{leptos_dom::layout::use_window_scroll, *};
+///
+/// #[component]
+/// fn Header() -> impl IntoView {
+///     let scroll = use_window_scroll();
+///     let stuck = move || scroll.y.get() > 64.0;
+///
+///     view! {
+///       <header class:sticky=stuck>"My Site"</header>
+///     }
+/// }
+/// ```
+pub fn use_window_scroll() -> WindowScroll {
+    let (x, set_x) = create_signal(0.0);
+    let (y, set_y) = create_signal(0.0);
+
+    create_effect(move |_| {
+        let update = move || {
+            let window = crate::window();
+            set_x.set(window.scroll_x().unwrap_or_default());
+            set_y.set(window.scroll_y().unwrap_or_default());
+        };
+        update();
+
+        let update = throttle_to_frame(update);
+        let handle = crate::helpers::window_event_listener(
+            crate::ev::scroll,
+            move |_| update(),
+        );
+        on_cleanup(move || handle.remove());
+    });
+
+    WindowScroll { x, y }
+}
+
+/// Tracks the inner dimensions of the window.
+///
+/// These are [`window.innerWidth`](https://developer.mozilla.org/en-US/docs/Web/API/Window/innerWidth)
+/// and [`window.innerHeight`](https://developer.mozilla.org/en-US/docs/Web/API/Window/innerHeight),
+/// updated whenever the window is resized.
+pub fn use_window_size() -> Size {
+    let (width, set_width) = create_signal(0.0);
+    let (height, set_height) = create_signal(0.0);
+
+    create_effect(move |_| {
+        let update = move || {
+            let window = crate::window();
+            set_width.set(
+                window
+                    .inner_width()
+                    .ok()
+                    .and_then(|w| w.as_f64())
+                    .unwrap_or_default(),
+            );
+            set_height.set(
+                window
+                    .inner_height()
+                    .ok()
+                    .and_then(|h| h.as_f64())
+                    .unwrap_or_default(),
+            );
+        };
+        update();
+
+        let update = throttle_to_frame(update);
+        let handle = crate::helpers::window_event_listener(
+            crate::ev::resize,
+            move |_| update(),
+        );
+        on_cleanup(move || handle.remove());
+    });
+
+    Size { width, height }
+}
+
+/// Tracks the size of the content box of the element loaded into the given
+/// [`NodeRef`], using a
+/// [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver).
+///
+/// If the `NodeRef` is loaded with a different element, the observer moves
+/// over to the new one.
+///
+/// ```
+/// use leptos::{html::Canvas, leptos_dom::layout::use_element_size, *};
+///
+/// #[component]
+/// fn Chart() -> impl IntoView {
+///     let canvas = create_node_ref::<Canvas>();
+///     let size = use_element_size(canvas);
+///
+///     view! {
+///       <canvas
+///         _ref=canvas
+///         width=move || size.width.get()
+///         height=move || size.height.get()
+///       ></canvas>
+///     }
+/// }
+/// ```
+pub fn use_element_size<T>(node_ref: NodeRef<T>) -> Size
+where
+    T: ElementDescriptor + Clone + 'static,
+{
+    let (width, set_width) = create_signal(0.0);
+    let (height, set_height) = create_signal(0.0);
+
+    create_effect(move |_| {
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        if let Some(el) = node_ref.get() {
+            use std::{cell::RefCell, rc::Rc};
+            use wasm_bindgen::{prelude::Closure, JsCast};
+
+            let latest = Rc::new(RefCell::new(None::<(f64, f64)>));
+            let update = throttle_to_frame({
+                let latest = Rc::clone(&latest);
+                move || {
+                    if let Some((w, h)) = latest.take() {
+                        set_width.set(w);
+                        set_height.set(h);
+                    }
+                }
+            });
+            let cb = Closure::<dyn Fn(js_sys::Array)>::new(
+                move |entries: js_sys::Array| {
+                    let entry = entries
+                        .get(entries.length().saturating_sub(1))
+                        .unchecked_into::<web_sys::ResizeObserverEntry>();
+                    let rect = entry.content_rect();
+                    *latest.borrow_mut() = Some((rect.width(), rect.height()));
+                    update();
+                },
+            )
+            .into_js_value();
+
+            let Ok(observer) = web_sys::ResizeObserver::new(cb.unchecked_ref())
+            else {
+                return;
+            };
+            let el: &web_sys::HtmlElement = el.element.as_ref();
+            observer.observe(el);
+            on_cleanup(move || observer.disconnect());
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            _ = node_ref;
+            _ = set_width;
+            _ = set_height;
+        }
+    });
+
+    Size { width, height }
+}
+
+/// Wraps the callback so that, no matter how often the returned function is
+/// called, the callback runs at most once per animation frame.
+fn throttle_to_frame(cb: impl Fn() + 'static) -> impl Fn() + 'static {
+    throttle(cb, crate::helpers::request_animation_frame)
+}
+
+/// Wraps the callback so that it runs at most once each time `schedule`
+/// calls back, no matter how often the returned function is called before.
+fn throttle<S>(cb: impl Fn() + 'static, schedule: S) -> impl Fn() + 'static
+where
+    S: Fn(Box<dyn FnOnce()>) + 'static,
+{
+    use std::{cell::Cell, rc::Rc};
+
+    let pending = Rc::new(Cell::new(false));
+    let cb = Rc::new(cb);
+    move || {
+        if !pending.replace(true) {
+            let pending = Rc::clone(&pending);
+            let cb = Rc::clone(&cb);
+            schedule(Box::new(move || {
+                pending.set(false);
+                cb();
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+    type Frames = Rc<RefCell<VecDeque<Box<dyn FnOnce()>>>>;
+
+    fn run_frame(frames: &Frames) {
+        let frame = frames.borrow_mut().pop_front();
+        if let Some(frame) = frame {
+            frame();
+        }
+    }
+
+    #[test]
+    fn throttle_runs_once_per_frame() {
+        let frames = Frames::default();
+        let runs = Rc::new(RefCell::new(0));
+        let update = throttle(
+            {
+                let runs = Rc::clone(&runs);
+                move || *runs.borrow_mut() += 1
+            },
+            {
+                let frames = Rc::clone(&frames);
+                move |frame| frames.borrow_mut().push_back(frame)
+            },
+        );
+
+        update();
+        update();
+        update();
+        assert_eq!(frames.borrow().len(), 1);
+        assert_eq!(*runs.borrow(), 0);
+
+        run_frame(&frames);
+        assert_eq!(*runs.borrow(), 1);
+
+        // the next call after the frame schedules another one
+        update();
+        assert_eq!(frames.borrow().len(), 1);
+        run_frame(&frames);
+        assert_eq!(*runs.borrow(), 2);
+    }
+
+    #[test]
+    fn throttle_reads_the_latest_value() {
+        let frames = Frames::default();
+        let latest = Rc::new(RefCell::new(0));
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let update = throttle(
+            {
+                let latest = Rc::clone(&latest);
+                let seen = Rc::clone(&seen);
+                move || seen.borrow_mut().push(*latest.borrow())
+            },
+            {
+                let frames = Rc::clone(&frames);
+                move |frame| frames.borrow_mut().push_back(frame)
+            },
+        );
+
+        for value in 1..=3 {
+            *latest.borrow_mut() = value;
+            update();
+        }
+        run_frame(&frames);
+
+        assert_eq!(*seen.borrow(), vec![3]);
+    }
+
+    // effects don't run on the server, so the signals keep their initial
+    // values, which hydration starts from as well
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn signals_start_at_zero_on_the_server() {
+        use crate::html::Div;
+        use leptos_reactive::{create_runtime, SignalGetUntracked};
+
+        let runtime = create_runtime();
+
+        let scroll = use_window_scroll();
+        let window = use_window_size();
+        let element = use_element_size(NodeRef::<Div>::new());
+        for signal in [
+            scroll.x,
+            scroll.y,
+            window.width,
+            window.height,
+            element.width,
+            element.height,
+        ] {
+            assert_eq!(signal.get_untracked(), 0.0);
+        }
+
+        runtime.dispose();
+    }
+}
@@ -12,11 +12,15 @@
 pub extern crate tracing;
 
 mod components;
+pub mod deadline;
 mod directive;
+pub mod drag_and_drop;
+pub mod error_pages;
 mod events;
 pub mod helpers;
 pub mod html;
 mod hydration;
+pub mod layout;
 /// Utilities for simple isomorphic logging to the console or terminal.
 pub mod logging;
 mod macro_helpers;
@@ -27,6 +31,11 @@ pub mod nonce;
 pub mod ssr;
 pub mod ssr_in_order;
 pub mod svg;
+#[cfg(all(
+    feature = "testing",
+    not(all(target_arch = "wasm32", feature = "web"))
+))]
+pub mod testing;
 mod transparent;
 
 use cfg_if::cfg_if;
@@ -307,6 +316,8 @@ cfg_if! {
       attrs: SmallVec<[(Oco<'static, str>, Oco<'static, str>); 4]>,
       children: ElementChildren,
       id: Option<HydrationKey>,
+      #[cfg(feature = "testing")]
+      testing: Box<crate::testing::ElementTesting>,
       #[cfg(debug_assertions)]
       /// Optional marker for the view macro source, in debug mode.
       pub view_marker: Option<String>
@@ -381,6 +392,8 @@ impl Element {
                 attrs,
                 children,
                 id,
+                #[cfg(feature = "testing")]
+                testing,
                 #[cfg(debug_assertions)]
                 view_marker,
             } = self;
@@ -391,6 +404,8 @@ impl Element {
                 element,
                 attrs,
                 children,
+                #[cfg(feature = "testing")]
+                testing,
                 #[cfg(debug_assertions)]
                 view_marker,
             }
@@ -425,6 +440,8 @@ impl Element {
               attrs: Default::default(),
               children: Default::default(),
               id: *el.hydration_id(),
+              #[cfg(feature = "testing")]
+              testing: Default::default(),
               #[cfg(debug_assertions)]
               view_marker: None
             }
@@ -75,6 +75,33 @@ impl Class {
     }
 }
 
+/// Converts a function that returns pairs of class names and whether they are
+/// present, such as a `HashMap<&str, bool>`, into the value of a `class`
+/// attribute that is updated whenever the function's signals change.
+///
+/// This is used by the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+/// macro for `class=(move || ...)`.
+pub fn class_list<I, C>(
+    classes: impl Fn() -> I + 'static,
+) -> impl Fn() -> String + 'static
+where
+    I: IntoIterator<Item = (C, bool)>,
+    C: AsRef<str>,
+{
+    move || {
+        let mut value = String::new();
+        for (name, present) in classes() {
+            if present {
+                if !value.is_empty() {
+                    value.push(' ');
+                }
+                value.push_str(name.as_ref());
+            }
+        }
+        value
+    }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use leptos_reactive::Oco;
 
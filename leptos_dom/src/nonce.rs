@@ -8,10 +8,15 @@ use std::{fmt::Display, ops::Deref};
 ///
 /// When the `nonce` feature is enabled on one of the server integrations,
 /// a nonce is generated during server rendering and added to all inline
-/// scripts used for HTML streaming and resource loading.
+/// scripts and styles the framework renders: those used for hydration, HTML
+/// streaming, resource loading and live reloading, and those added with
+/// `leptos_meta`. In the browser, the nonce of the page is read from those
+/// scripts, so that `<Script/>` and `<Style/>` tags added after hydration
+/// carry it too.
 ///
 /// The nonce being used during the current server response can be
-/// accessed using [`use_nonce`].
+/// accessed using [`use_nonce`], and a matching `Content-Security-Policy`
+/// can be created with [`Nonce::content_security_policy`].
 ///
 /// ```rust,ignore
 /// #[component]
@@ -59,6 +64,22 @@ impl Display for Nonce {
     }
 }
 
+impl Nonce {
+    /// Returns a `Content-Security-Policy` that allows the inline scripts and
+    /// styles that carry this nonce, the scripts they load (like the app's
+    /// JavaScript and WebAssembly), and any other resources from the same
+    /// origin as the page.
+    ///
+    /// Further directives can be appended to it, separated by `; `, like a
+    /// `connect-src` with the origins that the app makes requests to.
+    pub fn content_security_policy(&self) -> String {
+        format!(
+            "default-src 'self'; script-src 'nonce-{self}' 'strict-dynamic' \
+             'wasm-unsafe-eval'; style-src 'self' 'nonce-{self}'"
+        )
+    }
+}
+
 impl IntoAttribute for Nonce {
     fn into_attribute(self) -> Attribute {
         Attribute::String(self.0.into())
@@ -103,7 +124,27 @@ impl IntoAttribute for Nonce {
 /// }
 /// ```
 pub fn use_nonce() -> Option<Nonce> {
-    use_context::<Nonce>()
+    use_context::<Nonce>().or_else(page_nonce)
+}
+
+// the nonce of the inline scripts that were rendered on the server, which
+// browsers only expose as a property, not as an attribute
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn page_nonce() -> Option<Nonce> {
+    use wasm_bindgen::JsCast;
+
+    let script = crate::document()
+        .query_selector("script[nonce]")
+        .ok()??
+        .dyn_into::<web_sys::HtmlElement>()
+        .ok()?;
+    let nonce = script.nonce();
+    (!nonce.is_empty()).then_some(Nonce(nonce))
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+fn page_nonce() -> Option<Nonce> {
+    None
 }
 
 #[cfg(all(feature = "ssr", feature = "nonce"))]
@@ -3,14 +3,19 @@
 //! Server-side HTML rendering utilities.
 
 use crate::{
+    deadline::RenderDeadline,
     html::{ElementChildren, StringOrView},
     CoreComponent, HydrationCtx, HydrationKey, IntoView, View,
 };
 use cfg_if::cfg_if;
-use futures::{stream::FuturesUnordered, Future, Stream, StreamExt};
+use futures::{
+    future::{Either, Shared},
+    stream::FuturesUnordered,
+    Future, FutureExt, Stream, StreamExt,
+};
 use itertools::Itertools;
 use leptos_reactive::*;
-use std::pin::Pin;
+use std::{cell::RefCell, collections::HashSet, pin::Pin, rc::Rc};
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 
@@ -198,7 +203,8 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
     let resources = SharedContext::pending_resources();
     let pending_resources = serde_json::to_string(&resources).unwrap();
     let pending_fragments = SharedContext::pending_fragments();
-    let serializers = SharedContext::serialization_resolvers();
+    let serializers = serialization_resolvers();
+    let server_only = SharedContext::is_server_only();
     let nonce_str = crate::nonce::use_nonce()
         .map(|nonce| format!(" nonce=\"{nonce}\""))
         .unwrap_or_default();
@@ -206,18 +212,23 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
     let local_only = SharedContext::fragments_with_local_resources();
     let local_only = serde_json::to_string(&local_only).unwrap();
 
-    let mut blocking_fragments = FuturesUnordered::new();
-    let fragments = FuturesUnordered::new();
+    let in_order = SharedContext::in_order_fragments();
+
+    // the deadline starts once the shell has been rendered, so that it can be
+    // set while rendering it
+    let deadline = Deadline::start();
+
+    let mut blocking_fragments = Vec::new();
+    let mut fragments = Vec::new();
+    let mut in_order_fragments = Vec::new();
 
     for (fragment_id, data) in pending_fragments {
         if data.should_block {
-            blocking_fragments
-                .push(async move { (fragment_id, data.out_of_order.await) });
+            blocking_fragments.push((fragment_id, data.out_of_order));
+        } else if in_order.contains(&fragment_id) {
+            in_order_fragments.push((fragment_id, data.out_of_order));
         } else {
-            fragments.push(Box::pin(async move {
-                (fragment_id, data.out_of_order.await)
-            })
-                as Pin<Box<dyn Future<Output = (String, String)>>>);
+            fragments.push((fragment_id, data.out_of_order));
         }
     }
 
@@ -225,22 +236,24 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
         // HTML for the view function and script to store resources
         {
             let nonce_str = nonce_str.clone();
+            let deadline = deadline.clone();
             async move {
-                let resolvers = format!(
-                    "<script{nonce_str}>__LEPTOS_PENDING_RESOURCES = \
-                     {pending_resources};__LEPTOS_RESOLVED_RESOURCES = new \
-                     Map();__LEPTOS_RESOURCE_RESOLVERS = new \
-                     Map();__LEPTOS_LOCAL_ONLY = {local_only};</script>"
-                );
+                let resolvers = if server_only {
+                    String::new()
+                } else {
+                    format!(
+                        "<script{nonce_str}>__LEPTOS_PENDING_RESOURCES = \
+                         {pending_resources};__LEPTOS_RESOLVED_RESOURCES = \
+                         new Map();__LEPTOS_RESOURCE_RESOLVERS = new \
+                         Map();__LEPTOS_LOCAL_ONLY = {local_only};</script>"
+                    )
+                };
 
                 if replace_blocks {
-                    let mut blocks =
-                        Vec::with_capacity(blocking_fragments.len());
-                    while let Some((blocked_id, blocked_fragment)) =
-                        blocking_fragments.next().await
-                    {
-                        blocks.push((blocked_id, blocked_fragment));
-                    }
+                    let blocks = deadline
+                        .fragments(blocking_fragments)
+                        .collect::<Vec<_>>()
+                        .await;
 
                     let prefix = prefix();
 
@@ -259,38 +272,121 @@ pub fn render_to_stream_with_prefix_undisposed_with_context_and_block_replacemen
                             format!("{first}{blocked_fragment}{rest}").into();
                     }
 
-                    format!("{prefix}{shell}{resolvers}")
+                    shell_stream(
+                        nonce_str,
+                        format!("{prefix}{shell}"),
+                        resolvers,
+                        in_order_fragments,
+                        deadline,
+                    )
                 } else {
                     let mut blocking = String::new();
                     let mut blocking_fragments = fragments_to_chunks(
                         nonce_str.clone(),
-                        blocking_fragments,
+                        deadline.fragments(blocking_fragments),
                     );
 
                     while let Some(fragment) = blocking_fragments.next().await {
                         blocking.push_str(&fragment);
                     }
                     let prefix = prefix();
-                    format!("{prefix}{shell}{resolvers}{blocking}")
+                    shell_stream(
+                        nonce_str,
+                        format!("{prefix}{shell}"),
+                        format!("{resolvers}{blocking}"),
+                        in_order_fragments,
+                        deadline,
+                    )
                 }
             }
         },
     )
-    .chain(ooo_body_stream_recurse(nonce_str, fragments, serializers));
+    .flatten()
+    .chain(ooo_body_stream_recurse(
+        nonce_str.clone(),
+        fragments,
+        serializers,
+        deadline.clone(),
+    ))
+    .chain(futures::stream::once(async move {
+        deadline.load_in_browser(&nonce_str)
+    }));
 
     (stream, runtime)
 }
 
+// Streams the shell followed by the `tail`. If any fragments are streamed in
+// order, the shell is split at each of them, and the HTML that follows one is
+// only sent once it has resolved, in place of its fallback.
+fn shell_stream(
+    nonce_str: String,
+    shell: String,
+    tail: String,
+    in_order_fragments: Vec<(String, PinnedFuture<String>)>,
+    deadline: Deadline,
+) -> Pin<Box<dyn Stream<Item = String>>> {
+    if in_order_fragments.is_empty() {
+        return Box::pin(futures::stream::once(async move {
+            format!("{shell}{tail}")
+        }));
+    }
+
+    let mut in_place = Vec::new();
+    let mut elsewhere = Vec::new();
+    for (fragment_id, fragment) in in_order_fragments {
+        let open = format!("<!--suspense-open-{fragment_id}-->");
+        let close = format!("<!--suspense-close-{fragment_id}-->");
+        match shell.find(&open).and_then(|start| {
+            let end = start + open.len();
+            Some((end, end + shell[end..].find(&close)?))
+        }) {
+            Some((start, end)) => {
+                in_place.push((start, end, fragment_id, fragment))
+            }
+            // not in the shell itself, so it replaces its fallback like any
+            // other fragment
+            None => elsewhere.push((fragment_id, fragment)),
+        }
+    }
+    in_place.sort_by_key(|(start, ..)| *start);
+
+    let mut chunks = Vec::<PinnedFuture<String>>::new();
+    let mut cursor = 0;
+    for (start, end, fragment_id, fragment) in in_place {
+        // a fragment within the fallback of a previous one is never shown
+        if start < cursor {
+            continue;
+        }
+        let before = shell[cursor..start].to_string();
+        chunks.push(Box::pin(async move { before }));
+        // once the deadline has passed, the fallback is sent instead
+        let fallback = shell[start..end].to_string();
+        let fragment = deadline.fragment(fragment_id, fragment);
+        chunks
+            .push(Box::pin(async move { fragment.await.unwrap_or(fallback) }));
+        cursor = end;
+    }
+    let rest = format!("{}{tail}", &shell[cursor..]);
+    chunks.push(Box::pin(async move { rest }));
+
+    Box::pin(futures::stream::iter(chunks).then(|chunk| chunk).chain(
+        fragments_to_chunks(nonce_str, deadline.fragments(elsewhere)),
+    ))
+}
+
 fn ooo_body_stream_recurse(
     nonce_str: String,
-    fragments: FuturesUnordered<PinnedFuture<(String, String)>>,
+    fragments: Vec<(String, PinnedFuture<String>)>,
     serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+    deadline: Deadline,
 ) -> Pin<Box<dyn Stream<Item = String>>> {
     // resources and fragments
     // stream HTML for each <Suspense/> as it resolves
-    let fragments = fragments_to_chunks(nonce_str.clone(), fragments);
+    let fragments =
+        fragments_to_chunks(nonce_str.clone(), deadline.fragments(fragments));
     // stream data for each Resource as it resolves
-    let resources = render_serializers(nonce_str.clone(), serializers);
+    let resources =
+        render_serializers(nonce_str.clone(), deadline.resources(serializers));
 
     Box::pin(
         // TODO these should be combined again in a way that chains them appropriately
@@ -300,18 +396,18 @@ fn ooo_body_stream_recurse(
                 let pending = SharedContext::pending_fragments();
 
                 if !pending.is_empty() {
-                    let fragments = FuturesUnordered::new();
-                    let serializers = SharedContext::serialization_resolvers();
-                    for (fragment_id, data) in pending {
-                        fragments.push(Box::pin(async move {
-                            (fragment_id.clone(), data.out_of_order.await)
+                    let fragments = pending
+                        .into_iter()
+                        .map(|(fragment_id, data)| {
+                            (fragment_id, data.out_of_order)
                         })
-                            as Pin<Box<dyn Future<Output = (String, String)>>>);
-                    }
+                        .collect();
+                    let serializers = serialization_resolvers();
                     Box::pin(ooo_body_stream_recurse(
                         nonce_str,
                         fragments,
                         serializers,
+                        deadline,
                     ))
                         as Pin<Box<dyn Stream<Item = String>>>
                 } else {
@@ -325,6 +421,138 @@ fn ooo_body_stream_recurse(
     )
 }
 
+// The deadline for rendering, after which the fragments and resources that
+// have not resolved are no longer waited for, and are loaded in the browser
+// instead. See `crate::deadline`.
+#[derive(Clone)]
+struct Deadline {
+    timeout: Option<std::time::Duration>,
+    passed: Shared<PinnedFuture<()>>,
+    timed_out: Rc<RefCell<TimedOut>>,
+}
+
+#[derive(Default)]
+struct TimedOut {
+    fragments: Vec<String>,
+    sent_resources: HashSet<ResourceId>,
+}
+
+impl Deadline {
+    fn start() -> Self {
+        // the fragments of a page that is not hydrated cannot be loaded in
+        // the browser
+        let deadline = use_context::<RenderDeadline>()
+            .filter(|_| !SharedContext::is_server_only());
+        let timeout = deadline.as_ref().and_then(RenderDeadline::timeout);
+        let passed = deadline
+            .and_then(|deadline| deadline.start())
+            .unwrap_or_else(|| Box::pin(futures::future::pending()));
+        Self {
+            timeout,
+            passed: passed.shared(),
+            timed_out: Default::default(),
+        }
+    }
+
+    // the HTML of the fragment, or `None` if the deadline passes first
+    fn fragment(
+        &self,
+        fragment_id: String,
+        fragment: PinnedFuture<String>,
+    ) -> impl Future<Output = Option<String>> {
+        let passed = self.passed.clone();
+        let timed_out = Rc::clone(&self.timed_out);
+        async move {
+            match futures::future::select(fragment, passed).await {
+                Either::Left((html, _)) => Some(html),
+                Either::Right(_) => {
+                    timed_out.borrow_mut().fragments.push(fragment_id);
+                    None
+                }
+            }
+        }
+    }
+
+    // the fragments that resolve before the deadline passes
+    fn fragments(
+        &self,
+        fragments: Vec<(String, PinnedFuture<String>)>,
+    ) -> impl Stream<Item = (String, String)> {
+        fragments
+            .into_iter()
+            .map(|(fragment_id, fragment)| {
+                let fragment = self.fragment(fragment_id.clone(), fragment);
+                async move { fragment.await.map(|html| (fragment_id, html)) }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .filter_map(futures::future::ready)
+    }
+
+    // the resources that resolve before the deadline passes
+    fn resources(
+        &self,
+        serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+    ) -> impl Stream<Item = (ResourceId, String)> {
+        let timed_out = Rc::clone(&self.timed_out);
+        serializers
+            .into_iter()
+            .map(|serializer| {
+                let passed = self.passed.clone();
+                async move {
+                    match futures::future::select(serializer, passed).await {
+                        Either::Left((resolved, _)) => Some(resolved),
+                        Either::Right(_) => None,
+                    }
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .filter_map(futures::future::ready)
+            .inspect(move |(id, _)| {
+                timed_out.borrow_mut().sent_resources.insert(*id);
+            })
+    }
+
+    // Returns a script that makes the browser load the fragments and
+    // resources that did not resolve in time itself, as though they used
+    // only local resources, and logs those resources.
+    fn load_in_browser(&self, nonce_str: &str) -> String {
+        let timed_out = self.timed_out.borrow();
+        if timed_out.fragments.is_empty() {
+            return String::new();
+        }
+        let resources = SharedContext::pending_resources()
+            .into_iter()
+            .filter(|id| !timed_out.sent_resources.contains(id))
+            .collect::<Vec<_>>();
+
+        tracing::warn!(
+            "the render deadline of {:?} passed before {} <Suspense/> \
+             fragments resolved, so they will be loaded in the browser; the \
+             resources that had not resolved were: {}",
+            self.timeout.unwrap_or_default(),
+            timed_out.fragments.len(),
+            resources
+                .iter()
+                .map(|id| format!(
+                    "{id:?} ({})",
+                    SharedContext::resource_type_name(*id).unwrap_or("?")
+                ))
+                .join(", ")
+        );
+
+        let fragments = serde_json::to_string(&timed_out.fragments).unwrap();
+        let resources = serde_json::to_string(&resources).unwrap();
+        format!(
+            "<script{nonce_str}>(function() {{ let timedOut = \
+             {resources}.map(id => JSON.stringify(id));\
+             __LEPTOS_PENDING_RESOURCES = \
+             __LEPTOS_PENDING_RESOURCES.filter(id => \
+             !timedOut.includes(JSON.stringify(id)));\
+             __LEPTOS_LOCAL_ONLY.push(...{fragments}); }})();</script>"
+        )
+    }
+}
+
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     instrument(level = "trace", skip_all,)
@@ -700,9 +928,20 @@ pub(crate) fn to_kebab_case(name: &str) -> String {
     any(debug_assertions, feature = "ssr"),
     instrument(level = "trace", skip_all,)
 )]
+// The resources whose data is sent to the browser as they resolve, which is
+// not needed by pages that are not hydrated.
+pub(crate) fn serialization_resolvers(
+) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
+    if SharedContext::is_server_only() {
+        FuturesUnordered::new()
+    } else {
+        SharedContext::serialization_resolvers()
+    }
+}
+
 pub(crate) fn render_serializers(
     nonce_str: String,
-    serializers: FuturesUnordered<PinnedFuture<(ResourceId, String)>>,
+    serializers: impl Stream<Item = (ResourceId, String)>,
 ) -> impl Stream<Item = String> {
     serializers.map(move |(id, json)| {
         let id = serde_json::to_string(&id).unwrap();
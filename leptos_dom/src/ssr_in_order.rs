@@ -4,7 +4,7 @@
 
 use crate::{
     html::{ElementChildren, StringOrView},
-    ssr::{render_serializers, ToMarker},
+    ssr::{render_serializers, serialization_resolvers, ToMarker},
     CoreComponent, HydrationCtx, View,
 };
 use async_recursion::async_recursion;
@@ -127,11 +127,15 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
 
     let local_only = SharedContext::fragments_with_local_resources();
     let local_only = serde_json::to_string(&local_only).unwrap();
+    let server_only = SharedContext::is_server_only();
 
     let stream = futures::stream::once({
         let nonce_str = nonce_str.clone();
         async move {
             let prefix = prefix_rx.await.expect("to receive prefix");
+            if server_only {
+                return prefix.to_string();
+            }
             format!(
                 r#"
         {prefix}
@@ -148,7 +152,7 @@ pub fn render_to_stream_in_order_with_prefix_undisposed_with_context(
     .chain(rx)
     .chain(
         futures::stream::once(async move {
-            let serializers = SharedContext::serialization_resolvers();
+            let serializers = serialization_resolvers();
             render_serializers(nonce_str, serializers)
         })
         .flatten(),
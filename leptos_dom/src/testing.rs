@@ -0,0 +1,465 @@
+//! Renders views into a tree of nodes that can be inspected, and dispatches
+//! events to their listeners, without a browser.
+//!
+//! With the `testing` feature, elements that are not rendered in the browser
+//! keep their reactive attributes, reactive classes and event listeners, and
+//! dynamic children and lists are rendered again when their signals change.
+//! [`render`] then takes a snapshot of the current state of a view. This is
+//! what the `leptos_test` crate is built on.
+//!
+//! Views created by the `view` macro with the `ssr` feature are compiled into
+//! HTML templates, so they can't be inspected.
+//!
+//! Timeouts and intervals are scheduled on a virtual clock, which only moves
+//! forward when [`advance_time`] is called.
+
+use crate::{
+    html::ElementChildren, macro_helpers::Attribute, CoreComponent, Element,
+    View,
+};
+use leptos_reactive::{Oco, SignalWithUntracked};
+use std::{cell::RefCell, fmt, rc::Rc, time::Duration};
+use wasm_bindgen::{JsCast, JsValue};
+
+type Listener = Rc<RefCell<Box<dyn FnMut(web_sys::Event)>>>;
+type AttributeFn = Rc<dyn Fn() -> Attribute>;
+type ClassFn = Rc<dyn Fn() -> bool>;
+
+/// The reactive attributes, reactive classes and event listeners of an
+/// element.
+#[derive(Clone, Default)]
+pub(crate) struct ElementTesting {
+    pub(crate) attrs: Vec<(Oco<'static, str>, AttributeFn)>,
+    pub(crate) classes: Vec<(Oco<'static, str>, ClassFn)>,
+    pub(crate) listeners: Vec<(Oco<'static, str>, Listener)>,
+}
+
+// like the DOM node of an element in the browser, this is not compared
+impl PartialEq for ElementTesting {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ElementTesting {}
+
+impl ElementTesting {
+    pub(crate) fn add_listener(
+        &mut self,
+        name: Oco<'static, str>,
+        listener: impl FnMut(web_sys::Event) + 'static,
+    ) {
+        self.listeners
+            .push((name, Rc::new(RefCell::new(Box::new(listener)))));
+    }
+}
+
+/// A node of a rendered view.
+#[derive(Clone)]
+pub enum TestNode {
+    /// An element.
+    Element(TestElement),
+    /// A text node.
+    Text(String),
+}
+
+impl TestNode {
+    /// The text content of the node.
+    pub fn text(&self) -> String {
+        match self {
+            TestNode::Element(el) => el.text(),
+            TestNode::Text(text) => text.clone(),
+        }
+    }
+}
+
+impl fmt::Display for TestNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestNode::Element(el) => el.fmt(f),
+            TestNode::Text(text) => {
+                f.write_str(&html_escape::encode_text(text))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TestNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// An element of a rendered view, with the values its attributes had when it
+/// was rendered.
+#[derive(Clone)]
+pub struct TestElement {
+    name: String,
+    is_void: bool,
+    attrs: Vec<(String, String)>,
+    children: Vec<TestNode>,
+    listeners: Vec<(Oco<'static, str>, Listener)>,
+}
+
+impl TestElement {
+    /// The tag name of the element, such as `div`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The attributes of the element, in the order they were set.
+    pub fn attrs(&self) -> &[(String, String)] {
+        &self.attrs
+    }
+
+    /// The value of an attribute, or `None` if it is not set.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the element has a class.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attr("class").is_some_and(|classes| {
+            classes.split_whitespace().any(|c| c == class)
+        })
+    }
+
+    /// The child nodes of the element.
+    pub fn children(&self) -> &[TestNode] {
+        &self.children
+    }
+
+    /// The text content of the element and its descendants.
+    pub fn text(&self) -> String {
+        self.children.iter().map(TestNode::text).collect()
+    }
+
+    /// Whether the element has a listener for the event.
+    pub fn has_listener(&self, event: &str) -> bool {
+        self.listeners.iter().any(|(name, _)| name == event)
+    }
+
+    /// Calls the listeners of the element for the event, and returns whether
+    /// there were any.
+    ///
+    /// The listeners receive an empty event, so they can't call its methods.
+    /// While they run, [`event_target_value`](crate::helpers::event_target_value)
+    /// and [`event_target_checked`](crate::helpers::event_target_checked)
+    /// return the values of `target`.
+    pub fn dispatch(&self, event: &str, target: TargetState) -> bool {
+        let listeners = self
+            .listeners
+            .iter()
+            .filter(|(name, _)| name == event)
+            .map(|(_, listener)| Rc::clone(listener))
+            .collect::<Vec<_>>();
+        let prev = EVENT_TARGET.with(|t| t.replace(Some(target)));
+        for listener in &listeners {
+            let event = JsValue::UNDEFINED.unchecked_into::<web_sys::Event>();
+            (listener.borrow_mut())(event);
+        }
+        EVENT_TARGET.with(|t| *t.borrow_mut() = prev);
+        !listeners.is_empty()
+    }
+
+    /// All descendant elements, depth-first and in document order.
+    pub fn descendants(&self) -> Vec<&TestElement> {
+        fn collect<'a>(nodes: &'a [TestNode], into: &mut Vec<&'a TestElement>) {
+            for node in nodes {
+                if let TestNode::Element(el) = node {
+                    into.push(el);
+                    collect(&el.children, into);
+                }
+            }
+        }
+
+        let mut descendants = Vec::new();
+        collect(&self.children, &mut descendants);
+        descendants
+    }
+}
+
+impl fmt::Display for TestElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.name)?;
+        for (name, value) in &self.attrs {
+            if value.is_empty() {
+                write!(f, " {name}")?;
+            } else {
+                write!(
+                    f,
+                    " {name}=\"{}\"",
+                    html_escape::encode_double_quoted_attribute(value)
+                )?;
+            }
+        }
+        if self.is_void {
+            return f.write_str("/>");
+        }
+        f.write_str(">")?;
+        for child in &self.children {
+            child.fmt(f)?;
+        }
+        write!(f, "</{}>", self.name)
+    }
+}
+
+impl fmt::Debug for TestElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// The state of the target of an event dispatched with
+/// [`TestElement::dispatch`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TargetState {
+    /// The `value` of the target.
+    pub value: String,
+    /// Whether the target is `checked`.
+    pub checked: bool,
+}
+
+thread_local! {
+    static EVENT_TARGET: RefCell<Option<TargetState>> = const { RefCell::new(None) };
+}
+
+/// The target of the event that is being dispatched, if any.
+pub(crate) fn event_target() -> Option<TargetState> {
+    EVENT_TARGET.with(|t| t.borrow().clone())
+}
+
+/// Renders the current state of a view into a list of nodes.
+///
+/// Dynamic children and lists whose signals have changed since the view was
+/// last rendered are updated first.
+pub fn render(view: &View) -> Vec<TestNode> {
+    let mut nodes = Vec::new();
+    render_view(view, &mut nodes);
+    nodes
+}
+
+fn render_view(view: &View, nodes: &mut Vec<TestNode>) {
+    match view {
+        View::Element(el) => nodes.push(TestNode::Element(render_element(el))),
+        View::Text(text) => push_text(nodes, &text.content),
+        View::Component(component) => {
+            for child in &component.children {
+                render_view(child, nodes);
+            }
+        }
+        View::CoreComponent(component) | View::Suspense(_, component) => {
+            render_core_component(component, nodes)
+        }
+        View::Transparent(_) => {}
+    }
+}
+
+fn render_core_component(component: &CoreComponent, nodes: &mut Vec<TestNode>) {
+    match component {
+        CoreComponent::Unit(_) => {}
+        CoreComponent::DynChild(dyn_child) => {
+            if let Some(refresh) = dyn_child.refresh {
+                refresh.with_untracked(|_| {});
+            }
+            if let Some(child) = dyn_child.child.borrow().as_ref() {
+                render_view(child, nodes);
+            }
+        }
+        CoreComponent::Each(each) => {
+            if let Some(refresh) = each.refresh {
+                refresh.with_untracked(|_| {});
+            }
+            for item in each.children.borrow().iter().flatten() {
+                render_view(&item.child, nodes);
+            }
+        }
+    }
+}
+
+fn render_element(el: &Element) -> TestElement {
+    let mut attrs = el
+        .attrs
+        .iter()
+        .filter(|(name, _)| name != "data-hk")
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<Vec<_>>();
+    for (name, value) in &el.testing.attrs {
+        attrs.retain(|(attr, _)| attr != name);
+        if let Some(value) = value().as_nameless_value_string() {
+            attrs.push((name.to_string(), value.to_string()));
+        }
+    }
+    if !el.testing.classes.is_empty() {
+        let mut classes = attrs
+            .iter()
+            .find(|(name, _)| name == "class")
+            .map(|(_, classes)| {
+                classes.split_whitespace().map(String::from).collect()
+            })
+            .unwrap_or_else(Vec::new);
+        for (name, include) in &el.testing.classes {
+            classes.retain(|class| class != name);
+            if include() {
+                classes.push(name.to_string());
+            }
+        }
+        attrs.retain(|(name, _)| name != "class");
+        if !classes.is_empty() {
+            attrs.push(("class".into(), classes.join(" ")));
+        }
+    }
+
+    let mut children = Vec::new();
+    match &el.children {
+        ElementChildren::Empty => {}
+        ElementChildren::Children(views) => {
+            for view in views {
+                render_view(view, &mut children);
+            }
+        }
+        ElementChildren::InnerHtml(html) => {
+            children.push(TestNode::Text(html.to_string()))
+        }
+        ElementChildren::Chunks(_) => panic!(
+            "<{}> was created by the `view` macro with the `ssr` feature, \
+             and can't be rendered for testing",
+            el.name
+        ),
+    }
+
+    TestElement {
+        name: el.name.to_string(),
+        is_void: el.is_void,
+        attrs,
+        children,
+        listeners: el.testing.listeners.clone(),
+    }
+}
+
+fn push_text(nodes: &mut Vec<TestNode>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(TestNode::Text(prev)) = nodes.last_mut() {
+        prev.push_str(text);
+    } else {
+        nodes.push(TestNode::Text(text.to_string()));
+    }
+}
+
+type TimerCallback = Rc<RefCell<Option<Box<dyn FnMut()>>>>;
+
+struct Timer {
+    id: i32,
+    at: Duration,
+    interval: Option<Duration>,
+    callback: TimerCallback,
+}
+
+#[derive(Default)]
+struct Clock {
+    now: Duration,
+    next_id: i32,
+    timers: Vec<Timer>,
+}
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = Default::default();
+}
+
+/// Schedules a callback on the virtual clock, and returns its id.
+pub(crate) fn set_timer(
+    callback: impl FnMut() + 'static,
+    delay: Duration,
+    repeat: bool,
+) -> i32 {
+    CLOCK.with(|clock| {
+        let mut clock = clock.borrow_mut();
+        clock.next_id += 1;
+        let id = clock.next_id;
+        let at = clock.now + delay;
+        clock.timers.push(Timer {
+            id,
+            at,
+            interval: repeat.then_some(delay),
+            callback: Rc::new(RefCell::new(Some(Box::new(callback)))),
+        });
+        id
+    })
+}
+
+/// Cancels a callback scheduled on the virtual clock.
+pub(crate) fn clear_timer(id: i32) {
+    // the thread may be shutting down when a handle is cleared on cleanup
+    _ = CLOCK.try_with(|clock| {
+        clock.borrow_mut().timers.retain(|timer| timer.id != id)
+    });
+}
+
+/// The time that has passed on the virtual clock, which drives
+/// [`set_timeout`](crate::helpers::set_timeout),
+/// [`set_interval`](crate::helpers::set_interval) and
+/// [`debounce`](crate::helpers::debounce) without a browser.
+pub fn now() -> Duration {
+    CLOCK.with(|clock| clock.borrow().now)
+}
+
+/// Moves the virtual clock forward, running the timers that are due in the
+/// order they are due.
+///
+/// Timers that are scheduled by those timers also run if they are due before
+/// the end of `duration`.
+pub fn advance_time(duration: Duration) {
+    let end = now() + duration;
+    loop {
+        let next = CLOCK.with(|clock| {
+            let mut clock = clock.borrow_mut();
+            let index = clock
+                .timers
+                .iter()
+                .enumerate()
+                .filter(|(_, timer)| timer.at <= end)
+                .min_by_key(|(_, timer)| (timer.at, timer.id))
+                .map(|(index, _)| index)?;
+            let timer = &mut clock.timers[index];
+            let at = timer.at;
+            let callback = Rc::clone(&timer.callback);
+            match timer.interval {
+                // an interval of zero would run forever
+                Some(interval) => {
+                    timer.at += interval.max(Duration::from_millis(1))
+                }
+                None => {
+                    clock.timers.remove(index);
+                }
+            }
+            clock.now = at;
+            Some(callback)
+        });
+        let Some(callback) = next else {
+            break;
+        };
+        let mut callback = callback.borrow_mut();
+        if let Some(callback) = callback.as_mut() {
+            callback();
+        }
+    }
+    CLOCK.with(|clock| clock.borrow_mut().now = end);
+}
+
+/// The number of timers that are scheduled on the virtual clock.
+pub fn pending_timers() -> usize {
+    CLOCK.with(|clock| clock.borrow().timers.len())
+}
+
+/// Cancels all timers and sets the virtual clock back to zero.
+pub fn reset_time() {
+    // the timers are dropped after the clock is released, in case dropping
+    // them clears other timers
+    let clock = CLOCK.try_with(|clock| clock.take());
+    drop(clock);
+}
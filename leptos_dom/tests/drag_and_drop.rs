@@ -0,0 +1,88 @@
+//! Run with `wasm-pack test --headless --firefox -- --features web`.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use leptos::{html, *};
+use leptos_dom::drag_and_drop::{use_draggable, use_drop_zone};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// resolves once the effects that attach the directives have run
+async fn tick() {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    request_animation_frame(move || {
+        _ = tx.send(());
+    });
+    _ = rx.await;
+}
+
+fn press(el: &web_sys::HtmlElement, key: &str) {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let ev = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+        "keydown", &init,
+    )
+    .unwrap();
+    el.dispatch_event(&ev).unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn picking_up_inside_a_drop_zone_does_not_drop() {
+    let runtime = create_runtime();
+    let (dropped, set_dropped) = create_signal(Vec::<u32>::new());
+    let (state, drag) = use_draggable::<u32>();
+    let (zone, drop) =
+        use_drop_zone(move |n: u32| set_dropped.update(|d| d.push(n)));
+
+    // the draggable is inside the drop zone, as in the module's example
+    let item = html::li().directive(drag, 1);
+    let list = html::ul().directive(drop, ()).child(item.clone());
+    let item: &web_sys::HtmlElement = &item;
+    let list: &web_sys::HtmlElement = &list;
+    document().body().unwrap().append_child(list).unwrap();
+    tick().await;
+
+    press(item, "Enter");
+    assert!(state.is_dragging.get_untracked());
+    assert!(zone.can_drop.get_untracked());
+    assert!(dropped.get_untracked().is_empty());
+
+    // dropping it on the zone itself drops it once
+    press(list, "Enter");
+    assert_eq!(dropped.get_untracked(), vec![1]);
+    assert!(!state.is_dragging.get_untracked());
+
+    list.remove();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn escape_cancels_a_keyboard_drag() {
+    let runtime = create_runtime();
+    let (dropped, set_dropped) = create_signal(Vec::<u32>::new());
+    let (state, drag) = use_draggable::<u32>();
+    let (_, drop) =
+        use_drop_zone(move |n: u32| set_dropped.update(|d| d.push(n)));
+
+    let item = html::li().directive(drag, 1);
+    let zone = html::ul().directive(drop, ());
+    let item: &web_sys::HtmlElement = &item;
+    let zone: &web_sys::HtmlElement = &zone;
+    let body = document().body().unwrap();
+    body.append_child(item).unwrap();
+    body.append_child(zone).unwrap();
+    tick().await;
+
+    press(item, " ");
+    assert!(state.is_dragging.get_untracked());
+    press(item, "Escape");
+    assert!(!state.is_dragging.get_untracked());
+    press(zone, "Enter");
+    assert!(dropped.get_untracked().is_empty());
+
+    item.remove();
+    zone.remove();
+    runtime.dispose();
+}
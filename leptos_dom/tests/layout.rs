@@ -0,0 +1,90 @@
+//! Run with `wasm-pack test --headless --firefox -- --features web`.
+#![cfg(all(target_arch = "wasm32", feature = "web"))]
+
+use leptos::{html, *};
+use leptos_dom::layout::{
+    use_element_size, use_window_scroll, use_window_size,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// resolves after the next animation frame, when throttled updates have run
+async fn next_frame() {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    request_animation_frame(move || {
+        _ = tx.send(());
+    });
+    _ = rx.await;
+}
+
+fn mount(el: &web_sys::HtmlElement) {
+    document().body().unwrap().append_child(el).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn window_size_is_read_immediately() {
+    let runtime = create_runtime();
+
+    let size = use_window_size();
+
+    let window = window();
+    assert_eq!(
+        size.width.get_untracked(),
+        window.inner_width().unwrap().as_f64().unwrap()
+    );
+    assert_eq!(
+        size.height.get_untracked(),
+        window.inner_height().unwrap().as_f64().unwrap()
+    );
+
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn window_scroll_follows_scrolling() {
+    let runtime = create_runtime();
+    let spacer = html::div().attr("style", "height: 10000px");
+    let spacer: &web_sys::HtmlElement = &spacer;
+    mount(spacer);
+
+    let scroll = use_window_scroll();
+    window().scroll_to_with_x_and_y(0.0, 200.0);
+    // the scroll event is dispatched before the next frame renders, and the
+    // signals are updated in the frame after it
+    next_frame().await;
+    next_frame().await;
+
+    assert_eq!(scroll.y.get_untracked(), 200.0);
+
+    window().scroll_to_with_x_and_y(0.0, 0.0);
+    spacer.remove();
+    runtime.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn element_size_is_observed() {
+    let runtime = create_runtime();
+    let node_ref = create_node_ref::<html::Div>();
+    let el = html::div()
+        .attr("style", "width: 120px; height: 40px")
+        .node_ref(node_ref);
+    let el: &web_sys::HtmlElement = &el;
+    mount(el);
+
+    let size = use_element_size(node_ref);
+    next_frame().await;
+    next_frame().await;
+
+    assert_eq!(size.width.get_untracked(), 120.0);
+    assert_eq!(size.height.get_untracked(), 40.0);
+
+    el.style().set_property("width", "80px").unwrap();
+    next_frame().await;
+    next_frame().await;
+
+    assert_eq!(size.width.get_untracked(), 80.0);
+
+    el.remove();
+    runtime.dispose();
+}
@@ -18,6 +18,9 @@ use syn::{
 pub struct Model {
     is_transparent: bool,
     is_island: bool,
+    is_lazy: bool,
+    is_async: bool,
+    serializer: Option<Path>,
     docs: Docs,
     vis: Visibility,
     name: Ident,
@@ -33,7 +36,7 @@ impl Parse for Model {
 
         let docs = Docs::new(&item.attrs);
 
-        let props = item
+        let mut props = item
             .sig
             .inputs
             .clone()
@@ -41,6 +44,26 @@ impl Parse for Model {
             .map(Prop::new)
             .collect::<Vec<_>>();
 
+        // `async` components are rendered with a `<Suspense/>`, whose
+        // fallback is passed in with an extra prop
+        let is_async = item.sig.asyncness.is_some();
+        if is_async {
+            if let Some(prop) =
+                props.iter().find(|prop| prop.name.ident == "fallback")
+            {
+                abort!(
+                    prop.name.ident,
+                    "`async` components can't have a prop named `fallback`";
+                    help = "the `fallback` prop is added by the `#[component]` macro"
+                );
+            }
+            props.push(Prop::new(parse_quote! {
+                /// Shown while the component is loading. By default this is the empty view.
+                #[prop(optional, into)]
+                fallback: ::leptos::ViewFn
+            }));
+        }
+
         // We need to remove the `#[doc = ""]` and `#[builder(_)]`
         // attrs from the function signature
         drain_filter(&mut item.attrs, |attr| match &attr.meta {
@@ -70,6 +93,9 @@ impl Parse for Model {
         Ok(Self {
             is_transparent: false,
             is_island: false,
+            is_lazy: false,
+            is_async,
+            serializer: None,
             docs,
             vis: item.vis.clone(),
             name: convert_from_snake_case(&item.sig.ident),
@@ -110,6 +136,9 @@ impl ToTokens for Model {
         let Self {
             is_transparent,
             is_island,
+            is_lazy,
+            is_async,
+            serializer,
             docs,
             vis,
             name,
@@ -149,6 +178,7 @@ impl ToTokens for Model {
 
         let props_name = format_ident!("{name}Props");
         let props_builder_name = format_ident!("{name}PropsBuilder");
+        let props_spread_name = format_ident!("{name}PropsSpread");
         let props_serialized_name = format_ident!("{name}PropsSerialized");
         let trace_name = format!("<{name} />");
 
@@ -173,6 +203,7 @@ impl ToTokens for Model {
         };
 
         let prop_names = prop_names(props);
+        let prop_spread_setters = prop_spread_setters(vis, props);
 
         let builder_name_doc = LitStr::new(
             &format!(" Props for the [`{name}`] component."),
@@ -230,9 +261,14 @@ impl ToTokens for Model {
         let hydrate_fn_name =
             Ident::new(&format!("_island_{component_id}"), name.span());
 
+        let serializer = serializer.as_ref().map_or_else(
+            || quote! { ::leptos::JsonIslandSerializer },
+            |serializer| quote! { #serializer },
+        );
         let island_serialize_props = if is_island_with_other_props {
             quote! {
-                let _leptos_ser_props = ::leptos::serde_json::to_string(&props).expect("couldn't serialize island props");
+                let _leptos_ser_props = <#serializer as ::leptos::IslandSerializer>::serialize(&props)
+                    .unwrap_or_else(|e| panic!("couldn't serialize island props: {e}"));
             }
         } else {
             quote! {}
@@ -245,6 +281,15 @@ impl ToTokens for Model {
             quote! {}
         };
 
+        // lazy islands are only hydrated once the user interacts with them
+        let island_lazy = if *is_lazy {
+            quote! {
+                .attr("data-lazy", true)
+            }
+        } else {
+            quote! {}
+        };
+
         let body_name = unmodified_fn_name_from_fn_name(&body_name);
         let body_expr = if *is_island {
             quote! {
@@ -252,6 +297,16 @@ impl ToTokens for Model {
                     #body_name(#prop_names)
                 })
             }
+        } else if *is_async {
+            // the `fallback` prop is the last one, and is not an argument of
+            // the body
+            let body_prop_names = self::prop_names(&props[..props.len() - 1]);
+            quote! {
+                ::leptos::async_component(
+                    fallback,
+                    #body_name(#body_prop_names)
+                )
+            }
         } else {
             quote! {
                 #body_name(#prop_names)
@@ -283,6 +338,7 @@ impl ToTokens for Model {
                     .attr("data-component", #component_id)
                     .attr("data-hkc", ::leptos::leptos_dom::HydrationCtx::peek_always().to_string())
                     #island_serialized_props
+                    #island_lazy
                     .child(#component)
                 }
             }
@@ -421,7 +477,9 @@ impl ToTokens for Model {
                     let prop_names = props
                         .iter()
                         .filter_map(|prop| {
-                            if prop.name.ident == "children" {
+                            if prop.name.ident == "children"
+                                || prop.prop_opts.context
+                            {
                                 None
                             } else {
                                 let name = &prop.name.ident;
@@ -437,7 +495,9 @@ impl ToTokens for Model {
                     let prop_builders = props
                         .iter()
                         .filter_map(|prop| {
-                            if prop.name.ident == "children" {
+                            if prop.name.ident == "children"
+                                || prop.prop_opts.context
+                            {
                                 None
                             } else {
                                 let name = &prop.name.ident;
@@ -480,7 +540,7 @@ impl ToTokens for Model {
             let deserialize_island_props = if is_island_with_other_props {
                 quote! {
                     let props = el.dataset().get(::leptos::wasm_bindgen::intern("props"))
-                        .and_then(|data| ::leptos::serde_json::from_str::<#props_serialized_name>(&data).ok())
+                        .and_then(|data| <#serializer as ::leptos::IslandSerializer>::deserialize::<#props_serialized_name>(&data).ok())
                         .expect("could not deserialize props");
                 }
             } else {
@@ -497,8 +557,11 @@ impl ToTokens for Model {
                     #deserialize_island_props
                     _ = ::leptos::run_as_child(move || {
                         ::leptos::SharedContext::register_island(&el);
-                        ::leptos::leptos_dom::mount_to_with_stop_hydrating(el, false, move || {
-                            #name(#island_props)
+                        // lazy islands are hydrated after the rest of the page
+                        ::leptos::leptos_dom::HydrationCtx::with_hydration_on(move || {
+                            ::leptos::leptos_dom::mount_to_with_stop_hydrating(el, false, move || {
+                                #name(#island_props)
+                            })
                         })
                     });
                 }
@@ -536,6 +599,25 @@ impl ToTokens for Model {
                 }
             }
 
+            #[doc(hidden)]
+            #vis struct #props_spread_name #impl_generics (#props_name #generics) #where_clause;
+
+            impl #impl_generics #props_spread_name #generics #where_clause {
+                #prop_spread_setters
+
+                #[allow(missing_docs)]
+                #vis fn build(self) -> #props_name #generics {
+                    self.0
+                }
+            }
+
+            impl #impl_generics ::leptos::SpreadProps for #props_name #generics #where_clause {
+                type Spread = #props_spread_name #generics;
+                fn spread(self) -> Self::Spread {
+                    #props_spread_name(self)
+                }
+            }
+
             impl #impl_generics ::leptos::DynAttrs for #props_name #generics #where_clause {
                 fn dyn_attrs(mut self, v: Vec<(&'static str, ::leptos::Attribute)>) -> Self {
                     #dyn_attrs_props
@@ -586,13 +668,65 @@ impl Model {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    pub fn is_island(mut self) -> Self {
+    pub fn is_island(mut self, args: IslandArgs) -> Self {
+        if let Some(asyncness) = self.body.sig.asyncness {
+            abort!(
+                asyncness,
+                "islands can't be `async`";
+                help = "move the `async` part of the island into a `#[component]`"
+            );
+        }
         self.is_island = true;
+        self.is_lazy = args.is_lazy;
+        self.serializer = args.serializer;
 
         self
     }
 }
 
+/// The arguments of the `#[island]` macro, as in
+/// `#[island(lazy, serializer = MySerializer)]`.
+#[derive(Default)]
+pub struct IslandArgs {
+    is_lazy: bool,
+    serializer: Option<Path>,
+}
+
+impl Parse for IslandArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        let metas =
+            syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated(
+                input,
+            )?;
+        for meta in metas {
+            match meta {
+                Meta::Path(path) if path.is_ident("lazy") => {
+                    args.is_lazy = true;
+                }
+                Meta::NameValue(meta) if meta.path.is_ident("serializer") => {
+                    let syn::Expr::Path(serializer) = meta.value else {
+                        abort!(
+                            meta.value,
+                            "the serializer must be the path of a type";
+                            help = "try `#[island(serializer = MySerializer)]`"
+                        );
+                    };
+                    args.serializer = Some(serializer.path);
+                }
+                _ => abort!(
+                    meta,
+                    "only `lazy` and `serializer` are supported";
+                    help = "try `#[island(lazy)]`, \
+                            `#[island(serializer = MySerializer)]`, or \
+                            `#[island]`"
+                ),
+            }
+        }
+        Ok(args)
+    }
+}
+
 /// A model that is more lenient in case of a syntax error in the function body,
 /// but does not actually implement the behavior of the real model. This is
 /// used to improve IDEs and rust-analyzer's auto-completion behavior in case
@@ -683,8 +817,8 @@ impl Prop {
             abort!(arg, "receiver not allowed in `fn`");
         };
 
-        let prop_opts =
-            PropOpt::from_attributes(&typed.attrs).unwrap_or_else(|e| {
+        let mut prop_opts = PropOpt::from_attributes(&typed.attrs)
+            .unwrap_or_else(|e| {
                 // TODO: replace with `.unwrap_or_abort()` once https://gitlab.com/CreepySkeleton/proc-macro-error/-/issues/17 is fixed
                 abort!(e.span(), e.to_string());
             });
@@ -699,6 +833,13 @@ impl Prop {
             );
         };
 
+        // a prop that is not passed is taken from the context
+        if prop_opts.context {
+            let ty = &typed.ty;
+            prop_opts.default =
+                Some(parse_quote!(::leptos::expect_context::<#ty>()));
+        }
+
         Self {
             docs: Docs::new(&typed.attrs),
             prop_opts,
@@ -873,6 +1014,8 @@ struct PropOpt {
     default: Option<syn::Expr>,
     into: bool,
     attrs: bool,
+    #[attribute(conflicts = [optional, optional_no_strip, strip_option, default])]
+    context: bool,
 }
 
 struct TypedBuilderOpts {
@@ -977,12 +1120,14 @@ fn prop_builder_fields(
             } else {
                 quote!()
             };
-            let skip_children_serde =
-                if is_island_with_other_props && name.ident == "children" {
-                    quote!(#[serde(skip)])
-                } else {
-                    quote!()
-                };
+            // children and props taken from the context are not serialized
+            let skip_children_serde = if is_island_with_other_props
+                && (name.ident == "children" || prop_opts.context)
+            {
+                quote!(#[serde(skip)])
+            } else {
+                quote!()
+            };
 
             let PatIdent { ident, by_ref, .. } = &name;
 
@@ -998,11 +1143,58 @@ fn prop_builder_fields(
         .collect()
 }
 
+// setters that override a prop of a spread props struct, taking the same
+// values as the ones of the builder
+fn prop_spread_setters(vis: &Visibility, props: &[Prop]) -> TokenStream {
+    props
+        .iter()
+        .map(|prop| {
+            let Prop {
+                name,
+                prop_opts,
+                ty,
+                ..
+            } = prop;
+            let ident = &name.ident;
+
+            let TypedBuilderOpts {
+                strip_option, into, ..
+            } = TypedBuilderOpts::from_opts(prop_opts, is_option(ty));
+            let value_ty = if strip_option {
+                unwrap_option(ty)
+            } else {
+                ty.clone()
+            };
+            let (value_ty, value) = if into {
+                (
+                    quote! { impl ::std::convert::Into<#value_ty> },
+                    quote! { ::std::convert::Into::into(#ident) },
+                )
+            } else {
+                (quote! { #value_ty }, quote! { #ident })
+            };
+            let value = if strip_option {
+                quote! { ::std::option::Option::Some(#value) }
+            } else {
+                value
+            };
+
+            quote! {
+                #[allow(missing_docs)]
+                #vis fn #ident(mut self, #ident: #value_ty) -> Self {
+                    self.0.#ident = #value;
+                    self
+                }
+            }
+        })
+        .collect()
+}
+
 fn prop_serializer_fields(vis: &Visibility, props: &[Prop]) -> TokenStream {
     props
         .iter()
         .filter_map(|prop| {
-            if prop.name.ident == "children" {
+            if prop.name.ident == "children" || prop.prop_opts.context {
                 None
             } else {
                 let Prop {
@@ -38,10 +38,16 @@ impl Default for Mode {
 mod params;
 mod view;
 use crate::component::unmodified_fn_name_from_fn_name;
-use view::{client_template::render_template, render_view};
+use view::{
+    client_template::render_template, control_flow::lower_control_flow,
+    normalize::normalize_tags, render_view,
+    static_template::render_static_template, validate::validate,
+};
 mod component;
+mod query_params;
 mod slice;
 mod slot;
+mod typed_route;
 
 /// The `view` macro uses RSX (like JSX, but Rust!) It follows most of the
 /// same rules as HTML, with the following differences:
@@ -79,7 +85,7 @@ mod slot;
 /// ```
 ///
 /// 3. Components (functions annotated with `#[component]`) can be inserted as camel-cased tags. (Generics
-///    on components are specified as `<Component<T>/>`, not the turbofish `<Component::<T>/>`.)
+///    on components are specified as `<Component<T>/>` or with the turbofish `<Component::<T>/>`.)
 /// ```rust
 /// # use leptos::*;
 /// # let runtime = create_runtime();
@@ -289,6 +295,198 @@ mod slot;
 /// # runtime.dispose();
 /// ```
 ///
+/// 12. A block that starts with `move` followed by an `if`, `match`, or `for` expression is rerun
+///     whenever the signals it reads change, as if it were written `move || { }`. Each branch or
+///     arm can render a different type of view, and an `if` without an `else` renders nothing
+///     when its condition is false. The body of a `for` loop is rendered again for every item
+///     whenever the list changes, unless it starts with a `key = ...;` statement, in which case
+///     the items are rendered with keys by the `<For/>` component. As in the `key` of `<For/>`,
+///     the pattern of the loop is bound to a reference to the item in that statement. Blocks
+///     without `move` are ordinary Rust blocks, and are rendered once.
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// #[derive(Clone)]
+/// struct Todo {
+///     id: usize,
+///     title: &'static str,
+/// }
+///
+/// let (count, set_count) = create_signal(0);
+/// let (items, set_items) = create_signal(vec!["a", "b"]);
+/// let (todos, set_todos) = create_signal(vec![Todo { id: 0, title: "Write" }]);
+///
+/// view! {
+///   <div>
+///     {move if count.get() > 5 {
+///       view! { <p>"Big"</p> }
+///     } else {
+///       view! { <p>"Small"</p> }
+///     }}
+///     {move match count.get() {
+///       0 => "Zero",
+///       n => view! { <strong>{n}</strong> },
+///     }}
+///     <ul>
+///       {move for item in items.get() {
+///         view! { <li>{item}</li> }
+///       }}
+///     </ul>
+///     <ul>
+///       {move for todo in todos.get() {
+///         key = todo.id;
+///         view! { <li>{todo.title}</li> }
+///       }}
+///     </ul>
+///   </div>
+/// }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
+/// 13. A props struct that was already built can be spread into a component with `..props`
+///     directly after its name. Any props that follow override the ones in the struct, which
+///     makes it easy to configure a component programmatically or to forward its props
+///     through a wrapper component.
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// #[component]
+/// fn UserCard(#[prop(into)] name: String, admin: bool) -> impl IntoView {
+///   view! { <p>{name} {admin}</p> }
+/// }
+///
+/// #[component]
+/// fn AdminCard(props: UserCardProps) -> impl IntoView {
+///   view! { <UserCard ..props admin=true/> }
+/// }
+///
+/// let props = UserCardProps::builder().name("Ann").admin(false).build();
+/// view! { <AdminCard props/> }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
+/// 14. The tag name of an element can be given at runtime with a block, as in `<{tag}>`, where
+///     `tag` is a `String`, a `&str`, or any other type that implements `AsRef<str>`, such as
+///     an enum of the allowed tags. Attributes and children work as on any other element. A
+///     name that is not a valid tag name is rendered as a `<div>`.
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// #[derive(Clone, Copy)]
+/// enum Heading {
+///   H1,
+///   H2,
+/// }
+///
+/// impl AsRef<str> for Heading {
+///   fn as_ref(&self) -> &str {
+///     match self {
+///       Heading::H1 => "h1",
+///       Heading::H2 => "h2",
+///     }
+///   }
+/// }
+///
+/// let level = Heading::H2;
+/// view! { <{level} class="title">"Hello"</{level}> }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
+/// 15. Several classes or styles can be set at once with an object, as in
+///     `class:{ active: is_active(), "text-lg": true }` or `style:{ color: color(), "font-size": "12px" }`,
+///     which sets each of them as `class=("active", move || is_active())` would. A value that is
+///     computed is rerun whenever the signals it reads change, while literals, variables, and closures are
+///     used as they are. A function that returns pairs of class names and whether they are present,
+///     such as a `HashMap<&str, bool>`, can also be passed in parentheses as `class=(move || ...)`.
+/// ```rust
+/// # use leptos::*;
+/// # use std::collections::HashMap;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (is_active, set_is_active) = create_signal(false);
+/// let (size, set_size) = create_signal(12);
+/// let classes = move || HashMap::from([("active", is_active.get()), ("disabled", false)]);
+///
+/// view! {
+///   <button
+///     class:{ active: is_active.get(), "text-lg": true }
+///     style:{ "font-size": format!("{}px", size.get()), color: "red" }
+///     on:click=move |_| set_is_active.update(|active| *active = !*active)
+///   >
+///     "Toggle"
+///   </button>
+///   <p class=(move || classes())>"Status"</p>
+/// }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
+/// 16. The HTML in a view is checked at compile time for markup that the browser would parse into
+///     a different structure than the one that is rendered, which would break hydration: void
+///     elements like `<br>` with children, elements that cannot be nested, like a `<div>` inside a
+///     `<p>` or a `<tr>` directly inside a `<table>`, and attributes that are set more than once.
+///     Elements and attributes that are not known are warned about (on nightly), unless the view
+///     starts with `#![allow(unknown_html)]`.
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! {
+///   #![allow(unknown_html)]
+///   <div hx-get="/items" hx-swap="outerHTML">"Load"</div>
+/// }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
+/// ```compile_fail
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// // the browser would render the `<div>` after the `<p>`
+/// view! { <p><div>"Oops"</div></p> }
+/// # ;
+/// # runtime.dispose();
+/// ```
+///
+/// 17. Form elements can be bound two-way to a signal, such as an [`RwSignal`](https://docs.rs/leptos/latest/leptos/struct.RwSignal.html),
+///     with `bind:` attributes: the element shows the value of the signal, and the signal is
+///     updated when the user edits the element.
+///     * `bind:value` binds the `value` of an `<input>`, `<textarea>` or `<select>` to a `String`.
+///       While an input method editor (IME) is composing text, the signal is only updated once
+///       the composition has ended.
+///     * `bind:checked` binds the `checked` state of a checkbox to a `bool`.
+///     * `bind:group` binds a group of radio buttons to a `String`, which holds the `value`
+///       attribute of the selected radio button.
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let name = create_rw_signal(String::new());
+/// let subscribe = create_rw_signal(false);
+/// let plan = create_rw_signal("free".to_string());
+///
+/// view! {
+///   <input type="text" bind:value=name/>
+///   <input type="checkbox" bind:checked=subscribe/>
+///   <input type="radio" value="free" bind:group=plan/>
+///   <input type="radio" value="pro" bind:group=plan/>
+/// }
+/// # ;
+/// # };
+/// # runtime.dispose();
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::*;
@@ -324,6 +522,7 @@ mod slot;
 )]
 pub fn view(tokens: TokenStream) -> TokenStream {
     let tokens: proc_macro2::TokenStream = tokens.into();
+    let (allow_unknown_html, tokens) = view_inner_attribute(tokens);
     let mut tokens = tokens.into_iter();
 
     let first = tokens.next();
@@ -357,10 +556,18 @@ pub fn view(tokens: TokenStream) -> TokenStream {
             .chain(tokens)
             .collect()
     };
+    let tokens = lower_control_flow(normalize_tags(tokens));
     let config = rstml::ParserConfig::default().recover_block(true);
     let parser = rstml::Parser::new(config);
     let (nodes, errors) = parser.parse_recoverable(tokens).split_vec();
     let errors = errors.into_iter().map(|e| e.emit_as_expr_tokens());
+    for issue in validate(&nodes, allow_unknown_html) {
+        if issue.is_warning {
+            emit_warning!(issue.span, issue.message);
+        } else {
+            emit_error!(issue.span, issue.message);
+        }
+    }
     let nodes_output = render_view(
         &nodes,
         Mode::default(),
@@ -376,6 +583,32 @@ pub fn view(tokens: TokenStream) -> TokenStream {
     .into()
 }
 
+// a view can start with `#![allow(unknown_html)]`, so that the elements and
+// attributes that are not known are not warned about
+fn view_inner_attribute(
+    tokens: proc_macro2::TokenStream,
+) -> (bool, proc_macro2::TokenStream) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    match tokens.as_slice() {
+        [TokenTree::Punct(pound), TokenTree::Punct(bang), TokenTree::Group(attr), rest @ ..]
+            if pound.as_char() == '#'
+                && bang.as_char() == '!'
+                && attr.delimiter() == proc_macro2::Delimiter::Bracket =>
+        {
+            if attr.stream().to_string().replace(' ', "")
+                != "allow(unknown_html)"
+            {
+                abort!(
+                    attr,
+                    "only `#![allow(unknown_html)]` is supported in the `view!` macro"
+                );
+            }
+            (true, rest.iter().cloned().collect())
+        }
+        _ => (false, tokens.into_iter().collect()),
+    }
+}
+
 fn normalized_call_site(site: proc_macro::Span) -> Option<String> {
     cfg_if::cfg_if! {
         if #[cfg(all(debug_assertions, feature = "nightly"))] {
@@ -392,15 +625,57 @@ fn normalized_call_site(site: proc_macro::Span) -> Option<String> {
 
 /// An optimized, cached template for client-side rendering. Follows the same
 /// syntax as the [view!] macro. In hydration or server-side rendering mode,
-/// behaves exactly as the `view` macro. In client-side rendering mode, uses a `<template>`
-/// node to efficiently render the element. Should only be used with a single root element.
+/// behaves exactly as the `view` macro. In client-side rendering mode, with the `template_macro`
+/// feature enabled, uses a `<template>` node to efficiently render the element. Should only be used with a single root element.
+///
+/// A template that is entirely static, with no components, blocks, event listeners,
+/// or other dynamic parts, is compiled into a single HTML string in every mode. It is
+/// rendered as is on the server, and each instance in the browser is a clone of a
+/// `<template>` that is only parsed once, without creating each element separately.
+/// This makes it a good fit for large static sections, like marketing copy or icons,
+/// which can be used inside a normal view:
+///
+/// ```rust
+/// # use leptos::*;
+/// #[component]
+/// fn Close() -> impl IntoView {
+///     template! {
+///         <svg viewBox="0 0 24 24" width="24" height="24">
+///             <path d="M6 6l12 12M18 6L6 18" stroke="currentColor"/>
+///         </svg>
+///     }
+/// }
+///
+/// # let runtime = create_runtime();
+/// view! {
+///     <button aria-label="Close"><Close/></button>
+/// }
+/// # ;
+/// # runtime.dispose();
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro]
 pub fn template(tokens: TokenStream) -> TokenStream {
-    if cfg!(feature = "csr") {
-        match parse(tokens) {
+    if let Some(template) = parse(tokens.clone())
+        .ok()
+        .and_then(|nodes| render_static_template(&nodes))
+    {
+        template.into()
+    } else if cfg!(all(feature = "csr", feature = "template_macro")) {
+        let template = match parse(tokens.clone()) {
             Ok(nodes) => render_template(&nodes),
             Err(error) => error.to_compile_error(),
+        };
+        let view = proc_macro2::TokenStream::from(view(tokens));
+        // the `<template>` is only used in the browser
+        quote! {
+            {
+                #[cfg(target_arch = "wasm32")]
+                let view = ::leptos::IntoView::into_view(#template);
+                #[cfg(not(target_arch = "wasm32"))]
+                let view = ::leptos::IntoView::into_view(#view);
+                view
+            }
         }
         .into()
     } else {
@@ -480,21 +755,10 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 /// fn my_snake_case_component() -> impl IntoView {}
 /// ```
 ///
-/// * You can pass generic arguments, and they can either be defined in a `where` clause
-/// or inline in the generic block, but not in an `impl` in function argument position.
-///
-/// ```compile_error
-/// // ❌ This won't work.
-/// # use leptos::*;
-/// use leptos::html::Div;
-///
-/// #[component]
-/// fn MyComponent(render_prop: impl Fn() -> HtmlElement<Div>) -> impl IntoView {
-/// }
-/// ```
+/// * You can pass generic arguments, and they can either be defined in a `where` clause,
+///   inline in the generic block, or as an `impl Trait` in function argument position.
 ///
 /// ```
-/// // ✅ Do this instead
 /// # use leptos::*;
 /// use leptos::html::Div;
 ///
@@ -511,8 +775,20 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 ///     render_prop: T,
 /// ) -> impl IntoView {
 /// }
+///
+/// // or
+/// #[component]
+/// fn MyComponent3(render_prop: impl Fn() -> HtmlElement<Div>) -> impl IntoView {
+/// }
 /// ```
 ///
+///   When the generic arguments cannot be inferred from the props, for example because an
+///   optional prop of a generic type is not passed, they can be given in the `view` macro as
+///   `<MyComponent<T>/>` or `<MyComponent::<T>/>`. Each `impl Trait` is an additional generic
+///   argument that follows the ones that are declared, in the order of the props. This is
+///   also how to use `#[prop(into)]` on a prop whose type is a generic argument, which can
+///   only be converted into once that type is known.
+///
 /// 5. You can access the children passed into the component with the `children` property, which takes
 ///    an argument of the type `Children`. This is an alias for `Box<dyn FnOnce() -> Fragment>`.
 ///    If you need `children` to be a `Fn` or `FnMut`, you can use the `ChildrenFn` or `ChildrenFnMut`
@@ -559,6 +835,10 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 /// * `#[prop(optional_no_strip)]`: The same as `optional`, but requires values to be passed as `None` or
 ///   `Some(T)` explicitly. This means that the optional property can be omitted (and be `None`), or explicitly
 ///   specified as either `None` or `Some(T)`.
+/// * `#[prop(context)]`: If the user does not specify this property when they use the component,
+///   it will be taken from the context with [`expect_context`](https://docs.rs/leptos/latest/leptos/fn.expect_context.html).
+///   The props of an island that are taken from the context are not serialized, so their type does
+///   not need to implement `Serialize`.
 /// ```rust
 /// # use leptos::*;
 ///
@@ -586,6 +866,39 @@ pub fn template(tokens: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// ## Async Components
+/// A component can be an `async fn`, which can `.await` data (for example, from a server
+/// function) before returning its view. The macro adds an optional `fallback` prop, which
+/// takes a function that returns the view shown while the component is loading (by default,
+/// nothing), so an `async` component can't declare a prop with that name.
+///
+/// As a view can't be serialized, the body only runs in the browser, once, when the component
+/// is created: it is not rerun when the signals it reads change. The server renders the
+/// `fallback`, which the browser also renders until the body has loaded, so a page hydrates
+/// without a mismatch. To render data on the server, load it with a resource and a
+/// [`Suspense`](https://docs.rs/leptos/latest/leptos/fn.Suspense.html) instead. Islands can't
+/// be `async`.
+/// ```rust
+/// # use leptos::*;
+/// async fn load_count() -> i32 {
+///     // load the count from somewhere
+///     # 42
+/// }
+///
+/// #[component]
+/// async fn Count(label: &'static str) -> impl IntoView {
+///     let count = load_count().await;
+///     view! { <p>{label} ": " {count}</p> }
+/// }
+///
+/// #[component]
+/// pub fn App() -> impl IntoView {
+///     view! {
+///       <Count label="Count" fallback=|| view! { <p>"Loading..."</p> }/>
+///     }
+/// }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
@@ -662,6 +975,58 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// HTML isn't present in the DOM, even if hidden, it is never sent and not available
 /// to the client at all.
 ///
+/// ## Lazy Islands
+/// By default, every island is hydrated as soon as the app's WASM has loaded,
+/// which runs its component function in the browser. An island can instead be
+/// marked `#[island(lazy)]`, in which case it is left as the HTML rendered on
+/// the server until the user first interacts with it: hovers over it, focuses
+/// it, presses a pointer or key in it, or changes an input in it. It is then
+/// hydrated from its serialized props before the event reaches its handlers,
+/// so the event is not lost.
+///
+/// This makes the page interactive sooner when it has many islands, as only
+/// those the user interacts with are ever run in the browser. Any state that
+/// is not passed to the island as a prop, like its signals and resources, is
+/// created when it is hydrated, so resources created in a lazy island are
+/// loaded again at that point, rather than resumed from the server.
+///
+/// ```rust,ignore
+/// #[island(lazy)]
+/// pub fn Comment(comment: Comment) -> impl IntoView {
+///     let (expanded, set_expanded) = create_signal(false);
+///     view! {
+///         <button on:click=move |_| set_expanded.update(|e| *e = !*e)>
+///             {comment.author}
+///         </button>
+///         <Show when=expanded>{comment.text.clone()}</Show>
+///     }
+/// }
+/// ```
+///
+/// ## Serializing Props
+/// The props of an island are serialized as JSON into the HTML rendered on the
+/// server. An island can use another format, for example a more compact or a
+/// compressed one, by passing a type that implements
+/// [`IslandSerializer`](https://docs.rs/leptos/latest/leptos/trait.IslandSerializer.html)
+/// as in `#[island(serializer = MySerializer)]`.
+///
+/// A prop whose type does not implement `Serialize`, such as a signal or a
+/// handle to some shared state, can be marked `#[prop(context)]` instead. It is
+/// not serialized, and is taken from the context when it is not passed: on the
+/// server, from the context provided by the server components that render the
+/// island, and in the browser, from the context provided by the islands that
+/// contain it.
+///
+/// ```rust,ignore
+/// #[island(serializer = Base64Json)]
+/// pub fn Chart(
+///     points: Vec<(f64, f64)>,
+///     #[prop(context)] selected: RwSignal<Option<usize>>,
+/// ) -> impl IntoView {
+///     // ...
+/// }
+/// ```
+///
 /// ## Example
 /// ```rust,ignore
 /// use leptos::*;
@@ -704,14 +1069,16 @@ pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
-pub fn island(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+pub fn island(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as component::IslandArgs);
+
     let Ok(mut dummy) = syn::parse::<DummyModel>(s.clone()) else {
         return s;
     };
     let parse_result = syn::parse::<component::Model>(s);
 
     if let (ref mut unexpanded, Ok(model)) = (&mut dummy, parse_result) {
-        let expanded = model.is_island().into_token_stream();
+        let expanded = model.is_island(args).into_token_stream();
         if !matches!(unexpanded.vis, Visibility::Public(_)) {
             unexpanded.vis = Visibility::Public(Pub {
                 span: unexpanded.vis.span(),
@@ -842,6 +1209,78 @@ pub fn island(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// 4. A component can take any number of slots. A slot that can be passed more than once is
+///    collected into a `Vec`, in the order in which it is passed, and a slot that may not be
+///    passed at all is `#[prop(optional)]`, like any other prop.
+///
+/// 5. Slots can be rendered with a value, like render props: if the `children` of a slot are a
+///    [`TypedChildrenFn<T>`](../leptos/type.TypedChildrenFn.html), the value they are called with is
+///    bound with `let:` where the slot is used.
+///
+/// ```
+/// # use leptos::*;
+/// #[derive(Clone)]
+/// struct User {
+///     name: String,
+///     email: String,
+/// }
+///
+/// #[slot]
+/// struct Column {
+///     #[prop(into)]
+///     header: String,
+///     children: TypedChildrenFn<User>,
+/// }
+///
+/// #[slot]
+/// struct Empty {
+///     children: ChildrenFn,
+/// }
+///
+/// #[component]
+/// fn Table(
+///     rows: Vec<User>,
+///     #[prop(optional)] column: Vec<Column>,
+///     #[prop(optional)] empty: Option<Empty>,
+/// ) -> impl IntoView {
+///     if rows.is_empty() {
+///         return empty.map(|empty| (empty.children)()).into_view();
+///     }
+///     let headers = column
+///         .iter()
+///         .map(|column| view! { <th>{column.header.clone()}</th> })
+///         .collect_view();
+///     let rows = rows
+///         .into_iter()
+///         .map(|row| {
+///             let cells = column
+///                 .iter()
+///                 .map(|column| view! { <td>{(column.children)(row.clone())}</td> })
+///                 .collect_view();
+///             view! { <tr>{cells}</tr> }
+///         })
+///         .collect_view();
+///     view! {
+///         <table>
+///             <thead><tr>{headers}</tr></thead>
+///             <tbody>{rows}</tbody>
+///         </table>
+///     }
+///     .into_view()
+/// }
+///
+/// #[component]
+/// fn Users(users: Vec<User>) -> impl IntoView {
+///     view! {
+///         <Table rows=users>
+///             <Column slot header="Name" let:user>{user.name}</Column>
+///             <Column slot header="Email" let:user>{user.email}</Column>
+///             <Empty slot>"No users yet."</Empty>
+///         </Table>
+///     }
+/// }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
@@ -870,12 +1309,25 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// - `name`: sets the identifier for the server function’s type, which is a struct created
 ///    to hold the arguments (defaults to the function identifier in PascalCase)
 /// - `prefix`: a prefix at which the server function handler will be mounted (defaults to `/api`)
-///    your prefix must begin with `/`. Otherwise your function won't be found.
+///    your prefix must begin with `/`. Otherwise your function won't be found. This can also be
+///    the path to a `&str` constant, so that every server function in a module can share one
+///    (`const API: &str = "/api";` and `prefix = API`)
+/// - `version`: a version that is added to the prefix, so that `prefix = "/api", version = "v2"`
+///   mounts the handler under `/api/v2`
 /// - `endpoint`: specifies the exact path at which the server function handler will be mounted,
-///   relative to the prefix (defaults to the function name followed by unique hash)
+///   relative to the prefix (defaults to the function name followed by unique hash). Set this
+///   for server functions called by external clients, so that their URLs stay stable. The paths of
+///   all registered server functions can be listed with `server_fn::axum::server_fn_endpoints` or
+///   `server_fn::actix::server_fn_endpoints`
+///   Segments like `{id}` are path parameters, which are filled in from the argument of the same
+///   name (see [`server_fn::rest`](../server_fn/rest/index.html))
+/// - `method`: the HTTP method the server function is called with, which chooses its input
+///   encoding: `"GET"` (`GetUrl`), `"POST"` (`PostUrl`), `"PUT"` (`PutJson`), `"PATCH"`
+///   (`PatchJson`), or `"DELETE"` (`DeleteUrl`). This cannot be combined with `input`
 /// - `input`: the encoding for the arguments (defaults to `PostUrl`)
 /// - `output`: the encoding for the response (defaults to `Json`)
-/// - `client`: a custom `Client` implementation that will be used for this server fn
+/// - `client`: a custom `Client` implementation that will be used for this server fn (see
+///   [Choosing a Client](#choosing-a-client))
 /// - `encoding`: (legacy, may be deprecated in future) specifies the encoding, which may be one
 ///   of the following (not case sensitive)
 ///     - `"Url"`: `POST` request with URL-encoded arguments and JSON response
@@ -883,17 +1335,47 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///     - `"Cbor"`: `POST` request with CBOR-encoded arguments and response
 ///     - `"GetCbor"`: `GET` request with URL-encoded arguments and CBOR response
 /// - `req` and `res` specify the HTTP request and response types to be used on the server (these
-///   should usually only be necessary if you are integrating with a server other than Actix/Axum)
+///   should usually only be necessary if you are integrating with a server other than Actix/Axum,
+///   or if both the `actix` and `axum` features are enabled, in which case Axum's types are used
+///   by default)
 /// - `impl_from`: specifies whether to implement trait `From` for server function's type or not.
 ///   By default, if a server function only has one argument, the macro automatically implements the `From` trait
 ///   to convert from the argument type to the server function type, and vice versa, allowing you to convert
 ///   between them easily. Setting `impl_from` to `false` disables this, which can be necessary for argument types
 ///   for which this would create a conflicting implementation. (defaults to `true`)
+/// - `protocol`: set to `"websocket"` for a server function that takes a single
+///   `WebsocketChannel` argument and communicates with the client over a WebSocket, which is
+///   opened on the client with `connect()` on the server function's type (requires the
+///   `websocket` feature; see [`server_fn::websocket`](../server_fn/websocket/index.html))
+/// - `middleware`: a middleware layer, or an array of layers (`middleware = [AuthLayer, LogLayer]`),
+///   to wrap this server function in. Each layer wraps the ones before it. Middleware for every
+///   server function under a path prefix can be registered with `server_fn::axum::register_middleware`
+///   or `server_fn::actix::register_middleware`.
+/// - `retry`: a `RetryPolicy` (`retry = RetryPolicy::new(3)`) that failed calls to this server
+///   function are retried with on the client, which opts it in to retries even if it is not called
+///   with a `GET` request (see [`server_fn::retry`](../server_fn/retry/index.html))
+/// - `idempotent`: whether calling this server function more than once has the same effect as
+///   calling it once, so it is retried according to the global policy set with
+///   `server_fn::retry::set_retry_policy`. (defaults to `true` for `GET` requests and `false`
+///   otherwise, so that mutations are never retried unless they opt in)
+/// - `cache`: a `Duration` (`cache = Duration::from_secs(60)`) for which successful results of
+///   calling this server function on the client are cached, keyed by its arguments. Identical
+///   calls made while one is in flight share its request, and cached results can be cleared with
+///   `server_fn::cache::invalidate`. Use `create_server_fn_resource` to seed the cache with the
+///   results serialized during server-side rendering (see [`server_fn::cache`](../server_fn/cache/index.html))
+/// - `validate`: set to `true` to validate the arguments on the server before the body runs, with
+///   an implementation of `server_fn::validation::Validate` for the server function's struct. Invalid
+///   arguments return `ServerFnError::Validation` with a `422` status, whose per-field messages can
+///   be shown in a form with `Action::field_errors` (see [`server_fn::validation`](../server_fn/validation/index.html))
+/// - `batch`: set to `true` to send calls to this server function that are made at the same time
+///   as calls to other batched server functions in a single request, through a
+///   `server_fn::batch::BatchClient` that wraps the `client` (see [`server_fn::batch`](../server_fn/batch/index.html))
 ///
 /// ```rust,ignore
 /// #[server(
 ///   name = SomeStructName,
 ///   prefix = "/my_api",
+///   version = "v2",
 ///   endpoint = "my_fn",
 ///   input = Cbor,
 ///   output = Json
@@ -932,6 +1414,54 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// pub async fn with_default_value(#[server(default)] values: Vec<u32>) /* etc. */
 /// ```
 ///
+/// ## Choosing a Client
+///
+/// By default, server functions are called with a `fetch` request in the browser. With the
+/// `dynamic-client` feature, they use the `DynClient` instead, which sends each call with a
+/// `ServerFnClient` that is chosen while the app is running: one set for the whole app with
+/// `set_client`, one set for a single call with `with_client`, or otherwise the default for the
+/// target. This lets desktop and mobile apps send calls with `reqwest` or their own transport,
+/// and tests answer them with a `MockClient` (see
+/// [`server_fn::client::transport`](../server_fn/client/transport/index.html)). Server functions
+/// that take multipart form data or use WebSockets keep the default client.
+///
+/// ## OpenAPI Descriptions
+///
+/// With the `openapi` feature, server functions whose arguments are serialized with `serde`
+/// derive `JsonSchema` for their arguments and register a description of their endpoint, so
+/// that an OpenAPI document describing all of them can be generated with
+/// [`server_fn::openapi::spec`](../server_fn/openapi/fn.spec.html). Their argument and output
+/// types must implement `JsonSchema`.
+///
+/// ## Generic Server Functions
+///
+/// A server function can be generic over types, as long as it lists every set of types it can be
+/// called with in a `#[register]` attribute. Each of them gets its own endpoint, ending in the name
+/// of the type, and the argument struct is generic over the same types. This lets a server function
+/// be written once against a trait, like a data layer with a real and a mock implementation, with
+/// the implementation chosen by each call, or by a feature through a type alias:
+/// ```rust,ignore
+/// pub trait TodoStore: Send + 'static {
+///     fn list() -> impl Future<Output = Result<Vec<Todo>, ServerFnError>> + Send;
+/// }
+///
+/// #[server]
+/// #[register(<Database>, <Mock>)]
+/// pub async fn list_todos<S: TodoStore>() -> Result<Vec<Todo>, ServerFnError> {
+///     S::list().await
+/// }
+///
+/// // in the client
+/// let todos = list_todos::<Mock>().await;
+/// // the arguments of an action are created with `new`
+/// let action = create_server_action::<ListTodos<Database>>();
+/// action.dispatch(ListTodos::new());
+/// ```
+/// The types and the trait must exist in the client as well, although the trait can be implemented
+/// only on the server. Calling the server function with a type that is not registered fails to
+/// compile. Generic server functions cannot use a `custom` wrapper, and their arguments must be
+/// serialized with `serde`.
+///
 /// ## Important Notes
 /// - **Server functions must be `async`.** Even if the work being done inside the function body
 ///   can run synchronously on the server, from the client’s perspective it involves an asynchronous
@@ -946,9 +1476,8 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///   ad hoc HTTP API endpoint, not a magic formula. Any server function can be accessed by any HTTP
 ///   client. You should take care to sanitize any data being returned from the function to ensure it
 ///   does not leak data that should exist only on the server.
-/// - **Server functions can’t be generic.** Because each server function creates a separate API endpoint,
-///   it is difficult to monomorphize. As a result, server functions cannot be generic (for now?) If you need to use
-///   a generic function, you can define a generic inner function called by multiple concrete server functions.
+/// - **Generic server functions must be registered.** Because each server function creates a separate API endpoint,
+///   a generic server function must list the types it can be called with, as described above.
 /// - **Arguments and return types must be serializable.** We support a variety of different encodings,
 ///   but one way or another arguments need to be serialized to be sent to the server and deserialized
 ///   on the server, and the return type must be serialized on the server and deserialized on the client.
@@ -992,6 +1521,66 @@ pub fn params_derive(
     }
 }
 
+/// Derives [`TypedRoute`](https://docs.rs/leptos_router/latest/leptos_router/trait.TypedRoute.html)
+/// for a struct, from a `#[route(path = "...")]` attribute.
+///
+/// Each `:param`, `:param?` or `*splat` segment in the path must have a field of
+/// the same name, and every field must appear in the path, or the derive fails
+/// to compile. Fields for optional `:param?` segments must be `Option<_>`.
+///
+/// This also derives `Params` for the struct, so that `use_params` parses
+/// exactly the params the route declares: do not derive `Params` separately.
+///
+/// ```rust,ignore
+/// #[derive(Clone, PartialEq, TypedRoute)]
+/// #[route(path = "/users/:id/posts/:post?")]
+/// struct UserPost {
+///     id: usize,
+///     post: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(TypedRoute, attributes(route))]
+pub fn typed_route_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input).and_then(|ast| typed_route::typed_route_impl(&ast))
+    {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives [`QueryParams`](https://docs.rs/leptos_router/latest/leptos_router/trait.QueryParams.html)
+/// for a struct, so that it can be both parsed from and written to the URL
+/// search query.
+///
+/// This also derives `Params` for the struct: do not derive `Params`
+/// separately. Fields can be customized with `#[query(rename = "...")]`,
+/// `#[query(default)]`, `#[query(default = expr)]`, and
+/// `#[query(with = path)]`, where `path::parse` and `path::to_string` are
+/// used instead of `FromStr` and `ToString`.
+///
+/// ```rust,ignore
+/// #[derive(Clone, PartialEq, QueryParams)]
+/// struct Search {
+///     #[query(rename = "q")]
+///     text: Option<String>,
+///     #[query(default = 1)]
+///     page: usize,
+/// }
+/// ```
+#[proc_macro_derive(QueryParams, attributes(query))]
+pub fn query_params_derive(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    match syn::parse(input)
+        .and_then(|ast| query_params::query_params_impl(&ast))
+    {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 pub(crate) fn attribute_value(attr: &KeyedAttribute) -> &syn::Expr {
     match attr.value() {
         Some(value) => value,
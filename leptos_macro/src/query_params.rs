@@ -0,0 +1,183 @@
+use crate::typed_route::is_option;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Error, Expr, LitStr, Path};
+
+enum FieldDefault {
+    Required,
+    Default,
+    Expr(Box<Expr>),
+}
+
+struct QueryField<'a> {
+    field: &'a syn::Field,
+    key: String,
+    default: FieldDefault,
+    with: Option<Path>,
+}
+
+impl<'a> QueryField<'a> {
+    fn parse(field: &'a syn::Field) -> syn::Result<Self> {
+        let ident = field.ident.as_ref().expect("named fields");
+        let mut key = ident.to_string();
+        let mut default = FieldDefault::Required;
+        let mut with = None;
+        for attr in &field.attrs {
+            if !attr.path().is_ident("query") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    key = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    default = if meta.input.peek(syn::Token![=]) {
+                        FieldDefault::Expr(Box::new(meta.value()?.parse()?))
+                    } else {
+                        FieldDefault::Default
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("with") {
+                    with = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "expected `rename = \"...\"`, `default`, `default = \
+                         ...` or `with = ...`",
+                    ))
+                }
+            })?;
+        }
+        Ok(Self {
+            field,
+            key,
+            default,
+            with,
+        })
+    }
+
+    fn parse_from_map(&self) -> TokenStream {
+        let ident = &self.field.ident;
+        let key = &self.key;
+        let parse = match &self.with {
+            Some(with) => quote! {
+                #with::parse(value).map_err(|e| {
+                    ::leptos_router::ParamsError::invalid(#key, value, e)
+                })?
+            },
+            None => quote! {
+                ::leptos_router::parse_query_value(#key, value)?
+            },
+        };
+        let (parse, missing) = if is_option(&self.field.ty) {
+            (
+                quote! { ::std::option::Option::Some(#parse) },
+                quote! { ::std::option::Option::None },
+            )
+        } else {
+            (
+                parse,
+                quote! {
+                    return ::std::result::Result::Err(
+                        ::leptos_router::ParamsError::MissingParam(
+                            #key.to_string()
+                        )
+                    )
+                },
+            )
+        };
+        let missing = match &self.default {
+            FieldDefault::Required => missing,
+            FieldDefault::Default => {
+                quote! { ::std::default::Default::default() }
+            }
+            FieldDefault::Expr(expr) => quote! { #expr },
+        };
+        quote! {
+            #ident: match map.get(#key) {
+                ::std::option::Option::Some(value) => #parse,
+                ::std::option::Option::None => #missing,
+            }
+        }
+    }
+
+    fn write_to_map(&self) -> TokenStream {
+        let ident = &self.field.ident;
+        let key = &self.key;
+        let format = match &self.with {
+            Some(with) => quote! { #with::to_string(value) },
+            None => quote! { ::std::string::ToString::to_string(value) },
+        };
+        if is_option(&self.field.ty) {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#ident {
+                    map.insert(#key.to_string(), #format);
+                }
+            }
+        } else {
+            quote! {
+                let value = &self.#ident;
+                map.insert(#key.to_string(), #format);
+            }
+        }
+    }
+}
+
+pub fn query_params_impl(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    let fields =
+        match &ast.data {
+            syn::Data::Struct(syn::DataStruct {
+                fields: syn::Fields::Named(fields),
+                ..
+            }) => fields
+                .named
+                .iter()
+                .map(QueryField::parse)
+                .collect::<syn::Result<Vec<_>>>()?,
+            _ => return Err(Error::new(
+                ast.span(),
+                "`#[derive(QueryParams)]` only supports structs with named \
+                 fields",
+            )),
+        };
+    for (idx, field) in fields.iter().enumerate() {
+        if fields[..idx].iter().any(|other| other.key == field.key) {
+            return Err(Error::new(
+                field.field.span(),
+                format!("the query key `{}` is used more than once", field.key),
+            ));
+        }
+    }
+
+    let keys = fields.iter().map(|field| &field.key);
+    let from_map = fields.iter().map(QueryField::parse_from_map);
+    let to_map = fields.iter().map(QueryField::write_to_map);
+
+    Ok(quote! {
+        impl #impl_generics ::leptos_router::Params for #name #ty_generics
+        #where_clause
+        {
+            fn from_map(
+                map: &::leptos_router::ParamsMap
+            ) -> ::std::result::Result<Self, ::leptos_router::ParamsError> {
+                ::std::result::Result::Ok(Self { #(#from_map,)* })
+            }
+        }
+
+        impl #impl_generics ::leptos_router::QueryParams for #name #ty_generics
+        #where_clause
+        {
+            const KEYS: &'static [&'static str] = &[#(#keys),*];
+
+            fn to_map(&self) -> ::leptos_router::ParamsMap {
+                let mut map = ::leptos_router::ParamsMap::new();
+                #(#to_map)*
+                map
+            }
+        }
+    })
+}
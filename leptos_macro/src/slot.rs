@@ -5,7 +5,8 @@ use attribute_derive::FromAttr;
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
-    parse::Parse, parse_quote, Field, ItemStruct, LitStr, Meta, Type,
+    parse::Parse, parse_quote, AngleBracketedGenericArguments, Field,
+    GenericArgument, ItemStruct, LitStr, Meta, PathArguments, Type, TypePath,
     Visibility,
 };
 
@@ -116,11 +117,11 @@ impl ToTokens for Model {
             #prop_docs
             #[derive(::leptos::typed_builder_macro::TypedBuilder)]
             #[builder(doc, crate_module_path=::leptos::typed_builder)]
-            #vis struct #name #generics #where_clause {
+            #vis struct #name #impl_generics #where_clause {
                 #prop_builder_fields
             }
 
-            impl #generics From<#name #generics> for Vec<#name #generics> #where_clause {
+            impl #impl_generics From<#name #generics> for Vec<#name #generics> #where_clause {
                 fn from(value: #name #generics) -> Self {
                     vec![value]
                 }
@@ -267,16 +268,49 @@ fn prop_builder_fields(vis: &Visibility, props: &[Prop]) -> TokenStream {
 
             let builder_docs = prop_to_doc(prop, PropDocStyle::Inline);
 
+            // children rendered with a value take a closure, so that the type
+            // of the value bound with `let:` can be inferred
+            let typed_children = match typed_children_value(ty) {
+                Some(value_ty) if name == "children" => quote! {
+                    #[builder(setter(transform = |children: impl Fn(#value_ty) -> ::leptos::Fragment + 'static| {
+                        ::std::rc::Rc::new(children) as ::leptos::TypedChildrenFn<#value_ty>
+                    }))]
+                },
+                _ => quote! {},
+            };
+
             quote! {
                 #docs
                 #builder_docs
                 #builder_attrs
+                #typed_children
                 #vis #name: #ty,
             }
         })
         .collect()
 }
 
+/// The type of the value of `TypedChildrenFn<T>`.
+fn typed_children_value(ty: &Type) -> Option<&Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "TypedChildrenFn" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(AngleBracketedGenericArguments {
+            args,
+            ..
+        }) if args.len() == 1 => match args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn generate_prop_docs(props: &[Prop]) -> TokenStream {
     let required_prop_docs = props
         .iter()
@@ -0,0 +1,282 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Error, LitStr};
+
+enum Segment {
+    Static(String),
+    Param { name: String, optional: bool },
+    Splat(String),
+}
+
+fn parse_segments(path: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = path.value();
+    if !value.starts_with('/') {
+        return Err(Error::new(
+            path.span(),
+            "typed route paths must be absolute, i.e., begin with `/`",
+        ));
+    }
+    let segments = value
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if let Some(name) = s.strip_prefix(':') {
+                match name.strip_suffix('?') {
+                    Some(name) => Segment::Param {
+                        name: name.to_string(),
+                        optional: true,
+                    },
+                    None => Segment::Param {
+                        name: name.to_string(),
+                        optional: false,
+                    },
+                }
+            } else if let Some(name) = s.strip_prefix('*') {
+                Segment::Splat(name.to_string())
+            } else {
+                Segment::Static(s.to_string())
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(pos) =
+        segments.iter().position(|s| matches!(s, Segment::Splat(_)))
+    {
+        if pos != segments.len() - 1 {
+            return Err(Error::new(
+                path.span(),
+                "a wildcard `*` segment must be the last segment of the path",
+            ));
+        }
+    }
+    Ok(segments)
+}
+
+pub(crate) fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+pub fn typed_route_impl(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    let mut path = None;
+    for attr in &ast.attrs {
+        if attr.path().is_ident("route") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("path") {
+                    path = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `path = \"...\"`"))
+                }
+            })?;
+        }
+    }
+    let Some(path) = path else {
+        return Err(Error::new(
+            name.span(),
+            "`#[derive(TypedRoute)]` requires a `#[route(path = \"...\")]` \
+             attribute",
+        ));
+    };
+    let segments = parse_segments(&path)?;
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named.iter().collect::<Vec<_>>(),
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        }) => Vec::new(),
+        _ => {
+            return Err(Error::new(
+                ast.span(),
+                "`#[derive(TypedRoute)]` only supports structs with named \
+                 fields or unit structs",
+            ))
+        }
+    };
+
+    // every field must be a param, and every param must be a field
+    for field in &fields {
+        let ident = field.ident.as_ref().expect("named fields").to_string();
+        let segment = segments.iter().find(|s| match s {
+            Segment::Param { name, .. } | Segment::Splat(name) => {
+                *name == ident
+            }
+            Segment::Static(_) => false,
+        });
+        match segment {
+            None => {
+                return Err(Error::new(
+                    field.span(),
+                    format!(
+                        "field `{ident}` does not appear as a `:{ident}` or \
+                         `*{ident}` segment in the route path {:?}",
+                        path.value()
+                    ),
+                ))
+            }
+            Some(Segment::Param { optional: true, .. })
+                if !is_option(&field.ty) =>
+            {
+                return Err(Error::new(
+                    field.ty.span(),
+                    format!(
+                        "`:{ident}?` is an optional segment, so field \
+                         `{ident}` should be an `Option<_>`"
+                    ),
+                ))
+            }
+            _ => {}
+        }
+    }
+    for segment in &segments {
+        if let Segment::Param { name: param, .. } | Segment::Splat(param) =
+            segment
+        {
+            let has_field = fields.iter().any(|field| {
+                field.ident.as_ref().map(|i| i == param).unwrap_or(false)
+            });
+            if !has_field {
+                return Err(Error::new(
+                    path.span(),
+                    format!(
+                        "the route path declares a param `{param}`, but \
+                         `{name}` has no field named `{param}`"
+                    ),
+                ));
+            }
+        }
+    }
+
+    let push_segments = segments.iter().map(|segment| match segment {
+        Segment::Static(s) => {
+            let s = format!("/{s}");
+            quote! { path.push_str(#s); }
+        }
+        Segment::Param {
+            name,
+            optional: false,
+        } => {
+            let ident = syn::Ident::new(name, path.span());
+            quote! {
+                path.push('/');
+                path.push_str(&::leptos_router::escape(
+                    &::std::string::ToString::to_string(&self.#ident)
+                ));
+            }
+        }
+        Segment::Param {
+            name,
+            optional: true,
+        } => {
+            let ident = syn::Ident::new(name, path.span());
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#ident {
+                    path.push('/');
+                    path.push_str(&::leptos_router::escape(
+                        &::std::string::ToString::to_string(value)
+                    ));
+                }
+            }
+        }
+        Segment::Splat(name) => {
+            let ident = syn::Ident::new(name, path.span());
+            quote! {
+                let splat = ::std::string::ToString::to_string(&self.#ident);
+                if !splat.is_empty() {
+                    path.push('/');
+                    path.push_str(&splat);
+                }
+            }
+        }
+    });
+
+    let parse_fields = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named fields");
+        let ident_string = ident.to_string();
+        let ty = &field.ty;
+        if is_option(ty) {
+            quote! {
+                #ident: <#ty as ::leptos_router::IntoParam>::into_param(
+                    map.get(#ident_string).map(::std::string::String::as_str),
+                    #ident_string
+                )?
+            }
+        } else {
+            quote! {
+                #ident: {
+                    let value = map
+                        .get(#ident_string)
+                        .ok_or_else(|| ::leptos_router::ParamsError::MissingParam(
+                            #ident_string.to_string()
+                        ))?;
+                    value.parse::<#ty>().map_err(|e| {
+                        ::leptos_router::ParamsError::invalid(#ident_string, value, e)
+                    })?
+                }
+            }
+        }
+    });
+    let construct = if matches!(
+        ast.data,
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        })
+    ) {
+        quote! { Self }
+    } else {
+        quote! { Self { #(#parse_fields,)* } }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::leptos_router::TypedRoute for #name #ty_generics
+        #where_clause
+        {
+            const PATH: &'static str = #path;
+
+            fn to_path(&self) -> ::std::string::String {
+                let mut path = ::std::string::String::new();
+                #(#push_segments)*
+                if path.is_empty() {
+                    path.push('/');
+                }
+                path
+            }
+        }
+
+        impl #impl_generics ::leptos_router::Params for #name #ty_generics
+        #where_clause
+        {
+            fn from_map(
+                map: &::leptos_router::ParamsMap
+            ) -> ::std::result::Result<Self, ::leptos_router::ParamsError> {
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+
+        impl #impl_generics ::leptos_router::ToHref for #name #ty_generics
+        #where_clause
+        {
+            fn to_href(
+                &self
+            ) -> ::std::boxed::Box<dyn Fn() -> ::std::string::String + '_> {
+                let path = ::leptos_router::TypedRoute::to_path(self);
+                ::std::boxed::Box::new(move || path.clone())
+            }
+        }
+    })
+}
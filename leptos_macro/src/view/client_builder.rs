@@ -3,8 +3,9 @@ use super::{
     expr_to_ident, fancy_class_name, fancy_style_name,
     ide_helper::IdeTagHelper,
     is_ambiguous_element, is_custom_element, is_math_ml_element,
-    is_self_closing, is_svg_element, parse_event_name,
+    is_svg_element, parse_event_name,
     slot_helper::{get_slot, slot_to_tokens},
+    two_way_binding_method,
 };
 use crate::{attribute_value, view::directive_call_from_attribute_node};
 use leptos_hot_reload::parsing::{is_component_node, value_to_string};
@@ -175,7 +176,11 @@ pub(crate) fn element_to_tokens(
         // collect close_tag name to emit semantic information for IDE.
         let mut ide_helper_close_tag = IdeTagHelper::new();
         let close_tag = node.close_tag.as_ref().map(|c| &c.name);
-        let name = if is_custom_element(&tag) {
+        let name = if let NodeName::Block(block) = name {
+            // the tag name is only known at runtime, as in `<{tag}>`
+            parent_type = TagType::Html;
+            quote! { ::leptos::leptos_dom::html::dynamic(#block) }
+        } else if is_custom_element(&tag) {
             let name = node.name().to_string();
             // link custom ident to name span for IDE docs
             let custom = Ident::new("custom", node.name().span());
@@ -223,6 +228,7 @@ pub(crate) fn element_to_tokens(
                     || fancy_class_name(name, node).is_some()
                     || name.starts_with("style:")
                     || fancy_style_name(name, node).is_some()
+                    || name.starts_with("bind:")
                 {
                     None
                 } else {
@@ -232,6 +238,20 @@ pub(crate) fn element_to_tokens(
                 None
             }
         });
+        // two-way bindings come after the attributes, so that `bind:group`
+        // can read the `value` of the element
+        let two_way_bindings = node.attributes().iter().filter_map(|node| {
+            if let NodeAttribute::Attribute(node) = node {
+                let name = node.key.to_string();
+                if name.trim().starts_with("bind:") {
+                    Some(attribute_to_tokens(node, global_class))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        });
         let bindings = node.attributes().iter().filter_map(|node| {
             use rstml::node::NodeBlock;
             use syn::{Expr, ExprRange, RangeLimits, Stmt};
@@ -286,15 +306,6 @@ pub(crate) fn element_to_tokens(
             Some(class) => quote! { .classes(#class) },
         };
 
-        if is_self_closing(node) && !node.children.is_empty() {
-            proc_macro_error::abort!(
-                node.name().span(),
-                format!(
-                    "<{tag}> is a self-closing tag and cannot have children."
-                )
-            );
-        }
-
         let children = node
             .children
             .iter()
@@ -345,6 +356,7 @@ pub(crate) fn element_to_tokens(
             #(#ide_helper_close_tag)*
             #name
                 #(#attrs)*
+                #(#two_way_bindings)*
                 #(#bindings)*
                 #(#class_attrs)*
                 #(#style_attrs)*
@@ -430,6 +442,12 @@ pub(crate) fn attribute_to_tokens(
         quote! {
             #on(#event_type, #handler)
         }
+    } else if let Some(name) = name.strip_prefix("bind:") {
+        let value = attribute_value(node);
+        let method = two_way_binding_method(node, name);
+        quote! {
+            .#method(#value)
+        }
     } else if let Some(name) = name.strip_prefix("prop:") {
         let value = attribute_value(node);
         let prop = match &node.key {
@@ -294,6 +294,10 @@ fn attr_to_tokens(
     if name == "ref" {
         abort!(span, "node_ref not yet supported in template! macro")
     }
+    // Two-way bindings
+    else if name.starts_with("bind:") {
+        abort!(span, "`bind:` not yet supported in template! macro")
+    }
     // Event Handlers
     else if name.starts_with("on:") {
         let (event_type, handler) =
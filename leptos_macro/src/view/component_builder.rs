@@ -4,12 +4,14 @@ use super::{
     client_builder::{fragment_to_tokens, TagType},
     event_from_attribute_node,
 };
-use crate::view::directive_call_from_attribute_node;
+use crate::view::{
+    directive_call_from_attribute_node, GENERICS_KEY, SPREAD_PROPS_KEY,
+};
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
 use rstml::node::{NodeAttribute, NodeElement};
 use std::collections::HashMap;
-use syn::spanned::Spanned;
+use syn::{spanned::Spanned, Expr, ExprBlock, ExprPath, PathArguments, Stmt};
 
 pub(crate) fn component_to_tokens(
     node: &NodeElement,
@@ -51,6 +53,12 @@ pub(crate) fn component_to_tokens(
         }
     });
 
+    // props spread with `..props` are overridden by the ones that follow
+    let spread_props = attrs
+        .clone()
+        .find(|attr| attr.key.to_string() == SPREAD_PROPS_KEY)
+        .and_then(|attr| attr.value());
+
     let props = attrs
         .clone()
         .filter(|attr| {
@@ -59,6 +67,8 @@ pub(crate) fn component_to_tokens(
                 && !attr.key.to_string().starts_with("on:")
                 && !attr.key.to_string().starts_with("attr:")
                 && !attr.key.to_string().starts_with("use:")
+                && attr.key.to_string() != SPREAD_PROPS_KEY
+                && attr.key.to_string() != GENERICS_KEY
         })
         .map(|attr| {
             let name = &attr.key;
@@ -125,6 +135,7 @@ pub(crate) fn component_to_tokens(
         events.into_iter().chain(directives).collect::<Vec<_>>();
 
     let dyn_attrs = attrs
+        .clone()
         .filter(|attr| attr.key.to_string().starts_with("attr:"))
         .filter_map(|attr| {
             let name = &attr.key.to_string();
@@ -216,12 +227,27 @@ pub(crate) fn component_to_tokens(
         quote! { .#slot(#value) }
     });
 
-    let generics = &node.open_tag.generics;
-    let generics = if generics.lt_token.is_some() {
-        quote! { ::#generics }
-    } else {
-        quote! {}
-    };
+    // the generics are passed as `component:generics={generics::<T>}`
+    let generics = attrs
+        .clone()
+        .find(|attr| attr.key.to_string() == GENERICS_KEY)
+        .and_then(|attr| match attr.value()? {
+            Expr::Block(ExprBlock { block, .. }) => {
+                match block.stmts.first()? {
+                    Stmt::Expr(Expr::Path(ExprPath { path, .. }), _) => {
+                        match &path.segments.last()?.arguments {
+                            PathArguments::AngleBracketed(generics) => {
+                                Some(quote! { #generics })
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
 
     let name_ref = quote_spanned! {name.span()=>
         &#name
@@ -231,8 +257,13 @@ pub(crate) fn component_to_tokens(
         .build()
     };
 
-    let component_props_builder = quote_spanned! {name.span()=>
-        ::leptos::component_props_builder(#name_ref #generics)
+    let component_props_builder = match spread_props {
+        Some(spread_props) => quote_spanned! {name.span()=>
+            ::leptos::component_props_spread(#name_ref #generics, #spread_props)
+        },
+        None => quote_spanned! {name.span()=>
+            ::leptos::component_props_builder(#name_ref #generics)
+        },
     };
 
     #[allow(unused_mut)] // used in debug
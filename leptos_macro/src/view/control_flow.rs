@@ -0,0 +1,178 @@
+//! Lowers blocks in the view that consist of `move` followed by an `if`,
+//! `match`, or `for` expression, as in `{move if ready.get() { .. }}`, into
+//! closures, so that they are rerun whenever the signals they read change,
+//! like `{move || ...}` blocks are. Blocks without `move` are ordinary Rust
+//! and are left as they are.
+//!
+//! Each branch of an `if` or arm of a `match` is converted into a `View`, so
+//! that they can render different types. The body of a `for` loop is rendered
+//! once for each item, without keys, unless it starts with a `key = ...;`
+//! statement, in which case the loop is rendered by `<For/>` with that key.
+//!
+//! This runs on the tokens of the view before they are parsed, as `move if`
+//! is not a valid block of Rust.
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use quote::quote_spanned;
+use syn::{
+    parse_quote_spanned, spanned::Spanned, Block, Expr, ExprAssign,
+    ExprForLoop, ExprPath, Stmt,
+};
+
+pub(crate) fn lower_control_flow(tokens: TokenStream) -> TokenStream {
+    let mut output = Vec::new();
+    let mut is_attribute_value = false;
+    for token in tokens {
+        let lowered = match &token {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace
+                    && !is_attribute_value =>
+            {
+                lower_block(group)
+            }
+            _ => None,
+        };
+        is_attribute_value =
+            matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '=');
+        output.push(lowered.unwrap_or(token));
+    }
+    output.into_iter().collect()
+}
+
+fn lower_block(group: &Group) -> Option<TokenTree> {
+    let mut tokens = group.stream().into_iter();
+    match tokens.next()? {
+        TokenTree::Ident(ident) if ident == "move" => {}
+        _ => return None,
+    }
+    let rest = tokens.collect::<TokenStream>();
+    match rest.clone().into_iter().next()? {
+        TokenTree::Ident(ident)
+            if ident == "if" || ident == "match" || ident == "for" => {}
+        _ => return None,
+    }
+
+    let span = group.span();
+    let lowered = match syn::parse2::<Expr>(rest) {
+        Ok(Expr::ForLoop(for_loop)) if for_loop.label.is_none() => {
+            lower_for(for_loop)
+        }
+        Ok(expr @ (Expr::If(_) | Expr::Match(_))) => {
+            let lowered = lower_branches(expr);
+            quote_spanned! {span=> move || #lowered }
+        }
+        Ok(expr) => syn::Error::new(
+            expr.span(),
+            "expected an `if`, `match`, or unlabeled `for` expression after \
+             `move`",
+        )
+        .to_compile_error(),
+        Err(e) => e.to_compile_error(),
+    };
+    let mut block = Group::new(Delimiter::Brace, lowered);
+    block.set_span(span);
+    Some(TokenTree::Group(block))
+}
+
+fn lower_for(for_loop: ExprForLoop) -> TokenStream {
+    let span = for_loop.span();
+    let ExprForLoop {
+        pat, expr, body, ..
+    } = for_loop;
+    let mut stmts = body.stmts;
+    match stmts.first().and_then(key_expr).cloned() {
+        Some(key) => {
+            let rest = stmts.split_off(1);
+            quote_spanned! {span=>
+                ::leptos::component_view(
+                    #[allow(clippy::needless_borrows_for_generic_args)]
+                    &::leptos::For,
+                    ::leptos::component_props_builder(&::leptos::For)
+                        .each(move || #expr)
+                        .key(move |#pat| #key)
+                        .children(move |#pat| {
+                            ::leptos::IntoView::into_view({ #(#rest)* })
+                        })
+                        .build(),
+                )
+            }
+        }
+        None => quote_spanned! {span=>
+            move || {
+                ::std::iter::IntoIterator::into_iter(#expr)
+                    .map(|#pat| ::leptos::IntoView::into_view({ #(#stmts)* }))
+                    .collect::<::std::vec::Vec<_>>()
+            }
+        },
+    }
+}
+
+// the key of a `for` loop is given by a first statement like `key = item.id;`
+fn key_expr(stmt: &Stmt) -> Option<&Expr> {
+    let Stmt::Expr(Expr::Assign(ExprAssign { left, right, .. }), Some(_)) =
+        stmt
+    else {
+        return None;
+    };
+    match left.as_ref() {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("key") => {
+            Some(right)
+        }
+        _ => None,
+    }
+}
+
+// converts each branch of an `if` or `match` into a `View`
+fn lower_branches(expr: Expr) -> Expr {
+    match expr {
+        Expr::If(mut expr_if) => {
+            into_view_block(&mut expr_if.then_branch);
+            let (else_token, else_branch) = match expr_if.else_branch.take() {
+                Some((else_token, else_branch)) => match *else_branch {
+                    Expr::Block(mut block) => {
+                        into_view_block(&mut block.block);
+                        (else_token, Expr::Block(block))
+                    }
+                    other => (else_token, lower_branches(other)),
+                },
+                // renders nothing when the condition is false
+                None => {
+                    let span = expr_if.then_branch.span();
+                    (
+                        Default::default(),
+                        parse_quote_spanned! {span=>
+                            { ::leptos::IntoView::into_view(()) }
+                        },
+                    )
+                }
+            };
+            expr_if.else_branch = Some((else_token, Box::new(else_branch)));
+            Expr::If(expr_if)
+        }
+        Expr::Match(mut expr_match) => {
+            for arm in &mut expr_match.arms {
+                let body = &arm.body;
+                *arm.body = parse_quote_spanned! {body.span()=>
+                    ::leptos::IntoView::into_view(#body)
+                };
+                if arm.comma.is_none() {
+                    arm.comma = Some(Default::default());
+                }
+            }
+            Expr::Match(expr_match)
+        }
+        other => {
+            let span = other.span();
+            parse_quote_spanned! {span=>
+                ::leptos::IntoView::into_view(#other)
+            }
+        }
+    }
+}
+
+fn into_view_block(block: &mut Block) {
+    let span = block.span();
+    *block = parse_quote_spanned! {span=>
+        { ::leptos::IntoView::into_view(#block) }
+    };
+}
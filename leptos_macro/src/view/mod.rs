@@ -12,13 +12,18 @@ use syn::{
 pub mod client_builder;
 pub mod client_template;
 pub mod component_builder;
+pub(crate) mod control_flow;
 pub mod ide_helper;
+pub(crate) mod normalize;
 pub mod server_template;
 pub mod slot_helper;
+pub(crate) mod static_template;
 #[cfg(test)]
 mod tests;
+pub(crate) mod validate;
 
 pub(crate) use ide_helper::*;
+pub(crate) use normalize::{GENERICS_KEY, SPREAD_PROPS_KEY};
 
 pub(crate) fn render_view(
     nodes: &[Node],
@@ -591,3 +596,21 @@ pub(crate) fn directive_call_from_attribute_node(
 
     quote! { .directive(#handler, #[allow(clippy::useless_conversion)] #param) }
 }
+
+/// The [`HtmlElement`](leptos_dom::html::HtmlElement) method that a `bind:`
+/// two-way binding expands to.
+pub(crate) fn two_way_binding_method(
+    attr: &KeyedAttribute,
+    binding: &str,
+) -> Ident {
+    match binding {
+        "value" | "checked" | "group" => {
+            Ident::new(&format!("bind_{binding}"), attr.key.span())
+        }
+        _ => proc_macro_error::abort!(
+            attr.key,
+            "unknown two-way binding `bind:{}`", binding;
+            help = "try `bind:value`, `bind:checked` or `bind:group`"
+        ),
+    }
+}
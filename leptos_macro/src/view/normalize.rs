@@ -0,0 +1,354 @@
+//! Rewrites the syntax in tags that cannot be parsed into an equivalent one
+//! that can:
+//!
+//! - the generics of a component, as in `<List<Vec<i32>>/>` or
+//!   `<List::<Vec<i32>>/>`, become an attribute of its open tag, so that they
+//!   can be any type and do not need to be repeated in its close tag
+//! - props spread into a component, as in `<UserCard ..props/>`, become an
+//!   attribute that can be parsed like the other attributes of the component,
+//!   as in `<UserCard spread:props={props}/>`
+//! - the classes or styles of an element given as an object, as in
+//!   `class:{ active: is_active(), "text-lg": true }`, become one attribute
+//!   for each of them, as in `class=("active", move || is_active())`
+//! - a function that returns the classes of an element as pairs of names and
+//!   whether they are present, as in `class=(move || classes())`, becomes the
+//!   value of its `class` attribute
+//!
+//! Only a `..props` that directly follows the name of the component is
+//! rewritten, so that it cannot be confused with a range in the value of
+//! another attribute.
+
+use proc_macro2::{
+    Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree,
+};
+use quote::{quote, quote_spanned};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Expr, LitStr, Token,
+};
+
+/// The key of the attribute into which the props spread into a component are
+/// rewritten.
+pub(crate) const SPREAD_PROPS_KEY: &str = "spread:props";
+
+/// The key of the attribute into which the generics of a component are
+/// rewritten.
+pub(crate) const GENERICS_KEY: &str = "component:generics";
+
+pub(crate) fn normalize_tags(tokens: TokenStream) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut idx = 0;
+    // whether the tokens are the attributes of an element's open tag
+    let mut in_element_tag = false;
+    while idx < tokens.len() {
+        if in_element_tag {
+            if is_punct(tokens.get(idx), '>') {
+                in_element_tag = false;
+            } else if let Some(attributes) = object_attributes(&tokens, idx) {
+                output.extend(attributes);
+                idx += 3;
+                continue;
+            } else if let Some(attribute) = class_list_attribute(&tokens, idx) {
+                output.extend(attribute);
+                idx += 3;
+                continue;
+            }
+        }
+        if !is_punct(tokens.get(idx), '<') {
+            output.push(tokens[idx].clone());
+            idx += 1;
+            continue;
+        }
+
+        let is_close_tag = is_punct(tokens.get(idx + 1), '/');
+        let path_start = if is_close_tag { idx + 2 } else { idx + 1 };
+        let path_end = path_end(&tokens, path_start);
+        output.extend_from_slice(&tokens[idx..path_end]);
+        idx = path_end;
+        let is_component = matches!(
+            last_ident(&tokens[path_start..path_end]),
+            Some(ident) if ident.to_string().starts_with(char::is_uppercase)
+        );
+        in_element_tag = !is_component && !is_close_tag;
+        if !is_component {
+            continue;
+        }
+
+        let generics_start = if is_turbofish(&tokens, path_end) {
+            path_end + 2
+        } else {
+            path_end
+        };
+        let generics_end = generics_end(&tokens, generics_start);
+        if generics_end > generics_start {
+            // the generics of a close tag are the ones of its open tag
+            if !is_close_tag {
+                output.extend(generics_attribute(
+                    &tokens[generics_start..generics_end],
+                ));
+            }
+            idx = generics_end;
+        }
+
+        if is_close_tag || !is_spread(&tokens, idx) {
+            continue;
+        }
+
+        let span = tokens[idx].span();
+        let expr_end = expr_end(&tokens, idx + 2);
+        if expr_end == idx + 2 {
+            continue;
+        }
+        let expr = tokens[idx + 2..expr_end].iter().cloned().collect();
+        output.extend(keyed_attribute(SPREAD_PROPS_KEY, span, expr));
+        idx = expr_end;
+    }
+    output.into_iter().collect()
+}
+
+// the generics of a component can be any type, rather than only the
+// identifiers that can be parsed as the generics of a tag, so they are passed
+// as a path, as in `component:generics={generics::<Vec<T>>}`
+fn generics_attribute(generics: &[TokenTree]) -> Vec<TokenTree> {
+    let span = generics[0].span();
+    let mut joint_colon = Punct::new(':', Spacing::Joint);
+    joint_colon.set_span(span);
+    let mut colon = Punct::new(':', Spacing::Alone);
+    colon.set_span(span);
+    let path = [
+        TokenTree::Ident(Ident::new("generics", span)),
+        TokenTree::Punct(joint_colon),
+        TokenTree::Punct(colon),
+    ]
+    .into_iter()
+    .chain(generics.iter().cloned())
+    .collect();
+    keyed_attribute(GENERICS_KEY, span, path)
+}
+
+struct ObjectEntry {
+    name: LitStr,
+    value: Expr,
+}
+
+impl Parse for ObjectEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(LitStr) {
+            input.parse()?
+        } else {
+            let ident = Ident::parse_any(input)?;
+            LitStr::new(&ident.unraw().to_string(), ident.span())
+        };
+        input.parse::<Token![:]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
+// `class:{ active: is_active() }` becomes `class=("active", move || is_active())`,
+// and likewise for `style:{ ... }`
+fn object_attributes(
+    tokens: &[TokenTree],
+    idx: usize,
+) -> Option<Vec<TokenTree>> {
+    let Some(TokenTree::Ident(key)) = tokens.get(idx) else {
+        return None;
+    };
+    let Some(TokenTree::Group(object)) = tokens.get(idx + 2) else {
+        return None;
+    };
+    if (key != "class" && key != "style")
+        || !is_punct(tokens.get(idx + 1), ':')
+        || object.delimiter() != Delimiter::Brace
+    {
+        return None;
+    }
+
+    let entries = Punctuated::<ObjectEntry, Token![,]>::parse_terminated
+        .parse2(object.stream())
+        .unwrap_or_else(|error| {
+            proc_macro_error::abort!(
+                error.span(),
+                "{}", error;
+                help = "the {} of an element are given as `{}:{{ name: value, \"other-name\": value }}`",
+                if key == "class" { "classes" } else { "styles" }, key
+            )
+        });
+    let mut attributes = Vec::new();
+    for ObjectEntry { name, value } in entries {
+        // the values that are computed are rerun when the signals they read
+        // change, while literals, variables, and closures are used as they are
+        let value = match value {
+            Expr::Lit(_) | Expr::Path(_) | Expr::Closure(_) => {
+                quote! { #value }
+            }
+            _ => quote_spanned! {value.span()=> move || #value },
+        };
+        attributes.extend(quote! { #key=(#name, #value) });
+    }
+    Some(attributes)
+}
+
+// `class=(move || classes())` becomes the list of the classes that are present
+fn class_list_attribute(
+    tokens: &[TokenTree],
+    idx: usize,
+) -> Option<Vec<TokenTree>> {
+    let Some(TokenTree::Ident(key)) = tokens.get(idx) else {
+        return None;
+    };
+    let Some(TokenTree::Group(group)) = tokens.get(idx + 2) else {
+        return None;
+    };
+    let is_assignment = matches!(
+        tokens.get(idx + 1),
+        Some(TokenTree::Punct(punct))
+            if punct.as_char() == '=' && punct.spacing() == Spacing::Alone
+    );
+    if key != "class"
+        || !is_assignment
+        || group.delimiter() != Delimiter::Parenthesis
+    {
+        return None;
+    }
+    // a tuple, as in `class=("name", value)`, sets a single class
+    let Ok(Expr::Paren(paren)) = syn::parse2::<Expr>(quote! { #group }) else {
+        return None;
+    };
+
+    let classes = paren.expr;
+    let value = quote_spanned! {group.span()=>
+        { ::leptos::leptos_dom::class_list(#classes) }
+    };
+    Some(quote! { #key=#value }.into_iter().collect())
+}
+
+fn keyed_attribute(
+    key: &str,
+    span: Span,
+    value: TokenStream,
+) -> Vec<TokenTree> {
+    let mut tokens = Vec::new();
+    for (idx, part) in key.split(':').enumerate() {
+        if idx > 0 {
+            tokens.push(TokenTree::Punct(Punct::new(':', Spacing::Alone)));
+        }
+        tokens.push(TokenTree::Ident(Ident::new(part, span)));
+    }
+    tokens.push(TokenTree::Punct(Punct::new('=', Spacing::Alone)));
+    tokens.push(TokenTree::Group(Group::new(Delimiter::Brace, value)));
+    tokens
+}
+
+fn is_punct(token: Option<&TokenTree>, ch: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == ch)
+}
+
+fn is_path_separator(tokens: &[TokenTree], idx: usize) -> bool {
+    matches!(
+        tokens.get(idx),
+        Some(TokenTree::Punct(punct))
+            if punct.as_char() == ':' && punct.spacing() == Spacing::Joint
+    ) && is_punct(tokens.get(idx + 1), ':')
+}
+
+fn is_spread(tokens: &[TokenTree], idx: usize) -> bool {
+    matches!(
+        tokens.get(idx),
+        Some(TokenTree::Punct(punct))
+            if punct.as_char() == '.' && punct.spacing() == Spacing::Joint
+    ) && is_punct(tokens.get(idx + 1), '.')
+        && !is_punct(tokens.get(idx + 2), '.')
+        && !is_punct(tokens.get(idx + 2), '=')
+}
+
+fn last_ident(tokens: &[TokenTree]) -> Option<&Ident> {
+    tokens.iter().rev().find_map(|token| match token {
+        TokenTree::Ident(ident) => Some(ident),
+        _ => None,
+    })
+}
+
+fn is_turbofish(tokens: &[TokenTree], idx: usize) -> bool {
+    is_path_separator(tokens, idx) && is_punct(tokens.get(idx + 2), '<')
+}
+
+// the end of a tag name like `leptos::UserCard`
+fn path_end(tokens: &[TokenTree], mut idx: usize) -> usize {
+    if !matches!(tokens.get(idx), Some(TokenTree::Ident(_))) {
+        return idx;
+    }
+    idx += 1;
+    while is_path_separator(tokens, idx)
+        && matches!(tokens.get(idx + 2), Some(TokenTree::Ident(_)))
+    {
+        idx += 3;
+    }
+    idx
+}
+
+// the end of the generics of a tag, like `<T, Vec<T>>`
+fn generics_end(tokens: &[TokenTree], mut idx: usize) -> usize {
+    if !is_punct(tokens.get(idx), '<') {
+        return idx;
+    }
+    let mut depth = 0;
+    while let Some(token) = tokens.get(idx) {
+        // the `>` of an arrow, as in `fn() -> T`, does not close the generics
+        let is_arrow = idx > 0 && is_punct(tokens.get(idx - 1), '-');
+        idx += 1;
+        if is_punct(Some(token), '<') {
+            depth += 1;
+        } else if is_punct(Some(token), '>') && !is_arrow {
+            depth -= 1;
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+    idx
+}
+
+// the end of an expression made of a variable or a block, followed by any
+// field accesses, method calls, or path segments, like `props.clone()`
+fn expr_end(tokens: &[TokenTree], mut idx: usize) -> usize {
+    if !matches!(
+        tokens.get(idx),
+        Some(TokenTree::Ident(_) | TokenTree::Group(_))
+    ) {
+        return idx;
+    }
+    idx += 1;
+    loop {
+        match tokens.get(idx) {
+            Some(TokenTree::Group(group))
+                if group.delimiter() == Delimiter::Parenthesis
+                    || group.delimiter() == Delimiter::Bracket =>
+            {
+                idx += 1;
+            }
+            Some(TokenTree::Punct(punct))
+                if punct.as_char() == '.'
+                    && matches!(
+                        tokens.get(idx + 1),
+                        Some(TokenTree::Ident(_) | TokenTree::Literal(_))
+                    ) =>
+            {
+                idx += 2;
+            }
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '?' => {
+                idx += 1;
+            }
+            _ if is_path_separator(tokens, idx)
+                && matches!(tokens.get(idx + 2), Some(TokenTree::Ident(_))) =>
+            {
+                idx += 3;
+            }
+            _ => return idx,
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use super::{
     camel_case_tag_name,
+    client_builder::{self, TagType},
     component_builder::component_to_tokens,
     fancy_class_name, fancy_style_name,
     ide_helper::IdeTagHelper,
     is_custom_element, is_math_ml_element, is_self_closing, is_svg_element,
     parse_event_name,
     slot_helper::{get_slot, slot_to_tokens},
+    two_way_binding_method,
 };
 use crate::attribute_value;
 use leptos_hot_reload::parsing::{
@@ -14,7 +16,7 @@ use leptos_hot_reload::parsing::{
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 use rstml::node::{
-    KeyedAttribute, Node, NodeAttribute, NodeBlock, NodeElement,
+    KeyedAttribute, Node, NodeAttribute, NodeBlock, NodeElement, NodeName,
 };
 use std::collections::HashMap;
 use syn::spanned::Spanned;
@@ -115,6 +117,15 @@ pub(crate) fn root_element_to_tokens_ssr(
         } else {
             Some(component_to_tokens(node, global_class))
         }
+    } else if matches!(node.name(), NodeName::Block(_)) {
+        // an element with a dynamic tag name can't be part of the template
+        client_builder::element_to_tokens(
+            node,
+            TagType::Html,
+            None,
+            global_class,
+            view_marker,
+        )
     } else {
         let mut stmts_for_ide = IdeTagHelper::new();
         let mut exprs_for_compiler = Vec::<TokenStream>::new();
@@ -249,6 +260,25 @@ fn element_to_tokens_ssr(
         chunks.push(SsrElementChunks::View(quote! {
             ::leptos::IntoView::into_view(#[allow(unused_braces)] {#component})
         }));
+    } else if matches!(node.name(), NodeName::Block(_)) {
+        let element = client_builder::element_to_tokens(
+            node,
+            TagType::Html,
+            None,
+            global_class,
+            None,
+        );
+
+        if !template.is_empty() {
+            chunks.push(SsrElementChunks::String {
+                template: std::mem::take(template),
+                holes: std::mem::take(holes),
+            })
+        }
+
+        chunks.push(SsrElementChunks::View(quote! {
+            ::leptos::IntoView::into_view(#element)
+        }));
     } else {
         let tag_name = node.name().to_string();
         let tag_name = tag_name
@@ -275,6 +305,11 @@ fn element_to_tokens_ssr(
                 );
             }
         }
+        for attr in node.attributes() {
+            if let NodeAttribute::Attribute(attr) = attr {
+                two_way_binding_to_tokens_ssr(node, attr, template, holes);
+            }
+        }
         for attr in node.attributes() {
             use syn::{Expr, ExprRange, RangeLimits, Stmt};
 
@@ -442,6 +477,9 @@ fn attribute_to_tokens_ssr<'a>(
                 { _ = #value; }
             });
         }
+    } else if name.starts_with("bind:") {
+        // rendered by `two_way_binding_to_tokens_ssr`, once the other
+        // attributes are known
     } else if let Some(directive_name) = name.strip_prefix("use:") {
         let handler = syn::Ident::new(directive_name, attr.key.span());
         let value = attr.value();
@@ -506,6 +544,61 @@ fn attribute_to_tokens_ssr<'a>(
     None
 }
 
+fn two_way_binding_to_tokens_ssr(
+    node: &NodeElement,
+    attr: &KeyedAttribute,
+    template: &mut String,
+    holes: &mut Vec<TokenStream>,
+) {
+    let name = attr.key.to_string();
+    let Some(binding) = name.strip_prefix("bind:") else {
+        return;
+    };
+    two_way_binding_method(attr, binding);
+    let signal = attribute_value(attr);
+
+    template.push_str("{}");
+    holes.push(match binding {
+        "value" => quote! {
+            ::std::format!(
+                " value=\"{}\"",
+                ::leptos::leptos_dom::ssr::escape_attr(
+                    &::leptos::SignalGet::get(&#signal)
+                )
+            )
+        },
+        "checked" => quote! {
+            if ::leptos::SignalGet::get(&#signal) { " checked" } else { "" }
+        },
+        _ => {
+            let value = node
+                .attributes()
+                .iter()
+                .find_map(|attr| match attr {
+                    NodeAttribute::Attribute(attr)
+                        if attr.key.to_string() == "value" =>
+                    {
+                        attr.value()
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| {
+                    proc_macro_error::abort!(
+                        attr.key,
+                        "`bind:group` needs a `value` attribute"
+                    )
+                });
+            quote! {
+                if ::leptos::SignalGet::get(&#signal) == #value {
+                    " checked"
+                } else {
+                    ""
+                }
+            }
+        }
+    });
+}
+
 fn set_class_attribute_ssr(
     node: &NodeElement,
     template: &mut String,
@@ -1,6 +1,6 @@
 use super::{
     client_builder::{fragment_to_tokens, TagType},
-    convert_to_snake_case, ident_from_tag_name,
+    convert_to_snake_case, ident_from_tag_name, GENERICS_KEY,
 };
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
@@ -50,6 +50,7 @@ pub(crate) fn slot_to_tokens(
             !attr.key.to_string().starts_with("let:")
                 && !attr.key.to_string().starts_with("clone:")
                 && !attr.key.to_string().starts_with("attr:")
+                && attr.key.to_string() != GENERICS_KEY
         })
         .map(|attr| {
             let name = &attr.key;
@@ -0,0 +1,143 @@
+//! Renders a view that is entirely static, with no components, blocks, event
+//! listeners or other dynamic parts, into a single HTML string, which
+//! `leptos_dom::html::static_template` renders as is on the server and clones
+//! from a `<template>` in the browser.
+
+use super::{
+    is_ambiguous_element, is_math_ml_element, is_self_closing, is_svg_element,
+};
+use leptos_hot_reload::parsing::{
+    block_to_primitive_expression, is_component_node, value_to_string,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use rstml::node::{Node, NodeAttribute, NodeBlock, NodeElement, NodeName};
+use syn::{Expr, Lit};
+
+/// Renders the view with `leptos_dom::html::static_template`, if it is a
+/// single element that is entirely static.
+pub(crate) fn render_static_template(nodes: &[Node]) -> Option<TokenStream> {
+    let (tag, html) = static_html(nodes)?;
+    Some(quote! {
+        ::leptos::leptos_dom::html::static_template(#tag, #html)
+    })
+}
+
+/// The tag name and outer HTML of the view, if it is a single element that is
+/// entirely static.
+pub(crate) fn static_html(nodes: &[Node]) -> Option<(String, String)> {
+    let [Node::Element(node)] = nodes else {
+        return None;
+    };
+    let tag = node.name().to_string();
+    // an SVG or MathML element is only parsed as one inside an `<svg>` or
+    // `<math>`, so it is not cloned on its own
+    if (is_svg_element(&tag) || is_math_ml_element(&tag))
+        && !is_ambiguous_element(&tag)
+        && tag != "svg"
+        && tag != "math"
+    {
+        return None;
+    }
+    let mut html = String::new();
+    element_to_html(node, false, &mut html)?;
+    Some((tag, html))
+}
+
+fn element_to_html(
+    node: &NodeElement,
+    in_foreign: bool,
+    html: &mut String,
+) -> Option<()> {
+    if is_component_node(node) || !matches!(node.name(), NodeName::Path(_)) {
+        return None;
+    }
+    let name = node.name().to_string();
+    // `svg::a` and the like are rendered with the builders of their namespace
+    if name.contains("::") {
+        return None;
+    }
+    let in_foreign = in_foreign || name == "svg" || name == "math";
+
+    html.push('<');
+    html.push_str(&name);
+    for attr in node.attributes() {
+        let NodeAttribute::Attribute(attr) = attr else {
+            return None;
+        };
+        let key = attr.key.to_string();
+        let key = key.strip_prefix("attr:").unwrap_or(&key);
+        if DYNAMIC_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+            || DYNAMIC_ATTRIBUTES.contains(&key)
+        {
+            return None;
+        }
+        html.push(' ');
+        html.push_str(key);
+        match attr.value() {
+            None => {}
+            // `true` and `false` add or leave out the attribute at runtime
+            Some(Expr::Lit(lit)) if matches!(lit.lit, Lit::Bool(_)) => {
+                return None
+            }
+            Some(value) => {
+                let value = value_to_string(value)?;
+                html.push_str("=\"");
+                html.push_str(&html_escape::encode_double_quoted_attribute(
+                    &value,
+                ));
+                html.push('"');
+            }
+        }
+    }
+
+    if is_self_closing(node) {
+        html.push('>');
+        return Some(());
+    }
+    // only elements in SVG and MathML can be closed with `/>`
+    if in_foreign && node.children.is_empty() {
+        html.push_str("/>");
+        return Some(());
+    }
+    html.push('>');
+
+    let is_raw_text = name == "script" || name == "style";
+    for child in &node.children {
+        let text = match child {
+            Node::Element(child) => {
+                element_to_html(child, in_foreign, html)?;
+                continue;
+            }
+            Node::Text(text) => text.value_string(),
+            Node::RawText(text) => text.to_string_best(),
+            Node::Block(NodeBlock::ValidBlock(block)) => {
+                block_to_primitive_expression(block)
+                    .and_then(value_to_string)?
+            }
+            Node::Comment(_) | Node::Doctype(_) => continue,
+            Node::Block(_) | Node::Fragment(_) => return None,
+        };
+        if is_raw_text {
+            html.push_str(&text);
+        } else {
+            html.push_str(&html_escape::encode_safe(&text));
+        }
+    }
+
+    html.push_str("</");
+    html.push_str(&name);
+    html.push('>');
+    Some(())
+}
+
+// event listeners, properties, directives and the like need code that runs
+// for each instance
+const DYNAMIC_PREFIXES: &[&str] = &[
+    "on:", "prop:", "bind:", "class:", "style:", "use:", "clone:",
+];
+
+const DYNAMIC_ATTRIBUTES: &[&str] =
+    &["ref", "_ref", "ref_", "node_ref", "inner_html"];
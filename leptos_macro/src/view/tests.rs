@@ -117,3 +117,103 @@ for_all_modes! {
         <ExternalComponent on:custom.event.clear=move |_: Event| set_value(0) />
     "#
 }
+
+fn validate(input: &str) -> Vec<(String, bool)> {
+    let tokens = TokenStream::from_str(input).unwrap();
+    let nodes = rstml::parse2(tokens).unwrap();
+    crate::view::validate::validate(&nodes, false)
+        .into_iter()
+        .map(|issue| (issue.message, issue.is_warning))
+        .collect()
+}
+
+#[test]
+fn validate_valid_html() {
+    let issues = validate(
+        r##"
+        <div class="a" class=("b", true) on:click=handler data-id=1>
+            <p><span>"text"</span><br/></p>
+            <table><tbody><tr><td>"cell"</td></tr></tbody></table>
+            <svg viewBox="0 0 10 10"><a href="#"><title>"t"</title></a></svg>
+            <my-element foo="bar"/>
+        </div>
+    "##,
+    );
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn validate_errors() {
+    let issues = validate(
+        r#"
+        <p><span><div/></span></p>
+        <a href="/"><a href="/"/></a>
+        <table><tr/></table>
+        <tbody><td/></tbody>
+        <input type="text" type="number"/>
+        <br>"child"</br>
+    "#,
+    );
+    let errors = issues
+        .iter()
+        .filter(|(_, is_warning)| !is_warning)
+        .map(|(message, _)| message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(errors.len(), 6);
+    assert!(errors[0].starts_with("<div> cannot be inside a <p>"));
+    assert!(errors[1].starts_with("<a> cannot be inside another <a>"));
+    assert!(errors[2].starts_with("<tr> cannot be directly inside a <table>"));
+    assert!(errors[3].starts_with("<td> cannot be directly inside a <tbody>"));
+    assert_eq!(errors[4], "the attribute `type` is set more than once");
+    assert!(errors[5].starts_with("<br> is a self-closing tag"));
+}
+
+#[test]
+fn validate_unknown_html() {
+    let input = r#"<div colour="red"><blink/></div>"#;
+    assert_eq!(
+        validate(input),
+        [
+            (
+                "`colour` is not a known attribute of HTML elements"
+                    .to_string(),
+                true
+            ),
+            (
+                "<blink> is not a known HTML element; the name of a custom \
+                 element must contain a hyphen"
+                    .to_string(),
+                true
+            ),
+        ]
+    );
+
+    let tokens = TokenStream::from_str(input).unwrap();
+    let nodes = rstml::parse2(tokens).unwrap();
+    assert!(crate::view::validate::validate(&nodes, true).is_empty());
+}
+
+#[test]
+fn static_template() {
+    let static_html = |input: &str| {
+        let tokens = TokenStream::from_str(input).unwrap();
+        let nodes = rstml::parse2(tokens).unwrap();
+        crate::view::static_template::static_html(&nodes)
+    };
+
+    assert_eq!(
+        static_html(
+            r#"<div id="a&b" hidden><p>"1 < 2" {3}</p><img src="x.png"/></div>"#
+        ),
+        Some((
+            "div".to_string(),
+            r#"<div id="a&amp;b" hidden><p>1 &lt; 23</p><img src="x.png"></div>"#
+                .to_string()
+        ))
+    );
+    assert!(static_html(r#"<div>{value}</div>"#).is_none());
+    assert!(static_html(r#"<div on:click=handler/>"#).is_none());
+    assert!(static_html(r#"<div><Component/></div>"#).is_none());
+    assert!(static_html(r#"<path d="M0 0"/>"#).is_none());
+    assert!(static_html(r#"<div/><div/>"#).is_none());
+}
@@ -0,0 +1,519 @@
+//! Checks the HTML in a view for mistakes that can be found at compile time,
+//! in particular markup that the browser parses into a different structure
+//! than the one that was rendered, which breaks hydration:
+//!
+//! - void elements, like `<br>`, with children
+//! - elements that cannot be nested, like a `<div>` in a `<p>`, which the
+//!   browser moves out of it, or a `<tr>` directly in a `<table>`, for which it
+//!   inserts a `<tbody>`
+//! - attributes that are set more than once on the same element
+//!
+//! Elements and attributes that are not known are only warned about, and not
+//! at all in a view that starts with `#![allow(unknown_html)]`.
+
+use super::{is_math_ml_element, is_svg_element};
+use leptos_hot_reload::parsing::is_component_node;
+use proc_macro2::Span;
+use rstml::node::{Node, NodeAttribute, NodeElement, NodeName};
+use syn::{spanned::Spanned, Expr};
+
+/// A mistake found in the HTML of a view.
+pub(crate) struct Issue {
+    pub span: Span,
+    pub message: String,
+    /// Whether the mistake is only warned about.
+    pub is_warning: bool,
+}
+
+/// Checks the nodes of a view, returning the mistakes that were found.
+pub(crate) fn validate(nodes: &[Node], allow_unknown: bool) -> Vec<Issue> {
+    let mut validator = Validator {
+        allow_unknown,
+        ancestors: Vec::new(),
+        issues: Vec::new(),
+    };
+    validator.nodes(nodes);
+    validator.issues
+}
+
+struct Validator {
+    allow_unknown: bool,
+    // the names of the elements that contain the current one, up to the
+    // nearest component, as its children can be rendered anywhere
+    ancestors: Vec<String>,
+    issues: Vec<Issue>,
+}
+
+impl Validator {
+    fn nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            match node {
+                Node::Element(el) => self.element(el),
+                Node::Fragment(fragment) => self.nodes(&fragment.children),
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, el: &NodeElement) {
+        if is_component_node(el) {
+            let ancestors = std::mem::take(&mut self.ancestors);
+            self.nodes(&el.children);
+            self.ancestors = ancestors;
+            return;
+        }
+        // the name of an element like `<{tag}>` is only known at runtime
+        let NodeName::Path(_) = el.name() else {
+            let ancestors = std::mem::take(&mut self.ancestors);
+            self.nodes(&el.children);
+            self.ancestors = ancestors;
+            return;
+        };
+
+        let name = el.name().to_string();
+        let span = el.name().span();
+        let is_html = !name.starts_with("svg::")
+            && !name.starts_with("math::")
+            && !is_svg_element(&name)
+            && !is_math_ml_element(&name)
+            && !self.ancestors.iter().any(|a| a == "svg" || a == "math");
+
+        if is_html {
+            if !name.contains('-') && !HTML_ELEMENTS.contains(&name.as_str()) {
+                self.warn(
+                    span,
+                    format!(
+                        "<{name}> is not a known HTML element; the name of a \
+                         custom element must contain a hyphen"
+                    ),
+                );
+            }
+            if VOID_ELEMENTS.contains(&name.as_str()) && !el.children.is_empty()
+            {
+                self.error(
+                    span,
+                    format!(
+                        "<{name}> is a self-closing tag and cannot have \
+                         children."
+                    ),
+                );
+            }
+            self.nesting(&name, span);
+        }
+        self.attributes(el, &name, is_html);
+
+        self.ancestors.push(name);
+        self.nodes(&el.children);
+        self.ancestors.pop();
+    }
+
+    fn nesting(&mut self, name: &str, span: Span) {
+        let parent = self.ancestors.last().cloned();
+        let parent = parent.as_deref();
+        if CLOSES_P.contains(&name) && self.ancestors.iter().any(|a| a == "p") {
+            self.error(
+                span,
+                format!(
+                    "<{name}> cannot be inside a <p>, as the browser closes \
+                     the <p> before it, which breaks hydration"
+                ),
+            );
+        }
+        if ["a", "button", "form"].contains(&name)
+            && self.ancestors.iter().any(|a| a == name)
+        {
+            self.error(
+                span,
+                format!(
+                    "<{name}> cannot be inside another <{name}>, as the \
+                     browser closes or drops the outer one, which breaks \
+                     hydration"
+                ),
+            );
+        }
+        if name == "tr" && parent == Some("table") {
+            self.error(
+                span,
+                "<tr> cannot be directly inside a <table>, as the browser \
+                 inserts a <tbody> around it, which breaks hydration; wrap it \
+                 in a <tbody>, <thead>, or <tfoot>"
+                    .to_string(),
+            );
+        }
+        if ["td", "th"].contains(&name)
+            && matches!(parent, Some("table" | "tbody" | "thead" | "tfoot"))
+        {
+            self.error(
+                span,
+                format!(
+                    "<{name}> cannot be directly inside a <{}>, as the \
+                     browser inserts a <tr> around it, which breaks \
+                     hydration; wrap it in a <tr>",
+                    parent.unwrap_or_default()
+                ),
+            );
+        }
+    }
+
+    fn attributes(&mut self, el: &NodeElement, name: &str, is_html: bool) {
+        let mut seen = Vec::<String>::new();
+        for attr in el.attributes() {
+            let NodeAttribute::Attribute(attr) = attr else {
+                continue;
+            };
+            let key = attr.key.to_string();
+            // classes and styles given as tuples can be set more than once,
+            // and directives, event listeners and the like are not attributes
+            if key.contains(':') || matches!(attr.value(), Some(Expr::Tuple(_)))
+            {
+                continue;
+            }
+            if seen.contains(&key) {
+                self.error(
+                    attr.key.span(),
+                    format!("the attribute `{key}` is set more than once"),
+                );
+            } else {
+                seen.push(key.clone());
+            }
+
+            let key = key.trim_end_matches('_');
+            let is_known = key.starts_with("data-")
+                || key.starts_with("aria-")
+                || LEPTOS_ATTRIBUTES.contains(&key)
+                || HTML_ATTRIBUTES.contains(&key);
+            if is_html && !name.contains('-') && !is_known {
+                self.warn(
+                    attr.key.span(),
+                    format!(
+                        "`{key}` is not a known attribute of HTML elements"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn error(&mut self, span: Span, message: String) {
+        self.issues.push(Issue {
+            span,
+            message,
+            is_warning: false,
+        });
+    }
+
+    fn warn(&mut self, span: Span, message: String) {
+        if !self.allow_unknown {
+            self.issues.push(Issue {
+                span,
+                message,
+                is_warning: true,
+            });
+        }
+    }
+}
+
+// https://developer.mozilla.org/en-US/docs/Glossary/Void_element
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
+
+// the elements whose start tag closes an open `<p>`
+// https://html.spec.whatwg.org/multipage/grouping-content.html#the-p-element
+const CLOSES_P: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+const HTML_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "portal",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+// the attributes that are handled by the `view` macro itself
+const LEPTOS_ATTRIBUTES: &[&str] =
+    &["ref", "_ref", "ref_", "node_ref", "inner_html"];
+
+// the global attributes, and the attributes of any HTML element
+// https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes
+const HTML_ATTRIBUTES: &[&str] = &[
+    "accept",
+    "accept-charset",
+    "accesskey",
+    "action",
+    "align",
+    "allow",
+    "allowfullscreen",
+    "alt",
+    "as",
+    "async",
+    "autocapitalize",
+    "autocomplete",
+    "autofocus",
+    "autoplay",
+    "background",
+    "bgcolor",
+    "blocking",
+    "border",
+    "capture",
+    "charset",
+    "checked",
+    "cite",
+    "class",
+    "color",
+    "cols",
+    "colspan",
+    "content",
+    "contenteditable",
+    "controls",
+    "coords",
+    "crossorigin",
+    "csp",
+    "data",
+    "datetime",
+    "decoding",
+    "default",
+    "defer",
+    "dir",
+    "dirname",
+    "disabled",
+    "download",
+    "draggable",
+    "enctype",
+    "enterkeyhint",
+    "fetchpriority",
+    "for",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "headers",
+    "height",
+    "hidden",
+    "high",
+    "href",
+    "hreflang",
+    "http-equiv",
+    "id",
+    "imagesizes",
+    "imagesrcset",
+    "inert",
+    "inputmode",
+    "integrity",
+    "is",
+    "ismap",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "kind",
+    "label",
+    "lang",
+    "list",
+    "loading",
+    "loop",
+    "low",
+    "max",
+    "maxlength",
+    "media",
+    "method",
+    "min",
+    "minlength",
+    "multiple",
+    "muted",
+    "name",
+    "nomodule",
+    "nonce",
+    "novalidate",
+    "open",
+    "optimum",
+    "part",
+    "pattern",
+    "ping",
+    "placeholder",
+    "playsinline",
+    "popover",
+    "popovertarget",
+    "popovertargetaction",
+    "poster",
+    "preload",
+    "property",
+    "readonly",
+    "referrerpolicy",
+    "rel",
+    "required",
+    "reversed",
+    "role",
+    "rows",
+    "rowspan",
+    "sandbox",
+    "scope",
+    "selected",
+    "shape",
+    "size",
+    "sizes",
+    "slot",
+    "span",
+    "spellcheck",
+    "src",
+    "srcdoc",
+    "srclang",
+    "srcset",
+    "start",
+    "step",
+    "style",
+    "tabindex",
+    "target",
+    "title",
+    "translate",
+    "type",
+    "usemap",
+    "value",
+    "width",
+    "wrap",
+];
@@ -0,0 +1,126 @@
+use leptos::*;
+use std::fmt::Display;
+
+#[component]
+fn List<T, F, V>(items: Vec<T>, render: F) -> impl IntoView
+where
+    T: Clone + Display + 'static,
+    F: Fn(T) -> V + 'static,
+    V: IntoView,
+{
+    items.into_iter().map(render).collect_view()
+}
+
+#[component]
+fn Labeled<T: Display + Clone + 'static>(
+    value: T,
+    #[prop(into)] label: String,
+    #[prop(optional)] format: Option<fn(&T) -> String>,
+    #[prop(optional)] on_select: Option<impl Fn(T) + 'static>,
+) -> impl IntoView {
+    _ = on_select;
+    let text = format
+        .map(|f| f(&value))
+        .unwrap_or_else(|| value.to_string());
+    view! { <span>{label}": "{text}</span> }
+}
+
+#[component]
+fn Wrapper<T>(value: T, children: Children) -> impl IntoView
+where
+    T: IntoView,
+{
+    view! { <div>{value}{children()}</div> }
+}
+
+#[component]
+fn Greeting<T: Display + 'static>(#[prop(into)] name: T) -> impl IntoView {
+    format!("Hello, {name}!")
+}
+
+#[component]
+fn Count<const N: usize>(values: [u8; N]) -> impl IntoView {
+    values.len()
+}
+
+#[component]
+fn Title(
+    items: Vec<impl Display + 'static>,
+    title: impl Into<String>,
+) -> impl IntoView {
+    format!("{}: {}", title.into(), items.len())
+}
+
+fn render(view: impl FnOnce() -> View) -> String {
+    let runtime = create_runtime();
+    let html = view().render_to_string().to_string();
+    runtime.dispose();
+    html
+}
+
+#[test]
+fn where_clause() {
+    let html = render(|| {
+        view! { <List items=vec![1, 2] render=|n: i32| n * 2/> }.into_view()
+    });
+    assert!(html.contains('2') && html.contains('4'));
+}
+
+#[test]
+fn impl_trait_props() {
+    let html = render(|| {
+        view! { <Title items=vec![1, 2, 3] title="Items"/> }.into_view()
+    });
+    assert!(html.contains("Items: 3"));
+}
+
+#[test]
+fn const_generics() {
+    let html = render(|| view! { <Count values=[1, 2, 3]/> }.into_view());
+    assert!(html.contains('3'));
+}
+
+#[test]
+fn explicit_generics() {
+    let html = render(|| {
+        view! {
+            <List<i32, _, _> items=vec![1] render=|n| n/>
+            <Wrapper<Vec<&str>> value=vec!["a"]>"b"</Wrapper>
+        }
+        .into_view()
+    });
+    assert!(html.contains("<div") && html.contains('b'));
+}
+
+#[test]
+fn turbofish() {
+    let html = render(|| {
+        view! {
+            <List::<i32, _, _> items=vec![1] render=|n| n/>
+            <Wrapper::<&str> value="a">"b"</Wrapper>
+        }
+        .into_view()
+    });
+    assert!(html.contains("<div") && html.contains('b'));
+}
+
+#[test]
+fn optional_impl_trait_prop() {
+    // the type of an `impl Trait` prop that is not passed is given with
+    // the generics, after the ones that are declared
+    let html = render(|| {
+        view! {
+            <Labeled value=3 label="Count" on_select=|_| {}/>
+            <Labeled::<u8, fn(u8)> value=4 label="Count"/>
+        }
+        .into_view()
+    });
+    assert!(html.contains("Count: 3") && html.contains("Count: 4"));
+}
+
+#[test]
+fn into_generic_prop() {
+    // the value is converted into the generic type once it is given
+    let html = render(|| view! { <Greeting<String> name="Ann"/> }.into_view());
+    assert!(html.contains("Hello, Ann!"));
+}
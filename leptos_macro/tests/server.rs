@@ -94,5 +94,264 @@ cfg_if! {
             assert_eq!(<MyServerAction as ServerFn>::PATH, "/api/path/to/my/endpoint");
             assert_eq!(TypeId::of::<<MyServerAction as ServerFn>::InputEncoding>(), TypeId::of::<codec::PostUrl>());
         }
+
+        #[test]
+        fn server_generic() {
+            pub trait Store: Send + 'static {}
+            pub struct Memory;
+            impl Store for Memory {}
+            pub struct Database;
+            impl Store for Database {}
+
+            #[server]
+            #[register(<Memory>, <Database>)]
+            pub async fn count_items<S: Store>(filter: String) -> Result<usize, ServerFnError> {
+                Ok(filter.len())
+            }
+            assert_eq!(
+                <CountItems<Memory> as ServerFn>::PATH.trim_end_matches("/Memory").trim_end_matches(char::is_numeric),
+                "/api/count_items"
+            );
+            assert_eq!(
+                <CountItems<Database> as ServerFn>::PATH.trim_end_matches("/Database"),
+                <CountItems<Memory> as ServerFn>::PATH.trim_end_matches("/Memory")
+            );
+            assert_eq!(
+                format!("{:?}", CountItems::<Memory>::new("a".to_string())),
+                "CountItems { filter: \"a\" }"
+            );
+        }
+
+        #[test]
+        fn server_rest() {
+            #[server(method = "GET", endpoint = "users/{id}/files/{name}")]
+            pub async fn get_file(id: u32, name: String, version: u32) -> Result<(), ServerFnError> {
+                _ = (id, name, version);
+                Ok(())
+            }
+            #[server(method = "post", endpoint = "users")]
+            pub async fn create_user(name: String) -> Result<(), ServerFnError> {
+                _ = name;
+                Ok(())
+            }
+            #[server(method = "PUT", endpoint = "users/{id}")]
+            pub async fn update_user(id: u32, name: String) -> Result<(), ServerFnError> {
+                _ = (id, name);
+                Ok(())
+            }
+            #[server(method = "PATCH", endpoint = "users/{id}")]
+            pub async fn rename_user(id: u32, name: String) -> Result<(), ServerFnError> {
+                _ = (id, name);
+                Ok(())
+            }
+            #[server(method = "DELETE", endpoint = "users/{id}")]
+            pub async fn delete_user(id: u32) -> Result<(), ServerFnError> {
+                _ = id;
+                Ok(())
+            }
+            assert_eq!(TypeId::of::<<GetFile as ServerFn>::InputEncoding>(), TypeId::of::<codec::GetUrl>());
+            assert_eq!(TypeId::of::<<CreateUser as ServerFn>::InputEncoding>(), TypeId::of::<codec::PostUrl>());
+            assert_eq!(TypeId::of::<<UpdateUser as ServerFn>::InputEncoding>(), TypeId::of::<codec::PutJson>());
+            assert_eq!(TypeId::of::<<RenameUser as ServerFn>::InputEncoding>(), TypeId::of::<codec::PatchJson>());
+            assert_eq!(TypeId::of::<<DeleteUser as ServerFn>::InputEncoding>(), TypeId::of::<codec::DeleteUrl>());
+            assert_eq!(<DeleteUser as ServerFn>::PATH, "/api/users/{id}");
+
+            let file = GetFile { id: 42, name: "my notes/ü.txt".into(), version: 3 };
+            let path = file.request_path();
+            assert_eq!(path, "/api/users/42/files/my%20notes%2F%C3%BC.txt");
+
+            let mut args = GetFile { id: 0, name: String::new(), version: 3 };
+            assert_eq!(args.extract_path_params(&path), Ok(()));
+            assert_eq!((args.id, args.name.as_str(), args.version), (42, "my notes/ü.txt", 3));
+
+            // a missing or unparseable parameter is an error in the arguments
+            assert!(matches!(args.extract_path_params("/api/users/42/files/"), Err(ServerFnError::Args(_))));
+            assert!(matches!(args.extract_path_params("/api/users/me/files/a.txt"), Err(ServerFnError::Args(_))));
+        }
+
+        #[test]
+        fn server_retry() {
+            use leptos::server_fn::{
+                client::transport::{with_client, DynClient, MockClient},
+                retry::RetryPolicy,
+                validation::ValidationErrors,
+            };
+            use std::{
+                sync::{
+                    atomic::{AtomicUsize, Ordering},
+                    Arc,
+                },
+                time::Duration,
+            };
+
+            fn policy() -> RetryPolicy {
+                RetryPolicy::new(3).backoff(Duration::from_millis(1), Duration::from_millis(1))
+            }
+            #[server(client = DynClient, retry = policy())]
+            pub async fn flaky(fail_times: usize) -> Result<usize, ServerFnError> {
+                Ok(fail_times)
+            }
+            #[server(client = DynClient, retry = policy())]
+            pub async fn invalid() -> Result<(), ServerFnError> {
+                Ok(())
+            }
+
+            let calls = Arc::new(AtomicUsize::new(0));
+            let client = MockClient::new()
+                .on({
+                    let calls = Arc::clone(&calls);
+                    move |args: Flaky| {
+                        let call = calls.fetch_add(1, Ordering::SeqCst);
+                        async move {
+                            if call < args.fail_times {
+                                Err(ServerFnError::ServerError("unavailable".into()))
+                            } else {
+                                Ok(call)
+                            }
+                        }
+                    }
+                })
+                .on({
+                    let calls = Arc::clone(&calls);
+                    move |_: Invalid| {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        async move {
+                            let mut errors = ValidationErrors::new();
+                            errors.add("name", "required");
+                            Err(ServerFnError::Validation(errors))
+                        }
+                    }
+                });
+            let client = Arc::new(client);
+            // calls are made through the client, as they are when the server
+            // function is not compiled for the server
+            let call_flaky = |fail_times| futures::executor::block_on(with_client(
+                Arc::clone(&client),
+                Flaky { fail_times }.run_on_client_with_retries(),
+            ));
+
+            // a `500` is retried until the call succeeds
+            assert_eq!(call_flaky(2), Ok(2));
+            assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+            // but only as many times as the policy allows
+            assert_eq!(
+                call_flaky(5),
+                Err(ServerFnError::ServerError("unavailable".into()))
+            );
+            assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+            // a `422` is not retried
+            let invalid = futures::executor::block_on(with_client(
+                Arc::clone(&client),
+                Invalid {}.run_on_client_with_retries(),
+            ));
+            assert!(matches!(invalid, Err(ServerFnError::Validation(_))));
+            assert_eq!(calls.swap(0, Ordering::SeqCst), 1);
+        }
+
+        #[test]
+        fn server_cache() {
+            use leptos::server_fn::{
+                cache::{invalidate, run_cached},
+                client::transport::{with_client, DynClient, MockClient},
+            };
+            use std::{
+                sync::{
+                    atomic::{AtomicUsize, Ordering},
+                    Arc,
+                },
+                time::Duration,
+            };
+
+            #[server(client = DynClient, cache = Duration::from_millis(100))]
+            pub async fn get_count(id: u32) -> Result<usize, ServerFnError> {
+                Ok(id as usize)
+            }
+
+            let calls = Arc::new(AtomicUsize::new(0));
+            let client = Arc::new(MockClient::new().on({
+                let calls = Arc::clone(&calls);
+                move |_: GetCount| {
+                    let call = calls.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        // stays in flight while the other calls are made
+                        let mut in_flight = true;
+                        futures::future::poll_fn(|cx| {
+                            if std::mem::take(&mut in_flight) {
+                                cx.waker().wake_by_ref();
+                                std::task::Poll::Pending
+                            } else {
+                                std::task::Poll::Ready(())
+                            }
+                        })
+                        .await;
+                        Ok(call)
+                    }
+                }
+            }));
+            // calls are made through the client, as they are when the server
+            // function is not compiled for the server
+            fn cached(id: u32) -> impl std::future::Future<Output = Result<usize, ServerFnError>> {
+                run_cached(GetCount { id })
+            }
+            fn run<T>(client: &Arc<MockClient>, fut: impl std::future::Future<Output = T>) -> T {
+                futures::executor::block_on(with_client(Arc::clone(client), fut))
+            }
+
+            // identical calls in flight at the same time share a request
+            let (a, b, c) = run(&client, futures::future::join3(cached(1), cached(1), cached(2)));
+            assert_eq!((a, b, c), (Ok(0), Ok(0), Ok(1)));
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+            // and their result is reused until the TTL has passed
+            assert_eq!(run(&client, cached(1)), Ok(0));
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+            std::thread::sleep(Duration::from_millis(150));
+            assert_eq!(run(&client, cached(1)), Ok(2));
+            assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+            // or it is invalidated
+            invalidate(&GetCount { id: 1 });
+            assert_eq!(run(&client, cached(1)), Ok(3));
+            assert_eq!(run(&client, cached(1)), Ok(3));
+            assert_eq!(calls.load(Ordering::SeqCst), 4);
+        }
+
+        #[test]
+        fn server_mock_client() {
+            use leptos::server_fn::client::transport::{with_client, DynClient, MockClient};
+
+            #[server(client = DynClient)]
+            pub async fn add(a: i32, b: i32) -> Result<i32, ServerFnError> {
+                Ok(a + b)
+            }
+            #[server(client = DynClient)]
+            pub async fn divide(a: i32, b: i32) -> Result<i32, ServerFnError> {
+                Ok(a / b)
+            }
+            #[server(client = DynClient)]
+            pub async fn negate(a: i32) -> Result<i32, ServerFnError> {
+                Ok(-a)
+            }
+
+            let client = MockClient::new()
+                .on(|args: Add| async move { Ok(args.a + args.b) })
+                .on(|args: Divide| async move {
+                    args.a.checked_div(args.b).ok_or_else(|| ServerFnError::ServerError("division by zero".into()))
+                });
+            // calls are made through the client, as they are when the server
+            // function is not compiled for the server
+            let (sum, quotient, negated) = futures::executor::block_on(with_client(client, async {
+                (
+                    Add { a: 1, b: 2 }.run_on_client().await,
+                    Divide { a: 1, b: 0 }.run_on_client().await,
+                    Negate { a: 1 }.run_on_client().await,
+                )
+            }));
+            assert_eq!(sum, Ok(3));
+            assert_eq!(quotient, Err(ServerFnError::ServerError("division by zero".into())));
+            assert!(matches!(negated, Err(ServerFnError::Request(_))));
+        }
     }
 }
@@ -0,0 +1,131 @@
+use leptos::*;
+use std::fmt::Display;
+
+#[derive(Clone)]
+struct User {
+    name: &'static str,
+    age: u8,
+}
+
+#[slot]
+struct Column {
+    #[prop(into)]
+    header: String,
+    children: TypedChildrenFn<User>,
+}
+
+#[slot]
+struct Empty {
+    children: ChildrenFn,
+}
+
+#[component]
+fn Table(
+    rows: Vec<User>,
+    #[prop(optional)] column: Vec<Column>,
+    #[prop(optional)] empty: Option<Empty>,
+) -> impl IntoView {
+    if rows.is_empty() {
+        return empty.map(|empty| (empty.children)()).into_view();
+    }
+    let headers = column
+        .iter()
+        .map(|column| view! { <th>{column.header.clone()}</th> })
+        .collect_view();
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            column
+                .iter()
+                .map(|column| view! { <td>{(column.children)(row.clone())}</td> })
+                .collect_view()
+        })
+        .map(|cells| view! { <tr>{cells}</tr> })
+        .collect_view();
+    view! {
+        <table>
+            <thead><tr>{headers}</tr></thead>
+            <tbody>{rows}</tbody>
+        </table>
+    }
+    .into_view()
+}
+
+#[slot]
+struct Item<T: Display + 'static> {
+    value: T,
+}
+
+#[component]
+fn Items<T: Display + 'static>(item: Vec<Item<T>>) -> impl IntoView {
+    item.into_iter()
+        .map(|item| item.value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn render(view: impl FnOnce() -> View) -> String {
+    let runtime = create_runtime();
+    let html = view().render_to_string().to_string();
+    runtime.dispose();
+    html
+}
+
+#[test]
+fn slots_with_values() {
+    let rows = vec![User {
+        name: "Ann",
+        age: 3,
+    }];
+    let html = render(move || {
+        view! {
+            <Table rows>
+                <Column slot header="Name" let:user>{user.name}</Column>
+                <Column slot header="Age" let:user>{user.age}</Column>
+            </Table>
+        }
+        .into_view()
+    });
+    assert!(html.contains(">Name</th>") && html.contains(">Age</th>"));
+    assert!(html.contains("Ann") && html.contains('3'));
+}
+
+#[test]
+fn optional_slots() {
+    let html = render(|| {
+        view! {
+            <Table rows=vec![]>
+                <Empty slot>"No users"</Empty>
+            </Table>
+        }
+        .into_view()
+    });
+    assert!(html.contains("No users"));
+
+    let html = render(|| view! { <Table rows=vec![]/> }.into_view());
+    assert!(!html.contains("<table"));
+}
+
+#[test]
+fn generic_slots() {
+    let html = render(|| {
+        view! {
+            <Items>
+                <Item slot value=1/>
+                <Item slot value=2/>
+            </Items>
+        }
+        .into_view()
+    });
+    assert!(html.contains("1,2"));
+
+    let html = render(|| {
+        view! {
+            <Items>
+                <Item slot value="a"/>
+            </Items>
+        }
+        .into_view()
+    });
+    assert!(html.contains('a'));
+}
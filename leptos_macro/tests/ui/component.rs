@@ -44,4 +44,12 @@ fn default_with_invalid_value(
     _ = default;
 }
 
+#[component]
+async fn async_with_fallback(fallback: bool) -> impl IntoView {
+    _ = fallback;
+}
+
+#[island]
+async fn async_island() -> impl IntoView {}
+
 fn main() {}
@@ -22,6 +22,12 @@ pub struct SharedContext {
     pub pending_fragments: HashMap<String, FragmentData>,
     /// Suspense fragments that contain only local resources.
     pub fragments_with_local_resources: HashSet<String>,
+    /// Suspense fragments that should be streamed in order, even in an
+    /// out-of-order stream.
+    pub in_order_fragments: HashSet<String>,
+    /// Whether the page is rendered on the server only, without being
+    /// hydrated in the browser.
+    pub server_only: bool,
     #[cfg(feature = "experimental-islands")]
     pub no_hydrate: bool,
     #[cfg(all(feature = "hydrate", feature = "experimental-islands"))]
@@ -59,6 +65,14 @@ impl SharedContext {
             .unwrap_or_default()
     }
 
+    /// Returns the name of the type of the value of a serializable
+    /// [`Resource`](crate::Resource), so that it can be logged.
+    pub fn resource_type_name(id: ResourceId) -> Option<&'static str> {
+        with_runtime(|runtime| runtime.resource_type_name(id))
+            .ok()
+            .flatten()
+    }
+
     /// Registers the given [`SuspenseContext`](crate::SuspenseContext) with the current scope,
     /// calling the `resolver` when its resources are all resolved.
     #[cfg_attr(
@@ -226,6 +240,42 @@ impl SharedContext {
         })
         .unwrap_or_default()
     }
+
+    /// Takes the keys of the `<Suspense/>` fragments that should be streamed
+    /// in order, even in an out-of-order stream.
+    pub fn in_order_fragments() -> HashSet<String> {
+        with_runtime(|runtime| {
+            let mut shared_context = runtime.shared_context.borrow_mut();
+            std::mem::take(&mut shared_context.in_order_fragments)
+        })
+        .unwrap_or_default()
+    }
+
+    /// Marks a `<Suspense/>` fragment to be streamed in order, even in an
+    /// out-of-order stream.
+    pub fn register_in_order_fragment(key: String) {
+        with_runtime(|runtime| {
+            let mut shared_context = runtime.shared_context.borrow_mut();
+            shared_context.in_order_fragments.insert(key);
+        })
+        .unwrap_or_default()
+    }
+
+    /// Marks the page being rendered on the server as one that is not
+    /// hydrated, so that neither the script that loads the app in the browser
+    /// nor the data of its resources are sent with it.
+    pub fn set_server_only() {
+        _ = with_runtime(|runtime| {
+            runtime.shared_context.borrow_mut().server_only = true;
+        });
+    }
+
+    /// Whether the page being rendered on the server is not hydrated. See
+    /// [`SharedContext::set_server_only`].
+    pub fn is_server_only() -> bool {
+        with_runtime(|runtime| runtime.shared_context.borrow().server_only)
+            .unwrap_or_default()
+    }
 }
 
 /// Represents its pending `<Suspense/>` fragment.
@@ -299,6 +349,8 @@ impl Default for SharedContext {
                 pending_resources,
                 resolved_resources,
                 fragments_with_local_resources,
+                in_order_fragments: Default::default(),
+                server_only: false,
                 pending_fragments: Default::default(),
                 #[cfg(feature = "experimental-islands")]
                 no_hydrate: true,
@@ -318,6 +370,8 @@ impl Default for SharedContext {
                 resolved_resources: Default::default(),
                 pending_fragments: Default::default(),
                 fragments_with_local_resources: Default::default(),
+                in_order_fragments: Default::default(),
+                server_only: false,
                 #[cfg(feature = "experimental-islands")]
                 no_hydrate: true,
                 #[cfg(all(
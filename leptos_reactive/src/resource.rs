@@ -1487,6 +1487,8 @@ pub(crate) trait SerializableResource {
     ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
 
     fn should_send_to_client(&self) -> bool;
+
+    fn type_name(&self) -> &'static str;
 }
 
 impl<S, T> SerializableResource for ResourceState<S, T>
@@ -1526,6 +1528,10 @@ where
             true
         }
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 pub(crate) trait UnserializableResource {
@@ -1344,6 +1344,16 @@ impl Runtime {
             .collect()
     }
 
+    pub(crate) fn resource_type_name(
+        &self,
+        id: ResourceId,
+    ) -> Option<&'static str> {
+        match self.resources.borrow().get(id)? {
+            AnyResource::Serializable(res) => Some(res.type_name()),
+            AnyResource::Unserializable(_) => None,
+        }
+    }
+
     pub(crate) fn serialization_resolvers(
         &self,
     ) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
@@ -6,7 +6,7 @@ use leptos_reactive::{
     try_batch, use_context, StoredValue,
 };
 use server_fn::{error::ServerFnUrlError, ServerFn, ServerFnError};
-use std::{cell::Cell, future::Future, pin::Pin, rc::Rc};
+use std::{cell::Cell, future::Future, pin::Pin, rc::Rc, time::Duration};
 
 /// An action synchronizes an imperative `async` call to the synchronous reactive system.
 ///
@@ -296,7 +296,8 @@ where
 
         // When not on the server send a fetch to request the fn call.
         #[cfg(not(feature = "ssr"))]
-        let action_function = |args: &I| I::run_on_client(args.clone());
+        let action_function =
+            |args: &I| I::run_on_client_with_retries(args.clone());
 
         // create the action
         Action::new(action_function).using_server_fn()
@@ -329,6 +330,50 @@ where
         });
         self
     }
+
+    /// The error messages for the given field, if the most recent call to the
+    /// server function failed [validation](server_fn::validation) on the server.
+    ///
+    /// This can be used to show errors next to the inputs of an `ActionForm`.
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn field_errors(
+        &self,
+        field: impl Into<String>,
+    ) -> Signal<Vec<String>> {
+        let value = self.value();
+        let field = field.into();
+        Signal::derive(move || {
+            value.with(|value| match value {
+                Some(Err(err)) => err
+                    .validation_errors()
+                    .map(|errors| errors.field(&field).to_vec())
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            })
+        })
+    }
+
+    /// How long to wait before dispatching the action again, if the most
+    /// recent call to the server function exceeded a
+    /// [rate limit](server_fn::rate_limit) on the server.
+    ///
+    /// This can be used to disable a form's submit button until then.
+    #[cfg_attr(
+        any(debug_assertions, feature = "ssr"),
+        tracing::instrument(level = "trace", skip_all,)
+    )]
+    pub fn retry_after(&self) -> Signal<Option<Duration>> {
+        let value = self.value();
+        Signal::derive(move || {
+            value.with(|value| match value {
+                Some(Err(err)) => err.retry_after(),
+                _ => None,
+            })
+        })
+    }
 }
 
 impl<I, O> Clone for Action<I, O>
@@ -0,0 +1,214 @@
+use crate::{request::Cookies, Cookie};
+use leptos_reactive::{
+    create_rw_signal, Signal, SignalSet, SignalSetter, SignalWith,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+// the characters that are encoded in the values of cookies: everything but
+// letters, digits, and a few punctuation marks
+const COOKIE_VALUE: &AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Reads and writes a cookie as a signal, with the same API on the server and
+/// in the browser. The value is serialized as JSON, and is `None` if the
+/// cookie is not set (or cannot be deserialized).
+///
+/// - While rendering on the server, or in a server function, the cookie is
+///   read from the request, and setting it adds a `Set-Cookie` header to the
+///   response. Headers can only be added before the response is sent, which
+///   is before any `<Suspense/>` fragments are streamed.
+/// - In the browser, the cookie is read from and written to `document.cookie`.
+///   Every signal for the cookie is updated when it is set, and when the page
+///   regains focus, in case it was changed elsewhere. [`sync_cookies`] updates
+///   them when it may have been changed in another way, like by a response to
+///   a server function.
+///
+/// Setting the cookie to `None` removes it. Cookies that are `HttpOnly` can
+/// only be set on the server.
+///
+/// ```rust,ignore
+/// #[component]
+/// pub fn ThemeToggle() -> impl IntoView {
+///     let (dark, set_dark) = use_cookie::<bool>("dark");
+///     view! {
+///         <button on:click=move |_| set_dark(Some(!dark().unwrap_or(false)))>
+///             "Toggle theme"
+///         </button>
+///     }
+/// }
+/// ```
+pub fn use_cookie<T>(name: &str) -> (Signal<Option<T>>, SignalSetter<Option<T>>)
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    use_cookie_with(Cookie::new(name, ""))
+}
+
+/// Reads and writes a cookie as a signal, like [`use_cookie`], setting it
+/// with the attributes of the given [`Cookie`], like its path and how long it
+/// lasts. The value of the given cookie is ignored.
+///
+/// ```rust,ignore
+/// let (locale, set_locale) = use_cookie_with::<String>(
+///     Cookie::new("locale", "")
+///         .max_age(Duration::from_secs(60 * 60 * 24 * 365))
+///         .same_site(SameSite::Lax),
+/// );
+/// ```
+pub fn use_cookie_with<T>(
+    cookie: Cookie,
+) -> (Signal<Option<T>>, SignalSetter<Option<T>>)
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let raw = create_rw_signal(read_cookie(cookie.name()));
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    browser::register(cookie.name(), raw);
+
+    let value =
+        Signal::derive(move || raw.with(|raw| raw.as_deref().and_then(decode)));
+    let set_value = SignalSetter::map(move |value: Option<T>| {
+        let encoded = value.as_ref().and_then(encode);
+        write_cookie(&cookie, encoded.as_deref());
+        raw.set(encoded);
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        browser::sync();
+    });
+    (value, set_value)
+}
+
+/// Updates the signals of every cookie used with [`use_cookie`] in the
+/// browser from `document.cookie`, in case the cookies have been changed in
+/// another way. Does nothing on the server.
+pub fn sync_cookies() {
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    browser::sync();
+}
+
+fn read_cookie(name: &str) -> Option<String> {
+    match crate::request::extract::<Cookies>() {
+        Ok(cookies) => cookies.get(name).map(String::from),
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        Err(_) => browser::read(name),
+        #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+        Err(_) => None,
+    }
+}
+
+fn write_cookie(cookie: &Cookie, value: Option<&str>) {
+    let cookie = match value {
+        Some(value) => cookie.clone().value(value),
+        None => cookie.clone().value("").max_age(Duration::ZERO),
+    };
+    // on the server, it is set on the response; otherwise, in the browser
+    if crate::set_cookie(&cookie).is_err() {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        browser::write(&cookie.to_string());
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_string(value) {
+        Ok(json) => Some(utf8_percent_encode(&json, COOKIE_VALUE).to_string()),
+        Err(e) => {
+            tracing::error!("could not serialize the value of a cookie: {e}");
+            None
+        }
+    }
+}
+
+fn decode<T: DeserializeOwned>(raw: &str) -> Option<T> {
+    let value = percent_decode_str(raw).decode_utf8().ok()?;
+    // cookies set in other ways, like a session ID, are often plain strings
+    serde_json::from_str(&value).ok().or_else(|| {
+        serde_json::from_value(serde_json::Value::String(value.into_owned()))
+            .ok()
+    })
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod browser {
+    use crate::request::parse_cookies;
+    use leptos_reactive::{RwSignal, SignalSet, SignalWithUntracked};
+    use std::cell::{Cell, RefCell};
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::HtmlDocument;
+
+    thread_local! {
+        static SIGNALS: RefCell<Vec<(String, RwSignal<Option<String>>)>> =
+            const { RefCell::new(Vec::new()) };
+        static LISTENING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn document() -> Option<HtmlDocument> {
+        web_sys::window()?
+            .document()?
+            .dyn_into::<HtmlDocument>()
+            .ok()
+    }
+
+    fn cookies() -> String {
+        document()
+            .and_then(|document| document.cookie().ok())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn read(name: &str) -> Option<String> {
+        parse_cookies(&cookies())
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.to_string())
+    }
+
+    pub(super) fn write(cookie: &str) {
+        if let Some(document) = document() {
+            _ = document.set_cookie(cookie);
+        }
+    }
+
+    pub(super) fn register(name: &str, signal: RwSignal<Option<String>>) {
+        SIGNALS.with(|signals| {
+            signals.borrow_mut().push((name.to_string(), signal))
+        });
+        if !LISTENING.with(|listening| listening.replace(true)) {
+            let on_focus = Closure::<dyn Fn()>::new(sync);
+            if let Some(window) = web_sys::window() {
+                _ = window.add_event_listener_with_callback(
+                    "focus",
+                    on_focus.as_ref().unchecked_ref(),
+                );
+            }
+            on_focus.forget();
+        }
+    }
+
+    pub(super) fn sync() {
+        let cookies = cookies();
+        let mut changed = Vec::new();
+        SIGNALS.with(|signals| {
+            // signals that have been disposed of are removed
+            signals.borrow_mut().retain(|(name, signal)| {
+                let value = parse_cookies(&cookies)
+                    .find(|(n, _)| n == name)
+                    .map(|(_, value)| value.to_string());
+                match signal.try_with_untracked(|old| *old != value) {
+                    Some(true) => {
+                        changed.push((*signal, value));
+                        true
+                    }
+                    Some(false) => true,
+                    None => false,
+                }
+            })
+        });
+        // set once the signals are no longer borrowed, as their effects may
+        // use other cookies
+        for (signal, value) in changed {
+            signal.set(value);
+        }
+    }
+}
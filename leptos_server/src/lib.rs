@@ -116,7 +116,16 @@
 pub use server_fn::{error::ServerFnErrorErr, ServerFnError};
 
 mod action;
+mod cookie;
 mod multi_action;
+mod optimistic;
+pub mod request;
+mod resource;
+mod response;
 pub use action::*;
+pub use cookie::*;
 pub use multi_action::*;
+pub use optimistic::*;
+pub use resource::*;
+pub use response::*;
 extern crate tracing;
@@ -345,6 +345,6 @@ where
     #[cfg(feature = "ssr")]
     let c = move |args: &S| S::run_body(args.clone());
     #[cfg(not(feature = "ssr"))]
-    let c = move |args: &S| S::run_on_client(args.clone());
+    let c = move |args: &S| S::run_on_client_with_retries(args.clone());
     create_multi_action(c).using_server_fn::<S>()
 }
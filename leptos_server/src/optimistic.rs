@@ -0,0 +1,196 @@
+use crate::{Action, MultiAction};
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, signal_prelude::*, untrack,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The state of a single submission to an action, as seen by
+/// [`create_optimistic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptimisticSubmission<I> {
+    /// The submission is still waiting for the server, with the given input.
+    Pending(I),
+    /// The submission resolved successfully.
+    Succeeded,
+    /// The submission resolved with an error, or was canceled.
+    Failed,
+}
+
+/// An action whose in-flight submissions can be shown optimistically with
+/// [`create_optimistic`].
+///
+/// This is implemented for [`Action`] and [`MultiAction`] whenever the input
+/// can be cloned and the output is a `Result`, as it is for server actions.
+pub trait OptimisticSource<I>: Copy + 'static {
+    /// Returns the current state of each submission, with a key that is
+    /// unique to that submission. This should be tracked reactively.
+    fn optimistic_submissions(&self) -> Vec<(usize, OptimisticSubmission<I>)>;
+}
+
+impl<I, T, E> OptimisticSource<I> for Action<I, Result<T, E>>
+where
+    I: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    fn optimistic_submissions(&self) -> Vec<(usize, OptimisticSubmission<I>)> {
+        // each dispatch resolves to the next version
+        let version = self.version().get();
+        match self.input().get() {
+            Some(input) => {
+                vec![(version + 1, OptimisticSubmission::Pending(input))]
+            }
+            None => {
+                match self.value().with(|v| v.as_ref().map(Result::is_ok)) {
+                    Some(true) => {
+                        vec![(version, OptimisticSubmission::Succeeded)]
+                    }
+                    Some(false) => {
+                        vec![(version, OptimisticSubmission::Failed)]
+                    }
+                    None => vec![],
+                }
+            }
+        }
+    }
+}
+
+impl<I, T, E> OptimisticSource<I> for MultiAction<I, Result<T, E>>
+where
+    I: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    fn optimistic_submissions(&self) -> Vec<(usize, OptimisticSubmission<I>)> {
+        self.submissions().with(|submissions| {
+            submissions
+                .iter()
+                .enumerate()
+                .filter_map(|(key, submission)| {
+                    let state = if submission.pending().get() {
+                        // the input is cleared just before the submission
+                        // stops pending, so it may be briefly missing
+                        OptimisticSubmission::Pending(submission.input.get()?)
+                    } else if submission.canceled.get() {
+                        OptimisticSubmission::Failed
+                    } else {
+                        match submission
+                            .value
+                            .with(|v| v.as_ref().map(Result::is_ok))
+                        {
+                            Some(true) => OptimisticSubmission::Succeeded,
+                            _ => OptimisticSubmission::Failed,
+                        }
+                    };
+                    Some((key, state))
+                })
+                .collect()
+        })
+    }
+}
+
+/// Creates a memo of `source` with the inputs of an action's in-flight
+/// submissions applied to it, so that a list can show a new item as soon as
+/// the form adding it is submitted, rather than once the server responds.
+///
+/// `apply` is called for each pending input, in the order they were dispatched,
+/// to update a copy of the current value of `source`. When a submission
+/// resolves:
+/// - if it failed, or was canceled, its input is no longer applied, rolling the
+///   value back;
+/// - if it succeeded, its input is still applied until `source` next changes,
+///   which is typically when a resource that depends on the action's `version`
+///   reloads and includes the saved item. This avoids the item flickering out
+///   of the list in between.
+///
+/// This works with any action that submits a server function, including those
+/// dispatched by an `<ActionForm/>` or `<MultiActionForm/>`.
+///
+/// ```rust
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// #[server]
+/// async fn add_todo(title: String) -> Result<(), ServerFnError> {
+///     todo!()
+/// }
+///
+/// #[server]
+/// async fn get_todos() -> Result<Vec<String>, ServerFnError> {
+///     todo!()
+/// }
+///
+/// # if false {
+/// let add = create_server_multi_action::<AddTodo>();
+/// let todos = create_resource(move || add.version().get(), |_| get_todos());
+///
+/// // includes the titles of todos that are still being added
+/// let todos = create_optimistic(
+///     move || todos.get().and_then(Result::ok).unwrap_or_default(),
+///     add,
+///     |todos: &mut Vec<String>, input: &AddTodo| {
+///         todos.push(input.title.clone())
+///     },
+/// );
+/// # }
+/// # runtime.dispose();
+/// ```
+pub fn create_optimistic<T, I, S>(
+    source: impl Fn() -> T + 'static,
+    action: S,
+    apply: impl Fn(&mut T, &I) + 'static,
+) -> Memo<T>
+where
+    T: Clone + PartialEq + 'static,
+    I: Clone + 'static,
+    S: OptimisticSource<I>,
+{
+    let source = create_memo(move |_| source());
+    let pending = create_rw_signal(Vec::<I>::new());
+    // inputs of successful submissions, applied until `source` changes
+    let settled = create_rw_signal(Vec::<I>::new());
+
+    let seen: Rc<RefCell<HashMap<usize, I>>> = Default::default();
+    create_isomorphic_effect(move |_| {
+        let mut seen = seen.borrow_mut();
+        let mut now_pending = Vec::new();
+        for (key, submission) in action.optimistic_submissions() {
+            match submission {
+                OptimisticSubmission::Pending(input) => {
+                    seen.insert(key, input.clone());
+                    now_pending.push(input);
+                }
+                OptimisticSubmission::Succeeded => {
+                    if let Some(input) = seen.remove(&key) {
+                        settled.update(|settled| settled.push(input));
+                    }
+                }
+                OptimisticSubmission::Failed => {
+                    seen.remove(&key);
+                }
+            }
+        }
+        pending.set(now_pending);
+    });
+
+    create_isomorphic_effect(move |prev: Option<()>| {
+        source.track();
+        if prev.is_some() && untrack(move || !settled.with(Vec::is_empty)) {
+            settled.set(Vec::new());
+        }
+    });
+
+    create_memo(move |_| {
+        let mut value = source.get();
+        settled.with(|settled| {
+            for input in settled {
+                apply(&mut value, input);
+            }
+        });
+        pending.with(|pending| {
+            for input in pending {
+                apply(&mut value, input);
+            }
+        });
+        value
+    })
+}
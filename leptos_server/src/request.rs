@@ -0,0 +1,335 @@
+//! Extracts typed values from the request being handled on the server, in
+//! the same way with every server integration, whether in a server function
+//! or while rendering a component on the server:
+//!
+//! ```rust,ignore
+//! use leptos::request::{extract, ClientIp, Cookies, Headers};
+//!
+//! #[server]
+//! pub async fn whoami() -> Result<String, ServerFnError> {
+//!     let headers: Headers = extract()?;
+//!     let ClientIp(ip) = extract()?;
+//!     let agent = headers.get("user-agent").unwrap_or("unknown");
+//!     Ok(format!("{agent} at {ip}"))
+//! }
+//!
+//! #[component]
+//! pub fn Greeting() -> impl IntoView {
+//!     // `None` in the browser, where there is no request
+//!     let name = extract::<Cookies>()
+//!         .ok()
+//!         .and_then(|cookies| cookies.get("name").map(String::from));
+//!     view! { <p>"Hello, " {name}</p> }
+//! }
+//! ```
+//!
+//! Other values, like the user of an authenticated session, can be extracted
+//! by implementing [`FromServerRequest`] for them:
+//!
+//! ```rust,ignore
+//! pub struct SessionId(pub String);
+//!
+//! impl FromServerRequest for SessionId {
+//!     fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+//!         Cookies::from_request(req)?
+//!             .get("session")
+//!             .map(|id| SessionId(id.to_string()))
+//!             .ok_or_else(|| ServerFnError::new("not logged in"))
+//!     }
+//! }
+//! ```
+//!
+//! The server integrations still provide their own request types as context,
+//! and their own `extract` functions for the extractors of their frameworks.
+//!
+//! Values that are not part of the request itself, like a database pool or
+//! the session that an authentication middleware has loaded, can be provided
+//! as context for the request by the server's middleware, with a
+//! [`RequestContext`].
+
+use leptos_reactive::{provide_context, use_context};
+use server_fn::ServerFnError;
+use std::{
+    any::TypeId,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The request being handled on the server, from which [`extract`] extracts
+/// values. Each server integration implements this for its request type.
+pub trait ServerRequest {
+    /// The method of the request, like `GET`.
+    fn method(&self) -> &str;
+
+    /// The path and query string of the request.
+    fn uri(&self) -> String;
+
+    /// The headers of the request, with their names in lowercase. Headers
+    /// whose values are not valid UTF-8 are left out.
+    fn headers(&self) -> Vec<(&str, &str)>;
+
+    /// The address of the client that made the request, if known.
+    fn client_addr(&self) -> Option<SocketAddr>;
+}
+
+/// Wrapping type for the request provided as context by the server
+/// integration. See [`provide_server_request`].
+#[derive(Clone)]
+pub struct ServerRequestContext(Rc<dyn ServerRequest>);
+
+impl fmt::Debug for ServerRequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerRequestContext").finish()
+    }
+}
+
+/// Provides the request being handled, so that values can be extracted from
+/// it with [`extract`].
+///
+/// This is provided by the server integrations, so that server functions and
+/// components can extract values without depending on them.
+pub fn provide_server_request(req: impl ServerRequest + 'static) {
+    provide_context(ServerRequestContext(Rc::new(req)))
+}
+
+/// Values that server middleware provides as context for a request, like a
+/// database pool, the session of the user, or feature flags, which can then
+/// be used with `use_context` or `expect_context` by the components rendered
+/// for the request and by the server functions it calls.
+///
+/// Middleware adds it to the request with the `provide_request_context`
+/// function of the server integration, which provides each of its values as
+/// context before the app is rendered or the server function is run. Each
+/// value is provided once for every type: a value of a type that has already
+/// been inserted replaces it.
+///
+/// ```rust,ignore
+/// // an Axum middleware
+/// async fn auth(mut req: Request, next: Next) -> Response {
+///     let session = load_session(req.headers()).await;
+///     leptos_axum::provide_request_context(&mut req, session);
+///     next.run(req).await
+/// }
+///
+/// #[server]
+/// pub async fn whoami() -> Result<String, ServerFnError> {
+///     Ok(expect_context::<Session>().user_name)
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct RequestContext(Vec<(TypeId, Arc<dyn Fn() + Send + Sync>)>);
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RequestContext")
+            .field(&self.0.len())
+            .finish()
+    }
+}
+
+impl RequestContext {
+    /// Creates a context with no values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to be provided as context, replacing any value of the
+    /// same type.
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let provide = Arc::new(move || provide_context(value.clone()));
+        let id = TypeId::of::<T>();
+        match self.0.iter_mut().find(|(type_id, _)| *type_id == id) {
+            Some((_, existing)) => *existing = provide,
+            None => self.0.push((id, provide)),
+        }
+    }
+
+    /// Provides each of the values as context in the current reactive owner.
+    ///
+    /// This is called by the server integrations for each request.
+    pub fn provide(&self) {
+        for (_, provide) in &self.0 {
+            provide();
+        }
+    }
+}
+
+/// A value that can be extracted from the request with [`extract`].
+pub trait FromServerRequest: Sized {
+    /// Extracts the value from the request.
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError>;
+}
+
+/// Extracts a value from the request being handled, from within a server
+/// function or while rendering on the server.
+///
+/// Returns an error in the browser, or if the value cannot be extracted.
+pub fn extract<T: FromServerRequest>() -> Result<T, ServerFnError> {
+    let Some(req) = use_context::<ServerRequestContext>() else {
+        return Err(ServerFnError::ServerError(
+            "The request can only be extracted on the server, within a \
+             handler provided by a server integration."
+                .into(),
+        ));
+    };
+    T::from_request(&*req.0)
+}
+
+impl<T: FromServerRequest> FromServerRequest for Option<T> {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        Ok(T::from_request(req).ok())
+    }
+}
+
+/// The headers of the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers(Vec<(String, String)>);
+
+impl Headers {
+    /// Returns the first value of the header with the given name, which is
+    /// matched case-insensitively.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns every value of the header with the given name, which is
+    /// matched case-insensitively.
+    pub fn get_all<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Iterates over the names and values of the headers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl FromServerRequest for Headers {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        let headers = req
+            .headers()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Ok(Headers(headers))
+    }
+}
+
+/// The cookies sent with the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookies(Vec<(String, String)>);
+
+impl Cookies {
+    /// Returns the value of the cookie with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the value of the cookie with the given name, returning `None`
+    /// if there is no such cookie.
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>, ServerFnError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value.parse().map_err(|e| {
+                    ServerFnError::Deserialization(format!(
+                        "invalid value of cookie {name:?}: {e}"
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Iterates over the names and values of the cookies.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl FromServerRequest for Cookies {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        let cookies = req
+            .headers()
+            .into_iter()
+            .filter(|(name, _)| *name == "cookie")
+            .flat_map(|(_, value)| parse_cookies(value))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Ok(Cookies(cookies))
+    }
+}
+
+// the names and values of the cookies in a `Cookie` header, or in
+// `document.cookie`
+pub(crate) fn parse_cookies(
+    cookies: &str,
+) -> impl Iterator<Item = (&str, &str)> {
+    cookies.split(';').filter_map(|cookie| {
+        let (name, value) = cookie.split_once('=')?;
+        Some((name.trim(), value.trim().trim_matches('"')))
+    })
+}
+
+/// The IP address of the client that made the request.
+///
+/// This is the address of the connection, so it is the address of the proxy
+/// if the server is behind one. The address of the client is then usually
+/// sent by the proxy in a header, like `X-Forwarded-For`, which can be read
+/// from the [`Headers`] if the proxy is trusted to set it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientIp(pub IpAddr);
+
+impl FromServerRequest for ClientIp {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        req.client_addr()
+            .map(|addr| ClientIp(addr.ip()))
+            .ok_or_else(|| {
+                ServerFnError::ServerError(
+                    "the address of the client is not known".into(),
+                )
+            })
+    }
+}
+
+/// The method of the request, like `GET`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Method(pub String);
+
+impl FromServerRequest for Method {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        Ok(Method(req.method().to_string()))
+    }
+}
+
+/// The path and query string of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Uri(pub String);
+
+impl FromServerRequest for Uri {
+    fn from_request(req: &dyn ServerRequest) -> Result<Self, ServerFnError> {
+        Ok(Uri(req.uri()))
+    }
+}
@@ -0,0 +1,81 @@
+use leptos_reactive::{
+    create_memo, create_resource, signal_prelude::*, Resource, Serializable,
+};
+use serde::Serialize;
+use server_fn::{ServerFn, ServerFnError};
+
+/// Creates a [`Resource`] that calls the server function returned by
+/// `source`, and calls it again whenever that changes.
+///
+/// On the client, calls go through the server function's cache, if it is
+/// declared with `#[server(cache = <ttl>)]` (see
+/// [`server_fn::cache`](server_fn::cache::run_cached)): resources calling it
+/// with the same arguments at the same time share one request, and results
+/// are reused until they expire or are invalidated. The results serialized
+/// during server-side rendering also seed the cache when the page hydrates,
+/// so calling the server function with the same arguments later does not
+/// request them again.
+///
+/// ```rust,ignore
+/// #[server(cache = Duration::from_secs(60))]
+/// pub async fn get_user(id: usize) -> Result<User, ServerFnError> {
+///     // ...
+/// }
+///
+/// #[component]
+/// fn UserName(id: usize) -> impl IntoView {
+///     let user = create_server_fn_resource(move || GetUser { id });
+///     view! {
+///         <Suspense>
+///             {move || user.get().map(|user| user.map(|user| user.name))}
+///         </Suspense>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+pub fn create_server_fn_resource<S>(
+    source: impl Fn() -> S + 'static,
+) -> Resource<S, Result<S::Output, ServerFnError<S::Error>>>
+where
+    S: ServerFn + Clone + PartialEq + Serialize + 'static,
+    S::Output: Clone + Sync + 'static,
+    S::Error: Clone + Send + Sync + 'static,
+    Result<S::Output, ServerFnError<S::Error>>: Serializable,
+{
+    let source = create_memo(move |_| source());
+
+    // The server is able to call the function directly
+    #[cfg(feature = "ssr")]
+    let fetcher = S::run_body;
+
+    #[cfg(not(feature = "ssr"))]
+    let fetcher = server_fn::cache::run_cached;
+
+    let resource = create_resource(move || source.get(), fetcher);
+
+    // seed the cache with the first result, which is the one serialized
+    // during server-side rendering when hydrating, as long as it is still
+    // the result for the current arguments
+    #[cfg(not(feature = "ssr"))]
+    {
+        let initial = source.get_untracked();
+        leptos_reactive::create_effect(move |seeded: Option<bool>| {
+            if seeded == Some(true) {
+                return true;
+            }
+            resource.with(|value| match value {
+                Some(Ok(value)) if source.get_untracked() == initial => {
+                    server_fn::cache::seed(&initial, value.clone());
+                    true
+                }
+                Some(_) => true,
+                None => false,
+            })
+        });
+    }
+
+    resource
+}
@@ -0,0 +1,298 @@
+use leptos_reactive::{provide_context, use_context};
+use server_fn::ServerFnError;
+use std::{cell::Cell, fmt, rc::Rc, time::Duration};
+
+/// A change to the response on the server, which is applied by the server
+/// integration. See [`provide_server_response`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseUpdate<'a> {
+    /// Sets the status code of the response.
+    Status(u16),
+    /// Inserts a header, overwriting any previous value with the same name.
+    InsertHeader(&'a str, &'a str),
+    /// Appends a header, leaving any header with the same name intact.
+    AppendHeader(&'a str, &'a str),
+}
+
+type ResponseHandler = dyn Fn(ResponseUpdate<'_>) -> Result<(), String>;
+
+/// Wrapping type for a function provided as context to allow for changing the
+/// status code and headers of the response on the server. See
+/// [`provide_server_response`].
+#[derive(Clone)]
+pub struct ServerResponseFunction {
+    f: Rc<ResponseHandler>,
+    sent: Rc<Cell<bool>>,
+}
+
+impl fmt::Debug for ServerResponseFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerResponseFunction").finish()
+    }
+}
+
+/// Provides a function that applies a [`ResponseUpdate`] to the response on
+/// the server, returning an error if the status code or header is invalid.
+///
+/// This is provided by the server integrations, so that server functions can
+/// change the response with [`set_response_status`], [`insert_response_header`],
+/// [`append_response_header`], and [`set_cookie`] without depending on them.
+///
+/// While a page is rendered, the response can be changed until its status code
+/// and headers have been sent, which the integration marks with
+/// [`mark_response_sent`]. When streaming, this is once the shell has been
+/// rendered: the components rendered before then, and the `<Suspense/>`
+/// fragments that block the shell, can change the response, like a page that
+/// sets a `404` status code when the post it shows does not exist. Changes
+/// made after that, like in a fragment streamed in after the shell, are
+/// ignored, with a warning in debug builds.
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+pub fn provide_server_response(
+    handler: impl Fn(ResponseUpdate<'_>) -> Result<(), String> + 'static,
+) {
+    provide_context(ServerResponseFunction {
+        f: Rc::new(handler),
+        sent: Rc::new(Cell::new(false)),
+    })
+}
+
+/// Marks the status code and headers of the response as sent, after which
+/// changes to them are ignored. This is called by the server integrations once
+/// they have read them from the response options.
+pub fn mark_response_sent() {
+    if let Some(response) = use_context::<ServerResponseFunction>() {
+        response.sent.set(true);
+    }
+}
+
+/// Whether the status code and headers of the response have already been
+/// sent, so that they can no longer be changed. See
+/// [`provide_server_response`].
+pub fn response_sent() -> bool {
+    use_context::<ServerResponseFunction>()
+        .is_some_and(|response| response.sent.get())
+}
+
+/// Warns, in debug builds, that a change to the response is ignored because
+/// its status code and headers have already been sent. Returns whether they
+/// have.
+pub fn warn_if_response_sent(update: ResponseUpdate<'_>) -> bool {
+    let sent = response_sent();
+    if sent && cfg!(debug_assertions) {
+        tracing::warn!(
+            "{update:?} is ignored, as the status code and headers of the \
+             response have already been sent. They can only be changed while \
+             the shell of the page is rendered, before the first chunk is \
+             streamed."
+        );
+    }
+    sent
+}
+
+fn update_response(update: ResponseUpdate<'_>) -> Result<(), ServerFnError> {
+    let Some(response) = use_context::<ServerResponseFunction>() else {
+        return Err(ServerFnError::ServerError(
+            "The response can only be changed on the server, within a \
+             handler provided by a server integration."
+                .into(),
+        ));
+    };
+    if warn_if_response_sent(update) {
+        return Err(ServerFnError::ServerError(
+            "The response has already been sent.".into(),
+        ));
+    }
+    (response.f)(update).map_err(ServerFnError::ServerError)
+}
+
+/// Sets the status code of the response, from within a server function or
+/// while rendering on the server.
+///
+/// ```rust,ignore
+/// #[server]
+/// pub async fn create_post(title: String) -> Result<(), ServerFnError> {
+///     // ...
+///     set_response_status(201)?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_response_status(status: u16) -> Result<(), ServerFnError> {
+    update_response(ResponseUpdate::Status(status))
+}
+
+/// Inserts a header into the response, overwriting any previous value with the
+/// same name, from within a server function or while rendering on the server.
+pub fn insert_response_header(
+    name: &str,
+    value: &str,
+) -> Result<(), ServerFnError> {
+    update_response(ResponseUpdate::InsertHeader(name, value))
+}
+
+/// Appends a header to the response, leaving any header with the same name
+/// intact, from within a server function or while rendering on the server.
+pub fn append_response_header(
+    name: &str,
+    value: &str,
+) -> Result<(), ServerFnError> {
+    update_response(ResponseUpdate::AppendHeader(name, value))
+}
+
+/// Adds a `Set-Cookie` header to the response, from within a server function
+/// or while rendering on the server.
+///
+/// ```rust,ignore
+/// #[server]
+/// pub async fn login(user: String, password: String) -> Result<(), ServerFnError> {
+///     let session = start_session(&user, &password).await?;
+///     set_cookie(
+///         &Cookie::new("session", session)
+///             .http_only(true)
+///             .secure(true)
+///             .same_site(SameSite::Lax)
+///             .max_age(Duration::from_secs(60 * 60 * 24)),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_cookie(cookie: &Cookie) -> Result<(), ServerFnError> {
+    append_response_header("set-cookie", &cookie.to_string())
+}
+
+/// The `SameSite` attribute of a [`Cookie`], which controls whether it is sent
+/// with requests from other sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SameSite {
+    /// The cookie is only sent with requests from the same site.
+    Strict,
+    /// The cookie is also sent when navigating to the site from another site.
+    Lax,
+    /// The cookie is sent with all requests. This requires the cookie to be
+    /// `Secure`, so it is always marked as such.
+    None,
+}
+
+impl fmt::Display for SameSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        })
+    }
+}
+
+/// A cookie to be set with [`set_cookie`], which is displayed as the value of
+/// a `Set-Cookie` header.
+///
+/// By default, the cookie applies to the whole site (`Path=/`) and lasts until
+/// the browser is closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    max_age: Option<Duration>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    /// Creates a cookie with the given name and value.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            path: Some("/".into()),
+            domain: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    /// Creates a cookie that removes the cookie with the given name, by
+    /// expiring it immediately. Its path and domain should match those of the
+    /// cookie being removed.
+    pub fn removal(name: impl Into<String>) -> Self {
+        Self::new(name, "").max_age(Duration::ZERO)
+    }
+
+    /// The name of the cookie.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the value of the cookie.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Sets the path that the cookie is sent with requests to.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the domain that the cookie is sent with requests to.
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Sets how long the cookie lasts for.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets whether the cookie is only sent over HTTPS.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Sets whether the cookie is hidden from JavaScript.
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    /// Sets the [`SameSite`] attribute of the cookie.
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+impl fmt::Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(path) = &self.path {
+            write!(f, "; Path={path}")?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={domain}")?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if self.secure || self.same_site == Some(SameSite::None) {
+            f.write_str("; Secure")?;
+        }
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={same_site}")?;
+        }
+        Ok(())
+    }
+}
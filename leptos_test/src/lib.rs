@@ -0,0 +1,464 @@
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+//! # Leptos Test
+//!
+//! Leptos Test mounts components into a headless renderer, so that they can
+//! be tested with `cargo test`, without a browser.
+//!
+//! A [`TestHarness`] provides the context a component expects, such as a
+//! [`MetaContext`](leptos_meta::MetaContext) or a router integration, and
+//! mounts it. The mounted [`TestApp`] can then be queried by role, text or
+//! test id, receive events, and be checked after its signals have changed.
+//!
+//! Server functions can be replaced with mocks for the duration of a test,
+//! and timeouts and intervals, including the ones behind
+//! [`debounce`](leptos::leptos_dom::helpers::debounce), run on a virtual clock
+//! that only moves forward with [`TestApp::advance`].
+//!
+//! ```
+//! use leptos::*;
+//! use leptos_test::*;
+//!
+//! #[component]
+//! fn Counter() -> impl IntoView {
+//!     let (count, set_count) = create_signal(0);
+//!
+//!     html::button()
+//!         .on(ev::click, move |_| set_count.update(|n| *n += 1))
+//!         .child(move || format!("Clicked {} times", count.get()))
+//! }
+//!
+//! let app = TestHarness::new().mount(Counter);
+//!
+//! app.click(&app.get(By::Role("button")));
+//!
+//! assert_eq!(app.get(By::Role("button")).text(), "Clicked 1 times");
+//! ```
+//!
+//! # Limitations
+//! - With the `ssr` feature, the `view` macro compiles elements into HTML
+//!   templates, which can't be inspected. Test components in a crate (or with
+//!   a set of features) that doesn't enable `ssr`, or build their views with
+//!   the [`html`](leptos::html) builder.
+//! - Events are only dispatched to the element they target: they don't bubble,
+//!   and they don't have default actions, like submitting a form.
+//! - Mounting a `<Router/>` needs browser APIs unless `leptos_router` has the
+//!   `ssr` feature. Without it, [`TestHarness::router`] only provides the
+//!   router integration, for code that reads it directly.
+
+pub use leptos::leptos_dom::testing::{TargetState, TestElement, TestNode};
+use leptos::{
+    create_runtime, create_signal, leptos_dom::testing, provide_context,
+    IntoView, ReadSignal, RuntimeId, SignalSet, SignalWithUntracked, View,
+    WriteSignal,
+};
+use leptos_router::{History, LocationChange, RouterIntegrationContext};
+use server_fn::{error::ServerFnError, ServerFn};
+use std::{
+    any::Any,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Sets up the context for a component, and mounts it.
+///
+/// The harness creates its own reactive runtime and starts the virtual clock
+/// at zero. The runtime, the timers and the mocks of server functions are
+/// disposed when the [`TestApp`] it mounts is dropped.
+pub struct TestHarness {
+    runtime: RuntimeId,
+    history: Option<TestHistory>,
+    mocks: Vec<Box<dyn Any>>,
+}
+
+impl TestHarness {
+    /// Creates a new harness, with no context.
+    pub fn new() -> Self {
+        testing::reset_time();
+        Self {
+            runtime: create_runtime(),
+            history: None,
+            mocks: Vec::new(),
+        }
+    }
+
+    /// Provides a context value to the component.
+    pub fn context<T: Clone + 'static>(self, value: T) -> Self {
+        provide_context(value);
+        self
+    }
+
+    /// Provides a [`MetaContext`](leptos_meta::MetaContext), so that the
+    /// component can use `leptos_meta`.
+    pub fn meta(self) -> Self {
+        leptos_meta::provide_meta_context();
+        self
+    }
+
+    /// Provides a [`RouterIntegrationContext`] whose history starts at `path`,
+    /// and is only kept in memory.
+    pub fn router(self, path: impl Into<String>) -> Self {
+        let history = TestHistory::new(path);
+        provide_context(RouterIntegrationContext::new(history));
+        Self {
+            history: Some(history),
+            ..self
+        }
+    }
+
+    /// Replaces the server function `S` with `f` until the [`TestApp`] is
+    /// dropped.
+    ///
+    /// Calls to the server function run `f` with its arguments instead of
+    /// sending a request or running its body. See
+    /// [`server_fn::mock::mock`].
+    pub fn mock_server_fn<S, F, Fut>(mut self, f: F) -> Self
+    where
+        S: ServerFn + 'static,
+        F: Fn(S) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<S::Output, ServerFnError<S::Error>>>
+            + Send
+            + 'static,
+    {
+        self.mocks.push(Box::new(server_fn::mock::mock(f)));
+        self
+    }
+
+    /// Mounts the view returned by `f`.
+    pub fn mount<F, N>(self, f: F) -> TestApp
+    where
+        F: FnOnce() -> N,
+        N: IntoView,
+    {
+        TestApp {
+            view: f().into_view(),
+            runtime: self.runtime,
+            history: self.history,
+            _mocks: self.mocks,
+        }
+    }
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A way to find elements in a [`TestApp`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum By<'a> {
+    /// Elements with this ARIA role, either set with the `role` attribute or
+    /// implied by the element, like `button` for `<button>`.
+    Role(&'a str),
+    /// Elements whose own text, without the text of their children and
+    /// ignoring surrounding whitespace, is equal to this.
+    Text(&'a str),
+    /// Elements whose `data-testid` attribute is equal to this.
+    TestId(&'a str),
+}
+
+impl By<'_> {
+    fn matches(&self, el: &TestElement) -> bool {
+        match self {
+            By::Role(role) => self::role(el) == Some(role),
+            By::Text(text) => {
+                let own_text = el
+                    .children()
+                    .iter()
+                    .filter_map(|node| match node {
+                        TestNode::Text(text) => Some(text.as_str()),
+                        TestNode::Element(_) => None,
+                    })
+                    .collect::<String>();
+                own_text.trim() == *text
+            }
+            By::TestId(id) => el.attr("data-testid") == Some(id),
+        }
+    }
+}
+
+impl fmt::Display for By<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            By::Role(role) => write!(f, "role {role:?}"),
+            By::Text(text) => write!(f, "text {text:?}"),
+            By::TestId(id) => write!(f, "test id {id:?}"),
+        }
+    }
+}
+
+/// The ARIA role of an element.
+fn role(el: &TestElement) -> Option<&str> {
+    if let Some(role) = el.attr("role") {
+        return Some(role);
+    }
+    let role = match el.name() {
+        "a" | "area" if el.attr("href").is_some() => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "button" => "button",
+        "dialog" => "dialog",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" => "img",
+        "input" => match el.attr("type").unwrap_or("text") {
+            "button" | "image" | "reset" | "submit" => "button",
+            "checkbox" => "checkbox",
+            "number" => "spinbutton",
+            "radio" => "radio",
+            "range" => "slider",
+            "search" => "searchbox",
+            "email" | "tel" | "text" | "url" => "textbox",
+            _ => return None,
+        },
+        "li" => "listitem",
+        "main" => "main",
+        "nav" => "navigation",
+        "ol" | "ul" => "list",
+        "option" => "option",
+        "p" => "paragraph",
+        "progress" => "progressbar",
+        "select" => "combobox",
+        "table" => "table",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "tr" => "row",
+        _ => return None,
+    };
+    Some(role)
+}
+
+/// A mounted component.
+///
+/// Queries render the current state of the view, so elements should be
+/// queried again after its signals have changed.
+pub struct TestApp {
+    view: View,
+    runtime: RuntimeId,
+    history: Option<TestHistory>,
+    _mocks: Vec<Box<dyn Any>>,
+}
+
+impl TestApp {
+    /// The top-level nodes of the view.
+    pub fn nodes(&self) -> Vec<TestNode> {
+        testing::render(&self.view)
+    }
+
+    /// The view rendered as HTML.
+    pub fn html(&self) -> String {
+        self.nodes().iter().map(ToString::to_string).collect()
+    }
+
+    /// The text content of the view.
+    pub fn text(&self) -> String {
+        self.nodes().iter().map(TestNode::text).collect()
+    }
+
+    /// All elements that match, in document order.
+    pub fn all(&self, by: By) -> Vec<TestElement> {
+        fn collect(nodes: &[TestNode], by: By, into: &mut Vec<TestElement>) {
+            for node in nodes {
+                if let TestNode::Element(el) = node {
+                    if by.matches(el) {
+                        into.push(el.clone());
+                    }
+                    collect(el.children(), by, into);
+                }
+            }
+        }
+
+        let mut elements = Vec::new();
+        collect(&self.nodes(), by, &mut elements);
+        elements
+    }
+
+    /// The first element that matches, if any.
+    pub fn query(&self, by: By) -> Option<TestElement> {
+        self.all(by).into_iter().next()
+    }
+
+    /// The only element that matches.
+    ///
+    /// # Panics
+    /// Panics if no element, or more than one element, matches.
+    #[track_caller]
+    pub fn get(&self, by: By) -> TestElement {
+        let mut elements = self.all(by);
+        match elements.len() {
+            1 => elements.remove(0),
+            0 => panic!("no element with {by} in\n{}", self.html()),
+            n => panic!("{n} elements with {by} in\n{}", self.html()),
+        }
+    }
+
+    /// Dispatches an event to the element, and returns whether it had any
+    /// listeners for it.
+    pub fn dispatch(
+        &self,
+        el: &TestElement,
+        event: &str,
+        target: TargetState,
+    ) -> bool {
+        el.dispatch(event, target)
+    }
+
+    /// Clicks the element.
+    pub fn click(&self, el: &TestElement) {
+        self.dispatch(el, "click", target_state(el));
+    }
+
+    /// Types `value` into an input, dispatching `input` and `change` events.
+    pub fn input(&self, el: &TestElement, value: impl Into<String>) {
+        let target = TargetState {
+            value: value.into(),
+            ..target_state(el)
+        };
+        self.dispatch(el, "input", target.clone());
+        self.dispatch(el, "change", target);
+    }
+
+    /// Checks or unchecks a checkbox or radio button, dispatching `click`,
+    /// `input` and `change` events.
+    pub fn set_checked(&self, el: &TestElement, checked: bool) {
+        let target = TargetState {
+            checked,
+            ..target_state(el)
+        };
+        for event in ["click", "input", "change"] {
+            self.dispatch(el, event, target.clone());
+        }
+    }
+
+    /// The current path of the history provided by
+    /// [`TestHarness::router`], if any.
+    pub fn path(&self) -> Option<String> {
+        self.history.map(|history| history.path())
+    }
+
+    /// Moves the virtual clock forward, running the timeouts and intervals
+    /// that are due.
+    ///
+    /// Tasks spawned by those timers run when the app [settles](Self::settle).
+    pub fn advance(&self, duration: Duration) {
+        testing::advance_time(duration);
+    }
+
+    /// The time that has passed on the virtual clock since the harness was
+    /// created.
+    pub fn now(&self) -> Duration {
+        testing::now()
+    }
+
+    /// Waits until spawned tasks, like the ones loading resources, have had a
+    /// chance to run.
+    pub async fn settle(&self) {
+        for _ in 0..SETTLE_YIELDS {
+            YieldNow(false).await;
+        }
+    }
+
+    /// Waits until `condition` is true, letting spawned tasks run in between.
+    ///
+    /// # Panics
+    /// Panics if `condition` is still false after the tasks have settled.
+    pub async fn wait_for(&self, condition: impl Fn(&TestApp) -> bool) {
+        for _ in 0..SETTLE_YIELDS {
+            if condition(self) {
+                return;
+            }
+            YieldNow(false).await;
+        }
+        if !condition(self) {
+            panic!("the condition was never true in\n{}", self.html());
+        }
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        testing::reset_time();
+        self.runtime.dispose();
+    }
+}
+
+impl fmt::Debug for TestApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.html())
+    }
+}
+
+/// The target of an event on the element, with its current `value` and
+/// `checked` attributes.
+fn target_state(el: &TestElement) -> TargetState {
+    TargetState {
+        value: el.attr("value").unwrap_or_default().to_string(),
+        checked: el.attr("checked").is_some(),
+    }
+}
+
+/// How many times [`TestApp::settle`] yields to the executor.
+const SETTLE_YIELDS: usize = 64;
+
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// A [`History`] that is only kept in memory, provided by
+/// [`TestHarness::router`].
+#[derive(Clone, Copy, Debug)]
+pub struct TestHistory {
+    location: ReadSignal<LocationChange>,
+    set_location: WriteSignal<LocationChange>,
+}
+
+impl TestHistory {
+    /// Creates a history that starts at `path`.
+    pub fn new(path: impl Into<String>) -> Self {
+        let (location, set_location) = create_signal(LocationChange {
+            value: path.into(),
+            ..Default::default()
+        });
+        Self {
+            location,
+            set_location,
+        }
+    }
+
+    /// The current path.
+    pub fn path(&self) -> String {
+        self.location
+            .with_untracked(|location| location.value.clone())
+    }
+}
+
+impl History for TestHistory {
+    fn location(&self) -> ReadSignal<LocationChange> {
+        self.location
+    }
+
+    fn navigate(&self, loc: &LocationChange) {
+        self.set_location.set(loc.clone());
+    }
+}
@@ -0,0 +1,164 @@
+// the views are built without the `view` macro, which compiles elements into
+// HTML templates when `ssr` is enabled elsewhere in the workspace
+use leptos::{leptos_dom::Each, *};
+use leptos_router::{History, LocationChange, RouterIntegrationContext};
+use leptos_test::*;
+use std::{cell::Cell, rc::Rc};
+
+#[component]
+fn Counter() -> impl IntoView {
+    let (count, set_count) = create_signal(0);
+
+    html::div()
+        .child(
+            html::button()
+                .attr("data-testid", "increment")
+                .on(ev::click, move |_| set_count.update(|n| *n += 1))
+                .child("+1"),
+        )
+        .child(
+            html::p()
+                .class("even", move || count.get() % 2 == 0)
+                .child(move || format!("Count: {}", count.get())),
+        )
+}
+
+#[test]
+fn queries_by_role_text_and_test_id() {
+    let app = TestHarness::new().mount(Counter);
+
+    assert_eq!(app.get(By::Role("button")).text(), "+1");
+    assert_eq!(app.get(By::Text("Count: 0")).name(), "p");
+    assert_eq!(app.get(By::TestId("increment")).name(), "button");
+    assert!(app.query(By::Role("link")).is_none());
+    assert_eq!(app.all(By::Role("paragraph")).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "no element with role \"link\"")]
+fn get_panics_without_a_match() {
+    let app = TestHarness::new().mount(Counter);
+
+    app.get(By::Role("link"));
+}
+
+#[test]
+fn click_updates_the_view() {
+    let app = TestHarness::new().mount(Counter);
+
+    assert_eq!(
+        app.html(),
+        "<div><button data-testid=\"increment\">+1</button><p \
+         class=\"even\">Count: 0</p></div>"
+    );
+
+    app.click(&app.get(By::Role("button")));
+
+    assert_eq!(app.get(By::Role("paragraph")).text(), "Count: 1");
+    assert!(!app.get(By::Role("paragraph")).has_class("even"));
+
+    app.click(&app.get(By::Role("button")));
+
+    assert_eq!(app.get(By::Role("paragraph")).text(), "Count: 2");
+    assert!(app.get(By::Role("paragraph")).has_class("even"));
+}
+
+#[test]
+fn input_and_check_update_bound_signals() {
+    let harness = TestHarness::new();
+    let name = create_rw_signal(String::from("Alice"));
+    let agreed = create_rw_signal(false);
+    let app = harness.mount(move || {
+        html::form()
+            .child(html::input().attr("type", "text").bind_value(name))
+            .child(html::input().attr("type", "checkbox").bind_checked(agreed))
+            .child(html::p().child(move || {
+                format!("{} agreed: {}", name.get(), agreed.get())
+            }))
+    });
+
+    assert_eq!(app.get(By::Role("textbox")).attr("value"), Some("Alice"));
+
+    app.input(&app.get(By::Role("textbox")), "Bob");
+    app.set_checked(&app.get(By::Role("checkbox")), true);
+
+    assert_eq!(name.get_untracked(), "Bob");
+    assert!(agreed.get_untracked());
+    assert_eq!(app.get(By::Role("textbox")).attr("value"), Some("Bob"));
+    assert_eq!(app.get(By::Role("checkbox")).attr("checked"), Some(""));
+    assert_eq!(app.get(By::Role("paragraph")).text(), "Bob agreed: true");
+}
+
+#[test]
+fn keyed_lists_keep_their_rows() {
+    let harness = TestHarness::new();
+    let items = create_rw_signal(vec![1, 2, 3]);
+    let rows_created = Rc::new(Cell::new(0));
+    let app = harness.mount({
+        let rows_created = Rc::clone(&rows_created);
+        move || {
+            html::ul().child(Each::new(
+                move || items.get(),
+                |item| *item,
+                move |item| {
+                    rows_created.set(rows_created.get() + 1);
+                    html::li().child(item.to_string())
+                },
+            ))
+        }
+    });
+
+    assert_eq!(app.text(), "123");
+
+    items.update(|items| {
+        items.remove(0);
+        items.push(4);
+    });
+
+    assert_eq!(app.text(), "234");
+    assert_eq!(app.all(By::Role("listitem")).len(), 3);
+    assert_eq!(rows_created.get(), 4);
+}
+
+#[test]
+fn provides_context_and_meta() {
+    let app = TestHarness::new()
+        .context(String::from("from context"))
+        .meta()
+        .mount(|| {
+            let text = expect_context::<String>();
+            let has_meta = use_context::<leptos_meta::MetaContext>().is_some();
+            html::p().child(format!("{text}, meta: {has_meta}"))
+        });
+
+    assert_eq!(app.text(), "from context, meta: true");
+}
+
+#[test]
+fn router_history_is_kept_in_memory() {
+    let app = TestHarness::new().router("/").mount(|| {
+        let history = expect_context::<RouterIntegrationContext>();
+        html::button().on(ev::click, move |_| {
+            history.navigate(&LocationChange {
+                value: "/about".into(),
+                ..Default::default()
+            })
+        })
+    });
+
+    assert_eq!(app.path().as_deref(), Some("/"));
+
+    app.click(&app.get(By::Role("button")));
+
+    assert_eq!(app.path().as_deref(), Some("/about"));
+}
+
+#[test]
+fn wait_for_returns_once_the_condition_is_true() {
+    let app = TestHarness::new().mount(Counter);
+
+    futures::executor::block_on(async {
+        app.settle().await;
+        app.wait_for(|app| app.text().contains("Count: 0")).await;
+    });
+}
@@ -0,0 +1,38 @@
+use leptos::*;
+use leptos_test::*;
+
+#[server]
+pub async fn get_greeting(name: String) -> Result<String, ServerFnError> {
+    Ok(format!("Hello from the server, {name}!"))
+}
+
+#[test]
+fn mocks_replace_server_fns_until_the_app_is_dropped() {
+    let app = TestHarness::new()
+        .mock_server_fn::<GetGreeting, _, _>(
+            |GetGreeting { name }| async move {
+                Ok(format!("Hello from the mock, {name}!"))
+            },
+        )
+        .mount(|| html::p().child("mocked"));
+
+    let greeting =
+        futures::executor::block_on(get_greeting("Leptos".into())).unwrap();
+    assert_eq!(greeting, "Hello from the mock, Leptos!");
+
+    drop(app);
+
+    assert!(server_fn::mock::get::<GetGreeting>().is_none());
+}
+
+#[test]
+fn mocks_can_return_errors() {
+    let _app = TestHarness::new()
+        .mock_server_fn::<GetGreeting, _, _>(|_| async {
+            Err(ServerFnError::ServerError("unavailable".into()))
+        })
+        .mount(|| ());
+
+    let res = futures::executor::block_on(get_greeting("Leptos".into()));
+    assert_eq!(res, Err(ServerFnError::ServerError("unavailable".into())));
+}
@@ -0,0 +1,77 @@
+use leptos::{leptos_dom::helpers::debounce, *};
+use leptos_test::*;
+use std::time::Duration;
+
+#[test]
+fn debounced_input_waits_for_the_virtual_clock() {
+    let harness = TestHarness::new();
+    let search = create_rw_signal(String::new());
+    let app = harness.mount(move || {
+        let mut on_input = debounce(Duration::from_millis(300), move |value| {
+            search.set(value)
+        });
+        html::input()
+            .attr("type", "search")
+            .on(ev::input, move |ev| on_input(event_target_value(&ev)))
+    });
+    let input = app.get(By::Role("searchbox"));
+
+    app.input(&input, "lep");
+    app.advance(Duration::from_millis(200));
+    app.input(&input, "leptos");
+    app.advance(Duration::from_millis(200));
+
+    assert_eq!(search.get_untracked(), "");
+
+    app.advance(Duration::from_millis(100));
+
+    assert_eq!(search.get_untracked(), "leptos");
+    assert_eq!(app.now(), Duration::from_millis(500));
+}
+
+#[test]
+fn intervals_run_until_they_are_cleared() {
+    let harness = TestHarness::new();
+    let polls = create_rw_signal(0);
+    let app = harness.mount(move || {
+        let handle = set_interval_with_handle(
+            move || polls.update(|n| *n += 1),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        html::button()
+            .on(ev::click, move |_| handle.clear())
+            .child(move || format!("Polled {} times", polls.get()))
+    });
+
+    app.advance(Duration::from_millis(3500));
+
+    assert_eq!(app.text(), "Polled 3 times");
+
+    app.click(&app.get(By::Role("button")));
+    app.advance(Duration::from_secs(10));
+
+    assert_eq!(app.text(), "Polled 3 times");
+}
+
+#[test]
+fn timeouts_scheduled_by_timeouts_run_when_they_are_due() {
+    let harness = TestHarness::new();
+    let attempts = create_rw_signal(Vec::new());
+    let app = harness.mount(move || {
+        fn retry(attempts: RwSignal<Vec<Duration>>) {
+            attempts.update(|a| a.push(leptos_dom::testing::now()));
+            if attempts.with_untracked(Vec::len) < 3 {
+                set_timeout(move || retry(attempts), Duration::from_secs(2));
+            }
+        }
+        set_timeout(move || retry(attempts), Duration::from_secs(1));
+    });
+
+    app.advance(Duration::from_secs(10));
+
+    assert_eq!(
+        attempts.get_untracked(),
+        [1, 3, 5].map(Duration::from_secs).to_vec()
+    );
+}
@@ -0,0 +1,207 @@
+// the classes and attributes are only read when rendering on the server or in
+// the browser
+#![cfg_attr(
+    not(any(feature = "ssr", target_arch = "wasm32")),
+    allow(dead_code)
+)]
+
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// The classes and attributes that components add to the `<html>` or
+/// `<body>` element.
+///
+/// Each component that renders `<Html/>` or `<Body/>` adds its own set, which
+/// is removed again when it is unmounted. The classes of every set are
+/// combined, while an attribute set by several components takes the value of
+/// the one rendered last.
+#[derive(Clone, Default)]
+pub(crate) struct RootElementContext {
+    next_id: Rc<Cell<usize>>,
+    contributions: Rc<RefCell<Vec<Contribution>>>,
+    // the classes and attributes last set on the element, so that the ones
+    // that are no longer added by any component can be removed
+    #[cfg(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    ))]
+    applied: Rc<RefCell<(Vec<String>, Vec<&'static str>)>>,
+}
+
+struct Contribution {
+    id: usize,
+    class: Option<TextProp>,
+    attributes: Vec<(&'static str, Attribute)>,
+}
+
+impl RootElementContext {
+    /// Adds the classes and attributes of a component, until it is unmounted.
+    /// In the browser, they are applied to `el` whenever any of them changes.
+    pub fn contribute(
+        &self,
+        #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))]
+        el: web_sys::Element,
+        class: Option<TextProp>,
+        attributes: Vec<(&'static str, Attribute)>,
+    ) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.contributions.borrow_mut().push(Contribution {
+            id,
+            class,
+            attributes,
+        });
+
+        #[cfg(all(
+            target_arch = "wasm32",
+            any(feature = "csr", feature = "hydrate")
+        ))]
+        create_render_effect({
+            let this = self.clone();
+            let el = el.clone();
+            move |_| this.apply(&el)
+        });
+
+        on_cleanup({
+            let this = self.clone();
+            move || {
+                this.contributions
+                    .borrow_mut()
+                    .retain(|contribution| contribution.id != id);
+                #[cfg(all(
+                    target_arch = "wasm32",
+                    any(feature = "csr", feature = "hydrate")
+                ))]
+                untrack(|| this.apply(&el));
+            }
+        });
+    }
+
+    /// The classes added by every component, without duplicates.
+    pub fn classes(&self) -> Vec<String> {
+        let mut classes = Vec::<String>::new();
+        for contribution in self.contributions.borrow().iter() {
+            // a `class` passed with `attr:class` is combined as well
+            let values = contribution
+                .class
+                .iter()
+                .map(TextProp::get)
+                .chain(contribution.attributes.iter().filter_map(
+                    |(name, value)| {
+                        (*name == "class")
+                            .then(|| value.as_nameless_value_string())
+                            .flatten()
+                    },
+                ))
+                .collect::<Vec<_>>();
+            for class in
+                values.iter().flat_map(|value| value.split_whitespace())
+            {
+                if !classes.iter().any(|other| other == class) {
+                    classes.push(class.to_string());
+                }
+            }
+        }
+        classes
+    }
+
+    /// The value of each attribute, taken from the component rendered last
+    /// that sets it, in the order in which they were first added.
+    pub fn attributes(&self) -> Vec<(&'static str, Oco<'static, str>)> {
+        let mut attributes =
+            Vec::<(&'static str, Option<Oco<'static, str>>)>::new();
+        for contribution in self.contributions.borrow().iter() {
+            for (name, value) in &contribution.attributes {
+                if *name == "class" {
+                    continue;
+                }
+                let value = value.as_nameless_value_string();
+                match attributes.iter_mut().find(|(other, _)| other == name) {
+                    Some((_, other)) => *other = value,
+                    None => attributes.push((name, value)),
+                }
+            }
+        }
+        attributes
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .collect()
+    }
+
+    /// Converts the classes and attributes into an HTML string.
+    #[cfg(any(feature = "ssr", doc))]
+    pub fn as_string(&self) -> Option<String> {
+        use leptos::leptos_dom::ssr::escape_attr;
+
+        let classes = self.classes();
+        let class = (!classes.is_empty())
+            .then(|| format!("class=\"{}\"", escape_attr(&classes.join(" "))));
+        let mut val = self
+            .attributes()
+            .into_iter()
+            .map(|(name, value)| format!("{name}=\"{}\"", escape_attr(&value)))
+            .chain(class)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if val.is_empty() {
+            None
+        } else {
+            val.insert(0, ' ');
+            Some(val)
+        }
+    }
+
+    #[cfg(all(
+        target_arch = "wasm32",
+        any(feature = "csr", feature = "hydrate")
+    ))]
+    fn apply(&self, el: &web_sys::Element) {
+        let classes = self.classes();
+        let attributes = self.attributes();
+        let mut applied = self.applied.borrow_mut();
+
+        // classes set by the page itself, rather than by a component, are
+        // left in place
+        let class_list = el.class_list();
+        for class in &applied.0 {
+            if !classes.contains(class) {
+                _ = class_list.remove_1(class);
+            }
+        }
+        for class in &classes {
+            _ = class_list.add_1(class);
+        }
+        for name in &applied.1 {
+            if !attributes.iter().any(|(other, _)| other == name) {
+                _ = el.remove_attribute(name);
+            }
+        }
+        for (name, value) in &attributes {
+            _ = el.set_attribute(name, value);
+        }
+
+        *applied = (
+            classes,
+            attributes.into_iter().map(|(name, _)| name).collect(),
+        );
+    }
+}
+
+/// Converts a prop of `<Html/>` or `<Body/>` into one of its attributes.
+pub(crate) fn prop_attribute(
+    name: &'static str,
+    prop: Option<TextProp>,
+) -> Option<(&'static str, Attribute)> {
+    prop.map(|prop| {
+        (
+            name,
+            Attribute::Fn(Rc::new(move || Attribute::String(prop.get()))),
+        )
+    })
+}
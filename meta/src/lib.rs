@@ -53,6 +53,8 @@ use leptos::{
     leptos_dom::{debug_warn, html::AnyElement},
     *,
 };
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::collections::HashMap;
 use std::{
     cell::{Cell, RefCell},
     fmt::Debug,
@@ -62,10 +64,13 @@ use std::{
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 mod body;
+mod element;
 mod html;
 mod link;
 mod meta_tags;
+mod preload;
 mod script;
+mod social;
 mod style;
 mod stylesheet;
 mod title;
@@ -73,7 +78,9 @@ pub use body::*;
 pub use html::*;
 pub use link::*;
 pub use meta_tags::*;
+pub use preload::*;
 pub use script::*;
+pub use social::*;
 pub use style::*;
 pub use stylesheet::*;
 pub use title::*;
@@ -92,6 +99,8 @@ pub struct MetaContext {
     pub body: BodyContext,
     /// Other metadata tags.
     pub tags: MetaTagsContext,
+    /// Resources that the page asks the browser to load early.
+    pub preloads: PreloadContext,
 }
 
 /// Manages all of the element created by components.
@@ -107,6 +116,18 @@ pub struct MetaTagsContext {
             >,
         >,
     >,
+    // the elements replaced by a later one registered with the same id, which
+    // are restored when it is removed
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    #[allow(clippy::type_complexity)]
+    replaced: Rc<
+        RefCell<
+            HashMap<
+                Oco<'static, str>,
+                Vec<(HtmlElement<AnyElement>, Option<web_sys::Element>)>,
+            >,
+        >,
+    >,
 }
 
 impl core::fmt::Debug for MetaTagsContext {
@@ -123,11 +144,47 @@ impl MetaTagsContext {
             .borrow()
             .iter()
             .map(|(_, (builder_el, _))| {
-                builder_el.clone().into_view().render_to_string()
+                // marks the tags that were rendered on the server, so that
+                // those that are not hydrated can be removed later
+                builder_el
+                    .clone()
+                    .attr(SERVER_RENDERED_ATTR, true)
+                    .into_view()
+                    .render_to_string()
             })
             .collect()
     }
 
+    /// Removes the tags that were rendered on the server but that no
+    /// component has registered since the page was hydrated, like those of a
+    /// page that has been replaced by a client-side navigation.
+    ///
+    /// The router calls this after each navigation. The tags registered by
+    /// components are already removed when they are unmounted.
+    pub fn remove_unclaimed(&self) {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        {
+            let Ok(rendered) = document()
+                .head()
+                .unwrap_throw()
+                .query_selector_all(&format!("[{SERVER_RENDERED_ATTR}]"))
+            else {
+                return;
+            };
+            for idx in 0..rendered.length() {
+                let Some(el) = rendered
+                    .item(idx)
+                    .and_then(|el| el.dyn_into::<web_sys::Element>().ok())
+                else {
+                    continue;
+                };
+                if !self.is_registered(&el.id(), &el) {
+                    el.remove();
+                }
+            }
+        }
+    }
+
     #[doc(hidden)]
     pub fn register(
         &self,
@@ -139,8 +196,24 @@ impl MetaTagsContext {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
                 use leptos::document;
 
-                let element_to_hydrate = document()
-                    .get_element_by_id(&id);
+                // the element rendered on the server is only reused while the
+                // page is hydrated, as one left over from a previous page may
+                // have the same id
+                let rendered = document().get_element_by_id(&id);
+                let element_to_hydrate = match rendered {
+                    Some(rendered)
+                        if leptos::leptos_dom::HydrationCtx::is_hydrating() =>
+                    {
+                        Some(rendered)
+                    }
+                    Some(rendered) => {
+                        if !self.is_registered(&id, &rendered) {
+                            rendered.remove();
+                        }
+                        None
+                    }
+                    None => None,
+                };
 
                 let el = element_to_hydrate.unwrap_or_else({
                     let builder_el = builder_el.clone();
@@ -159,9 +232,16 @@ impl MetaTagsContext {
                     let els = self.els.clone();
                     let id = id.clone();
                     move || {
-                        let head = document().head().unwrap_throw();
-                        _ = head.remove_child(&el);
-                        els.borrow_mut().swap_remove(&id);
+                        el.remove();
+                        // another component may have registered the same id
+                        // since
+                        let mut els = els.borrow_mut();
+                        if els
+                            .get(&id)
+                            .is_some_and(|(_, current)| current.as_ref() == Some(&el))
+                        {
+                            els.swap_remove(&id);
+                        }
                     }
                 });
 
@@ -177,6 +257,98 @@ impl MetaTagsContext {
     }
 }
 
+impl MetaTagsContext {
+    /// Registers an element that replaces any other registered with the same
+    /// id, like the `og:title` of a page replacing that of the whole app,
+    /// until it is removed again.
+    #[doc(hidden)]
+    pub fn register_unique(
+        &self,
+        id: Oco<'static, str>,
+        builder_el: HtmlElement<AnyElement>,
+    ) {
+        cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use leptos::document;
+
+                let head = document().head().unwrap_throw();
+                let el: web_sys::Element =
+                    (*builder_el).clone().unchecked_into();
+
+                // replaces the element rendered on the server, which is not
+                // updated when the content changes
+                if let Some(rendered) = document().get_element_by_id(&id) {
+                    if rendered != el {
+                        rendered.remove();
+                    }
+                }
+                if let Some(replaced) = self.els.borrow().get(&id).cloned() {
+                    if let Some(el) = &replaced.1 {
+                        el.remove();
+                    }
+                    self.replaced
+                        .borrow_mut()
+                        .entry(id.clone())
+                        .or_default()
+                        .push(replaced);
+                }
+                head.append_child(&el).unwrap_throw();
+                self.els
+                    .borrow_mut()
+                    .insert(id.clone(), (builder_el, Some(el.clone())));
+
+                on_cleanup({
+                    let els = self.els.clone();
+                    let replaced = self.replaced.clone();
+                    move || {
+                        el.remove();
+                        let mut els = els.borrow_mut();
+                        let mut replaced = replaced.borrow_mut();
+                        let is_current = els
+                            .get(&id)
+                            .is_some_and(|(_, current)| current.as_ref() == Some(&el));
+                        if !is_current {
+                            if let Some(replaced) = replaced.get_mut(&id) {
+                                replaced
+                                    .retain(|(_, other)| other.as_ref() != Some(&el));
+                            }
+                            return;
+                        }
+                        match replaced.get_mut(&id).and_then(Vec::pop) {
+                            Some((builder_el, previous)) => {
+                                if let Some(previous) = &previous {
+                                    _ = head.append_child(previous);
+                                }
+                                els.insert(id, (builder_el, previous));
+                            }
+                            None => {
+                                els.swap_remove(&id);
+                            }
+                        }
+                    }
+                });
+            } else {
+                self.els.borrow_mut().insert(id, (builder_el, None));
+            }
+        }
+    }
+}
+
+impl MetaTagsContext {
+    // whether `el` is the element currently registered with `id`
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn is_registered(&self, id: &str, el: &web_sys::Element) -> bool {
+        self.els
+            .borrow()
+            .get(id)
+            .is_some_and(|(_, current)| current.as_ref() == Some(el))
+    }
+}
+
+// the attribute that marks the tags rendered on the server
+#[cfg(any(feature = "ssr", feature = "csr", feature = "hydrate"))]
+const SERVER_RENDERED_ATTR: &str = "data-leptos-meta";
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 struct MetaTagId(usize);
 
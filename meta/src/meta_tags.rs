@@ -1,9 +1,15 @@
 use crate::{use_head, TextProp};
-use leptos::{component, Attribute, IntoView};
+use leptos::{component, Attribute, IntoView, Oco};
 
 /// Injects an [`HTMLMetaElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMetaElement) into the document
 /// head to set metadata
 ///
+/// Each tag is keyed by its `key`, or else its `name`, `property`, `http-equiv`, or `charset`, and
+/// replaces any other tag with the same key that was rendered before it, in both the server's
+/// HTML and the live document head, until it is unmounted. This lets a page override the tags set
+/// near the root of the app. Tags that should be repeated, like several `og:image`s, can be given
+/// different `key`s.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -20,6 +26,16 @@ use leptos::{component, Attribute, IntoView};
 ///     </main>
 ///   }
 /// }
+///
+/// #[component]
+/// fn Gallery() -> impl IntoView {
+///   view! {
+///     // replaces the description of the app
+///     <Meta name="description" content="Photos of crabs."/>
+///     <Meta key="image-1" property="og:image" content="https://example.com/1.png"/>
+///     <Meta key="image-2" property="og:image" content="https://example.com/2.png"/>
+///   }
+/// }
 /// ```
 #[component(transparent)]
 pub fn Meta(
@@ -38,26 +54,49 @@ pub fn Meta(
     /// The [`content`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-content) attribute.
     #[prop(optional, into)]
     content: Option<TextProp>,
+    /// The key that the tag replaces others with, which defaults to its `name`, `property`,
+    /// `http-equiv`, or `charset`.
+    #[prop(optional, into)]
+    key: Option<Oco<'static, str>>,
     /// Custom attributes.
     #[prop(attrs, optional)]
     attrs: Vec<(&'static str, Attribute)>,
 ) -> impl IntoView {
     let meta = use_head();
-    let next_id = meta.tags.get_next_id();
-    let id = format!("leptos-link-{}", next_id.0);
+    let key = key.map(|key| key.to_string()).or_else(|| {
+        name.as_ref()
+            .or(property.as_ref())
+            .or(http_equiv.as_ref())
+            .map(|value| value.get().to_string())
+            .or_else(|| charset.as_ref().map(|_| "charset".to_string()))
+    });
+    let id = match &key {
+        Some(key) => format!("leptos-meta-{key}"),
+        None => format!("leptos-link-{}", meta.tags.get_next_id().0),
+    };
 
-    let builder_el = leptos::leptos_dom::html::as_meta_tag(move || {
-        attrs
-            .into_iter()
-            .fold(leptos::leptos_dom::html::meta(), |el, (name, value)| {
-                el.attr(name, value)
-            })
-            .attr("charset", move || charset.as_ref().map(|v| v.get()))
-            .attr("name", move || name.as_ref().map(|v| v.get()))
-            .attr("property", move || property.as_ref().map(|v| v.get()))
-            .attr("http-equiv", move || http_equiv.as_ref().map(|v| v.get()))
-            .attr("content", move || content.as_ref().map(|v| v.get()))
+    let builder_el = leptos::leptos_dom::html::as_meta_tag({
+        let id = key.as_ref().map(|_| id.clone());
+        move || {
+            attrs
+                .into_iter()
+                .fold(leptos::leptos_dom::html::meta(), |el, (name, value)| {
+                    el.attr(name, value)
+                })
+                .attr("id", id)
+                .attr("charset", move || charset.as_ref().map(|v| v.get()))
+                .attr("name", move || name.as_ref().map(|v| v.get()))
+                .attr("property", move || property.as_ref().map(|v| v.get()))
+                .attr("http-equiv", move || {
+                    http_equiv.as_ref().map(|v| v.get())
+                })
+                .attr("content", move || content.as_ref().map(|v| v.get()))
+        }
     });
 
-    meta.tags.register(id.into(), builder_el.into_any());
+    if key.is_some() {
+        meta.tags.register_unique(id.into(), builder_el.into_any());
+    } else {
+        meta.tags.register(id.into(), builder_el.into_any());
+    }
 }
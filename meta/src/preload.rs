@@ -0,0 +1,247 @@
+use crate::{Link, LinkProps};
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// Contains the resources that the page being rendered on the server has
+/// asked the browser to [preload](Preload) or [prefetch](Prefetch).
+///
+/// The server integrations send these as a `Link` header with the page, so
+/// that the browser can start loading them before it reads the HTML. Proxies
+/// and CDNs that support it can also send them as HTTP 103 Early Hints.
+#[derive(Clone, Default)]
+pub struct PreloadContext {
+    hints: Rc<RefCell<Vec<ResourceHint>>>,
+}
+
+impl core::fmt::Debug for PreloadContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PreloadContext")
+            .field(&self.hints.borrow())
+            .finish()
+    }
+}
+
+impl PreloadContext {
+    /// The resources that have been preloaded or prefetched, in the order in
+    /// which they were rendered.
+    pub fn hints(&self) -> Vec<ResourceHint> {
+        self.hints.borrow().clone()
+    }
+
+    /// The value of the `Link` header that lists the resources, or `None` if
+    /// there are none.
+    pub fn link_header(&self) -> Option<String> {
+        let hints = self.hints.borrow();
+        (!hints.is_empty()).then(|| {
+            hints
+                .iter()
+                .map(ResourceHint::to_link)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    #[cfg(feature = "ssr")]
+    fn add(&self, hint: ResourceHint) {
+        let mut hints = self.hints.borrow_mut();
+        if !hints
+            .iter()
+            .any(|other| other.rel == hint.rel && other.href == hint.href)
+        {
+            hints.push(hint);
+        }
+    }
+}
+
+/// A resource that the browser is asked to load before it is needed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceHint {
+    /// The `rel` of the link: `preload`, `modulepreload`, or `prefetch`.
+    pub rel: &'static str,
+    /// The URL of the resource.
+    pub href: String,
+    /// The kind of resource, like `font`, `image`, or `fetch`.
+    pub as_: Option<String>,
+    /// The MIME type of the resource.
+    pub type_: Option<String>,
+    /// The CORS mode the resource is requested with.
+    pub crossorigin: Option<String>,
+}
+
+impl ResourceHint {
+    /// Formats the hint as a link of a `Link` header, like
+    /// `</font.woff2>; rel=preload; as=font; crossorigin`.
+    pub fn to_link(&self) -> String {
+        let mut link = format!("<{}>; rel={}", self.href, self.rel);
+        if let Some(as_) = &self.as_ {
+            link.push_str(&format!("; as={as_}"));
+        }
+        if let Some(type_) = &self.type_ {
+            link.push_str(&format!("; type=\"{type_}\""));
+        }
+        match self.crossorigin.as_deref() {
+            Some("" | "anonymous") => link.push_str("; crossorigin"),
+            Some(crossorigin) => {
+                link.push_str(&format!("; crossorigin={crossorigin}"))
+            }
+            None => {}
+        }
+        link
+    }
+}
+
+/// Asks the browser to load a resource that the page needs soon, like a font
+/// or the image at the top of the page, by injecting a
+/// `<link rel="preload">` into the document head.
+///
+/// When rendered on the server, the resource is also sent in the `Link`
+/// header of the page (see [`PreloadContext`]). Fonts and `fetch` requests are
+/// made with `crossorigin="anonymous"` unless another `crossorigin` is given,
+/// as the browser would otherwise not reuse them. A JavaScript module, with
+/// `as_="module"`, is preloaded with `<link rel="modulepreload">`.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <Preload href="/fonts/inter.woff2" as_="font" type_="font/woff2"/>
+///       <Preload href="/hero.avif" as_="image" fetchpriority="high"/>
+///       <Prefetch href="/api/next-page"/>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn Preload(
+    /// The URL of the resource.
+    #[prop(into)]
+    href: Oco<'static, str>,
+    /// The kind of resource, like `font`, `image`, `script`, `style`,
+    /// `fetch`, or `module`.
+    #[prop(into)]
+    as_: Oco<'static, str>,
+    /// The MIME type of the resource, like `font/woff2`.
+    #[prop(optional, into)]
+    type_: Option<Oco<'static, str>>,
+    /// The CORS mode the resource is requested with.
+    #[prop(optional, into)]
+    crossorigin: Option<Oco<'static, str>>,
+    /// The media query for which the resource is needed.
+    #[prop(optional, into)]
+    media: Option<Oco<'static, str>>,
+    /// The priority of the request, like `high`.
+    #[prop(optional, into)]
+    fetchpriority: Option<Oco<'static, str>>,
+    /// The `srcset` of a responsive image.
+    #[prop(optional, into)]
+    imagesrcset: Option<Oco<'static, str>>,
+    /// The `sizes` of a responsive image.
+    #[prop(optional, into)]
+    imagesizes: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    let (rel, as_) = if as_ == "module" {
+        ("modulepreload", None)
+    } else {
+        ("preload", Some(as_))
+    };
+    let crossorigin = crossorigin.or_else(|| {
+        matches!(as_.as_deref(), Some("font" | "fetch"))
+            .then_some(Oco::Borrowed("anonymous"))
+    });
+    hint(
+        ResourceHint {
+            rel,
+            href: href.to_string(),
+            as_: as_.as_ref().map(ToString::to_string),
+            type_: type_.as_ref().map(ToString::to_string),
+            crossorigin: crossorigin.as_ref().map(ToString::to_string),
+        },
+        Link(LinkProps {
+            id: Some(format!("leptos-{rel}-{href}").into()),
+            rel: Some(rel.into()),
+            href: Some(href),
+            as_,
+            type_,
+            crossorigin,
+            media,
+            fetchpriority,
+            imagesrcset,
+            imagesizes,
+            ..link_props()
+        }),
+    )
+}
+
+/// Asks the browser to load a resource that a page the user is likely to
+/// visit next needs, while it is idle, by injecting a `<link rel="prefetch">`
+/// into the document head.
+///
+/// When rendered on the server, the resource is also sent in the `Link`
+/// header of the page (see [`PreloadContext`]). See [`Preload`] for an
+/// example.
+#[component(transparent)]
+pub fn Prefetch(
+    /// The URL of the resource.
+    #[prop(into)]
+    href: Oco<'static, str>,
+    /// The kind of resource, like `document`, `script`, or `fetch`.
+    #[prop(optional, into)]
+    as_: Option<Oco<'static, str>>,
+    /// The CORS mode the resource is requested with.
+    #[prop(optional, into)]
+    crossorigin: Option<Oco<'static, str>>,
+) -> impl IntoView {
+    hint(
+        ResourceHint {
+            rel: "prefetch",
+            href: href.to_string(),
+            as_: as_.as_ref().map(ToString::to_string),
+            type_: None,
+            crossorigin: crossorigin.as_ref().map(ToString::to_string),
+        },
+        Link(LinkProps {
+            id: Some(format!("leptos-prefetch-{href}").into()),
+            rel: Some("prefetch".into()),
+            href: Some(href),
+            as_,
+            crossorigin,
+            ..link_props()
+        }),
+    )
+}
+
+// records the hint for the `Link` header of the page being rendered
+#[cfg_attr(not(feature = "ssr"), allow(unused_variables))]
+fn hint(hint: ResourceHint, link: impl IntoView) -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    crate::use_head().preloads.add(hint);
+    link
+}
+
+fn link_props() -> LinkProps {
+    LinkProps {
+        id: None,
+        as_: None,
+        crossorigin: None,
+        disabled: None,
+        fetchpriority: None,
+        href: None,
+        hreflang: None,
+        imagesizes: None,
+        imagesrcset: None,
+        integrity: None,
+        media: None,
+        prefetch: None,
+        referrerpolicy: None,
+        rel: None,
+        sizes: None,
+        title: None,
+        type_: None,
+        blocking: None,
+        attrs: Vec::new(),
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{use_head, TextProp};
+use leptos::{component, IntoView};
+
+/// Injects the [Open Graph](https://ogp.me/) meta tags that describe the page when it is shared,
+/// like `<meta property="og:title" content="..."/>`, into the document head.
+///
+/// Each tag replaces the one with the same property set by another `<OpenGraph/>` or `<Meta/>`
+/// rendered earlier, so the app can set defaults near its root that each page then overrides,
+/// until it is unmounted.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <OpenGraph
+///         site_name="Leptos Online"
+///         kind="website"
+///         image="https://example.com/og.png"
+///       />
+///       // ... routing logic here
+///     }
+/// }
+///
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     view! {
+///       // keeps the site name and image of the app
+///       <OpenGraph kind="article" title="A post" description="About things."/>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn OpenGraph(
+    /// The `og:title` of the page.
+    #[prop(optional, into)]
+    title: Option<TextProp>,
+    /// The `og:description` of the page.
+    #[prop(optional, into)]
+    description: Option<TextProp>,
+    /// The URL of the `og:image` of the page.
+    #[prop(optional, into)]
+    image: Option<TextProp>,
+    /// The `og:image:alt` text describing the image.
+    #[prop(optional, into)]
+    image_alt: Option<TextProp>,
+    /// The `og:type` of the page, like `website` or `article`.
+    #[prop(optional, into)]
+    kind: Option<TextProp>,
+    /// The canonical `og:url` of the page.
+    #[prop(optional, into)]
+    url: Option<TextProp>,
+    /// The `og:site_name` of the site the page belongs to.
+    #[prop(optional, into)]
+    site_name: Option<TextProp>,
+    /// The `og:locale` of the page, like `en_US`.
+    #[prop(optional, into)]
+    locale: Option<TextProp>,
+) -> impl IntoView {
+    for (property, content) in [
+        ("og:title", title),
+        ("og:description", description),
+        ("og:image", image),
+        ("og:image:alt", image_alt),
+        ("og:type", kind),
+        ("og:url", url),
+        ("og:site_name", site_name),
+        ("og:locale", locale),
+    ] {
+        register_tag("property", property, content);
+    }
+}
+
+/// Injects the [Twitter card](https://developer.x.com/en/docs/twitter-for-websites/cards/overview/markup)
+/// meta tags that describe the page when it is shared, like
+/// `<meta name="twitter:card" content="summary_large_image"/>`, into the document head.
+///
+/// Each tag replaces the one with the same name set by another `<TwitterCard/>` or `<Meta/>`
+/// rendered earlier, so the app can set defaults near its root that each page then overrides,
+/// until it is unmounted.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Post() -> impl IntoView {
+///     view! {
+///       <TwitterCard
+///         card="summary_large_image"
+///         site="@leptos_rs"
+///         title="A post"
+///         image="https://example.com/post.png"
+///       />
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn TwitterCard(
+    /// The `twitter:card` type, like `summary` or `summary_large_image`.
+    #[prop(optional, into)]
+    card: Option<TextProp>,
+    /// The `twitter:title` of the page.
+    #[prop(optional, into)]
+    title: Option<TextProp>,
+    /// The `twitter:description` of the page.
+    #[prop(optional, into)]
+    description: Option<TextProp>,
+    /// The URL of the `twitter:image` of the page.
+    #[prop(optional, into)]
+    image: Option<TextProp>,
+    /// The `twitter:image:alt` text describing the image.
+    #[prop(optional, into)]
+    image_alt: Option<TextProp>,
+    /// The `twitter:site`, the username of the site, like `@leptos_rs`.
+    #[prop(optional, into)]
+    site: Option<TextProp>,
+    /// The `twitter:creator`, the username of the author of the page.
+    #[prop(optional, into)]
+    creator: Option<TextProp>,
+) -> impl IntoView {
+    for (name, content) in [
+        ("twitter:card", card),
+        ("twitter:title", title),
+        ("twitter:description", description),
+        ("twitter:image", image),
+        ("twitter:image:alt", image_alt),
+        ("twitter:site", site),
+        ("twitter:creator", creator),
+    ] {
+        register_tag("name", name, content);
+    }
+}
+
+// registers a `<meta>` tag with the given `name` or `property`, which replaces
+// any other with the same one
+fn register_tag(
+    attr: &'static str,
+    key: &'static str,
+    content: Option<TextProp>,
+) {
+    let Some(content) = content else {
+        return;
+    };
+    let id = format!("leptos-meta-{key}");
+    let builder_el = leptos::leptos_dom::html::as_meta_tag({
+        let id = id.clone();
+        move || {
+            leptos::leptos_dom::html::meta()
+                .attr("id", id)
+                .attr(attr, key)
+                .attr("content", move || content.get())
+        }
+    });
+    use_head()
+        .tags
+        .register_unique(id.into(), builder_el.into_any());
+}
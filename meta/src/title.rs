@@ -11,6 +11,8 @@ pub struct TitleContext {
     #[cfg(any(feature = "csr", feature = "hydrate"))]
     el: Rc<RefCell<Option<web_sys::HtmlTitleElement>>>,
     formatter: Rc<RefCell<Option<Formatter>>>,
+    template: Rc<RefCell<Option<TitleTemplate>>>,
+    text_template: Rc<RefCell<Option<TitleTemplate>>>,
     text: Rc<RefCell<Option<TextProp>>>,
 }
 
@@ -18,6 +20,17 @@ impl TitleContext {
     /// Converts the title into a string that can be used as the text content of a `<title>` tag.
     pub fn as_string(&self) -> Option<Oco<'static, str>> {
         let title = self.text.borrow().as_ref().map(TextProp::get);
+        let title = title.map(|title| {
+            match self
+                .text_template
+                .borrow()
+                .as_ref()
+                .or(self.template.borrow().as_ref())
+            {
+                Some(template) => template.format(&title).into(),
+                None => title,
+            }
+        });
         title.map(|title| {
             if let Some(formatter) = &*self.formatter.borrow() {
                 (formatter.0)(title.into_owned()).into()
@@ -48,6 +61,59 @@ where
     }
 }
 
+/// A template that the text of each `<Title/>` is formatted with, like
+/// `"%s — MyApp"`, in which `%s` is replaced by the text and `%%` is a literal
+/// `%`.
+///
+/// ```
+/// use leptos_meta::TitleTemplate;
+///
+/// let template = TitleTemplate::new("%s — MyApp");
+/// assert_eq!(template.format("Home"), "Home — MyApp");
+/// assert_eq!(TitleTemplate::new("100%% %s").format("Rust"), "100% Rust");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TitleTemplate(Oco<'static, str>);
+
+impl TitleTemplate {
+    /// Creates a template from a string in which `%s` is replaced by the text.
+    pub fn new(template: impl Into<Oco<'static, str>>) -> Self {
+        Self(template.into())
+    }
+
+    /// Formats the text with the template.
+    pub fn format(&self, text: &str) -> String {
+        let mut title = String::with_capacity(self.0.len() + text.len());
+        let mut chars = self.0.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('%', Some('s')) => {
+                    chars.next();
+                    title.push_str(text);
+                }
+                ('%', Some('%')) => {
+                    chars.next();
+                    title.push('%');
+                }
+                _ => title.push(c),
+            }
+        }
+        title
+    }
+}
+
+impl From<&'static str> for TitleTemplate {
+    fn from(template: &'static str) -> Self {
+        Self::new(template)
+    }
+}
+
+impl From<String> for TitleTemplate {
+    fn from(template: String) -> Self {
+        Self::new(template)
+    }
+}
+
 /// A component to set the document’s title by creating an [`HTMLTitleElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLTitleElement).
 ///
 /// The `title` and `formatter` can be set independently of one another. For example, you can create a root-level
@@ -88,11 +154,48 @@ where
 ///     }
 /// }
 /// ```
+///
+/// A [`TitleTemplate`] can be set near the root in the same way, so that each title is formatted
+/// consistently, and then overridden for the text of a specific page, like with `"%s"` to use the
+/// text as it is:
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <Title template="%s — Leptos Online"/>
+///       // ... routing logic here
+///     }
+/// }
+///
+/// #[component]
+/// fn PageA() -> impl IntoView {
+///     view! {
+///       <Title text="Page A"/> // sets title to "Page A — Leptos Online"
+///     }
+/// }
+///
+/// #[component]
+/// fn Home() -> impl IntoView {
+///     view! {
+///       <Title text="Leptos Online" template="%s"/> // sets title to "Leptos Online"
+///     }
+/// }
+/// ```
 #[component(transparent)]
 pub fn Title(
     /// A function that will be applied to any text value before it’s set as the title.
     #[prop(optional, into)]
     formatter: Option<Formatter>,
+    /// A template that the text is formatted with before the `formatter` is applied. Without
+    /// `text`, this sets the template for every title; with `text`, it only applies to it.
+    #[prop(optional, into)]
+    template: Option<TitleTemplate>,
     /// Sets the current `document.title`.
     #[prop(optional, into)]
     text: Option<TextProp>,
@@ -104,9 +207,7 @@ pub fn Title(
             if let Some(formatter) = formatter {
                 *meta.title.formatter.borrow_mut() = Some(formatter);
             }
-            if let Some(text) = text {
-                *meta.title.text.borrow_mut() = Some(text);
-            }
+            set_text(&meta.title, text, template);
 
             let el = {
                 let mut el_ref = meta.title.el.borrow_mut();
@@ -149,8 +250,26 @@ pub fn Title(
             if let Some(formatter) = formatter {
                 *meta.title.formatter.borrow_mut() = Some(formatter);
             }
-            if let Some(text) = text {
-                *meta.title.text.borrow_mut() = Some(text);
+            set_text(&meta.title, text, template);
+        }
+    }
+}
+
+// sets the text of the title, along with the template that only applies to it,
+// or else the template for every title
+fn set_text(
+    title: &TitleContext,
+    text: Option<TextProp>,
+    template: Option<TitleTemplate>,
+) {
+    match text {
+        Some(text) => {
+            *title.text.borrow_mut() = Some(text);
+            *title.text_template.borrow_mut() = template;
+        }
+        None => {
+            if let Some(template) = template {
+                *title.template.borrow_mut() = Some(template);
             }
         }
     }
@@ -0,0 +1,87 @@
+use crate::{ParamsMap, RouteMatch};
+use std::rc::Rc;
+
+/// The title of a route, shown in its [`Breadcrumb`].
+///
+/// This can be a fixed string, or a function of the params that were matched,
+/// so that `/users/:id` can be titled with the user's ID.
+#[derive(Clone)]
+pub struct RouteTitle(Rc<dyn Fn(&ParamsMap) -> String>);
+
+impl RouteTitle {
+    /// Returns the title for the given params.
+    pub fn get(&self, params: &ParamsMap) -> String {
+        (self.0)(params)
+    }
+}
+
+impl core::fmt::Debug for RouteTitle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("RouteTitle").finish()
+    }
+}
+
+impl From<&'static str> for RouteTitle {
+    fn from(title: &'static str) -> Self {
+        Self(Rc::new(move |_| title.to_string()))
+    }
+}
+
+impl From<String> for RouteTitle {
+    fn from(title: String) -> Self {
+        Self(Rc::new(move |_| title.clone()))
+    }
+}
+
+impl<F> From<F> for RouteTitle
+where
+    F: Fn(&ParamsMap) -> String + 'static,
+{
+    fn from(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+}
+
+/// One level of the currently-matched route tree, as returned by
+/// [`use_breadcrumbs`](crate::use_breadcrumbs).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Breadcrumb {
+    /// The part of the current URL matched by this route, like `/users/42`.
+    pub path: String,
+    /// The full path pattern of the route, like `/users/:id`.
+    pub pattern: String,
+    /// The title of the route, if it has one.
+    pub title: Option<String>,
+    /// The params matched by this route and its parents.
+    pub params: ParamsMap,
+}
+
+pub(crate) fn breadcrumbs(matches: &[RouteMatch]) -> Vec<Breadcrumb> {
+    let mut crumbs: Vec<Breadcrumb> = Vec::with_capacity(matches.len());
+    for m in matches {
+        let title = m
+            .route
+            .key
+            .title
+            .as_ref()
+            .map(|title| title.get(&m.path_match.params));
+        match crumbs.last_mut() {
+            // an index route shares its parent's path: keep one crumb, which
+            // takes the most deeply-nested title
+            Some(prev) if prev.path == m.path_match.path => {
+                prev.pattern.clone_from(&m.route.pattern);
+                prev.params.clone_from(&m.path_match.params);
+                if title.is_some() {
+                    prev.title = title;
+                }
+            }
+            _ => crumbs.push(Breadcrumb {
+                path: m.path_match.path.clone(),
+                pattern: m.route.pattern.clone(),
+                title,
+                params: m.path_match.params.clone(),
+            }),
+        }
+    }
+    crumbs
+}
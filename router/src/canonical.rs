@@ -0,0 +1,83 @@
+use crate::{escape, use_location, ParamsMap};
+use leptos::{
+    component, create_isomorphic_effect, view, IntoView, Oco, SignalWith,
+};
+use leptos_meta::Link;
+
+/// Injects a `<link rel="canonical">` into the document head with the URL of
+/// the current route, which is updated after each client-side navigation.
+///
+/// The URL is made of the `base_url` of the site, like
+/// `https://example.com`, usually read from the app's configuration, and the
+/// path of the current location. Query params are left out, except for those
+/// listed in `query_params`, which are kept in the order they are listed in,
+/// so that the same page reached with tracking params or in a different order
+/// has a single canonical URL.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_meta::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     provide_meta_context();
+///
+///     view! {
+///       <Router>
+///         <CanonicalUrl base_url="https://example.com" query_params=["page"]/>
+///         <Routes>
+///           <Route path="/posts" view=|| ()/>
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[component(transparent)]
+pub fn CanonicalUrl(
+    /// The public URL of the site, like `https://example.com`.
+    #[prop(into)]
+    base_url: Oco<'static, str>,
+    /// The query params that are part of the canonical URL, like `page`.
+    #[prop(optional, into)]
+    query_params: Vec<&'static str>,
+) -> impl IntoView {
+    let location = use_location();
+
+    // the link is registered within the effect, so it is removed again when
+    // it reruns after a navigation
+    create_isomorphic_effect(move |_| {
+        let href = location.pathname.with(|pathname| {
+            location.query.with(|query| {
+                canonical_url(&base_url, pathname, query, &query_params)
+            })
+        });
+        _ = view! { <Link id="leptos-canonical" rel="canonical" href/> };
+    });
+}
+
+/// Joins the `base_url` of the site, the `pathname`, and the params of `query`
+/// that are listed in `query_params` into a canonical URL.
+pub fn canonical_url(
+    base_url: &str,
+    pathname: &str,
+    query: &ParamsMap,
+    query_params: &[&str],
+) -> String {
+    let mut url = base_url.trim_end_matches('/').to_string();
+    if !pathname.starts_with('/') {
+        url.push('/');
+    }
+    url.push_str(pathname);
+    let mut separator = '?';
+    for key in query_params {
+        if let Some(value) = query.get(key) {
+            url.push(separator);
+            url.push_str(&escape(key));
+            url.push('=');
+            url.push_str(&escape(value));
+            separator = '&';
+        }
+    }
+    url
+}
@@ -20,6 +20,44 @@ use web_sys::{
 type OnFormData = Rc<dyn Fn(&web_sys::FormData)>;
 type OnResponse = Rc<dyn Fn(&web_sys::Response)>;
 type OnError = Rc<dyn Fn(&gloo_net::Error)>;
+type OnProgress = Rc<dyn Fn(UploadProgress)>;
+
+/// The progress of a `multipart/form-data` upload, passed to the `on_progress`
+/// callback of [`<Form/>`](Form).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UploadProgress {
+    /// The number of bytes uploaded so far.
+    pub loaded: f64,
+    /// The total number of bytes to upload, if it is known.
+    pub total: Option<f64>,
+}
+
+impl UploadProgress {
+    /// The fraction of the upload that has completed, between `0.0` and `1.0`,
+    /// if the total size is known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total
+            .filter(|total| *total > 0.0)
+            .map(|total| (self.loaded / total).min(1.0))
+    }
+}
+
+// the response to a POSTed form, and the URL it was redirected to, if any
+struct FormResponse {
+    resp: web_sys::Response,
+    redirected_to: Option<String>,
+}
+
+impl From<gloo_net::http::Response> for FormResponse {
+    fn from(resp: gloo_net::http::Response) -> Self {
+        let resp = web_sys::Response::from(resp);
+        let redirected_to = resp.redirected().then(|| resp.url());
+        Self {
+            resp,
+            redirected_to,
+        }
+    }
+}
 
 /// An HTML [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form) progressively
 /// enhanced to use client-side routing.
@@ -61,6 +99,10 @@ pub fn Form<A>(
     /// A callback will be called if the attempt to submit the form results in an error.
     #[prop(optional)]
     on_error: Option<OnError>,
+    /// A callback that will be called with the [`UploadProgress`] of the request body as a
+    /// `multipart/form-data` form, such as one containing file inputs, is submitted.
+    #[prop(optional)]
+    on_progress: Option<OnProgress>,
     /// A [`NodeRef`] in which the `<form>` element should be stored.
     #[prop(optional)]
     node_ref: Option<NodeRef<html::Form>>,
@@ -92,6 +134,95 @@ where
             .await
     }
 
+    // fetch() can't report upload progress, so use an XMLHttpRequest
+    async fn post_form_data_with_progress(
+        action: &str,
+        form_data: FormData,
+        on_progress: OnProgress,
+    ) -> Result<FormResponse, gloo_net::Error> {
+        use futures::channel::oneshot;
+        use std::cell::RefCell;
+        use wasm_bindgen::closure::Closure;
+        use web_sys::{ProgressEvent, ResponseInit, XmlHttpRequest};
+
+        fn js_error(e: JsValue) -> gloo_net::Error {
+            gloo_net::Error::GlooError(format!("{e:?}"))
+        }
+
+        let xhr = XmlHttpRequest::new().map_err(js_error)?;
+        xhr.open("POST", action).map_err(js_error)?;
+        xhr.set_request_header("Accept", "application/json")
+            .map_err(js_error)?;
+
+        let on_progress = Closure::<dyn FnMut(ProgressEvent)>::new(
+            move |ev: ProgressEvent| {
+                on_progress(UploadProgress {
+                    loaded: ev.loaded(),
+                    total: ev.length_computable().then(|| ev.total()),
+                })
+            },
+        );
+        xhr.upload()
+            .map_err(js_error)?
+            .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+
+        let (tx, rx) = oneshot::channel();
+        let tx = Rc::new(RefCell::new(Some(tx)));
+        let settle = |loaded: bool| {
+            let tx = Rc::clone(&tx);
+            Closure::<dyn FnMut()>::new(move || {
+                if let Some(tx) = tx.borrow_mut().take() {
+                    _ = tx.send(loaded);
+                }
+            })
+        };
+        let on_load = settle(true);
+        let on_fail = settle(false);
+        xhr.set_onload(Some(on_load.as_ref().unchecked_ref()));
+        xhr.set_onerror(Some(on_fail.as_ref().unchecked_ref()));
+        xhr.set_onabort(Some(on_fail.as_ref().unchecked_ref()));
+        xhr.send_with_opt_form_data(Some(&form_data))
+            .map_err(js_error)?;
+
+        if !rx.await.unwrap_or(false) {
+            return Err(gloo_net::Error::GlooError(
+                "network error while uploading form".into(),
+            ));
+        }
+
+        let status = xhr.status().map_err(js_error)?;
+        let init = ResponseInit::new();
+        init.set_status(status);
+        // these statuses can't have a body
+        let body = if matches!(status, 101 | 204 | 205 | 304) {
+            None
+        } else {
+            xhr.response_text().map_err(js_error)?
+        };
+        let resp = web_sys::Response::new_with_opt_str_and_init(
+            body.as_deref(),
+            &init,
+        )
+        .map_err(js_error)?;
+
+        // XMLHttpRequest follows redirects, so compare the final URL to the one requested
+        let requested = web_sys::Url::new_with_base(
+            action,
+            &window().location().href().unwrap_or_default(),
+        )
+        .map(|url| url.href())
+        .unwrap_or_else(|_| action.to_string());
+        let response_url = xhr.response_url();
+        let redirected_to = (!response_url.is_empty()
+            && response_url != requested)
+            .then_some(response_url);
+
+        Ok(FormResponse {
+            resp,
+            redirected_to,
+        })
+    }
+
     async fn post_params(
         action: &str,
         enctype: &str,
@@ -116,6 +247,7 @@ where
         on_form_data: Option<OnFormData>,
         on_response: Option<OnResponse>,
         on_error: Option<OnError>,
+        on_progress: Option<OnProgress>,
         class: Option<Attribute>,
         children: Children,
         node_ref: Option<NodeRef<html::Form>>,
@@ -156,89 +288,35 @@ where
                 } else {
                     action
                 };
-                // multipart POST (setting Context-Type breaks the request)
-                if method == "post" && enctype == "multipart/form-data" {
+                // POST
+                if method == "post" {
                     ev.prevent_default();
                     ev.stop_propagation();
 
                     let on_response = on_response.clone();
                     let on_error = on_error.clone();
+                    let on_progress = on_progress.clone();
                     spawn_local(async move {
-                        let res = post_form_data(&action, form_data).await;
-                        match res {
-                            Err(e) => {
-                                error!("<Form/> error while POSTing: {e:#?}");
-                                if let Some(on_error) = on_error {
-                                    on_error(&e);
-                                }
-                                if let Some(error) = error {
-                                    error.try_set(Some(Box::new(e)));
+                        // multipart POST (setting Context-Type breaks the request)
+                        let res = if enctype == "multipart/form-data" {
+                            match on_progress {
+                                Some(on_progress) => {
+                                    post_form_data_with_progress(
+                                        &action,
+                                        form_data,
+                                        on_progress,
+                                    )
+                                    .await
                                 }
+                                None => post_form_data(&action, form_data)
+                                    .await
+                                    .map(FormResponse::from),
                             }
-                            Ok(resp) => {
-                                let resp = web_sys::Response::from(resp);
-                                if let Some(version) = action_version {
-                                    version.update(|n| *n += 1);
-                                }
-                                if let Some(error) = error {
-                                    error.try_set(None);
-                                }
-                                if let Some(on_response) = on_response.clone() {
-                                    on_response(&resp);
-                                }
-                                // Check all the logical 3xx responses that might
-                                // get returned from a server function
-                                if resp.redirected() {
-                                    let resp_url = &resp.url();
-                                    match Url::try_from(resp_url.as_str()) {
-                                        Ok(url) => {
-                                            if url.origin
-                                                != current_window_origin()
-                                                || navigate.is_none()
-                                            {
-                                                _ = window()
-                                                    .location()
-                                                    .set_href(
-                                                        resp_url.as_str(),
-                                                    );
-                                            } else {
-                                                #[allow(
-                                                    clippy::unnecessary_unwrap
-                                                )]
-                                                let navigate =
-                                                    navigate.unwrap();
-                                                navigate(
-                                                    &format!(
-                                                        "{}{}{}",
-                                                        url.pathname,
-                                                        if url.search.is_empty()
-                                                        {
-                                                            ""
-                                                        } else {
-                                                            "?"
-                                                        },
-                                                        url.search,
-                                                    ),
-                                                    navigate_options,
-                                                )
-                                            }
-                                        }
-                                        Err(e) => warn!("{}", e),
-                                    }
-                                }
-                            }
-                        }
-                    });
-                }
-                // POST
-                else if method == "post" {
-                    ev.prevent_default();
-                    ev.stop_propagation();
-
-                    let on_response = on_response.clone();
-                    let on_error = on_error.clone();
-                    spawn_local(async move {
-                        let res = post_params(&action, &enctype, params).await;
+                        } else {
+                            post_params(&action, &enctype, params)
+                                .await
+                                .map(FormResponse::from)
+                        };
                         match res {
                             Err(e) => {
                                 error!("<Form/> error while POSTing: {e:#?}");
@@ -249,8 +327,10 @@ where
                                     error.try_set(Some(Box::new(e)));
                                 }
                             }
-                            Ok(resp) => {
-                                let resp = web_sys::Response::from(resp);
+                            Ok(FormResponse {
+                                resp,
+                                redirected_to,
+                            }) => {
                                 if let Some(version) = action_version {
                                     version.update(|n| *n += 1);
                                 }
@@ -262,8 +342,7 @@ where
                                 }
                                 // Check all the logical 3xx responses that might
                                 // get returned from a server function
-                                if resp.redirected() {
-                                    let resp_url = &resp.url();
+                                if let Some(resp_url) = &redirected_to {
                                     match Url::try_from(resp_url.as_str()) {
                                         Ok(url) => {
                                             if url.origin
@@ -359,6 +438,7 @@ where
         on_form_data,
         on_response,
         on_error,
+        on_progress,
         class,
         children,
         node_ref,
@@ -391,6 +471,10 @@ fn current_window_origin() -> String {
 /// default `Url` encoding. This is to ensure that `<ActionForm/>` works correctly
 /// both before and after WASM has loaded.
 ///
+/// ## Optimistic UI
+/// The input of a submission that is still in flight can be shown before the server
+/// responds with [`create_optimistic`](leptos_server::create_optimistic).
+///
 /// ## Complex Inputs
 /// Server function arguments that are structs with nested serializable fields
 /// should make use of indexing notation of `serde_qs`.
@@ -637,6 +721,149 @@ where
     action_form
 }
 
+/// Automatically turns a server [Action](leptos_server::Action) whose server function
+/// takes `multipart/form-data` into an HTML
+/// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
+/// progressively enhanced to use client-side routing.
+///
+/// This allows forms containing file inputs to be submitted as a server action: before
+/// WASM has loaded, the browser posts the form as `multipart/form-data` itself, and
+/// afterwards, the form's [`FormData`] is dispatched to the action. Either way, the server
+/// function receives the body as a stream of [`MultipartData`](server_fn::codec::MultipartData).
+///
+/// ```rust,ignore
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// use server_fn::codec::{MultipartData, MultipartFormData};
+///
+/// #[server(input = MultipartFormData)]
+/// async fn upload(data: MultipartData) -> Result<usize, ServerFnError> {
+///     let mut data = data.into_inner().unwrap();
+///     let mut size = 0;
+///     while let Ok(Some(mut field)) = data.next_field().await {
+///         while let Ok(Some(chunk)) = field.chunk().await {
+///             size += chunk.len();
+///         }
+///     }
+///     Ok(size)
+/// }
+///
+/// #[component]
+/// fn UploadForm() -> impl IntoView {
+///     let upload = Action::<Upload, _>::server();
+///     view! {
+///       <MultipartActionForm action=upload>
+///         <input type="file" name="file"/>
+///         <input type="submit"/>
+///       </MultipartActionForm>
+///     }
+/// }
+/// ```
+#[cfg(feature = "multipart")]
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+#[component]
+pub fn MultipartActionForm<ServFn>(
+    /// The action from which to build the form. This should include a URL, which can be generated
+    /// by default using [`create_server_action`](leptos_server::create_server_action) or added
+    /// manually using [`using_server_fn`](leptos_server::Action::using_server_fn).
+    action: Action<
+        ServFn,
+        Result<ServFn::Output, ServerFnError<ServFn::Error>>,
+    >,
+    /// Sets the `id` attribute on the underlying `<form>` tag
+    #[prop(optional, into)]
+    id: Option<AttributeValue>,
+    /// Sets the `class` attribute on the underlying `<form>` tag, making it easier to style.
+    #[prop(optional, into)]
+    class: Option<AttributeValue>,
+    /// A [`NodeRef`] in which the `<form>` element should be stored.
+    #[prop(optional)]
+    node_ref: Option<NodeRef<html::Form>>,
+    /// Arbitrary attributes to add to the `<form>`
+    #[prop(attrs, optional)]
+    attributes: Vec<(&'static str, Attribute)>,
+    /// Component children; should include the HTML of the form elements.
+    children: Children,
+) -> impl IntoView
+where
+    ServFn: ServerFn<InputEncoding = server_fn::codec::MultipartFormData>
+        + From<server_fn::codec::MultipartData>
+        + 'static,
+{
+    let has_router = has_router();
+    if !has_router {
+        _ = server_fn::redirect::set_redirect_hook(|loc: &str| {
+            if let Some(url) = resolve_redirect_url(loc) {
+                _ = window().location().set_href(&url.href());
+            }
+        });
+    }
+    let action_url = action.url().unwrap_or_else(|| {
+        debug_warn!(
+            "<MultipartActionForm/> action needs a URL. Either use \
+             create_server_action() or Action::using_server_fn()."
+        );
+        String::new()
+    });
+    let version = action.version();
+    let value = action.value();
+
+    let class = class.map(|bx| bx.into_attribute_boxed());
+    let id = id.map(|bx| bx.into_attribute_boxed());
+
+    let on_submit = move |ev: SubmitEvent| {
+        if ev.default_prevented() {
+            return;
+        }
+
+        // <button formmethod="dialog"> should *not* dispatch the action
+        let is_dialog = ev
+            .submitter()
+            .and_then(|el| el.get_attribute("formmethod"))
+            .as_deref()
+            == Some("dialog");
+        if is_dialog {
+            return;
+        }
+
+        ev.prevent_default();
+
+        match form_data_from_event(&ev) {
+            Ok(form_data) => {
+                action.dispatch(ServFn::from(form_data.into()));
+            }
+            Err(err) => {
+                error!("Error reading multipart form data: {err:?}");
+                batch(move || {
+                    value.set(Some(Err(ServerFnError::Serialization(
+                        err.to_string(),
+                    ))));
+                    version.update(|n| *n += 1);
+                });
+            }
+        }
+    };
+
+    let mut action_form = form()
+        .attr("action", action_url)
+        .attr("method", "post")
+        .attr("enctype", "multipart/form-data")
+        .attr("id", id)
+        .attr("class", class)
+        .on(ev::submit, on_submit)
+        .child(children());
+    if let Some(node_ref) = node_ref {
+        action_form = action_form.node_ref(node_ref)
+    };
+    for (attr_name, attr_value) in attributes {
+        action_form = action_form.attr(attr_name, attr_value);
+    }
+    action_form
+}
+
 fn form_data_from_event(
     ev: &SubmitEvent,
 ) -> Result<FormData, FromFormDataError> {
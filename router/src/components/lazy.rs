@@ -0,0 +1,248 @@
+use crate::{
+    components::route::define_route, Loader, Method, NamedViews, ParamsGuard,
+    RouteErrorView, RouteLoader, RouteMeta, RouteTitle, ScrollBehavior,
+    SsrMode, TrailingSlash,
+};
+use futures::{
+    future::{LocalBoxFuture, Shared},
+    FutureExt,
+};
+use leptos::*;
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+/// A route view that is only loaded the first time it is needed.
+///
+/// A `LazyView` wraps an async loader that resolves to the view function. The
+/// loader runs the first time the route is navigated to, or earlier, if the
+/// route is preloaded: an [`<A/>`](crate::A) preloads the routes it links to
+/// when it is hovered or focused, and [`RouterContext::preload`](crate::RouterContext::preload)
+/// can be used to preload a route manually. Once loaded, the view is cached,
+/// and later navigations render it immediately.
+///
+/// While the loader is pending, the navigation is considered to still be in
+/// progress, just as if the route were waiting on a resource: the `set_is_routing`
+/// signal of the [`Router`](crate::Router) stays `true`, and any `<Transition/>`
+/// or `<Suspense/>` above the route handles the pending state.
+///
+/// This is the hook for code splitting: the loader can fetch and instantiate a
+/// separately-compiled module before returning a view from it.
+#[derive(Clone)]
+pub struct LazyView {
+    inner: Rc<LazyViewInner>,
+}
+
+struct LazyViewInner {
+    load: Box<dyn Fn() -> LocalBoxFuture<'static, ViewFn>>,
+    loading: RefCell<Option<Shared<LocalBoxFuture<'static, ViewFn>>>>,
+}
+
+impl LazyView {
+    /// Creates a lazily-loaded view from an async loader.
+    pub fn new<F, Fut, V>(load: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = V> + 'static,
+        V: Into<ViewFn> + 'static,
+    {
+        Self {
+            inner: Rc::new(LazyViewInner {
+                load: Box::new(move || load().map(Into::into).boxed_local()),
+                loading: Default::default(),
+            }),
+        }
+    }
+
+    fn load(&self) -> Shared<LocalBoxFuture<'static, ViewFn>> {
+        self.inner
+            .loading
+            .borrow_mut()
+            .get_or_insert_with(|| (self.inner.load)().shared())
+            .clone()
+    }
+
+    /// Whether the view has finished loading.
+    pub fn is_loaded(&self) -> bool {
+        self.inner
+            .loading
+            .borrow()
+            .as_ref()
+            .and_then(|loading| loading.peek())
+            .is_some()
+    }
+
+    /// Starts loading the view, if it hasn't been loaded already.
+    pub fn preload(&self) {
+        if self.inner.loading.borrow().is_none() {
+            let load = self.load();
+            spawn_local(async move {
+                load.await;
+            });
+        }
+    }
+
+    pub(crate) fn view(&self) -> View {
+        let load = self.load();
+        if let Some(view) = load.peek() {
+            return view.run();
+        }
+
+        // reading the resource marks the route as pending until the view loads
+        let resource = create_resource(|| (), {
+            let load = load.clone();
+            move |_| {
+                let load = load.clone();
+                async move {
+                    load.await;
+                }
+            }
+        });
+        let loaded = create_trigger();
+        spawn_local({
+            let load = load.clone();
+            async move {
+                load.await;
+                loaded.notify();
+            }
+        });
+        (move || {
+            loaded.track();
+            resource.get().and_then(|_| load.peek().map(ViewFn::run))
+        })
+        .into_view()
+    }
+}
+
+impl core::fmt::Debug for LazyView {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LazyView")
+            .field("is_loaded", &self.is_loaded())
+            .finish()
+    }
+}
+
+/// Describes a route whose view is loaded lazily, on first navigation or when
+/// it is preloaded. Otherwise, this works the same way as
+/// [`<Route/>`](crate::Route). See [`LazyView`].
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn Admin() -> impl IntoView {
+///     "Admin panel"
+/// }
+///
+/// async fn load_admin() -> impl Fn() -> View {
+///     // e.g., fetch and instantiate a separately-compiled module here
+///     || view! { <Admin/> }.into_view()
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <A href="/admin">"Admin"</A>
+///         <Routes>
+///           <LazyRoute path="/admin" view=LazyView::new(load_admin)/>
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+#[component(transparent)]
+pub fn LazyRoute<P>(
+    /// The path fragment that this route should match. This can be static (`users`),
+    /// include a parameter (`:id`) or an optional parameter (`:id?`), or match a
+    /// wildcard (`user/*any`). A parameter can be constrained to values that parse as
+    /// a primitive type (`:id<u64>`), and a wildcard can be followed by more segments
+    /// (`files/*path/edit`).
+    path: P,
+    /// The lazily-loaded view that should be shown when this route is matched.
+    view: LazyView,
+    /// The mode that this route prefers during server-side rendering. Defaults to out-of-order streaming.
+    #[prop(optional)]
+    ssr: SsrMode,
+    /// The HTTP methods that this route can handle (defaults to only `GET`).
+    #[prop(default = &[Method::Get])]
+    methods: &'static [Method],
+    /// A data-loading function that will be called when the route is matched. Its results can be
+    /// accessed with [`use_route_data`](crate::use_route_data).
+    #[prop(optional, into)]
+    data: Option<Loader>,
+    /// Async data loaders that begin fetching as soon as the route is matched, on the server
+    /// during SSR and on the client during navigation. Their results can be accessed from
+    /// this route and any nested routes with [`use_loader`](crate::use_loader).
+    #[prop(optional)]
+    loaders: Vec<RouteLoader>,
+    /// Additional views that should be shown in the parent route's named
+    /// outlets (`<Outlet name="..."/>`) when this route is matched.
+    #[prop(optional)]
+    named_views: NamedViews,
+    /// The title of this route, which is included in its [`Breadcrumb`](crate::Breadcrumb).
+    /// This can be a string, or a function of the matched params.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
+    /// Document metadata applied through `leptos_meta` while this route is matched,
+    /// overriding that of its parent routes. See [`RouteMeta`](crate::RouteMeta).
+    #[prop(optional)]
+    meta: Option<RouteMeta>,
+    /// Checks the params of this route before its view is rendered, rendering a
+    /// "bad request" view instead if they are invalid. See [`ParamsGuard`](crate::ParamsGuard).
+    #[prop(optional)]
+    params: Option<ParamsGuard>,
+    /// Rendered in place of this route's view when an error is rendered within it, or within
+    /// a nested route without an error view of its own. See [`RouteErrorView`](crate::RouteErrorView).
+    #[prop(optional, into)]
+    error_view: Option<RouteErrorView>,
+    /// How the router should update the scroll position after navigating to this route.
+    /// Overrides the behavior set on the [`Router`](crate::Router).
+    #[prop(optional, into)]
+    scroll_behavior: Option<ScrollBehavior>,
+    /// How this route should handle trailing slashes in its path.
+    /// Overrides any setting applied to [`crate::components::Router`].
+    /// Serves as a default for any inner Routes.
+    #[prop(optional)]
+    trailing_slash: Option<TrailingSlash>,
+    /// Whether this route, and the routes nested in it, are only rendered on the server.
+    /// Their pages are not hydrated, so they are sent without the script that loads the
+    /// app's JS and WASM, and without the data of their resources. Any interactivity on
+    /// them is lost, but they are faster to load, which suits pages of static content.
+    #[prop(optional)]
+    server_only: bool,
+    /// `children` may be empty or include nested routes.
+    #[prop(optional)]
+    children: Option<Children>,
+) -> impl IntoView
+where
+    P: core::fmt::Display,
+{
+    let mut route = define_route(
+        children,
+        path.to_string(),
+        Rc::new({
+            let view = view.clone();
+            move || view.view()
+        }),
+        ssr,
+        methods,
+        data,
+        loaders,
+        named_views,
+        title,
+        meta,
+        params,
+        error_view,
+        scroll_behavior,
+        None,
+        None,
+        trailing_slash,
+        server_only,
+    );
+    route.lazy = Some(view);
+    route
+}
@@ -211,6 +211,15 @@ where
         // DRY here to avoid WASM binary size bloat
         #[cfg(not(feature = "ssr"))]
         {
+            // start loading any lazy routes this link points to
+            let router = crate::use_router();
+            let preload = move || {
+                href.with_untracked(|href| {
+                    if let Some(href) = href {
+                        router.preload(href);
+                    }
+                })
+            };
             let mut a = view! {
                 <a
                     href=move || href.get().unwrap_or_default()
@@ -220,6 +229,11 @@ where
                     aria-current=move || if is_active.get() { Some("page") } else { None }
                     class=class
                     id=id
+                    on:mouseenter={
+                        let preload = preload.clone();
+                        move |_| preload()
+                    }
+                    on:focus=move |_| preload()
                 >
                     {children()}
                 </a>
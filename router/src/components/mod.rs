@@ -1,19 +1,23 @@
 mod form;
+mod lazy;
 mod link;
 mod outlet;
 mod progress;
 mod redirect;
 mod route;
+mod route_error;
 mod router;
 mod routes;
 mod static_render;
 
 pub use form::*;
+pub use lazy::*;
 pub use link::*;
 pub use outlet::*;
 pub use progress::*;
 pub use redirect::*;
 pub use route::*;
+pub use route_error::*;
 pub use router::*;
 pub use routes::*;
 pub use static_render::*;
@@ -1,20 +1,31 @@
 use crate::{
     animation::{Animation, AnimationState},
-    use_is_back_navigation, use_location, use_route, RouteContext,
+    use_is_back_navigation, use_location, use_route, use_router, RouteContext,
     SetIsRouting,
 };
 use leptos::{leptos_dom::HydrationCtx, *};
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use web_sys::AnimationEvent;
 
 /// Displays the child route nested in a parent route, allowing you to control exactly where
 /// that child route is displayed. Renders nothing if there is no nested child.
+///
+/// A layout can contain several outlets: given a `name`, the outlet displays the view the
+/// matched child route provides for that name in its [`named_views`](crate::NamedViews),
+/// rather than its main view.
 #[cfg_attr(
     any(debug_assertions, feature = "ssr"),
     tracing::instrument(level = "trace", skip_all,)
 )]
 #[component]
-pub fn Outlet() -> impl IntoView {
+pub fn Outlet(
+    /// The name of this outlet. If `None`, the child route's main `view` is displayed.
+    #[prop(optional)]
+    name: Option<&'static str>,
+) -> impl IntoView {
     _ = HydrationCtx::next_outlet();
     let id = HydrationCtx::id();
     let route = use_route();
@@ -30,9 +41,12 @@ pub fn Outlet() -> impl IntoView {
 
     let is_showing = Rc::new(Cell::new(None::<usize>));
     let (outlet, set_outlet) = create_signal(None::<View>);
-    let build_outlet = as_child_of_current_owner(|child: RouteContext| {
+    let build_outlet = as_child_of_current_owner(move |child: RouteContext| {
         provide_context(child.clone());
-        child.outlet().into_view()
+        match name {
+            Some(name) => child.named_outlet(name).into_view(),
+            None => child.outlet().into_view(),
+        }
     });
     create_isomorphic_effect(move |prev_disposer| {
         child_id.track();
@@ -138,14 +152,21 @@ pub fn AnimatedOutlet(
     /// CSS class added after other animations have completed.
     #[prop(optional)]
     finally: Option<&'static str>,
+    /// The name of this outlet. If `None`, the child route's main `view` is displayed.
+    /// See [`Outlet`].
+    #[prop(optional)]
+    name: Option<&'static str>,
 ) -> impl IntoView {
     let pathname = use_location().pathname;
     let route = use_route();
     let is_showing = Rc::new(Cell::new(None::<usize>));
     let (outlet, set_outlet) = create_signal(None::<View>);
-    let build_outlet = as_child_of_current_owner(|child: RouteContext| {
+    let build_outlet = as_child_of_current_owner(move |child: RouteContext| {
         provide_context(child.clone());
-        child.outlet().into_view()
+        match name {
+            Some(name) => child.named_outlet(name).into_view(),
+            None => child.outlet().into_view(),
+        }
     });
 
     let animation = Animation {
@@ -256,6 +277,230 @@ pub fn AnimatedOutlet(
     }
 }
 
+/// Displays the child route nested in a parent route, animating between the old and new
+/// views whenever the child route changes.
+///
+/// Unlike [`AnimatedOutlet`], which plays its animations one after another, both views stay
+/// mounted while the transition runs: each view is wrapped in a `<div>`, the outgoing one
+/// with the `exit` class and the incoming one with the `enter` class, and the outgoing view
+/// is removed once its `animationend` event fires. These CSS classes must have associated
+/// animations. A view without a class for its part of the transition is added or removed
+/// immediately.
+///
+/// Until every transition has finished, the navigation is considered to still be underway,
+/// i.e., the `set_is_routing` signal passed to the [`Router`](crate::Router) stays `true`.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Layout() -> impl IntoView {
+///     view! {
+///       <nav>"..."</nav>
+///       // e.g., `.slide-in { animation: slide-in 200ms }`
+///       <TransitionOutlet class="pages" enter="slide-in" exit="slide-out"/>
+///     }
+/// }
+/// ```
+#[component]
+pub fn TransitionOutlet(
+    /// Classes to be applied to the `<div>` that contains the views.
+    #[prop(optional, into)]
+    class: Option<TextProp>,
+    /// CSS class added to the incoming view while it is being mounted.
+    #[prop(optional)]
+    enter: Option<&'static str>,
+    /// CSS class added to the outgoing view while it is being unmounted.
+    #[prop(optional)]
+    exit: Option<&'static str>,
+    /// CSS class added to the incoming view in a “back” navigation. Defaults to `enter`.
+    #[prop(optional)]
+    enter_back: Option<&'static str>,
+    /// CSS class added to the outgoing view in a “back” navigation. Defaults to `exit`.
+    #[prop(optional)]
+    exit_back: Option<&'static str>,
+    /// The name of this outlet. If `None`, the child route's main `view` is displayed.
+    /// See [`Outlet`].
+    #[prop(optional)]
+    name: Option<&'static str>,
+) -> impl IntoView {
+    let route = use_route();
+    let route_states = expect_context::<Memo<crate::RouterState>>();
+    let transitions = use_router().inner.transitions;
+    let is_back = use_is_back_navigation();
+
+    let child_id = create_memo({
+        let route = route.clone();
+        move |_| {
+            route_states.track();
+            route.child().map(|child| child.id())
+        }
+    });
+
+    let layers = create_rw_signal(Vec::<TransitionLayer>::new());
+    let disposers = Rc::new(RefCell::new(Vec::<(usize, Disposer)>::new()));
+    let next_key = Cell::new(0);
+    let build_outlet = as_child_of_current_owner(move |child: RouteContext| {
+        provide_context(child.clone());
+        match name {
+            Some(name) => child.named_outlet(name).into_view(),
+            None => child.outlet().into_view(),
+        }
+    });
+
+    // removes a layer once it has finished exiting
+    let remove = {
+        let disposers = Rc::clone(&disposers);
+        move |key: usize| {
+            layers.update(|layers| layers.retain(|layer| layer.key != key));
+            disposers.borrow_mut().retain(|(k, _)| *k != key);
+        }
+    };
+
+    create_isomorphic_effect({
+        let remove = remove.clone();
+        move |prev: Option<Option<usize>>| {
+            let id = child_id.get();
+            if prev.as_ref() == Some(&id) {
+                return id;
+            }
+            let back = is_back.get_untracked();
+            let (enter, exit) = if back {
+                (enter_back.or(enter), exit_back.or(exit))
+            } else {
+                (enter, exit)
+            };
+
+            // start exiting whatever is currently shown
+            let current = layers.with_untracked(|layers| {
+                layers
+                    .iter()
+                    .filter(|layer| {
+                        layer.phase.get_untracked() != TransitionPhase::Exit
+                    })
+                    .map(|layer| (layer.key, layer.phase, layer.class))
+                    .collect::<Vec<_>>()
+            });
+            for (key, phase, class) in current {
+                match exit {
+                    Some(exit) if prev.is_some() => {
+                        if phase.get_untracked() == TransitionPhase::Idle {
+                            transitions.update(|n| *n += 1);
+                        }
+                        phase.set(TransitionPhase::Exit);
+                        class.set(exit);
+                    }
+                    _ => {
+                        if phase.get_untracked() != TransitionPhase::Idle {
+                            transitions.update(|n| *n -= 1);
+                        }
+                        remove(key);
+                    }
+                }
+            }
+
+            if let Some(child) = route.child() {
+                let key = next_key.get();
+                next_key.set(key + 1);
+                let (view, disposer) = build_outlet(child);
+                disposers.borrow_mut().push((key, disposer));
+                // don't animate the initial render
+                let (phase, class) = match enter {
+                    Some(enter) if prev.is_some() => {
+                        transitions.update(|n| *n += 1);
+                        (TransitionPhase::Enter, enter)
+                    }
+                    _ => (TransitionPhase::Idle, ""),
+                };
+                layers.update(|layers| {
+                    layers.push(TransitionLayer {
+                        key,
+                        view,
+                        class: create_rw_signal(class),
+                        phase: create_rw_signal(phase),
+                    })
+                });
+            }
+            id
+        }
+    });
+
+    on_cleanup(move || {
+        let animating = layers.with_untracked(|layers| {
+            layers
+                .iter()
+                .filter(|layer| {
+                    layer.phase.try_get_untracked()
+                        != Some(TransitionPhase::Idle)
+                })
+                .count()
+        });
+        transitions.try_update(|n| *n = n.saturating_sub(animating));
+    });
+
+    let class = move || class.as_ref().map(|class| class.get());
+
+    view! {
+        <div class=class>
+            <For
+                each=move || layers.get()
+                key=|layer| layer.key
+                children=move |layer| {
+                    let TransitionLayer { key, view, class, phase } = layer;
+                    let node_ref = create_node_ref::<html::Div>();
+                    let remove = remove.clone();
+                    let animationend = move |ev: AnimationEvent| {
+                        use wasm_bindgen::JsCast;
+                        // ignore animations of the view's own contents
+                        let is_target = match (ev.target(), node_ref.get()) {
+                            (Some(target), Some(el)) => target
+                                .unchecked_ref::<web_sys::Node>()
+                                .is_same_node(Some(&el)),
+                            _ => true,
+                        };
+                        if !is_target {
+                            return;
+                        }
+                        ev.stop_propagation();
+                        match phase.get_untracked() {
+                            TransitionPhase::Idle => {}
+                            TransitionPhase::Enter => {
+                                transitions.update(|n| *n -= 1);
+                                phase.set(TransitionPhase::Idle);
+                                class.set("");
+                            }
+                            TransitionPhase::Exit => {
+                                transitions.update(|n| *n -= 1);
+                                remove(key);
+                            }
+                        }
+                    };
+                    view! {
+                        <div node_ref=node_ref class=move || class.get() on:animationend=animationend>
+                            {view}
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TransitionPhase {
+    Idle,
+    Enter,
+    Exit,
+}
+
+#[derive(Clone)]
+struct TransitionLayer {
+    key: usize,
+    view: View,
+    class: RwSignal<&'static str>,
+    phase: RwSignal<TransitionPhase>,
+}
+
 /*
 /// Displays the child route nested in a parent route, allowing you to control exactly where
 /// that child route is displayed. Renders nothing if there is no nested child.
@@ -8,7 +8,8 @@ use std::rc::Rc;
 /// side. If rendered on the server, this sets a `302` status code and sets a `Location`
 /// header. If rendered in the browser, it uses client-side navigation to redirect.
 /// In either case, it resolves the route relative to the current route. (To use
-/// an absolute path, prefix it with `/`).
+/// an absolute path, prefix it with `/`; it is resolved relative to the `base`
+/// of the [`Router`](crate::Router), if any.)
 ///
 /// **Note**: Support for server-side redirects is provided by the server framework
 /// integrations ([`leptos_actix`] and [`leptos_axum`]. If you’re not using one of those
@@ -46,7 +47,14 @@ where
         #[allow(unused)]
         let navigate = use_navigate();
         #[cfg(any(feature = "csr", feature = "hydrate"))]
-        navigate(&path, options.unwrap_or_default());
+        navigate(
+            &path,
+            NavigateOptions {
+                // the path has already been resolved above
+                resolve: false,
+                ..options.unwrap_or_default()
+            },
+        );
         #[cfg(not(any(feature = "csr", feature = "hydrate")))]
         {
             leptos::logging::debug_warn!(
@@ -1,7 +1,8 @@
 use crate::{
     matching::{resolve_path, PathMatch, RouteDefinition, RouteMatch},
-    ParamsMap, RouterContext, SsrMode, StaticData, StaticMode, StaticParamsMap,
-    TrailingSlash,
+    ParamsGuard, ParamsMap, RouteErrorView, RouteMeta, RouteTitle,
+    RouterContext, ScrollBehavior, SsrMode, StaticData, StaticMode,
+    StaticParamsMap, TrailingSlash,
 };
 use leptos::{leptos_dom::Transparent, *};
 use std::{
@@ -29,7 +30,17 @@ pub(in crate::components) fn new_route_id() -> usize {
 }
 
 /// Represents an HTTP method that can be handled by this route.
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum Method {
     /// The [`GET`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/GET) method
     /// requests a representation of the specified resource.
@@ -60,7 +71,9 @@ pub enum Method {
 pub fn Route<E, F, P>(
     /// The path fragment that this route should match. This can be static (`users`),
     /// include a parameter (`:id`) or an optional parameter (`:id?`), or match a
-    /// wildcard (`user/*any`).
+    /// wildcard (`user/*any`). A parameter can be constrained to values that parse as
+    /// a primitive type (`:id<u64>`), and a wildcard can be followed by more segments
+    /// (`files/*path/edit`).
     path: P,
     /// The view that should be shown when this route is matched. This can be any function
     /// that returns a type that implements [`IntoView`] (like `|| view! { <p>"Show this"</p> })`
@@ -76,11 +89,46 @@ pub fn Route<E, F, P>(
     /// accessed with [`use_route_data`](crate::use_route_data).
     #[prop(optional, into)]
     data: Option<Loader>,
+    /// Async data loaders that begin fetching as soon as the route is matched, on the server
+    /// during SSR and on the client during navigation. Their results can be accessed from
+    /// this route and any nested routes with [`use_loader`](crate::use_loader).
+    #[prop(optional)]
+    loaders: Vec<RouteLoader>,
+    /// Additional views that should be shown in the parent route's named
+    /// outlets (`<Outlet name="..."/>`) when this route is matched.
+    #[prop(optional)]
+    named_views: NamedViews,
+    /// The title of this route, which is included in its [`Breadcrumb`](crate::Breadcrumb).
+    /// This can be a string, or a function of the matched params.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
+    /// Document metadata applied through `leptos_meta` while this route is matched,
+    /// overriding that of its parent routes. See [`RouteMeta`](crate::RouteMeta).
+    #[prop(optional)]
+    meta: Option<RouteMeta>,
+    /// Checks the params of this route before its view is rendered, rendering a
+    /// "bad request" view instead if they are invalid. See [`ParamsGuard`](crate::ParamsGuard).
+    #[prop(optional)]
+    params: Option<ParamsGuard>,
+    /// Rendered in place of this route's view when an error is rendered within it, or within
+    /// a nested route without an error view of its own. See [`RouteErrorView`](crate::RouteErrorView).
+    #[prop(optional, into)]
+    error_view: Option<RouteErrorView>,
+    /// How the router should update the scroll position after navigating to this route.
+    /// Overrides the behavior set on the [`Router`](crate::Router).
+    #[prop(optional, into)]
+    scroll_behavior: Option<ScrollBehavior>,
     /// How this route should handle trailing slashes in its path.
     /// Overrides any setting applied to [`crate::components::Router`].
     /// Serves as a default for any inner Routes.
     #[prop(optional)]
     trailing_slash: Option<TrailingSlash>,
+    /// Whether this route, and the routes nested in it, are only rendered on the server.
+    /// Their pages are not hydrated, so they are sent without the script that loads the
+    /// app's JS and WASM, and without the data of their resources. Any interactivity on
+    /// them is lost, but they are faster to load, which suits pages of static content.
+    #[prop(optional)]
+    server_only: bool,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -97,9 +145,17 @@ where
         ssr,
         methods,
         data,
+        loaders,
+        named_views,
+        title,
+        meta,
+        params,
+        error_view,
+        scroll_behavior,
         None,
         None,
         trailing_slash,
+        server_only,
     )
 }
 
@@ -146,7 +202,9 @@ where
 pub fn ProtectedRoute<P, E, F, C>(
     /// The path fragment that this route should match. This can be static (`users`),
     /// include a parameter (`:id`) or an optional parameter (`:id?`), or match a
-    /// wildcard (`user/*any`).
+    /// wildcard (`user/*any`). A parameter can be constrained to values that parse as
+    /// a primitive type (`:id<u64>`), and a wildcard can be followed by more segments
+    /// (`files/*path/edit`).
     path: P,
     /// The path that will be redirected to if the condition is `false`.
     redirect_path: P,
@@ -164,11 +222,46 @@ pub fn ProtectedRoute<P, E, F, C>(
     /// accessed with [`use_route_data`](crate::use_route_data).
     #[prop(optional, into)]
     data: Option<Loader>,
+    /// Async data loaders that begin fetching as soon as the route is matched, on the server
+    /// during SSR and on the client during navigation. Their results can be accessed from
+    /// this route and any nested routes with [`use_loader`](crate::use_loader).
+    #[prop(optional)]
+    loaders: Vec<RouteLoader>,
+    /// Additional views that should be shown in the parent route's named
+    /// outlets (`<Outlet name="..."/>`) when this route is matched.
+    #[prop(optional)]
+    named_views: NamedViews,
+    /// The title of this route, which is included in its [`Breadcrumb`](crate::Breadcrumb).
+    /// This can be a string, or a function of the matched params.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
+    /// Document metadata applied through `leptos_meta` while this route is matched,
+    /// overriding that of its parent routes. See [`RouteMeta`](crate::RouteMeta).
+    #[prop(optional)]
+    meta: Option<RouteMeta>,
+    /// Checks the params of this route before its view is rendered, rendering a
+    /// "bad request" view instead if they are invalid. See [`ParamsGuard`](crate::ParamsGuard).
+    #[prop(optional)]
+    params: Option<ParamsGuard>,
+    /// Rendered in place of this route's view when an error is rendered within it, or within
+    /// a nested route without an error view of its own. See [`RouteErrorView`](crate::RouteErrorView).
+    #[prop(optional, into)]
+    error_view: Option<RouteErrorView>,
+    /// How the router should update the scroll position after navigating to this route.
+    /// Overrides the behavior set on the [`Router`](crate::Router).
+    #[prop(optional, into)]
+    scroll_behavior: Option<ScrollBehavior>,
     /// How this route should handle trailing slashes in its path.
     /// Overrides any setting applied to [`crate::components::Router`].
     /// Serves as a default for any inner Routes.
     #[prop(optional)]
     trailing_slash: Option<TrailingSlash>,
+    /// Whether this route, and the routes nested in it, are only rendered on the server.
+    /// Their pages are not hydrated, so they are sent without the script that loads the
+    /// app's JS and WASM, and without the data of their resources. Any interactivity on
+    /// them is lost, but they are faster to load, which suits pages of static content.
+    #[prop(optional)]
+    server_only: bool,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -195,9 +288,17 @@ where
         ssr,
         methods,
         data,
+        loaders,
+        named_views,
+        title,
+        meta,
+        params,
+        error_view,
+        scroll_behavior,
         None,
         None,
         trailing_slash,
+        server_only,
     )
 }
 
@@ -211,13 +312,17 @@ where
 pub fn StaticRoute<E, F, P, S>(
     /// The path fragment that this route should match. This can be static (`users`),
     /// include a parameter (`:id`) or an optional parameter (`:id?`), or match a
-    /// wildcard (`user/*any`).
+    /// wildcard (`user/*any`). A parameter can be constrained to values that parse as
+    /// a primitive type (`:id<u64>`), and a wildcard can be followed by more segments
+    /// (`files/*path/edit`).
     path: P,
     /// The view that should be shown when this route is matched. This can be any function
     /// that returns a type that implements [IntoView] (like `|| view! { <p>"Show this"</p> })`
     /// or `|| view! { <MyComponent/>` } or even, for a component with no props, `MyComponent`).
     view: F,
-    /// Creates a map of the params that should be built for a particular route.
+    /// Creates a map of the params that should be built for a particular route, either
+    /// as a list of values for each param or as the exact combinations of values to
+    /// build. See [`StaticParamsMap`].
     static_params: S,
     /// The static route mode
     #[prop(optional)]
@@ -226,11 +331,46 @@ pub fn StaticRoute<E, F, P, S>(
     /// accessed with [`use_route_data`](crate::use_route_data).
     #[prop(optional, into)]
     data: Option<Loader>,
+    /// Async data loaders that begin fetching as soon as the route is matched, on the server
+    /// during SSR and on the client during navigation. Their results can be accessed from
+    /// this route and any nested routes with [`use_loader`](crate::use_loader).
+    #[prop(optional)]
+    loaders: Vec<RouteLoader>,
+    /// Additional views that should be shown in the parent route's named
+    /// outlets (`<Outlet name="..."/>`) when this route is matched.
+    #[prop(optional)]
+    named_views: NamedViews,
+    /// The title of this route, which is included in its [`Breadcrumb`](crate::Breadcrumb).
+    /// This can be a string, or a function of the matched params.
+    #[prop(optional, into)]
+    title: Option<RouteTitle>,
+    /// Document metadata applied through `leptos_meta` while this route is matched,
+    /// overriding that of its parent routes. See [`RouteMeta`](crate::RouteMeta).
+    #[prop(optional)]
+    meta: Option<RouteMeta>,
+    /// Checks the params of this route before its view is rendered, rendering a
+    /// "bad request" view instead if they are invalid. See [`ParamsGuard`](crate::ParamsGuard).
+    #[prop(optional)]
+    params: Option<ParamsGuard>,
+    /// Rendered in place of this route's view when an error is rendered within it, or within
+    /// a nested route without an error view of its own. See [`RouteErrorView`](crate::RouteErrorView).
+    #[prop(optional, into)]
+    error_view: Option<RouteErrorView>,
+    /// How the router should update the scroll position after navigating to this route.
+    /// Overrides the behavior set on the [`Router`](crate::Router).
+    #[prop(optional, into)]
+    scroll_behavior: Option<ScrollBehavior>,
     /// How this route should handle trailing slashes in its path.
     /// Overrides any setting applied to [`crate::components::Router`].
     /// Serves as a default for any inner Routes.
     #[prop(optional)]
     trailing_slash: Option<TrailingSlash>,
+    /// Whether this route, and the routes nested in it, are only rendered on the server.
+    /// Their pages are not hydrated, so they are sent without the script that loads the
+    /// app's JS and WASM, and without the data of their resources. Any interactivity on
+    /// them is lost, but they are faster to load, which suits pages of static content.
+    #[prop(optional)]
+    server_only: bool,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Children>,
@@ -251,9 +391,17 @@ where
         SsrMode::default(),
         &[Method::Get],
         data,
+        loaders,
+        named_views,
+        title,
+        meta,
+        params,
+        error_view,
+        scroll_behavior,
         Some(mode),
         Some(Arc::new(static_params)),
         trailing_slash,
+        server_only,
     )
 }
 
@@ -269,9 +417,17 @@ pub(crate) fn define_route(
     ssr_mode: SsrMode,
     methods: &'static [Method],
     data: Option<Loader>,
+    loaders: Vec<RouteLoader>,
+    named_views: NamedViews,
+    title: Option<RouteTitle>,
+    meta: Option<RouteMeta>,
+    params: Option<ParamsGuard>,
+    error_view: Option<RouteErrorView>,
+    scroll_behavior: Option<ScrollBehavior>,
     static_mode: Option<StaticMode>,
     static_params: Option<StaticData>,
     trailing_slash: Option<TrailingSlash>,
+    server_only: bool,
 ) -> RouteDefinition {
     let children = children
         .map(|children| {
@@ -296,9 +452,18 @@ pub(crate) fn define_route(
         ssr_mode,
         methods,
         data,
+        loaders,
+        named_views,
+        title,
+        meta,
+        params,
+        error_view,
+        scroll_behavior,
+        lazy: None,
         static_mode,
         static_params,
         trailing_slash,
+        server_only,
     }
 }
 
@@ -332,13 +497,34 @@ impl RouteContext {
             view: element,
             id,
             data,
+            loaders,
+            named_views,
+            params: params_guard,
+            error_view,
+            server_only,
             ..
         } = route.key;
+        // the page is not hydrated if any of its matched routes is server-only
+        #[cfg(feature = "ssr")]
+        if server_only {
+            SharedContext::set_server_only();
+        }
+        #[cfg(not(feature = "ssr"))]
+        let _ = server_only;
         let params = create_memo(move |_| {
             matcher()
                 .map(|matched| matched.path_match.params)
                 .unwrap_or_default()
         });
+        let provided_loaders = loaders
+            .iter()
+            .map(|loader| (loader.load)(params))
+            .collect::<Rc<[_]>>();
+        let provide_loaders = move || {
+            for provide_loader in provided_loaders.iter() {
+                provide_loader();
+            }
+        };
 
         let inner = Rc::new(RouteContextInner {
             id,
@@ -347,7 +533,39 @@ impl RouteContext {
             path: create_rw_signal(path),
             original_path: route.original_path.to_string(),
             params,
-            outlet: Box::new(move || Some(element())),
+            outlet: Box::new({
+                let provide_loaders = provide_loaders.clone();
+                move || {
+                    provide_loaders();
+                    let element = Rc::clone(&element);
+                    let guard = params_guard.clone();
+                    let render = move || {
+                        let Some(guard) = guard else {
+                            return element();
+                        };
+                        let invalid = create_memo({
+                            let guard = guard.clone();
+                            move |_| {
+                                params.with(|params| guard.check(params).err())
+                            }
+                        });
+                        (move || match invalid.get() {
+                            Some(error) => guard.render_error(error),
+                            None => element(),
+                        })
+                        .into_view()
+                    };
+                    Some(match &error_view {
+                        Some(error_view) => error_view.catch(render),
+                        None => render(),
+                    })
+                }
+            }),
+            named_outlet: Box::new(move |name| {
+                let view = named_views.get(name)?;
+                provide_loaders();
+                Some(view())
+            }),
             data: RefCell::new(None),
         });
         if let Some(loader) = data {
@@ -413,6 +631,7 @@ impl RouteContext {
                 original_path: path.to_string(),
                 params: create_memo(|_| ParamsMap::new()),
                 outlet: Box::new(move || fallback.as_ref().map(move |f| f())),
+                named_outlet: Box::new(|_| None),
                 data: Default::default(),
             }),
         }
@@ -443,6 +662,12 @@ impl RouteContext {
         (self.inner.outlet)()
     }
 
+    /// The view the current route provides for the parent's named outlet
+    /// `name`, if any. See [`NamedViews`].
+    pub fn named_outlet(&self, name: &str) -> impl IntoView {
+        (self.inner.named_outlet)(name)
+    }
+
     /// The http method used to navigate to this route. Defaults to [`Method::Get`] when unavailable like in client side routing
     pub fn method(&self) -> Method {
         use_context().unwrap_or_default()
@@ -457,9 +682,12 @@ pub(crate) struct RouteContextInner {
     pub(crate) original_path: String,
     pub(crate) params: Memo<ParamsMap>,
     pub(crate) outlet: Box<dyn Fn() -> Option<View>>,
+    pub(crate) named_outlet: Box<NamedOutletFn>,
     pub(crate) data: RefCell<Option<Rc<dyn Any>>>,
 }
 
+type NamedOutletFn = dyn Fn(&str) -> Option<View>;
+
 impl PartialEq for RouteContextInner {
     fn eq(&self, other: &Self) -> bool {
         self.base_path == other.base_path
@@ -494,3 +722,229 @@ where
         }
     }
 }
+
+/// An async data loader for a [`<Route/>`](crate::Route).
+///
+/// Each loader starts loading as soon as its route is matched, with the
+/// current route params as its input, and reloads whenever those params
+/// change. Because loading begins before the route's view is rendered, and
+/// outside of any component, data for nested routes are loaded in parallel
+/// rather than in a waterfall.
+///
+/// During server-side rendering, a [`RouteLoader::new`] loader is streamed:
+/// its data are sent down to the client whenever they are ready, while the
+/// rest of the page renders in the meantime. A [`RouteLoader::blocking`]
+/// loader instead holds back the response until its data are ready, which is
+/// useful for the critical data a page (or its `<head>`) cannot do without.
+///
+/// A route can have any number of loaders, each of which is accessed by the
+/// type of data it returns, using [`use_loader`](crate::use_loader).
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct User(String);
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct Comments(Vec<String>);
+///
+/// async fn load_user(params: ParamsMap) -> User {
+///     User(params.get("id").cloned().unwrap_or_default())
+/// }
+///
+/// async fn load_comments(_params: ParamsMap) -> Comments {
+///     Comments(vec![])
+/// }
+///
+/// #[component]
+/// fn UserPage() -> impl IntoView {
+///     let user = use_loader::<User>().expect("user loader");
+///     let comments = use_loader::<Comments>().expect("comments loader");
+///     view! {
+///       <h1>{move || user.get().map(|user| user.0)}</h1>
+///       <Suspense fallback=|| "Loading comments...">
+///         {move || comments.get().map(|c| c.0.len())}
+///       </Suspense>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <Routes>
+///           <Route
+///             path="/users/:id"
+///             view=UserPage
+///             loaders=vec![
+///                 RouteLoader::blocking(load_user),
+///                 RouteLoader::new(load_comments),
+///             ]
+///           />
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RouteLoader {
+    pub(crate) load: Rc<LoadFn>,
+}
+
+// creates the loader's resource, returning a function that provides it
+// as context
+type LoadFn = dyn Fn(Memo<ParamsMap>) -> Rc<dyn Fn()>;
+
+impl core::fmt::Debug for RouteLoader {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RouteLoader").finish()
+    }
+}
+
+impl RouteLoader {
+    /// Creates a loader whose data are streamed to the client during
+    /// server-side rendering, once they have loaded.
+    pub fn new<T, Fu>(loader: impl Fn(ParamsMap) -> Fu + 'static) -> Self
+    where
+        T: Serializable + Clone + 'static,
+        Fu: Future<Output = T> + 'static,
+    {
+        let loader = Rc::new(loader);
+        Self::with_resource(move |params| {
+            let loader = Rc::clone(&loader);
+            create_resource(move || params.get(), move |params| loader(params))
+        })
+    }
+
+    /// Creates a loader that blocks the server-side rendered response until
+    /// its data have loaded.
+    pub fn blocking<T, Fu>(loader: impl Fn(ParamsMap) -> Fu + 'static) -> Self
+    where
+        T: Serializable + Clone + 'static,
+        Fu: Future<Output = T> + 'static,
+    {
+        let loader = Rc::new(loader);
+        Self::with_resource(move |params| {
+            let loader = Rc::clone(&loader);
+            create_blocking_resource(
+                move || params.get(),
+                move |params| loader(params),
+            )
+        })
+    }
+
+    fn with_resource<T>(
+        create: impl Fn(Memo<ParamsMap>) -> Resource<ParamsMap, T> + 'static,
+    ) -> Self
+    where
+        T: Clone + 'static,
+    {
+        let loader = Rc::new(create);
+        Self {
+            load: Rc::new(move |params| {
+                let resource = loader(params);
+                Rc::new(move || provide_context(LoaderData(resource)))
+            }),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct LoaderData<T: 'static>(pub Resource<ParamsMap, T>);
+
+/// Views that a [`<Route/>`](crate::Route) provides for the named outlets of
+/// its parent route.
+///
+/// A layout can declare any number of named outlets alongside its default
+/// [`<Outlet/>`](crate::Outlet), e.g., `<Outlet name="sidebar"/>`. When one
+/// of its child routes is matched, each named outlet shows the view that
+/// child provides under the same name, or nothing if it doesn't provide one.
+/// This allows several regions of a layout to be driven by the same URL, as
+/// in a master-detail view.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn Layout() -> impl IntoView {
+///     view! {
+///       <nav><Outlet name="toolbar"/></nav>
+///       <aside><Outlet name="sidebar"/></aside>
+///       <main><Outlet/></main>
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <Routes>
+///           <Route path="/contacts" view=Layout>
+///             <Route
+///               path=":id"
+///               view=|| "Contact details"
+///               named_views=NamedViews::new()
+///                 .view("sidebar", || "Contact list")
+///                 .view("toolbar", || "Edit | Delete")
+///             />
+///             <Route
+///               path=""
+///               view=|| "Select a contact"
+///               named_views=NamedViews::new()
+///                 .view("sidebar", || "Contact list")
+///             />
+///           </Route>
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NamedViews(Vec<(&'static str, ViewFactory)>);
+
+type ViewFactory = Rc<dyn Fn() -> View>;
+
+impl NamedViews {
+    /// Creates an empty set of named views.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a view for the outlet called `name`, replacing any view already
+    /// provided for it.
+    pub fn view<E, F>(mut self, name: &'static str, view: F) -> Self
+    where
+        E: IntoView,
+        F: Fn() -> E + 'static,
+    {
+        let view: ViewFactory = Rc::new(move || view().into_view());
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, existing)) => *existing = view,
+            None => self.0.push((name, view)),
+        }
+        self
+    }
+
+    /// Returns the view provided for the outlet called `name`, if any.
+    pub fn get(&self, name: &str) -> Option<Rc<dyn Fn() -> View>> {
+        self.0
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, view)| Rc::clone(view))
+    }
+
+    /// Whether no named views have been provided.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl core::fmt::Debug for NamedViews {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
@@ -0,0 +1,134 @@
+use leptos::*;
+use std::rc::Rc;
+
+/// An error page for a [`<Route/>`](crate::Route), rendered in place of the
+/// route's view whenever an error is rendered anywhere within it: by the
+/// route's own view, by the data of its loaders, or by a nested route that
+/// does not have an error view of its own. Errors in routes without an error
+/// view fall through to the nearest parent route that has one.
+///
+/// During server-side rendering, rendering the error view also sets the
+/// status code of the response, which defaults to `500`.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// fn missing_user() -> Result<(), ServerFnError> {
+///     Err(ServerFnError::new("no such user"))
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <Routes>
+///           <Route
+///             path="/"
+///             view=|| view! { <Outlet/> }
+///             error_view={|errors: RwSignal<Errors>| view! {
+///               <h1>"Something went wrong"</h1>
+///               <p>{move || errors.get().into_iter().count()} " errors"</p>
+///             }}
+///           >
+///             // errors here are rendered by the parent's error view
+///             <Route path="" view=missing_user/>
+///             <Route
+///               path="users/:id"
+///               view=missing_user
+///               error_view=RouteErrorView::new(|_| "User not found").status(404)
+///             />
+///           </Route>
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RouteErrorView {
+    view: Rc<dyn Fn(RwSignal<Errors>) -> View>,
+    status: u16,
+}
+
+impl core::fmt::Debug for RouteErrorView {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RouteErrorView")
+            .field("status", &self.status)
+            .finish()
+    }
+}
+
+impl<F, IV> From<F> for RouteErrorView
+where
+    F: Fn(RwSignal<Errors>) -> IV + 'static,
+    IV: IntoView,
+{
+    fn from(view: F) -> Self {
+        Self::new(view)
+    }
+}
+
+impl RouteErrorView {
+    /// Creates an error view from a function of the errors that were caught.
+    pub fn new<IV: IntoView>(
+        view: impl Fn(RwSignal<Errors>) -> IV + 'static,
+    ) -> Self {
+        Self {
+            view: Rc::new(move |errors| view(errors).into_view()),
+            status: 500,
+        }
+    }
+
+    /// Sets the status code of the server-side rendered response when this
+    /// error view is rendered.
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Renders `children`, catching any errors they render with this view.
+    pub(crate) fn catch(
+        &self,
+        children: impl FnOnce() -> View + 'static,
+    ) -> View {
+        let this = self.clone();
+        view! {
+            <ErrorBoundary fallback=move |errors| this.render(errors)>
+                {children()}
+            </ErrorBoundary>
+        }
+    }
+
+    fn render(&self, errors: RwSignal<Errors>) -> View {
+        if let Some(set_status) = use_context::<ServerStatusFunction>() {
+            (set_status.f)(self.status);
+        }
+        (self.view)(errors)
+    }
+}
+
+/// Wrapping type for a function provided as context to allow for setting the
+/// status code of the response on the server. See [`provide_server_status`]
+/// and [`RouteErrorView`].
+#[derive(Clone)]
+pub struct ServerStatusFunction {
+    f: Rc<dyn Fn(u16)>,
+}
+
+impl core::fmt::Debug for ServerStatusFunction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ServerStatusFunction").finish()
+    }
+}
+
+/// Provides a function that can be used to set the status code of the
+/// response on the server, as when a route renders its error view.
+#[cfg_attr(
+    any(debug_assertions, feature = "ssr"),
+    tracing::instrument(level = "trace", skip_all,)
+)]
+pub fn provide_server_status(handler: impl Fn(u16) + 'static) {
+    provide_context(ServerStatusFunction {
+        f: Rc::new(handler),
+    })
+}
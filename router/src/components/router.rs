@@ -1,7 +1,13 @@
 use crate::{
-    create_location, matching::resolve_path, resolve_redirect_url,
-    scroll_to_el, use_location, use_navigate, Branch, History, Location,
-    LocationChange, RouteContext, RouterIntegrationContext, State,
+    create_location,
+    history_state::HistoryStates,
+    matching::{get_route_matches, resolve_path},
+    params_guard::BadRequestView,
+    resolve_redirect_url,
+    scroll::{apply_scroll, window_scroll_position},
+    scroll_to_el, use_location, use_navigate, Branch, Branches, History,
+    Location, LocationChange, NavigationKind, ParamsError, RouteContext,
+    RouterIntegrationContext, ScrollBehavior, ScrollContext, State,
 };
 #[cfg(not(feature = "ssr"))]
 use crate::{unescape, Url};
@@ -16,7 +22,11 @@ use leptos::{
 #[cfg(feature = "transition")]
 use leptos_reactive::use_transition;
 use send_wrapper::SendWrapper;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
 use thiserror::Error;
 #[cfg(not(feature = "ssr"))]
 use wasm_bindgen::JsCast;
@@ -26,18 +36,32 @@ use wasm_bindgen::UnwrapThrowExt;
 /// the root of the application.
 #[component]
 pub fn Router(
-    /// The base URL for the router. Defaults to `""`.
-    #[prop(optional)]
-    base: Option<&'static str>,
+    /// The base path under which the app is served, like `/app`. Defaults to `""`.
+    ///
+    /// Routes are matched relative to it, and the links, redirects and navigations
+    /// of the router resolve absolute paths to include it, so `<A href="/about"/>`
+    /// links to `/app/about`. When rendering on the server, this should match
+    /// the `site_base_path` of the `LeptosOptions` given to the integration.
+    #[prop(optional, into)]
+    base: Option<Oco<'static, str>>,
     /// A fallback that should be shown if no route is matched.
     #[prop(optional)]
     fallback: Option<fn() -> View>,
-    /// A signal that will be set while the navigation process is underway.
+    /// A view that should be shown if the params of a route do not pass its
+    /// [`ParamsGuard`](crate::ParamsGuard), unless the guard has its own.
+    #[prop(optional)]
+    bad_request: Option<fn(ParamsError) -> View>,
+    /// A signal that will be set while the navigation process is underway, including while
+    /// any [`<TransitionOutlet/>`](crate::TransitionOutlet) is animating between routes.
     #[prop(optional, into)]
     set_is_routing: Option<SignalSetter<bool>>,
     /// How trailing slashes should be handled in [`Route`] paths.
     #[prop(optional)]
     trailing_slash: TrailingSlash,
+    /// How the router should update the scroll position at the end of each navigation.
+    /// This can be overridden for individual routes and navigations.
+    #[prop(optional)]
+    scroll_behavior: ScrollBehavior,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [`Routes`](crate::Routes) component somewhere
     /// to define and display [`Route`](crate::Route)s.
@@ -48,11 +72,26 @@ pub fn Router(
     id: usize,
 ) -> impl IntoView {
     // create a new RouterContext and provide it to every component beneath the router
-    let router = RouterContext::new(id, base, fallback, trailing_slash);
+    let router = RouterContext::new(
+        id,
+        base.as_deref(),
+        fallback,
+        trailing_slash,
+        scroll_behavior,
+    );
+    let transitions = router.inner.transitions;
     provide_context(router);
     provide_context(GlobalSuspenseContext::new());
+    if let Some(bad_request) = bad_request {
+        provide_context(BadRequestView(bad_request));
+    }
     if let Some(set_is_routing) = set_is_routing {
-        provide_context(SetIsRouting(set_is_routing));
+        // routing is only complete once route transitions have finished
+        let (is_navigating, set_is_navigating) = create_signal(false);
+        create_isomorphic_effect(move |_| {
+            set_is_routing.set(is_navigating.get() || transitions.get() > 0);
+        });
+        provide_context(SetIsRouting(set_is_navigating.into()));
     }
 
     // set server function redirect hook
@@ -106,16 +145,35 @@ pub(crate) struct RouterContextInner {
     pub base: RouteContext,
     trailing_slash: TrailingSlash,
     pub possible_routes: RefCell<Option<Vec<Branch>>>,
-    #[allow(unused)] // used in CSR/hydrate
-    base_path: String,
+    pub(crate) base_path: String,
     history: Box<dyn History>,
-    reference: ReadSignal<String>,
+    pub(crate) reference: ReadSignal<String>,
     set_reference: WriteSignal<String>,
-    referrers: Rc<RefCell<Vec<LocationChange>>>,
+    pub(crate) referrers: Rc<RefCell<Vec<LocationChange>>>,
     state: ReadSignal<State>,
     set_state: WriteSignal<State>,
     pub(crate) is_back: RwSignal<bool>,
     pub(crate) path_stack: StoredValue<Vec<String>>,
+    pub(crate) transitions: RwSignal<usize>,
+    scroll_behavior: ScrollBehavior,
+    pub(crate) route_scroll_behavior: RefCell<Option<ScrollBehavior>>,
+    pub(crate) is_traversing: Cell<bool>,
+    // the pathname the page behind intercepted routes is still showing, if any
+    background: RwSignal<Option<String>>,
+    // the keys of the branches of each `<Routes intercept=true/>`
+    pub(crate) intercepting: RefCell<Vec<String>>,
+    pub(crate) routed_pathname: Memo<String>,
+    pub(crate) intercepted_pathname: Memo<Option<String>>,
+    scroll_positions: RefCell<HashMap<String, (f64, f64)>>,
+    pub(crate) history_states: HistoryStates,
+    pending_scroll: RefCell<Option<PendingScroll>>,
+}
+
+// how to scroll once the current navigation (including any redirects) ends
+struct PendingScroll {
+    kind: NavigationKind,
+    behavior: Option<ScrollBehavior>,
+    global_suspense: Option<GlobalSuspenseContext>,
 }
 
 impl core::fmt::Debug for RouterContextInner {
@@ -140,9 +198,10 @@ impl RouterContext {
     )]
     pub(crate) fn new(
         id: usize,
-        base: Option<&'static str>,
+        base: Option<&str>,
         fallback: Option<fn() -> View>,
         trailing_slash: TrailingSlash,
+        scroll_behavior: ScrollBehavior,
     ) -> Self {
         cfg_if! {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
@@ -195,6 +254,14 @@ impl RouterContext {
 
         // Each field of `location` reactively represents a different part of the current location
         let location = create_location(reference, state);
+        let background = create_rw_signal(None::<String>);
+        let pathname = location.pathname;
+        let routed_pathname = create_memo(move |_| {
+            background.get().unwrap_or_else(|| pathname.get())
+        });
+        let intercepted_pathname = create_memo(move |_| {
+            background.with(Option::is_some).then(|| pathname.get())
+        });
         let referrers: Rc<RefCell<Vec<LocationChange>>> =
             Rc::new(RefCell::new(Vec::new()));
 
@@ -234,6 +301,17 @@ impl RouterContext {
             set_state,
             possible_routes: Default::default(),
             is_back: create_rw_signal(false),
+            transitions: create_rw_signal(0),
+            scroll_behavior,
+            route_scroll_behavior: Default::default(),
+            is_traversing: Default::default(),
+            background,
+            intercepting: Default::default(),
+            routed_pathname,
+            intercepted_pathname,
+            scroll_positions: Default::default(),
+            history_states: Default::default(),
+            pending_scroll: Default::default(),
         });
 
         // handle all click events on anchor tags
@@ -244,6 +322,24 @@ impl RouterContext {
                 move |ev| inner.clone().handle_anchor_click(ev)
             });
             on_cleanup(move || click_event.remove());
+
+            // the router restores scroll positions itself
+            if let Ok(history) = window().history() {
+                _ = history
+                    .set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+            }
+
+            // once a navigation has replaced the page that was rendered on the
+            // server, its head tags that were not hydrated are removed
+            #[cfg(feature = "meta")]
+            if let Some(meta) = use_context::<leptos_meta::MetaContext>() {
+                create_effect(move |prev: Option<()>| {
+                    routed_pathname.track();
+                    if prev.is_some() {
+                        meta.tags.remove_unclaimed();
+                    }
+                });
+            }
         }
 
         Self { inner }
@@ -267,6 +363,19 @@ impl RouterContext {
         self.inner.trailing_slash.clone()
     }
 
+    /// Starts loading the views of any lazily-loaded routes matched by the given path.
+    /// See [`LazyView`](crate::LazyView).
+    pub fn preload(&self, path: &str) {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        Branches::for_each_match(self.id(), path, |matches| {
+            for m in matches {
+                if let Some(lazy) = &m.route.key.lazy {
+                    lazy.preload();
+                }
+            }
+        });
+    }
+
     /// A list of all possible routes this router can match.
     pub fn possible_branches(&self) -> Vec<Branch> {
         self.inner
@@ -288,6 +397,13 @@ impl RouterContextInner {
         options: &NavigateOptions,
     ) -> Result<(), NavigationError> {
         let this = Rc::clone(&self);
+        let kind = if self.is_traversing.take() {
+            NavigationKind::Traverse
+        } else if options.replace {
+            NavigationKind::Replace
+        } else {
+            NavigationKind::Push
+        };
 
         untrack(move || {
             let resolved_to = if options.resolve {
@@ -311,6 +427,32 @@ impl RouterContextInner {
                     if resolved_to != this.reference.get()
                         || options.state != (this.state).get()
                     {
+                        let intercepted = self.is_intercepted(&resolved_to);
+                        if self.referrers.borrow().is_empty() {
+                            // remember where we were, to restore it if the
+                            // user comes back to this URL
+                            self.scroll_positions.borrow_mut().insert(
+                                self.reference.get(),
+                                window_scroll_position(),
+                            );
+                            self.history_states.save(&self.reference.get());
+                            if kind != NavigationKind::Traverse {
+                                self.history_states.clear(&resolved_to);
+                            }
+                            *self.pending_scroll.borrow_mut() =
+                                Some(PendingScroll {
+                                    kind,
+                                    // the page stays where it is behind
+                                    // intercepted routes
+                                    behavior: options
+                                        .scroll_behavior
+                                        .clone()
+                                        .or(intercepted.then_some(
+                                            ScrollBehavior::Preserve,
+                                        )),
+                                    global_suspense: use_context(),
+                                });
+                        }
                         {
                             self.referrers.borrow_mut().push(LocationChange {
                                 value: self.reference.get(),
@@ -328,11 +470,20 @@ impl RouterContextInner {
 
                         let resolved = resolved_to.to_string();
                         let state = options.state.clone();
-                        set_reference.update(move |r| *r = resolved);
-
-                        set_state.update({
-                            let next_state = state.clone();
-                            move |state| *state = next_state
+                        let background = self.background;
+                        let pathname = self.location.pathname;
+                        batch(|| {
+                            background.set(intercepted.then(|| {
+                                background
+                                    .get_untracked()
+                                    .unwrap_or_else(|| pathname.get_untracked())
+                            }));
+                            set_reference.update(move |r| *r = resolved);
+
+                            set_state.update({
+                                let next_state = state.clone();
+                                move |state| *state = next_state
+                            });
                         });
 
                         let global_suspense =
@@ -376,6 +527,15 @@ impl RouterContextInner {
         })
     }
 
+    // whether `to` is matched by any `<Routes intercept=true/>`, so that the
+    // current page should stay mounted behind it
+    fn is_intercepted(&self, to: &str) -> bool {
+        let path = to.split(['?', '#']).next().unwrap_or_default();
+        self.intercepting.borrow().iter().any(|key| {
+            !get_route_matches(self.id, key, path.to_string()).is_empty()
+        })
+    }
+
     pub(crate) fn navigate_end(self: Rc<Self>, mut next: LocationChange) {
         let first = self.referrers.borrow().first().cloned();
         if let Some(first) = first {
@@ -383,11 +543,29 @@ impl RouterContextInner {
                 next.replace = first.replace;
                 next.scroll = first.scroll;
                 self.history.navigate(&next);
+                self.scroll_end(&next);
             }
             self.referrers.borrow_mut().clear();
         }
     }
 
+    fn scroll_end(&self, next: &LocationChange) {
+        let Some(pending) = self.pending_scroll.take() else {
+            return;
+        };
+        let behavior = pending
+            .behavior
+            .or_else(|| self.route_scroll_behavior.borrow().clone())
+            .unwrap_or_else(|| self.scroll_behavior.clone());
+        let cx = ScrollContext::new(
+            next.value.clone(),
+            pending.kind,
+            self.scroll_positions.borrow().get(&next.value).copied(),
+            next.scroll,
+        );
+        apply_scroll(&behavior, cx, pending.global_suspense);
+    }
+
     #[cfg(not(feature = "ssr"))]
     pub(crate) fn handle_anchor_click(self: Rc<Self>, ev: web_sys::Event) {
         use wasm_bindgen::JsValue;
@@ -478,6 +656,7 @@ impl RouterContextInner {
                     resolve: false,
                     replace,
                     scroll: !a.has_attribute("noscroll"),
+                    scroll_behavior: None,
                     state: State(state),
                 },
             ) {
@@ -499,7 +678,17 @@ pub enum NavigationError {
 }
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::use_navigate).
+///
+/// Options are set with the builder methods named after its fields, starting
+/// from the defaults:
+/// ```rust
+/// # use leptos_router::*;
+/// let options = NavigateOptions::default()
+///     .replace(true)
+///     .scroll_behavior(ScrollBehavior::Preserve);
+/// ```
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct NavigateOptions {
     /// Whether the URL being navigated to should be resolved relative to the current route.
     pub resolve: bool,
@@ -509,6 +698,10 @@ pub struct NavigateOptions {
     /// If `true`, the router will scroll to the top of the window at the end of navigation.
     /// Defaults to `true`.
     pub scroll: bool,
+    /// How the router should update the scroll position at the end of this navigation,
+    /// overriding the behavior of the route being navigated to and of the router.
+    /// Defaults to `None`.
+    pub scroll_behavior: Option<ScrollBehavior>,
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
     /// onto the history stack during navigation.
     pub state: State,
@@ -520,11 +713,49 @@ impl Default for NavigateOptions {
             resolve: true,
             replace: false,
             scroll: true,
+            scroll_behavior: None,
             state: State(None),
         }
     }
 }
 
+impl NavigateOptions {
+    /// Sets whether the URL being navigated to should be resolved relative to
+    /// the current route.
+    pub fn resolve(mut self, resolve: bool) -> Self {
+        self.resolve = resolve;
+        self
+    }
+
+    /// Sets whether the new location will replace the current route in the
+    /// history stack.
+    pub fn replace(mut self, replace: bool) -> Self {
+        self.replace = replace;
+        self
+    }
+
+    /// Sets whether the router will scroll to the top of the window at the
+    /// end of navigation.
+    pub fn scroll(mut self, scroll: bool) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Sets how the router should update the scroll position at the end of
+    /// this navigation.
+    pub fn scroll_behavior(mut self, scroll_behavior: ScrollBehavior) -> Self {
+        self.scroll_behavior = Some(scroll_behavior);
+        self
+    }
+
+    /// Sets the state that should be pushed onto the history stack during
+    /// navigation.
+    pub fn state(mut self, state: State) -> Self {
+        self.state = state;
+        self
+    }
+}
+
 /// Declares how you would like to handle trailing slashes in Route paths. This
 /// can be set on [`Router`] and overridden in [`crate::components::Route`]
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
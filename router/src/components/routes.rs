@@ -5,6 +5,7 @@ use crate::{
         expand_optionals, get_route_matches, join_paths, Branch, Matcher,
         RouteDefinition, RouteMatch,
     },
+    route_meta::apply_route_meta,
     use_is_back_navigation, use_route, NavigateOptions, Redirect, RouteContext,
     RouterContext, SetIsRouting, TrailingSlash,
 };
@@ -72,9 +73,38 @@ use std::{
 )]
 #[component]
 pub fn Routes(
-    /// Base path relative at which the routes are mounted.
+    /// Base path at which the routes are mounted, relative to the base path of the
+    /// [`Router`](crate::Router).
     #[prop(optional)]
     base: Option<String>,
+    /// Whether these routes intercept client-side navigations, rendering alongside
+    /// the page that was showing, which stays mounted in the other `<Routes/>`.
+    ///
+    /// This is useful for modals: if `/photos/:id` is also defined in an intercepting
+    /// `<Routes/>`, following a link to a photo from the feed shows it over the feed,
+    /// while loading `/photos/123` directly (or reloading) renders the full page from the
+    /// main `<Routes/>`. Intercepting routes render nothing when no navigation is
+    /// being intercepted.
+    ///
+    /// ```rust
+    /// # use leptos::*;
+    /// # use leptos_router::*;
+    /// # if false {
+    /// view! {
+    ///   <Router>
+    ///     <Routes>
+    ///       <Route path="/" view=|| "Feed"/>
+    ///       <Route path="/photos/:id" view=|| "Photo page"/>
+    ///     </Routes>
+    ///     <Routes intercept=true>
+    ///       <Route path="/photos/:id" view=|| view! { <dialog open>"Photo"</dialog> }/>
+    ///     </Routes>
+    ///   </Router>
+    /// }
+    /// # ;}
+    /// ```
+    #[prop(optional)]
+    intercept: bool,
     children: Children,
 ) -> impl IntoView {
     let router = use_context::<RouterContext>()
@@ -82,19 +112,30 @@ pub fn Routes(
     let router_id = router.id();
 
     let base_route = router.base();
-    let base = base.unwrap_or_default();
-
-    Branches::initialize(&router, &base, children());
+    let mut base =
+        join_paths(&router.inner.base_path, &base.unwrap_or_default());
+
+    let current_route = if intercept {
+        let key = intercepting_key(&base);
+        Branches::initialize_with_key(&router, &key, &base, children());
+        router.inner.intercepting.borrow_mut().push(key.clone());
+        // from here on, the branches are looked up by their key
+        base = key;
+        router.inner.intercepted_pathname
+    } else {
+        Branches::initialize(&router, &base, children());
 
-    #[cfg(feature = "ssr")]
-    if let Some(context) = use_context::<crate::PossibleBranchContext>() {
-        Branches::with(router_id, &base, |branches| {
-            *context.0.borrow_mut() = branches.to_vec()
-        });
-    }
+        #[cfg(feature = "ssr")]
+        if let Some(context) = use_context::<crate::PossibleBranchContext>() {
+            Branches::with(router_id, &base, |branches| {
+                *context.0.borrow_mut() = branches.to_vec()
+            });
+        }
 
-    let next_route = router.pathname();
-    let current_route = next_route;
+        let routed_pathname = router.inner.routed_pathname;
+        apply_route_meta(router_id, &base, routed_pathname);
+        create_memo(move |_| Some(routed_pathname.get()))
+    };
 
     let root_equal = Rc::new(Cell::new(true));
     let route_states =
@@ -136,7 +177,8 @@ pub fn AnimatedRoutes(
     /// Base classes to be applied to the `<div>` wrapping the routes during any animation state.
     #[prop(optional, into)]
     class: Option<TextProp>,
-    /// Base path relative at which the routes are mounted.
+    /// Base path at which the routes are mounted, relative to the base path of the
+    /// [`Router`](crate::Router).
     #[prop(optional)]
     base: Option<String>,
     /// CSS class added when route is being unmounted
@@ -164,7 +206,7 @@ pub fn AnimatedRoutes(
     let router_id = router.id();
 
     let base_route = router.base();
-    let base = base.unwrap_or_default();
+    let base = join_paths(&router.inner.base_path, &base.unwrap_or_default());
 
     Branches::initialize(&router, &base, children());
 
@@ -186,7 +228,9 @@ pub fn AnimatedRoutes(
     let is_back = use_is_back_navigation();
     let (animation_state, set_animation_state) =
         create_signal(AnimationState::Finally);
-    let next_route = router.pathname();
+    let next_route = router.inner.routed_pathname;
+
+    apply_route_meta(router_id, &base, next_route);
 
     let is_complete = Rc::new(Cell::new(true));
     let animation_and_route = create_memo({
@@ -225,7 +269,7 @@ pub fn AnimatedRoutes(
         }
     });
     let current_animation = create_memo(move |_| animation_and_route.get().0);
-    let current_route = create_memo(move |_| animation_and_route.get().1);
+    let current_route = create_memo(move |_| Some(animation_and_route.get().1));
 
     let root_equal = Rc::new(Cell::new(true));
     let route_states =
@@ -277,14 +321,34 @@ pub(crate) struct Branches;
 type BranchesCacheKey = (usize, Cow<'static, str>);
 thread_local! {
     static BRANCHES: RefCell<HashMap<BranchesCacheKey, Vec<Branch>>> = RefCell::new(HashMap::new());
+    static DEFINITIONS: RefCell<HashMap<BranchesCacheKey, Vec<RouteDefinition>>> = RefCell::new(HashMap::new());
+}
+
+// distinguishes the branches of `<Routes intercept=true/>` from those of
+// the main `<Routes/>` mounted at the same base
+const INTERCEPT_SUFFIX: &str = "\0intercept";
+
+pub(crate) fn intercepting_key(base: &str) -> String {
+    format!("{base}{INTERCEPT_SUFFIX}")
 }
 
 impl Branches {
     pub fn initialize(router: &RouterContext, base: &str, children: Fragment) {
+        Self::initialize_with_key(router, base, base, children)
+    }
+
+    pub(crate) fn initialize_with_key(
+        router: &RouterContext,
+        key: &str,
+        base: &str,
+        children: Fragment,
+    ) {
+        let intercepting = key.ends_with(INTERCEPT_SUFFIX);
         BRANCHES.with(|branches| {
             #[cfg(debug_assertions)]
             {
                 if cfg!(any(feature = "csr", feature = "hydrate"))
+                    && !intercepting
                     && !branches.borrow().is_empty()
                 {
                     leptos::logging::warn!(
@@ -295,7 +359,7 @@ impl Branches {
             }
 
             let mut current = branches.borrow_mut();
-            if !current.contains_key(&(router.id(), Cow::from(base))) {
+            if !current.contains_key(&(router.id(), Cow::from(key))) {
                 let mut branches = Vec::new();
                 let mut children = children
                     .as_children()
@@ -326,7 +390,16 @@ impl Branches {
                     true,
                     base,
                 );
-                current.insert((router.id(), Cow::Owned(base.into())), branches);
+                current.insert((router.id(), Cow::Owned(key.into())), branches);
+                // intercepting routes duplicate the paths of the main routes
+                if !intercepting {
+                    DEFINITIONS.with(|definitions| {
+                        definitions.borrow_mut().insert(
+                            (router.id(), Cow::Owned(base.into())),
+                            children,
+                        )
+                    });
+                }
             }
         })
     }
@@ -345,6 +418,42 @@ impl Branches {
             cb(branches)
         })
     }
+
+    /// Calls `cb` with the base path and route definitions of each set of
+    /// branches registered for the given router.
+    pub fn for_each_definition(
+        router_id: usize,
+        mut cb: impl FnMut(&str, &[RouteDefinition]),
+    ) {
+        DEFINITIONS.with(|definitions| {
+            for ((id, base), defs) in definitions.borrow().iter() {
+                if *id == router_id {
+                    cb(base, defs);
+                }
+            }
+        })
+    }
+
+    /// Calls `cb` with the routes matched by `path`, in each set of branches
+    /// registered for the given router.
+    pub fn for_each_match(
+        router_id: usize,
+        path: &str,
+        mut cb: impl FnMut(&[RouteMatch]),
+    ) {
+        BRANCHES.with(|branches| {
+            for ((id, key), branches) in branches.borrow().iter() {
+                if *id != router_id || key.ends_with(INTERCEPT_SUFFIX) {
+                    continue;
+                }
+                if let Some(matches) =
+                    branches.iter().find_map(|branch| branch.matcher(path))
+                {
+                    cb(&matches);
+                }
+            }
+        })
+    }
 }
 
 // <Route>s may inherit settings from each other or <Router>.
@@ -381,12 +490,25 @@ fn route_states(
     router_id: usize,
     base: String,
     router: &RouterContext,
-    current_route: Memo<String>,
+    current_route: Memo<Option<String>>,
     root_equal: &Rc<Cell<bool>>,
 ) -> Memo<RouterState> {
     // whenever path changes, update matches
-    let matches = create_memo(move |_| {
-        get_route_matches(router_id, &base, current_route.get())
+    let matches = create_memo({
+        let router = Rc::clone(&router.inner);
+        move |_| {
+            // there is nothing to match while intercepting routes are inactive
+            let Some(current_route) = current_route.get() else {
+                return Default::default();
+            };
+            let matches = get_route_matches(router_id, &base, current_route);
+            // the most deeply-nested route that sets a scroll behavior wins
+            *router.route_scroll_behavior.borrow_mut() = matches
+                .iter()
+                .rev()
+                .find_map(|m| m.route.key.scroll_behavior.clone());
+            matches
+        }
     });
 
     // iterate over the new matches, reusing old routes when they are the same
@@ -518,6 +640,10 @@ fn root_route(
             provide_context(route_states);
             route_states.with(|state| {
                 if state.routes.borrow().is_empty() {
+                    // no route matches, so the app's not found page, if it
+                    // has one, replaces the router's fallback
+                    #[cfg(feature = "ssr")]
+                    leptos::error_pages::set_not_found();
                     let (outlet, disposer) = outlet(base_route.clone());
                     drop(std::mem::replace(
                         &mut *root_disposer.borrow_mut(),
@@ -596,21 +722,7 @@ pub struct RouteData {
 
 impl RouteData {
     fn score(&self) -> i32 {
-        let (pattern, splat) = match self.pattern.split_once("/*") {
-            Some((p, s)) => (p, Some(s)),
-            None => (self.pattern.as_str(), None),
-        };
-        let segments = pattern
-            .split('/')
-            .filter(|n| !n.is_empty())
-            .collect::<Vec<_>>();
-        #[allow(clippy::bool_to_int_with_if)] // on the splat.is_none()
-        segments.iter().fold(
-            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
-            |score, segment| {
-                score + if segment.starts_with(':') { 2 } else { 3 }
-            },
-        )
+        self.matcher.score()
     }
 }
 
@@ -652,6 +764,31 @@ fn create_branches(
 
     if stack.is_empty() {
         branches.sort_by_key(|branch| Reverse(branch.score));
+        #[cfg(debug_assertions)]
+        warn_ambiguous_branches(branches);
+    }
+}
+
+// two routes that match exactly the same paths can only ever render the
+// first one defined, which is almost certainly a mistake
+#[cfg(debug_assertions)]
+fn warn_ambiguous_branches(branches: &[Branch]) {
+    let leaves = branches
+        .iter()
+        .filter_map(|branch| branch.routes.last())
+        .collect::<Vec<_>>();
+    for (i, route) in leaves.iter().enumerate() {
+        if let Some(other) = leaves[..i]
+            .iter()
+            .find(|other| other.matcher.is_ambiguous_with(&route.matcher))
+        {
+            leptos::logging::warn!(
+                "[Routes] The route `{}` matches the same paths as the route \
+                 `{}`, so it will never be rendered.",
+                route.pattern,
+                other.pattern
+            );
+        }
     }
 }
 
@@ -691,7 +828,10 @@ fn create_routes(
         trailing_slash.normalize_route_path(&mut path);
         let pattern = if is_leaf {
             path
-        } else if let Some((path, _splat)) = path.split_once("/*") {
+        } else if let Some((path, _splat)) = path
+            .rsplit_once("/*")
+            .filter(|(_, splat)| !splat.contains('/'))
+        {
             path.to_string()
         } else {
             path
@@ -759,6 +899,14 @@ fn redirect_route_for(route: &RouteDefinition) -> Option<RouteDefinition> {
         path: new_pattern,
         children: vec![],
         data: None,
+        loaders: vec![],
+        named_views: Default::default(),
+        title: None,
+        meta: None,
+        params: None,
+        error_view: None,
+        scroll_behavior: None,
+        lazy: None,
         methods: route.methods,
         id: new_route_id(),
         view,
@@ -766,6 +914,7 @@ fn redirect_route_for(route: &RouteDefinition) -> Option<RouteDefinition> {
         static_mode: route.static_mode,
         static_params: None,
         trailing_slash: None, // Shouldn't be needed/used from here on out
+        server_only: route.server_only,
     };
 
     Some(new_route)
@@ -1,7 +1,14 @@
+use crate::{split_constraint, ParamsMap};
 #[cfg(feature = "ssr")]
-use crate::{RouteListing, RouterIntegrationContext, ServerIntegration};
+use crate::{
+    Method, RouteListing, RouterIntegrationContext, ServerIntegration,
+};
+#[cfg(feature = "ssr")]
+use futures::Stream;
 #[cfg(feature = "ssr")]
-use leptos::{create_runtime, provide_context, IntoView, LeptosOptions};
+use leptos::{
+    create_runtime, provide_context, IntoView, LeptosOptions, RuntimeId,
+};
 #[cfg(feature = "ssr")]
 use leptos_meta::MetaContext;
 use linear_map::LinearMap;
@@ -16,10 +23,32 @@ use std::{
     path::PathBuf,
     pin::Pin,
     sync::Arc,
+    time::Duration,
 };
 
+/// The params used to build the concrete paths of a statically-rendered route.
+///
+/// Values for each param can be listed with [`insert`](Self::insert), in which
+/// case a path is built for every combination of them. When only some
+/// combinations are valid (e.g., each blog post has its own year and slug),
+/// they can be listed one by one with [`push`](Self::push) instead, or the map
+/// can be collected from an iterator of [`ParamsMap`]s. Any param missing
+/// from a combination is filled in from the listed values.
+///
+/// ```
+/// # use leptos_router::*;
+/// let posts = [("2023", "hello-world"), ("2024", "leptos-0-6")];
+/// let params = posts
+///     .iter()
+///     .map(|(year, slug)| params_map! { "year" => year, "slug" => slug })
+///     .collect::<StaticParamsMap>();
+/// assert_eq!(params.combinations().len(), 2);
+/// ```
 #[derive(Debug, Default, Serialize, Deserialize)]
-pub struct StaticParamsMap(pub LinearMap<String, Vec<String>>);
+pub struct StaticParamsMap(
+    pub LinearMap<String, Vec<String>>,
+    #[serde(default)] Vec<ParamsMap>,
+);
 
 impl StaticParamsMap {
     /// Create a new empty `StaticParamsMap`.
@@ -39,6 +68,24 @@ impl StaticParamsMap {
     pub fn get(&self, key: &str) -> Option<&Vec<String>> {
         self.0.get(key)
     }
+
+    /// Adds a single combination of param values to build.
+    #[inline]
+    pub fn push(&mut self, params: ParamsMap) {
+        self.1.push(params);
+    }
+
+    /// The combinations of param values added with [`push`](Self::push).
+    #[inline]
+    pub fn combinations(&self) -> &[ParamsMap] {
+        &self.1
+    }
+}
+
+impl FromIterator<ParamsMap> for StaticParamsMap {
+    fn from_iter<T: IntoIterator<Item = ParamsMap>>(iter: T) -> Self {
+        Self(LinearMap::new(), iter.into_iter().collect())
+    }
 }
 
 #[doc(hidden)]
@@ -47,6 +94,7 @@ pub struct StaticPath<'b, 'a: 'b> {
     path: &'a str,
     segments: Vec<StaticPathSegment<'a>>,
     params: LinearMap<&'a str, &'b Vec<String>>,
+    combinations: &'b [ParamsMap],
 }
 
 #[doc(hidden)]
@@ -54,6 +102,7 @@ pub struct StaticPath<'b, 'a: 'b> {
 enum StaticPathSegment<'a> {
     Static(&'a str),
     Param(&'a str),
+    OptionalParam(&'a str),
     Wildcard(&'a str),
 }
 
@@ -66,12 +115,16 @@ impl<'b, 'a: 'b> StaticPath<'b, 'a> {
                 .split('/')
                 .filter(|s| !s.is_empty())
                 .map(|s| match s.chars().next() {
-                    Some(':') => Param(&s[1..]),
+                    Some(':') => match s[1..].strip_suffix('?') {
+                        Some(name) => OptionalParam(split_constraint(name).0),
+                        None => Param(split_constraint(&s[1..]).0),
+                    },
                     Some('*') => Wildcard(&s[1..]),
                     _ => Static(s),
                 })
                 .collect::<Vec<_>>(),
             params: LinearMap::new(),
+            combinations: &[],
         }
     }
 
@@ -79,7 +132,7 @@ impl<'b, 'a: 'b> StaticPath<'b, 'a> {
         use StaticPathSegment::*;
         for segment in self.segments.iter() {
             match segment {
-                Param(name) | Wildcard(name) => {
+                Param(name) | OptionalParam(name) | Wildcard(name) => {
                     if let Some(value) = params.get(name) {
                         self.params.insert(name, value);
                     }
@@ -87,40 +140,71 @@ impl<'b, 'a: 'b> StaticPath<'b, 'a> {
                 _ => {}
             }
         }
+        // the most specific route's combinations replace its parents'
+        if !params.combinations().is_empty() {
+            self.combinations = params.combinations();
+        }
     }
 
     pub fn into_paths(self) -> Vec<ResolvedStaticPath> {
+        self.try_into_paths().unwrap_or_else(|name| {
+            panic!("missing param {} for path: {}", name, self.path)
+        })
+    }
+
+    // like `into_paths`, but returns the name of a param that has no values
+    // instead of panicking
+    pub(crate) fn try_into_paths(
+        &self,
+    ) -> Result<Vec<ResolvedStaticPath>, &'a str> {
+        let empty = ParamsMap::new();
+        if self.combinations.is_empty() {
+            self.paths_for(&empty)
+        } else {
+            let mut paths = Vec::new();
+            for combination in self.combinations {
+                paths.extend(self.paths_for(combination)?);
+            }
+            Ok(paths)
+        }
+    }
+
+    // builds every path for a single combination, filling in any params
+    // it's missing with every listed value
+    fn paths_for(
+        &self,
+        combination: &ParamsMap,
+    ) -> Result<Vec<ResolvedStaticPath>, &'a str> {
         use StaticPathSegment::*;
         let mut paths = vec![ResolvedStaticPath(String::new())];
 
-        for segment in self.segments {
-            match segment {
+        for segment in &self.segments {
+            let (name, optional) = match segment {
                 Static(s) => {
                     paths = paths
                         .into_iter()
                         .map(|p| ResolvedStaticPath(format!("{p}/{s}")))
                         .collect::<Vec<_>>();
+                    continue;
                 }
-                Param(name) | Wildcard(name) => {
-                    let mut new_paths = vec![];
-                    for path in paths {
-                        let Some(params) = self.params.get(name) else {
-                            panic!(
-                                "missing param {} for path: {}",
-                                name, self.path
-                            );
-                        };
-                        for val in params.iter() {
-                            new_paths.push(ResolvedStaticPath(format!(
-                                "{path}/{val}"
-                            )));
-                        }
-                    }
-                    paths = new_paths;
+                Param(name) | Wildcard(name) => (*name, false),
+                OptionalParam(name) => (*name, true),
+            };
+            let values = match (combination.get(name), self.params.get(name)) {
+                (Some(value), _) => std::slice::from_ref(value),
+                (None, Some(values)) => values.as_slice(),
+                (None, None) if optional => continue,
+                (None, None) => return Err(name),
+            };
+            let mut new_paths = vec![];
+            for path in paths {
+                for val in values {
+                    new_paths.push(ResolvedStaticPath(format!("{path}/{val}")));
                 }
             }
+            paths = new_paths;
         }
-        paths
+        Ok(paths)
     }
 
     pub fn parent(&self) -> Option<StaticPath<'b, 'a>> {
@@ -173,6 +257,22 @@ impl ResolvedStaticPath {
         app_fn: impl Fn() -> IV + 'static + Clone,
         additional_context: impl Fn() + 'static + Clone,
     ) -> String
+    where
+        IV: IntoView + 'static,
+    {
+        let (stream, runtime) = self.render(app_fn, additional_context);
+        leptos_integration_utils::build_async_response(stream, options, runtime)
+            .await
+    }
+
+    // renders the app at this path in order, in a new runtime that is not
+    // disposed of
+    #[cfg(feature = "ssr")]
+    pub(crate) fn render<IV>(
+        &self,
+        app_fn: impl Fn() -> IV + 'static + Clone,
+        additional_context: impl Fn() + 'static + Clone,
+    ) -> (impl Stream<Item = String>, RuntimeId)
     where
         IV: IntoView + 'static,
     {
@@ -187,9 +287,7 @@ impl ResolvedStaticPath {
                 (app_fn)().into_view()
             }
         };
-        let (stream, runtime) = leptos::ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(app, move || "".into(), additional_context.clone());
-        leptos_integration_utils::build_async_response(stream, options, runtime)
-            .await
+        leptos::ssr::render_to_stream_in_order_with_prefix_undisposed_with_context(app, move || "".into(), additional_context)
     }
 
     #[cfg(feature = "ssr")]
@@ -244,31 +342,15 @@ pub async fn build_static_routes_with_additional_context<IV>(
 where
     IV: IntoView + 'static,
 {
-    let mut static_data: HashMap<&str, StaticParamsMap> = HashMap::new();
-    let runtime = create_runtime();
-    additional_context();
-    for (key, value) in static_data_map {
-        match value {
-            Some(value) => static_data.insert(key, value.as_ref()().await),
-            None => static_data.insert(key, StaticParamsMap::default()),
-        };
-    }
-    runtime.dispose();
+    let static_data =
+        resolve_static_data(additional_context.clone(), static_data_map).await;
     let static_routes = routes
         .iter()
         .filter(|route| route.static_mode().is_some())
         .collect::<Vec<_>>();
     // TODO: maybe make this concurrent in some capacity
     for route in static_routes {
-        let mut path = StaticPath::new(route.leptos_path());
-        for p in path.parents().into_iter().rev() {
-            if let Some(data) = static_data.get(p.path()) {
-                path.add_params(data);
-            }
-        }
-        if let Some(data) = static_data.get(path.path()) {
-            path.add_params(data);
-        }
+        let path = static_path(route.leptos_path(), &static_data);
         #[allow(clippy::print_stdout)]
         for path in path.into_paths() {
             println!("building static route: {path}");
@@ -279,6 +361,133 @@ where
     Ok(())
 }
 
+// calls the function of each route that provides its static params, in a
+// runtime in which `additional_context` has been called
+#[cfg(feature = "ssr")]
+pub(crate) async fn resolve_static_data(
+    additional_context: impl Fn(),
+    static_data_map: &StaticDataMap,
+) -> HashMap<&str, StaticParamsMap> {
+    let mut static_data = HashMap::new();
+    let runtime = create_runtime();
+    additional_context();
+    for (key, value) in static_data_map {
+        match value {
+            Some(value) => static_data.insert(key.as_str(), value().await),
+            None => {
+                static_data.insert(key.as_str(), StaticParamsMap::default())
+            }
+        };
+    }
+    runtime.dispose();
+    static_data
+}
+
+// the paths of every route that handles `GET` requests, paired with the
+// route they are built from, leaving out the routes with params that cannot
+// be enumerated from their static params
+#[cfg(feature = "ssr")]
+pub(crate) async fn enumerate_paths<'a>(
+    additional_context: impl Fn(),
+    routes: &'a [RouteListing],
+    static_data_map: &StaticDataMap,
+) -> Vec<(&'a str, String)> {
+    let static_data =
+        resolve_static_data(additional_context, static_data_map).await;
+    let mut paths: Vec<(&str, String)> = Vec::new();
+    for route in routes {
+        if !route.methods().any(|method| method == Method::Get) {
+            continue;
+        }
+        let resolved = match static_path(route.leptos_path(), &static_data)
+            .try_into_paths()
+        {
+            Ok(resolved) => resolved,
+            Err(param) => {
+                tracing::debug!(
+                    "cannot enumerate the paths of {}, as there are no values \
+                     for its param {param}",
+                    route.leptos_path()
+                );
+                continue;
+            }
+        };
+        let pattern = match route.leptos_path() {
+            "" => "/",
+            path => path,
+        };
+        for path in resolved {
+            let path = if path.0.is_empty() {
+                "/".to_string()
+            } else {
+                path.0
+            };
+            if !paths.iter().any(|(_, p)| *p == path) {
+                paths.push((pattern, path));
+            }
+        }
+    }
+    paths
+}
+
+// the path of a route, with the static params of it and its parents
+#[cfg(feature = "ssr")]
+pub(crate) fn static_path<'b, 'a: 'b>(
+    leptos_path: &'a str,
+    static_data: &'b HashMap<&str, StaticParamsMap>,
+) -> StaticPath<'b, 'a> {
+    let mut path = StaticPath::new(leptos_path);
+    for p in path.parents().into_iter().rev() {
+        if let Some(data) = static_data.get(p.path()) {
+            path.add_params(data);
+        }
+    }
+    if let Some(data) = static_data.get(path.path()) {
+        path.add_params(data);
+    }
+    path
+}
+
+/// Re-renders the static page for a concrete path (e.g., `/blog/hello-world`),
+/// for use when the data it was built from has changed.
+///
+/// This works for routes in either [`StaticMode`]. To rebuild an
+/// [`Incremental`](StaticMode::Incremental) route lazily instead, remove its
+/// page with [`invalidate_static_route`], and it will be rendered again on the
+/// next request.
+#[cfg(feature = "ssr")]
+pub async fn rebuild_static_route<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    additional_context: impl Fn() + 'static + Clone,
+    path: &str,
+) -> Result<(), std::io::Error>
+where
+    IV: IntoView + 'static,
+{
+    ResolvedStaticPath(path.to_string())
+        .write(options, app_fn, additional_context)
+        .await
+        .map(|_| ())
+}
+
+/// Removes the static page for a concrete path (e.g., `/blog/hello-world`),
+/// if it has been built.
+///
+/// An [`Incremental`](StaticMode::Incremental) route is rendered again on its
+/// next request. An [`Upfront`](StaticMode::Upfront) route will not be found
+/// until it is rebuilt with [`rebuild_static_route`].
+#[cfg(feature = "ssr")]
+pub fn invalidate_static_route(
+    options: &LeptosOptions,
+    path: &str,
+) -> Result<(), std::io::Error> {
+    match std::fs::remove_file(static_file_path(options, path)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
 pub type StaticData = Arc<StaticDataFn>;
 
 pub type StaticDataFn = dyn Fn() -> Pin<Box<dyn Future<Output = StaticParamsMap> + Send + Sync>>
@@ -291,12 +500,30 @@ pub type StaticDataMap = HashMap<String, Option<StaticData>>;
 /// The mode to use when rendering the route statically.
 /// On mode `Upfront`, the route will be built with the server is started using the provided static
 /// data. On mode `Incremental`, the route will be built on the first request to it and then cached
-/// and returned statically for subsequent requests.
-#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// and returned statically for subsequent requests. On mode `Revalidate`, the route is built like
+/// on mode `Incremental`, and once its page is older than the given duration, the next request to
+/// it is served the stale page while the route is built again in the background.
+///
+/// `Revalidate` is supported by `leptos_axum`, where pages of routes in any mode can also be
+/// marked as stale with `leptos_axum::revalidate`. Other integrations treat it as `Incremental`.
+#[derive(
+    Default,
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
 pub enum StaticMode {
     #[default]
     Upfront,
     Incremental,
+    Revalidate(Duration),
 }
 
 #[doc(hidden)]
@@ -85,12 +85,14 @@ impl History for BrowserIntegration {
                 request_animation_frame(move || {
                     is_back.set(false);
                 });
+                router.inner.is_traversing.set(true);
                 if let Err(e) = router.inner.navigate_from_route(
                     &change.value,
                     &NavigateOptions {
                         resolve: false,
                         replace: change.replace,
                         scroll: change.scroll,
+                        scroll_behavior: None,
                         state: change.state,
                     },
                 ) {
@@ -123,8 +125,6 @@ impl History for BrowserIntegration {
                 .push_state_with_url(state, "", Some(&loc.value))
                 .unwrap_throw();
         }
-        // scroll to el
-        scroll_to_el(loc.scroll);
     }
 }
 
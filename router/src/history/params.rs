@@ -129,6 +129,73 @@ impl Params for () {
     }
 }
 
+/// A typed representation of the URL search query, which can be both parsed
+/// from the query and written back into it.
+///
+/// This is usually implemented with `#[derive(QueryParams)]`, which also
+/// derives [`Params`]. Each field is read from (and written to) the query key
+/// of the same name, unless it is renamed. The derive supports the following
+/// field attributes:
+/// - `#[query(rename = "q")]` uses a different query key for the field.
+/// - `#[query(default)]` or `#[query(default = expr)]` uses a default value
+///   when the key is missing, rather than failing to parse.
+/// - `#[query(with = path)]` parses and serializes the field with
+///   `path::parse(&str) -> Result<T, E>` and `path::to_string(&T) -> String`,
+///   instead of [`FromStr`] and [`ToString`].
+///
+/// `Option<_>` fields are `None` when the key is missing, and are left out of
+/// the query when they are `None`.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// mod comma_separated {
+///     use std::convert::Infallible;
+///
+///     pub fn parse(value: &str) -> Result<Vec<String>, Infallible> {
+///         Ok(value.split(',').map(String::from).collect())
+///     }
+///
+///     pub fn to_string(value: &[String]) -> String {
+///         value.join(",")
+///     }
+/// }
+///
+/// #[derive(Clone, PartialEq, QueryParams)]
+/// struct Search {
+///     #[query(rename = "q")]
+///     text: Option<String>,
+///     #[query(default = 1)]
+///     page: usize,
+///     #[query(default, with = comma_separated)]
+///     tags: Vec<String>,
+/// }
+///
+/// # #[cfg(feature = "ssr")] {
+/// let search = Search::from_map(&params_map! { "q" => "leptos" }).unwrap();
+/// assert_eq!(search.text.as_deref(), Some("leptos"));
+/// assert_eq!(search.page, 1);
+/// assert!(search.tags.is_empty());
+/// # }
+/// ```
+pub trait QueryParams: Params {
+    /// The query keys that are read and written by this type.
+    const KEYS: &'static [&'static str];
+
+    /// Serializes the value into a map of query keys and values.
+    fn to_map(&self) -> ParamsMap;
+}
+
+#[doc(hidden)]
+pub fn parse_query_value<T>(key: &str, value: &str) -> Result<T, ParamsError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    T::from_str(value).map_err(|e| ParamsError::invalid(key, value, e))
+}
+
 pub trait IntoParam
 where
     Self: Sized,
@@ -144,13 +211,13 @@ where
 {
     fn into_param(
         value: Option<&str>,
-        _name: &str,
+        name: &str,
     ) -> Result<Self, ParamsError> {
         match value {
             None => Ok(None),
             Some(value) => match T::from_str(value) {
                 Ok(value) => Ok(Some(value)),
-                Err(e) => Err(ParamsError::Params(Arc::new(e))),
+                Err(e) => Err(ParamsError::invalid(name, value, e)),
             },
         }
     }
@@ -168,7 +235,7 @@ cfg_if::cfg_if! {
         {
             fn into_param(value: Option<&str>, name: &str) -> Result<Self, ParamsError> {
                 let value = value.ok_or_else(|| ParamsError::MissingParam(name.to_string()))?;
-                Self::from_str(value).map_err(|e| ParamsError::Params(Arc::new(e)))
+                Self::from_str(value).map_err(|e| ParamsError::invalid(name, value, e))
             }
         }
     }
@@ -180,15 +247,62 @@ pub enum ParamsError {
     /// A field was missing from the route params.
     #[error("could not find parameter {0}")]
     MissingParam(String),
+    /// The value of a param could not be parsed into the type of its field.
+    #[error("could not parse parameter {name} from {value:?}: {source}")]
+    InvalidParam {
+        /// The name of the param.
+        name: String,
+        /// The value that could not be parsed.
+        value: String,
+        /// The error returned by the parser.
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
     /// Something went wrong while deserializing a field.
     #[error("failed to deserialize parameters")]
     Params(Arc<dyn std::error::Error + Send + Sync>),
 }
 
+impl ParamsError {
+    #[doc(hidden)]
+    pub fn invalid(
+        name: &str,
+        value: &str,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::InvalidParam {
+            name: name.to_string(),
+            value: value.to_string(),
+            source: Arc::new(source),
+        }
+    }
+
+    /// The name of the param that caused the error, if known.
+    pub fn param_name(&self) -> Option<&str> {
+        match self {
+            Self::MissingParam(name) | Self::InvalidParam { name, .. } => {
+                Some(name)
+            }
+            Self::Params(_) => None,
+        }
+    }
+}
+
 impl PartialEq for ParamsError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::MissingParam(l0), Self::MissingParam(r0)) => l0 == r0,
+            (
+                Self::InvalidParam {
+                    name: l_name,
+                    value: l_value,
+                    ..
+                },
+                Self::InvalidParam {
+                    name: r_name,
+                    value: r_value,
+                    ..
+                },
+            ) => l_name == r_name && l_value == r_value,
             (Self::Params(_), Self::Params(_)) => false,
             _ => false,
         }
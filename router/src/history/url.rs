@@ -28,10 +28,24 @@ pub fn unescape(s: &str) -> String {
     js_sys::decode_uri_component(s).unwrap().into()
 }
 
+// the characters left unescaped by `encodeURIComponent`, so that the same
+// URLs are generated on the server and in the browser
+#[cfg(feature = "ssr")]
+const URI_COMPONENT: &percent_encoding::AsciiSet =
+    &percent_encoding::NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'_')
+        .remove(b'.')
+        .remove(b'!')
+        .remove(b'~')
+        .remove(b'*')
+        .remove(b'\'')
+        .remove(b'(')
+        .remove(b')');
+
 #[cfg(feature = "ssr")]
 pub fn escape(s: &str) -> String {
-    percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC)
-        .to_string()
+    percent_encoding::utf8_percent_encode(s, URI_COMPONENT).to_string()
 }
 
 #[cfg(not(feature = "ssr"))]
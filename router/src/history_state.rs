@@ -0,0 +1,56 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+type Saver = Rc<dyn Fn() -> Option<String>>;
+
+/// The state that components have associated with each history entry with
+/// [`use_history_state`](crate::use_history_state), serialized as JSON and
+/// keyed by the URL of the entry.
+#[derive(Default)]
+pub(crate) struct HistoryStates {
+    // the saved state of each entry the user has left
+    entries: RefCell<HashMap<String, HashMap<String, String>>>,
+    // serializes the current value of each piece of state on the current page
+    savers: RefCell<HashMap<String, Saver>>,
+}
+
+impl HistoryStates {
+    /// Saves the current value of each registered piece of state for `url`,
+    /// which is about to be left.
+    pub fn save(&self, url: &str) {
+        let savers = self.savers.borrow();
+        if savers.is_empty() {
+            return;
+        }
+        let saved = savers
+            .iter()
+            .filter_map(|(key, save)| Some((key.clone(), save()?)))
+            .collect();
+        self.entries.borrow_mut().insert(url.to_string(), saved);
+    }
+
+    /// Forgets the state saved for `url`, which is being visited as a new
+    /// history entry rather than by going back or forward.
+    pub fn clear(&self, url: &str) {
+        self.entries.borrow_mut().remove(url);
+    }
+
+    /// Returns the state saved under `key` for `url`, if any.
+    pub fn get(&self, url: &str, key: &str) -> Option<String> {
+        self.entries.borrow().get(url)?.get(key).cloned()
+    }
+
+    pub fn register(&self, key: String, save: Saver) {
+        self.savers.borrow_mut().insert(key, save);
+    }
+
+    pub fn unregister(&self, key: &str, save: &Saver) {
+        let mut savers = self.savers.borrow_mut();
+        // a component for the next page may already have taken the key
+        if savers
+            .get(key)
+            .is_some_and(|current| Rc::ptr_eq(current, save))
+        {
+            savers.remove(key);
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
-    Location, NavigateOptions, Params, ParamsError, ParamsMap, RouteContext,
-    RouterContext,
+    breadcrumbs::breadcrumbs, components::LoaderData, resolve_path, Branches,
+    Breadcrumb, Location, NavigateOptions, Params, ParamsError, ParamsMap,
+    QueryParams, RouteContext, RouterContext,
 };
 use leptos::{
-    request_animation_frame, signal_prelude::*, use_context, window, Oco,
+    create_effect, leptos_dom::helpers::TimeoutHandle, on_cleanup,
+    request_animation_frame, set_timeout_with_handle, signal_prelude::*,
+    use_context, window, Oco, Resource,
 };
-use std::{rc::Rc, str::FromStr};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{borrow::Cow, cell::Cell, rc::Rc, str::FromStr, time::Duration};
 
 /// Constructs a signal synchronized with a specific URL query parameter.
 ///
@@ -94,6 +98,145 @@ where
     (get, set)
 }
 
+/// Options for a [`query_signal_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct QuerySignalOptions {
+    /// If `true`, writes replace the current history entry rather than pushing
+    /// a new one. (Defaults to `false`.)
+    pub replace: bool,
+    /// If set, writes only update the URL once the signal has not been written
+    /// to for this long, e.g., while the user is typing into a search field.
+    pub debounce: Option<Duration>,
+}
+
+/// Returns a signal kept in sync with the URL query parameter `key`.
+///
+/// Reading the signal returns the value of the parameter, parsed as `T`, or
+/// `T::default()` if it is missing or cannot be parsed. Setting the signal
+/// navigates to the current URL with the parameter updated, or removed if the
+/// value is the default, so that state like filters, sorting or the current
+/// page survives reloads and can be shared as a link. Navigating back and
+/// forward updates the signal in turn.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// pub fn Products() -> impl IntoView {
+///     let filter = query_signal::<String>("filter");
+///     let page = query_signal::<u32>("page");
+///
+///     view! {
+///         <input
+///             prop:value=filter
+///             on:input=move |ev| filter.set(event_target_value(&ev))
+///         />
+///         <button on:click=move |_| page.update(|page| *page += 1)>
+///             "Next page"
+///         </button>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn query_signal<T>(key: impl Into<Oco<'static, str>>) -> RwSignal<T>
+where
+    T: FromStr + ToString + PartialEq + Default + Clone,
+{
+    query_signal_with_options(key, QuerySignalOptions::default())
+}
+
+/// Returns a signal kept in sync with the URL query parameter `key`, with
+/// control over how the URL is updated. See [`query_signal`].
+#[track_caller]
+pub fn query_signal_with_options<T>(
+    key: impl Into<Oco<'static, str>>,
+    options: QuerySignalOptions,
+) -> RwSignal<T>
+where
+    T: FromStr + ToString + PartialEq + Default + Clone,
+{
+    let key: Oco<'static, str> = key.into();
+    let query_map = use_query_map();
+    let navigate = use_navigate();
+    let location = use_location();
+
+    let from_url = create_memo({
+        let key = key.clone();
+        move |_: Option<&T>| {
+            query_map.with(|map| {
+                map.get(&key)
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_default()
+            })
+        }
+    });
+    let signal = create_rw_signal(from_url.get_untracked());
+
+    // URL -> signal, when navigating back and forward
+    create_effect(move |_| {
+        let value = from_url.get();
+        if signal.with_untracked(|current| *current != value) {
+            signal.set(value);
+        }
+    });
+
+    // signal -> URL
+    let write = Rc::new(move || {
+        let Some(value) = signal.try_get_untracked() else {
+            return;
+        };
+        if from_url.with_untracked(|current| *current == value) {
+            return;
+        }
+        let mut new_query_map = query_map.get_untracked();
+        if value == T::default() {
+            new_query_map.remove(&key);
+        } else {
+            new_query_map.insert(key.to_string(), value.to_string());
+        }
+        let qs = new_query_map.to_query_string();
+        let path = location.pathname.get_untracked();
+        let hash = location.hash.get_untracked();
+        navigate(
+            &format!("{path}{qs}{hash}"),
+            NavigateOptions {
+                replace: options.replace,
+                scroll: false,
+                ..Default::default()
+            },
+        );
+    });
+    let pending = Rc::new(Cell::new(None::<TimeoutHandle>));
+    on_cleanup({
+        let pending = Rc::clone(&pending);
+        move || {
+            if let Some(handle) = pending.take() {
+                handle.clear();
+            }
+        }
+    });
+    create_effect(move |prev: Option<()>| {
+        signal.track();
+        if prev.is_none() {
+            return;
+        }
+        match options.debounce {
+            None => write(),
+            Some(delay) => {
+                if let Some(handle) = pending.take() {
+                    handle.clear();
+                }
+                let write = Rc::clone(&write);
+                pending
+                    .set(set_timeout_with_handle(move || write(), delay).ok());
+            }
+        }
+    });
+
+    signal
+}
+
 #[track_caller]
 pub(crate) fn has_router() -> bool {
     use_context::<RouterContext>().is_some()
@@ -129,6 +272,16 @@ pub fn use_route_data<T: Clone + 'static>() -> Option<T> {
     downcast
 }
 
+/// Returns the data from the [`RouteLoader`](crate::RouteLoader) of type `T`
+/// declared on the current route or any of its parent routes.
+///
+/// The data are loaded in a [`Resource`], so they should be read under
+/// `<Suspense/>` or `<Transition/>`.
+#[track_caller]
+pub fn use_loader<T: Clone + 'static>() -> Option<Resource<ParamsMap, T>> {
+    use_context::<LoaderData<T>>().map(|loader| loader.0)
+}
+
 /// Returns the current [`Location`], which contains reactive variables
 #[track_caller]
 pub fn use_location() -> Location {
@@ -152,6 +305,101 @@ where
     create_memo(move |_| route.params().with(T::from_map))
 }
 
+/// Returns the chain of routes matched by the current URL, from the outermost
+/// to the most deeply-nested, with the path, [title](crate::RouteTitle) and
+/// params of each. An index route is merged into its parent's crumb.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn Breadcrumbs() -> impl IntoView {
+///     let crumbs = use_breadcrumbs();
+///     view! {
+///       <nav>
+///         <For
+///           each=move || crumbs.get()
+///           key=|crumb| crumb.path.clone()
+///           let:crumb
+///         >
+///           <A href=crumb.path>{crumb.title}</A>
+///         </For>
+///       </nav>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_breadcrumbs() -> Memo<Vec<Breadcrumb>> {
+    let router = use_router();
+    let router_id = router.id();
+    let pathname = router.pathname();
+    create_memo(move |_| {
+        let mut crumbs = Vec::new();
+        pathname.with(|path| {
+            Branches::for_each_match(router_id, path, |matches| {
+                if crumbs.is_empty() {
+                    crumbs = breadcrumbs(matches);
+                }
+            })
+        });
+        crumbs
+    })
+}
+
+/// Returns a signal for a piece of UI state, like the selected tab or
+/// whether an accordion is open, that belongs to the current history entry.
+///
+/// When the user navigates away, the current value is saved with the entry
+/// they are leaving. If they come back to it with the back or forward
+/// buttons, the signal starts with the saved value rather than `default`.
+/// A new visit to the same URL starts with `default` again.
+///
+/// `key` identifies the state within the page, so it should be unique among
+/// the components rendered at the same time.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn Tabs() -> impl IntoView {
+///     let tab = use_history_state("tab", || 0usize);
+///     view! {
+///       <button on:click=move |_| tab.set(0)>"Overview"</button>
+///       <button on:click=move |_| tab.set(1)>"Details"</button>
+///       <p>"Tab " {tab}</p>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_history_state<T>(
+    key: impl Into<String>,
+    default: impl FnOnce() -> T,
+) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let key = key.into();
+    let router = use_router();
+    let states = &router.inner.history_states;
+
+    let saved = states
+        .get(&router.inner.reference.get_untracked(), &key)
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let signal = create_rw_signal(saved.unwrap_or_else(default));
+
+    let save: Rc<dyn Fn() -> Option<String>> = Rc::new(move || {
+        signal
+            .try_with_untracked(|value| serde_json::to_string(value).ok())
+            .flatten()
+    });
+    states.register(key.clone(), Rc::clone(&save));
+    on_cleanup(move || router.inner.history_states.unregister(&key, &save));
+
+    signal
+}
+
 /// Returns a raw key-value map of the URL search query.
 #[track_caller]
 pub fn use_query_map() -> Memo<ParamsMap> {
@@ -168,17 +416,83 @@ where
     create_memo(move |_| router.inner.location.query.with(|m| T::from_map(m)))
 }
 
+/// Returns a function that writes the given [`QueryParams`] into the URL
+/// search query, navigating to the updated URL.
+///
+/// Only the keys owned by `T` (see [`QueryParams::KEYS`]) are changed: any
+/// other keys in the current query, and the path and hash, are kept as they
+/// are. Set [`replace`](NavigateOptions::replace) in the [`NavigateOptions`] to replace the current
+/// history entry rather than pushing a new one. Together with
+/// [`use_query::<T>`](use_query), this gives typed, reactive read and write
+/// access to the query.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[derive(Clone, PartialEq, QueryParams)]
+/// struct Pagination {
+///     #[query(default = 1)]
+///     page: usize,
+/// }
+///
+/// #[component]
+/// fn Pager() -> impl IntoView {
+///     let query = use_query::<Pagination>();
+///     let set_query = use_set_query::<Pagination>();
+///     let page = move || query.with(|q| q.as_ref().map(|q| q.page).unwrap_or(1));
+///
+///     view! {
+///       <button on:click=move |_| {
+///           set_query(
+///               Pagination { page: page() + 1 },
+///               NavigateOptions::default().replace(true).scroll(false),
+///           )
+///       }>
+///         "Next page"
+///       </button>
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_set_query<T>() -> impl Fn(T, NavigateOptions) + Clone
+where
+    T: QueryParams,
+{
+    let query_map = use_query_map();
+    let navigate = use_navigate();
+    let location = use_location();
+
+    move |value: T, options| {
+        let mut new_query_map = query_map.get_untracked();
+        for key in T::KEYS {
+            new_query_map.remove(key);
+        }
+        for (key, value) in value.to_map().0 {
+            new_query_map.insert(key, value);
+        }
+        let qs = new_query_map.to_query_string();
+        let path = location.pathname.get_untracked();
+        let hash = location.hash.get_untracked();
+        let new_url = format!("{path}{qs}{hash}");
+        navigate(&new_url, options);
+    }
+}
+
 /// Resolves the given path relative to the current route.
+///
+/// Absolute paths (beginning with `/`) are resolved relative to the `base`
+/// of the [`Router`](crate::Router), if any.
 #[track_caller]
 pub fn use_resolved_path(
     path: impl Fn() -> String + 'static,
 ) -> Memo<Option<String>> {
     let route = use_route();
+    let base_path = use_router().inner.base_path.clone();
 
     create_memo(move |_| {
         let path = path();
         if path.starts_with('/') {
-            Some(path)
+            resolve_path(&base_path, &path, None).map(Cow::into_owned)
         } else {
             route.resolve_path_tracked(&path)
         }
@@ -0,0 +1,175 @@
+use crate::{
+    join_paths, Branches, Method, ParamsMap, RouteDefinition, RouterContext,
+    SsrMode, StaticMode,
+};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+/// A serializable snapshot of the state of a [`Router`](crate::Router),
+/// returned by [`RouterContext::inspect`].
+///
+/// This is meant as the backend for developer tools, and for tests that
+/// assert on routing behavior.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterSnapshot {
+    /// The base path of the router.
+    pub base: String,
+    /// Every route registered with the router's `<Routes/>`, as a tree.
+    pub routes: Vec<RouteNode>,
+    /// The current location.
+    pub location: LocationSnapshot,
+    /// The routes matched by the current location, from the outermost to the
+    /// most deeply-nested.
+    pub matched: Vec<MatchedRoute>,
+    /// The navigation that is currently in progress, if any.
+    pub pending: Option<PendingNavigation>,
+    /// The number of route transitions (see
+    /// [`TransitionOutlet`](crate::TransitionOutlet)) still animating.
+    pub transitions: usize,
+}
+
+/// A route registered with the router. See [`RouterSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteNode {
+    /// The unique ID of the route.
+    pub id: usize,
+    /// The path fragment of the route, as it was defined.
+    pub path: String,
+    /// The full path pattern of the route, including its parents' paths.
+    pub pattern: String,
+    /// The HTTP methods the route can handle.
+    pub methods: Vec<Method>,
+    /// The mode the route prefers during server-side rendering.
+    pub ssr_mode: SsrMode,
+    /// The route's mode of static generation, if any.
+    pub static_mode: Option<StaticMode>,
+    /// Whether the route's view is loaded lazily.
+    pub lazy: bool,
+    /// Whether the route has a title.
+    pub has_title: bool,
+    /// The routes nested inside this one.
+    pub children: Vec<RouteNode>,
+}
+
+impl RouteNode {
+    fn new(def: &RouteDefinition, parent_pattern: &str) -> Self {
+        let pattern = join_paths(parent_pattern, &def.path);
+        Self {
+            id: def.id,
+            path: def.path.clone(),
+            children: def
+                .children
+                .iter()
+                .map(|child| RouteNode::new(child, &pattern))
+                .collect(),
+            pattern,
+            methods: def.methods.to_vec(),
+            ssr_mode: def.ssr_mode,
+            static_mode: def.static_mode,
+            lazy: def.lazy.is_some(),
+            has_title: def.title.is_some(),
+        }
+    }
+}
+
+/// The current location of the router. See [`RouterSnapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationSnapshot {
+    /// The path of the URL.
+    pub pathname: String,
+    /// The raw query string.
+    pub search: String,
+    /// The query string parsed into its key-value pairs.
+    pub query: ParamsMap,
+    /// The hash fragment.
+    pub hash: String,
+}
+
+/// A route matched by the current location. See [`RouterSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchedRoute {
+    /// The ID of the route, which is the same as the ID of its [`RouteNode`].
+    pub id: usize,
+    /// The full path pattern of the route.
+    pub pattern: String,
+    /// The part of the current URL matched by this route.
+    pub path: String,
+    /// The params matched by this route and its parents.
+    pub params: ParamsMap,
+    /// The title of the route, if it has one.
+    pub title: Option<String>,
+}
+
+/// A navigation that has started but not yet finished, for example because
+/// it is waiting on resources to load. See [`RouterSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingNavigation {
+    /// The URL the navigation started from.
+    pub from: String,
+    /// The URL being navigated to.
+    pub to: String,
+    /// The number of redirects followed so far.
+    pub redirects: usize,
+}
+
+impl RouterContext {
+    /// Returns a serializable snapshot of the state of the router: the route
+    /// tree, the current location and matched routes, and any pending navigation.
+    ///
+    /// The location and navigation state are read reactively, so calling this in
+    /// a memo or effect will rerun it when they change.
+    pub fn inspect(&self) -> RouterSnapshot {
+        let inner = &self.inner;
+        let location = &inner.location;
+        let pathname = location.pathname.get();
+
+        let mut routes = Vec::new();
+        Branches::for_each_definition(self.id(), |base, defs| {
+            routes.extend(defs.iter().map(|def| RouteNode::new(def, base)));
+        });
+
+        let mut matched = Vec::new();
+        Branches::for_each_match(self.id(), &pathname, |matches| {
+            if matched.is_empty() {
+                matched = matches
+                    .iter()
+                    .map(|m| MatchedRoute {
+                        id: m.route.id,
+                        pattern: m.route.pattern.clone(),
+                        path: m.path_match.path.clone(),
+                        params: m.path_match.params.clone(),
+                        title: m
+                            .route
+                            .key
+                            .title
+                            .as_ref()
+                            .map(|title| title.get(&m.path_match.params)),
+                    })
+                    .collect();
+            }
+        });
+
+        let pending = {
+            let referrers = inner.referrers.borrow();
+            referrers.first().map(|first| PendingNavigation {
+                from: first.value.clone(),
+                to: inner.reference.get(),
+                redirects: referrers.len() - 1,
+            })
+        };
+
+        RouterSnapshot {
+            base: inner.base_path.clone(),
+            routes,
+            location: LocationSnapshot {
+                pathname,
+                search: location.search.get(),
+                query: location.query.get(),
+                hash: location.hash.get(),
+            },
+            matched,
+            pending,
+            transitions: inner.transitions.get(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! The logic shared by the server integrations (like `leptos_axum` and
+//! `leptos_actix`), from which an integration for another server, like
+//! `hyper` used directly, `rocket`, or a custom server, can be built.
+//!
+//! An integration has four parts:
+//! 1. **Routes**: [`generate_route_list_with_format`] walks the app's route
+//!    tree and returns a [`RouteListing`] for each route, with its path in
+//!    the syntax of the server's router, so that each can be registered with
+//!    a handler for its [`SsrMode`] and [`methods`](RouteListing::methods).
+//! 2. **Context**: each page is rendered in a new reactive runtime, in which
+//!    [`provide_server_contexts`] provides the context the app expects, like
+//!    its [`RouterIntegrationContext`] and `MetaContext`. The parts that
+//!    depend on the server, like providing its request type and setting the
+//!    status code of the response, are supplied by a [`ServerAdapter`].
+//! 3. **Streaming**: the app is rendered with one of the `render_to_stream_*`
+//!    functions of `leptos::ssr`, and the stream they return is turned into a
+//!    whole HTML document by `leptos_integration_utils::html_stream` (or
+//!    `leptos_integration_utils::build_async_response`, for
+//!    [`SsrMode::Async`]). These replace the page with the app's
+//!    [error pages](leptos::error_pages), if it sets them, when the URL does
+//!    not match any route or the shell renders errors that are not caught by
+//!    an `<ErrorBoundary/>`.
+//! 4. **Server functions**: the server function registered for a request is
+//!    looked up with the `get_server_fn_service` function of the `server_fn`
+//!    integration for the server's request type, and called with
+//!    [`run_server_fn`]. WebSocket server functions only run once their
+//!    connection is open, after the handler has returned, and are run with
+//!    [`run_in_new_runtime`] so that they have the same context.
+//!
+//! ```rust,ignore
+//! struct MyAdapter {
+//!     req: MyRequest,
+//!     res: MyResponseOptions,
+//! }
+//!
+//! impl ServerAdapter for MyAdapter {
+//!     fn provide_contexts(&self) {
+//!         provide_context(self.req.clone());
+//!         provide_context(self.res.clone());
+//!     }
+//!
+//!     fn set_status(&self, status: u16) {
+//!         self.res.set_status(status);
+//!     }
+//!
+//!     fn redirect(&self, path: &str) {
+//!         self.res.set_status(302);
+//!         self.res.insert_header("Location", path);
+//!     }
+//! }
+//!
+//! async fn render_page(req: MyRequest, options: &LeptosOptions) -> MyResponse {
+//!     let res = MyResponseOptions::default();
+//!     let adapter = MyAdapter { req: req.clone(), res: res.clone() };
+//!     let (stream, runtime) =
+//!         leptos::ssr::render_to_stream_with_prefix_undisposed_with_context(
+//!             App.into_view(),
+//!             |_| generate_head_metadata_separated().1.into(),
+//!             move || provide_server_contexts(req.url(), adapter),
+//!         );
+//!     let html = html_stream(options, stream, runtime, sleep).await;
+//!     // the shell has been rendered, so its status and headers are set
+//!     res.into_response(html)
+//! }
+//! ```
+
+use crate::{
+    provide_server_redirect, provide_server_status, RouteListing,
+    RouterIntegrationContext, ServerIntegration, StaticDataMap,
+};
+use leptos::*;
+use leptos_meta::MetaContext;
+use std::{future::Future, pin::pin, rc::Rc};
+
+/// The parts of rendering a page that depend on the server, which an
+/// integration implements for each request.
+pub trait ServerAdapter: 'static {
+    /// Provides the server's own types as context, like its request and the
+    /// options of the response, so that the app can use them.
+    fn provide_contexts(&self);
+
+    /// Sets the status code of the response.
+    fn set_status(&self, status: u16);
+
+    /// Redirects the client to the given path, as
+    /// [`<Redirect/>`](crate::Redirect) does while rendering.
+    fn redirect(&self, path: &str);
+}
+
+/// Provides the context that the app expects when it is rendered on the
+/// server for a request for `path`:
+/// - [`RouterIntegrationContext`]
+/// - [`MetaContext`]
+/// - the [status](provide_server_status) and
+///   [redirect](provide_server_redirect) functions, which call the adapter
+/// - the state in which the app sets its
+///   [`ErrorPages`](leptos::error_pages::ErrorPages)
+/// - [`Nonce`](leptos::nonce::Nonce), with the `nonce` feature
+/// - the types provided by [`ServerAdapter::provide_contexts`]
+pub fn provide_server_contexts(
+    path: impl ToString,
+    adapter: impl ServerAdapter,
+) {
+    let integration = ServerIntegration {
+        path: path.to_string(),
+    };
+    provide_context(RouterIntegrationContext::new(integration));
+    provide_context(MetaContext::new());
+    adapter.provide_contexts();
+    let adapter = Rc::new(adapter);
+    provide_server_redirect({
+        let adapter = Rc::clone(&adapter);
+        move |path| adapter.redirect(path)
+    });
+    provide_server_status({
+        let adapter = Rc::clone(&adapter);
+        move |status| adapter.set_status(status)
+    });
+    leptos::error_pages::provide_error_pages_state(move |status| {
+        adapter.set_status(status)
+    });
+    #[cfg(feature = "nonce")]
+    leptos::nonce::provide_nonce();
+}
+
+/// Generates the list of routes in the app, with each path converted to the
+/// syntax of the server's router by `format_path`.
+///
+/// The root route is always `/`, and the list has only that route if the app
+/// has no router. `excluded_routes`, in the server's syntax, are removed from
+/// the list, so that they can be given a custom handler.
+pub fn generate_route_list_with_format<IV>(
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    excluded_routes: Option<Vec<String>>,
+    additional_context: impl Fn() + 'static + Clone,
+    format_path: impl Fn(&str) -> String,
+) -> (Vec<RouteListing>, StaticDataMap)
+where
+    IV: IntoView + 'static,
+{
+    let (routes, static_data_map) =
+        crate::generate_route_list_inner_with_context(
+            app_fn,
+            additional_context,
+        );
+    if routes.is_empty() {
+        let root = RouteListing::new(
+            "/",
+            "",
+            Default::default(),
+            [crate::Method::Get],
+            None,
+        );
+        return (vec![root], static_data_map);
+    }
+    let mut routes = routes
+        .into_iter()
+        .map(|listing| {
+            let path = match listing.path() {
+                "" => "/".to_string(),
+                path => format_path(path),
+            };
+            RouteListing::new(
+                path,
+                listing.leptos_path(),
+                listing.mode(),
+                listing.methods(),
+                listing.static_mode(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if let Some(excluded_routes) = excluded_routes {
+        routes.retain(|p| !excluded_routes.iter().any(|e| e == p.path()));
+    }
+    (routes, static_data_map)
+}
+
+/// Runs a server function in a new reactive runtime, in which
+/// `additional_context` is called first, and then `run`, which can provide
+/// the server's own types as context before returning the future that calls
+/// the server function.
+///
+/// The runtime is disposed of once the future has completed, so it should
+/// also apply any changes the server function made to the response options.
+pub async fn run_server_fn<Fut>(
+    additional_context: impl Fn(),
+    run: impl FnOnce() -> Fut,
+) -> Fut::Output
+where
+    Fut: Future,
+{
+    let runtime = create_runtime();
+    additional_context();
+    let output = run().await;
+    runtime.dispose();
+    output
+}
+
+/// Runs a future that outlives the handler of the request that started it,
+/// like a WebSocket server function once its connection is open, in a new
+/// reactive runtime in which `provide_contexts` is called first.
+///
+/// The runtime is made current each time the future is polled, as other
+/// requests may be handled on the same thread in between, and is disposed of
+/// once the future has completed or been dropped.
+pub async fn run_in_new_runtime<Fut>(
+    provide_contexts: impl FnOnce(),
+    fut: Fut,
+) -> Fut::Output
+where
+    Fut: Future,
+{
+    struct DisposeOnDrop(RuntimeId);
+
+    impl Drop for DisposeOnDrop {
+        fn drop(&mut self) {
+            self.0.dispose();
+        }
+    }
+
+    let runtime = create_runtime();
+    let _runtime = DisposeOnDrop(runtime);
+    provide_contexts();
+    let mut fut = pin!(fut);
+    std::future::poll_fn(move |cx| {
+        set_current_runtime(runtime);
+        fut.as_mut().poll(cx)
+    })
+    .await
+}
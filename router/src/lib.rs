@@ -177,6 +177,8 @@
 //! - `ssr` Server-side rendering: Generate an HTML string (typically on the server)
 //! - `hydrate` Hydration: use this to add interactivity to an SSRed Leptos app
 //! - `nightly`: On `nightly` Rust, enables the function-call syntax for signal getters and setters.
+//! - `meta`: Applies the [`RouteMeta`] of matched routes and provides `CanonicalUrl`, through
+//!   `leptos_meta`.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in.
@@ -190,19 +192,52 @@
 #![allow(stable_features)]
 
 mod animation;
+mod breadcrumbs;
+#[cfg(feature = "meta")]
+mod canonical;
 mod components;
 #[cfg(any(feature = "ssr", doc))]
 mod extract_routes;
 mod history;
+mod history_state;
 mod hooks;
+mod inspect;
+// provides `MetaContext`, so needs `leptos_meta` even for docs
+#[cfg(any(feature = "ssr", all(doc, feature = "meta")))]
+mod integration;
 #[doc(hidden)]
 pub mod matching;
+mod params_guard;
+#[cfg(feature = "ssr")]
+pub mod prerender;
 mod render_mode;
+mod route_meta;
+mod scroll;
+#[cfg(feature = "ssr")]
+pub mod sitemap;
+mod typed_route;
+pub use breadcrumbs::*;
+#[cfg(feature = "meta")]
+pub use canonical::*;
 pub use components::*;
 #[cfg(any(feature = "ssr", doc))]
 pub use extract_routes::*;
 pub use history::*;
 pub use hooks::*;
+pub use inspect::*;
+#[cfg(any(feature = "ssr", all(doc, feature = "meta")))]
+pub use integration::*;
 pub use matching::{RouteDefinition, *};
+pub use params_guard::*;
+#[cfg(feature = "ssr")]
+pub use prerender::{
+    prerender, prerender_routes, prerender_routes_with_additional_context,
+    prerender_with_additional_context,
+};
 pub use render_mode::*;
+pub use route_meta::RouteMeta;
+pub use scroll::*;
+#[cfg(feature = "ssr")]
+pub use sitemap::{generate_sitemap, generate_sitemap_with_additional_context};
+pub use typed_route::*;
 extern crate tracing;
@@ -14,11 +14,147 @@ pub struct PathMatch {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Matcher {
     splat: Option<String>,
-    segments: Vec<String>,
+    segments: Vec<Segment>,
+    suffix: Vec<Segment>,
     len: usize,
     partial: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param {
+        name: String,
+        constraint: Option<ParamConstraint>,
+    },
+}
+
+/// A type that a path param must parse as, written `:id<u64>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParamConstraint {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    F32,
+    F64,
+    Bool,
+    Char,
+}
+
+impl ParamConstraint {
+    fn parse(ty: &str) -> Option<Self> {
+        Some(match ty {
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u128" => Self::U128,
+            "usize" => Self::Usize,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "i128" => Self::I128,
+            "isize" => Self::Isize,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            "bool" => Self::Bool,
+            "char" => Self::Char,
+            _ => return None,
+        })
+    }
+
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::U8 => value.parse::<u8>().is_ok(),
+            Self::U16 => value.parse::<u16>().is_ok(),
+            Self::U32 => value.parse::<u32>().is_ok(),
+            Self::U64 => value.parse::<u64>().is_ok(),
+            Self::U128 => value.parse::<u128>().is_ok(),
+            Self::Usize => value.parse::<usize>().is_ok(),
+            Self::I8 => value.parse::<i8>().is_ok(),
+            Self::I16 => value.parse::<i16>().is_ok(),
+            Self::I32 => value.parse::<i32>().is_ok(),
+            Self::I64 => value.parse::<i64>().is_ok(),
+            Self::I128 => value.parse::<i128>().is_ok(),
+            Self::Isize => value.parse::<isize>().is_ok(),
+            Self::F32 => value.parse::<f32>().is_ok(),
+            Self::F64 => value.parse::<f64>().is_ok(),
+            Self::Bool => value.parse::<bool>().is_ok(),
+            Self::Char => value.parse::<char>().is_ok(),
+        }
+    }
+}
+
+impl Segment {
+    fn parse(segment: &str, path: &str) -> Self {
+        let Some(param) = segment.strip_prefix(':') else {
+            return Self::Static(segment.to_string());
+        };
+        let (name, constraint) = split_constraint(param);
+        let constraint = constraint.map(|ty| {
+            ParamConstraint::parse(ty).unwrap_or_else(|| {
+                panic!(
+                    "unknown constraint `<{ty}>` on param `{name}` in route \
+                     path `{path}`"
+                )
+            })
+        });
+        Self::Param {
+            name: name.to_string(),
+            constraint,
+        }
+    }
+
+    // returns the value of the param, if this segment is a param
+    fn test(&self, loc_segment: &str) -> Result<Option<String>, ()> {
+        match self {
+            Self::Static(segment) if segment == loc_segment => Ok(None),
+            Self::Static(_) => Err(()),
+            Self::Param { constraint, .. } => {
+                let value = unescape(loc_segment);
+                match constraint {
+                    Some(constraint) if !constraint.accepts(&value) => Err(()),
+                    _ => Ok(Some(value)),
+                }
+            }
+        }
+    }
+
+    fn same_shape(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => a == b,
+            (
+                Self::Param { constraint: a, .. },
+                Self::Param { constraint: b, .. },
+            ) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Splits a param like `id<u64>` into its name and the type it is
+/// constrained to, if any.
+#[doc(hidden)]
+pub fn split_constraint(param: &str) -> (&str, Option<&str>) {
+    match param
+        .strip_suffix('>')
+        .and_then(|param| param.split_once('<'))
+    {
+        Some((name, ty)) => (name, Some(ty.trim())),
+        None => (param, None),
+    }
+}
+
 impl Matcher {
     #[doc(hidden)]
     pub fn new(path: &str) -> Self {
@@ -27,15 +163,26 @@ impl Matcher {
 
     #[doc(hidden)]
     pub fn new_with_partial(path: &str, partial: bool) -> Self {
-        let (pattern, splat) = match path.split_once("/*") {
-            Some((p, s)) => (p, Some(s.to_string())),
-            None => (path, None),
+        let (pattern, splat, suffix) = match path.split_once("/*") {
+            Some((p, s)) => match s.split_once('/') {
+                Some((s, suffix)) => (p, Some(s.to_string()), suffix),
+                None => (p, Some(s.to_string()), ""),
+            },
+            None => (path, None, ""),
         };
-        let segments: Vec<String> = get_segments(pattern);
+        let segments: Vec<Segment> = get_segments::<&str>(pattern)
+            .into_iter()
+            .map(|segment| Segment::parse(segment, path))
+            .collect();
+        let suffix: Vec<Segment> = get_segments::<&str>(suffix)
+            .into_iter()
+            .map(|segment| Segment::parse(segment, path))
+            .collect();
         let len = segments.len();
         Self {
             splat,
             segments,
+            suffix,
             len,
             partial,
         }
@@ -53,9 +200,9 @@ impl Matcher {
         // quick path: not a match if
         // 1) matcher has add'l segments not found in location
         // 2) location has add'l segments, there's no splat, and partial matches not allowed
-        if loc_len < self.len
+        if loc_len < self.len + self.suffix.len()
             || (len_diff > 0 && self.splat.is_none() && !self.partial)
-            || (self.splat.is_none() && trailing_iter.clone().count() > 1)
+            || (!self.is_wildcard() && trailing_iter.clone().count() > 1)
         {
             None
         }
@@ -67,17 +214,21 @@ impl Matcher {
             for (segment, loc_segment) in
                 self.segments.iter().zip(loc_segments.iter())
             {
-                if let Some(param_name) = segment.strip_prefix(':') {
-                    params.insert(param_name.into(), unescape(loc_segment));
-                } else if segment != loc_segment {
-                    // if any segment doesn't match and isn't a param, there's no path match
-                    return None;
+                // if any segment doesn't match and isn't a param, there's no path match
+                if let Some(value) = segment.test(loc_segment).ok()? {
+                    if let Segment::Param { name, .. } = segment {
+                        params.insert(name.clone(), value);
+                    }
                 }
 
                 path.push('/');
                 path.push_str(loc_segment);
             }
 
+            if !self.suffix.is_empty() {
+                return self.test_suffix(&loc_segments, path, params);
+            }
+
             if let Some(splat) = &self.splat {
                 if !splat.is_empty() {
                     let mut value = if len_diff > 0 {
@@ -99,9 +250,92 @@ impl Matcher {
         }
     }
 
+    // matches a wildcard that is followed by more segments, like
+    // `/files/*path/edit`: the wildcard takes as few segments as it can
+    fn test_suffix(
+        &self,
+        loc_segments: &[&str],
+        mut path: String,
+        mut params: ParamsMap,
+    ) -> Option<PathMatch> {
+        let last_start = loc_segments.len() - self.suffix.len();
+        let first_start = if self.partial { self.len } else { last_start };
+        (first_start..=last_start).find_map(|start| {
+            let rest = &loc_segments[start..start + self.suffix.len()];
+            let mut suffix_params = Vec::new();
+            for (segment, loc_segment) in self.suffix.iter().zip(rest) {
+                if let Some(value) = segment.test(loc_segment).ok()? {
+                    if let Segment::Param { name, .. } = segment {
+                        suffix_params.push((name.clone(), value));
+                    }
+                }
+            }
+
+            let splat = self.splat.as_deref().unwrap_or_default();
+            if !splat.is_empty() {
+                params.insert(
+                    splat.to_string(),
+                    loc_segments[self.len..start].join("/"),
+                );
+            }
+            for (name, value) in suffix_params {
+                params.insert(name, value);
+            }
+            for loc_segment in &loc_segments[self.len..start + rest.len()] {
+                path.push('/');
+                path.push_str(loc_segment);
+            }
+            Some(PathMatch {
+                path: std::mem::take(&mut path),
+                params: std::mem::take(&mut params),
+            })
+        })
+    }
+
+    /// Whether the matcher ends in a wildcard.
     #[doc(hidden)]
     pub(crate) fn is_wildcard(&self) -> bool {
-        self.splat.is_some()
+        self.splat.is_some() && self.suffix.is_empty()
+    }
+
+    /// Whether this matcher and `other` match exactly the same locations,
+    /// differing at most in the names of their params.
+    pub(crate) fn is_ambiguous_with(&self, other: &Matcher) -> bool {
+        fn same_shape(a: &[Segment], b: &[Segment]) -> bool {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.same_shape(b))
+        }
+
+        self.partial == other.partial
+            && self.splat.is_some() == other.splat.is_some()
+            && same_shape(&self.segments, &other.segments)
+            && same_shape(&self.suffix, &other.suffix)
+    }
+
+    /// How specific this matcher is: static segments are more specific than
+    /// constrained params, which are more specific than plain params.
+    pub(crate) fn score(&self) -> i32 {
+        // a trailing slash doesn't make a path any more specific
+        let segments = self
+            .segments
+            .iter()
+            .chain(&self.suffix)
+            .filter(|segment| !matches!(segment, Segment::Static(s) if s.is_empty()))
+            .collect::<Vec<_>>();
+        let len = segments.len() as i32;
+        segments.into_iter().fold(
+            2 * len - if self.splat.is_none() { 0 } else { 2 },
+            |score, segment| {
+                score
+                    + match segment {
+                        Segment::Static(_) => 6,
+                        Segment::Param {
+                            constraint: Some(_),
+                            ..
+                        } => 5,
+                        Segment::Param { .. } => 4,
+                    }
+            },
+        )
     }
 }
 
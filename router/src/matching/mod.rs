@@ -25,18 +25,18 @@ pub(crate) fn get_route_matches(
     {
         use lru::LruCache;
         use std::{cell::RefCell, num::NonZeroUsize};
-        type RouteMatchCache = LruCache<(usize, String), Rc<Vec<RouteMatch>>>;
+        type RouteMatchCache =
+            LruCache<(usize, String, String), Rc<Vec<RouteMatch>>>;
         thread_local! {
             static ROUTE_MATCH_CACHE: RefCell<RouteMatchCache> = RefCell::new(LruCache::new(NonZeroUsize::new(32).unwrap()));
         }
 
         ROUTE_MATCH_CACHE.with(|cache| {
             let mut cache = cache.borrow_mut();
-            Rc::clone(
-                cache.get_or_insert((router_id, location.clone()), || {
-                    build_route_matches(router_id, base, location)
-                }),
-            )
+            Rc::clone(cache.get_or_insert(
+                (router_id, base.to_string(), location.clone()),
+                || build_route_matches(router_id, base, location),
+            ))
         })
     }
 
@@ -69,7 +69,10 @@ pub struct Branch {
 }
 
 impl Branch {
-    fn matcher<'a>(&'a self, location: &'a str) -> Option<Vec<RouteMatch>> {
+    pub(crate) fn matcher<'a>(
+        &'a self,
+        location: &'a str,
+    ) -> Option<Vec<RouteMatch>> {
         let mut matches = Vec::new();
         for route in self.routes.iter().rev() {
             match route.matcher.test(location) {
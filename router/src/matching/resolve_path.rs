@@ -77,7 +77,9 @@ fn begins_with_query_or_hash(text: &str) -> bool {
 }
 
 fn remove_wildcard(text: &str) -> String {
-    text.rsplit_once('*')
+    // only a trailing wildcard is removed: `/files/*path/edit` is kept as-is
+    text.rsplit_once("/*")
+        .filter(|(_, splat)| !splat.contains('/'))
         .map(|(prefix, _)| prefix)
         .unwrap_or(text)
         .trim_end_matches('/')
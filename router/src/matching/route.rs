@@ -1,4 +1,8 @@
-use crate::{Loader, Method, SsrMode, StaticData, StaticMode, TrailingSlash};
+use crate::{
+    LazyView, Loader, Method, NamedViews, ParamsGuard, RouteErrorView,
+    RouteLoader, RouteMeta, RouteTitle, ScrollBehavior, SsrMode, StaticData,
+    StaticMode, TrailingSlash,
+};
 use leptos::leptos_dom::View;
 use std::rc::Rc;
 
@@ -21,12 +25,40 @@ pub struct RouteDefinition {
     pub methods: &'static [Method],
     /// A data loader function that will be called when this route is matched.
     pub data: Option<Loader>,
+    /// Async data loaders that will begin loading when this route is matched.
+    pub loaders: Vec<RouteLoader>,
+    /// Views this route provides for the named outlets of its parent route.
+    pub named_views: NamedViews,
+    /// How the router should update the scroll position after navigating to this route.
+    pub scroll_behavior: Option<ScrollBehavior>,
+    /// The title of this route, used in [`Breadcrumb`](crate::Breadcrumb)s.
+    pub title: Option<RouteTitle>,
+    /// The document metadata applied while this route is matched.
+    pub meta: Option<RouteMeta>,
+    /// Checks the params of this route before its view is rendered.
+    pub params: Option<ParamsGuard>,
+    /// Rendered in place of this route's view when an error is rendered within it.
+    pub error_view: Option<RouteErrorView>,
+    /// The lazily-loaded view of this route, if any, which can be preloaded.
+    pub lazy: Option<LazyView>,
     /// The route's preferred mode of static generation, if any
     pub static_mode: Option<StaticMode>,
     /// The data required to fill any dynamic segments in the path during static rendering.
     pub static_params: Option<StaticData>,
     /// How a trailng slash in `path` should be handled.
     pub trailing_slash: Option<TrailingSlash>,
+    /// Whether the pages of this route, and of the routes nested in it, are
+    /// only rendered on the server, without being hydrated.
+    pub server_only: bool,
+}
+
+impl RouteDefinition {
+    /// Adds an async data loader that will begin loading when this route is
+    /// matched, like those in the `loaders` prop of [`<Route/>`](crate::Route).
+    pub fn loader(mut self, loader: RouteLoader) -> Self {
+        self.loaders.push(loader);
+        self
+    }
 }
 
 impl core::fmt::Debug for RouteDefinition {
@@ -37,6 +69,7 @@ impl core::fmt::Debug for RouteDefinition {
             .field("ssr_mode", &self.ssr_mode)
             .field("static_render", &self.static_mode)
             .field("trailing_slash", &self.trailing_slash)
+            .field("server_only", &self.server_only)
             .finish()
     }
 }
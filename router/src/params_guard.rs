@@ -0,0 +1,98 @@
+use crate::{Params, ParamsError, ParamsMap};
+use leptos::{use_context, IntoView, View};
+use std::rc::Rc;
+
+type ParamsCheck = dyn Fn(&ParamsMap) -> Result<(), ParamsError>;
+
+/// The view the [`<Router/>`](crate::Router) shows by default when the params
+/// of a route do not pass its [`ParamsGuard`].
+#[derive(Clone, Copy)]
+pub(crate) struct BadRequestView(pub fn(ParamsError) -> View);
+
+/// Checks that the params of a route can be parsed into a [`Params`] type
+/// before the route's view is rendered, set with the `params` prop of a
+/// [`<Route/>`](crate::Route).
+///
+/// If a param is missing or cannot be parsed, the route renders a "bad
+/// request" view for the [`ParamsError`] instead, which names the param that
+/// failed. This is the guard's own view if it has one, or else the
+/// `bad_request` view of the [`<Router/>`](crate::Router), or else the error
+/// message. The view inside the route can then call
+/// [`use_params`](crate::use_params) and unwrap the result.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[derive(Params, Clone, PartialEq)]
+/// struct UserParams {
+///     id: Option<usize>,
+/// }
+///
+/// #[component]
+/// fn User() -> impl IntoView {
+///     let params = use_params::<UserParams>();
+///     move || params.get().map(|params| params.id)
+/// }
+///
+/// # if false {
+/// view! {
+///   <Router>
+///     <Routes>
+///       <Route
+///         path="/users/:id"
+///         view=User
+///         params={
+///             ParamsGuard::new::<UserParams>().bad_request(|err| {
+///                 view! { <p>"Not a user: " {err.to_string()}</p> }.into_view()
+///             })
+///         }
+///       />
+///     </Routes>
+///   </Router>
+/// };
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ParamsGuard {
+    check: Rc<ParamsCheck>,
+    bad_request: Option<fn(ParamsError) -> View>,
+}
+
+impl ParamsGuard {
+    /// Creates a guard that checks that the params can be parsed as `T`.
+    pub fn new<T: Params + 'static>() -> Self {
+        Self {
+            check: Rc::new(|map| T::from_map(map).map(|_| ())),
+            bad_request: None,
+        }
+    }
+
+    /// Sets the view rendered when the params are invalid.
+    pub fn bad_request(mut self, view: fn(ParamsError) -> View) -> Self {
+        self.bad_request = Some(view);
+        self
+    }
+
+    /// Checks the given params.
+    pub fn check(&self, params: &ParamsMap) -> Result<(), ParamsError> {
+        (self.check)(params)
+    }
+
+    /// Renders the "bad request" view for the given error.
+    pub(crate) fn render_error(&self, error: ParamsError) -> View {
+        match self
+            .bad_request
+            .or_else(|| use_context::<BadRequestView>().map(|view| view.0))
+        {
+            Some(view) => view(error),
+            None => error.to_string().into_view(),
+        }
+    }
+}
+
+impl core::fmt::Debug for ParamsGuard {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ParamsGuard").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,205 @@
+//! Renders pages of the app to files, without running a server.
+//!
+//! [`prerender`] renders the given paths of the app, and
+//! [`prerender_routes`] every path of its routes that can be enumerated, in
+//! the same way as [`generate_sitemap`](crate::generate_sitemap). Each page is
+//! rendered in full, as with [`SsrMode::Async`](crate::SsrMode::Async), and
+//! written to the output directory as `{path}.html` (or `index.html` for
+//! `/`), along with the data of the resources it loaded, serialized as they
+//! are sent to the browser, in `{path}.resources.json`:
+//!
+//! ```json
+//! [[{"idx":1,"version":1},"{\"title\":\"Hello, world!\"}"]]
+//! ```
+//!
+//! Pages are rendered like on the server, so this must be run in a `LocalSet`
+//! of a `tokio` runtime, or another executor that
+//! [`spawn_local`](leptos::spawn_local) can spawn tasks on, like from a small
+//! binary or a `build.rs`:
+//!
+//! ```rust,ignore
+//! #[tokio::main]
+//! async fn main() -> std::io::Result<()> {
+//!     let options = get_configuration(None).await.unwrap().leptos_options;
+//!     let (routes, static_data_map) = generate_route_list_with_ssg(App);
+//!     let pages = tokio::task::LocalSet::new()
+//!         .run_until(prerender_routes(
+//!             &options,
+//!             App,
+//!             &routes,
+//!             &static_data_map,
+//!             "dist",
+//!         ))
+//!         .await?;
+//!     println!("rendered {} pages", pages.len());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    components::{enumerate_paths, ResolvedStaticPath},
+    RouteListing, StaticDataMap,
+};
+use futures::{stream, StreamExt};
+use leptos::{set_current_runtime, IntoView, LeptosOptions, SharedContext};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// A page written by [`prerender`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrerenderedPage {
+    /// The path of the page, like `/blog/hello-world`.
+    pub path: String,
+    /// The file the page was written to.
+    pub html: PathBuf,
+    /// The file the data of the page's resources was written to.
+    pub resources: PathBuf,
+}
+
+/// Renders the app at each of the given paths, like `/blog/hello-world`, and
+/// writes the pages to `out_dir`. See the [module docs](self).
+pub async fn prerender<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    paths: impl IntoIterator<Item = impl ToString>,
+    out_dir: impl AsRef<Path>,
+) -> io::Result<Vec<PrerenderedPage>>
+where
+    IV: IntoView + 'static,
+{
+    prerender_with_additional_context(options, app_fn, || {}, paths, out_dir)
+        .await
+}
+
+/// Renders the app at each of the given paths, like [`prerender`], calling
+/// `additional_context` before each page is rendered.
+pub async fn prerender_with_additional_context<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    additional_context: impl Fn() + 'static + Clone,
+    paths: impl IntoIterator<Item = impl ToString>,
+    out_dir: impl AsRef<Path>,
+) -> io::Result<Vec<PrerenderedPage>>
+where
+    IV: IntoView + 'static,
+{
+    let out_dir = out_dir.as_ref();
+    let mut pages = Vec::new();
+    for path in paths {
+        let path = path.to_string();
+        let (html, resources) = render_page(
+            options,
+            app_fn.clone(),
+            additional_context.clone(),
+            &path,
+        )
+        .await;
+        let page = PrerenderedPage {
+            html: file_path(out_dir, &path, "html"),
+            resources: file_path(out_dir, &path, "resources.json"),
+            path,
+        };
+        if let Some(dir) = page.html.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&page.html, html)?;
+        std::fs::write(&page.resources, resources)?;
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+/// Renders the app at every path of its routes that can be enumerated, and
+/// writes the pages to `out_dir`. See the [module docs](self).
+pub async fn prerender_routes<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    routes: &[RouteListing],
+    static_data_map: &StaticDataMap,
+    out_dir: impl AsRef<Path>,
+) -> io::Result<Vec<PrerenderedPage>>
+where
+    IV: IntoView + 'static,
+{
+    prerender_routes_with_additional_context(
+        options,
+        app_fn,
+        || {},
+        routes,
+        static_data_map,
+        out_dir,
+    )
+    .await
+}
+
+/// Renders the app at every path of its routes that can be enumerated, like
+/// [`prerender_routes`], calling `additional_context` before their static
+/// params are enumerated and before each page is rendered.
+pub async fn prerender_routes_with_additional_context<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    additional_context: impl Fn() + 'static + Clone,
+    routes: &[RouteListing],
+    static_data_map: &StaticDataMap,
+    out_dir: impl AsRef<Path>,
+) -> io::Result<Vec<PrerenderedPage>>
+where
+    IV: IntoView + 'static,
+{
+    let paths =
+        enumerate_paths(additional_context.clone(), routes, static_data_map)
+            .await
+            .into_iter()
+            .map(|(_, path)| path);
+    prerender_with_additional_context(
+        options,
+        app_fn,
+        additional_context,
+        paths,
+        out_dir,
+    )
+    .await
+}
+
+// renders the page at `path`, returning its HTML and the serialized data of
+// its resources
+async fn render_page<IV>(
+    options: &LeptosOptions,
+    app_fn: impl Fn() -> IV + 'static + Clone,
+    additional_context: impl Fn() + 'static + Clone,
+    path: &str,
+) -> (String, String)
+where
+    IV: IntoView + 'static,
+{
+    let (stream, runtime) =
+        ResolvedStaticPath(path.to_string()).render(app_fn, additional_context);
+    // the page is rendered in its own runtime as the stream is polled
+    set_current_runtime(runtime);
+    let body = stream.collect::<String>().await;
+
+    // the resources have resolved once the page has been rendered in order
+    let mut resources = SharedContext::serialization_resolvers()
+        .collect::<Vec<_>>()
+        .await;
+    resources.sort_by_key(|(id, _)| *id);
+    let resources = serde_json::to_string(&resources)
+        .expect("resource IDs and serialized data can be serialized as JSON");
+
+    set_current_runtime(runtime);
+    let html = leptos_integration_utils::build_async_response(
+        stream::once(async move { body }),
+        options,
+        runtime,
+    )
+    .await;
+    (html, resources)
+}
+
+fn file_path(out_dir: &Path, path: &str, extension: &str) -> PathBuf {
+    let path = path.trim_matches('/');
+    let path = if path.is_empty() { "index" } else { path };
+    out_dir.join(format!("{path}.{extension}"))
+}
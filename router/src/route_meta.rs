@@ -0,0 +1,208 @@
+#[cfg(feature = "meta")]
+use crate::{matching::get_route_matches, RouteMatch};
+use crate::{Branches, RouteTitle};
+#[cfg(feature = "meta")]
+use leptos::{create_isomorphic_effect, create_memo, view, SignalGet};
+use leptos::{Memo, Oco};
+#[cfg(feature = "meta")]
+use leptos_meta::{Meta, OpenGraph, Title};
+
+/// Document metadata for a route, which is applied through `leptos_meta`
+/// whenever the route is matched, both during server rendering and after
+/// client-side navigations. This requires the `meta` feature.
+///
+/// Each field of a nested route overrides the same field of its parent routes,
+/// so a layout route can set a `title_template` and default `description`
+/// that its children refine.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// # if false {
+/// view! {
+///   <Router>
+///     <Routes>
+///       <Route
+///         path=""
+///         view=|| view! { <Outlet/> }
+///         meta=RouteMeta {
+///           title_template: Some("{} | My Site".into()),
+///           description: Some("A site about things.".into()),
+///           ..Default::default()
+///         }
+///       >
+///         <Route
+///           path="users/:id"
+///           view=|| ()
+///           title=|params: &ParamsMap| format!("User {}", params.get("id").unwrap())
+///           meta=RouteMeta {
+///             robots: Some("noindex".into()),
+///             ..Default::default()
+///           }
+///         />
+///       </Route>
+///     </Routes>
+///   </Router>
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RouteMeta {
+    /// The document title. Defaults to the `title` of the route, if any.
+    pub title: Option<RouteTitle>,
+    /// A template the title is formatted with, where `{}` is replaced by the
+    /// title, like `"{} | My Site"`.
+    pub title_template: Option<Oco<'static, str>>,
+    /// The `description` meta tag.
+    pub description: Option<Oco<'static, str>>,
+    /// The `og:title` meta tag.
+    pub og_title: Option<Oco<'static, str>>,
+    /// The `og:description` meta tag.
+    pub og_description: Option<Oco<'static, str>>,
+    /// The `og:image` meta tag.
+    pub og_image: Option<Oco<'static, str>>,
+    /// The `og:type` meta tag.
+    pub og_type: Option<Oco<'static, str>>,
+    /// The `robots` meta tag, like `"noindex, nofollow"`.
+    pub robots: Option<Oco<'static, str>>,
+}
+
+/// The metadata of the matched route tree, with nested routes' fields
+/// taking precedence over their parents'.
+#[cfg(feature = "meta")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MatchedMeta {
+    pub title: Option<String>,
+    // whether the title has been formatted with a `title_template`
+    pub title_templated: bool,
+    pub description: Option<String>,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image: Option<String>,
+    pub og_type: Option<String>,
+    pub robots: Option<String>,
+}
+
+/// Merges the metadata of the matched routes, or returns `None` if none of
+/// them declares any.
+#[cfg(feature = "meta")]
+pub(crate) fn matched_meta(matches: &[RouteMatch]) -> Option<MatchedMeta> {
+    fn set(field: &mut Option<String>, value: &Option<Oco<'static, str>>) {
+        if let Some(value) = value {
+            *field = Some(value.to_string());
+        }
+    }
+
+    let mut merged: Option<MatchedMeta> = None;
+    let mut title = None;
+    let mut template = None;
+    for m in matches {
+        let route = &m.route.key;
+        let params = &m.path_match.params;
+        if let Some(route_title) = route
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.title.as_ref())
+            .or(route.title.as_ref())
+        {
+            title = Some(route_title.get(params));
+        }
+        let Some(meta) = &route.meta else {
+            continue;
+        };
+        let merged = merged.get_or_insert_with(Default::default);
+        if meta.title_template.is_some() {
+            template.clone_from(&meta.title_template);
+        }
+        set(&mut merged.description, &meta.description);
+        set(&mut merged.og_title, &meta.og_title);
+        set(&mut merged.og_description, &meta.og_description);
+        set(&mut merged.og_image, &meta.og_image);
+        set(&mut merged.og_type, &meta.og_type);
+        set(&mut merged.robots, &meta.robots);
+    }
+    merged.map(|mut merged| {
+        merged.title_templated = title.is_some() && template.is_some();
+        merged.title = title.map(|title| match &template {
+            Some(template) => template.replace("{}", &title),
+            None => title,
+        });
+        merged
+    })
+}
+
+/// Applies the metadata of the routes matched by the `<Routes/>` mounted at
+/// `base`, if any of them declares [`RouteMeta`].
+pub(crate) fn apply_route_meta(
+    router_id: usize,
+    base: &str,
+    pathname: Memo<String>,
+) {
+    let has_meta = Branches::with(router_id, base, |branches| {
+        branches.iter().any(|branch| {
+            branch.routes.iter().any(|route| route.key.meta.is_some())
+        })
+    });
+    if !has_meta {
+        return;
+    }
+
+    #[cfg(not(feature = "meta"))]
+    {
+        _ = pathname;
+        leptos::logging::debug_warn!(
+            "a route sets `meta`, which is only applied with the `meta` \
+             feature of `leptos_router`"
+        );
+    }
+
+    #[cfg(feature = "meta")]
+    apply_matched_meta(router_id, base.to_string(), pathname);
+}
+
+#[cfg(feature = "meta")]
+fn apply_matched_meta(router_id: usize, base: String, pathname: Memo<String>) {
+    let meta = create_memo(move |_| {
+        let path = pathname.get();
+        matched_meta(&get_route_matches(router_id, &base, path))
+    });
+
+    // the tags are registered within the effect, so they are removed again
+    // when it reruns for a new set of matched routes
+    create_isomorphic_effect(move |_| {
+        let Some(meta) = meta.get() else {
+            return;
+        };
+        // a title formatted with a route's `title_template` is not formatted
+        // again with the app's `TitleTemplate`
+        match meta.title {
+            Some(title) if meta.title_templated => {
+                _ = view! { <Title text=title template="%s"/> };
+            }
+            Some(title) => {
+                _ = view! { <Title text=title/> };
+            }
+            None => {}
+        }
+        if let Some(description) = meta.description {
+            _ = view! { <Meta name="description" content=description/> };
+        }
+        // replace the tags of any `<OpenGraph/>` rendered by the app
+        if let Some(title) = meta.og_title {
+            _ = view! { <OpenGraph title/> };
+        }
+        if let Some(description) = meta.og_description {
+            _ = view! { <OpenGraph description/> };
+        }
+        if let Some(image) = meta.og_image {
+            _ = view! { <OpenGraph image/> };
+        }
+        if let Some(kind) = meta.og_type {
+            _ = view! { <OpenGraph kind/> };
+        }
+        if let Some(robots) = meta.robots {
+            _ = view! { <Meta name="robots" content=robots/> };
+        }
+    });
+}
@@ -0,0 +1,340 @@
+use leptos::*;
+use std::rc::Rc;
+
+/// Describes how the router should update the scroll position at the end of a
+/// navigation.
+///
+/// This can be set for the whole app with the `scroll_behavior` prop on the
+/// [`<Router/>`](crate::Router), for a particular route with the
+/// `scroll_behavior` prop on [`<Route/>`](crate::Route), and for a single
+/// navigation with [`NavigateOptions::scroll_behavior`](crate::NavigateOptions).
+/// A navigation's own behavior takes precedence over the behavior of the most
+/// deeply-nested matched route that sets one, which takes precedence over the
+/// router's.
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <Routes>
+///           // the inbox scrolls inside its own panel, so leave the window alone
+///           <Route
+///             path="/inbox"
+///             view=|| view! { <div class="panel">"..."</div> }
+///             scroll_behavior=ScrollBehavior::Preserve
+///           />
+///           <Route
+///             path="/feed"
+///             view=|| "..."
+///             scroll_behavior=ScrollBehavior::custom(|scroll: &ScrollContext| {
+///                 if scroll.kind == NavigationKind::Traverse {
+///                     // restore the position of a scrollable panel here
+///                 }
+///             })
+///           />
+///         </Routes>
+///       </Router>
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub enum ScrollBehavior {
+    /// The default behavior:
+    /// - if the URL has a `#fragment`, the element with that `id` is scrolled
+    ///   into view, waiting for any pending `<Suspense/>` to resolve if it
+    ///   cannot be found yet;
+    /// - otherwise, a back/forward navigation restores the scroll position the
+    ///   page had when it was left;
+    /// - otherwise, the window is scrolled to the top, unless
+    ///   [`NavigateOptions::scroll`](crate::NavigateOptions) is `false`.
+    #[default]
+    Auto,
+    /// Always scrolls the window to the top.
+    Top,
+    /// Leaves the scroll position as it is.
+    Preserve,
+    /// Calls the given function instead of scrolling.
+    Custom(Rc<dyn Fn(&ScrollContext)>),
+}
+
+impl ScrollBehavior {
+    /// Creates a [`ScrollBehavior::Custom`] from the given function.
+    pub fn custom(f: impl Fn(&ScrollContext) + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+}
+
+impl core::fmt::Debug for ScrollBehavior {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "Auto"),
+            Self::Top => write!(f, "Top"),
+            Self::Preserve => write!(f, "Preserve"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// The kind of navigation that just took place.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationKind {
+    /// A new entry was pushed onto the history stack.
+    Push,
+    /// The current entry of the history stack was replaced.
+    Replace,
+    /// The user moved back or forward through the history stack.
+    Traverse,
+}
+
+/// Information about a navigation, passed to a [`ScrollBehavior::Custom`]
+/// function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrollContext {
+    /// The URL that was navigated to.
+    pub url: String,
+    /// The kind of navigation.
+    pub kind: NavigationKind,
+    /// The decoded `#fragment` of the URL, if any.
+    pub hash: Option<String>,
+    /// The scroll position of the window when the URL was last left, if any.
+    pub saved_position: Option<(f64, f64)>,
+    /// Whether the navigation asked to scroll to the top of the page. See
+    /// [`NavigateOptions::scroll`](crate::NavigateOptions).
+    pub scroll: bool,
+}
+
+impl ScrollContext {
+    pub(crate) fn new(
+        url: String,
+        kind: NavigationKind,
+        saved_position: Option<(f64, f64)>,
+        scroll: bool,
+    ) -> Self {
+        Self {
+            hash: url_hash(&url),
+            url,
+            kind,
+            saved_position,
+            scroll,
+        }
+    }
+}
+
+// where to scroll at the end of a navigation, decided apart from the DOM
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ScrollTarget {
+    None,
+    // the element with this `id`, or `otherwise` if there is none
+    Element {
+        id: String,
+        otherwise: Option<(f64, f64)>,
+    },
+    Position(f64, f64),
+    Custom,
+}
+
+pub(crate) fn scroll_target(
+    behavior: &ScrollBehavior,
+    cx: &ScrollContext,
+) -> ScrollTarget {
+    match behavior {
+        ScrollBehavior::Preserve => ScrollTarget::None,
+        ScrollBehavior::Top => ScrollTarget::Position(0.0, 0.0),
+        ScrollBehavior::Custom(_) => ScrollTarget::Custom,
+        ScrollBehavior::Auto => {
+            let position = match (cx.kind, cx.saved_position) {
+                (NavigationKind::Traverse, Some(position)) => Some(position),
+                _ if cx.scroll => Some((0.0, 0.0)),
+                _ => None,
+            };
+            match (&cx.hash, position) {
+                (Some(id), otherwise) => ScrollTarget::Element {
+                    id: id.clone(),
+                    otherwise,
+                },
+                (None, Some((x, y))) => ScrollTarget::Position(x, y),
+                (None, None) => ScrollTarget::None,
+            }
+        }
+    }
+}
+
+/// Applies the scroll behavior at the end of a navigation.
+pub(crate) fn apply_scroll(
+    behavior: &ScrollBehavior,
+    cx: ScrollContext,
+    global_suspense: Option<GlobalSuspenseContext>,
+) {
+    match scroll_target(behavior, &cx) {
+        ScrollTarget::None => {}
+        ScrollTarget::Position(x, y) => window().scroll_to_with_x_and_y(x, y),
+        ScrollTarget::Custom => {
+            if let ScrollBehavior::Custom(f) = behavior {
+                f(&cx)
+            }
+        }
+        ScrollTarget::Element { id, otherwise } => {
+            if scroll_to_id(&id) {
+                return;
+            }
+            // the target may be inside a <Suspense/> that hasn't resolved
+            if let Some(global) = global_suspense {
+                spawn_local(async move {
+                    global.with_inner(|s| s.to_future()).await;
+                    scroll_to_id(&id);
+                });
+                return;
+            }
+            if let Some((x, y)) = otherwise {
+                window().scroll_to_with_x_and_y(x, y);
+            }
+        }
+    }
+}
+
+fn scroll_to_id(id: &str) -> bool {
+    match document().get_element_by_id(id) {
+        Some(el) => {
+            el.scroll_into_view();
+            true
+        }
+        None => false,
+    }
+}
+
+// Returns the decoded `#fragment` of the given URL, if it has a non-empty one.
+pub(crate) fn url_hash(url: &str) -> Option<String> {
+    let (_, hash) = url.split_once('#')?;
+    if hash.is_empty() {
+        return None;
+    }
+    Some(
+        percent_encoding::percent_decode_str(hash)
+            .decode_utf8()
+            .map(String::from)
+            .unwrap_or_else(|_| hash.to_string()),
+    )
+}
+
+// The current scroll position of the window.
+pub(crate) fn window_scroll_position() -> (f64, f64) {
+    let window = window();
+    (
+        window.scroll_x().unwrap_or_default(),
+        window.scroll_y().unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(url: &str) -> ScrollContext {
+        ScrollContext::new(url.into(), NavigationKind::Push, None, true)
+    }
+
+    #[test]
+    fn navigating_to_a_hash_scrolls_to_its_element() {
+        let cx = push("/docs#getting%20started");
+
+        assert_eq!(cx.hash.as_deref(), Some("getting started"));
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Auto, &cx),
+            ScrollTarget::Element {
+                id: "getting started".into(),
+                otherwise: Some((0.0, 0.0)),
+            }
+        );
+    }
+
+    #[test]
+    fn anchor_on_the_same_page_scrolls_to_its_element() {
+        // like `<a href="#intro" noscroll>`, which doesn't scroll to the top
+        let cx = ScrollContext::new(
+            "/docs#intro".into(),
+            NavigationKind::Push,
+            None,
+            false,
+        );
+
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Auto, &cx),
+            ScrollTarget::Element {
+                id: "intro".into(),
+                otherwise: None,
+            }
+        );
+    }
+
+    #[test]
+    fn going_back_restores_the_saved_position() {
+        let cx = ScrollContext::new(
+            "/feed".into(),
+            NavigationKind::Traverse,
+            Some((0.0, 480.0)),
+            true,
+        );
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Auto, &cx),
+            ScrollTarget::Position(0.0, 480.0)
+        );
+
+        // an element named by the hash still comes first
+        let cx = ScrollContext::new(
+            "/feed#post-3".into(),
+            NavigationKind::Traverse,
+            Some((0.0, 480.0)),
+            true,
+        );
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Auto, &cx),
+            ScrollTarget::Element {
+                id: "post-3".into(),
+                otherwise: Some((0.0, 480.0)),
+            }
+        );
+    }
+
+    #[test]
+    fn navigating_without_a_hash_scrolls_to_the_top() {
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Auto, &push("/about")),
+            ScrollTarget::Position(0.0, 0.0)
+        );
+        assert_eq!(push("/about#").hash, None);
+        assert_eq!(
+            scroll_target(
+                &ScrollBehavior::Auto,
+                &ScrollContext::new(
+                    "/about".into(),
+                    NavigationKind::Replace,
+                    None,
+                    false
+                )
+            ),
+            ScrollTarget::None
+        );
+    }
+
+    #[test]
+    fn other_behaviors_override_the_hash() {
+        let cx = push("/docs#intro");
+
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Preserve, &cx),
+            ScrollTarget::None
+        );
+        assert_eq!(
+            scroll_target(&ScrollBehavior::Top, &cx),
+            ScrollTarget::Position(0.0, 0.0)
+        );
+        assert_eq!(
+            scroll_target(&ScrollBehavior::custom(|_| {}), &cx),
+            ScrollTarget::Custom
+        );
+    }
+}
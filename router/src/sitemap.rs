@@ -0,0 +1,230 @@
+//! Generates a `sitemap.xml` listing the pages of the app, from its routes.
+//!
+//! Every route that handles `GET` requests is listed. Routes with params are
+//! listed once for each of their paths, which are enumerated from the same
+//! static params that static routes are built from (see
+//! [`StaticParamsMap`](crate::StaticParamsMap)). Routes with params for which
+//! none are provided, like a route that renders any user's profile, cannot be
+//! enumerated, and are left out.
+//!
+//! ```rust,ignore
+//! let (routes, static_data_map) = generate_route_list_with_ssg(App);
+//! let sitemap = generate_sitemap("https://example.com", &routes, &static_data_map)
+//!     .await
+//!     .exclude("/admin")
+//!     .route("/blog/:slug", |entry| {
+//!         entry.priority = Some(0.8);
+//!         entry.lastmod = last_modified(&entry.path);
+//!     });
+//!
+//! // written at build time...
+//! sitemap.write(&leptos_options)?;
+//! // ...or served by the server
+//! let xml = sitemap.to_xml();
+//! let app = Router::new().route(
+//!     "/sitemap.xml",
+//!     get(|| async move { ([(CONTENT_TYPE, "application/xml")], xml) }),
+//! );
+//! ```
+
+use crate::{components::enumerate_paths, RouteListing, StaticDataMap};
+use leptos::LeptosOptions;
+use std::{fmt, fmt::Write, path::Path};
+
+/// How often the page at a URL is likely to change, as a hint to search
+/// engines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeFrequency {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl fmt::Display for ChangeFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeFrequency::Always => "always",
+            ChangeFrequency::Hourly => "hourly",
+            ChangeFrequency::Daily => "daily",
+            ChangeFrequency::Weekly => "weekly",
+            ChangeFrequency::Monthly => "monthly",
+            ChangeFrequency::Yearly => "yearly",
+            ChangeFrequency::Never => "never",
+        })
+    }
+}
+
+/// A URL listed in a [`Sitemap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    /// The route the URL was generated from, like `/blog/:slug`.
+    pub route: String,
+    /// The path of the URL, like `/blog/hello-world`.
+    pub path: String,
+    /// When the page was last modified, as a W3C datetime like
+    /// `2024-05-01` or `2024-05-01T12:00:00+00:00`.
+    pub lastmod: Option<String>,
+    /// How often the page is likely to change.
+    pub changefreq: Option<ChangeFrequency>,
+    /// The priority of the page relative to the other pages of the site,
+    /// from `0.0` to `1.0`.
+    pub priority: Option<f32>,
+}
+
+/// The URLs of the pages of the app, generated from its routes by
+/// [`generate_sitemap`]. See the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sitemap {
+    base_url: String,
+    /// The URLs listed in the sitemap.
+    pub entries: Vec<SitemapEntry>,
+}
+
+impl Sitemap {
+    /// Creates an empty sitemap for the site at `base_url`, like
+    /// `https://example.com`.
+    pub fn new(base_url: impl ToString) -> Self {
+        Self {
+            base_url: base_url.to_string().trim_end_matches('/').to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Calls `f` with each URL generated from the route with the given path,
+    /// in the syntax of the router, like `/blog/:slug`, so that it can set
+    /// when the page was last modified and its priority.
+    pub fn route(
+        mut self,
+        route: &str,
+        mut f: impl FnMut(&mut SitemapEntry),
+    ) -> Self {
+        self.entries
+            .iter_mut()
+            .filter(|entry| entry.route == route)
+            .for_each(&mut f);
+        self
+    }
+
+    /// Leaves out the URLs generated from the route with the given path, in
+    /// the syntax of the router, and from the routes nested in it.
+    pub fn exclude(mut self, route: &str) -> Self {
+        let route = route.trim_end_matches('/');
+        self.entries.retain(|entry| {
+            entry.route != route
+                && !entry
+                    .route
+                    .strip_prefix(route)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        });
+        self
+    }
+
+    /// Renders the sitemap as XML.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            let loc = format!("{}{}", self.base_url, entry.path);
+            _ = write!(xml, "  <url>\n    <loc>{}</loc>\n", escape(&loc));
+            if let Some(lastmod) = &entry.lastmod {
+                _ = writeln!(xml, "    <lastmod>{}</lastmod>", escape(lastmod));
+            }
+            if let Some(changefreq) = entry.changefreq {
+                _ = writeln!(xml, "    <changefreq>{changefreq}</changefreq>");
+            }
+            if let Some(priority) = entry.priority {
+                _ = writeln!(
+                    xml,
+                    "    <priority>{:.1}</priority>",
+                    priority.clamp(0.0, 1.0)
+                );
+            }
+            xml.push_str("  </url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+
+    /// Renders a `robots.txt` that allows every crawler to crawl the site
+    /// except for the given paths, like `/admin/`, and points them to the
+    /// sitemap at `/sitemap.xml`.
+    pub fn robots_txt<'a>(
+        &self,
+        disallow: impl IntoIterator<Item = &'a str>,
+    ) -> String {
+        let mut robots = String::from("User-agent: *\n");
+        let mut disallow = disallow.into_iter().peekable();
+        if disallow.peek().is_none() {
+            robots.push_str("Allow: /\n");
+        }
+        for path in disallow {
+            _ = writeln!(robots, "Disallow: {path}");
+        }
+        _ = writeln!(robots, "\nSitemap: {}/sitemap.xml", self.base_url);
+        robots
+    }
+
+    /// Writes the sitemap to `sitemap.xml` in the `site_root`, to be served
+    /// with the other static files of the site.
+    pub fn write(&self, options: &LeptosOptions) -> Result<(), std::io::Error> {
+        let root = Path::new(options.site_root.as_str());
+        std::fs::create_dir_all(root)?;
+        std::fs::write(root.join("sitemap.xml"), self.to_xml())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Generates the sitemap of the site at `base_url`, like
+/// `https://example.com`, from the routes of the app. See the
+/// [module docs](self).
+pub async fn generate_sitemap(
+    base_url: &str,
+    routes: &[RouteListing],
+    static_data_map: &StaticDataMap,
+) -> Sitemap {
+    generate_sitemap_with_additional_context(
+        base_url,
+        || {},
+        routes,
+        static_data_map,
+    )
+    .await
+}
+
+/// Generates the sitemap of the site at `base_url`, like
+/// [`generate_sitemap`], calling `additional_context` before the static
+/// params of the routes are enumerated.
+pub async fn generate_sitemap_with_additional_context(
+    base_url: &str,
+    additional_context: impl Fn(),
+    routes: &[RouteListing],
+    static_data_map: &StaticDataMap,
+) -> Sitemap {
+    let mut sitemap = Sitemap::new(base_url);
+    sitemap.entries =
+        enumerate_paths(additional_context, routes, static_data_map)
+            .await
+            .into_iter()
+            .map(|(route, path)| SitemapEntry {
+                route: route.to_string(),
+                path,
+                lastmod: None,
+                changefreq: None,
+                priority: None,
+            })
+            .collect();
+    sitemap
+}
@@ -0,0 +1,81 @@
+use crate::{use_navigate, NavigateOptions, Params};
+
+/// A route described as a type, rather than as a string path.
+///
+/// A typed route knows the path pattern it matches ([`TypedRoute::PATH`]), and
+/// can build a concrete URL from its own fields ([`TypedRoute::to_path`]). This
+/// is usually implemented with `#[derive(TypedRoute)]`, which checks at compile
+/// time that the struct's fields and the path's params agree, and also derives
+/// [`Params`], so `use_params::<T>()` always parses exactly the params the route
+/// declares.
+///
+/// Typed routes also implement [`ToHref`](crate::ToHref), so they can be used
+/// directly as the `href` of an [`<A/>`](crate::A).
+///
+/// ```rust
+/// use leptos::*;
+/// use leptos_router::*;
+///
+/// #[derive(Clone, PartialEq, TypedRoute)]
+/// #[route(path = "/users/:id")]
+/// struct User {
+///     id: usize,
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///       <Router>
+///         <A href=User { id: 42 }>"User #42"</A>
+///         <Routes>
+///           <Route path=User::PATH view=UserPage/>
+///         </Routes>
+///       </Router>
+///     }
+/// }
+///
+/// #[component]
+/// fn UserPage() -> impl IntoView {
+///     let user = use_params::<User>();
+///     move || user.with(|user| user.as_ref().map(|user| user.id).ok())
+/// }
+/// ```
+///
+/// `PATH` is always an absolute path. When a typed route is used inside nested
+/// `<Route/>`s, the nested route definitions must together add up to the same
+/// path.
+pub trait TypedRoute: Params {
+    /// The path pattern matched by this route, e.g., `/users/:id`.
+    const PATH: &'static str;
+
+    /// Builds the URL path for this route, filling in each param from the
+    /// corresponding field.
+    fn to_path(&self) -> String;
+}
+
+/// Returns a function that navigates to the given [`TypedRoute`].
+///
+/// This works like [`use_navigate`], but builds the URL from the route's
+/// fields rather than taking a string.
+///
+/// ```rust
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # #[derive(Clone, PartialEq, TypedRoute)]
+/// # #[route(path = "/users/:id")]
+/// # struct User { id: usize }
+/// # let runtime = create_runtime();
+/// # if false { // can't actually navigate, no <Router/>
+/// let navigate_to = use_navigate_to();
+/// navigate_to(User { id: 42 }, Default::default());
+/// # }
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn use_navigate_to<R>() -> impl Fn(R, NavigateOptions) + Clone
+where
+    R: TypedRoute,
+{
+    let navigate = use_navigate();
+    move |route, options| navigate(&route.to_path(), options)
+}
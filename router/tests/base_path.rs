@@ -0,0 +1,51 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        fn render_at(base: &'static str, path: &str) -> String {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let html = view! {
+                <Router base>
+                    <Routes>
+                        <Route path="" view=|| view! { <p>"Home"</p> }/>
+                        <Route path="users/:id" view=|| view! {
+                            <p>"User"</p>
+                            <A href="/users">"All users"</A>
+                            <A href="settings">"Settings"</A>
+                        }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn routes_match_under_base_path() {
+            assert!(render_at("/app", "/app").contains("Home"));
+            assert!(render_at("/app", "/app/users/42").contains("User"));
+            assert!(!render_at("/app", "/users/42").contains("User"));
+        }
+
+        #[test]
+        fn links_include_base_path() {
+            let html = render_at("/app", "/app/users/42");
+            assert!(html.contains(r#"href="/app/users""#));
+            assert!(html.contains(r#"href="/app/users/42/settings""#));
+        }
+
+        #[test]
+        fn empty_base_path_matches_root() {
+            assert!(render_at("", "/users/42").contains("User"));
+        }
+    }
+}
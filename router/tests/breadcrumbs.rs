@@ -0,0 +1,76 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            static CRUMBS: RefCell<Vec<(String, Option<String>)>> = Default::default();
+        }
+
+        fn capture() {
+            let crumbs = use_breadcrumbs()
+                .get_untracked()
+                .into_iter()
+                .map(|crumb| (crumb.path, crumb.title))
+                .collect();
+            CRUMBS.with(|c| *c.borrow_mut() = crumbs);
+        }
+
+        fn crumbs_at(path: &str) -> Vec<(String, Option<String>)> {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            _ = view! {
+                <Router>
+                    <Routes>
+                        <Route path="" view=|| view! { <Outlet/> } title="Home">
+                            <Route path="users" view=|| view! { <Outlet/> } title="Users">
+                                <Route path="" view=capture title="All users"/>
+                                <Route
+                                    path=":id"
+                                    view=|| view! { <Outlet/> }
+                                    title=|params: &ParamsMap| format!("User {}", params.get("id").unwrap())
+                                >
+                                    <Route path="settings" view=capture/>
+                                </Route>
+                            </Route>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string();
+            let crumbs = CRUMBS.with(RefCell::take);
+            runtime.dispose();
+            crumbs
+        }
+
+        #[test]
+        fn breadcrumbs_follow_matched_routes() {
+            assert_eq!(
+                crumbs_at("/users/42/settings"),
+                vec![
+                    ("".to_string(), Some("Home".to_string())),
+                    ("/users".to_string(), Some("Users".to_string())),
+                    ("/users/42".to_string(), Some("User 42".to_string())),
+                    ("/users/42/settings".to_string(), None),
+                ]
+            );
+        }
+
+        #[test]
+        fn breadcrumbs_merge_index_routes() {
+            assert_eq!(
+                crumbs_at("/users"),
+                vec![
+                    ("".to_string(), Some("Home".to_string())),
+                    ("/users".to_string(), Some("All users".to_string())),
+                ]
+            );
+        }
+    }
+}
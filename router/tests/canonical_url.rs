@@ -0,0 +1,54 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(all(feature = "ssr", feature = "meta"))] {
+        use leptos::*;
+        use leptos_meta::*;
+        use leptos_router::*;
+
+        fn head_at(path: &str) -> String {
+            let runtime = create_runtime();
+            provide_meta_context();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            _ = view! {
+                <Router>
+                    <CanonicalUrl base_url="https://example.com/" query_params=["page", "sort"]/>
+                    <Routes>
+                        <Route path="/posts" view=|| ()/>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string();
+            let head = use_head().dehydrate();
+            runtime.dispose();
+            head
+        }
+
+        #[test]
+        fn canonical_url_is_the_current_path() {
+            let head = head_at("/posts");
+            assert!(head.contains(r#"rel="canonical""#));
+            assert!(head.contains(r#"href="https://example.com/posts""#));
+        }
+
+        #[test]
+        fn canonical_url_keeps_only_listed_query_params() {
+            let head = head_at("/posts?utm_source=feed&sort=new&page=2");
+            assert!(head.contains(r#"href="https://example.com/posts?page=2&amp;sort=new""#));
+        }
+
+        #[test]
+        fn canonical_url_escapes_query_values() {
+            let query = ParamsMap(
+                [("page".to_string(), "a b".to_string())].into_iter().collect(),
+            );
+            assert_eq!(
+                canonical_url("https://example.com", "/", &query, &["page"]),
+                "https://example.com/?page=a%20b"
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::cell::RefCell;
+
+        thread_local! {
+            static SNAPSHOT: RefCell<RouterSnapshot> = Default::default();
+        }
+
+        fn capture() {
+            let snapshot = untrack(|| use_router().inspect());
+            SNAPSHOT.with(|s| *s.borrow_mut() = snapshot);
+        }
+
+        fn inspect_at(path: &str) -> RouterSnapshot {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            _ = view! {
+                <Router>
+                    <Routes>
+                        <Route path="/" view=capture/>
+                        <Route path="/users" view=|| view! { <Outlet/> } title="Users">
+                            <Route path=":id<u64>" view=capture ssr=SsrMode::Async/>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string();
+            let snapshot = SNAPSHOT.with(RefCell::take);
+            runtime.dispose();
+            snapshot
+        }
+
+        #[test]
+        fn inspect_lists_route_tree() {
+            let snapshot = inspect_at("/");
+            let patterns = snapshot
+                .routes
+                .iter()
+                .map(|route| {
+                    (
+                        route.pattern.as_str(),
+                        route
+                            .children
+                            .iter()
+                            .map(|child| child.pattern.as_str())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                patterns,
+                vec![("", vec![]), ("/users", vec!["/users/:id<u64>"])]
+            );
+            assert!(snapshot.routes[1].has_title);
+            assert_eq!(snapshot.routes[1].children[0].ssr_mode, SsrMode::Async);
+            assert_eq!(snapshot.routes[1].children[0].methods, vec![Method::Get]);
+        }
+
+        #[test]
+        fn inspect_reports_location_and_matches() {
+            let snapshot = inspect_at("/users/42?tab=posts#top");
+            assert_eq!(snapshot.location.pathname, "/users/42");
+            assert_eq!(snapshot.location.query, params_map! { "tab" => "posts" });
+            let matched = snapshot
+                .matched
+                .iter()
+                .map(|m| (m.path.as_str(), m.title.as_deref()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                matched,
+                vec![("/users", Some("Users")), ("/users/42", None)]
+            );
+            assert_eq!(
+                snapshot.matched[1].params,
+                params_map! { "id" => "42" }
+            );
+            assert_eq!(snapshot.pending, None);
+        }
+
+        #[test]
+        fn inspect_snapshot_is_serializable() {
+            let snapshot = inspect_at("/users/42");
+            let json = serde_json::to_string(&snapshot).unwrap();
+            let parsed: RouterSnapshot = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, snapshot);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        fn render_at(path: &str) -> String {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let html = view! {
+                <Router>
+                    <Routes>
+                        <Route path="/" view=|| view! { <p>"Feed"</p> }/>
+                        <Route path="/photos/:id" view=|| view! { <p>"Photo page"</p> }/>
+                    </Routes>
+                    <Routes intercept=true>
+                        <Route path="/photos/:id" view=|| view! { <dialog>"Photo modal"</dialog> }/>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn main_routes_render_full_page_on_load() {
+            let html = render_at("/photos/1");
+            assert!(html.contains("Photo page"));
+            assert!(!html.contains("Photo modal"));
+        }
+
+        #[test]
+        fn intercepting_routes_render_nothing_without_navigation() {
+            let html = render_at("/");
+            assert!(html.contains("Feed"));
+            assert!(!html.contains("Photo"));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::RefCell, rc::Rc};
+
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct User(String);
+
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Posts(Vec<String>);
+
+        #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Unused;
+
+        async fn render_at(path: &str) -> (String, Vec<String>, Option<User>) {
+            tokio::task::LocalSet::new()
+                .run_until(async move {
+                    let runtime = create_runtime();
+                    let (html, loaded, user) = render_routes(path).await;
+                    runtime.dispose();
+                    (html, loaded, user)
+                })
+                .await
+        }
+
+        async fn render_routes(path: &str) -> (String, Vec<String>, Option<User>) {
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let loaded = Rc::new(RefCell::new(Vec::new()));
+            let user_loader = {
+                let loaded = Rc::clone(&loaded);
+                RouteLoader::blocking(move |params: ParamsMap| {
+                    let id = params.get("id").cloned().unwrap_or_default();
+                    loaded.borrow_mut().push(format!("user {id}"));
+                    async move { User(id) }
+                })
+            };
+            let posts_loader = {
+                let loaded = Rc::clone(&loaded);
+                RouteLoader::new(move |params: ParamsMap| {
+                    let id = params.get("id").cloned().unwrap_or_default();
+                    loaded.borrow_mut().push(format!("posts {id}"));
+                    async move { Posts(vec![id]) }
+                })
+            };
+            let user = Rc::new(RefCell::new(None));
+            let user_view = {
+                let user = Rc::clone(&user);
+                move || {
+                    let loader = use_loader::<User>();
+                    *user.borrow_mut() = loader;
+                    let posts = use_loader::<Posts>().is_some();
+                    let unused = use_loader::<Unused>().is_some();
+                    view! {
+                        <p>{format!("User {} {posts} {unused}", loader.is_some())}</p>
+                    }
+                }
+            };
+            let html = view! {
+                <Router>
+                    <Routes>
+                        <Route path="/" view=|| view! { <main><Outlet/></main> }>
+                            <Route path="" view=|| {
+                                let has_user = use_loader::<User>().is_some();
+                                view! { <p>"Home " {has_user}</p> }
+                            }/>
+                            <Route
+                                path="users/:id"
+                                view=|| view! { <Outlet/> }
+                                loaders=vec![user_loader]
+                            >
+                                <Route
+                                    path=""
+                                    view=user_view
+                                    loaders=vec![posts_loader]
+                                />
+                            </Route>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            // lets the loaders' futures run
+            tokio::task::yield_now().await;
+            let user = user.borrow().and_then(|user| user.get());
+            let loaded = loaded.borrow().clone();
+            (html, loaded, user)
+        }
+
+        #[tokio::test]
+        async fn loaders_load_with_the_route_params() {
+            let (_, loaded, user) = render_at("/users/42").await;
+            assert_eq!(loaded, vec!["user 42", "posts 42"]);
+            assert_eq!(user, Some(User("42".into())));
+        }
+
+        #[tokio::test]
+        async fn nested_routes_see_their_parents_loaders() {
+            let (html, _, _) = render_at("/users/42").await;
+            assert!(html.contains("User true true false"), "{html}");
+        }
+
+        #[tokio::test]
+        async fn unmatched_loaders_do_not_run() {
+            let (html, loaded, user) = render_at("/").await;
+            assert!(html.contains("Home false"), "{html}");
+            assert!(loaded.is_empty());
+            assert_eq!(user, None);
+        }
+    }
+}
@@ -153,5 +153,80 @@ cfg_if! {
                 })
             );
         }
+
+        #[test]
+        fn matcher_should_match_param_that_satisfies_constraint() {
+            let matcher = Matcher::new("/users/:id<u64>");
+            let matched = matcher.test("/users/42");
+            assert_eq!(
+                matched,
+                Some(PathMatch {
+                    path: "/users/42".into(),
+                    params: params_map!(
+                        "id" => "42"
+                    )
+                })
+            );
+        }
+
+        #[test]
+        fn matcher_should_not_match_param_that_violates_constraint() {
+            let matcher = Matcher::new("/users/:id<u64>");
+            assert_eq!(matcher.test("/users/me"), None);
+            assert_eq!(matcher.test("/users/-1"), None);
+        }
+
+        #[test]
+        #[should_panic(expected = "unknown constraint")]
+        fn matcher_should_panic_on_unknown_constraint() {
+            Matcher::new("/users/:id<uuid>");
+        }
+
+        #[test]
+        fn matcher_should_match_wildcard_followed_by_segments() {
+            let matcher = Matcher::new("/files/*path/edit");
+            let matched = matcher.test("/files/docs/2024/notes.md/edit");
+            assert_eq!(
+                matched,
+                Some(PathMatch {
+                    path: "/files/docs/2024/notes.md/edit".into(),
+                    params: params_map!(
+                        "path" => "docs/2024/notes.md"
+                    )
+                })
+            );
+            assert_eq!(matcher.test("/files/docs/notes.md"), None);
+        }
+
+        #[test]
+        fn matcher_should_match_empty_wildcard_followed_by_segments() {
+            let matcher = Matcher::new("/files/*path/:action<bool>");
+            let matched = matcher.test("/files/true");
+            assert_eq!(
+                matched,
+                Some(PathMatch {
+                    path: "/files/true".into(),
+                    params: params_map!(
+                        "path" => "",
+                        "action" => "true"
+                    )
+                })
+            );
+        }
+
+        #[test]
+        fn partial_matcher_should_match_shortest_wildcard_followed_by_segments() {
+            let matcher = Matcher::new_with_partial("/repo/*path/tree", true);
+            let matched = matcher.test("/repo/a/b/tree/src/tree");
+            assert_eq!(
+                matched,
+                Some(PathMatch {
+                    path: "/repo/a/b/tree".into(),
+                    params: params_map!(
+                        "path" => "a/b"
+                    )
+                })
+            );
+        }
     }
 }
@@ -0,0 +1,90 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        fn render_at(path: &str) -> String {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let html = view! {
+                <Router>
+                    <Routes>
+                        <Route
+                            path="/contacts"
+                            view=|| view! {
+                                <nav><Outlet name="toolbar"/></nav>
+                                <aside><Outlet name="sidebar"/></aside>
+                                <main><Outlet/></main>
+                            }
+                        >
+                            <Route
+                                path=""
+                                view=|| "Select a contact"
+                                named_views=NamedViews::new()
+                                    .view("sidebar", || "Contact list")
+                            />
+                            <Route
+                                path=":id"
+                                view=|| view! {
+                                    <section><Outlet name="details"/></section>
+                                    <article><Outlet/></article>
+                                }
+                                named_views=NamedViews::new()
+                                    .view("sidebar", || "Old list")
+                                    .view("sidebar", || "Contact list")
+                                    .view("toolbar", || "Edit | Delete")
+                            >
+                                <Route
+                                    path=""
+                                    view=|| "Contact"
+                                    named_views=NamedViews::new()
+                                        .view("details", || "Contact details")
+                                        .view("toolbar", || "Not shown")
+                                />
+                            </Route>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            html
+        }
+
+        fn region<'a>(html: &'a str, tag: &str) -> &'a str {
+            let start = html.find(&format!("<{tag}")).unwrap();
+            let end = html.find(&format!("</{tag}>")).unwrap();
+            &html[start..end]
+        }
+
+        #[test]
+        fn named_outlets_show_the_views_of_the_child_route() {
+            let html = render_at("/contacts/1");
+            assert!(region(&html, "aside").contains("Contact list"));
+            assert!(region(&html, "nav").contains("Edit | Delete"));
+            assert!(!html.contains("Old list"));
+        }
+
+        #[test]
+        fn named_outlets_without_a_view_are_empty() {
+            let html = render_at("/contacts");
+            assert!(region(&html, "main").contains("Select a contact"));
+            assert!(region(&html, "aside").contains("Contact list"));
+            assert!(!region(&html, "nav").contains("Edit | Delete"));
+        }
+
+        #[test]
+        fn nested_named_views_fill_the_outlets_of_their_parent() {
+            let html = render_at("/contacts/1");
+            assert!(region(&html, "section").contains("Contact details"));
+            assert!(region(&html, "article").contains("Contact"));
+            assert!(!html.contains("Not shown"));
+        }
+    }
+}
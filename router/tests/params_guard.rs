@@ -0,0 +1,86 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        #[derive(Params, Clone, PartialEq)]
+        struct UserParams {
+            id: Option<usize>,
+        }
+
+        #[component]
+        fn User() -> impl IntoView {
+            let params = use_params::<UserParams>();
+            move || params.get().map(|params| format!("User {}", params.id.unwrap_or_default()))
+        }
+
+        fn render_at(
+            path: &str,
+            guard: ParamsGuard,
+            bad_request: Option<fn(ParamsError) -> View>,
+        ) -> String {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let html = view! {
+                <Router bad_request=bad_request.unwrap_or(|err| err.to_string().into_view())>
+                    <Routes>
+                        <Route path="users/:id" view=User params=guard/>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn invalid_param_is_named() {
+            let err = UserParams::from_map(&params_map! { "id" => "abc" })
+                .err()
+                .unwrap();
+            assert_eq!(err.param_name(), Some("id"));
+            assert_eq!(
+                err,
+                ParamsError::InvalidParam {
+                    name: "id".into(),
+                    value: "abc".into(),
+                    source: std::sync::Arc::new("abc".parse::<usize>().unwrap_err()),
+                }
+            );
+        }
+
+        #[test]
+        fn valid_params_render_view() {
+            let html = render_at("/users/42", ParamsGuard::new::<UserParams>(), None);
+            assert!(html.contains("User 42"));
+        }
+
+        #[test]
+        fn invalid_params_render_bad_request() {
+            let html = render_at(
+                "/users/abc",
+                ParamsGuard::new::<UserParams>()
+                    .bad_request(|err| format!("Bad {}", err.param_name().unwrap()).into_view()),
+                None,
+            );
+            assert!(html.contains("Bad id"));
+            assert!(!html.contains("User"));
+        }
+
+        #[test]
+        fn router_bad_request_is_the_default() {
+            let html = render_at(
+                "/users/abc",
+                ParamsGuard::new::<UserParams>(),
+                Some(|_| "Router bad request".into_view()),
+            );
+            assert!(html.contains("Router bad request"));
+        }
+    }
+}
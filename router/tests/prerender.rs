@@ -0,0 +1,78 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        #[component]
+        fn App() -> impl IntoView {
+            view! {
+                <Router>
+                    <Routes>
+                        <Route path="" view=|| view! { <p>"home"</p> }/>
+                        <Route path="users/:id" view=|| view! { <p>"user"</p> }/>
+                        <StaticRoute
+                            path="blog/:slug"
+                            view=|| {
+                                let params = use_params_map();
+                                let title = create_resource(
+                                    move || params.get().get("slug").cloned(),
+                                    |slug| async move { slug.map(|slug| slug.to_uppercase()) },
+                                );
+                                view! {
+                                    <Suspense>
+                                        <p>{move || title.get()}</p>
+                                    </Suspense>
+                                }
+                            }
+                            static_params=|| Box::pin(async {
+                                let mut params = StaticParamsMap::new();
+                                params.insert("slug", vec!["hello".to_string()]);
+                                params
+                            })
+                        />
+                    </Routes>
+                </Router>
+            }
+        }
+
+        #[tokio::test]
+        async fn writes_enumerable_routes() {
+            let out_dir = std::env::temp_dir()
+                .join(format!("leptos-prerender-{}", std::process::id()));
+            let options = LeptosOptions::builder().output_name("app").build();
+            let (routes, static_data_map) = generate_route_list_inner(App);
+            let pages = tokio::task::LocalSet::new()
+                .run_until(prerender_routes(
+                    &options,
+                    App,
+                    &routes,
+                    &static_data_map,
+                    &out_dir,
+                ))
+                .await
+                .unwrap();
+
+            let mut paths =
+                pages.iter().map(|page| page.path.as_str()).collect::<Vec<_>>();
+            paths.sort();
+            assert_eq!(paths, ["/", "/blog/hello"]);
+
+            let home = std::fs::read_to_string(out_dir.join("index.html"))
+                .unwrap();
+            assert!(home.starts_with("<!DOCTYPE html>"));
+            assert!(home.contains(">home</p>"));
+            let post =
+                std::fs::read_to_string(out_dir.join("blog/hello.html")).unwrap();
+            assert!(post.contains("-->HELLO<!--"));
+            let resources = std::fs::read_to_string(
+                out_dir.join("blog/hello.resources.json"),
+            )
+            .unwrap();
+            assert!(resources.ends_with(r#","\"HELLO\""]]"#), "{resources}");
+
+            std::fs::remove_dir_all(out_dir).unwrap();
+        }
+    }
+}
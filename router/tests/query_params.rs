@@ -2,7 +2,10 @@ use cfg_if::cfg_if;
 
 cfg_if! {
     if #[cfg(feature = "ssr")] {
-        use leptos_router::{Url, params_map};
+        use leptos::QueryParams;
+        use leptos_router::{
+            params_map, Params, ParamsError, QueryParams, Url,
+        };
 
         macro_rules! assert_params_map {
             ([$($key:expr => $val:expr),*] , $actual:expr) => (
@@ -48,5 +51,80 @@ cfg_if! {
                 url.search_params
             };
         }
+
+        mod comma_separated {
+            use std::convert::Infallible;
+
+            pub fn parse(value: &str) -> Result<Vec<String>, Infallible> {
+                Ok(value.split(',').map(String::from).collect())
+            }
+
+            pub fn to_string(value: &[String]) -> String {
+                value.join(",")
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, QueryParams)]
+        struct Search {
+            #[query(rename = "q")]
+            text: Option<String>,
+            #[query(default = 1)]
+            page: usize,
+            #[query(default, with = comma_separated)]
+            tags: Vec<String>,
+            sort: String,
+        }
+
+        #[test]
+        fn query_params_from_map() {
+            assert_eq!(
+                Search::from_map(&params_map! { "sort" => "name" }),
+                Ok(Search {
+                    text: None,
+                    page: 1,
+                    tags: vec![],
+                    sort: "name".into(),
+                })
+            );
+            assert_eq!(
+                Search::from_map(&params_map! {
+                    "q" => "leptos",
+                    "page" => 3,
+                    "tags" => "rust,web",
+                    "sort" => "date",
+                }),
+                Ok(Search {
+                    text: Some("leptos".into()),
+                    page: 3,
+                    tags: vec!["rust".into(), "web".into()],
+                    sort: "date".into(),
+                })
+            );
+            assert_eq!(
+                Search::from_map(&params_map! {}),
+                Err(ParamsError::MissingParam("sort".into()))
+            );
+            assert!(Search::from_map(&params_map! {
+                "page" => "first",
+                "sort" => "name",
+            })
+            .is_err());
+        }
+
+        #[test]
+        fn query_params_to_map() {
+            assert_eq!(Search::KEYS, &["q", "page", "tags", "sort"]);
+            let search = Search {
+                text: None,
+                page: 2,
+                tags: vec!["a".into(), "b".into()],
+                sort: "name".into(),
+            };
+            assert_eq!(
+                search.to_map(),
+                params_map! { "page" => 2, "tags" => "a,b", "sort" => "name" }
+            );
+            assert_eq!(Search::from_map(&search.to_map()), Ok(search));
+        }
     }
 }
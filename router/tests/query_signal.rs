@@ -0,0 +1,46 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        #[component]
+        fn Products() -> impl IntoView {
+            let filter = query_signal::<String>("filter");
+            let page = query_signal::<u32>("page");
+            move || format!("[{}|{}]", filter.get(), page.get())
+        }
+
+        fn render_at(path: &str) -> String {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let html = view! {
+                <Router>
+                    <Routes>
+                        <Route path="/products" view=Products/>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            html
+        }
+
+        #[test]
+        fn query_signal_reads_url() {
+            let html = render_at("/products?filter=shoes&page=3");
+            assert!(html.contains("[shoes|3]"));
+        }
+
+        #[test]
+        fn query_signal_defaults_missing_or_invalid_values() {
+            let html = render_at("/products?page=abc");
+            assert!(html.contains("[|0]"));
+        }
+    }
+}
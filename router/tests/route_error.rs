@@ -0,0 +1,76 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+        use std::{cell::Cell, rc::Rc};
+
+        fn broken() -> Result<(), ServerFnError> {
+            Err(ServerFnError::new("oops"))
+        }
+
+        fn render_at(path: &str) -> (String, Option<u16>) {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            let status = Rc::new(Cell::new(None));
+            provide_server_status({
+                let status = Rc::clone(&status);
+                move |code| status.set(Some(code))
+            });
+            let html = view! {
+                <Router>
+                    <Routes>
+                        <Route
+                            path="/"
+                            view=|| view! { <main><Outlet/></main> }
+                            error_view=|_| view! { <p>"App error"</p> }
+                        >
+                            <Route path="" view=|| view! { <p>"Home"</p> }/>
+                            <Route
+                                path="broken"
+                                view=broken
+                            />
+                            <Route
+                                path="users/:id"
+                                view=broken
+                                error_view=RouteErrorView::new(|_| view! { <p>"User not found"</p> })
+                                    .status(404)
+                            />
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string()
+            .to_string();
+            runtime.dispose();
+            (html, status.get())
+        }
+
+        #[test]
+        fn routes_without_errors_render_normally() {
+            let (html, status) = render_at("/");
+            assert!(html.contains("Home"));
+            assert!(!html.contains("App error"));
+            assert_eq!(status, None);
+        }
+
+        #[test]
+        fn route_error_view_catches_its_errors() {
+            let (html, status) = render_at("/users/1");
+            assert!(html.contains("User not found"));
+            assert!(!html.contains("App error"));
+            assert_eq!(status, Some(404));
+        }
+
+        #[test]
+        fn errors_fall_through_to_parent_error_view() {
+            let (html, status) = render_at("/broken");
+            assert!(html.contains("App error"));
+            assert_eq!(status, Some(500));
+        }
+    }
+}
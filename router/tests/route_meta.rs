@@ -0,0 +1,76 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(all(feature = "ssr", feature = "meta"))] {
+        use leptos::*;
+        use leptos_meta::*;
+        use leptos_router::*;
+
+        fn head_at(path: &str) -> String {
+            let runtime = create_runtime();
+            provide_meta_context();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            _ = view! {
+                // overridden by the metadata of the routes
+                <Title template="%s — App"/>
+                <OpenGraph kind="profile"/>
+                <Meta name="description" content="An app"/>
+                <Router>
+                    <Routes>
+                        <Route
+                            path=""
+                            view=|| view! { <Outlet/> }
+                            title="Home"
+                            meta=RouteMeta {
+                                title_template: Some("{} | Site".into()),
+                                description: Some("A site".into()),
+                                og_type: Some("website".into()),
+                                ..Default::default()
+                            }
+                        >
+                            <Route path="" view=|| ()/>
+                            <Route
+                                path="users/:id"
+                                view=|| ()
+                                title=|params: &ParamsMap| format!("User {}", params.get("id").unwrap())
+                                meta=RouteMeta {
+                                    description: Some("A user".into()),
+                                    robots: Some("noindex".into()),
+                                    ..Default::default()
+                                }
+                            />
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string();
+            let head = use_head().dehydrate();
+            runtime.dispose();
+            head
+        }
+
+        #[test]
+        fn parent_meta_applies_to_child_routes() {
+            let head = head_at("/");
+            assert!(head.contains("<title>Home | Site</title>"));
+            assert!(head.contains(r#"content="A site""#));
+            assert_eq!(head.matches(r#"property="og:type""#).count(), 1);
+            assert!(!head.contains("profile"));
+            assert_eq!(head.matches(r#"name="description""#).count(), 1);
+            assert!(!head.contains("robots"));
+        }
+
+        #[test]
+        fn child_meta_overrides_parent() {
+            let head = head_at("/users/42");
+            assert!(head.contains("<title>User 42 | Site</title>"));
+            assert!(head.contains(r#"content="A user""#));
+            assert!(!head.contains(r#"content="A site""#));
+            assert!(head.contains(r#"content="website""#));
+            assert!(head.contains(r#"content="noindex""#));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::*;
+
+        fn is_server_only_at(path: &str) -> bool {
+            let runtime = create_runtime();
+            provide_context(RouterIntegrationContext::new(ServerIntegration {
+                path: format!("http://leptos.dev{path}"),
+            }));
+            _ = view! {
+                <Router>
+                    <Routes>
+                        <Route path="" view=|| ()/>
+                        <Route path="docs" view=|| view! { <Outlet/> } server_only=true>
+                            <Route path="" view=|| ()/>
+                            <Route path=":page" view=|| ()/>
+                        </Route>
+                    </Routes>
+                </Router>
+            }
+            .into_view()
+            .render_to_string();
+            let server_only = SharedContext::is_server_only();
+            runtime.dispose();
+            server_only
+        }
+
+        #[test]
+        fn other_routes_are_hydrated() {
+            assert!(!is_server_only_at("/"));
+        }
+
+        #[test]
+        fn server_only_applies_to_nested_routes() {
+            assert!(is_server_only_at("/docs"));
+            assert!(is_server_only_at("/docs/intro"));
+        }
+    }
+}
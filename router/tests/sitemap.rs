@@ -0,0 +1,86 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::*;
+        use leptos_router::{sitemap::ChangeFrequency, *};
+
+        #[component]
+        fn App() -> impl IntoView {
+            view! {
+                <Router>
+                    <Routes>
+                        <Route path="" view=|| ()/>
+                        <Route path="about" view=|| ()/>
+                        <Route path="users/:id" view=|| ()/>
+                        <Route path="admin" view=|| view! { <Outlet/> }>
+                            <Route path="settings" view=|| ()/>
+                        </Route>
+                        <StaticRoute
+                            path="blog/:slug"
+                            view=|| ()
+                            static_params=|| Box::pin(async {
+                                let mut params = StaticParamsMap::new();
+                                params.insert(
+                                    "slug",
+                                    vec!["hello".to_string(), "a&b".to_string()],
+                                );
+                                params
+                            })
+                        />
+                    </Routes>
+                </Router>
+            }
+        }
+
+        fn sitemap() -> sitemap::Sitemap {
+            let (routes, static_data_map) = generate_route_list_inner(App);
+            futures::executor::block_on(generate_sitemap(
+                "https://leptos.dev/",
+                &routes,
+                &static_data_map,
+            ))
+        }
+
+        #[test]
+        fn lists_enumerable_routes() {
+            let mut paths = sitemap()
+                .entries
+                .into_iter()
+                .map(|entry| entry.path)
+                .collect::<Vec<_>>();
+            paths.sort();
+            assert_eq!(
+                paths,
+                ["/", "/about", "/admin/settings", "/blog/a&b", "/blog/hello"]
+            );
+        }
+
+        #[test]
+        fn route_hooks_and_exclusions() {
+            let sitemap = sitemap()
+                .exclude("/admin")
+                .route("/blog/:slug", |entry| {
+                    entry.priority = Some(0.8);
+                    entry.changefreq = Some(ChangeFrequency::Weekly);
+                })
+                .route("/", |entry| entry.lastmod = Some("2024-05-01".into()));
+            let xml = sitemap.to_xml();
+            assert!(!xml.contains("/admin"));
+            assert!(xml.contains(
+                "<url>\n    <loc>https://leptos.dev/blog/a&amp;b</loc>\n    \
+                 <changefreq>weekly</changefreq>\n    \
+                 <priority>0.8</priority>\n  </url>"
+            ));
+            assert!(xml.contains(
+                "<loc>https://leptos.dev/</loc>\n    \
+                 <lastmod>2024-05-01</lastmod>"
+            ));
+            assert_eq!(
+                sitemap.robots_txt(["/admin/"]),
+                "User-agent: *\nDisallow: /admin/\n\n\
+                 Sitemap: https://leptos.dev/sitemap.xml\n"
+            );
+        }
+    }
+}
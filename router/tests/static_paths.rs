@@ -0,0 +1,61 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos_router::{params_map, StaticParamsMap, StaticPath};
+
+        fn paths(path: &str, params: &[&StaticParamsMap]) -> Vec<String> {
+            let mut path = StaticPath::new(path);
+            for params in params {
+                path.add_params(params);
+            }
+            path.into_paths().into_iter().map(|p| p.0).collect()
+        }
+
+        #[test]
+        fn static_paths_from_lists() {
+            let mut params = StaticParamsMap::new();
+            params.insert("lang", vec!["en".into(), "fr".into()]);
+            params.insert("id", vec!["1".into(), "2".into()]);
+            assert_eq!(
+                paths("/:lang/posts/:id", &[&params]),
+                ["/en/posts/1", "/en/posts/2", "/fr/posts/1", "/fr/posts/2"]
+            );
+        }
+
+        #[test]
+        fn static_paths_from_combinations() {
+            let params = [("2023", "hello"), ("2024", "leptos")]
+                .iter()
+                .map(|(year, slug)| params_map! { "year" => year, "slug" => slug })
+                .collect::<StaticParamsMap>();
+            assert_eq!(
+                paths("/blog/:year/:slug", &[&params]),
+                ["/blog/2023/hello", "/blog/2024/leptos"]
+            );
+        }
+
+        #[test]
+        fn static_paths_fill_combinations_from_lists() {
+            let mut parent = StaticParamsMap::new();
+            parent.insert("lang", vec!["en".into(), "fr".into()]);
+            let mut child = StaticParamsMap::new();
+            child.push(params_map! { "slug" => "hello" });
+            assert_eq!(
+                paths("/:lang/:slug", &[&parent, &child]),
+                ["/en/hello", "/fr/hello"]
+            );
+        }
+
+        #[test]
+        fn static_paths_skip_missing_optional_params() {
+            let mut params = StaticParamsMap::new();
+            params.push(params_map! {});
+            params.push(params_map! { "page" => 2 });
+            assert_eq!(
+                paths("/archive/:page?", &[&params]),
+                ["/archive", "/archive/2"]
+            );
+        }
+    }
+}
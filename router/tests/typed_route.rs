@@ -0,0 +1,62 @@
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "ssr")] {
+        use leptos::TypedRoute;
+        use leptos_router::{params_map, Params, ParamsError, TypedRoute};
+
+        #[derive(Debug, Clone, PartialEq, TypedRoute)]
+        #[route(path = "/users/:id/posts/:post?")]
+        struct UserPost {
+            id: usize,
+            post: Option<String>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, TypedRoute)]
+        #[route(path = "/files/*path")]
+        struct File {
+            path: String,
+        }
+
+        #[derive(Debug, Clone, PartialEq, TypedRoute)]
+        #[route(path = "/")]
+        struct Home;
+
+        #[test]
+        fn typed_route_builds_path() {
+            assert_eq!(UserPost::PATH, "/users/:id/posts/:post?");
+            assert_eq!(
+                UserPost { id: 7, post: None }.to_path(),
+                "/users/7/posts"
+            );
+            assert_eq!(
+                UserPost { id: 7, post: Some("hello world".into()) }.to_path(),
+                "/users/7/posts/hello%20world"
+            );
+            // escaped like `encodeURIComponent`, as in the browser
+            assert_eq!(
+                UserPost { id: 7, post: Some("a-b_c.d~(e)/f".into()) }.to_path(),
+                "/users/7/posts/a-b_c.d~(e)%2Ff"
+            );
+            assert_eq!(File { path: "a/b.txt".into() }.to_path(), "/files/a/b.txt");
+            assert_eq!(Home.to_path(), "/");
+        }
+
+        #[test]
+        fn typed_route_parses_params() {
+            assert_eq!(
+                UserPost::from_map(&params_map! { "id" => 7, "post" => "x" }),
+                Ok(UserPost { id: 7, post: Some("x".into()) })
+            );
+            assert_eq!(
+                UserPost::from_map(&params_map! { "id" => 7 }),
+                Ok(UserPost { id: 7, post: None })
+            );
+            assert_eq!(
+                UserPost::from_map(&params_map! { "post" => "x" }),
+                Err(ParamsError::MissingParam("id".into()))
+            );
+            assert!(UserPost::from_map(&params_map! { "id" => "seven" }).is_err());
+        }
+    }
+}
@@ -0,0 +1,712 @@
+//! Server functions can be called through a [`BatchClient`], which combines
+//! calls that are made at the same time (within the same tick of the async
+//! runtime, like those made while a page loads) into a single request:
+//!
+//! ```rust,ignore
+//! #[server(batch = true)]
+//! pub async fn get_user(id: u32) -> Result<User, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! #[server(batch = true)]
+//! pub async fn get_notifications() -> Result<Vec<Notification>, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! // both are sent to the server in one request
+//! let (user, notifications) = futures::join!(get_user(1), get_notifications());
+//! ```
+//!
+//! The batch is sent to [`BATCH_PATH`], where the server integration runs each
+//! call with the headers of the batch request, and responds with the response
+//! of each call, so that every call still returns its own result or error.
+//! Each call still runs through the middleware of its server function, but
+//! only the status code, body, and redirect of its response are kept. Headers
+//! and cookies set with the response options of the server integration apply
+//! to the batch as a whole.
+//!
+//! Batches are sent with the client of the first call in them, and hold up to
+//! [`MAX_BATCH_SIZE`] calls. Server functions that stream their arguments or
+//! take multipart form data cannot be batched.
+
+use crate::{
+    client::Client,
+    error::{NoCustomError, ServerFnError, ServerFnErrorSerde},
+    request::ClientReq,
+    response::ClientRes,
+};
+use bytes::Bytes;
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt, Stream,
+};
+use http::{Method, StatusCode};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::Infallible,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The path that batches of server function calls are sent to.
+pub const BATCH_PATH: &str = "/api/__batch";
+
+/// The largest number of calls that are sent to the server in one batch.
+pub const MAX_BATCH_SIZE: usize = 64;
+
+const JSON: &str = "application/json";
+
+/// The body of a batched request or response, which is sent as a string if it
+/// is text, and as an array of bytes otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BatchBody {
+    /// A UTF-8 body.
+    Text(String),
+    /// A binary body.
+    Bytes(Vec<u8>),
+}
+
+impl BatchBody {
+    /// Returns the body as bytes.
+    pub fn into_bytes(self) -> Bytes {
+        match self {
+            BatchBody::Text(text) => text.into(),
+            BatchBody::Bytes(bytes) => bytes.into(),
+        }
+    }
+}
+
+impl From<Bytes> for BatchBody {
+    fn from(bytes: Bytes) -> Self {
+        match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => BatchBody::Text(text),
+            Err(e) => BatchBody::Bytes(e.into_bytes()),
+        }
+    }
+}
+
+/// A call to a server function that is sent to the server as part of a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRequest {
+    /// The HTTP method of the call.
+    pub method: String,
+    /// The path of the server function.
+    pub path: String,
+    /// The query string of the call, if any.
+    pub query: Option<String>,
+    /// The `Content-Type` of the arguments.
+    pub content_type: String,
+    /// The `Accept` header of the call.
+    pub accepts: String,
+    /// The encoded arguments.
+    pub body: BatchBody,
+}
+
+impl BatchRequest {
+    fn new(
+        method: Method,
+        path: &str,
+        query: Option<&str>,
+        content_type: &str,
+        accepts: &str,
+        body: BatchBody,
+    ) -> Self {
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query.map(ToString::to_string),
+            content_type: content_type.to_string(),
+            accepts: accepts.to_string(),
+            body,
+        }
+    }
+
+    /// The path and query string of the call.
+    pub fn uri(&self) -> String {
+        match &self.query {
+            Some(query) => format!("{}?{query}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+impl<CustErr> ClientReq<CustErr> for BatchRequest {
+    type FormData = Infallible;
+
+    fn try_new_get(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::GET,
+            path,
+            Some(query),
+            content_type,
+            accepts,
+            BatchBody::Text(String::new()),
+        ))
+    }
+
+    fn try_new_post(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::POST,
+            path,
+            None,
+            content_type,
+            accepts,
+            BatchBody::Text(body),
+        ))
+    }
+
+    fn try_new_delete(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::DELETE,
+            path,
+            Some(query),
+            content_type,
+            accepts,
+            BatchBody::Text(String::new()),
+        ))
+    }
+
+    fn try_new_put(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::PUT,
+            path,
+            None,
+            content_type,
+            accepts,
+            BatchBody::Text(body),
+        ))
+    }
+
+    fn try_new_patch(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::PATCH,
+            path,
+            None,
+            content_type,
+            accepts,
+            BatchBody::Text(body),
+        ))
+    }
+
+    fn try_new_post_bytes(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::POST,
+            path,
+            None,
+            content_type,
+            accepts,
+            body.into(),
+        ))
+    }
+
+    fn try_new_post_form_data(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        match body {}
+    }
+
+    fn try_new_multipart(
+        _path: &str,
+        _accepts: &str,
+        body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        match body {}
+    }
+
+    fn try_new_streaming(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Err(ServerFnError::Request(
+            "streaming requests cannot be batched".into(),
+        ))
+    }
+}
+
+/// The response to a call to a server function that was made as part of a
+/// batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The `Location` header of the response, if any.
+    pub location: Option<String>,
+    /// Whether the response has the [`REDIRECT_HEADER`](crate::redirect::REDIRECT_HEADER)
+    /// or a `Location` header.
+    pub redirect: bool,
+    /// The body of the response.
+    pub body: BatchBody,
+}
+
+impl BatchResponse {
+    /// Creates the response to a call that failed with the given error.
+    pub fn error(err: &ServerFnError, status: u16) -> Self {
+        Self {
+            status,
+            location: None,
+            redirect: false,
+            body: BatchBody::Text(err.ser().unwrap_or_default()),
+        }
+    }
+}
+
+impl<CustErr> ClientRes<CustErr> for BatchResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        match self.body {
+            BatchBody::Text(text) => Ok(text),
+            BatchBody::Bytes(bytes) => String::from_utf8(bytes)
+                .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+        }
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        Ok(self.body.into_bytes())
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(futures::stream::once(futures::future::ready(Ok(self
+            .body
+            .into_bytes()))))
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn status_text(&self) -> String {
+        StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn location(&self) -> String {
+        self.location.clone().unwrap_or_default()
+    }
+
+    fn has_redirect(&self) -> bool {
+        self.redirect
+    }
+}
+
+/// A [`Client`] that sends calls made at the same time in a single request to
+/// [`BATCH_PATH`], with the client `C`. See the [module docs](self).
+pub struct BatchClient<C>(PhantomData<C>);
+
+// the responses to a batch, once it has been sent
+type Flush =
+    Shared<BoxFuture<'static, Result<Arc<Vec<BatchResponse>>, ServerFnError>>>;
+
+type Calls = Arc<Mutex<Vec<BatchRequest>>>;
+
+// the batch that calls are currently added to
+static PENDING: Lazy<Mutex<Option<(Calls, Flush)>>> =
+    Lazy::new(Default::default);
+
+impl<CustErr, C> Client<CustErr> for BatchClient<C>
+where
+    C: Client<NoCustomError> + 'static,
+{
+    type Request = BatchRequest;
+    type Response = BatchResponse;
+
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send
+    {
+        let (index, flush) = {
+            let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+            let (calls, flush) = match pending.as_ref() {
+                Some((calls, flush))
+                    if calls
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .len()
+                        < MAX_BATCH_SIZE =>
+                {
+                    (Arc::clone(calls), flush.clone())
+                }
+                _ => {
+                    let calls = Calls::default();
+                    let flush = flush::<C>(Arc::clone(&calls));
+                    *pending = Some((Arc::clone(&calls), flush.clone()));
+                    (calls, flush)
+                }
+            };
+            let mut calls = calls.lock().unwrap_or_else(|e| e.into_inner());
+            calls.push(req);
+            (calls.len() - 1, flush)
+        };
+        async move {
+            // every call made at the same time is added before the batch is
+            // sent by whichever of them polls it first
+            YieldNow(false).await;
+            let responses = flush.await.map_err(ServerFnError::into_custom)?;
+            responses.get(index).cloned().ok_or_else(|| {
+                ServerFnError::Deserialization(
+                    "the batch response is missing a call".into(),
+                )
+            })
+        }
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        C::sleep(duration)
+    }
+}
+
+// takes the calls out of the pending batch, and sends them
+fn flush<C>(calls: Calls) -> Flush
+where
+    C: Client<NoCustomError> + 'static,
+{
+    async move {
+        let calls = {
+            let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+            if pending
+                .as_ref()
+                .is_some_and(|(pending, _)| Arc::ptr_eq(pending, &calls))
+            {
+                *pending = None;
+            }
+            let mut calls = calls.lock().unwrap_or_else(|e| e.into_inner());
+            mem::take(&mut *calls)
+        };
+        send::<C>(calls).await.map(Arc::new)
+    }
+    .boxed()
+    .shared()
+}
+
+async fn send<C>(
+    calls: Vec<BatchRequest>,
+) -> Result<Vec<BatchResponse>, ServerFnError>
+where
+    C: Client<NoCustomError>,
+{
+    let body = serde_json::to_string(&calls).map_err(|e| {
+        ServerFnError::<NoCustomError>::Serialization(e.to_string())
+    })?;
+    let req = C::Request::try_new_post(BATCH_PATH, JSON, JSON, body)?;
+    let res = C::send(req).await?;
+    let status = res.status();
+    let text = res.try_into_string().await?;
+    if (400..=599).contains(&status) {
+        return Err(ServerFnError::de(&text));
+    }
+    let responses =
+        serde_json::from_str::<Vec<BatchResponse>>(&text).map_err(|e| {
+            ServerFnError::<NoCustomError>::Deserialization(e.to_string())
+        })?;
+    Ok(responses)
+}
+
+// returns `Pending` once, so that other tasks can run before it completes
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Responds to a batch of calls, by running each of them with `run` and
+/// responding with their responses in the same order.
+#[allow(unused)] // used by server integrations
+pub(crate) async fn handle_batch<Req, Res, Fut>(
+    req: Req,
+    run: impl Fn(BatchRequest) -> Fut,
+) -> Res
+where
+    Req: crate::request::Req<NoCustomError>,
+    Res: crate::response::Res<NoCustomError>,
+    Fut: Future<Output = BatchResponse>,
+{
+    let calls = match req.try_into_bytes().await.and_then(|body| {
+        serde_json::from_slice::<Vec<BatchRequest>>(&body)
+            .map_err(|e| ServerFnError::Args(e.to_string()))
+    }) {
+        Ok(calls) if calls.len() > MAX_BATCH_SIZE => {
+            return Res::error_response_with_status(
+                BATCH_PATH,
+                &ServerFnError::Args(format!(
+                    "a batch can have at most {MAX_BATCH_SIZE} calls"
+                )),
+                400,
+            )
+        }
+        Ok(calls) => calls,
+        Err(e) => return Res::error_response_with_status(BATCH_PATH, &e, 400),
+    };
+    let responses = futures::future::join_all(calls.into_iter().map(|call| {
+        // batches cannot be nested
+        let res = (call.path != BATCH_PATH).then(|| run(call));
+        async move {
+            match res {
+                Some(res) => res.await,
+                None => BatchResponse::error(
+                    &ServerFnError::Args("batches cannot be nested".into()),
+                    400,
+                ),
+            }
+        }
+    }))
+    .await;
+    match serde_json::to_string(&responses) {
+        Ok(json) => Res::try_from_string(JSON, json)
+            .unwrap_or_else(|e| Res::error_response(BATCH_PATH, &e)),
+        Err(e) => Res::error_response(
+            BATCH_PATH,
+            &ServerFnError::Serialization(e.to_string()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // the pending batch is shared, so calls made by different tests at the
+    // same time would be sent together
+    static BATCHING: Mutex<()> = Mutex::new(());
+
+    static BATCHES_SENT: AtomicUsize = AtomicUsize::new(0);
+
+    // responds to each call in a batch with its own body, or with an error
+    // for calls to `/api/fail`
+    struct EchoClient;
+
+    impl Client<NoCustomError> for EchoClient {
+        type Request = BatchRequest;
+        type Response = BatchResponse;
+
+        async fn send(
+            req: BatchRequest,
+        ) -> Result<BatchResponse, ServerFnError> {
+            BATCHES_SENT.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(req.path, BATCH_PATH);
+            let calls = serde_json::from_slice::<Vec<BatchRequest>>(
+                &req.body.into_bytes(),
+            )
+            .unwrap();
+            let responses = calls
+                .into_iter()
+                .map(|call| match call.path.as_str() {
+                    "/api/fail" => BatchResponse::error(
+                        &ServerFnError::ServerError("failed".into()),
+                        500,
+                    ),
+                    _ => BatchResponse {
+                        status: 200,
+                        location: None,
+                        redirect: false,
+                        body: call.body,
+                    },
+                })
+                .collect::<Vec<_>>();
+            Ok(BatchResponse {
+                status: 200,
+                location: None,
+                redirect: false,
+                body: BatchBody::Text(
+                    serde_json::to_string(&responses).unwrap(),
+                ),
+            })
+        }
+    }
+
+    fn call(
+        path: &str,
+        body: &str,
+    ) -> impl Future<Output = Result<BatchResponse, ServerFnError>> + Send {
+        let req = <BatchRequest as ClientReq<NoCustomError>>::try_new_post(
+            path,
+            JSON,
+            JSON,
+            body.to_string(),
+        )
+        .unwrap();
+        <BatchClient<EchoClient> as Client<NoCustomError>>::send(req)
+    }
+
+    fn body(res: Result<BatchResponse, ServerFnError>) -> BatchBody {
+        res.unwrap().body
+    }
+
+    #[test]
+    fn calls_made_at_the_same_time_are_sent_together() {
+        let _batching = BATCHING.lock().unwrap_or_else(|e| e.into_inner());
+        let sent = BATCHES_SENT.load(Ordering::SeqCst);
+
+        let (a, b, c) = block_on(futures::future::join3(
+            call("/api/a", "1"),
+            call("/api/b", "2"),
+            call("/api/c", "3"),
+        ));
+        assert_eq!(BATCHES_SENT.load(Ordering::SeqCst), sent + 1);
+        assert_eq!(body(a), BatchBody::Text("1".into()));
+        assert_eq!(body(b), BatchBody::Text("2".into()));
+        assert_eq!(body(c), BatchBody::Text("3".into()));
+
+        // calls made later are sent in a batch of their own
+        assert_eq!(
+            body(block_on(call("/api/a", "4"))),
+            BatchBody::Text("4".into())
+        );
+        assert_eq!(BATCHES_SENT.load(Ordering::SeqCst), sent + 2);
+    }
+
+    #[test]
+    fn a_failing_call_does_not_fail_the_others() {
+        let _batching = BATCHING.lock().unwrap_or_else(|e| e.into_inner());
+
+        let (a, fail, b) = block_on(futures::future::join3(
+            call("/api/a", "1"),
+            call("/api/fail", "2"),
+            call("/api/b", "3"),
+        ));
+        assert_eq!(body(a), BatchBody::Text("1".into()));
+        assert_eq!(body(b), BatchBody::Text("3".into()));
+
+        let fail = fail.unwrap();
+        assert_eq!(fail.status, 500);
+        let text = block_on(ClientRes::<NoCustomError>::try_into_string(fail))
+            .unwrap();
+        assert_eq!(
+            ServerFnError::<NoCustomError>::de(&text),
+            ServerFnError::ServerError("failed".into())
+        );
+    }
+
+    #[test]
+    fn large_batches_are_split() {
+        let _batching = BATCHING.lock().unwrap_or_else(|e| e.into_inner());
+        let sent = BATCHES_SENT.load(Ordering::SeqCst);
+
+        let bodies = (0..MAX_BATCH_SIZE + 1)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        let responses = block_on(futures::future::join_all(
+            bodies.iter().map(|body| call("/api/a", body)),
+        ));
+        assert_eq!(BATCHES_SENT.load(Ordering::SeqCst), sent + 2);
+        for (res, body) in responses.into_iter().zip(bodies) {
+            assert_eq!(res.unwrap().body, BatchBody::Text(body));
+        }
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    #[test]
+    fn server_responds_to_each_call_in_order() {
+        use ::axum::body::Body;
+        use http::{Request, Response};
+
+        let calls =
+            ["/api/slow", "/api/fail", BATCH_PATH, "/api/fast"].map(|path| {
+                <BatchRequest as ClientReq<NoCustomError>>::try_new_post(
+                    path,
+                    JSON,
+                    JSON,
+                    path.into(),
+                )
+                .unwrap()
+            });
+        let req = Request::post(BATCH_PATH)
+            .body(Body::from(serde_json::to_string(&calls).unwrap()))
+            .unwrap();
+
+        let res: Response<Body> =
+            block_on(handle_batch(req, |call| async move {
+                match call.path.as_str() {
+                    // finishes after the calls that follow it
+                    "/api/slow" => {
+                        YieldNow(false).await;
+                        YieldNow(false).await;
+                    }
+                    "/api/fail" => {
+                        return BatchResponse::error(
+                            &ServerFnError::ServerError("failed".into()),
+                            500,
+                        )
+                    }
+                    _ => {}
+                }
+                BatchResponse {
+                    status: 200,
+                    location: None,
+                    redirect: false,
+                    body: call.body,
+                }
+            }));
+        assert_eq!(res.status(), 200);
+
+        let body =
+            block_on(::axum::body::to_bytes(res.into_body(), usize::MAX))
+                .unwrap();
+        let responses =
+            serde_json::from_slice::<Vec<BatchResponse>>(&body).unwrap();
+        let statuses =
+            responses.iter().map(|res| res.status).collect::<Vec<_>>();
+        assert_eq!(statuses, [200, 500, 400, 200]);
+        assert_eq!(responses[0].body, BatchBody::Text("/api/slow".into()));
+        assert_eq!(responses[3].body, BatchBody::Text("/api/fast".into()));
+    }
+}
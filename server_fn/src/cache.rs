@@ -0,0 +1,136 @@
+use crate::{error::ServerFnError, ServerFn};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{any::Any, collections::HashMap, sync::Mutex, time::Duration};
+
+// calls are keyed by the server function's path and its serialized arguments
+type Key = (&'static str, String);
+
+static CACHE: Lazy<Mutex<HashMap<Key, Box<dyn Any + Send>>>> =
+    Lazy::new(Default::default);
+
+// a call that is in flight, or has completed at the given time
+type Call<S> = Shared<
+    BoxFuture<
+        'static,
+        (
+            Result<
+                <S as ServerFn>::Output,
+                ServerFnError<<S as ServerFn>::Error>,
+            >,
+            Duration,
+        ),
+    >,
+>;
+
+/// Calls the server function from the client, through the cache of calls to
+/// server functions declared with `#[server(cache = <ttl>)]`.
+///
+/// A call with the same arguments as one that is still in flight shares its
+/// response, rather than making another request. A successful result is then
+/// reused for calls with the same arguments until its
+/// [`cache_ttl`](ServerFn::cache_ttl) has passed, or it is cleared with
+/// [`invalidate`]. Server functions without a TTL are always called.
+pub async fn run_cached<S>(
+    server_fn: S,
+) -> Result<S::Output, ServerFnError<S::Error>>
+where
+    S: ServerFn + Clone + Serialize + 'static,
+    S::Output: Clone + Sync + 'static,
+    S::Error: Clone + Send + Sync + 'static,
+{
+    let Some((ttl, key)) = S::cache_ttl().zip(key(&server_fn)) else {
+        return server_fn.run_on_client_with_retries().await;
+    };
+    let call = {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let cached = cache
+            .get(&key)
+            .and_then(|call| call.downcast_ref::<Call<S>>())
+            .filter(|call| match call.peek() {
+                None => true,
+                Some((Ok(_), completed_at)) => now() < *completed_at + ttl,
+                Some((Err(_), _)) => false,
+            })
+            .cloned();
+        match cached {
+            Some(call) => call,
+            None => {
+                let call: Call<S> = async move {
+                    let res = server_fn.run_on_client_with_retries().await;
+                    (res, now())
+                }
+                .boxed()
+                .shared();
+                cache.insert(key, Box::new(call.clone()));
+                call
+            }
+        }
+    };
+    call.await.0
+}
+
+/// Stores `value` as the cached result of calling the server function with
+/// `args`, as if it had just been returned by the server.
+///
+/// This is used to seed the cache with results that were serialized during
+/// server-side rendering, so that the client does not request them again.
+pub fn seed<S>(args: &S, value: S::Output)
+where
+    S: ServerFn + Serialize + 'static,
+    S::Output: Clone + Sync + 'static,
+    S::Error: Clone + Send + Sync + 'static,
+{
+    if let Some(key) = key(args) {
+        let call: Call<S> =
+            futures::future::ready((Ok(value), now())).boxed().shared();
+        CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Box::new(call));
+    }
+}
+
+/// Clears the cached result of calling the server function with `args`, so
+/// that the next call with them makes a new request.
+pub fn invalidate<S>(args: &S)
+where
+    S: ServerFn + Serialize,
+{
+    if let Some(key) = key(args) {
+        CACHE.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+    }
+}
+
+/// Clears every cached result of calling the server function, whatever its
+/// arguments.
+pub fn invalidate_all<S>()
+where
+    S: ServerFn,
+{
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(path, _), _| *path != S::PATH);
+}
+
+fn key<S>(args: &S) -> Option<Key>
+where
+    S: ServerFn + Serialize,
+{
+    serde_json::to_string(args).ok().map(|args| (S::PATH, args))
+}
+
+// the time since the Unix epoch, as `std::time` is not available in the browser
+#[cfg(all(feature = "browser", target_arch = "wasm32"))]
+fn now() -> Duration {
+    Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+}
+
+#[cfg(not(all(feature = "browser", target_arch = "wasm32")))]
+fn now() -> Duration {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+}
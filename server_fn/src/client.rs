@@ -1,5 +1,7 @@
 use crate::{error::ServerFnError, request::ClientReq, response::ClientRes};
-use std::{future::Future, sync::OnceLock};
+use std::{future::Future, sync::OnceLock, time::Duration};
+
+pub mod transport;
 
 static ROOT_URL: OnceLock<&'static str> = OnceLock::new();
 
@@ -31,6 +33,20 @@ pub trait Client<CustErr> {
     fn send(
         req: Self::Request,
     ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send;
+
+    /// Waits for the given duration, as between retries of a failed request.
+    ///
+    /// By default, this waits on a separate thread.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        let (tx, rx) = futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(duration);
+            _ = tx.send(());
+        });
+        async move {
+            _ = rx.await;
+        }
+    }
 }
 
 #[cfg(feature = "browser")]
@@ -38,11 +54,102 @@ pub trait Client<CustErr> {
 pub mod browser {
     use super::Client;
     use crate::{
-        error::ServerFnError, request::browser::BrowserRequest,
+        error::ServerFnError,
+        request::browser::{BrowserRequest, Request},
         response::browser::BrowserResponse,
     };
+    use gloo_net::http::Response;
     use send_wrapper::SendWrapper;
-    use std::future::Future;
+    use std::{cell::RefCell, future::Future, rc::Rc, time::Duration};
+    use wasm_bindgen::JsValue;
+    use web_sys::{AbortController, RequestInit};
+
+    type RequestInterceptor = Rc<dyn Fn(&Request) -> Result<(), ServerFnError>>;
+    type ResponseInterceptor =
+        Rc<dyn Fn(&Response) -> Result<(), ServerFnError>>;
+
+    thread_local! {
+        static REQUEST_INTERCEPTORS: RefCell<Vec<RequestInterceptor>> =
+            Default::default();
+        static RESPONSE_INTERCEPTORS: RefCell<Vec<ResponseInterceptor>> =
+            Default::default();
+    }
+
+    /// Registers a function that is called with every server function request
+    /// made by the [`BrowserClient`], before it is sent.
+    ///
+    /// It can modify the request's headers, e.g., to attach an authorization
+    /// token or tracing headers, or return an error to cancel the request,
+    /// which the server function call then returns.
+    ///
+    /// ```rust,ignore
+    /// intercept_requests(|req| {
+    ///     if let Some(token) = auth_token() {
+    ///         req.headers().set("Authorization", &format!("Bearer {token}"));
+    ///     }
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn intercept_requests(
+        interceptor: impl Fn(&Request) -> Result<(), ServerFnError> + 'static,
+    ) {
+        REQUEST_INTERCEPTORS.with(|interceptors| {
+            interceptors.borrow_mut().push(Rc::new(interceptor))
+        });
+    }
+
+    /// Registers a function that is called with every response received by
+    /// the [`BrowserClient`], before it is deserialized.
+    ///
+    /// It can observe the response, or return an error to short-circuit the
+    /// server function call, which then returns that error.
+    ///
+    /// ```rust,ignore
+    /// intercept_responses(|res| {
+    ///     if res.status() == 401 {
+    ///         _ = window().location().set_href("/login");
+    ///         return Err(ServerFnError::new("not logged in"));
+    ///     }
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn intercept_responses(
+        interceptor: impl Fn(&Response) -> Result<(), ServerFnError> + 'static,
+    ) {
+        RESPONSE_INTERCEPTORS.with(|interceptors| {
+            interceptors.borrow_mut().push(Rc::new(interceptor))
+        });
+    }
+
+    // aborts the `fetch` request if the call is dropped before the response
+    // has been received, e.g., through its `AbortHandle`
+    struct AbortOnDrop(Option<AbortController>);
+
+    impl AbortOnDrop {
+        // returns a copy of the request that can be aborted
+        fn attach(req: Request) -> Result<(Request, Self), JsValue> {
+            let controller = AbortController::new()?;
+            let init = RequestInit::new();
+            init.set_signal(Some(&controller.signal()));
+            let req = web_sys::Request::new_with_request_and_init(
+                &req.into(),
+                &init,
+            )?;
+            Ok((req.into(), Self(Some(controller))))
+        }
+
+        fn disarm(mut self) {
+            self.0 = None;
+        }
+    }
+
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            if let Some(controller) = self.0.take() {
+                controller.abort();
+            }
+        }
+    }
 
     /// Implements [`Client`] for a `fetch` request in the browser.    
     pub struct BrowserClient;
@@ -56,12 +163,66 @@ pub mod browser {
         ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>>
                + Send {
             SendWrapper::new(async move {
-                req.0
-                    .take()
-                    .send()
-                    .await
-                    .map(|res| BrowserResponse(SendWrapper::new(res)))
-                    .map_err(|e| ServerFnError::Request(e.to_string()))
+                let BrowserRequest(req, upload) = req;
+                let req = req.take();
+                #[cfg(feature = "tracing")]
+                if let Some(trace_parent) = crate::trace::trace_parent() {
+                    req.headers()
+                        .set(crate::trace::TRACE_PARENT_HEADER, &trace_parent);
+                }
+                #[cfg(feature = "csrf")]
+                req.headers().set(
+                    crate::csrf::HEADER_NAME,
+                    &crate::csrf::browser_token()
+                        .map_err(ServerFnError::into_custom)?,
+                );
+                let interceptors = REQUEST_INTERCEPTORS
+                    .with(|interceptors| interceptors.borrow().clone());
+                for interceptor in interceptors {
+                    interceptor(&req).map_err(ServerFnError::into_custom)?;
+                }
+
+                let res = match upload {
+                    Some(upload) => {
+                        upload.take().send(req).await.map_err(|e| {
+                            ServerFnError::Request(format!("{e:?}"))
+                        })
+                    }
+                    None => {
+                        let (req, abort) =
+                            AbortOnDrop::attach(req).map_err(|e| {
+                                ServerFnError::Request(format!("{e:?}"))
+                            })?;
+                        let res = req
+                            .send()
+                            .await
+                            .map_err(|e| ServerFnError::Request(e.to_string()));
+                        abort.disarm();
+                        res
+                    }
+                }?;
+
+                let interceptors = RESPONSE_INTERCEPTORS
+                    .with(|interceptors| interceptors.borrow().clone());
+                for interceptor in interceptors {
+                    interceptor(&res).map_err(ServerFnError::into_custom)?;
+                }
+                Ok(BrowserResponse(SendWrapper::new(res)))
+            })
+        }
+
+        fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+            SendWrapper::new(async move {
+                let promise = js_sys::Promise::new(&mut |resolve, _| {
+                    if let Some(window) = web_sys::window() {
+                        _ = window
+                            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                                &resolve,
+                                duration.as_millis().try_into().unwrap_or(i32::MAX),
+                            );
+                    }
+                });
+                _ = wasm_bindgen_futures::JsFuture::from(promise).await;
             })
         }
     }
@@ -72,9 +233,54 @@ pub mod browser {
 pub mod reqwest {
     use super::Client;
     use crate::{error::ServerFnError, request::reqwest::CLIENT};
-    use futures::TryFutureExt;
+    use once_cell::sync::Lazy;
     use reqwest::{Request, Response};
-    use std::future::Future;
+    use std::sync::{Arc, RwLock};
+
+    type RequestInterceptor =
+        Arc<dyn Fn(&mut Request) -> Result<(), ServerFnError> + Send + Sync>;
+    type ResponseInterceptor =
+        Arc<dyn Fn(&Response) -> Result<(), ServerFnError> + Send + Sync>;
+
+    static REQUEST_INTERCEPTORS: Lazy<RwLock<Vec<RequestInterceptor>>> =
+        Lazy::new(Default::default);
+    static RESPONSE_INTERCEPTORS: Lazy<RwLock<Vec<ResponseInterceptor>>> =
+        Lazy::new(Default::default);
+
+    /// Registers a function that is called with every server function request
+    /// made by the [`ReqwestClient`], before it is sent.
+    ///
+    /// It can modify the request, e.g., to attach an authorization token or
+    /// tracing headers, or return an error to cancel the request, which the
+    /// server function call then returns.
+    pub fn intercept_requests(
+        interceptor: impl Fn(&mut Request) -> Result<(), ServerFnError>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        REQUEST_INTERCEPTORS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(interceptor));
+    }
+
+    /// Registers a function that is called with every response received by
+    /// the [`ReqwestClient`], before it is deserialized.
+    ///
+    /// It can observe the response, or return an error to short-circuit the
+    /// server function call, which then returns that error.
+    pub fn intercept_responses(
+        interceptor: impl Fn(&Response) -> Result<(), ServerFnError>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        RESPONSE_INTERCEPTORS
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Arc::new(interceptor));
+    }
 
     /// Implements [`Client`] for a request made by [`reqwest`].
     pub struct ReqwestClient;
@@ -83,13 +289,60 @@ pub mod reqwest {
         type Request = Request;
         type Response = Response;
 
-        fn send(
-            req: Self::Request,
-        ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>>
-               + Send {
-            CLIENT
+        async fn send(
+            mut req: Self::Request,
+        ) -> Result<Self::Response, ServerFnError<CustErr>> {
+            // without a cookie jar, each request carries its own token, as
+            // both the cookie and the header
+            #[cfg(feature = "csrf")]
+            {
+                use reqwest::header::{HeaderValue, COOKIE};
+
+                let token = crate::csrf::new_token()
+                    .map_err(ServerFnError::into_custom)?;
+                let header = |value: &str| {
+                    HeaderValue::from_str(value)
+                        .map_err(|e| ServerFnError::Request(e.to_string()))
+                };
+                let cookie =
+                    header(&format!("{}={token}", crate::csrf::COOKIE_NAME))?;
+                let token = header(&token)?;
+                let headers = req.headers_mut();
+                headers.append(COOKIE, cookie);
+                headers.insert(crate::csrf::HEADER_NAME, token);
+            }
+
+            #[cfg(feature = "tracing")]
+            if let Some(trace_parent) =
+                crate::trace::trace_parent().and_then(|value| {
+                    reqwest::header::HeaderValue::from_str(&value).ok()
+                })
+            {
+                req.headers_mut()
+                    .insert(crate::trace::TRACE_PARENT_HEADER, trace_parent);
+            }
+
+            let interceptors = REQUEST_INTERCEPTORS
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            for interceptor in interceptors {
+                interceptor(&mut req).map_err(ServerFnError::into_custom)?;
+            }
+
+            let res = CLIENT
                 .execute(req)
-                .map_err(|e| ServerFnError::Request(e.to_string()))
+                .await
+                .map_err(|e| ServerFnError::Request(e.to_string()))?;
+
+            let interceptors = RESPONSE_INTERCEPTORS
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone();
+            for interceptor in interceptors {
+                interceptor(&res).map_err(ServerFnError::into_custom)?;
+            }
+            Ok(res)
         }
     }
 }
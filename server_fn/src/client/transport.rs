@@ -0,0 +1,869 @@
+//! A [`ServerFnClient`] sends server function calls as plain
+//! [`TransportRequest`]s, which makes it possible to choose how they reach
+//! the server while the app is running, instead of with the `client` argument
+//! of each server function. This is useful on targets other than the browser,
+//! like desktop or mobile apps, which might call the server over `reqwest`
+//! or through their own IPC, and to replace the server with a test double in
+//! integration tests.
+//!
+//! Server functions use the [`DynClient`] when they are declared with
+//! `#[server(client = DynClient)]`, or by default with the `dynamic-client`
+//! feature. It sends each call with the first of:
+//! 1. the client set for the call with [`with_client`],
+//! 2. the client set for the app with [`set_client`],
+//! 3. the [`BrowserClient`](super::browser::BrowserClient) in the browser,
+//!    or else the [`ReqwestClient`](super::reqwest::ReqwestClient), if their
+//!    features are enabled.
+//!
+//! ```rust,ignore
+//! // in a desktop app
+//! set_client(ReqwestClient);
+//!
+//! // in a test
+//! let client = MockClient::new().on(|args: GetUser| async move {
+//!     Ok(User { id: args.id, name: "Alice".into() })
+//! });
+//! let user = with_client(client, get_user(1)).await?;
+//! ```
+//!
+//! Server functions that take multipart form data cannot be sent with a
+//! [`ServerFnClient`], and keep using the default client of their target.
+
+use super::Client;
+use crate::{
+    codec::{ByteStream, Encoding, FromReq, IntoRes, SyncStream},
+    error::{NoCustomError, ServerFnError, ServerFnErrorSerde},
+    request::{ClientReq, Req},
+    response::{ClientRes, Res},
+    rest, ServerFn,
+};
+use bytes::Bytes;
+use futures::{future::BoxFuture, FutureExt, Stream, StreamExt, TryStreamExt};
+use http::{Method, StatusCode};
+use once_cell::sync::Lazy;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    convert::Infallible,
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, RwLock},
+    task::{Context, Poll},
+};
+
+/// Sends server function calls to the server, however it can be reached.
+///
+/// Unlike [`Client`], this is object safe, so that it can be chosen while the
+/// app is running. See the [module docs](self).
+pub trait ServerFnClient: Send + Sync + 'static {
+    /// Sends the request and receives a response.
+    ///
+    /// A response with an error status is returned as `Ok(_)`, and its body
+    /// is deserialized into the error of the call. Failing to reach the
+    /// server at all should return a [`ServerFnError::Request`].
+    fn send(
+        &self,
+        req: TransportRequest,
+    ) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>>;
+}
+
+impl<C: ServerFnClient + ?Sized> ServerFnClient for Arc<C> {
+    fn send(
+        &self,
+        req: TransportRequest,
+    ) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>> {
+        (**self).send(req)
+    }
+}
+
+/// The body of a [`TransportRequest`] or [`TransportResponse`].
+#[derive(Debug)]
+pub enum TransportBody {
+    /// A UTF-8 body.
+    Text(String),
+    /// A binary body.
+    Bytes(Bytes),
+    /// A body that is streamed, as it is sent or received.
+    Stream(ByteStream),
+}
+
+impl TransportBody {
+    /// Waits for the whole body, and returns it as bytes.
+    pub async fn into_bytes(self) -> Result<Bytes, ServerFnError> {
+        match self {
+            TransportBody::Text(text) => Ok(text.into()),
+            TransportBody::Bytes(bytes) => Ok(bytes),
+            TransportBody::Stream(stream) => {
+                let chunks =
+                    stream.into_inner().try_collect::<Vec<_>>().await?;
+                Ok(chunks.concat().into())
+            }
+        }
+    }
+
+    /// Waits for the whole body, and returns it as a string.
+    pub async fn into_string(self) -> Result<String, ServerFnError> {
+        match self {
+            TransportBody::Text(text) => Ok(text),
+            body => String::from_utf8(body.into_bytes().await?.into())
+                .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+        }
+    }
+
+    /// Returns the body as a stream of bytes.
+    pub fn into_stream(self) -> ByteStream {
+        match self {
+            TransportBody::Text(text) => {
+                futures::stream::once(async move { Bytes::from(text) }).into()
+            }
+            TransportBody::Bytes(bytes) => {
+                futures::stream::once(async move { bytes }).into()
+            }
+            TransportBody::Stream(stream) => stream,
+        }
+    }
+}
+
+impl From<String> for TransportBody {
+    fn from(text: String) -> Self {
+        TransportBody::Text(text)
+    }
+}
+
+impl From<Bytes> for TransportBody {
+    fn from(bytes: Bytes) -> Self {
+        TransportBody::Bytes(bytes)
+    }
+}
+
+impl From<ByteStream> for TransportBody {
+    fn from(stream: ByteStream) -> Self {
+        TransportBody::Stream(stream)
+    }
+}
+
+/// A call to a server function, as sent by a [`ServerFnClient`].
+#[derive(Debug)]
+pub struct TransportRequest {
+    /// The HTTP method of the call.
+    pub method: Method,
+    /// The path of the server function, relative to the server URL.
+    pub path: String,
+    /// The query string of the call, if any.
+    pub query: Option<String>,
+    /// The `Content-Type` of the arguments.
+    pub content_type: String,
+    /// The `Accept` header of the call.
+    pub accepts: String,
+    /// The encoded arguments.
+    pub body: TransportBody,
+}
+
+impl TransportRequest {
+    fn new(
+        method: Method,
+        path: &str,
+        query: Option<&str>,
+        content_type: &str,
+        accepts: &str,
+        body: TransportBody,
+    ) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            query: query.map(ToString::to_string),
+            content_type: content_type.to_string(),
+            accepts: accepts.to_string(),
+            body,
+        }
+    }
+
+    /// The path and query string of the call.
+    pub fn uri(&self) -> String {
+        match &self.query {
+            Some(query) => format!("{}?{query}", self.path),
+            None => self.path.clone(),
+        }
+    }
+
+    /// Converts the call into the request type of a [`Client`].
+    pub fn into_client_req<R>(self) -> Result<R, ServerFnError>
+    where
+        R: ClientReq<NoCustomError>,
+    {
+        let Self {
+            method,
+            path,
+            query,
+            content_type,
+            accepts,
+            body,
+        } = self;
+        let query = query.unwrap_or_default();
+        match (method, body) {
+            (Method::GET, _) => {
+                R::try_new_get(&path, &accepts, &content_type, &query)
+            }
+            (Method::DELETE, _) => {
+                R::try_new_delete(&path, &accepts, &content_type, &query)
+            }
+            (Method::POST, TransportBody::Text(body)) => {
+                R::try_new_post(&path, &accepts, &content_type, body)
+            }
+            (Method::POST, TransportBody::Bytes(body)) => {
+                R::try_new_post_bytes(&path, &accepts, &content_type, body)
+            }
+            (Method::POST, TransportBody::Stream(body)) => {
+                R::try_new_streaming(
+                    &path,
+                    &accepts,
+                    &content_type,
+                    body.into_inner(),
+                )
+            }
+            (method, body) => {
+                let body = match body {
+                    TransportBody::Text(body) => body,
+                    TransportBody::Bytes(body) => {
+                        String::from_utf8(body.into()).map_err(|e| {
+                            ServerFnError::<NoCustomError>::Request(
+                                e.to_string(),
+                            )
+                        })?
+                    }
+                    TransportBody::Stream(_) => {
+                        return Err(ServerFnError::Request(format!(
+                            "{method} requests cannot have a streaming body"
+                        )))
+                    }
+                };
+                match method {
+                    Method::PUT => {
+                        R::try_new_put(&path, &accepts, &content_type, body)
+                    }
+                    Method::PATCH => {
+                        R::try_new_patch(&path, &accepts, &content_type, body)
+                    }
+                    method => Err(ServerFnError::Request(format!(
+                        "unsupported HTTP method {method}"
+                    ))),
+                }
+            }
+        }
+    }
+}
+
+impl<CustErr> ClientReq<CustErr> for TransportRequest {
+    type FormData = Infallible;
+
+    fn try_new_get(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::GET,
+            path,
+            Some(query),
+            content_type,
+            accepts,
+            TransportBody::Text(String::new()),
+        ))
+    }
+
+    fn try_new_post(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::POST,
+            path,
+            None,
+            content_type,
+            accepts,
+            body.into(),
+        ))
+    }
+
+    fn try_new_delete(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::DELETE,
+            path,
+            Some(query),
+            content_type,
+            accepts,
+            TransportBody::Text(String::new()),
+        ))
+    }
+
+    fn try_new_put(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::PUT,
+            path,
+            None,
+            content_type,
+            accepts,
+            body.into(),
+        ))
+    }
+
+    fn try_new_patch(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::PATCH,
+            path,
+            None,
+            content_type,
+            accepts,
+            body.into(),
+        ))
+    }
+
+    fn try_new_post_bytes(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::POST,
+            path,
+            None,
+            content_type,
+            accepts,
+            body.into(),
+        ))
+    }
+
+    fn try_new_post_form_data(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        match body {}
+    }
+
+    fn try_new_multipart(
+        _path: &str,
+        _accepts: &str,
+        body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        match body {}
+    }
+
+    fn try_new_streaming(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(Self::new(
+            Method::POST,
+            path,
+            None,
+            content_type,
+            accepts,
+            ByteStream::new(body).into(),
+        ))
+    }
+}
+
+// allows the `MockClient` to decode the arguments like the server would
+impl<CustErr> Req<CustErr> for TransportRequest
+where
+    CustErr: 'static,
+{
+    fn as_path(&self) -> &str {
+        &self.path
+    }
+
+    fn as_query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    fn to_content_type(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.content_type))
+    }
+
+    fn accepts(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.accepts))
+    }
+
+    fn referer(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        self.body
+            .into_bytes()
+            .await
+            .map_err(ServerFnError::into_custom)
+    }
+
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        self.body
+            .into_string()
+            .await
+            .map_err(ServerFnError::into_custom)
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(self.body.into_stream().into_inner())
+    }
+}
+
+/// The response to a call to a server function, as received by a
+/// [`ServerFnClient`].
+#[derive(Debug)]
+pub struct TransportResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The `Location` header of the response, if any.
+    pub location: Option<String>,
+    /// Whether the response has the [`REDIRECT_HEADER`](crate::redirect::REDIRECT_HEADER)
+    /// or a `Location` header.
+    pub redirect: bool,
+    /// The body of the response.
+    pub body: TransportBody,
+}
+
+impl TransportResponse {
+    /// Creates a response with the given status code and body.
+    pub fn new(status: u16, body: impl Into<TransportBody>) -> Self {
+        Self {
+            status,
+            location: None,
+            redirect: false,
+            body: body.into(),
+        }
+    }
+
+    /// Creates the response to a call that failed with the given error.
+    pub fn error<CustErr>(err: &ServerFnError<CustErr>, status: u16) -> Self
+    where
+        CustErr: std::str::FromStr + fmt::Display,
+    {
+        Self::new(status, err.ser().unwrap_or_default())
+    }
+
+    /// Converts the response of a [`Client`].
+    pub fn from_client_res<R>(res: R) -> Result<Self, ServerFnError>
+    where
+        R: ClientRes<NoCustomError>,
+    {
+        let status = res.status();
+        let location = res.location();
+        let redirect = res.has_redirect();
+        let body = ByteStream::new(res.try_into_stream()?);
+        Ok(Self {
+            status,
+            location: (!location.is_empty()).then_some(location),
+            redirect,
+            body: body.into(),
+        })
+    }
+}
+
+impl<CustErr> ClientRes<CustErr> for TransportResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        self.body
+            .into_string()
+            .await
+            .map_err(ServerFnError::into_custom)
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        self.body
+            .into_bytes()
+            .await
+            .map_err(ServerFnError::into_custom)
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(SyncStream(Mutex::new(Box::pin(
+            self.body.into_stream().into_inner(),
+        ))))
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn status_text(&self) -> String {
+        StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn location(&self) -> String {
+        self.location.clone().unwrap_or_default()
+    }
+
+    fn has_redirect(&self) -> bool {
+        self.redirect
+    }
+}
+
+// allows the `MockClient` to encode the result like the server would
+impl<CustErr> Res<CustErr> for TransportResponse
+where
+    CustErr: fmt::Display,
+{
+    fn try_from_string(
+        content_type: &str,
+        data: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        _ = content_type;
+        Ok(Self::new(200, data))
+    }
+
+    fn try_from_bytes(
+        content_type: &str,
+        data: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        _ = content_type;
+        Ok(Self::new(200, data))
+    }
+
+    fn try_from_stream(
+        content_type: &str,
+        data: impl Stream<Item = Result<Bytes, ServerFnError<CustErr>>>
+            + Send
+            + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        _ = content_type;
+        let data = data.map(|chunk| chunk.map_err(ServerFnError::new));
+        Ok(Self::new(200, ByteStream::new(data)))
+    }
+
+    fn error_response(path: &str, err: &ServerFnError<CustErr>) -> Self {
+        Self::error_response_with_status(path, err, 500)
+    }
+
+    fn error_response_with_status(
+        _path: &str,
+        err: &ServerFnError<CustErr>,
+        status: u16,
+    ) -> Self {
+        Self::new(status, err.to_string())
+    }
+
+    fn redirect(&mut self, path: &str) {
+        self.status = 302;
+        self.location = Some(path.to_string());
+        self.redirect = true;
+    }
+}
+
+// sends a call with a statically-known client
+#[cfg(any(feature = "browser", feature = "reqwest"))]
+fn send_with<C>(
+    req: TransportRequest,
+) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>>
+where
+    C: Client<NoCustomError>,
+    C::Request: 'static,
+    C::Response: 'static,
+{
+    let req = req.into_client_req::<C::Request>();
+    Box::pin(async move {
+        let res = C::send(req?).await?;
+        TransportResponse::from_client_res(res)
+    })
+}
+
+#[cfg(feature = "browser")]
+impl ServerFnClient for super::browser::BrowserClient {
+    fn send(
+        &self,
+        req: TransportRequest,
+    ) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>> {
+        send_with::<Self>(req)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl ServerFnClient for super::reqwest::ReqwestClient {
+    fn send(
+        &self,
+        req: TransportRequest,
+    ) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>> {
+        send_with::<Self>(req)
+    }
+}
+
+static APP_CLIENT: Lazy<RwLock<Option<Arc<dyn ServerFnClient>>>> =
+    Lazy::new(Default::default);
+
+thread_local! {
+    static CALL_CLIENT: RefCell<Option<Arc<dyn ServerFnClient>>> =
+        const { RefCell::new(None) };
+}
+
+/// Sets the client that the [`DynClient`] sends calls with, unless another
+/// one is set for the call with [`with_client`].
+///
+/// This replaces any client that was set before.
+pub fn set_client(client: impl ServerFnClient) {
+    *APP_CLIENT.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(client));
+}
+
+/// Removes the client set with [`set_client`], so that the [`DynClient`] goes
+/// back to its default.
+pub fn clear_client() {
+    *APP_CLIENT.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Runs the future, sending any calls to server functions that use the
+/// [`DynClient`] that it makes with the given client.
+///
+/// This only applies to calls that are made while the future itself is
+/// polled, and not to those in tasks that it spawns.
+pub fn with_client<F>(
+    client: impl ServerFnClient,
+    fut: F,
+) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    WithClient {
+        client: Arc::new(client),
+        fut: Box::pin(fut),
+    }
+}
+
+struct WithClient<F> {
+    client: Arc<dyn ServerFnClient>,
+    fut: Pin<Box<F>>,
+}
+
+impl<F> Future for WithClient<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // the previous client is restored even if the future panics
+        struct Restore(Option<Arc<dyn ServerFnClient>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let prev = self.0.take();
+                CALL_CLIENT.with(|client| *client.borrow_mut() = prev);
+            }
+        }
+
+        let prev = CALL_CLIENT.with(|client| {
+            client.borrow_mut().replace(Arc::clone(&self.client))
+        });
+        let _restore = Restore(prev);
+        self.fut.as_mut().poll(cx)
+    }
+}
+
+fn current_client() -> Option<Arc<dyn ServerFnClient>> {
+    CALL_CLIENT
+        .with(|client| client.borrow().clone())
+        .or_else(|| {
+            APP_CLIENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+        })
+        .or_else(builtin_client)
+}
+
+#[cfg(all(feature = "browser", target_arch = "wasm32"))]
+fn builtin_client() -> Option<Arc<dyn ServerFnClient>> {
+    Some(Arc::new(super::browser::BrowserClient))
+}
+
+#[cfg(all(
+    feature = "reqwest",
+    not(all(feature = "browser", target_arch = "wasm32"))
+))]
+fn builtin_client() -> Option<Arc<dyn ServerFnClient>> {
+    Some(Arc::new(super::reqwest::ReqwestClient))
+}
+
+#[cfg(not(any(
+    all(feature = "browser", target_arch = "wasm32"),
+    feature = "reqwest"
+)))]
+fn builtin_client() -> Option<Arc<dyn ServerFnClient>> {
+    None
+}
+
+/// A [`Client`] that sends calls with the [`ServerFnClient`] that is chosen
+/// while the app is running. See the [module docs](self).
+pub struct DynClient;
+
+impl<CustErr> Client<CustErr> for DynClient {
+    type Request = TransportRequest;
+    type Response = TransportResponse;
+
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send
+    {
+        // the client is chosen when the call is made, within `with_client`
+        let client = current_client();
+        async move {
+            let client = client.ok_or_else(|| {
+                ServerFnError::Request(
+                    "no server function client has been set".into(),
+                )
+            })?;
+            client.send(req).await.map_err(ServerFnError::into_custom)
+        }
+    }
+}
+
+type MockHandler = Box<
+    dyn Fn(TransportRequest) -> BoxFuture<'static, TransportResponse>
+        + Send
+        + Sync,
+>;
+
+/// A [`ServerFnClient`] that answers calls to server functions in the same
+/// process, with handlers that stand in for their bodies, as a test double.
+///
+/// Arguments and results are encoded and decoded as they would be when
+/// sending them to the server, so the call exercises the server function's
+/// encodings. A call to a server function without a handler fails with a
+/// [`ServerFnError::Request`].
+///
+/// ```rust,ignore
+/// let client = MockClient::new()
+///     .on(|args: GetUser| async move {
+///         Ok(User { id: args.id, name: "Alice".into() })
+///     })
+///     .on(|_: DeleteUser| async move {
+///         Err(ServerFnError::ServerError("not allowed".into()))
+///     });
+/// set_client(client);
+/// ```
+#[derive(Default)]
+pub struct MockClient {
+    handlers: Vec<(Method, &'static str, MockHandler)>,
+}
+
+impl Debug for MockClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockClient")
+            .field(
+                "handlers",
+                &self
+                    .handlers
+                    .iter()
+                    .map(|(method, path, _)| (method, path))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl MockClient {
+    /// Creates a client without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers calls to the server function `S` with the given handler,
+    /// which replaces any handler set for it before.
+    pub fn on<S, F, Fut>(mut self, handler: F) -> Self
+    where
+        S: ServerFn + FromReq<S::InputEncoding, TransportRequest, S::Error>,
+        S::Output: IntoRes<S::OutputEncoding, TransportResponse, S::Error>,
+        S::Error: 'static,
+        F: Fn(S) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<S::Output, ServerFnError<S::Error>>>
+            + Send
+            + 'static,
+    {
+        let handler = Arc::new(handler);
+        let run: MockHandler = Box::new(move |req| {
+            let handler = Arc::clone(&handler);
+            Box::pin(async move {
+                let path = req.path.clone();
+                let res = async move {
+                    let mut args = <S as FromReq<
+                        S::InputEncoding,
+                        TransportRequest,
+                        S::Error,
+                    >>::from_req(req)
+                    .await?;
+                    args.extract_path_params(&path)?;
+                    args.validate().map_err(ServerFnError::Validation)?;
+                    let output = handler(args).await?;
+                    <S::Output as IntoRes<
+                        S::OutputEncoding,
+                        TransportResponse,
+                        S::Error,
+                    >>::into_res(output)
+                    .await
+                };
+                res.await.unwrap_or_else(|err| {
+                    TransportResponse::error(&err, S::error_status(&err))
+                })
+            })
+        });
+        let method = S::InputEncoding::METHOD;
+        self.handlers
+            .retain(|(m, path, _)| (m, *path) != (&method, S::PATH));
+        self.handlers.push((method, S::PATH, run));
+        self
+    }
+}
+
+impl ServerFnClient for MockClient {
+    fn send(
+        &self,
+        req: TransportRequest,
+    ) -> BoxFuture<'static, Result<TransportResponse, ServerFnError>> {
+        let handler = self.handlers.iter().find(|(method, path, _)| {
+            *method == req.method && rest::matches(path, &req.path)
+        });
+        match handler {
+            Some((_, _, run)) => run(req).map(Ok).boxed(),
+            None => {
+                let path = req.path;
+                Box::pin(async move {
+                    Err(ServerFnError::Request(format!(
+                        "no mock response for {path}"
+                    )))
+                })
+            }
+        }
+    }
+}
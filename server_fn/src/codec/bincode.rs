@@ -0,0 +1,167 @@
+use super::{Encoding, FromReq, FromRes, IntoReq, IntoRes};
+use crate::{
+    error::ServerFnError,
+    request::{ClientReq, Req},
+    response::{ClientRes, Res},
+};
+use bytes::Bytes;
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Pass arguments and receive responses using [`bincode`] in a `POST` request.
+///
+/// `bincode` is a compact binary format with very little encoding overhead,
+/// meant for apps where both the client and the server are written in Rust,
+/// such as desktop frontends. The format does not describe its own schema, so
+/// the client and the server must be built with the same argument and return
+/// types.
+pub struct Bincode;
+
+impl Encoding for Bincode {
+    const CONTENT_TYPE: &'static str = "application/bincode";
+    const METHOD: Method = Method::POST;
+}
+
+impl<CustErr, T, Request> IntoReq<Bincode, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let data = bincode::serialize(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_post_bytes(
+            path,
+            accepts,
+            Bincode::CONTENT_TYPE,
+            Bytes::from(data),
+        )
+    }
+}
+
+impl<CustErr, T, Request> FromReq<Bincode, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let data = req.try_into_bytes().await?;
+        bincode::deserialize::<T>(&data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))
+    }
+}
+
+impl<CustErr, T, Response> IntoRes<Bincode, Response, CustErr> for T
+where
+    Response: Res<CustErr>,
+    T: Serialize + Send,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        let data = bincode::serialize(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Response::try_from_bytes(Bincode::CONTENT_TYPE, Bytes::from(data))
+    }
+}
+
+impl<CustErr, T, Response> FromRes<Bincode, Response, CustErr> for T
+where
+    Response: ClientRes<CustErr> + Send,
+    T: DeserializeOwned,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let data = res.try_into_bytes().await?;
+        bincode::deserialize(&data)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::transport::{TransportRequest, TransportResponse},
+        error::NoCustomError,
+    };
+    use futures::executor::block_on;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u64,
+        name: String,
+        roles: Vec<String>,
+    }
+
+    fn user() -> User {
+        User {
+            id: 42,
+            name: "Ann".into(),
+            roles: vec!["admin".into()],
+        }
+    }
+
+    #[test]
+    fn arguments_round_trip() {
+        let req: TransportRequest =
+            IntoReq::<Bincode, _, NoCustomError>::into_req(
+                user(),
+                "/api/get_user",
+                "application/json",
+            )
+            .unwrap();
+        assert_eq!(req.content_type, Bincode::CONTENT_TYPE);
+        assert_eq!(req.accepts, "application/json");
+
+        let args = block_on(
+            <User as FromReq<Bincode, _, NoCustomError>>::from_req(req),
+        );
+        assert_eq!(args, Ok(user()));
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let res: TransportResponse =
+            block_on(IntoRes::<Bincode, _, NoCustomError>::into_res(user()))
+                .unwrap();
+
+        let user = block_on(
+            <User as FromRes<Bincode, _, NoCustomError>>::from_res(res),
+        );
+        assert_eq!(user, Ok(self::user()));
+    }
+
+    #[test]
+    fn malformed_bodies_are_errors() {
+        // cut off before its end, as by an interrupted request
+        let mut body = bincode::serialize(&user()).unwrap();
+        body.truncate(body.len() - 8);
+        let body = Bytes::from(body);
+
+        let req =
+            <TransportRequest as ClientReq<NoCustomError>>::try_new_post_bytes(
+                "/api/get_user",
+                Bincode::CONTENT_TYPE,
+                Bincode::CONTENT_TYPE,
+                body.clone(),
+            )
+            .unwrap();
+        assert!(matches!(
+            block_on(<User as FromReq<Bincode, _, NoCustomError>>::from_req(
+                req
+            )),
+            Err(ServerFnError::Args(_))
+        ));
+
+        let res = TransportResponse::new(200, body);
+        assert!(matches!(
+            block_on(<User as FromRes<Bincode, _, NoCustomError>>::from_res(
+                res
+            )),
+            Err(ServerFnError::Deserialization(_))
+        ));
+    }
+}
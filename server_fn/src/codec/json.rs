@@ -10,6 +10,12 @@ use serde::{de::DeserializeOwned, Serialize};
 /// Pass arguments and receive responses as JSON in the body of a `POST` request.
 pub struct Json;
 
+/// Pass arguments as JSON in the body of a `PUT` request.
+pub struct PutJson;
+
+/// Pass arguments as JSON in the body of a `PATCH` request.
+pub struct PatchJson;
+
 impl Encoding for Json {
     const CONTENT_TYPE: &'static str = "application/json";
     const METHOD: Method = Method::POST;
@@ -66,3 +72,69 @@ where
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))
     }
 }
+
+impl Encoding for PutJson {
+    const CONTENT_TYPE: &'static str = "application/json";
+    const METHOD: Method = Method::PUT;
+}
+
+impl<CustErr, T, Request> IntoReq<PutJson, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize + Send,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let data = serde_json::to_string(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_put(path, accepts, PutJson::CONTENT_TYPE, data)
+    }
+}
+
+impl<CustErr, T, Request> FromReq<PutJson, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let string_data = req.try_into_string().await?;
+        serde_json::from_str::<Self>(&string_data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))
+    }
+}
+
+impl Encoding for PatchJson {
+    const CONTENT_TYPE: &'static str = "application/json";
+    const METHOD: Method = Method::PATCH;
+}
+
+impl<CustErr, T, Request> IntoReq<PatchJson, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize + Send,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let data = serde_json::to_string(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_patch(path, accepts, PatchJson::CONTENT_TYPE, data)
+    }
+}
+
+impl<CustErr, T, Request> FromReq<PatchJson, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let string_data = req.try_into_string().await?;
+        serde_json::from_str::<Self>(&string_data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))
+    }
+}
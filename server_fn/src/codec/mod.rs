@@ -11,8 +11,9 @@
 //! Rather than a limited number of encodings, this crate allows you to define server functions that
 //! mix and match the input encoding and output encoding. To define a new encoding, you simply implement
 //! an input combination ([`IntoReq`] and [`FromReq`]) and/or an output encoding ([`IntoRes`] and [`FromRes`]).
-//! This genuinely is an and/or: while some encodings can be used for both input and output (`Json`, `Cbor`, `Rkyv`),
-//! others can only be used for input (`GetUrl`, `MultipartData`).
+//! This genuinely is an and/or: while some encodings can be used for both input and output (`Json`, `Cbor`, `Rkyv`,
+//! `MsgPack`, `Protobuf`, `Bincode`, `Postcard`), others can only be used for input (`GetUrl`, `PostUrl`, `DeleteUrl`, `PutUrl`, `PatchUrl`,
+//! `PutJson`, `PatchJson`, `MultipartData`).
 
 #[cfg(feature = "cbor")]
 mod cbor;
@@ -49,6 +50,21 @@ mod msgpack;
 #[cfg(feature = "msgpack")]
 pub use msgpack::*;
 
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "bincode")]
+pub use bincode::*;
+
+#[cfg(feature = "postcard")]
+mod postcard;
+#[cfg(feature = "postcard")]
+pub use postcard::*;
+
+#[cfg(feature = "protobuf")]
+mod protobuf;
+#[cfg(feature = "protobuf")]
+pub use protobuf::*;
+
 mod stream;
 use crate::error::ServerFnError;
 use futures::Future;
@@ -8,7 +8,20 @@ use bytes::Bytes;
 use http::Method;
 use serde::{de::DeserializeOwned, Serialize};
 
-/// A codec for MessagePack.
+/// A codec for [MessagePack](https://msgpack.org/), a compact binary format
+/// that is much faster to encode and decode than JSON for large numeric data.
+///
+/// Arguments are sent in the body of a `POST` request, and both the request
+/// and the response have the `application/msgpack` content type, so the same
+/// endpoint can be called by any MessagePack client. It works with both the
+/// browser and the `reqwest` clients.
+///
+/// ```rust,ignore
+/// #[server(input = MsgPack, output = MsgPack)]
+/// pub async fn samples(channel: u8) -> Result<Vec<f32>, ServerFnError> {
+///     todo!()
+/// }
+/// ```
 pub struct MsgPack;
 
 impl Encoding for MsgPack {
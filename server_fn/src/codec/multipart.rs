@@ -6,8 +6,10 @@ use crate::{
 };
 use futures::StreamExt;
 use http::Method;
-use multer::Multipart;
-use web_sys::FormData;
+use multer::{Field, Multipart};
+use web_sys::{File, FileList, FormData};
+
+pub use crate::request::browser::UploadProgress;
 
 /// Encodes multipart form data.
 ///
@@ -48,6 +50,21 @@ impl MultipartData {
             MultipartData::Server(_) => None,
         }
     }
+
+    /// Calls `on_progress` as the data is uploaded, on the client side.
+    ///
+    /// On the server side, this does nothing.
+    pub fn on_progress(
+        self,
+        on_progress: impl Fn(UploadProgress) + 'static,
+    ) -> Self {
+        match self {
+            MultipartData::Client(data) => {
+                MultipartData::Client(data.on_progress(on_progress))
+            }
+            MultipartData::Server(data) => MultipartData::Server(data),
+        }
+    }
 }
 
 impl From<FormData> for MultipartData {
@@ -56,6 +73,114 @@ impl From<FormData> for MultipartData {
     }
 }
 
+/// One or more files uploaded to a server function as multipart form data.
+///
+/// On the client, create it from the `FileList` of an `<input type="file">`, a
+/// single `File`, or a `FormData`, and use [`FileUpload::on_progress`] to follow
+/// the upload, e.g., to show a progress bar. On the server, read the files one
+/// at a time with [`FileUpload::next_file`]: each one is streamed in chunks as it
+/// arrives, rather than buffered in memory.
+///
+/// ```rust,ignore
+/// #[server(input = MultipartFormData)]
+/// pub async fn upload(files: FileUpload) -> Result<usize, ServerFnError> {
+///     let mut files = files;
+///     let mut size = 0;
+///     while let Some(mut file) = files.next_file().await? {
+///         while let Some(chunk) = file.chunk().await? {
+///             size += chunk.len();
+///         }
+///     }
+///     Ok(size)
+/// }
+///
+/// // on the client
+/// let (progress, set_progress) = create_signal(UploadProgress::default());
+/// let on_change = move |ev: ev::Event| {
+///     let input = event_target::<web_sys::HtmlInputElement>(&ev);
+///     if let Some(files) = input.files() {
+///         let files = FileUpload::from(files)
+///             .on_progress(move |progress| set_progress.set(progress));
+///         spawn_local(async move {
+///             _ = upload(files).await;
+///         });
+///     }
+/// };
+/// ```
+#[derive(Debug)]
+pub struct FileUpload(MultipartData);
+
+impl FileUpload {
+    /// Calls `on_progress` as the files are uploaded, on the client side.
+    ///
+    /// On the server side, this does nothing.
+    pub fn on_progress(
+        self,
+        on_progress: impl Fn(UploadProgress) + 'static,
+    ) -> Self {
+        Self(self.0.on_progress(on_progress))
+    }
+
+    /// Returns the next uploaded file, skipping any other form fields, or
+    /// `None` once all of them have been read.
+    ///
+    /// On the client side, this always returns `None`.
+    pub async fn next_file(
+        &mut self,
+    ) -> Result<Option<Field<'static>>, ServerFnError> {
+        let MultipartData::Server(data) = &mut self.0 else {
+            return Ok(None);
+        };
+        while let Some(field) = data.next_field().await? {
+            if field.file_name().is_some() {
+                return Ok(Some(field));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the underlying multipart data.
+    pub fn into_inner(self) -> MultipartData {
+        self.0
+    }
+}
+
+impl From<MultipartData> for FileUpload {
+    fn from(value: MultipartData) -> Self {
+        Self(value)
+    }
+}
+
+impl From<FileUpload> for MultipartData {
+    fn from(value: FileUpload) -> Self {
+        value.0
+    }
+}
+
+impl From<FormData> for FileUpload {
+    fn from(value: FormData) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<File> for FileUpload {
+    fn from(value: File) -> Self {
+        let form = FormData::new().expect("could not create FormData");
+        _ = form.append_with_blob_and_filename("file", &value, &value.name());
+        form.into()
+    }
+}
+
+impl From<FileList> for FileUpload {
+    fn from(value: FileList) -> Self {
+        let form = FormData::new().expect("could not create FormData");
+        for file in (0..value.length()).filter_map(|i| value.get(i)) {
+            _ = form.append_with_blob_and_filename("file", &file, &file.name());
+        }
+        form.into()
+    }
+}
+
 impl<CustErr, T, Request> IntoReq<MultipartFormData, Request, CustErr> for T
 where
     Request: ClientReq<CustErr, FormData = BrowserFormData>,
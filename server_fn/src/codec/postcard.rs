@@ -0,0 +1,167 @@
+use super::{Encoding, FromReq, FromRes, IntoReq, IntoRes};
+use crate::{
+    error::ServerFnError,
+    request::{ClientReq, Req},
+    response::{ClientRes, Res},
+};
+use bytes::Bytes;
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Pass arguments and receive responses using [`postcard`] in a `POST` request.
+///
+/// `postcard` is a compact binary format that produces very small payloads,
+/// meant for apps where both the client and the server are written in Rust,
+/// such as desktop frontends. The format does not describe its own schema, so
+/// the client and the server must be built with the same argument and return
+/// types.
+pub struct Postcard;
+
+impl Encoding for Postcard {
+    const CONTENT_TYPE: &'static str = "application/postcard";
+    const METHOD: Method = Method::POST;
+}
+
+impl<CustErr, T, Request> IntoReq<Postcard, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let data = postcard::to_allocvec(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_post_bytes(
+            path,
+            accepts,
+            Postcard::CONTENT_TYPE,
+            Bytes::from(data),
+        )
+    }
+}
+
+impl<CustErr, T, Request> FromReq<Postcard, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let data = req.try_into_bytes().await?;
+        postcard::from_bytes::<T>(&data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))
+    }
+}
+
+impl<CustErr, T, Response> IntoRes<Postcard, Response, CustErr> for T
+where
+    Response: Res<CustErr>,
+    T: Serialize + Send,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        let data = postcard::to_allocvec(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Response::try_from_bytes(Postcard::CONTENT_TYPE, Bytes::from(data))
+    }
+}
+
+impl<CustErr, T, Response> FromRes<Postcard, Response, CustErr> for T
+where
+    Response: ClientRes<CustErr> + Send,
+    T: DeserializeOwned,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let data = res.try_into_bytes().await?;
+        postcard::from_bytes(&data)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::transport::{TransportRequest, TransportResponse},
+        error::NoCustomError,
+    };
+    use futures::executor::block_on;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: u64,
+        name: String,
+        roles: Vec<String>,
+    }
+
+    fn user() -> User {
+        User {
+            id: 42,
+            name: "Ann".into(),
+            roles: vec!["admin".into()],
+        }
+    }
+
+    #[test]
+    fn arguments_round_trip() {
+        let req: TransportRequest =
+            IntoReq::<Postcard, _, NoCustomError>::into_req(
+                user(),
+                "/api/get_user",
+                "application/json",
+            )
+            .unwrap();
+        assert_eq!(req.content_type, Postcard::CONTENT_TYPE);
+        assert_eq!(req.accepts, "application/json");
+
+        let args = block_on(
+            <User as FromReq<Postcard, _, NoCustomError>>::from_req(req),
+        );
+        assert_eq!(args, Ok(user()));
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let res: TransportResponse =
+            block_on(IntoRes::<Postcard, _, NoCustomError>::into_res(user()))
+                .unwrap();
+
+        let user = block_on(
+            <User as FromRes<Postcard, _, NoCustomError>>::from_res(res),
+        );
+        assert_eq!(user, Ok(self::user()));
+    }
+
+    #[test]
+    fn malformed_bodies_are_errors() {
+        // cut off before its end, as by an interrupted request
+        let mut body = postcard::to_allocvec(&user()).unwrap();
+        body.truncate(body.len() - 8);
+        let body = Bytes::from(body);
+
+        let req =
+            <TransportRequest as ClientReq<NoCustomError>>::try_new_post_bytes(
+                "/api/get_user",
+                Postcard::CONTENT_TYPE,
+                Postcard::CONTENT_TYPE,
+                body.clone(),
+            )
+            .unwrap();
+        assert!(matches!(
+            block_on(<User as FromReq<Postcard, _, NoCustomError>>::from_req(
+                req
+            )),
+            Err(ServerFnError::Args(_))
+        ));
+
+        let res = TransportResponse::new(200, body);
+        assert!(matches!(
+            block_on(<User as FromRes<Postcard, _, NoCustomError>>::from_res(
+                res
+            )),
+            Err(ServerFnError::Deserialization(_))
+        ));
+    }
+}
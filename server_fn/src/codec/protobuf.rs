@@ -0,0 +1,173 @@
+use super::{Encoding, FromReq, FromRes, IntoReq, IntoRes};
+use crate::{
+    error::ServerFnError,
+    request::{ClientReq, Req},
+    response::{ClientRes, Res},
+};
+use bytes::Bytes;
+use http::Method;
+use prost::Message;
+
+/// Pass arguments and receive responses as [Protocol Buffers](https://protobuf.dev/)
+/// in a `POST` request, using [`prost`].
+///
+/// Any type that implements [`prost::Message`] can be used, such as the types
+/// generated by `prost-build` from a `.proto` file, so the endpoint can also be
+/// called by clients in other languages using the same schema. The arguments
+/// type of the server function is itself the message, so it needs to derive
+/// `prost::Message` rather than `serde`'s traits:
+///
+/// ```rust,ignore
+/// #[server(input = Protobuf, output = Protobuf)]
+/// pub async fn get_user(
+///     #[prost(uint64, tag = "1")]
+///     id: u64,
+/// ) -> Result<proto::User, ServerFnError> {
+///     todo!()
+/// }
+/// ```
+pub struct Protobuf;
+
+impl Encoding for Protobuf {
+    const CONTENT_TYPE: &'static str = "application/x-protobuf";
+    const METHOD: Method = Method::POST;
+}
+
+impl<CustErr, T, Request> IntoReq<Protobuf, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Message,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        Request::try_new_post_bytes(
+            path,
+            accepts,
+            Protobuf::CONTENT_TYPE,
+            Bytes::from(self.encode_to_vec()),
+        )
+    }
+}
+
+impl<CustErr, T, Request> FromReq<Protobuf, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: Message + Default,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let data = req.try_into_bytes().await?;
+        T::decode(data).map_err(|e| ServerFnError::Args(e.to_string()))
+    }
+}
+
+impl<CustErr, T, Response> IntoRes<Protobuf, Response, CustErr> for T
+where
+    Response: Res<CustErr>,
+    T: Message + Send,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        Response::try_from_bytes(
+            Protobuf::CONTENT_TYPE,
+            Bytes::from(self.encode_to_vec()),
+        )
+    }
+}
+
+impl<CustErr, T, Response> FromRes<Protobuf, Response, CustErr> for T
+where
+    Response: ClientRes<CustErr> + Send,
+    T: Message + Default,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let data = res.try_into_bytes().await?;
+        T::decode(data)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::transport::{TransportRequest, TransportResponse},
+        error::NoCustomError,
+    };
+    use futures::executor::block_on;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct User {
+        #[prost(uint64, tag = "1")]
+        id: u64,
+        #[prost(string, tag = "2")]
+        name: String,
+    }
+
+    fn user() -> User {
+        User {
+            id: 42,
+            name: "Ann".into(),
+        }
+    }
+
+    #[test]
+    fn arguments_round_trip() {
+        let req: TransportRequest =
+            IntoReq::<Protobuf, _, NoCustomError>::into_req(
+                user(),
+                "/api/get_user",
+                "application/json",
+            )
+            .unwrap();
+        assert_eq!(req.content_type, Protobuf::CONTENT_TYPE);
+        assert_eq!(req.accepts, "application/json");
+
+        let args = block_on(
+            <User as FromReq<Protobuf, _, NoCustomError>>::from_req(req),
+        );
+        assert_eq!(args, Ok(user()));
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let res: TransportResponse =
+            block_on(IntoRes::<Protobuf, _, NoCustomError>::into_res(user()))
+                .unwrap();
+
+        let user = block_on(
+            <User as FromRes<Protobuf, _, NoCustomError>>::from_res(res),
+        );
+        assert_eq!(user, Ok(self::user()));
+    }
+
+    #[test]
+    fn malformed_bodies_are_errors() {
+        // a field with a length that runs past the end of the body
+        let body = Bytes::from_static(&[0x12, 0x10, b'A']);
+
+        let req =
+            <TransportRequest as ClientReq<NoCustomError>>::try_new_post_bytes(
+                "/api/get_user",
+                Protobuf::CONTENT_TYPE,
+                Protobuf::CONTENT_TYPE,
+                body.clone(),
+            )
+            .unwrap();
+        assert!(matches!(
+            block_on(<User as FromReq<Protobuf, _, NoCustomError>>::from_req(
+                req
+            )),
+            Err(ServerFnError::Args(_))
+        ));
+
+        let res = TransportResponse::new(200, body);
+        assert!(matches!(
+            block_on(<User as FromRes<Protobuf, _, NoCustomError>>::from_res(
+                res
+            )),
+            Err(ServerFnError::Deserialization(_))
+        ));
+    }
+}
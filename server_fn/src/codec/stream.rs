@@ -8,12 +8,39 @@ use crate::{
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use http::Method;
-use std::{fmt::Debug, pin::Pin};
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+    task::{Context, Poll},
+};
 
 /// An encoding that represents a stream of bytes.
 ///
 /// A server function that uses this as its output encoding should return [`ByteStream`].
 ///
+/// A server function that uses this as its input encoding should take a single
+/// [`ByteStream`] argument, which lets large uploads be sent and handled as they are
+/// read, without buffering the whole body on either side. Any stream of bytes can be
+/// passed as the argument:
+///
+/// ```rust,ignore
+/// #[server(input = Streaming)]
+/// pub async fn upload(data: ByteStream) -> Result<usize, ServerFnError> {
+///     let mut data = data.into_inner();
+///     let mut len = 0;
+///     while let Some(chunk) = data.next().await {
+///         len += chunk?.len();
+///     }
+///     Ok(len)
+/// }
+///
+/// // on the client
+/// let len = upload(chunks.into()).await?;
+/// ```
+///
+/// If the client's stream yields an error, the request is aborted.
+///
 /// ## Browser Support for Streaming Input
 ///
 /// Browser fetch requests do not currently support full request duplexing, which
@@ -32,14 +59,20 @@ impl Encoding for Streaming {
 impl<CustErr, T, Request> IntoReq<Streaming, Request, CustErr> for T
 where
     Request: ClientReq<CustErr>,
-    T: Stream<Item = Bytes> + Send + Sync + 'static,
+    T: Into<ByteStream>,
 {
     fn into_req(
         self,
         path: &str,
         accepts: &str,
     ) -> Result<Request, ServerFnError<CustErr>> {
-        Request::try_new_streaming(path, accepts, Streaming::CONTENT_TYPE, self)
+        let data = self.into();
+        Request::try_new_streaming(
+            path,
+            accepts,
+            Streaming::CONTENT_TYPE,
+            data.into_inner(),
+        )
     }
 }
 
@@ -129,6 +162,27 @@ where
     }
 }
 
+/// A stream that is `Sync`, as some request and response bodies must be,
+/// because it can only be polled through a mutable reference.
+pub(crate) struct SyncStream<S>(pub(crate) Mutex<S>);
+
+impl<S> Stream for SyncStream<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .poll_next_unpin(cx)
+    }
+}
+
 /// An encoding that represents a stream of text.
 ///
 /// A server function that uses this as its output encoding should return [`TextStream`].
@@ -213,7 +267,7 @@ where
             path,
             accepts,
             Streaming::CONTENT_TYPE,
-            data.0.map(|chunk| chunk.unwrap_or_default().into()),
+            data.0.map(|chunk| chunk.map(Into::into)),
         )
     }
 }
@@ -263,3 +317,461 @@ where
         }))))
     }
 }
+
+/// An encoding that represents a stream of JSON values, sent as
+/// [newline-delimited JSON](https://github.com/ndjson/ndjson-spec).
+///
+/// A server function that uses this as its output encoding should return [`JsonStream`].
+/// Each value is sent to the client as soon as it is yielded, which makes this useful
+/// for progress reporting or incremental query results.
+///
+/// ```rust,ignore
+/// #[server(output = StreamingJson)]
+/// pub async fn search(query: String) -> Result<JsonStream<SearchResult>, ServerFnError> {
+///     Ok(JsonStream::from(search_index(query)))
+/// }
+///
+/// // on the client
+/// let mut results = search("leptos".into()).await?.into_inner();
+/// while let Some(result) = results.next().await {
+///     // ...
+/// }
+/// ```
+///
+/// Dropping the stream on the client cancels the response. To continue the stream
+/// when its response is interrupted, see [`JsonStream::reconnecting`].
+#[cfg(feature = "json")]
+pub struct StreamingJson;
+
+#[cfg(feature = "json")]
+impl Encoding for StreamingJson {
+    const CONTENT_TYPE: &'static str = "application/x-ndjson";
+    const METHOD: Method = Method::POST;
+}
+
+/// A stream of typed values.
+///
+/// A server function can return this type if its output encoding is [`StreamingJson`].
+/// An error yielded by the stream on the server is yielded by the stream on the client,
+/// which continues with any values that follow it.
+#[cfg(feature = "json")]
+pub struct JsonStream<T, CustErr = NoCustomError>(
+    Pin<Box<dyn Stream<Item = Result<T, ServerFnError<CustErr>>> + Send>>,
+);
+
+#[cfg(feature = "json")]
+impl<T, CustErr> Debug for JsonStream<T, CustErr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("JsonStream").finish()
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T, CustErr> JsonStream<T, CustErr> {
+    /// Creates a new `JsonStream` from the given stream.
+    pub fn new(
+        value: impl Stream<Item = Result<T, ServerFnError<CustErr>>>
+            + Send
+            + 'static,
+    ) -> Self {
+        Self(Box::pin(value))
+    }
+
+    /// Creates a stream that calls `connect` again when its response is
+    /// interrupted, at most `max_reconnects` times in a row, and continues
+    /// with the values of the new response.
+    ///
+    /// `connect` is given the number of values received so far, so that the
+    /// server function can resume the stream where it was interrupted instead
+    /// of starting it over. Errors yielded by the stream on the server do not
+    /// cause it to reconnect.
+    ///
+    /// ```rust,ignore
+    /// #[server(output = StreamingJson)]
+    /// pub async fn search(
+    ///     query: String,
+    ///     skip: usize,
+    /// ) -> Result<JsonStream<SearchResult>, ServerFnError> {
+    ///     Ok(JsonStream::from(search_index(query).skip(skip)))
+    /// }
+    ///
+    /// // on the client
+    /// let mut results = JsonStream::reconnecting(3, move |received| {
+    ///     search(query.clone(), received)
+    /// })
+    /// .into_inner();
+    /// ```
+    pub fn reconnecting<F, Fut>(max_reconnects: u32, connect: F) -> Self
+    where
+        F: FnMut(usize) -> Fut + Send + 'static,
+        Fut: std::future::Future<
+                Output = Result<JsonStream<T, CustErr>, ServerFnError<CustErr>>,
+            > + Send,
+        T: Send + 'static,
+        CustErr: Send + 'static,
+    {
+        struct State<T, CustErr, F> {
+            stream: Option<JsonStream<T, CustErr>>,
+            connect: Option<F>,
+            received: usize,
+            reconnects: u32,
+        }
+
+        let state = State {
+            stream: None,
+            connect: Some(connect),
+            received: 0,
+            reconnects: 0,
+        };
+        Self(Box::pin(futures::stream::unfold(
+            state,
+            move |mut state| async move {
+                loop {
+                    let connect = state.connect.as_mut()?;
+                    let stream = match state.stream.as_mut() {
+                        Some(stream) => stream,
+                        None => match connect(state.received).await {
+                            Ok(stream) => state.stream.insert(stream),
+                            Err(e) => {
+                                if is_interrupted(&e)
+                                    && state.reconnects < max_reconnects
+                                {
+                                    state.reconnects += 1;
+                                    continue;
+                                }
+                                state.connect = None;
+                                return Some((Err(e), state));
+                            }
+                        },
+                    };
+                    match stream.0.next().await {
+                        Some(Ok(value)) => {
+                            state.received += 1;
+                            state.reconnects = 0;
+                            return Some((Ok(value), state));
+                        }
+                        Some(Err(e))
+                            if is_interrupted(&e)
+                                && state.reconnects < max_reconnects =>
+                        {
+                            state.reconnects += 1;
+                            state.stream = None;
+                        }
+                        Some(Err(e)) => return Some((Err(e), state)),
+                        None => return None,
+                    }
+                }
+            },
+        )))
+    }
+
+    /// Consumes the wrapper, returning a stream of values.
+    pub fn into_inner(
+        self,
+    ) -> impl Stream<Item = Result<T, ServerFnError<CustErr>>> + Send {
+        self.0
+    }
+}
+
+#[cfg(feature = "json")]
+impl<S, T> From<S> for JsonStream<T>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: 'static,
+{
+    fn from(value: S) -> Self {
+        Self(Box::pin(value.map(Ok)))
+    }
+}
+
+// errors are sent as a line starting with `!`, followed by the serialized
+// error as a JSON string, which can never be mistaken for a JSON value
+#[cfg(feature = "json")]
+const JSON_STREAM_ERROR_PREFIX: u8 = b'!';
+
+#[cfg(feature = "json")]
+impl<T, CustErr, Response> IntoRes<StreamingJson, Response, CustErr>
+    for JsonStream<T, CustErr>
+where
+    Response: Res<CustErr>,
+    T: serde::Serialize + 'static,
+    CustErr: std::str::FromStr + std::fmt::Display + 'static,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        use crate::error::ServerFnErrorSerde;
+
+        Response::try_from_stream(
+            StreamingJson::CONTENT_TYPE,
+            self.into_inner().map(|value| {
+                let mut line = match value {
+                    Ok(value) => serde_json::to_vec(&value).map_err(|e| {
+                        ServerFnError::Serialization(e.to_string())
+                    })?,
+                    Err(e) => {
+                        let e = e.ser().unwrap_or_else(|_| e.to_string());
+                        let mut line = vec![JSON_STREAM_ERROR_PREFIX];
+                        line.extend(serde_json::to_vec(&e).map_err(|e| {
+                            ServerFnError::Serialization(e.to_string())
+                        })?);
+                        line
+                    }
+                };
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            }),
+        )
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T, CustErr, Response> FromRes<StreamingJson, Response, CustErr>
+    for JsonStream<T, CustErr>
+where
+    Response: ClientRes<CustErr> + Send,
+    T: serde::de::DeserializeOwned + Send + 'static,
+    CustErr: std::str::FromStr + std::fmt::Display + Send + 'static,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let stream = res.try_into_stream()?;
+        Ok(JsonStream(Box::pin(json_lines(stream).map(
+            |line| match line {
+                Ok(line) => parse_json_line(&line),
+                Err(e) => Err(interrupted(e)),
+            },
+        ))))
+    }
+}
+
+// an error reading the body of the response means that it was interrupted,
+// which is reported like the network error it most likely is
+#[cfg(feature = "json")]
+fn interrupted<CustErr>(error: ServerFnError) -> ServerFnError<CustErr> {
+    match error {
+        ServerFnError::Request(e) | ServerFnError::Response(e) => {
+            ServerFnError::Request(e)
+        }
+        e => e.into_custom(),
+    }
+}
+
+#[cfg(feature = "json")]
+fn is_interrupted<CustErr>(error: &ServerFnError<CustErr>) -> bool {
+    matches!(error, ServerFnError::Request(_))
+}
+
+/// Splits a stream of bytes into lines, which may span several chunks.
+#[cfg(feature = "json")]
+fn json_lines(
+    stream: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+) -> impl Stream<Item = Result<Vec<u8>, ServerFnError>> + Send {
+    futures::stream::unfold(
+        (Box::pin(stream), Vec::new(), false),
+        |(mut stream, mut buf, mut done)| async move {
+            loop {
+                if let Some(end) = buf.iter().position(|byte| *byte == b'\n') {
+                    let line = buf.drain(..=end).collect();
+                    return Some((Ok(line), (stream, buf, done)));
+                }
+                if done {
+                    if buf.is_empty() {
+                        return None;
+                    }
+                    let line = std::mem::take(&mut buf);
+                    return Some((Ok(line), (stream, buf, done)));
+                }
+                match stream.next().await {
+                    Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                    // the response was interrupted, so any partial line is lost
+                    Some(Err(e)) => {
+                        buf.clear();
+                        return Some((Err(e), (stream, buf, true)));
+                    }
+                    None => done = true,
+                }
+            }
+        },
+    )
+    .filter(|line| {
+        let blank = matches!(
+            line,
+            Ok(line) if line.iter().all(u8::is_ascii_whitespace)
+        );
+        futures::future::ready(!blank)
+    })
+}
+
+#[cfg(feature = "json")]
+fn parse_json_line<T, CustErr>(line: &[u8]) -> Result<T, ServerFnError<CustErr>>
+where
+    T: serde::de::DeserializeOwned,
+    CustErr: std::str::FromStr + std::fmt::Display,
+{
+    use crate::error::ServerFnErrorSerde;
+
+    // trailing whitespace, like the newline, is ignored by `serde_json`
+    match line.split_first() {
+        Some((&JSON_STREAM_ERROR_PREFIX, error)) => {
+            let error = serde_json::from_slice::<String>(error)
+                .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
+            Err(ServerFnError::de(&error))
+        }
+        _ => serde_json::from_slice(line)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::{
+        fmt::{self, Display},
+        str::FromStr,
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct NotFound(String);
+
+    impl Display for NotFound {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl FromStr for NotFound {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(NotFound(s.to_string()))
+        }
+    }
+
+    fn lines(
+        chunks: Vec<Result<&'static str, ServerFnError>>,
+    ) -> Vec<Result<String, ServerFnError>> {
+        let chunks = futures::stream::iter(
+            chunks.into_iter().map(|chunk| chunk.map(Bytes::from)),
+        );
+        block_on(
+            json_lines(chunks)
+                .map(|line| line.map(|line| String::from_utf8(line).unwrap()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn lines_can_be_split_across_chunks() {
+        assert_eq!(
+            lines(vec![Ok("[1,"), Ok("2]\n[3"), Ok("]\n\n"), Ok("4\n")]),
+            vec![
+                Ok("[1,2]\n".to_string()),
+                Ok("[3]\n".to_string()),
+                Ok("4\n".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_final_line_is_yielded() {
+        assert_eq!(
+            lines(vec![Ok("1\n2")]),
+            vec![Ok("1\n".to_string()), Ok("2".to_string())]
+        );
+        assert_eq!(
+            parse_json_line::<u32, NoCustomError>(b"2"),
+            Ok(2),
+            "a complete value without its newline is still parsed"
+        );
+        assert!(matches!(
+            parse_json_line::<Vec<u32>, NoCustomError>(b"[1,"),
+            Err(ServerFnError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn interrupted_stream_ends_with_its_error() {
+        assert_eq!(
+            lines(vec![
+                Ok("1\n2"),
+                Err(ServerFnError::Response("connection reset".into())),
+                Ok("3\n"),
+            ]),
+            vec![
+                Ok("1\n".to_string()),
+                Err(ServerFnError::Response("connection reset".into()))
+            ]
+        );
+        assert_eq!(
+            interrupted::<NotFound>(ServerFnError::Response(
+                "connection reset".into()
+            )),
+            ServerFnError::Request("connection reset".into())
+        );
+    }
+
+    #[test]
+    fn error_lines_are_deserialized() {
+        let error =
+            ServerFnError::WrappedServerError(NotFound("no such user".into()));
+        let line = {
+            use crate::error::ServerFnErrorSerde;
+            let mut line = vec![JSON_STREAM_ERROR_PREFIX];
+            line.extend(serde_json::to_vec(&error.ser().unwrap()).unwrap());
+            line.push(b'\n');
+            line
+        };
+
+        assert_eq!(parse_json_line::<u32, NotFound>(&line), Err(error));
+        assert_eq!(
+            parse_json_line::<u32, NotFound>(b"42\n"),
+            Ok(42),
+            "values are parsed as usual"
+        );
+    }
+
+    #[test]
+    fn reconnects_where_the_stream_was_interrupted() {
+        let connections = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let stream = JsonStream::<usize>::reconnecting(2, {
+            let connections = connections.clone();
+            move |received| {
+                connections.lock().unwrap().push(received);
+                let values = (received..4).map(Ok).collect::<Vec<_>>();
+                // every response but the last one is interrupted after a value
+                let values = match values.len() {
+                    1 => values,
+                    _ => vec![
+                        values[0].clone(),
+                        Err(ServerFnError::Request("connection reset".into())),
+                    ],
+                };
+                async move { Ok(JsonStream::new(futures::stream::iter(values))) }
+            }
+        });
+
+        assert_eq!(
+            block_on(stream.into_inner().collect::<Vec<_>>()),
+            vec![Ok(0), Ok(1), Ok(2), Ok(3)]
+        );
+        assert_eq!(*connections.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn gives_up_reconnecting_and_keeps_server_errors() {
+        let stream = JsonStream::<usize>::reconnecting(1, |_| async {
+            Ok(JsonStream::new(futures::stream::iter(vec![
+                Err(ServerFnError::ServerError("not found".into())),
+                Err(ServerFnError::Request("connection reset".into())),
+            ])))
+        });
+
+        assert_eq!(
+            block_on(stream.into_inner().collect::<Vec<_>>()),
+            vec![
+                Err(ServerFnError::ServerError("not found".into())),
+                Err(ServerFnError::ServerError("not found".into())),
+                Err(ServerFnError::Request("connection reset".into())),
+            ]
+        );
+    }
+}
@@ -12,6 +12,15 @@ pub struct GetUrl;
 /// Pass arguments as the URL-encoded body of a `POST` request.
 pub struct PostUrl;
 
+/// Pass arguments as a URL-encoded query string of a `DELETE` request.
+pub struct DeleteUrl;
+
+/// Pass arguments as the URL-encoded body of a `PUT` request.
+pub struct PutUrl;
+
+/// Pass arguments as the URL-encoded body of a `PATCH` request.
+pub struct PatchUrl;
+
 impl Encoding for GetUrl {
     const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
     const METHOD: Method = Method::GET;
@@ -80,6 +89,108 @@ where
     }
 }
 
+impl Encoding for DeleteUrl {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+    const METHOD: Method = Method::DELETE;
+}
+
+impl<CustErr, T, Request> IntoReq<DeleteUrl, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize + Send,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let data = serde_qs::to_string(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_delete(path, accepts, DeleteUrl::CONTENT_TYPE, &data)
+    }
+}
+
+impl<CustErr, T, Request> FromReq<DeleteUrl, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let string_data = req.as_query().unwrap_or_default();
+        let args = serde_qs::from_str::<Self>(string_data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))?;
+        Ok(args)
+    }
+}
+
+impl Encoding for PutUrl {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+    const METHOD: Method = Method::PUT;
+}
+
+impl<CustErr, T, Request> IntoReq<PutUrl, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize + Send,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let qs = serde_qs::to_string(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_put(path, accepts, PutUrl::CONTENT_TYPE, qs)
+    }
+}
+
+impl<CustErr, T, Request> FromReq<PutUrl, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let string_data = req.try_into_string().await?;
+        let args = serde_qs::from_str::<Self>(&string_data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))?;
+        Ok(args)
+    }
+}
+
+impl Encoding for PatchUrl {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+    const METHOD: Method = Method::PATCH;
+}
+
+impl<CustErr, T, Request> IntoReq<PatchUrl, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+    T: Serialize + Send,
+{
+    fn into_req(
+        self,
+        path: &str,
+        accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        let qs = serde_qs::to_string(&self)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        Request::try_new_patch(path, accepts, PatchUrl::CONTENT_TYPE, qs)
+    }
+}
+
+impl<CustErr, T, Request> FromReq<PatchUrl, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+    T: DeserializeOwned,
+{
+    async fn from_req(req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        let string_data = req.try_into_string().await?;
+        let args = serde_qs::from_str::<Self>(&string_data)
+            .map_err(|e| ServerFnError::Args(e.to_string()))?;
+        Ok(args)
+    }
+}
+
 /* #[async_trait]
 impl<T, Request, Response> Codec<Request, Response, GetUrlJson> for T
 where
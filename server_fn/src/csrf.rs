@@ -0,0 +1,483 @@
+//! With the `csrf` feature, server functions are protected from cross-site
+//! request forgery, in which a page on another site makes the browser call a
+//! server function with the user's cookies.
+//!
+//! Every call that is not a `GET`, `HEAD` or `OPTIONS` request has to carry a
+//! token in the [`HEADER_NAME`] header, which matches the token in the
+//! [`COOKIE_NAME`] cookie. A page on another site can make the browser send
+//! the cookie, but cannot read it, so it cannot send the header.
+//! - The server integrations set the cookie on every server-rendered page whose
+//!   request does not carry one.
+//! - The browser client reads the token from the cookie (setting the cookie
+//!   first, if there is none) and attaches it to every call.
+//! - The `reqwest` client, which has no cookie jar, sends a new token as both
+//!   the cookie and the header with every call.
+//! - Calls without a matching token are rejected with a `403 Forbidden`
+//!   response, which the client receives as a [`ServerFnError::ServerError`].
+//!
+//! Forms that are submitted before the page is hydrated (or without
+//! JavaScript) cannot attach the header. Form submissions are accepted
+//! without it if their `Origin` is the site itself, as browsers send the
+//! `Origin` of form submissions from other sites.
+//!
+//! Server functions called in other ways, like with `fetch` from JavaScript,
+//! should set the header to the token in the cookie:
+//!
+//! ```js
+//! const token = document.cookie.match(/leptos_csrf=([^;]+)/)?.[1];
+//! fetch("/api/add_todo", {
+//!     method: "POST",
+//!     headers: { "x-leptos-csrf": token },
+//!     body,
+//! });
+//! ```
+//!
+//! The cookie is only sent to the site that set it, so server functions that
+//! are called on a different origin (with
+//! [`set_server_url`](crate::client::set_server_url)) cannot be protected in
+//! this way.
+//!
+//! ## Options
+//!
+//! By default, the server sets the cookie with the `Secure` attribute, so that
+//! it is only sent over HTTPS (or to `localhost`), and checks the `Origin` of
+//! form submissions against the `Host` header. Both can be changed with
+//! [`set_options`]:
+//!
+//! ```rust,ignore
+//! // behind a reverse proxy, which sets `X-Forwarded-Host` to the host that
+//! // the browser requested
+//! set_options(CsrfOptions::default().trust_forwarded_host(true));
+//! ```
+
+use crate::error::{NoCustomError, ServerFnError};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+/// The name of the cookie that holds the token.
+pub const COOKIE_NAME: &str = "leptos_csrf";
+
+/// The name of the header in which the client sends the token.
+pub const HEADER_NAME: &str = "x-leptos-csrf";
+
+const TOKEN_LEN: usize = 32;
+
+/// How the server sets the cookie and checks requests. See the
+/// [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CsrfOptions {
+    /// Whether the cookie is set with the `Secure` attribute, so that the
+    /// browser only sends it over HTTPS (or to `localhost`). Defaults to
+    /// `true`.
+    pub secure_cookie: bool,
+    /// Whether the `Origin` of form submissions is checked against the
+    /// `X-Forwarded-Host` header, when there is one, rather than against the
+    /// `Host` header. This should only be enabled behind a proxy that sets
+    /// the header, as clients can set it themselves. Defaults to `false`.
+    pub trust_forwarded_host: bool,
+}
+
+impl Default for CsrfOptions {
+    fn default() -> Self {
+        Self {
+            secure_cookie: true,
+            trust_forwarded_host: false,
+        }
+    }
+}
+
+impl CsrfOptions {
+    /// Sets whether the cookie is set with the `Secure` attribute.
+    pub fn secure_cookie(mut self, secure_cookie: bool) -> Self {
+        self.secure_cookie = secure_cookie;
+        self
+    }
+
+    /// Sets whether the `Origin` of form submissions is checked against the
+    /// `X-Forwarded-Host` header.
+    pub fn trust_forwarded_host(mut self, trust_forwarded_host: bool) -> Self {
+        self.trust_forwarded_host = trust_forwarded_host;
+        self
+    }
+}
+
+static OPTIONS: Lazy<RwLock<CsrfOptions>> = Lazy::new(Default::default);
+
+/// Sets how the server sets the cookie and checks requests.
+///
+/// This replaces any options that were set before.
+pub fn set_options(options: CsrfOptions) {
+    *OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = options;
+}
+
+fn options() -> CsrfOptions {
+    *OPTIONS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Generates a new random token.
+pub fn new_token() -> Result<String, ServerFnError> {
+    let mut token = [0; TOKEN_LEN];
+    getrandom::getrandom(&mut token).map_err(|e| {
+        ServerFnError::<NoCustomError>::ServerError(e.to_string())
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(token))
+}
+
+/// The `Set-Cookie` header value that stores the token in the cookie for the
+/// whole site.
+pub fn cookie(token: &str) -> String {
+    cookie_with(token, options().secure_cookie)
+}
+
+fn cookie_with(token: &str, secure: bool) -> String {
+    let secure = if secure { "; Secure" } else { "" };
+    format!("{COOKIE_NAME}={token}; Path=/; SameSite=Strict{secure}")
+}
+
+/// Finds the token in the value of a `Cookie` header.
+pub fn token_from_cookies(cookies: &str) -> Option<&str> {
+    cookies.split(';').find_map(|cookie| {
+        let (name, value) = cookie.split_once('=')?;
+        (name.trim() == COOKIE_NAME && !value.trim().is_empty())
+            .then(|| value.trim())
+    })
+}
+
+/// Returns the `Set-Cookie` header value that issues a new token, if none of
+/// the `Cookie` headers of a request carries one.
+pub fn issue<'a>(cookies: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    if cookies.into_iter().any(|c| token_from_cookies(c).is_some()) {
+        None
+    } else {
+        new_token().ok().map(|token| cookie(&token))
+    }
+}
+
+/// Checks that a server function request with the given method is not
+/// forged, from its `Cookie` headers and a function that looks up its other
+/// headers by name.
+pub fn verify<'a>(
+    method: &str,
+    cookies: impl IntoIterator<Item = &'a str>,
+    header: impl Fn(&str) -> Option<&'a str>,
+) -> Result<(), ServerFnError> {
+    verify_with(options(), method, cookies, header)
+}
+
+fn verify_with<'a>(
+    options: CsrfOptions,
+    method: &str,
+    cookies: impl IntoIterator<Item = &'a str>,
+    header: impl Fn(&str) -> Option<&'a str>,
+) -> Result<(), ServerFnError> {
+    if matches!(method, "GET" | "HEAD" | "OPTIONS") {
+        return Ok(());
+    }
+    let cookie = cookies.into_iter().find_map(token_from_cookies);
+    match (cookie, header(HEADER_NAME)) {
+        (Some(cookie), Some(token)) if constant_time_eq(cookie, token) => {
+            Ok(())
+        }
+        (_, Some(_)) => {
+            Err(ServerFnError::ServerError("invalid CSRF token".into()))
+        }
+        (_, None)
+            if is_form(header("content-type"))
+                && is_same_origin(options, &header) =>
+        {
+            Ok(())
+        }
+        (_, None) => {
+            Err(ServerFnError::ServerError("missing CSRF token".into()))
+        }
+    }
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+// the content types of requests that an HTML form can make
+fn is_form(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|content_type| {
+        [
+            "application/x-www-form-urlencoded",
+            "multipart/form-data",
+            "text/plain",
+        ]
+        .iter()
+        .any(|form| content_type.starts_with(form))
+    })
+}
+
+fn is_same_origin<'a>(
+    options: CsrfOptions,
+    header: &impl Fn(&str) -> Option<&'a str>,
+) -> bool {
+    let origin = header("origin")
+        .and_then(|origin| origin.split_once("://"))
+        .map(|(_, host)| host);
+    let host = options
+        .trust_forwarded_host
+        .then(|| header("x-forwarded-host"))
+        .flatten()
+        .or_else(|| header("host"));
+    match (origin, host) {
+        (Some(origin), Some(host)) => origin.eq_ignore_ascii_case(host),
+        _ => false,
+    }
+}
+
+/// Reads the token from the cookie of the page, setting a new one if there
+/// is none.
+#[cfg(feature = "browser")]
+pub(crate) fn browser_token() -> Result<String, ServerFnError> {
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlDocument;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.dyn_into::<HtmlDocument>().ok())
+        .ok_or_else(|| {
+            ServerFnError::<NoCustomError>::Request(
+                "no document to read the CSRF token from".into(),
+            )
+        })?;
+    let cookies = document.cookie().map_err(|e| {
+        ServerFnError::<NoCustomError>::Request(format!("{e:?}"))
+    })?;
+    if let Some(token) = token_from_cookies(&cookies) {
+        return Ok(token.to_string());
+    }
+    let token = new_token()?;
+    // the browser ignores a `Secure` cookie set by a page served over HTTP
+    let secure = document
+        .location()
+        .and_then(|location| location.protocol().ok())
+        .is_some_and(|protocol| protocol == "https:");
+    document
+        .set_cookie(&cookie_with(&token, secure))
+        .map_err(|e| {
+            ServerFnError::<NoCustomError>::Request(format!("{e:?}"))
+        })?;
+    Ok(token)
+}
+
+/// Rejects forged server function requests before they reach the server
+/// function.
+#[allow(unused)] // used by server integrations
+pub(crate) struct CsrfService<Req, Res>(
+    pub(crate) crate::middleware::BoxedService<Req, Res>,
+);
+
+#[cfg(feature = "axum-no-default")]
+mod axum {
+    use super::CsrfService;
+    use crate::{middleware::Service, response::Res};
+    use axum::body::Body;
+    use http::{header::COOKIE, Request, Response};
+    use std::{future::Future, pin::Pin};
+
+    impl Service<Request<Body>, Response<Body>>
+        for CsrfService<Request<Body>, Response<Body>>
+    {
+        fn run(
+            &mut self,
+            req: Request<Body>,
+        ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+            let headers = req.headers();
+            let verified = super::verify(
+                req.method().as_str(),
+                headers
+                    .get_all(COOKIE)
+                    .iter()
+                    .filter_map(|value| value.to_str().ok()),
+                |name| headers.get(name).and_then(|value| value.to_str().ok()),
+            );
+            match verified {
+                Ok(()) => self.0 .0.run(req),
+                Err(err) => {
+                    let path = req.uri().path().to_string();
+                    Box::pin(async move {
+                        Response::error_response_with_status(&path, &err, 403)
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix {
+    use super::CsrfService;
+    use crate::{
+        middleware::Service,
+        request::actix::ActixRequest,
+        response::{actix::ActixResponse, Res},
+    };
+    use actix_web::http::header::COOKIE;
+    use std::{future::Future, pin::Pin};
+
+    impl Service<ActixRequest, ActixResponse>
+        for CsrfService<ActixRequest, ActixResponse>
+    {
+        fn run(
+            &mut self,
+            req: ActixRequest,
+        ) -> Pin<Box<dyn Future<Output = ActixResponse> + Send>> {
+            let http_req = &req.0 .0;
+            let headers = http_req.headers();
+            let verified = super::verify(
+                http_req.method().as_str(),
+                headers
+                    .get_all(COOKIE)
+                    .filter_map(|value| value.to_str().ok()),
+                |name| headers.get(name).and_then(|value| value.to_str().ok()),
+            );
+            match verified {
+                Ok(()) => self.0 .0.run(req),
+                Err(err) => {
+                    let res = ActixResponse::error_response_with_status(
+                        http_req.path(),
+                        &err,
+                        403,
+                    );
+                    Box::pin(async move { res })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers<'a>(
+        headers: &'a [(&'a str, &'a str)],
+    ) -> impl Fn(&str) -> Option<&'a str> {
+        move |name| {
+            headers
+                .iter()
+                .find(|(header, _)| *header == name)
+                .map(|(_, value)| *value)
+        }
+    }
+
+    fn verify_default<'a>(
+        method: &str,
+        cookies: &[&'a str],
+        header: impl Fn(&str) -> Option<&'a str>,
+    ) -> Result<(), ServerFnError> {
+        verify_with(CsrfOptions::default(), method, cookies.to_vec(), header)
+    }
+
+    #[test]
+    fn safe_methods_are_not_checked() {
+        for method in ["GET", "HEAD", "OPTIONS"] {
+            assert_eq!(verify_default(method, &[], headers(&[])), Ok(()));
+        }
+        assert!(verify_default("POST", &[], headers(&[])).is_err());
+    }
+
+    #[test]
+    fn header_must_match_cookie() {
+        let cookies = ["theme=dark; leptos_csrf=abc"];
+
+        assert_eq!(
+            verify_default("POST", &cookies, headers(&[(HEADER_NAME, "abc")])),
+            Ok(())
+        );
+        assert_eq!(
+            verify_default("POST", &cookies, headers(&[(HEADER_NAME, "abd")])),
+            Err(ServerFnError::ServerError("invalid CSRF token".into()))
+        );
+        assert_eq!(
+            verify_default("POST", &[], headers(&[(HEADER_NAME, "abc")])),
+            Err(ServerFnError::ServerError("invalid CSRF token".into()))
+        );
+        assert_eq!(
+            verify_default("POST", &cookies, headers(&[])),
+            Err(ServerFnError::ServerError("missing CSRF token".into()))
+        );
+    }
+
+    #[test]
+    fn forms_from_the_same_origin_are_accepted() {
+        let form = [
+            ("content-type", "application/x-www-form-urlencoded"),
+            ("origin", "https://example.com"),
+            ("host", "example.com"),
+        ];
+        assert_eq!(verify_default("POST", &[], headers(&form)), Ok(()));
+
+        let other_origin = [
+            ("content-type", "multipart/form-data; boundary=x"),
+            ("origin", "https://evil.com"),
+            ("host", "example.com"),
+        ];
+        assert!(verify_default("POST", &[], headers(&other_origin)).is_err());
+
+        let no_origin =
+            [("content-type", "text/plain"), ("host", "example.com")];
+        assert!(verify_default("POST", &[], headers(&no_origin)).is_err());
+
+        let not_a_form = [
+            ("content-type", "application/json"),
+            ("origin", "https://example.com"),
+            ("host", "example.com"),
+        ];
+        assert!(verify_default("POST", &[], headers(&not_a_form)).is_err());
+    }
+
+    #[test]
+    fn forwarded_host_is_only_trusted_when_enabled() {
+        let forwarded = [
+            ("content-type", "application/x-www-form-urlencoded"),
+            ("origin", "https://evil.com"),
+            ("x-forwarded-host", "evil.com"),
+            ("host", "example.com"),
+        ];
+        assert!(verify_default("POST", &[], headers(&forwarded)).is_err());
+
+        let trusted = CsrfOptions::default().trust_forwarded_host(true);
+        assert_eq!(
+            verify_with(trusted, "POST", [], headers(&forwarded)),
+            Ok(())
+        );
+
+        // the `Host` header is still used when there is no forwarded host
+        let direct = [
+            ("content-type", "application/x-www-form-urlencoded"),
+            ("origin", "https://example.com"),
+            ("host", "example.com"),
+        ];
+        assert_eq!(verify_with(trusted, "POST", [], headers(&direct)), Ok(()));
+    }
+
+    #[test]
+    fn cookie_is_secure_unless_disabled() {
+        assert_eq!(
+            cookie_with("abc", true),
+            "leptos_csrf=abc; Path=/; SameSite=Strict; Secure"
+        );
+        assert_eq!(
+            cookie_with("abc", false),
+            "leptos_csrf=abc; Path=/; SameSite=Strict"
+        );
+    }
+
+    #[test]
+    fn token_is_read_from_cookies() {
+        assert_eq!(token_from_cookies("a=1; leptos_csrf=abc"), Some("abc"));
+        assert_eq!(token_from_cookies("leptos_csrf="), None);
+        assert_eq!(issue(["leptos_csrf=abc"]), None);
+        assert!(issue(["a=1"]).is_some());
+    }
+}
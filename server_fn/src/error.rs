@@ -1,10 +1,15 @@
-use serde::{Deserialize, Serialize};
+use crate::{
+    rate_limit::RATE_LIMIT_STATUS,
+    validation::{ValidationErrors, VALIDATION_STATUS},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     error, fmt,
     fmt::{Display, Write},
     ops,
     str::FromStr,
     sync::Arc,
+    time::Duration,
 };
 use thiserror::Error;
 use url::Url;
@@ -178,11 +183,15 @@ impl<E> ViaError<E> for WrapError<E> {
 /// Unlike [`ServerFnErrorErr`], this does not implement [`Error`](trait@std::error::Error).
 /// This means that other error types can easily be converted into it using the
 /// `?` operator.
+///
+/// New kinds of errors may be added in minor releases, so matches on it
+/// must include a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "rkyv",
     derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
 )]
+#[non_exhaustive]
 pub enum ServerFnError<E = NoCustomError> {
     /// A user-defined custom error type, which defaults to [`NoCustomError`].
     WrappedServerError(E),
@@ -202,6 +211,44 @@ pub enum ServerFnError<E = NoCustomError> {
     Args(String),
     /// Occurs on the server if there's a missing argument.
     MissingArg(String),
+    /// Occurs on the server if the arguments fail validation. See [`crate::validation`].
+    Validation(ValidationErrors),
+    /// Occurs on the server if the call exceeds a rate limit, with the number
+    /// of seconds after which it can be made again. See [`crate::rate_limit`].
+    RateLimited(u64),
+}
+
+impl<CustErr> ServerFnError<CustErr> {
+    /// The error messages for each invalid field, if the arguments failed
+    /// validation on the server.
+    pub fn validation_errors(&self) -> Option<&ValidationErrors> {
+        match self {
+            ServerFnError::Validation(errors) => Some(errors),
+            _ => None,
+        }
+    }
+
+    /// How long to wait before making the call again, if it exceeded a rate
+    /// limit on the server.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ServerFnError::RateLimited(secs) => {
+                Some(Duration::from_secs(*secs))
+            }
+            _ => None,
+        }
+    }
+
+    /// The HTTP status code of the response for this error: `422` if the
+    /// arguments failed validation, `429` if the call exceeded a rate limit,
+    /// and `500` otherwise.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ServerFnError::Validation(_) => VALIDATION_STATUS,
+            ServerFnError::RateLimited(_) => RATE_LIMIT_STATUS,
+            _ => 500,
+        }
+    }
 }
 
 impl ServerFnError<NoCustomError> {
@@ -209,6 +256,29 @@ impl ServerFnError<NoCustomError> {
     pub fn new(msg: impl ToString) -> Self {
         Self::ServerError(msg.to_string())
     }
+
+    /// Converts the error into one with a custom error type, which it does
+    /// not contain.
+    #[allow(unused)] // used by the clients
+    pub(crate) fn into_custom<CustErr>(self) -> ServerFnError<CustErr> {
+        match self {
+            ServerFnError::WrappedServerError(e) => {
+                ServerFnError::ServerError(e.to_string())
+            }
+            ServerFnError::Registration(s) => ServerFnError::Registration(s),
+            ServerFnError::Request(s) => ServerFnError::Request(s),
+            ServerFnError::Response(s) => ServerFnError::Response(s),
+            ServerFnError::ServerError(s) => ServerFnError::ServerError(s),
+            ServerFnError::Deserialization(s) => {
+                ServerFnError::Deserialization(s)
+            }
+            ServerFnError::Serialization(s) => ServerFnError::Serialization(s),
+            ServerFnError::Args(s) => ServerFnError::Args(s),
+            ServerFnError::MissingArg(s) => ServerFnError::MissingArg(s),
+            ServerFnError::Validation(e) => ServerFnError::Validation(e),
+            ServerFnError::RateLimited(s) => ServerFnError::RateLimited(s),
+        }
+    }
 }
 
 impl<CustErr> From<CustErr> for ServerFnError<CustErr> {
@@ -251,6 +321,10 @@ where
                 ServerFnError::Response(s) =>
                     format!("error generating HTTP response: {s}"),
                 ServerFnError::WrappedServerError(e) => format!("{e}"),
+                ServerFnError::Validation(e) =>
+                    format!("invalid server function arguments: {e}"),
+                ServerFnError::RateLimited(s) =>
+                    format!("too many requests, retry after {s} seconds"),
             }
         )
     }
@@ -301,6 +375,13 @@ where
             ServerFnError::MissingArg(e) => {
                 write!(&mut buf, "MissingArg|{e}")
             }
+            ServerFnError::Validation(e) => {
+                let json = serde_json::to_string(e).map_err(|_| fmt::Error)?;
+                write!(&mut buf, "Validation|{json}")
+            }
+            ServerFnError::RateLimited(s) => {
+                write!(&mut buf, "RateLimited|{s}")
+            }
         }?;
         Ok(buf)
     }
@@ -330,6 +411,12 @@ where
                 "MissingArg" => {
                     Some(ServerFnError::MissingArg(data.to_string()))
                 }
+                "Validation" => serde_json::from_str(data)
+                    .ok()
+                    .map(ServerFnError::Validation),
+                "RateLimited" => {
+                    data.parse().ok().map(ServerFnError::RateLimited)
+                }
                 _ => None,
             })
             .unwrap_or_else(|| {
@@ -340,6 +427,118 @@ where
     }
 }
 
+/// A custom error type that a server function can return directly, as
+/// `Result<T, MyError>`, rather than wrapped in a [`ServerFnError`].
+///
+/// The error is sent to the client with `serde`, so it keeps its structure, and
+/// the client can match on it rather than on its text. Errors that occur while
+/// calling the server function, like network errors or errors deserializing
+/// its arguments, are converted into it with
+/// [`from_server_fn_error`](FromServerFnError::from_server_fn_error).
+///
+/// ```rust,ignore
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// pub enum LoginError {
+///     WrongPassword,
+///     Locked { until: String },
+///     Other(String),
+/// }
+///
+/// impl FromServerFnError for LoginError {
+///     fn from_server_fn_error(err: ServerFnError) -> Self {
+///         LoginError::Other(err.to_string())
+///     }
+///
+///     fn status_code(&self) -> u16 {
+///         match self {
+///             LoginError::WrongPassword | LoginError::Locked { .. } => 401,
+///             LoginError::Other(_) => 500,
+///         }
+///     }
+/// }
+///
+/// #[server]
+/// pub async fn login(user: String, password: String) -> Result<(), LoginError> {
+///     // ...
+/// }
+///
+/// // on the client
+/// match login(user, password).await {
+///     Ok(()) => { /* ... */ }
+///     Err(LoginError::WrongPassword) => { /* ... */ }
+///     Err(LoginError::Locked { until }) => { /* ... */ }
+///     Err(LoginError::Other(e)) => { /* ... */ }
+/// }
+/// ```
+pub trait FromServerFnError: Serialize + DeserializeOwned {
+    /// Converts an error that occurred while calling the server function into
+    /// this type.
+    fn from_server_fn_error(err: ServerFnError) -> Self;
+
+    /// The HTTP status code of the response when a server function returns
+    /// this error. (Defaults to `500`.)
+    fn status_code(&self) -> u16 {
+        500
+    }
+}
+
+/// Wraps a [`FromServerFnError`] type as the custom error of a
+/// [`ServerFnError`], which is how server functions that return it directly
+/// send it to the client.
+///
+/// It is serialized as JSON, which is also how it is displayed.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Typed<E>(pub E);
+
+impl<E> Typed<E> {
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<E: Serialize> Display for Typed<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl<E: DeserializeOwned> FromStr for Typed<E> {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Typed)
+    }
+}
+
+impl<E: FromServerFnError> ServerFnError<Typed<E>> {
+    /// Converts the error into the [`FromServerFnError`] type: either the one
+    /// returned by the server function, or the error that occurred while
+    /// calling it.
+    pub fn into_typed(self) -> E {
+        let err = match self {
+            ServerFnError::WrappedServerError(Typed(e)) => return e,
+            ServerFnError::Registration(s) => ServerFnError::Registration(s),
+            ServerFnError::Request(s) => ServerFnError::Request(s),
+            ServerFnError::Response(s) => ServerFnError::Response(s),
+            ServerFnError::ServerError(s) => ServerFnError::ServerError(s),
+            ServerFnError::Deserialization(s) => {
+                ServerFnError::Deserialization(s)
+            }
+            ServerFnError::Serialization(s) => ServerFnError::Serialization(s),
+            ServerFnError::Args(s) => ServerFnError::Args(s),
+            ServerFnError::MissingArg(s) => ServerFnError::MissingArg(s),
+            ServerFnError::Validation(e) => ServerFnError::Validation(e),
+            ServerFnError::RateLimited(s) => ServerFnError::RateLimited(s),
+        };
+        E::from_server_fn_error(err)
+    }
+}
+
 impl<E> std::error::Error for ServerFnError<E>
 where
     E: std::error::Error + 'static,
@@ -362,7 +561,10 @@ where
 ///
 /// [`ServerFnError`] and [`ServerFnErrorErr`] mutually implement [`From`], so
 /// it is easy to convert between the two types.
+///
+/// Like [`ServerFnError`], new kinds of errors may be added in minor releases.
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ServerFnErrorErr<E = NoCustomError> {
     /// A user-defined custom error type, which defaults to [`NoCustomError`].
     #[error("internal error: {0}")]
@@ -391,6 +593,12 @@ pub enum ServerFnErrorErr<E = NoCustomError> {
     /// Occurs on the server if there is an error creating an HTTP response.
     #[error("error creating response {0}")]
     Response(String),
+    /// Occurs on the server if the arguments fail validation.
+    #[error("invalid server function arguments: {0}")]
+    Validation(ValidationErrors),
+    /// Occurs on the server if the call exceeds a rate limit.
+    #[error("too many requests, retry after {0} seconds")]
+    RateLimited(u64),
 }
 
 impl<CustErr> From<ServerFnError<CustErr>> for ServerFnErrorErr<CustErr> {
@@ -417,6 +625,12 @@ impl<CustErr> From<ServerFnError<CustErr>> for ServerFnErrorErr<CustErr> {
                 ServerFnErrorErr::WrappedServerError(value)
             }
             ServerFnError::Response(value) => ServerFnErrorErr::Response(value),
+            ServerFnError::Validation(value) => {
+                ServerFnErrorErr::Validation(value)
+            }
+            ServerFnError::RateLimited(value) => {
+                ServerFnErrorErr::RateLimited(value)
+            }
         }
     }
 }
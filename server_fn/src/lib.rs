@@ -67,9 +67,9 @@
 //!   ad hoc HTTP API endpoint, not a magic formula. Any server function can be accessed by any HTTP
 //!   client. You should take care to sanitize any data being returned from the function to ensure it
 //!   does not leak data that should exist only on the server.
-//! - **Server functions can’t be generic.** Because each server function creates a separate API endpoint,
-//!   it is difficult to monomorphize. As a result, server functions cannot be generic (for now?) If you need to use
-//!   a generic function, you can define a generic inner function called by multiple concrete server functions.
+//! - **Generic server functions must be registered.** Because each server function creates a separate API endpoint,
+//!   a generic server function must list the types it can be called with, like `#[register(<Database>, <Mock>)]`,
+//!   each of which gets its own endpoint.
 //! - **Arguments and return types must be serializable.** We support a variety of different encodings,
 //!   but one way or another arguments need to be serialized to be sent to the server and deserialized
 //!   on the server, and the return type must be serialized on the server and deserialized on the client.
@@ -96,23 +96,53 @@
 //! [`serde_qs`]: <https://docs.rs/serde_qs/latest/serde_qs/>
 //! [`cbor`]: <https://docs.rs/cbor/latest/cbor/>
 
+/// Batching calls to server functions made at the same time into one request.
+pub mod batch;
+
 /// Implementations of the client side of the server function call.
 pub mod client;
 
+/// Caching and deduplication of server function calls on the client.
+pub mod cache;
+
 /// Encodings for arguments and results.
 pub mod codec;
+/// Protection of server functions from cross-site request forgery.
+#[cfg(feature = "csrf")]
+pub mod csrf;
 
 #[macro_use]
 /// Error types and utilities.
 pub mod error;
 /// Types to add server middleware to a server function.
 pub mod middleware;
+/// Replacing server functions with mock implementations in tests.
+pub mod mock;
+/// OpenAPI descriptions of server functions.
+#[cfg(feature = "openapi")]
+pub mod openapi;
+/// Limiting how often clients can call server functions.
+pub mod rate_limit;
 /// Utilities to allow client-side redirects.
 pub mod redirect;
 /// Types and traits for  for HTTP requests.
 pub mod request;
 /// Types and traits for HTTP responses.
 pub mod response;
+/// REST-style HTTP methods and path parameters for server functions.
+pub mod rest;
+/// Retrying failed server function calls.
+pub mod retry;
+/// Signed and encrypted values that are passed through the client.
+#[cfg(feature = "sealed")]
+pub mod sealed;
+/// Tracing spans for server function calls.
+#[cfg(feature = "tracing")]
+pub mod trace;
+/// Validating the arguments of server functions on the server.
+pub mod validation;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 #[cfg(feature = "actix")]
 #[doc(hidden)]
@@ -129,20 +159,36 @@ pub use error::ServerFnError;
 use error::ServerFnErrorSerde;
 #[cfg(feature = "form-redirects")]
 use error::ServerFnUrlError;
+pub use futures::future::AbortHandle;
+use futures::{future, FutureExt};
 use http::Method;
-use middleware::{Layer, Service};
+use middleware::{BoxedService, Layer, Service};
 use once_cell::sync::Lazy;
+#[cfg(feature = "protobuf")]
+pub use prost;
 use redirect::RedirectHook;
 use request::Req;
 use response::{ClientRes, Res};
+use retry::{RetryCause, RetryPolicy};
 #[cfg(feature = "rkyv")]
 pub use rkyv;
+#[cfg(feature = "openapi")]
+pub use schemars;
 #[doc(hidden)]
 pub use serde;
 #[doc(hidden)]
 #[cfg(feature = "serde-lite")]
 pub use serde_lite;
-use std::{fmt::Display, future::Future, pin::Pin, str::FromStr, sync::Arc};
+#[cfg(feature = "ssr")]
+use std::any::TypeId;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+};
 #[doc(hidden)]
 pub use xxhash_rust;
 
@@ -225,12 +271,74 @@ where
         Self::PATH
     }
 
+    /// The path that this call to the server function is sent to, which is
+    /// [`Self::PATH`] with any path parameters filled in from the arguments.
+    /// See [`rest`].
+    fn request_path(&self) -> Cow<'static, str> {
+        Cow::Borrowed(Self::PATH)
+    }
+
+    /// Sets the arguments that are path parameters from the path of the
+    /// request, which matches [`Self::PATH`]. See [`rest`].
+    fn extract_path_params(
+        &mut self,
+        path: &str,
+    ) -> Result<(), ServerFnError<Self::Error>> {
+        _ = path;
+        Ok(())
+    }
+
     /// Middleware that should be applied to this server function.
     fn middlewares(
     ) -> Vec<Arc<dyn Layer<Self::ServerRequest, Self::ServerResponse>>> {
         Vec::new()
     }
 
+    /// The [`RetryPolicy`] that failed calls to this server function are
+    /// retried with on the client, if any.
+    ///
+    /// This defaults to the global policy set with [`retry::set_retry_policy`]
+    /// for server functions called with a `GET` request, which are assumed to
+    /// be idempotent, and to no retries otherwise.
+    fn retry_policy() -> Option<RetryPolicy> {
+        if Self::InputEncoding::METHOD == Method::GET {
+            retry::retry_policy()
+        } else {
+            None
+        }
+    }
+
+    /// How long a successful result of calling this server function on the
+    /// client is cached for, if at all. See [`cache::run_cached`].
+    fn cache_ttl() -> Option<std::time::Duration> {
+        None
+    }
+
+    /// The HTTP status code of the response when the server function returns
+    /// the given error, which defaults to `422` for [`ServerFnError::Validation`]
+    /// and `500` otherwise.
+    ///
+    /// For server functions that return a [`FromServerFnError`](error::FromServerFnError)
+    /// type, this is its [`status_code`](error::FromServerFnError::status_code).
+    fn error_status(err: &ServerFnError<Self::Error>) -> u16 {
+        err.status_code()
+    }
+
+    /// A hash of the arguments, which identifies calls with the same
+    /// arguments in [traces](crate::trace) without recording them.
+    ///
+    /// The `#[server]` macro hashes their `Debug` representation.
+    fn args_fingerprint(&self) -> Option<u64> {
+        None
+    }
+
+    /// Validates the arguments on the server before [`Self::run_body`] runs,
+    /// which does nothing unless the server function is declared with
+    /// `#[server(validate = true)]`. See [`validation`].
+    fn validate(&self) -> Result<(), validation::ValidationErrors> {
+        Ok(())
+    }
+
     /// The body of the server function. This will only run on the server.
     fn run_body(
         self,
@@ -251,7 +359,18 @@ where
         #[cfg(feature = "form-redirects")]
         let mut referer = req.referer().as_deref().map(ToOwned::to_owned);
 
-        async move {
+        #[cfg(feature = "tracing")]
+        let span = trace::server_fn_span(&trace::SpanInfo {
+            name: std::any::type_name::<Self>(),
+            path: Self::PATH,
+            method: &Self::InputEncoding::METHOD,
+            trace_parent: req.trace_parent().as_deref(),
+        });
+
+        let fut = async move {
+            #[cfg(feature = "tracing")]
+            let start = std::time::Instant::now();
+
             #[allow(unused_variables, unused_mut)]
             // used in form redirects feature
             let (mut res, err) = Self::execute_on_server(req)
@@ -259,11 +378,18 @@ where
                 .map(|res| (res, None))
                 .unwrap_or_else(|e| {
                     (
-                        Self::ServerResponse::error_response(Self::PATH, &e),
+                        Self::ServerResponse::error_response_with_status(
+                            Self::PATH,
+                            &e,
+                            Self::error_status(&e),
+                        ),
                         Some(e),
                     )
                 });
 
+            #[cfg(feature = "tracing")]
+            trace::record_outcome(start, err.as_ref());
+
             // if it accepts HTML, we'll redirect to the Referer
             #[cfg(feature = "form-redirects")]
             if accepts_html {
@@ -286,7 +412,10 @@ where
             }
 
             res
-        }
+        };
+        #[cfg(feature = "tracing")]
+        let fut = tracing::Instrument::instrument(fut, span);
+        fut
     }
 
     #[doc(hidden)]
@@ -296,13 +425,68 @@ where
     {
         async move {
             // create and send request on client
+            let path = self.request_path();
             let req =
-                self.into_req(Self::PATH, Self::OutputEncoding::CONTENT_TYPE)?;
+                self.into_req(&path, Self::OutputEncoding::CONTENT_TYPE)?;
             Self::run_on_client_with_req(req, redirect::REDIRECT_HOOK.get())
                 .await
         }
     }
 
+    /// Calls the server function from the client like `run_on_client`,
+    /// retrying failed calls according to its [`retry_policy`](Self::retry_policy).
+    #[doc(hidden)]
+    fn run_on_client_with_retries(
+        self,
+    ) -> impl Future<Output = Result<Self::Output, ServerFnError<Self::Error>>> + Send
+    where
+        Self: Clone,
+    {
+        async move {
+            let Some(policy) = Self::retry_policy() else {
+                return self.run_on_client().await;
+            };
+            let mut attempt = 1;
+            loop {
+                let req = self.clone().into_req(
+                    &self.request_path(),
+                    Self::OutputEncoding::CONTENT_TYPE,
+                )?;
+                // the error is not `Send`, so it must not be held across the
+                // delay before the next attempt
+                let outcome = {
+                    let res = Self::Client::send(req).await;
+                    let cause = match &res {
+                        Ok(res) if (400..=599).contains(&res.status()) => {
+                            Some(RetryCause::Status(res.status()))
+                        }
+                        Err(ServerFnError::Request(_)) => {
+                            Some(RetryCause::Network)
+                        }
+                        _ => None,
+                    };
+                    match cause
+                        .and_then(|cause| policy.retry_after(attempt, cause))
+                    {
+                        Some(delay) => Err(delay),
+                        None => Ok(res?),
+                    }
+                };
+                match outcome {
+                    Ok(res) => {
+                        return Self::run_on_client_with_res(
+                            res,
+                            redirect::REDIRECT_HOOK.get(),
+                        )
+                        .await
+                    }
+                    Err(delay) => Self::Client::sleep(delay).await,
+                }
+                attempt += 1;
+            }
+        }
+    }
+
     #[doc(hidden)]
     fn run_on_client_with_req(
         req: <Self::Client as Client<Self::Error>>::Request,
@@ -311,7 +495,17 @@ where
     {
         async move {
             let res = Self::Client::send(req).await?;
+            Self::run_on_client_with_res(res, redirect_hook).await
+        }
+    }
 
+    #[doc(hidden)]
+    fn run_on_client_with_res(
+        res: <Self::Client as Client<Self::Error>>::Response,
+        redirect_hook: Option<&RedirectHook>,
+    ) -> impl Future<Output = Result<Self::Output, ServerFnError<Self::Error>>> + Send
+    {
+        async move {
             let status = res.status();
             let location = res.location();
             let has_redirect_header = res.has_redirect();
@@ -335,6 +529,42 @@ where
         }
     }
 
+    /// Calls the server function, returning the call along with an
+    /// [`AbortHandle`] that cancels it.
+    ///
+    /// Aborting the call drops it, which cancels the underlying request, and
+    /// makes it return a [`ServerFnError::Request`]. Integrations that stop
+    /// handling a request when its connection is closed then drop the
+    /// server function on the server, too. This is useful to cancel requests
+    /// that have been superseded by newer ones, as in a typeahead search:
+    ///
+    /// ```rust,ignore
+    /// let (call, handle) = Search { query }.abortable();
+    /// if let Some(previous) = in_flight.replace(handle) {
+    ///     previous.abort();
+    /// }
+    /// let results = call.await?;
+    /// ```
+    fn abortable(
+        self,
+    ) -> (
+        impl Future<Output = Result<Self::Output, ServerFnError<Self::Error>>>
+            + Send,
+        AbortHandle,
+    ) {
+        #[cfg(feature = "ssr")]
+        let call = self.run_body();
+        #[cfg(not(feature = "ssr"))]
+        let call = self.run_on_client();
+        let (call, handle) = future::abortable(call);
+        let call = call.map(|res| {
+            res.unwrap_or_else(|_| {
+                Err(ServerFnError::Request("the call was aborted".into()))
+            })
+        });
+        (call, handle)
+    }
+
     /// Runs the server function (on the server), bubbling up an `Err(_)` after any stage.
     #[doc(hidden)]
     fn execute_on_server(
@@ -342,8 +572,18 @@ where
     ) -> impl Future<
         Output = Result<Self::ServerResponse, ServerFnError<Self::Error>>,
     > + Send {
-        async {
-            let this = Self::from_req(req).await?;
+        // path parameters are taken from the path, which is not available
+        // once the request has been consumed
+        let path =
+            rest::has_params(Self::PATH).then(|| req.as_path().to_owned());
+        async move {
+            let mut this = Self::from_req(req).await?;
+            #[cfg(feature = "tracing")]
+            trace::record_args(this.args_fingerprint());
+            if let Some(path) = path {
+                this.extract_path_params(&path)?;
+            }
+            this.validate().map_err(ServerFnError::Validation)?;
             let output = this.run_body().await?;
             let res = output.into_res().await?;
             Ok(res)
@@ -360,10 +600,11 @@ pub use inventory;
 macro_rules! initialize_server_fn_map {
     ($req:ty, $res:ty) => {
         once_cell::sync::Lazy::new(|| {
-            $crate::inventory::iter::<ServerFnTraitObj<$req, $res>>
-                .into_iter()
-                .map(|obj| (obj.path(), obj.clone()))
-                .collect()
+            let mut map = std::collections::HashMap::<_, Vec<_>>::new();
+            for obj in $crate::inventory::iter::<ServerFnTraitObj<$req, $res>> {
+                map.entry(obj.path()).or_default().push(obj.clone());
+            }
+            map.into_iter().collect()
         })
     };
 }
@@ -377,6 +618,8 @@ pub type MiddlewareSet<Req, Res> = Vec<Arc<dyn Layer<Req, Res>>>;
 pub struct ServerFnTraitObj<Req, Res> {
     path: &'static str,
     method: Method,
+    input_encoding: &'static str,
+    output_encoding: &'static str,
     handler: fn(Req) -> Pin<Box<dyn Future<Output = Res> + Send>>,
     middleware: fn() -> MiddlewareSet<Req, Res>,
 }
@@ -386,12 +629,16 @@ impl<Req, Res> ServerFnTraitObj<Req, Res> {
     pub const fn new(
         path: &'static str,
         method: Method,
+        input_encoding: &'static str,
+        output_encoding: &'static str,
         handler: fn(Req) -> Pin<Box<dyn Future<Output = Res> + Send>>,
         middleware: fn() -> MiddlewareSet<Req, Res>,
     ) -> Self {
         Self {
             path,
             method,
+            input_encoding,
+            output_encoding,
             handler,
             middleware,
         }
@@ -407,6 +654,26 @@ impl<Req, Res> ServerFnTraitObj<Req, Res> {
         self.method.clone()
     }
 
+    /// The content type of the server function's arguments.
+    pub fn input_encoding(&self) -> &'static str {
+        self.input_encoding
+    }
+
+    /// The content type of the server function's response.
+    pub fn output_encoding(&self) -> &'static str {
+        self.output_encoding
+    }
+
+    /// Describes the server function's API endpoint.
+    pub fn endpoint(&self) -> ServerFnEndpoint {
+        ServerFnEndpoint {
+            path: self.path,
+            method: self.method(),
+            input_encoding: self.input_encoding,
+            output_encoding: self.output_encoding,
+        }
+    }
+
     /// The handler for this server function.
     pub fn handler(&self, req: Req) -> impl Future<Output = Res> + Send {
         (self.handler)(req)
@@ -434,24 +701,156 @@ impl<Req, Res> Clone for ServerFnTraitObj<Req, Res> {
         Self {
             path: self.path,
             method: self.method.clone(),
+            input_encoding: self.input_encoding,
+            output_encoding: self.output_encoding,
             handler: self.handler,
             middleware: self.middleware,
         }
     }
 }
 
+/// The API endpoint of a registered server function, which can be used to
+/// document the server functions for external clients or reverse proxies.
+///
+/// It is displayed as its method, path, and content types, like
+/// `POST /api/v2/login (application/x-www-form-urlencoded -> application/json)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerFnEndpoint {
+    /// The path of the server function, including its prefix.
+    pub path: &'static str,
+    /// The HTTP method the server function expects.
+    pub method: Method,
+    /// The content type of the server function's arguments.
+    pub input_encoding: &'static str,
+    /// The content type of the server function's response.
+    pub output_encoding: &'static str,
+}
+
+impl Display for ServerFnEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({} -> {})",
+            self.method, self.path, self.input_encoding, self.output_encoding
+        )
+    }
+}
+
+#[allow(unused)] // used by server integrations
+fn sorted_endpoints<Req, Res>(
+    server_fns: &LazyServerFnMap<Req, Res>,
+) -> Vec<ServerFnEndpoint> {
+    let mut endpoints = server_fns
+        .iter()
+        .flat_map(|item| {
+            item.iter()
+                .map(|server_fn| server_fn.endpoint())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    endpoints.sort_by(|a, b| a.path.cmp(b.path));
+    endpoints
+}
+
 #[allow(unused)] // used by server integrations
 type LazyServerFnMap<Req, Res> =
-    Lazy<DashMap<&'static str, ServerFnTraitObj<Req, Res>>>;
+    Lazy<DashMap<&'static str, Vec<ServerFnTraitObj<Req, Res>>>>;
+
+/// Finds the server function that handles requests with the given method to
+/// the given path, which can have path parameters.
+///
+/// Requests with a different method are still handled by a server function at
+/// the same path if none of them expects their method.
+#[allow(unused)] // used by server integrations
+fn find_server_fn<Req, Res>(
+    server_fns: &LazyServerFnMap<Req, Res>,
+    path: &str,
+    method: &Method,
+) -> Option<ServerFnTraitObj<Req, Res>> {
+    let for_method = |candidates: &[ServerFnTraitObj<Req, Res>]| {
+        candidates
+            .iter()
+            .find(|server_fn| server_fn.method == *method)
+            .or_else(|| candidates.first())
+            .cloned()
+    };
+    if let Some(candidates) = server_fns.get(path) {
+        return for_method(&candidates);
+    }
+    server_fns
+        .iter()
+        .filter(|item| {
+            rest::has_params(item.key()) && rest::matches(item.key(), path)
+        })
+        .find_map(|item| {
+            item.iter()
+                .find(|server_fn| server_fn.method == *method)
+                .cloned()
+        })
+        .or_else(|| {
+            server_fns
+                .iter()
+                .find(|item| {
+                    rest::has_params(item.key())
+                        && rest::matches(item.key(), path)
+                })
+                .and_then(|item| for_method(&item))
+        })
+}
+
+// registers a server function, replacing any at the same path with the same method
+#[allow(unused)] // used by server integrations
+fn register_server_fn<Req, Res>(
+    server_fns: &LazyServerFnMap<Req, Res>,
+    server_fn: ServerFnTraitObj<Req, Res>,
+) {
+    let mut entry = server_fns.entry(server_fn.path).or_default();
+    entry.retain(|existing| existing.method != server_fn.method);
+    entry.push(server_fn);
+}
+
+#[allow(unused)] // used by server integrations
+type LazyPrefixMiddleware<Req, Res> =
+    Lazy<std::sync::RwLock<Vec<(&'static str, Arc<dyn Layer<Req, Res>>)>>>;
+
+/// Wraps the server function in its own middleware, and then in any middleware
+/// registered for a prefix of its path.
+#[allow(unused)] // used by server integrations
+fn server_fn_service<Req, Res>(
+    server_fn: &ServerFnTraitObj<Req, Res>,
+    prefix_middleware: &LazyPrefixMiddleware<Req, Res>,
+) -> BoxedService<Req, Res>
+where
+    Req: Send + 'static,
+    Res: 'static,
+{
+    let mut service = BoxedService::new(server_fn.clone());
+    for middleware in server_fn.middleware() {
+        service = middleware.layer(service);
+    }
+    let prefix_middleware =
+        prefix_middleware.read().unwrap_or_else(|e| e.into_inner());
+    for (prefix, middleware) in prefix_middleware.iter() {
+        if server_fn.path().starts_with(prefix) {
+            service = middleware.layer(service);
+        }
+    }
+    service
+}
 
 #[cfg(feature = "ssr")]
 impl<Req: 'static, Res: 'static> inventory::Collect
     for ServerFnTraitObj<Req, Res>
 {
-    #[inline]
     fn registry() -> &'static inventory::Registry {
-        static REGISTRY: inventory::Registry = inventory::Registry::new();
-        &REGISTRY
+        // a `static` in a generic function is shared by all of its
+        // instantiations, so server functions registered for one framework
+        // would be read back as another's: keep one registry per type instead
+        static REGISTRIES: Lazy<DashMap<TypeId, &'static inventory::Registry>> =
+            Lazy::new(DashMap::new);
+        *REGISTRIES
+            .entry(TypeId::of::<Self>())
+            .or_insert_with(|| Box::leak(Box::new(inventory::Registry::new())))
     }
 }
 
@@ -459,17 +858,47 @@ impl<Req: 'static, Res: 'static> inventory::Collect
 #[cfg(feature = "axum-no-default")]
 pub mod axum {
     use crate::{
-        middleware::{BoxedService, Service},
-        Encoding, LazyServerFnMap, ServerFn, ServerFnTraitObj,
+        batch::{self, BatchRequest, BatchResponse, BATCH_PATH},
+        middleware::{BoxedService, Layer, Service},
+        redirect::REDIRECT_HEADER,
+        Encoding, LazyPrefixMiddleware, LazyServerFnMap, ServerFn,
+        ServerFnEndpoint, ServerFnError, ServerFnTraitObj,
     };
     use axum::body::Body;
-    use http::{Method, Request, Response, StatusCode};
+    use http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, LOCATION},
+        HeaderMap, HeaderValue, Method, Request, Response, StatusCode,
+    };
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
 
     static REGISTERED_SERVER_FUNCTIONS: LazyServerFnMap<
         Request<Body>,
         Response<Body>,
     > = initialize_server_fn_map!(Request<Body>, Response<Body>);
 
+    static PREFIX_MIDDLEWARE: LazyPrefixMiddleware<
+        Request<Body>,
+        Response<Body>,
+    > = once_cell::sync::Lazy::new(Default::default);
+
+    /// Wraps every server function whose path starts with `prefix` (like
+    /// `/api/admin`) in the given middleware, such as a `tower` layer.
+    ///
+    /// This is applied outside any middleware declared on the server functions
+    /// themselves, so it runs first. Middleware registered later runs before
+    /// middleware registered earlier. It should be registered before the server
+    /// starts handling requests.
+    pub fn register_middleware(
+        prefix: &'static str,
+        middleware: impl Layer<Request<Body>, Response<Body>>,
+    ) {
+        PREFIX_MIDDLEWARE
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((prefix, Arc::new(middleware)));
+    }
+
     /// Explicitly register a server function. This is only necessary if you are
     /// running the server in a WASM environment (or a rare environment that the
     /// `inventory` crate won't work in.).
@@ -480,11 +909,13 @@ pub mod axum {
                 ServerResponse = Response<Body>,
             > + 'static,
     {
-        REGISTERED_SERVER_FUNCTIONS.insert(
-            T::PATH,
+        crate::register_server_fn(
+            &REGISTERED_SERVER_FUNCTIONS,
             ServerFnTraitObj::new(
                 T::PATH,
                 T::InputEncoding::METHOD,
+                T::InputEncoding::CONTENT_TYPE,
+                T::OutputEncoding::CONTENT_TYPE,
                 |req| Box::pin(T::run_on_server(req)),
                 T::middlewares,
             ),
@@ -495,14 +926,25 @@ pub mod axum {
     pub fn server_fn_paths() -> impl Iterator<Item = (&'static str, Method)> {
         REGISTERED_SERVER_FUNCTIONS
             .iter()
-            .map(|item| (item.path(), item.method()))
+            .flat_map(|item| {
+                item.iter()
+                    .map(|server_fn| (server_fn.path(), server_fn.method()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// All registered server function endpoints, sorted by path.
+    pub fn server_fn_endpoints() -> Vec<ServerFnEndpoint> {
+        crate::sorted_endpoints(&REGISTERED_SERVER_FUNCTIONS)
     }
 
     /// An Axum handler that responds to a server function request.
     pub async fn handle_server_fn(req: Request<Body>) -> Response<Body> {
         let path = req.uri().path();
 
-        if let Some(mut service) = get_server_fn_service(path) {
+        if let Some(mut service) = get_server_fn_service(path, req.method()) {
             service.run(req).await
         } else {
             Response::builder()
@@ -522,17 +964,91 @@ pub mod axum {
     }
 
     /// Returns the server function at the given path as a service that can be modified.
+    ///
+    /// The path can fill in any path parameters of the server function, and
+    /// the method chooses between server functions at the same path.
     pub fn get_server_fn_service(
         path: &str,
+        method: &Method,
     ) -> Option<BoxedService<Request<Body>, Response<Body>>> {
-        REGISTERED_SERVER_FUNCTIONS.get(path).map(|server_fn| {
-            let middleware = (server_fn.middleware)();
-            let mut service = BoxedService::new(server_fn.clone());
-            for middleware in middleware {
-                service = middleware.layer(service);
+        crate::find_server_fn(&REGISTERED_SERVER_FUNCTIONS, path, method).map(
+            |server_fn| {
+                let service =
+                    crate::server_fn_service(&server_fn, &PREFIX_MIDDLEWARE);
+                #[cfg(feature = "csrf")]
+                let service =
+                    BoxedService::new(crate::csrf::CsrfService(service));
+                service
+            },
+        )
+    }
+
+    inventory::submit! {
+        ServerFnTraitObj::new(
+            BATCH_PATH,
+            Method::POST,
+            "application/json",
+            "application/json",
+            |req| Box::pin(handle_batch(req)),
+            Vec::new,
+        )
+    }
+
+    // runs each call in a batch as a request with the headers of the batch
+    async fn handle_batch(req: Request<Body>) -> Response<Body> {
+        let headers = req.headers().clone();
+        batch::handle_batch(req, |call| {
+            let req = batched_request(&headers, call);
+            async move {
+                let res = match req {
+                    Ok(req) => handle_server_fn(req).await,
+                    Err(e) => {
+                        return BatchResponse::error(
+                            &ServerFnError::Args(e.to_string()),
+                            400,
+                        )
+                    }
+                };
+                let status = res.status().as_u16();
+                let location = res
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .map(ToString::to_string);
+                let redirect = location.is_some()
+                    || res.headers().contains_key(REDIRECT_HEADER);
+                match res.into_body().collect().await {
+                    Ok(body) => BatchResponse {
+                        status,
+                        location,
+                        redirect,
+                        body: body.to_bytes().into(),
+                    },
+                    Err(e) => BatchResponse::error(
+                        &ServerFnError::Response(e.to_string()),
+                        500,
+                    ),
+                }
             }
-            service
         })
+        .await
+    }
+
+    fn batched_request(
+        headers: &HeaderMap,
+        call: BatchRequest,
+    ) -> Result<Request<Body>, http::Error> {
+        let mut req = Request::builder()
+            .method(call.method.as_str())
+            .uri(call.uri())
+            .body(Body::from(call.body.into_bytes()))?;
+        let req_headers = req.headers_mut();
+        req_headers.extend(headers.clone());
+        req_headers
+            .insert(CONTENT_TYPE, HeaderValue::try_from(call.content_type)?);
+        req_headers.insert(ACCEPT, HeaderValue::try_from(call.accepts)?);
+        req_headers.remove(CONTENT_LENGTH);
+        Ok(req)
     }
 }
 
@@ -540,20 +1056,53 @@ pub mod axum {
 #[cfg(feature = "actix")]
 pub mod actix {
     use crate::{
-        middleware::BoxedService, request::actix::ActixRequest,
-        response::actix::ActixResponse, Encoding, LazyServerFnMap, ServerFn,
-        ServerFnTraitObj,
+        batch::{self, BatchRequest, BatchResponse, BATCH_PATH},
+        middleware::{BoxedService, Layer},
+        redirect::REDIRECT_HEADER,
+        request::actix::ActixRequest,
+        response::actix::ActixResponse,
+        Encoding, LazyPrefixMiddleware, LazyServerFnMap, ServerFn,
+        ServerFnEndpoint, ServerFnError, ServerFnTraitObj,
+    };
+    use actix_web::{
+        body, dev,
+        http::header::{self, HeaderMap},
+        test::TestRequest,
+        web::Payload,
+        FromRequest, HttpRequest, HttpResponse,
     };
-    use actix_web::{web::Payload, HttpRequest, HttpResponse};
     use http::Method;
     #[doc(hidden)]
     pub use send_wrapper::SendWrapper;
+    use std::{future::Future, sync::Arc};
 
     static REGISTERED_SERVER_FUNCTIONS: LazyServerFnMap<
         ActixRequest,
         ActixResponse,
     > = initialize_server_fn_map!(ActixRequest, ActixResponse);
 
+    static PREFIX_MIDDLEWARE: LazyPrefixMiddleware<
+        ActixRequest,
+        ActixResponse,
+    > = once_cell::sync::Lazy::new(Default::default);
+
+    /// Wraps every server function whose path starts with `prefix` (like
+    /// `/api/admin`) in the given middleware.
+    ///
+    /// This is applied outside any middleware declared on the server functions
+    /// themselves, so it runs first. Middleware registered later runs before
+    /// middleware registered earlier. It should be registered before the server
+    /// starts handling requests.
+    pub fn register_middleware(
+        prefix: &'static str,
+        middleware: impl Layer<ActixRequest, ActixResponse>,
+    ) {
+        PREFIX_MIDDLEWARE
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((prefix, Arc::new(middleware)));
+    }
+
     /// Explicitly register a server function. This is only necessary if you are
     /// running the server in a WASM environment (or a rare environment that the
     /// `inventory` crate won't work in.).
@@ -564,11 +1113,13 @@ pub mod actix {
                 ServerResponse = ActixResponse,
             > + 'static,
     {
-        REGISTERED_SERVER_FUNCTIONS.insert(
-            T::PATH,
+        crate::register_server_fn(
+            &REGISTERED_SERVER_FUNCTIONS,
             ServerFnTraitObj::new(
                 T::PATH,
                 T::InputEncoding::METHOD,
+                T::InputEncoding::CONTENT_TYPE,
+                T::OutputEncoding::CONTENT_TYPE,
                 |req| Box::pin(T::run_on_server(req)),
                 T::middlewares,
             ),
@@ -579,7 +1130,18 @@ pub mod actix {
     pub fn server_fn_paths() -> impl Iterator<Item = (&'static str, Method)> {
         REGISTERED_SERVER_FUNCTIONS
             .iter()
-            .map(|item| (item.path(), item.method()))
+            .flat_map(|item| {
+                item.iter()
+                    .map(|server_fn| (server_fn.path(), server_fn.method()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// All registered server function endpoints, sorted by path.
+    pub fn server_fn_endpoints() -> Vec<ServerFnEndpoint> {
+        crate::sorted_endpoints(&REGISTERED_SERVER_FUNCTIONS)
     }
 
     /// An Actix handler that responds to a server function request.
@@ -588,13 +1150,7 @@ pub mod actix {
         payload: Payload,
     ) -> HttpResponse {
         let path = req.uri().path();
-        if let Some(server_fn) = REGISTERED_SERVER_FUNCTIONS.get(path) {
-            let middleware = (server_fn.middleware)();
-            // http::Method is the only non-Copy type here
-            let mut service = BoxedService::new(server_fn.clone());
-            for middleware in middleware {
-                service = middleware.layer(service);
-            }
+        if let Some(mut service) = get_server_fn_service(path, req.method()) {
             service
                 .0
                 .run(ActixRequest::from((req, payload)))
@@ -616,16 +1172,100 @@ pub mod actix {
     }
 
     /// Returns the server function at the given path as a service that can be modified.
+    ///
+    /// The path can fill in any path parameters of the server function, and
+    /// the method chooses between server functions at the same path.
     pub fn get_server_fn_service(
         path: &str,
+        method: &actix_web::http::Method,
     ) -> Option<BoxedService<ActixRequest, ActixResponse>> {
-        REGISTERED_SERVER_FUNCTIONS.get(path).map(|server_fn| {
-            let middleware = (server_fn.middleware)();
-            let mut service = BoxedService::new(server_fn.clone());
-            for middleware in middleware {
-                service = middleware.layer(service);
+        // Actix uses an older version of `http`
+        let method = Method::from_bytes(method.as_str().as_bytes()).ok()?;
+        crate::find_server_fn(&REGISTERED_SERVER_FUNCTIONS, path, &method).map(
+            |server_fn| {
+                let service =
+                    crate::server_fn_service(&server_fn, &PREFIX_MIDDLEWARE);
+                #[cfg(feature = "csrf")]
+                let service =
+                    BoxedService::new(crate::csrf::CsrfService(service));
+                service
+            },
+        )
+    }
+
+    inventory::submit! {
+        ServerFnTraitObj::new(
+            BATCH_PATH,
+            Method::POST,
+            "application/json",
+            "application/json",
+            |req| Box::pin(handle_batch(req)),
+            Vec::new,
+        )
+    }
+
+    // runs each call in a batch as a request with the headers of the batch
+    fn handle_batch(
+        req: ActixRequest,
+    ) -> impl Future<Output = ActixResponse> + Send {
+        let headers = req.0 .0.headers().clone();
+        SendWrapper::new(batch::handle_batch(req, move |call| {
+            let headers = headers.clone();
+            SendWrapper::new(async move {
+                let (req, mut payload) = batched_request(&headers, call);
+                let payload =
+                    match Payload::from_request(&req, &mut payload).await {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            return BatchResponse::error(
+                                &ServerFnError::Args(e.to_string()),
+                                400,
+                            )
+                        }
+                    };
+                let res = handle_server_fn(req, payload).await;
+                let status = res.status().as_u16();
+                let location = res
+                    .headers()
+                    .get(header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .map(ToString::to_string);
+                let redirect = location.is_some()
+                    || res.headers().contains_key(REDIRECT_HEADER);
+                match body::to_bytes(res.into_body()).await {
+                    Ok(body) => BatchResponse {
+                        status,
+                        location,
+                        redirect,
+                        body: body.into(),
+                    },
+                    Err(e) => BatchResponse::error(
+                        &ServerFnError::Response(e.to_string()),
+                        500,
+                    ),
+                }
+            })
+        }))
+    }
+
+    fn batched_request(
+        headers: &HeaderMap,
+        call: BatchRequest,
+    ) -> (HttpRequest, dev::Payload) {
+        let mut req = TestRequest::default()
+            .method(
+                actix_web::http::Method::from_bytes(call.method.as_bytes())
+                    .unwrap_or_default(),
+            )
+            .uri(&call.uri());
+        for (name, value) in headers {
+            if name != header::CONTENT_LENGTH {
+                req = req.append_header((name.clone(), value.clone()));
             }
-            service
-        })
+        }
+        req.insert_header((header::CONTENT_TYPE, call.content_type))
+            .insert_header((header::ACCEPT, call.accepts))
+            .set_payload(call.body.into_bytes())
+            .to_http_parts()
     }
 }
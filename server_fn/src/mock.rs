@@ -0,0 +1,131 @@
+use crate::{error::ServerFnError, ServerFn};
+use futures::future::BoxFuture;
+use std::sync::Arc;
+#[cfg(feature = "mock")]
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    marker::PhantomData,
+};
+
+/// A mock implementation of the server function `S`.
+pub type Mock<S> = Arc<
+    dyn Fn(
+            S,
+        ) -> BoxFuture<
+            'static,
+            Result<
+                <S as ServerFn>::Output,
+                ServerFnError<<S as ServerFn>::Error>,
+            >,
+        > + Send
+        + Sync,
+>;
+
+#[cfg(feature = "mock")]
+thread_local! {
+    static MOCKS: RefCell<HashMap<TypeId, Box<dyn Any>>> = Default::default();
+}
+
+/// Returns the mock implementation of the server function `S` that was
+/// registered on this thread with [`mock`], if any.
+///
+/// This is called by the functions generated by the `#[server]` macro before
+/// running the server function, and always returns `None` without the `mock`
+/// feature.
+#[doc(hidden)]
+#[inline(always)]
+pub fn get<S>() -> Option<Mock<S>>
+where
+    S: ServerFn + 'static,
+{
+    #[cfg(feature = "mock")]
+    {
+        MOCKS.with(|mocks| {
+            mocks
+                .borrow()
+                .get(&TypeId::of::<S>())
+                .and_then(|mock| mock.downcast_ref::<Mock<S>>())
+                .cloned()
+        })
+    }
+
+    #[cfg(not(feature = "mock"))]
+    {
+        None
+    }
+}
+
+/// Replaces the server function `S` with `f` on this thread, until the
+/// returned [`MockGuard`] is dropped.
+///
+/// Calls to the server function, on the client or the server, run `f` with
+/// its arguments instead, without serializing them or sending a request.
+/// Because mocks are registered per thread, each test (which `cargo test`
+/// runs on its own thread) has its own mocks.
+///
+/// ```rust,ignore
+/// #[server]
+/// pub async fn get_count() -> Result<usize, ServerFnError> {
+///     // reads the count from a database
+/// }
+///
+/// let _mock = server_fn::mock::mock::<GetCount, _, _>(|_| async { Ok(3) });
+///
+/// assert_eq!(get_count().await.unwrap(), 3);
+/// ```
+#[cfg(feature = "mock")]
+pub fn mock<S, F, Fut>(f: F) -> MockGuard<S>
+where
+    S: ServerFn + 'static,
+    F: Fn(S) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<S::Output, ServerFnError<S::Error>>>
+        + Send
+        + 'static,
+{
+    use futures::FutureExt;
+
+    let mock: Mock<S> = Arc::new(move |args| f(args).boxed());
+    let previous = MOCKS.with(|mocks| {
+        mocks.borrow_mut().insert(TypeId::of::<S>(), Box::new(mock))
+    });
+    MockGuard {
+        previous,
+        ty: PhantomData,
+    }
+}
+
+/// Restores the previous implementation of a server function replaced with
+/// [`mock`] when it is dropped.
+#[cfg(feature = "mock")]
+#[must_use = "the mock is removed when the guard is dropped"]
+pub struct MockGuard<S: 'static> {
+    previous: Option<Box<dyn Any>>,
+    ty: PhantomData<fn() -> S>,
+}
+
+#[cfg(feature = "mock")]
+impl<S: 'static> Drop for MockGuard<S> {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        // the thread may be shutting down when a guard is dropped
+        _ = MOCKS.try_with(|mocks| {
+            let mut mocks = mocks.borrow_mut();
+            match previous {
+                Some(previous) => mocks.insert(TypeId::of::<S>(), previous),
+                None => mocks.remove(&TypeId::of::<S>()),
+            }
+        });
+    }
+}
+
+#[cfg(feature = "mock")]
+impl<S: 'static> std::fmt::Debug for MockGuard<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockGuard")
+            .field("server_fn", &std::any::type_name::<S>())
+            .finish()
+    }
+}
@@ -0,0 +1,209 @@
+//! With the `openapi` feature, every server function registers a description
+//! of its endpoint, with [JSON schemas](schemars) of its arguments and
+//! output, from which an [OpenAPI 3.0](https://spec.openapis.org/oas/v3.0.3)
+//! document describing all of them is generated with [`spec`].
+//!
+//! The types of their arguments and outputs must implement
+//! [`JsonSchema`](schemars::JsonSchema), which can be derived for types that
+//! implement `Serialize` and `Deserialize`, and takes their `serde`
+//! attributes into account. The document can be served from an endpoint, or
+//! written to a file as part of the build, to be used by other clients or to
+//! test the API against:
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize, JsonSchema)]
+//! pub struct User {
+//!     name: String,
+//! }
+//!
+//! /// Gets the user with the given ID.
+//! #[server(method = "GET", endpoint = "users/{id}")]
+//! pub async fn get_user(id: u32) -> Result<User, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! let app = Router::new().route(
+//!     "/openapi.json",
+//!     get(|| async { Json(server_fn::openapi::spec("My API", "1.0.0")) }),
+//! );
+//! ```
+//!
+//! Each server function is described as an operation at its path and method,
+//! named after its struct and documented with its doc comment. Arguments of
+//! `GET` and `DELETE` requests are query parameters, path parameters are
+//! taken from its endpoint, and the arguments of other requests are its
+//! request body. Server functions that take multipart or streaming arguments
+//! are not described.
+
+use http::Method;
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::Schema,
+};
+use serde_json::{json, Map, Value};
+
+/// The description of a server function's endpoint, which is registered by
+/// the `#[server]` macro.
+pub struct ServerFnSchema {
+    path: &'static str,
+    method: Method,
+    input_encoding: &'static str,
+    output_encoding: &'static str,
+    name: &'static str,
+    docs: &'static str,
+    args: fn(&mut SchemaGenerator) -> Schema,
+    output: Option<fn(&mut SchemaGenerator) -> Schema>,
+}
+
+impl ServerFnSchema {
+    /// Describes a server function, from its endpoint, the name of its
+    /// struct, its docs, and the schemas of its arguments and output (if the
+    /// output is not a stream).
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        path: &'static str,
+        method: Method,
+        input_encoding: &'static str,
+        output_encoding: &'static str,
+        name: &'static str,
+        docs: &'static str,
+        args: fn(&mut SchemaGenerator) -> Schema,
+        output: Option<fn(&mut SchemaGenerator) -> Schema>,
+    ) -> Self {
+        Self {
+            path,
+            method,
+            input_encoding,
+            output_encoding,
+            name,
+            docs,
+            args,
+            output,
+        }
+    }
+
+    /// The path of the server function.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The HTTP method the server function expects.
+    pub fn method(&self) -> Method {
+        self.method.clone()
+    }
+
+    // the OpenAPI operation object for the server function
+    fn operation(&self, gen: &mut SchemaGenerator) -> Value {
+        let mut args = (self.args)(gen).into_object();
+        // the struct's docs link to the function, which is described instead
+        args.metadata = None;
+        let object = args.object();
+        let mut parameters = Vec::new();
+        for name in self.path.split('/').filter_map(|segment| {
+            segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'))
+        }) {
+            object.required.remove(name);
+            let schema =
+                object.properties.remove(name).unwrap_or(Schema::Bool(true));
+            parameters.push(json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": schema,
+            }));
+        }
+
+        let mut operation = Map::new();
+        operation.insert("operationId".into(), self.name.into());
+        let docs = self.docs.trim();
+        if !docs.is_empty() {
+            let summary = docs.lines().next().unwrap_or_default().trim();
+            operation.insert("summary".into(), summary.into());
+            operation.insert("description".into(), docs.into());
+        }
+        if self.method == Method::GET || self.method == Method::DELETE {
+            for (name, schema) in &object.properties {
+                parameters.push(json!({
+                    "name": name,
+                    "in": "query",
+                    "required": object.required.contains(name),
+                    "schema": schema,
+                }));
+            }
+        } else {
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": {
+                        self.input_encoding: { "schema": args },
+                    },
+                }),
+            );
+        }
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), parameters.into());
+        }
+
+        let output = match self.output {
+            Some(output) => json!(output(gen)),
+            None => json!({ "type": "string", "format": "binary" }),
+        };
+        operation.insert(
+            "responses".into(),
+            json!({
+                "200": {
+                    "description": "The result of the server function.",
+                    "content": {
+                        self.output_encoding: { "schema": output },
+                    },
+                },
+                "default": {
+                    "description": "An error, serialized as `Variant|message`.",
+                    "content": {
+                        "text/plain": { "schema": { "type": "string" } },
+                    },
+                },
+            }),
+        );
+        Value::Object(operation)
+    }
+}
+
+inventory::collect!(ServerFnSchema);
+
+/// Generates an OpenAPI 3.0 document describing every registered server
+/// function, with the given title and version of the API.
+pub fn spec(title: &str, version: &str) -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let mut server_fns = inventory::iter::<ServerFnSchema>
+        .into_iter()
+        .collect::<Vec<_>>();
+    server_fns.sort_by_key(|server_fn| (server_fn.path, server_fn.name));
+
+    let mut paths = Map::new();
+    for server_fn in server_fns {
+        let operation = server_fn.operation(&mut gen);
+        if let Value::Object(path) = paths
+            .entry(server_fn.path)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            path.insert(
+                server_fn.method.as_str().to_ascii_lowercase(),
+                operation,
+            );
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": title,
+            "version": version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+        },
+    })
+}
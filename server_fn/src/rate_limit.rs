@@ -0,0 +1,356 @@
+//! Server functions can be rate limited with the [`RateLimit`] middleware,
+//! which counts the calls made by each client, identified by a key that is
+//! extracted from the request (like its IP address, session, or user ID), and
+//! rejects the calls that exceed the limit:
+//!
+//! ```rust,ignore
+//! fn client_ip(req: &Request<Body>) -> Option<String> {
+//!     req.extensions()
+//!         .get::<ConnectInfo<SocketAddr>>()
+//!         .map(|ConnectInfo(addr)| addr.ip().to_string())
+//! }
+//!
+//! // at most 5 calls per minute from each IP address
+//! #[server]
+//! #[middleware(RateLimit::new(5, Duration::from_secs(60), client_ip))]
+//! pub async fn log_in(user: String, password: String) -> Result<(), ServerFnError> {
+//!     // ...
+//! }
+//!
+//! // for every server function whose path starts with `/api/search`
+//! server_fn::axum::register_middleware(
+//!     "/api/search",
+//!     RateLimit::new(100, Duration::from_secs(60), client_ip),
+//! );
+//! ```
+//!
+//! Calls are counted separately for each path, so each server function has
+//! its own limit. Calls for which the key cannot be extracted are not limited.
+//!
+//! A call that exceeds the limit is rejected with `429 Too Many Requests` and
+//! a `Retry-After` header, and returns
+//! [`ServerFnError::RateLimited`](crate::ServerFnError::RateLimited) with the
+//! number of seconds after which it can be made again, which can be read with
+//! [`ServerFnError::retry_after`](crate::ServerFnError::retry_after).
+//!
+//! The calls are counted in memory by default, so each server process has
+//! its own counts. [`set_rate_limit_store`] replaces this with another
+//! [`RateLimitStore`], like one that is shared by several servers.
+
+use crate::{
+    error::{NoCustomError, ServerFnError},
+    middleware::{BoxedService, Layer, Service},
+    request::Req,
+    response::Res,
+};
+use dashmap::DashMap;
+use futures::lock::Mutex;
+use once_cell::sync::Lazy;
+use std::{
+    future::{self, Future},
+    pin::Pin,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// The HTTP status code of the response when a server function call exceeds
+/// a rate limit.
+pub const RATE_LIMIT_STATUS: u16 = 429;
+
+static RATE_LIMIT_STORE: Lazy<RwLock<Arc<dyn RateLimitStore>>> =
+    Lazy::new(|| RwLock::new(Arc::new(MemoryStore::default())));
+
+/// Sets the [`RateLimitStore`] in which every [`RateLimit`] counts calls.
+///
+/// It should be set before the server starts handling requests.
+pub fn set_rate_limit_store(store: impl RateLimitStore) {
+    *RATE_LIMIT_STORE.write().unwrap_or_else(|e| e.into_inner()) =
+        Arc::new(store);
+}
+
+fn rate_limit_store() -> Arc<dyn RateLimitStore> {
+    Arc::clone(&RATE_LIMIT_STORE.read().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Counts the calls made to rate-limited server functions.
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Counts a call for the given key, which may be made at most `limit`
+    /// times per `window`.
+    ///
+    /// Returns how long to wait before making the call again, if it exceeds
+    /// the limit.
+    fn hit(
+        &self,
+        key: String,
+        limit: u32,
+        window: Duration,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Duration>> + Send>>;
+}
+
+/// A [`RateLimitStore`] that counts calls in memory, in fixed windows that
+/// start with the first call for each key.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    windows: DashMap<String, Window>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    start: Instant,
+    length: Duration,
+    count: u32,
+}
+
+// the number of keys above which expired windows are removed
+const MAX_WINDOWS: usize = 10_000;
+
+impl RateLimitStore for MemoryStore {
+    fn hit(
+        &self,
+        key: String,
+        limit: u32,
+        window: Duration,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Duration>> + Send>> {
+        let now = Instant::now();
+        if self.windows.len() > MAX_WINDOWS {
+            self.windows
+                .retain(|_, window| now - window.start < window.length);
+        }
+        let mut entry = self.windows.entry(key).or_insert(Window {
+            start: now,
+            length: window,
+            count: 0,
+        });
+        let elapsed = now - entry.start;
+        if elapsed >= window {
+            *entry = Window {
+                start: now,
+                length: window,
+                count: 0,
+            };
+        }
+        let res = if entry.count < limit {
+            entry.count += 1;
+            Ok(())
+        } else {
+            Err(entry.length.saturating_sub(elapsed))
+        };
+        Box::pin(future::ready(res))
+    }
+}
+
+/// Middleware that limits how often each client can call a server function.
+/// See the [module docs](self).
+pub struct RateLimit<Req> {
+    limit: u32,
+    window: Duration,
+    key: KeyFn<Req>,
+}
+
+type KeyFn<Req> = Arc<dyn Fn(&Req) -> Option<String> + Send + Sync>;
+
+impl<Req> RateLimit<Req> {
+    /// Allows at most `limit` calls per `window` for each key that `key`
+    /// extracts from the requests.
+    pub fn new(
+        limit: u32,
+        window: Duration,
+        key: impl Fn(&Req) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            limit,
+            window,
+            key: Arc::new(key),
+        }
+    }
+}
+
+impl<Req> Clone for RateLimit<Req> {
+    fn clone(&self) -> Self {
+        Self {
+            limit: self.limit,
+            window: self.window,
+            key: Arc::clone(&self.key),
+        }
+    }
+}
+
+impl<Rq, Rs> Layer<Rq, Rs> for RateLimit<Rq>
+where
+    Rq: Req<NoCustomError> + Send + 'static,
+    Rs: Res<NoCustomError> + Send + 'static,
+{
+    fn layer(&self, inner: BoxedService<Rq, Rs>) -> BoxedService<Rq, Rs> {
+        BoxedService::new(RateLimitService {
+            limit: self.clone(),
+            inner: Arc::new(Mutex::new(inner)),
+        })
+    }
+}
+
+struct RateLimitService<Rq, Rs> {
+    limit: RateLimit<Rq>,
+    inner: Arc<Mutex<BoxedService<Rq, Rs>>>,
+}
+
+impl<Rq, Rs> Service<Rq, Rs> for RateLimitService<Rq, Rs>
+where
+    Rq: Req<NoCustomError> + Send + 'static,
+    Rs: Res<NoCustomError> + Send + 'static,
+{
+    fn run(&mut self, req: Rq) -> Pin<Box<dyn Future<Output = Rs> + Send>> {
+        let inner = Arc::clone(&self.inner);
+        let Some(key) = (self.limit.key)(&req) else {
+            return Box::pin(async move {
+                let res = inner.lock().await.0.run(req);
+                res.await
+            });
+        };
+        let path = req.as_path().to_string();
+        let RateLimit { limit, window, .. } = self.limit;
+        let hit = rate_limit_store().hit(
+            store_key(&path, limit, window, &key),
+            limit,
+            window,
+        );
+        Box::pin(async move {
+            if let Err(retry_after) = hit.await {
+                // rounded up, so that the call is not retried too early
+                let secs = retry_after.as_secs()
+                    + u64::from(retry_after.subsec_nanos() > 0);
+                let err = ServerFnError::RateLimited(secs.max(1));
+                return Rs::error_response_with_status(
+                    &path,
+                    &err,
+                    err.status_code(),
+                );
+            }
+            let res = inner.lock().await.0.run(req);
+            res.await
+        })
+    }
+}
+
+// limits with different windows on the same path are counted apart
+fn store_key(path: &str, limit: u32, window: Duration, key: &str) -> String {
+    format!("{path}|{limit}/{}|{key}", window.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn memory_store_resets_the_window() {
+        let store = MemoryStore::default();
+        let window = Duration::from_millis(50);
+        let hit = || block_on(store.hit("client".into(), 2, window));
+
+        assert_eq!(hit(), Ok(()));
+        assert_eq!(hit(), Ok(()));
+        let retry_after = hit().unwrap_err();
+        assert!(retry_after > Duration::ZERO && retry_after <= window);
+
+        std::thread::sleep(window);
+        assert_eq!(hit(), Ok(()));
+    }
+
+    #[test]
+    fn memory_store_prunes_expired_windows() {
+        let store = MemoryStore::default();
+        let window = Duration::from_millis(1);
+        for i in 0..=MAX_WINDOWS {
+            block_on(store.hit(i.to_string(), 1, window)).unwrap();
+        }
+        assert_eq!(store.windows.len(), MAX_WINDOWS + 1);
+
+        std::thread::sleep(Duration::from_millis(5));
+        block_on(store.hit("client".into(), 1, Duration::from_secs(60)))
+            .unwrap();
+        assert_eq!(store.windows.len(), 1);
+    }
+
+    #[test]
+    fn calls_are_counted_per_path_limit_and_window() {
+        let minute = Duration::from_secs(60);
+        let key = store_key("/api/log_in", 5, minute, "127.0.0.1");
+
+        assert_eq!(key, "/api/log_in|5/60000|127.0.0.1");
+        assert_ne!(key, store_key("/api/search", 5, minute, "127.0.0.1"));
+        assert_ne!(key, store_key("/api/log_in", 10, minute, "127.0.0.1"));
+        assert_ne!(
+            key,
+            store_key("/api/log_in", 5, Duration::from_secs(1), "127.0.0.1")
+        );
+        assert_ne!(key, store_key("/api/log_in", 5, minute, "127.0.0.2"));
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    mod axum_service {
+        use super::*;
+        use crate::error::ServerFnErrorSerde;
+        use ::axum::body::Body;
+        use http::{header, Request, Response, StatusCode};
+
+        struct Ok200;
+
+        impl Service<Request<Body>, Response<Body>> for Ok200 {
+            fn run(
+                &mut self,
+                _req: Request<Body>,
+            ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>>
+            {
+                Box::pin(future::ready(Response::new(Body::empty())))
+            }
+        }
+
+        fn service(path: &str) -> impl FnMut(Option<&str>) -> Response<Body> {
+            let limit = RateLimit::new(
+                1,
+                Duration::from_secs(60),
+                |req: &Request<Body>| {
+                    req.headers()
+                        .get("x-user")
+                        .and_then(|user| user.to_str().ok())
+                        .map(str::to_string)
+                },
+            );
+            let mut service = limit.layer(BoxedService::new(Ok200));
+            let path = path.to_string();
+            move |user| {
+                let mut req = Request::builder().uri(&path);
+                if let Some(user) = user {
+                    req = req.header("x-user", user);
+                }
+                block_on(service.0.run(req.body(Body::empty()).unwrap()))
+            }
+        }
+
+        #[test]
+        fn calls_over_the_limit_are_rejected_with_retry_after() {
+            let mut call = service("/api/rate_limit/rejected");
+            assert_eq!(call(Some("ann")).status(), StatusCode::OK);
+            assert_eq!(call(Some("bob")).status(), StatusCode::OK);
+
+            let res = call(Some("ann"));
+            assert_eq!(res.status().as_u16(), RATE_LIMIT_STATUS);
+            assert_eq!(res.headers()[header::RETRY_AFTER], "60");
+            let body = block_on(::axum::body::to_bytes(res.into_body(), 1024))
+                .unwrap();
+            let err = ServerFnError::<NoCustomError>::de(
+                std::str::from_utf8(&body).unwrap(),
+            );
+            assert_eq!(err, ServerFnError::RateLimited(60));
+            assert_eq!(err.retry_after(), Some(Duration::from_secs(60)));
+        }
+
+        #[test]
+        fn calls_without_a_key_are_not_limited() {
+            let mut call = service("/api/rate_limit/no_key");
+            for _ in 0..3 {
+                assert_eq!(call(None).status(), StatusCode::OK);
+            }
+        }
+    }
+}
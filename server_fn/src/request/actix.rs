@@ -1,7 +1,7 @@
 use crate::{error::ServerFnError, request::Req};
 use actix_web::{web::Payload, HttpRequest};
 use bytes::Bytes;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use send_wrapper::SendWrapper;
 use std::{borrow::Cow, future::Future};
 
@@ -18,6 +18,11 @@ impl ActixRequest {
         self.0.take()
     }
 
+    /// Returns the raw Actix request, without its body.
+    pub fn request(&self) -> &HttpRequest {
+        &self.0 .0
+    }
+
     fn header(&self, name: &str) -> Option<Cow<'_, str>> {
         self.0
              .0
@@ -37,6 +42,10 @@ impl<CustErr> Req<CustErr> for ActixRequest
 where
     CustErr: 'static,
 {
+    fn as_path(&self) -> &str {
+        self.0 .0.uri().path()
+    }
+
     fn as_query(&self) -> Option<&str> {
         self.0 .0.uri().query()
     }
@@ -53,6 +62,10 @@ where
         self.header("Referer")
     }
 
+    fn trace_parent(&self) -> Option<Cow<'_, str>> {
+        self.header("traceparent")
+    }
+
     fn try_into_bytes(
         self,
     ) -> impl Future<Output = Result<Bytes, ServerFnError<CustErr>>> + Send
@@ -88,9 +101,13 @@ where
     fn try_into_stream(
         self,
     ) -> Result<
-        impl Stream<Item = Result<Bytes, ServerFnError>> + Send,
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
         ServerFnError<CustErr>,
     > {
-        Ok(futures::stream::once(async { todo!() }))
+        // as above, the payload is only ever polled on the request's thread
+        let payload = self.0.take().1;
+        Ok(SendWrapper::new(payload.map(|chunk| {
+            chunk.map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        })))
     }
 }
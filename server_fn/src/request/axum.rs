@@ -12,6 +12,10 @@ impl<CustErr> Req<CustErr> for Request<Body>
 where
     CustErr: 'static,
 {
+    fn as_path(&self) -> &str {
+        self.uri().path()
+    }
+
     fn as_query(&self) -> Option<&str> {
         self.uri().query()
     }
@@ -34,6 +38,12 @@ where
             .map(|h| String::from_utf8_lossy(h.as_bytes()))
     }
 
+    fn trace_parent(&self) -> Option<Cow<'_, str>> {
+        self.headers()
+            .get("traceparent")
+            .map(|h| String::from_utf8_lossy(h.as_bytes()))
+    }
+
     async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
         let (_parts, body) = self.into_parts();
 
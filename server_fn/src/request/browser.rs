@@ -3,20 +3,34 @@ use crate::{client::get_server_url, error::ServerFnError};
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 pub use gloo_net::http::Request;
+use gloo_net::http::Response;
 use js_sys::{Reflect, Uint8Array};
 use send_wrapper::SendWrapper;
-use std::ops::{Deref, DerefMut};
-use wasm_bindgen::JsValue;
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use wasm_streams::ReadableStream;
-use web_sys::{FormData, Headers, RequestInit, UrlSearchParams};
+use web_sys::{
+    FormData, Headers, ProgressEvent, RequestInit, ResponseInit,
+    UrlSearchParams, XmlHttpRequest, XmlHttpRequestResponseType,
+};
 
 /// A `fetch` request made in the browser.
 #[derive(Debug)]
-pub struct BrowserRequest(pub(crate) SendWrapper<Request>);
+pub struct BrowserRequest(
+    pub(crate) SendWrapper<Request>,
+    // multipart uploads that report their progress are sent with
+    // `XMLHttpRequest` instead, as `fetch` cannot report it
+    pub(crate) Option<SendWrapper<Upload>>,
+);
 
 impl From<Request> for BrowserRequest {
     fn from(value: Request) -> Self {
-        Self(SendWrapper::new(value))
+        Self(SendWrapper::new(value), None)
     }
 }
 
@@ -48,11 +62,138 @@ impl DerefMut for BrowserRequest {
 
 /// The `FormData` type available in the browser.
 #[derive(Debug)]
-pub struct BrowserFormData(pub(crate) SendWrapper<FormData>);
+pub struct BrowserFormData(
+    pub(crate) SendWrapper<FormData>,
+    pub(crate) Option<SendWrapper<OnProgress>>,
+);
 
 impl From<FormData> for BrowserFormData {
     fn from(value: FormData) -> Self {
-        Self(SendWrapper::new(value))
+        Self(SendWrapper::new(value), None)
+    }
+}
+
+impl BrowserFormData {
+    /// Calls `on_progress` as the form data is uploaded, when it is sent as
+    /// multipart form data.
+    pub fn on_progress(
+        mut self,
+        on_progress: impl Fn(UploadProgress) + 'static,
+    ) -> Self {
+        self.1 = Some(SendWrapper::new(OnProgress(Rc::new(on_progress))));
+        self
+    }
+}
+
+/// The progress of an upload, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// The number of bytes sent so far.
+    pub sent: u64,
+    /// The total number of bytes to send, or `0` if it is not known.
+    pub total: u64,
+}
+
+impl UploadProgress {
+    /// The fraction of the upload that has been sent, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.sent as f64 / self.total as f64
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct OnProgress(Rc<dyn Fn(UploadProgress)>);
+
+impl Debug for OnProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OnProgress").finish()
+    }
+}
+
+/// A multipart upload that reports its progress.
+#[derive(Debug)]
+pub(crate) struct Upload {
+    form: FormData,
+    on_progress: OnProgress,
+}
+
+impl Upload {
+    /// Sends the upload with `XMLHttpRequest`, to the URL and with the
+    /// headers of `req`.
+    pub(crate) async fn send(self, req: Request) -> Result<Response, JsValue> {
+        let xhr = XmlHttpRequest::new()?;
+        // aborts the upload if the call is dropped before it completes; this
+        // does nothing once the response has been received
+        let _abort = AbortOnDrop(xhr.clone());
+        xhr.open_with_async("POST", &req.url(), true)?;
+        for (name, value) in req.headers().entries() {
+            xhr.set_request_header(&name, &value)?;
+        }
+        xhr.set_response_type(XmlHttpRequestResponseType::Arraybuffer);
+
+        let on_progress = self.on_progress.0;
+        let progress =
+            Closure::<dyn Fn(ProgressEvent)>::new(move |ev: ProgressEvent| {
+                on_progress(UploadProgress {
+                    sent: ev.loaded() as u64,
+                    total: if ev.length_computable() {
+                        ev.total() as u64
+                    } else {
+                        0
+                    },
+                })
+            });
+        xhr.upload()?
+            .set_onprogress(Some(progress.as_ref().unchecked_ref()));
+
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let tx = Rc::new(Cell::new(Some(tx)));
+        let finished = |loaded: bool| {
+            let tx = Rc::clone(&tx);
+            Closure::<dyn Fn()>::new(move || {
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(loaded);
+                }
+            })
+        };
+        let (on_load, on_error) = (finished(true), finished(false));
+        xhr.set_onload(Some(on_load.as_ref().unchecked_ref()));
+        xhr.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        xhr.set_onabort(Some(on_error.as_ref().unchecked_ref()));
+        xhr.send_with_opt_form_data(Some(&self.form))?;
+        if !rx.await.unwrap_or(false) {
+            return Err(JsValue::from_str("the upload failed"));
+        }
+
+        let headers = Headers::new()?;
+        for line in xhr.get_all_response_headers()?.split("\r\n") {
+            if let Some((name, value)) = line.split_once(": ") {
+                headers.append(name, value)?;
+            }
+        }
+        let init = ResponseInit::new();
+        init.set_status(xhr.status()?);
+        init.set_headers(&headers);
+        let body = Uint8Array::new(&xhr.response()?);
+        let res = web_sys::Response::new_with_opt_buffer_source_and_init(
+            Some(&body),
+            &init,
+        )?;
+        Ok(Response::from(res))
+    }
+}
+
+struct AbortOnDrop(XmlHttpRequest);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if self.0.ready_state() != XmlHttpRequest::DONE {
+            _ = self.0.abort();
+        }
     }
 }
 
@@ -73,13 +214,13 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         url.push_str(path);
         url.push('?');
         url.push_str(query);
-        Ok(Self(SendWrapper::new(
+        Ok(Self::from(
             Request::get(&url)
                 .header("Content-Type", content_type)
                 .header("Accept", accepts)
                 .build()
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        )))
+        ))
     }
 
     fn try_new_post(
@@ -92,13 +233,74 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
         url.push_str(path);
-        Ok(Self(SendWrapper::new(
+        Ok(Self::from(
             Request::post(&url)
                 .header("Content-Type", content_type)
                 .header("Accept", accepts)
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        )))
+        ))
+    }
+
+    fn try_new_delete(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let server_url = get_server_url();
+        let mut url = String::with_capacity(
+            server_url.len() + path.len() + 1 + query.len(),
+        );
+        url.push_str(server_url);
+        url.push_str(path);
+        url.push('?');
+        url.push_str(query);
+        Ok(Self::from(
+            Request::delete(&url)
+                .header("Content-Type", content_type)
+                .header("Accept", accepts)
+                .build()
+                .map_err(|e| ServerFnError::Request(e.to_string()))?,
+        ))
+    }
+
+    fn try_new_put(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let server_url = get_server_url();
+        let mut url = String::with_capacity(server_url.len() + path.len());
+        url.push_str(server_url);
+        url.push_str(path);
+        Ok(Self::from(
+            Request::put(&url)
+                .header("Content-Type", content_type)
+                .header("Accept", accepts)
+                .body(body)
+                .map_err(|e| ServerFnError::Request(e.to_string()))?,
+        ))
+    }
+
+    fn try_new_patch(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let server_url = get_server_url();
+        let mut url = String::with_capacity(server_url.len() + path.len());
+        url.push_str(server_url);
+        url.push_str(path);
+        Ok(Self::from(
+            Request::patch(&url)
+                .header("Content-Type", content_type)
+                .header("Accept", accepts)
+                .body(body)
+                .map_err(|e| ServerFnError::Request(e.to_string()))?,
+        ))
     }
 
     fn try_new_post_bytes(
@@ -113,13 +315,13 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         url.push_str(path);
         let body: &[u8] = &body;
         let body = Uint8Array::from(body).buffer();
-        Ok(Self(SendWrapper::new(
+        Ok(Self::from(
             Request::post(&url)
                 .header("Content-Type", content_type)
                 .header("Accept", accepts)
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        )))
+        ))
     }
 
     fn try_new_multipart(
@@ -131,12 +333,22 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
         url.push_str(path);
-        Ok(Self(SendWrapper::new(
-            Request::post(&url)
-                .header("Accept", accepts)
-                .body(body.0.take())
-                .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        )))
+        let form = body.0.take();
+        let upload = body.1.map(|on_progress| {
+            SendWrapper::new(Upload {
+                form: form.clone(),
+                on_progress: on_progress.take(),
+            })
+        });
+        Ok(Self(
+            SendWrapper::new(
+                Request::post(&url)
+                    .header("Accept", accepts)
+                    .body(form)
+                    .map_err(|e| ServerFnError::Request(e.to_string()))?,
+            ),
+            upload,
+        ))
     }
 
     fn try_new_post_form_data(
@@ -156,24 +368,24 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                         },
                     ))
                 })?;
-        Ok(Self(SendWrapper::new(
+        Ok(Self::from(
             Request::post(path)
                 .header("Content-Type", content_type)
                 .header("Accept", accepts)
                 .body(url_params)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
-        )))
+        ))
     }
 
     fn try_new_streaming(
         path: &str,
         accepts: &str,
         content_type: &str,
-        body: impl Stream<Item = Bytes> + 'static,
+        body: impl Stream<Item = Result<Bytes, ServerFnError>> + 'static,
     ) -> Result<Self, ServerFnError<CustErr>> {
         let req = streaming_request(path, accepts, content_type, body)
             .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
-        Ok(Self(SendWrapper::new(req)))
+        Ok(Self::from(req))
     }
 }
 
@@ -181,12 +393,13 @@ fn streaming_request(
     path: &str,
     accepts: &str,
     content_type: &str,
-    body: impl Stream<Item = Bytes> + 'static,
+    body: impl Stream<Item = Result<Bytes, ServerFnError>> + 'static,
 ) -> Result<Request, JsValue> {
+    // an error errors the `ReadableStream`, which aborts the request
     let stream = ReadableStream::from_stream(body.map(|bytes| {
-        let data = Uint8Array::from(bytes.as_ref());
-        let data = JsValue::from(data);
-        Ok(data) as Result<JsValue, JsValue>
+        bytes
+            .map(|bytes| JsValue::from(Uint8Array::from(bytes.as_ref())))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }))
     .into_raw();
     let headers = Headers::new()?;
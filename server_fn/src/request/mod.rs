@@ -40,6 +40,30 @@ where
         body: String,
     ) -> Result<Self, ServerFnError<CustErr>>;
 
+    /// Attempts to construct a new `DELETE` request.
+    fn try_new_delete(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>>;
+
+    /// Attempts to construct a new `PUT` request with a text body.
+    fn try_new_put(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>>;
+
+    /// Attempts to construct a new `PATCH` request with a text body.
+    fn try_new_patch(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>>;
+
     /// Attempts to construct a new `POST` request with a binary body.
     fn try_new_post_bytes(
         path: &str,
@@ -64,11 +88,13 @@ where
     ) -> Result<Self, ServerFnError<CustErr>>;
 
     /// Attempts to construct a new `POST` request with a streaming body.
+    ///
+    /// If the stream yields an error, the request is aborted.
     fn try_new_streaming(
         path: &str,
         accepts: &str,
         content_type: &str,
-        body: impl Stream<Item = Bytes> + Send + 'static,
+        body: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
     ) -> Result<Self, ServerFnError<CustErr>>;
 }
 
@@ -77,6 +103,9 @@ pub trait Req<CustErr>
 where
     Self: Sized,
 {
+    /// Returns the path of the request’s URL, without its query string.
+    fn as_path(&self) -> &str;
+
     /// Returns the query string of the request’s URL, starting after the `?`.
     fn as_query(&self) -> Option<&str>;
 
@@ -89,6 +118,12 @@ where
     /// Returns the `Referer` header, if any.
     fn referer(&self) -> Option<Cow<'_, str>>;
 
+    /// Returns the `traceparent` header, which carries the trace context of
+    /// the client, if any.
+    fn trace_parent(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
     /// Attempts to extract the body of the request into [`Bytes`].
     fn try_into_bytes(
         self,
@@ -116,6 +151,10 @@ impl<CustErr> Req<CustErr> for BrowserMockReq
 where
     CustErr: 'static,
 {
+    fn as_path(&self) -> &str {
+        unreachable!()
+    }
+
     fn as_query(&self) -> Option<&str> {
         unreachable!()
     }
@@ -1,10 +1,14 @@
 use super::ClientReq;
-use crate::{client::get_server_url, error::ServerFnError};
+use crate::{client::get_server_url, codec::SyncStream, error::ServerFnError};
 use bytes::Bytes;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use once_cell::sync::Lazy;
-use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Body,
+};
 pub use reqwest::{multipart::Form, Client, Method, Request, Url};
+use std::sync::Mutex;
 
 pub(crate) static CLIENT: Lazy<Client> = Lazy::new(Client::new);
 
@@ -46,6 +50,56 @@ impl<CustErr> ClientReq<CustErr> for Request {
             .map_err(|e| ServerFnError::Request(e.to_string()))
     }
 
+    fn try_new_delete(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let url = format!("{}{}", get_server_url(), path);
+        let mut url = Url::try_from(url.as_str())
+            .map_err(|e| ServerFnError::Request(e.to_string()))?;
+        url.set_query(Some(query));
+        CLIENT
+            .delete(url)
+            .header(CONTENT_TYPE, content_type)
+            .header(ACCEPT, accepts)
+            .build()
+            .map_err(|e| ServerFnError::Request(e.to_string()))
+    }
+
+    fn try_new_put(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let url = format!("{}{}", get_server_url(), path);
+        CLIENT
+            .put(url)
+            .header(CONTENT_TYPE, content_type)
+            .header(ACCEPT, accepts)
+            .body(body)
+            .build()
+            .map_err(|e| ServerFnError::Request(e.to_string()))
+    }
+
+    fn try_new_patch(
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let url = format!("{}{}", get_server_url(), path);
+        CLIENT
+            .patch(url)
+            .header(CONTENT_TYPE, content_type)
+            .header(ACCEPT, accepts)
+            .body(body)
+            .build()
+            .map_err(|e| ServerFnError::Request(e.to_string()))
+    }
+
     fn try_new_post_bytes(
         path: &str,
         accepts: &str,
@@ -91,28 +145,21 @@ impl<CustErr> ClientReq<CustErr> for Request {
     }
 
     fn try_new_streaming(
-        _path: &str,
-        _accepts: &str,
-        _content_type: &str,
-        _body: impl Stream<Item = Bytes> + 'static,
+        path: &str,
+        accepts: &str,
+        content_type: &str,
+        body: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
     ) -> Result<Self, ServerFnError<CustErr>> {
-        todo!("Streaming requests are not yet implemented for reqwest.")
-        // We run into a fundamental issue here.
-        // To be a reqwest body, the type must be Sync
-        // That means the streaming types need to be wrappers over Sync streams
-        // However, Axum BodyDataStream is !Sync, so we can't use the same wrapper type there
-
-        /*        let url = format!("{}{}", get_server_url(), path);
-            let body = Body::wrap_stream(
-                body.map(|chunk| Ok(chunk) as Result<Bytes, ServerFnErrorErr>),
-            );
-            CLIENT
-                .post(url)
-                .header(CONTENT_TYPE, content_type)
-                .header(ACCEPT, accepts)
-                .body(body)
-                .build()
-                .map_err(|e| ServerFnError::Request(e.to_string()))
-        }*/
+        let url = format!("{}{}", get_server_url(), path);
+        let body = Body::wrap_stream(SyncStream(Mutex::new(Box::pin(
+            body.map(|chunk| chunk.map_err(|e| e.to_string())),
+        ))));
+        CLIENT
+            .post(url)
+            .header(CONTENT_TYPE, content_type)
+            .header(ACCEPT, accepts)
+            .body(body)
+            .build()
+            .map_err(|e| ServerFnError::Request(e.to_string()))
     }
 }
@@ -81,10 +81,27 @@ where
     }
 
     fn error_response(path: &str, err: &ServerFnError<CustErr>) -> Self {
+        Self::error_response_with_status(path, err, 500)
+    }
+
+    fn error_response_with_status(
+        path: &str,
+        err: &ServerFnError<CustErr>,
+        status: u16,
+    ) -> Self {
+        let mut builder = HttpResponse::build(
+            StatusCode::from_u16(status)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+        builder.append_header((SERVER_FN_ERROR_HEADER, path));
+        if let Some(retry_after) = err.retry_after() {
+            builder.insert_header((
+                header::RETRY_AFTER,
+                retry_after.as_secs().to_string(),
+            ));
+        }
         ActixResponse(SendWrapper::new(
-            HttpResponse::build(StatusCode::INTERNAL_SERVER_ERROR)
-                .append_header((SERVER_FN_ERROR_HEADER, path))
-                .body(err.ser().unwrap_or_else(|_| err.to_string())),
+            builder.body(err.ser().unwrap_or_else(|_| err.to_string())),
         ))
     }
 
@@ -51,7 +51,13 @@ impl<CustErr> ClientRes<CustErr> for BrowserResponse {
         let stream = ReadableStream::from_raw(self.0.body().unwrap())
             .into_stream()
             .map(|data| {
-                let data = data.unwrap().unchecked_into::<Uint8Array>();
+                // e.g., the connection was lost or the request was aborted
+                let data = match data {
+                    Ok(data) => data.unchecked_into::<Uint8Array>(),
+                    Err(e) => {
+                        return Err(ServerFnError::Response(format!("{e:?}")))
+                    }
+                };
                 let mut buf = Vec::new();
                 let length = data.length();
                 buf.resize(length as usize, 0);
@@ -56,9 +56,25 @@ where
     }
 
     fn error_response(path: &str, err: &ServerFnError<CustErr>) -> Self {
-        Response::builder()
-            .status(http::StatusCode::INTERNAL_SERVER_ERROR)
-            .header(SERVER_FN_ERROR_HEADER, path)
+        Self::error_response_with_status(path, err, 500)
+    }
+
+    fn error_response_with_status(
+        path: &str,
+        err: &ServerFnError<CustErr>,
+        status: u16,
+    ) -> Self {
+        let mut builder = Response::builder()
+            .status(
+                StatusCode::from_u16(status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            )
+            .header(SERVER_FN_ERROR_HEADER, path);
+        if let Some(retry_after) = err.retry_after() {
+            builder =
+                builder.header(header::RETRY_AFTER, retry_after.as_secs());
+        }
+        builder
             .body(err.ser().unwrap_or_else(|_| err.to_string()).into())
             .unwrap()
     }
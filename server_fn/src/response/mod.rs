@@ -44,6 +44,19 @@ where
     /// Converts an error into a response, with a `500` status code and the error text as its body.
     fn error_response(path: &str, err: &ServerFnError<CustErr>) -> Self;
 
+    /// Converts an error into a response, with the given status code and the error text as its
+    /// body.
+    ///
+    /// By default, this ignores the status code and uses [`Res::error_response`].
+    fn error_response_with_status(
+        path: &str,
+        err: &ServerFnError<CustErr>,
+        status: u16,
+    ) -> Self {
+        _ = status;
+        Self::error_response(path, err)
+    }
+
     /// Redirect the response by setting a 302 code and Location header.
     fn redirect(&mut self, path: &str);
 }
@@ -0,0 +1,160 @@
+//! Server functions can be declared with the HTTP method that they are called
+//! with, and with path parameters that are taken from their arguments, so that
+//! their endpoints can be used as a REST API by clients other than Leptos:
+//!
+//! ```rust,ignore
+//! #[server(method = "GET", endpoint = "users/{id}")]
+//! pub async fn get_user(id: u32) -> Result<User, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! #[server(method = "PUT", endpoint = "users/{id}")]
+//! pub async fn update_user(id: u32, name: String) -> Result<User, ServerFnError> {
+//!     // ...
+//! }
+//!
+//! #[server(method = "DELETE", endpoint = "users/{id}")]
+//! pub async fn delete_user(id: u32) -> Result<(), ServerFnError> {
+//!     // ...
+//! }
+//! ```
+//!
+//! The method chooses the input encoding: `GET` uses [`GetUrl`](crate::codec::GetUrl),
+//! `POST` uses [`PostUrl`](crate::codec::PostUrl), `PUT` uses [`PutJson`](crate::codec::PutJson),
+//! `PATCH` uses [`PatchJson`](crate::codec::PatchJson), and `DELETE` uses
+//! [`DeleteUrl`](crate::codec::DeleteUrl).
+//!
+//! Each `{name}` segment of the endpoint is filled in with the argument of the
+//! same name when the server function is called, and parsed from the path of
+//! the request on the server, so it can be left out of the query string or
+//! body. Path parameters must implement `Display`, `FromStr`, and `Default`.
+//! Several server functions can share an endpoint if they use different methods.
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet};
+use std::borrow::Cow;
+
+// the characters that are not allowed in a path segment
+const SEGMENT: &AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Whether the path pattern of a server function has any `{name}` segments.
+pub fn has_params(pattern: &str) -> bool {
+    pattern
+        .split('/')
+        .any(|segment| param_name(segment).is_some())
+}
+
+/// Fills in the `{name}` segments of a path pattern with the given values,
+/// percent-encoding them.
+pub fn fill(pattern: &str, params: &[(&str, String)]) -> String {
+    pattern
+        .split('/')
+        .map(|segment| match param_name(segment) {
+            Some(name) => params
+                .iter()
+                .find(|(param, _)| *param == name)
+                .map(|(_, value)| {
+                    Cow::from(utf8_percent_encode(value, SEGMENT))
+                })
+                .unwrap_or(Cow::Borrowed(segment)),
+            None => Cow::Borrowed(segment),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Matches a request path against a path pattern, returning the decoded value
+/// of each `{name}` segment if it matches.
+pub fn params<'a>(
+    pattern: &'a str,
+    path: &'a str,
+) -> Option<Vec<(&'a str, Cow<'a, str>)>> {
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.split('/');
+    let mut params = Vec::new();
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return Some(params),
+            (Some(expected), Some(segment)) => match param_name(expected) {
+                Some(name) if !segment.is_empty() => {
+                    let value =
+                        percent_decode_str(segment).decode_utf8().ok()?;
+                    params.push((name, value));
+                }
+                Some(_) => return None,
+                None if expected == segment => {}
+                None => return None,
+            },
+            _ => return None,
+        }
+    }
+}
+
+/// Whether a request path matches a path pattern.
+pub fn matches(pattern: &str, path: &str) -> bool {
+    params(pattern, path).is_some()
+}
+
+fn param_name(segment: &str) -> Option<&str> {
+    segment.strip_prefix('{')?.strip_suffix('}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATTERN: &str = "/api/users/{id}/files/{name}";
+
+    #[test]
+    fn params_are_filled_in_and_extracted() {
+        assert!(has_params(PATTERN));
+        assert!(!has_params("/api/users"));
+
+        let path =
+            fill(PATTERN, &[("id", "42".into()), ("name", "a.txt".into())]);
+        assert_eq!(path, "/api/users/42/files/a.txt");
+        assert_eq!(
+            params(PATTERN, &path),
+            Some(vec![("id", "42".into()), ("name", "a.txt".into())])
+        );
+    }
+
+    #[test]
+    fn segments_are_percent_encoded() {
+        let path = fill(
+            PATTERN,
+            &[("id", "42".into()), ("name", "my notes/ü?.txt".into())],
+        );
+        assert_eq!(path, "/api/users/42/files/my%20notes%2F%C3%BC%3F.txt");
+        assert_eq!(
+            params(PATTERN, &path),
+            Some(vec![
+                ("id", "42".into()),
+                ("name", "my notes/ü?.txt".into())
+            ])
+        );
+        // segments that are not valid UTF-8 once decoded do not match
+        assert_eq!(params(PATTERN, "/api/users/42/files/%FF"), None);
+    }
+
+    #[test]
+    fn paths_with_missing_params_do_not_match() {
+        assert!(matches(PATTERN, "/api/users/42/files/a.txt"));
+        assert!(!matches(PATTERN, "/api/users//files/a.txt"));
+        assert!(!matches(PATTERN, "/api/users/42/files/"));
+        assert!(!matches(PATTERN, "/api/users/42/files"));
+        assert!(!matches(PATTERN, "/api/users/42/files/a.txt/b"));
+        assert!(!matches(PATTERN, "/api/groups/42/files/a.txt"));
+    }
+
+    #[test]
+    fn missing_values_leave_the_pattern() {
+        assert_eq!(
+            fill(PATTERN, &[("id", "42".into())]),
+            "/api/users/42/files/{name}"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+static RETRY_POLICY: RwLock<Option<RetryPolicy>> = RwLock::new(None);
+
+/// Sets the [`RetryPolicy`] for every idempotent server function that does not
+/// have one of its own.
+///
+/// Server functions called with a `GET` request are idempotent by default;
+/// others only when they are declared with `#[server(idempotent = true)]`, so
+/// that mutations are never retried unless they have opted in.
+pub fn set_retry_policy(policy: RetryPolicy) {
+    *RETRY_POLICY.write().unwrap_or_else(|e| e.into_inner()) = Some(policy);
+}
+
+/// Returns the [`RetryPolicy`] set with [`set_retry_policy`], if any.
+pub fn retry_policy() -> Option<RetryPolicy> {
+    RETRY_POLICY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The reason a server function call failed, which decides whether it is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryCause {
+    /// The request could not be sent, or no response was received.
+    Network,
+    /// The server responded with an error status code.
+    Status(u16),
+}
+
+/// Describes how often, and after how long, a failed server function call is
+/// made again.
+///
+/// By default, calls are retried after network errors and `5xx` responses,
+/// waiting 100ms before the first retry and twice as long before each of the
+/// following ones, up to 10s.
+///
+/// ```rust,ignore
+/// // for a single server function
+/// #[server(retry = RetryPolicy::new(3))]
+/// pub async fn load_feed() -> Result<Vec<Post>, ServerFnError> {
+///     // ...
+/// }
+///
+/// // for every idempotent server function
+/// set_retry_policy(
+///     RetryPolicy::new(5)
+///         .backoff(Duration::from_millis(250), Duration::from_secs(5))
+///         .retry_on(|cause| cause == RetryCause::Network),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_on: Arc<dyn Fn(RetryCause) -> bool + Send + Sync>,
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that makes a call at most `max_attempts` times,
+    /// including the first one.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            retry_on: Arc::new(|cause| match cause {
+                RetryCause::Network => true,
+                RetryCause::Status(status) => (500..=599).contains(&status),
+            }),
+        }
+    }
+
+    /// Sets how long to wait before the first retry, which doubles with each
+    /// following one, up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets which failures the call is retried after.
+    pub fn retry_on(
+        mut self,
+        retry_on: impl Fn(RetryCause) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_on = Arc::new(retry_on);
+        self
+    }
+
+    /// Returns how long to wait before making the call again, if attempt
+    /// number `attempt` failed because of `cause` and it should be retried.
+    pub(crate) fn retry_after(
+        &self,
+        attempt: u32,
+        cause: RetryCause,
+    ) -> Option<Duration> {
+        (attempt < self.max_attempts && (self.retry_on)(cause)).then(|| {
+            self.initial_backoff
+                .saturating_mul(2u32.saturating_pow(attempt - 1))
+                .min(self.max_backoff)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy = RetryPolicy::new(7).backoff(ms(100), ms(1000));
+        let delays = (1..=7)
+            .map(|attempt| policy.retry_after(attempt, RetryCause::Network))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            delays,
+            [
+                Some(ms(100)),
+                Some(ms(200)),
+                Some(ms(400)),
+                Some(ms(800)),
+                Some(ms(1000)),
+                Some(ms(1000)),
+                // the last attempt is not retried
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn backoff_does_not_overflow() {
+        let policy = RetryPolicy::new(u32::MAX);
+        assert_eq!(
+            policy.retry_after(100, RetryCause::Network),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            RetryPolicy::new(1).retry_after(1, RetryCause::Network),
+            None
+        );
+    }
+
+    #[test]
+    fn network_errors_and_server_errors_are_retried_by_default() {
+        let policy = RetryPolicy::new(3);
+        let retried = |cause| policy.retry_after(1, cause).is_some();
+
+        assert!(retried(RetryCause::Network));
+        assert!(retried(RetryCause::Status(500)));
+        assert!(retried(RetryCause::Status(503)));
+        assert!(!retried(RetryCause::Status(400)));
+        assert!(!retried(RetryCause::Status(404)));
+        assert!(!retried(RetryCause::Status(422)));
+        assert!(!retried(RetryCause::Status(429)));
+    }
+
+    #[test]
+    fn retried_causes_can_be_chosen() {
+        let policy = RetryPolicy::new(3)
+            .retry_on(|cause| cause == RetryCause::Status(429));
+        let retried = |cause| policy.retry_after(1, cause).is_some();
+
+        assert!(retried(RetryCause::Status(429)));
+        assert!(!retried(RetryCause::Status(500)));
+        assert!(!retried(RetryCause::Network));
+    }
+}
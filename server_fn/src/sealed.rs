@@ -0,0 +1,623 @@
+//! With the `sealed` feature, values that the server hands to the client only
+//! so that it can pass them back to a server function, like the props of an
+//! island that are embedded in the HTML, can be sealed so that the client (and
+//! anyone between it and the server) cannot tamper with them, or read them:
+//! - a [`Signed`] value can be read by the client, but carries a signature
+//!   (HMAC-SHA256) that the server checks when it receives it back;
+//! - an [`Encrypted`] value is sent to the client as an opaque token, which
+//!   is encrypted and authenticated (AES-256-GCM).
+//!
+//! Both are serialized as strings, and are checked when they are deserialized
+//! on the server, so that a server function that takes one as an argument
+//! fails with [`ServerFnError::Args`] if it has been tampered with.
+//!
+//! ```rust,ignore
+//! // on startup
+//! set_keyring(Keyring::new("2024-06", env::var("SEAL_KEY")?));
+//!
+//! #[island]
+//! fn Checkout(order: Signed<OrderId>, discount: Encrypted<Discount>) -> impl IntoView {
+//!     // `order` can be read on the client, but `discount` cannot
+//!     let pay = create_action(move |_| pay(order.clone(), discount.clone()));
+//!     // ...
+//! }
+//!
+//! #[server]
+//! pub async fn pay(
+//!     order: Signed<OrderId>,
+//!     discount: Encrypted<Discount>,
+//! ) -> Result<(), ServerFnError> {
+//!     // both were created by the server
+//!     let discount = discount.into_inner().expect("decrypted on the server");
+//!     // ...
+//! }
+//!
+//! // when rendering the page
+//! view! { <Checkout order=Signed::new(order_id)? discount=Encrypted::new(discount)?/> }
+//! ```
+//!
+//! To seal all the arguments of a server function, it can take a single
+//! sealed struct.
+//!
+//! ## Key Rotation
+//!
+//! Values are sealed with the current key of the [`Keyring`], and name the
+//! key they were sealed with, so that values sealed with a previous key can
+//! still be opened while it is being rotated out:
+//!
+//! ```rust,ignore
+//! set_keyring(
+//!     Keyring::new("2024-06", new_secret).with_previous("2024-01", old_secret),
+//! );
+//! ```
+//!
+//! The type of a value is sealed with it, so that a value sealed as one type
+//! cannot be passed back as another. As this uses the name of the type, which
+//! can change between compiler versions, values sealed by a server built with
+//! one compiler may not be opened by a server built with another.
+//!
+//! Values can only be sealed and checked on the server, which holds the keys.
+//! On the client, a [`Signed`] value is read without checking its signature,
+//! and an [`Encrypted`] value cannot be read at all.
+
+use crate::error::{NoCustomError, ServerFnError};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use sha2::Sha256;
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone)]
+struct Key {
+    id: String,
+    signing: [u8; 32],
+    encryption: [u8; 32],
+}
+
+impl Key {
+    fn new(id: String, secret: &[u8]) -> Self {
+        Self {
+            id,
+            signing: derive_key(secret, b"server_fn sealed: signing"),
+            encryption: derive_key(secret, b"server_fn sealed: encryption"),
+        }
+    }
+
+    fn mac<T>(&self, data: &str) -> HmacSha256 {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(&self.signing)
+            .expect("HMAC takes keys of any length");
+        mac.update(type_tag::<T>().as_bytes());
+        mac.update(data.as_bytes());
+        mac
+    }
+
+    fn aad<T>(&self) -> Vec<u8> {
+        [self.id.as_bytes(), type_tag::<T>().as_bytes()].concat()
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(&self.encryption.into())
+    }
+}
+
+// binds a sealed value to its type, so that it can't be opened as another; the
+// NUL byte separates it from the data, as neither a type name nor an ID
+// contains one
+fn type_tag<T>() -> String {
+    format!("\0{}\0", std::any::type_name::<T>())
+}
+
+// separate keys are used for signing and encryption
+fn derive_key(secret: &[u8], purpose: &[u8]) -> [u8; 32] {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(secret)
+        .expect("HMAC takes keys of any length");
+    mac.update(purpose);
+    mac.finalize().into_bytes().into()
+}
+
+/// The keys that values are sealed with. See the [module docs](self).
+///
+/// Each key is named by an ID, which is included in the values sealed with
+/// it, and derived from a secret, which should be long and random.
+#[derive(Clone)]
+pub struct Keyring {
+    // the current key comes first
+    keys: Vec<Key>,
+}
+
+impl Keyring {
+    /// Creates a keyring that seals values with the given key.
+    pub fn new(id: impl Into<String>, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            keys: vec![Key::new(id.into(), secret.as_ref())],
+        }
+    }
+
+    /// Adds a previous key, which values sealed with it can still be opened
+    /// with, but which new values are not sealed with.
+    pub fn with_previous(
+        mut self,
+        id: impl Into<String>,
+        secret: impl AsRef<[u8]>,
+    ) -> Self {
+        self.keys.push(Key::new(id.into(), secret.as_ref()));
+        self
+    }
+
+    fn current(&self) -> &Key {
+        &self.keys[0]
+    }
+
+    fn get(&self, id: &str) -> Result<&Key, String> {
+        self.keys
+            .iter()
+            .find(|key| key.id == id)
+            .ok_or_else(|| format!("unknown key {id:?}"))
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field(
+                "keys",
+                &self.keys.iter().map(|key| &key.id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+static KEYRING: Lazy<RwLock<Option<Arc<Keyring>>>> =
+    Lazy::new(Default::default);
+
+/// Sets the keys that values are sealed and opened with on the server.
+///
+/// This replaces any keyring that was set before.
+pub fn set_keyring(keyring: Keyring) {
+    *KEYRING.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(keyring));
+}
+
+fn keyring() -> Result<Arc<Keyring>, String> {
+    KEYRING
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .ok_or_else(|| "no keyring has been set with `set_keyring`".into())
+}
+
+fn decode(data: &str) -> Result<Vec<u8>, String> {
+    URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(|_| "malformed sealed value".into())
+}
+
+/// A value that is signed by the server, so that it can be read, but not
+/// changed, by the client. See the [module docs](self).
+///
+/// It is serialized as a string of the ID of the key it was signed with, the
+/// value serialized as JSON, and its signature, which is checked when it is
+/// deserialized on the server.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signed<T> {
+    value: T,
+    token: String,
+}
+
+impl<T: Serialize> Signed<T> {
+    /// Signs the value with the current key of the [`Keyring`].
+    pub fn new(value: T) -> Result<Self, ServerFnError> {
+        let keyring =
+            keyring().map_err(ServerFnError::<NoCustomError>::ServerError)?;
+        Self::with_keyring(&keyring, value)
+    }
+
+    fn with_keyring(
+        keyring: &Keyring,
+        value: T,
+    ) -> Result<Self, ServerFnError> {
+        let key = keyring.current();
+        let payload = serde_json::to_vec(&value).map_err(|e| {
+            ServerFnError::<NoCustomError>::Serialization(e.to_string())
+        })?;
+        let signed = format!("{}.{}", key.id, URL_SAFE_NO_PAD.encode(payload));
+        let signature = URL_SAFE_NO_PAD
+            .encode(key.mac::<T>(&signed).finalize().into_bytes());
+        Ok(Self {
+            value,
+            token: format!("{signed}.{signature}"),
+        })
+    }
+}
+
+impl<T> Signed<T> {
+    /// Consumes the wrapper, returning the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Signed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Serialize for Signed<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.token)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Signed<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        let value = open_signed(&token).map_err(D::Error::custom)?;
+        Ok(Self { value, token })
+    }
+}
+
+fn open_signed<T: DeserializeOwned>(token: &str) -> Result<T, String> {
+    // only the server holds the keys to check the signature with
+    let keyring = if cfg!(feature = "ssr") {
+        Some(keyring()?)
+    } else {
+        None
+    };
+    open_signed_with(keyring.as_deref(), token)
+}
+
+fn open_signed_with<T: DeserializeOwned>(
+    keyring: Option<&Keyring>,
+    token: &str,
+) -> Result<T, String> {
+    let malformed = || "malformed signed value".to_string();
+    let (signed, signature) = token.rsplit_once('.').ok_or_else(malformed)?;
+    let (id, payload) = signed.rsplit_once('.').ok_or_else(malformed)?;
+    if let Some(keyring) = keyring {
+        keyring
+            .get(id)?
+            .mac::<T>(signed)
+            .verify_slice(&decode(signature)?)
+            .map_err(|_| "invalid signature".to_string())?;
+    }
+    serde_json::from_slice(&decode(payload)?).map_err(|e| e.to_string())
+}
+
+/// A value that is encrypted by the server, so that it can be neither read
+/// nor changed by the client. See the [module docs](self).
+///
+/// It is serialized as a string of the ID of the key it was encrypted with,
+/// and the encrypted value, which is decrypted when it is deserialized on the
+/// server.
+#[derive(Clone)]
+pub struct Encrypted<T> {
+    value: Option<T>,
+    token: String,
+}
+
+impl<T: Serialize> Encrypted<T> {
+    /// Encrypts the value with the current key of the [`Keyring`].
+    pub fn new(value: T) -> Result<Self, ServerFnError> {
+        let keyring =
+            keyring().map_err(ServerFnError::<NoCustomError>::ServerError)?;
+        Self::with_keyring(&keyring, value)
+    }
+
+    fn with_keyring(
+        keyring: &Keyring,
+        value: T,
+    ) -> Result<Self, ServerFnError> {
+        let key = keyring.current();
+        let plaintext = serde_json::to_vec(&value).map_err(|e| {
+            ServerFnError::<NoCustomError>::Serialization(e.to_string())
+        })?;
+        let mut data = vec![0; NONCE_LEN];
+        getrandom::getrandom(&mut data).map_err(|e| {
+            ServerFnError::<NoCustomError>::ServerError(e.to_string())
+        })?;
+        let ciphertext = key
+            .cipher()
+            .encrypt(
+                Nonce::from_slice(&data),
+                Payload {
+                    msg: &plaintext,
+                    aad: &key.aad::<T>(),
+                },
+            )
+            .map_err(|_| {
+                ServerFnError::<NoCustomError>::Serialization(
+                    "failed to encrypt value".into(),
+                )
+            })?;
+        data.extend(ciphertext);
+        Ok(Self {
+            value: Some(value),
+            token: format!("{}.{}", key.id, URL_SAFE_NO_PAD.encode(data)),
+        })
+    }
+}
+
+impl<T> Encrypted<T> {
+    /// Returns the value, which is only available on the server.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Consumes the wrapper, returning the value, which is only available on
+    /// the server.
+    pub fn into_inner(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T> fmt::Debug for Encrypted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Encrypted").field(&self.token).finish()
+    }
+}
+
+impl<T> Serialize for Encrypted<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.token)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Encrypted<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        // only the server holds the keys to decrypt the value with
+        let value = if cfg!(feature = "ssr") {
+            let keyring = keyring().map_err(D::Error::custom)?;
+            Some(
+                open_encrypted_with(&keyring, &token)
+                    .map_err(D::Error::custom)?,
+            )
+        } else {
+            None
+        };
+        Ok(Self { value, token })
+    }
+}
+
+fn open_encrypted_with<T: DeserializeOwned>(
+    keyring: &Keyring,
+    token: &str,
+) -> Result<T, String> {
+    let (id, data) = token
+        .rsplit_once('.')
+        .ok_or_else(|| "malformed encrypted value".to_string())?;
+    let data = decode(data)?;
+    if data.len() < NONCE_LEN {
+        return Err("malformed encrypted value".into());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let key = keyring.get(id)?;
+    let plaintext = key
+        .cipher()
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &key.aad::<T>(),
+            },
+        )
+        .map_err(|_| "invalid encrypted value".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    fn keyring() -> Keyring {
+        Keyring::new("2024-06", "a long and random secret")
+    }
+
+    // replaces the segment of a token at `index`
+    fn replace_segment(token: &str, index: usize, segment: &str) -> String {
+        let mut segments = token.split('.').collect::<Vec<_>>();
+        segments[index] = segment;
+        segments.join(".")
+    }
+
+    #[test]
+    fn signed_round_trip() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+
+        assert!(signed.token.starts_with("2024-06."));
+        assert_eq!(
+            open_signed_with::<u32>(Some(&keyring()), &signed.token),
+            Ok(42)
+        );
+    }
+
+    #[test]
+    fn signed_rejects_tampered_payload() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+        let token =
+            replace_segment(&signed.token, 1, &URL_SAFE_NO_PAD.encode("43"));
+
+        assert_eq!(
+            open_signed_with::<u32>(Some(&keyring()), &token),
+            Err("invalid signature".into())
+        );
+    }
+
+    #[test]
+    fn signed_rejects_tampered_signature() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+        let other = Signed::with_keyring(&keyring(), 43u32).unwrap();
+        let token = replace_segment(
+            &signed.token,
+            2,
+            other.token.rsplit('.').next().unwrap(),
+        );
+
+        assert_eq!(
+            open_signed_with::<u32>(Some(&keyring()), &token),
+            Err("invalid signature".into())
+        );
+    }
+
+    #[test]
+    fn signed_rejects_another_type() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+
+        assert_eq!(
+            open_signed_with::<u64>(Some(&keyring()), &signed.token),
+            Err("invalid signature".into())
+        );
+    }
+
+    #[test]
+    fn signed_rejects_unknown_key() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+        let other = Keyring::new("2024-12", "another secret");
+
+        assert_eq!(
+            open_signed_with::<u32>(Some(&other), &signed.token),
+            Err("unknown key \"2024-06\"".into())
+        );
+    }
+
+    #[test]
+    fn signed_with_previous_key_after_rotation() {
+        let signed = Signed::with_keyring(&keyring(), 42u32).unwrap();
+        let rotated = Keyring::new("2024-12", "another secret")
+            .with_previous("2024-06", "a long and random secret");
+
+        assert_eq!(
+            open_signed_with::<u32>(Some(&rotated), &signed.token),
+            Ok(42)
+        );
+        assert!(Signed::with_keyring(&rotated, 42u32)
+            .unwrap()
+            .token
+            .starts_with("2024-12."));
+    }
+
+    #[test]
+    fn encrypted_round_trip() {
+        let encrypted =
+            Encrypted::with_keyring(&keyring(), String::from("secret"))
+                .unwrap();
+
+        assert!(!encrypted.token.contains("secret"));
+        assert_eq!(
+            open_encrypted_with::<String>(&keyring(), &encrypted.token),
+            Ok("secret".into())
+        );
+    }
+
+    #[test]
+    fn encrypted_rejects_tampered_value() {
+        let encrypted =
+            Encrypted::with_keyring(&keyring(), String::from("secret"))
+                .unwrap();
+        let (id, data) = encrypted.token.rsplit_once('.').unwrap();
+        let mut data = URL_SAFE_NO_PAD.decode(data).unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        let token = format!("{id}.{}", URL_SAFE_NO_PAD.encode(data));
+
+        assert_eq!(
+            open_encrypted_with::<String>(&keyring(), &token),
+            Err("invalid encrypted value".into())
+        );
+    }
+
+    #[test]
+    fn encrypted_rejects_another_type() {
+        let encrypted =
+            Encrypted::with_keyring(&keyring(), String::from("secret"))
+                .unwrap();
+
+        assert_eq!(
+            open_encrypted_with::<Box<str>>(&keyring(), &encrypted.token),
+            Err("invalid encrypted value".into())
+        );
+    }
+
+    #[test]
+    fn encrypted_rejects_unknown_key() {
+        let encrypted =
+            Encrypted::with_keyring(&keyring(), String::from("secret"))
+                .unwrap();
+        let other = Keyring::new("2024-12", "another secret");
+
+        assert_eq!(
+            open_encrypted_with::<String>(&other, &encrypted.token),
+            Err("unknown key \"2024-06\"".into())
+        );
+    }
+
+    #[test]
+    fn encrypted_with_previous_key_after_rotation() {
+        let encrypted =
+            Encrypted::with_keyring(&keyring(), String::from("secret"))
+                .unwrap();
+        let rotated = Keyring::new("2024-12", "another secret")
+            .with_previous("2024-06", "a long and random secret");
+
+        assert_eq!(
+            open_encrypted_with::<String>(&rotated, &encrypted.token),
+            Ok("secret".into())
+        );
+    }
+
+    // the only test that sets the global keyring
+    #[test]
+    fn deserializing_checks_sealed_values() {
+        set_keyring(keyring());
+        let signed =
+            serde_json::to_string(&Signed::new(42u32).unwrap()).unwrap();
+        let encrypted = serde_json::to_string(
+            &Encrypted::new(String::from("secret")).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Signed<u32>>(&signed)
+                .unwrap()
+                .into_inner(),
+            42
+        );
+        assert_eq!(
+            serde_json::from_str::<Encrypted<String>>(&encrypted)
+                .unwrap()
+                .into_inner(),
+            Some("secret".into())
+        );
+        assert!(
+            serde_json::from_str::<Signed<u32>>("\"2024-06.NDI.\"").is_err()
+        );
+    }
+}
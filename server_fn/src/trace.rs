@@ -0,0 +1,144 @@
+//! With the `tracing` feature, each call to a server function runs in a
+//! [`tracing`] span named `server_fn`, with fields for:
+//! - `server_fn.name`: the name of its type, like `my_app::api::AddTodo`;
+//! - `server_fn.path` and `http.method`: its endpoint;
+//! - `server_fn.args`: a fingerprint of its arguments, which is the same for
+//!   calls with the same arguments without recording what they are;
+//! - `trace_parent`: the trace context sent by the client, if any;
+//! - `duration_ms`: how long the call took;
+//! - `outcome`: whether it returned `ok` or an `error`, and the `error`
+//!   itself.
+//!
+//! The span covers deserializing the arguments, running the server function,
+//! and serializing its result, so spans created within it are nested in it.
+//!
+//! ## Propagating Trace Context
+//!
+//! The clients send the trace context returned by the function set with
+//! [`set_trace_parent`] in the [W3C `traceparent`](https://www.w3.org/TR/trace-context/)
+//! header, which is passed to the function that creates the span on the
+//! server. With [`set_make_span`], this can make the span a child of the
+//! client's span, so that a call can be followed from end to end:
+//!
+//! ```rust,ignore
+//! // on the client
+//! set_trace_parent(|| current_trace_parent());
+//!
+//! // on the server
+//! set_make_span(|info| {
+//!     let span = default_span(info);
+//!     if let Some(parent) = info.trace_parent {
+//!         span.set_parent(extract_context(parent));
+//!     }
+//!     span
+//! });
+//! ```
+
+use crate::ServerFnError;
+use http::Method;
+use std::{
+    fmt::Display,
+    sync::{Arc, RwLock},
+    time::Instant,
+};
+use tracing::{field::Empty, Span};
+
+/// The name of the header in which the clients send the trace context.
+pub const TRACE_PARENT_HEADER: &str = "traceparent";
+
+type MakeSpan = Arc<dyn Fn(&SpanInfo<'_>) -> Span + Send + Sync>;
+type TraceParent = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+static MAKE_SPAN: RwLock<Option<MakeSpan>> = RwLock::new(None);
+static TRACE_PARENT: RwLock<Option<TraceParent>> = RwLock::new(None);
+
+/// Describes a server function call, for which a span is created.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SpanInfo<'a> {
+    /// The name of the server function's type.
+    pub name: &'static str,
+    /// The path of the server function's endpoint.
+    pub path: &'static str,
+    /// The HTTP method the server function is called with.
+    pub method: &'a Method,
+    /// The `traceparent` header of the request, if any.
+    pub trace_parent: Option<&'a str>,
+}
+
+/// Sets the function that creates the span for each server function call,
+/// in place of [`default_span`].
+///
+/// Fields that are recorded once the call has completed are only recorded if
+/// the span declares them, as [`default_span`] does.
+pub fn set_make_span(
+    make_span: impl Fn(&SpanInfo<'_>) -> Span + Send + Sync + 'static,
+) {
+    *MAKE_SPAN.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(make_span));
+}
+
+/// Creates the span for a server function call, with the fields described
+/// in the [module docs](self).
+pub fn default_span(info: &SpanInfo<'_>) -> Span {
+    tracing::info_span!(
+        "server_fn",
+        server_fn.name = info.name,
+        server_fn.path = info.path,
+        http.method = info.method.as_str(),
+        server_fn.args = Empty,
+        trace_parent = info.trace_parent,
+        duration_ms = Empty,
+        outcome = Empty,
+        error = Empty,
+    )
+}
+
+/// Sets the function that returns the trace context of the current call on
+/// the client, which is sent in the [`TRACE_PARENT_HEADER`] header.
+pub fn set_trace_parent(
+    trace_parent: impl Fn() -> Option<String> + Send + Sync + 'static,
+) {
+    *TRACE_PARENT.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(trace_parent));
+}
+
+#[allow(unused)] // used by the clients
+pub(crate) fn trace_parent() -> Option<String> {
+    let trace_parent = TRACE_PARENT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()?;
+    trace_parent()
+}
+
+pub(crate) fn server_fn_span(info: &SpanInfo<'_>) -> Span {
+    let make_span = MAKE_SPAN.read().unwrap_or_else(|e| e.into_inner()).clone();
+    match make_span {
+        Some(make_span) => make_span(info),
+        None => default_span(info),
+    }
+}
+
+pub(crate) fn record_args(fingerprint: Option<u64>) {
+    if let Some(fingerprint) = fingerprint {
+        Span::current().record("server_fn.args", format!("{fingerprint:016x}"));
+    }
+}
+
+pub(crate) fn record_outcome<E: Display>(
+    start: Instant,
+    err: Option<&ServerFnError<E>>,
+) {
+    let span = Span::current();
+    span.record("duration_ms", start.elapsed().as_secs_f64() * 1000.0);
+    match err {
+        None => {
+            span.record("outcome", "ok");
+        }
+        Some(err) => {
+            span.record("outcome", "error");
+            span.record("error", tracing::field::display(err));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Server functions can validate their arguments after they have been
+//! deserialized on the server, before the body of the function runs:
+//!
+//! ```rust,ignore
+//! #[server(validate = true)]
+//! pub async fn sign_up(name: String, age: u8) -> Result<(), ServerFnError> {
+//!     // only runs if the arguments are valid
+//! }
+//!
+//! impl Validate for SignUp {
+//!     fn validate(&self) -> Result<(), ValidationErrors> {
+//!         let mut errors = ValidationErrors::new();
+//!         if self.name.trim().is_empty() {
+//!             errors.add("name", "Please enter your name.");
+//!         }
+//!         if self.age < 18 {
+//!             errors.add("age", "You must be at least 18.");
+//!         }
+//!         errors.into_result()
+//!     }
+//! }
+//! ```
+//!
+//! If validation fails, the server responds with `422 Unprocessable Entity`,
+//! and the call returns [`ServerFnError::Validation`](crate::ServerFnError::Validation),
+//! which holds the error messages for each field. These can be shown next to the
+//! inputs of a form with [`ServerFnError::validation_errors`](crate::ServerFnError::validation_errors).
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// The HTTP status code of the response when a server function's arguments
+/// fail validation.
+pub const VALIDATION_STATUS: u16 = 422;
+
+/// Validates the arguments of a server function, which is run on the server
+/// before its body when it is declared with `#[server(validate = true)]`.
+pub trait Validate {
+    /// Returns the error messages for each invalid field, if any.
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// The error messages for each field of a server function's arguments that
+/// failed validation, keyed by the name of the field.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[serde(transparent)]
+pub struct ValidationErrors(BTreeMap<String, Vec<String>>);
+
+impl ValidationErrors {
+    /// Creates an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error message for the given field.
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.0.entry(field.into()).or_default().push(message.into());
+        self
+    }
+
+    /// The error messages for the given field, which are empty if it is valid.
+    pub fn field(&self, field: &str) -> &[String] {
+        self.0.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over each invalid field and its error messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.0
+            .iter()
+            .map(|(field, messages)| (field.as_str(), messages.as_slice()))
+    }
+
+    /// Returns `Ok(())` if there are no errors, and the errors otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (field, messages)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field}: {}", messages.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{NoCustomError, ServerFnError, ServerFnErrorSerde};
+
+    fn errors() -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        errors
+            .add("name", "Please enter your name.")
+            .add("age", "You must be at least 18.")
+            .add("age", "Ages are numbers, like 18 | 19.");
+        errors
+    }
+
+    #[test]
+    fn errors_round_trip_with_their_fields() {
+        let err = ServerFnError::<NoCustomError>::Validation(errors());
+        assert_eq!(err.status_code(), VALIDATION_STATUS);
+
+        let err = ServerFnError::<NoCustomError>::de(&err.ser().unwrap());
+        let errors = err.validation_errors().unwrap();
+        assert_eq!(errors.field("name"), ["Please enter your name."]);
+        assert_eq!(
+            errors.field("age"),
+            [
+                "You must be at least 18.",
+                "Ages are numbers, like 18 | 19."
+            ]
+        );
+        assert!(errors.field("email").is_empty());
+    }
+
+    #[cfg(feature = "axum-no-default")]
+    #[test]
+    fn errors_are_sent_with_status_422() {
+        use crate::response::Res;
+        use axum::body::Body;
+        use http::Response;
+
+        let err = ServerFnError::<NoCustomError>::Validation(errors());
+        let res =
+            <Response<Body> as Res<NoCustomError>>::error_response_with_status(
+                "/api/sign_up",
+                &err,
+                err.status_code(),
+            );
+        assert_eq!(res.status().as_u16(), 422);
+
+        let body = futures::executor::block_on(axum::body::to_bytes(
+            res.into_body(),
+            usize::MAX,
+        ))
+        .unwrap();
+        let body = std::str::from_utf8(&body).unwrap();
+        assert_eq!(ServerFnError::<NoCustomError>::de(body), err);
+    }
+}
@@ -0,0 +1,707 @@
+//! Server functions that communicate over a WebSocket, rather than through a single
+//! request and response.
+//!
+//! A server function declared with `#[server(protocol = "websocket")]` takes a single
+//! [`WebsocketChannel`] argument, through which it receives messages from the client
+//! and sends messages back to it, and runs for as long as the connection is open:
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize)]
+//! pub enum ClientMsg { Say(String) }
+//! #[derive(Serialize, Deserialize)]
+//! pub enum ServerMsg { Said(String) }
+//!
+//! #[server(protocol = "websocket")]
+//! pub async fn chat(
+//!     channel: WebsocketChannel<ClientMsg, ServerMsg>,
+//! ) -> Result<(), ServerFnError> {
+//!     let (mut tx, mut rx) = channel.split();
+//!     while let Some(ClientMsg::Say(text)) = rx.next().await.transpose()? {
+//!         tx.send(ServerMsg::Said(text)).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! On the client, `Chat::connect()` opens the connection, returning the other end of the
+//! channel, which sends `ClientMsg` and receives `ServerMsg`:
+//!
+//! ```rust,ignore
+//! let mut channel = Chat::connect().await?;
+//! channel.send(ClientMsg::Say("hello".into())).await?;
+//! let reply = channel.next().await;
+//! ```
+//!
+//! Messages are sent as JSON text frames. If the server function returns an error, the
+//! client receives it as the last item of its stream. Dropping either end of the channel
+//! closes the connection; on the server, the function is cancelled when the client
+//! disconnects.
+//!
+//! With the `websocket` feature of `leptos_axum` or `leptos_actix`, the function runs in
+//! the context of the request that opened the connection, like any other server function,
+//! so it can extract the request's headers or cookies, or use the context provided by the
+//! handler.
+
+use crate::{
+    codec::{Encoding, FromReq, FromRes, IntoReq, IntoRes},
+    error::{NoCustomError, ServerFnError, ServerFnErrorSerde},
+    request::{ClientReq, Req},
+    response::{ClientRes, Res},
+};
+use futures::{
+    channel::mpsc::{UnboundedReceiver, UnboundedSender},
+    future::ready,
+    Sink, SinkExt, Stream, StreamExt,
+};
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The protocol used by WebSocket server functions.
+///
+/// Their arguments and return values are never encoded in an HTTP request or response:
+/// instead, the request is upgraded to a WebSocket connection.
+pub struct Websocket;
+
+impl Encoding for Websocket {
+    const CONTENT_TYPE: &'static str = "application/json";
+    const METHOD: Method = Method::GET;
+}
+
+const NOT_HTTP: &str = "WebSocket server functions cannot be called as HTTP \
+                        requests: use `connect()` on the server function's \
+                        argument type instead.";
+
+impl<CustErr, T, Request> IntoReq<Websocket, Request, CustErr> for T
+where
+    Request: ClientReq<CustErr>,
+{
+    fn into_req(
+        self,
+        _path: &str,
+        _accepts: &str,
+    ) -> Result<Request, ServerFnError<CustErr>> {
+        Err(ServerFnError::Request(NOT_HTTP.into()))
+    }
+}
+
+impl<CustErr, T, Request> FromReq<Websocket, Request, CustErr> for T
+where
+    Request: Req<CustErr> + Send + 'static,
+{
+    async fn from_req(_req: Request) -> Result<Self, ServerFnError<CustErr>> {
+        Err(ServerFnError::Request(NOT_HTTP.into()))
+    }
+}
+
+impl<CustErr, T, Response> IntoRes<Websocket, Response, CustErr> for T
+where
+    Response: Res<CustErr>,
+    T: Send,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        Err(ServerFnError::Response(NOT_HTTP.into()))
+    }
+}
+
+impl<CustErr, T, Response> FromRes<Websocket, Response, CustErr> for T
+where
+    Response: ClientRes<CustErr> + Send,
+{
+    async fn from_res(_res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        Err(ServerFnError::Response(NOT_HTTP.into()))
+    }
+}
+
+/// One end of a WebSocket connection to or from a server function, which receives
+/// messages of type `In` and sends messages of type `Out`.
+///
+/// The channel is both a [`Stream`] of incoming messages and a [`Sink`] for outgoing
+/// ones, and can be split into the two with [`WebsocketChannel::split`].
+pub struct WebsocketChannel<In, Out> {
+    incoming: Pin<Box<dyn Stream<Item = Result<In, ServerFnError>> + Send>>,
+    outgoing: Pin<Box<dyn Sink<Out, Error = ServerFnError> + Send>>,
+}
+
+impl<In, Out> Debug for WebsocketChannel<In, Out> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebsocketChannel").finish()
+    }
+}
+
+impl<In, Out> WebsocketChannel<In, Out>
+where
+    In: DeserializeOwned + Send + 'static,
+    Out: Serialize + Send + 'static,
+{
+    // the messages are sent to and from the socket by the server integration
+    // or the browser as text frames
+    pub(crate) fn from_frames(
+        incoming: UnboundedReceiver<Result<String, ServerFnError>>,
+        outgoing: UnboundedSender<String>,
+    ) -> Self {
+        Self {
+            incoming: Box::pin(
+                incoming.map(|frame| frame.and_then(|frame| decode(&frame))),
+            ),
+            outgoing: Box::pin(
+                outgoing
+                    .sink_map_err(|e| {
+                        ServerFnError::<NoCustomError>::Request(e.to_string())
+                    })
+                    .with(|message: Out| ready(encode(&message))),
+            ),
+        }
+    }
+}
+
+impl<In, Out> WebsocketChannel<In, Out> {
+    /// Splits the channel into the sink for outgoing messages and the stream of
+    /// incoming ones, which can be used independently.
+    #[allow(clippy::type_complexity)]
+    pub fn split(
+        self,
+    ) -> (
+        Pin<Box<dyn Sink<Out, Error = ServerFnError> + Send>>,
+        Pin<Box<dyn Stream<Item = Result<In, ServerFnError>> + Send>>,
+    ) {
+        (self.outgoing, self.incoming)
+    }
+}
+
+impl<In, Out> Stream for WebsocketChannel<In, Out> {
+    type Item = Result<In, ServerFnError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.incoming.as_mut().poll_next(cx)
+    }
+}
+
+impl<In, Out> Sink<Out> for WebsocketChannel<In, Out> {
+    type Error = ServerFnError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.outgoing.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: Out,
+    ) -> Result<(), Self::Error> {
+        self.outgoing.as_mut().start_send(item)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.outgoing.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.outgoing.as_mut().poll_close(cx)
+    }
+}
+
+/// Relates the channel a WebSocket server function receives to the channel its client
+/// receives, which has the types of its messages swapped.
+pub trait ServerChannel {
+    /// The client's end of the channel.
+    type Client;
+}
+
+impl<In, Out> ServerChannel for WebsocketChannel<In, Out> {
+    type Client = WebsocketChannel<Out, In>;
+}
+
+// errors are sent as a frame starting with `!`, followed by the serialized
+// error as a JSON string, which can never be mistaken for a JSON value
+const ERROR_PREFIX: char = '!';
+
+fn encode<T: Serialize>(message: &T) -> Result<String, ServerFnError> {
+    serde_json::to_string(message)
+        .map_err(|e| ServerFnError::Serialization(e.to_string()))
+}
+
+#[cfg(feature = "ssr")]
+fn encode_error<CustErr>(error: &ServerFnError<CustErr>) -> String
+where
+    CustErr: std::str::FromStr + std::fmt::Display,
+{
+    let error = error.ser().unwrap_or_else(|_| error.to_string());
+    let error = serde_json::to_string(&error).unwrap_or_default();
+    format!("{ERROR_PREFIX}{error}")
+}
+
+fn decode<T: DeserializeOwned>(frame: &str) -> Result<T, ServerFnError> {
+    match frame.strip_prefix(ERROR_PREFIX) {
+        Some(error) => {
+            let error = serde_json::from_str::<String>(error).map_err(|e| {
+                ServerFnError::<NoCustomError>::Deserialization(e.to_string())
+            })?;
+            Err(ServerFnError::de(&error))
+        }
+        None => serde_json::from_str(frame)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+    }
+}
+
+/// The handler that runs a WebSocket server function once the connection is open,
+/// given the text frames it receives and a sender for the frames it sends.
+pub type OnUpgrade = Box<
+    dyn FnOnce(
+            UnboundedReceiver<Result<String, ServerFnError>>,
+            UnboundedSender<String>,
+        ) -> Pin<Box<dyn std::future::Future<Output = ()> + Send>>
+        + Send,
+>;
+
+/// The future that runs a WebSocket server function once its connection is
+/// open.
+pub type RunFuture = Pin<Box<dyn std::future::Future<Output = ()> + Send>>;
+
+/// Wraps the future that runs a WebSocket server function once its connection
+/// is open.
+///
+/// The server function runs after the handler of the request that opened the
+/// connection has returned. A server integration can insert an `UpgradeContext`
+/// into the extensions of the request, so that the server function runs in the
+/// context of that request, as other server functions do.
+#[derive(Clone)]
+pub struct UpgradeContext(std::sync::Arc<std::sync::Mutex<Option<WrapRun>>>);
+
+type WrapRun = Box<dyn FnOnce(RunFuture) -> RunFuture + Send>;
+
+impl Debug for UpgradeContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UpgradeContext").finish()
+    }
+}
+
+impl UpgradeContext {
+    /// Creates an `UpgradeContext` that runs the server function with `wrap`,
+    /// which returns a future that awaits the given one.
+    pub fn new(
+        wrap: impl FnOnce(RunFuture) -> RunFuture + Send + 'static,
+    ) -> Self {
+        Self(std::sync::Arc::new(std::sync::Mutex::new(Some(Box::new(
+            wrap,
+        )))))
+    }
+
+    // wraps the server function, if the request has an `UpgradeContext`
+    #[cfg(any(feature = "axum-no-default", feature = "actix"))]
+    fn wrap(this: Option<&Self>, run: RunFuture) -> RunFuture {
+        let wrap = this.and_then(|this| {
+            this.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+        });
+        match wrap {
+            Some(wrap) => wrap(run),
+            None => run,
+        }
+    }
+}
+
+/// A server request that can be upgraded to a WebSocket connection.
+pub trait WebsocketRequest<Response, CustErr>: Sized {
+    /// Upgrades the request, returning the response that accepts the connection,
+    /// and running `on_upgrade` once it has been established.
+    fn upgrade(
+        self,
+        on_upgrade: OnUpgrade,
+    ) -> impl std::future::Future<
+        Output = Result<Response, ServerFnError<CustErr>>,
+    > + Send;
+}
+
+#[doc(hidden)]
+#[cfg(feature = "ssr")]
+pub async fn run_on_server<S, In, Out>(
+    req: S::ServerRequest,
+    from_channel: fn(WebsocketChannel<In, Out>) -> S,
+) -> S::ServerResponse
+where
+    S: crate::ServerFn + 'static,
+    S::ServerRequest: WebsocketRequest<S::ServerResponse, S::Error>,
+    In: DeserializeOwned + Send + 'static,
+    Out: Serialize + Send + 'static,
+{
+    let on_upgrade: OnUpgrade = Box::new(move |incoming, outgoing| {
+        Box::pin(async move {
+            let errors = outgoing.clone();
+            let channel = WebsocketChannel::from_frames(incoming, outgoing);
+            if let Err(e) = from_channel(channel).run_body().await {
+                _ = errors.unbounded_send(encode_error(&e));
+            }
+        })
+    });
+    req.upgrade(on_upgrade).await.unwrap_or_else(|e| {
+        S::ServerResponse::error_response_with_status(
+            S::PATH,
+            &e,
+            S::error_status(&e),
+        )
+    })
+}
+
+/// Opens a WebSocket connection to the server function at `path`, returning the
+/// client's end of its channel.
+///
+/// This is called by the `connect()` function generated for WebSocket server functions.
+#[cfg(feature = "browser")]
+pub async fn connect<In, Out, CustErr>(
+    path: &str,
+) -> Result<WebsocketChannel<In, Out>, ServerFnError<CustErr>>
+where
+    In: DeserializeOwned + Send + 'static,
+    Out: Serialize + Send + 'static,
+{
+    use futures::channel::mpsc;
+    use gloo_net::websocket::{futures::WebSocket, Message, WebSocketError};
+
+    let location = web_sys::window()
+        .ok_or_else(|| ServerFnError::Request("no window".into()))?
+        .location();
+    let scheme = match location.protocol().as_deref() {
+        Ok("https:") => "wss",
+        _ => "ws",
+    };
+    let host = location
+        .host()
+        .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
+    let socket = WebSocket::open(&format!("{scheme}://{host}{path}"))
+        .map_err(|e| ServerFnError::Request(e.to_string()))?;
+    let (mut sink, mut stream) = socket.split();
+
+    let (incoming_tx, incoming_rx) = mpsc::unbounded();
+    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded::<String>();
+    wasm_bindgen_futures::spawn_local(async move {
+        while let Some(message) = stream.next().await {
+            let frame = match message {
+                Ok(Message::Text(text)) => Ok(text),
+                Ok(Message::Bytes(_)) => continue,
+                // the server closed the connection normally
+                Err(WebSocketError::ConnectionClose(event))
+                    if event.was_clean =>
+                {
+                    break
+                }
+                Err(e) => Err(ServerFnError::Request(e.to_string())),
+            };
+            let failed = frame.is_err();
+            if incoming_tx.unbounded_send(frame).is_err() || failed {
+                break;
+            }
+        }
+    });
+    wasm_bindgen_futures::spawn_local(async move {
+        while let Some(frame) = outgoing_rx.next().await {
+            if sink.send(Message::Text(frame)).await.is_err() {
+                break;
+            }
+        }
+        _ = sink.close().await;
+    });
+
+    Ok(WebsocketChannel::from_frames(incoming_rx, outgoing_tx))
+}
+
+/// Opens a WebSocket connection to the server function at `path`, returning the
+/// client's end of its channel.
+///
+/// This is called by the `connect()` function generated for WebSocket server functions.
+#[cfg(not(feature = "browser"))]
+pub async fn connect<In, Out, CustErr>(
+    path: &str,
+) -> Result<WebsocketChannel<In, Out>, ServerFnError<CustErr>> {
+    Err(ServerFnError::Request(format!(
+        "WebSocket server functions can only be connected to from the \
+         browser, and {path} was not."
+    )))
+}
+
+#[cfg(feature = "axum-no-default")]
+mod axum {
+    use super::{OnUpgrade, UpgradeContext, WebsocketRequest};
+    use crate::error::ServerFnError;
+    use axum::{
+        body::Body,
+        extract::{
+            ws::{Message, WebSocketUpgrade},
+            FromRequestParts,
+        },
+        response::IntoResponse,
+    };
+    use futures::{channel::mpsc, future, SinkExt, StreamExt};
+    use http::{Request, Response};
+
+    impl<CustErr> WebsocketRequest<Response<Body>, CustErr> for Request<Body>
+    where
+        CustErr: Send,
+    {
+        async fn upgrade(
+            self,
+            on_upgrade: OnUpgrade,
+        ) -> Result<Response<Body>, ServerFnError<CustErr>> {
+            let (mut parts, _) = self.into_parts();
+            let context = parts.extensions.get::<UpgradeContext>().cloned();
+            let upgrade = WebSocketUpgrade::from_request_parts(&mut parts, &())
+                .await
+                .map_err(|e| ServerFnError::Request(e.body_text()))?;
+            Ok(upgrade
+                .on_upgrade(move |socket| async move {
+                    let (mut sink, mut stream) = socket.split();
+                    let (incoming_tx, incoming_rx) = mpsc::unbounded();
+                    let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded();
+
+                    let read = async move {
+                        while let Some(message) = stream.next().await {
+                            let frame = match message {
+                                Ok(Message::Text(text)) => Ok(text),
+                                Ok(Message::Close(_)) => break,
+                                Ok(_) => continue,
+                                Err(e) => {
+                                    Err(ServerFnError::Request(e.to_string()))
+                                }
+                            };
+                            let failed = frame.is_err();
+                            if incoming_tx.unbounded_send(frame).is_err()
+                                || failed
+                            {
+                                break;
+                            }
+                        }
+                    };
+                    let write = async move {
+                        while let Some(frame) = outgoing_rx.next().await {
+                            if sink.send(Message::Text(frame)).await.is_err() {
+                                break;
+                            }
+                        }
+                        _ = sink.close().await;
+                    };
+                    let run = UpgradeContext::wrap(
+                        context.as_ref(),
+                        on_upgrade(incoming_rx, outgoing_tx),
+                    );
+
+                    // the server function is cancelled if the client disconnects
+                    future::select(
+                        Box::pin(read),
+                        Box::pin(future::join(run, write)),
+                    )
+                    .await;
+                })
+                .into_response())
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix {
+    use super::{OnUpgrade, UpgradeContext, WebsocketRequest};
+    use crate::{
+        error::ServerFnError, request::actix::ActixRequest,
+        response::actix::ActixResponse,
+    };
+    use actix_web::HttpMessage;
+    use actix_ws::Message;
+    use futures::{channel::mpsc, future, StreamExt};
+    use send_wrapper::SendWrapper;
+
+    impl<CustErr> WebsocketRequest<ActixResponse, CustErr> for ActixRequest {
+        fn upgrade(
+            self,
+            on_upgrade: OnUpgrade,
+        ) -> impl std::future::Future<
+            Output = Result<ActixResponse, ServerFnError<CustErr>>,
+        > + Send {
+            // Actix keeps the request on a single thread
+            SendWrapper::new(async move {
+                let (req, payload) = self.take();
+                let (res, session, mut stream) =
+                    actix_ws::handle(&req, payload)
+                        .map_err(|e| ServerFnError::Request(e.to_string()))?;
+                let (incoming_tx, incoming_rx) = mpsc::unbounded();
+                let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded();
+
+                let mut pong = session.clone();
+                let read = async move {
+                    while let Some(message) = stream.next().await {
+                        let frame = match message {
+                            Ok(Message::Text(text)) => Ok(text.to_string()),
+                            Ok(Message::Ping(bytes)) => {
+                                _ = pong.pong(&bytes).await;
+                                continue;
+                            }
+                            Ok(Message::Close(_)) => break,
+                            Ok(_) => continue,
+                            Err(e) => {
+                                Err(ServerFnError::Request(e.to_string()))
+                            }
+                        };
+                        let failed = frame.is_err();
+                        if incoming_tx.unbounded_send(frame).is_err() || failed
+                        {
+                            break;
+                        }
+                    }
+                };
+                let mut session = session;
+                let write = async move {
+                    while let Some(frame) = outgoing_rx.next().await {
+                        if session.text(frame).await.is_err() {
+                            return;
+                        }
+                    }
+                    _ = session.close(None).await;
+                };
+                let context = req.extensions().get::<UpgradeContext>().cloned();
+                let run = UpgradeContext::wrap(
+                    context.as_ref(),
+                    on_upgrade(incoming_rx, outgoing_tx),
+                );
+
+                // the server function is cancelled if the client disconnects
+                actix_web::rt::spawn(async move {
+                    future::select(
+                        Box::pin(read),
+                        Box::pin(future::join(run, write)),
+                    )
+                    .await;
+                });
+
+                Ok(ActixResponse::from(res))
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{channel::mpsc, executor::block_on, future, FutureExt};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum ClientMsg {
+        Say(String),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum ServerMsg {
+        Said(String),
+    }
+
+    // carries the frames sent by one end of the connection to the other, as
+    // the socket does
+    fn pipe(
+        frames: UnboundedReceiver<String>,
+        to: UnboundedSender<Result<String, ServerFnError>>,
+    ) -> impl std::future::Future<Output = ()> {
+        frames.map(|frame| Ok(Ok(frame))).forward(to).map(|_| ())
+    }
+
+    #[test]
+    fn messages_round_trip_until_closed() {
+        let (server_out, to_client) = mpsc::unbounded();
+        let (client_in, client_frames) = mpsc::unbounded();
+        let (client_out, to_server) = mpsc::unbounded();
+        let (server_in, server_frames) = mpsc::unbounded();
+        let server = WebsocketChannel::<ClientMsg, ServerMsg>::from_frames(
+            server_frames,
+            server_out,
+        );
+        let client = WebsocketChannel::<ServerMsg, ClientMsg>::from_frames(
+            client_frames,
+            client_out,
+        );
+
+        let run_server = async move {
+            let (mut tx, mut rx) = server.split();
+            while let Some(ClientMsg::Say(text)) =
+                rx.next().await.transpose()?
+            {
+                tx.send(ServerMsg::Said(text)).await?;
+            }
+            Ok::<_, ServerFnError>(())
+        };
+        let run_client = async move {
+            let (mut tx, mut rx) = client.split();
+            tx.send(ClientMsg::Say("hello".into())).await.unwrap();
+            assert_eq!(
+                rx.next().await,
+                Some(Ok(ServerMsg::Said("hello".into())))
+            );
+            tx.send(ClientMsg::Say("bye".into())).await.unwrap();
+            assert_eq!(
+                rx.next().await,
+                Some(Ok(ServerMsg::Said("bye".into())))
+            );
+
+            // closing the client's end ends the server function, which
+            // closes the server's end
+            drop(tx);
+            assert_eq!(rx.next().await, None);
+        };
+
+        let (_, _, server, ()) = block_on(future::join4(
+            pipe(to_client, client_in),
+            pipe(to_server, server_in),
+            run_server,
+            run_client,
+        ));
+        assert_eq!(server, Ok(()));
+    }
+
+    #[test]
+    fn malformed_frames_are_decode_errors() {
+        let (frames_tx, frames) = mpsc::unbounded();
+        let (outgoing, _) = mpsc::unbounded();
+        let mut channel = WebsocketChannel::<ServerMsg, ClientMsg>::from_frames(
+            frames, outgoing,
+        );
+        frames_tx.unbounded_send(Ok("{\"Said\":".into())).unwrap();
+        frames_tx
+            .unbounded_send(Ok("{\"Said\":\"hi\"}".into()))
+            .unwrap();
+        drop(frames_tx);
+
+        let messages = block_on(channel.by_ref().collect::<Vec<_>>());
+        assert!(matches!(
+            messages[0],
+            Err(ServerFnError::Deserialization(_))
+        ));
+        assert_eq!(messages[1], Ok(ServerMsg::Said("hi".into())));
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn errors_of_the_server_function_are_decoded() {
+        let error = ServerFnError::<NoCustomError>::ServerError(
+            "the room is full".into(),
+        );
+        let frame = encode_error(&error);
+
+        assert!(frame.starts_with(ERROR_PREFIX));
+        assert_eq!(decode::<ServerMsg>(&frame), Err(error));
+        assert!(matches!(
+            decode::<ServerMsg>("!not a string"),
+            Err(ServerFnError::Deserialization(_))
+        ));
+    }
+}
@@ -7,7 +7,7 @@
 //! This crate contains the implementation of the `server_fn` macro. [`server_macro_impl`] can be used to implement custom versions of the macro for different frameworks that allow users to pass a custom context from the server to the server function.
 
 use convert_case::{Case, Converter};
-use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
@@ -55,6 +55,85 @@ pub fn server_macro_impl(
         }
     });
 
+    // extract all #[register] attributes, which list the types that a generic
+    // server function can be called with
+    let mut registrations: Vec<AngleBracketedGenericArguments> = vec![];
+    for attr in std::mem::take(&mut body.attrs) {
+        if attr.meta.path().is_ident("register") {
+            registrations.extend(attr.parse_args_with(
+                Punctuated::<AngleBracketedGenericArguments, Token![,]>::parse_terminated,
+            )?);
+        } else {
+            body.attrs.push(attr);
+        }
+    }
+    let type_params = body
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let is_generic = !type_params.is_empty();
+    if body.generics.lifetimes().next().is_some()
+        || body.generics.const_params().next().is_some()
+    {
+        return Err(syn::Error::new(
+            body.generics.span(),
+            "server functions can only be generic over types",
+        ));
+    }
+    if is_generic && registrations.is_empty() {
+        return Err(syn::Error::new(
+            body.generics.span(),
+            "generic server functions must list the types they can be called \
+             with, like #[register(<MyType>)], as each of them has its own \
+             endpoint",
+        ));
+    }
+    if let Some(registration) = registrations.first().filter(|_| !is_generic) {
+        return Err(syn::Error::new(
+            registration.span(),
+            "only generic server functions can be registered for types",
+        ));
+    }
+    if let Some(registration) = registrations
+        .iter()
+        .find(|registration| registration.args.len() != type_params.len())
+    {
+        return Err(syn::Error::new(
+            registration.span(),
+            format!(
+                "expected {} type(s), one for each type parameter of the \
+                 server function",
+                type_params.len()
+            ),
+        ));
+    }
+
+    let args = syn::parse::<ServerFnArgs>(args.into())?;
+
+    // `{name}` segments of the endpoint are path parameters, which are taken
+    // from the arguments of the same name
+    let path_params = args
+        .fn_path
+        .as_ref()
+        .map(path_param_names)
+        .transpose()?
+        .unwrap_or_default();
+    for (name, span) in &path_params {
+        let is_arg = body.inputs.iter().any(|f| match f {
+            FnArg::Typed(t) => {
+                matches!(&*t.pat, Pat::Ident(ident) if ident.ident == name)
+            }
+            FnArg::Receiver(_) => false,
+        });
+        if !is_arg {
+            return Err(syn::Error::new(
+                *span,
+                format!("path parameter `{name}` is not an argument of the server function"),
+            ));
+        }
+    }
+
     let fields = body
         .inputs
         .iter_mut()
@@ -70,16 +149,20 @@ pub fn server_macro_impl(
             };
 
             // strip `mut`, which is allowed in fn args but not in struct fields
+            // path parameters can be left out of the query string or body
+            let mut default = false;
             if let Pat::Ident(ident) = &mut *typed_arg.pat {
                 ident.mutability = None;
+                default =
+                    path_params.iter().any(|(name, _)| ident.ident == name);
             }
 
-            // allow #[server(default)] on fields
-            let mut default = false;
+            // allow #[server(default)] on fields, and move any other attributes
+            // (like `#[prost(...)]`) from the fn argument to the struct field
             let mut other_attrs = Vec::new();
-            for attr in typed_arg.attrs.iter() {
+            for attr in std::mem::take(&mut typed_arg.attrs) {
                 if !attr.path().is_ident("server") {
-                    other_attrs.push(attr.clone());
+                    other_attrs.push(attr);
                     continue;
                 }
                 attr.parse_nested_meta(|meta| {
@@ -94,18 +177,17 @@ pub fn server_macro_impl(
                     }
                 })?;
             }
-            typed_arg.attrs = other_attrs;
+            let PatType { pat, ty, .. } = typed_arg;
             if default {
-                Ok(quote! { #[serde(default)] pub #typed_arg })
+                Ok(quote! { #(#other_attrs)* #[serde(default)] pub #pat: #ty })
             } else {
-                Ok(quote! { pub #typed_arg })
+                Ok(quote! { #(#other_attrs)* pub #pat: #ty })
             }
         })
         .collect::<Result<Vec<_>>>()?;
 
     let dummy = body.to_dummy_output();
     let dummy_name = body.to_dummy_ident();
-    let args = syn::parse::<ServerFnArgs>(args.into())?;
 
     // default values for args
     let ServerFnArgs {
@@ -115,16 +197,36 @@ pub fn server_macro_impl(
         input_derive,
         output,
         fn_path,
+        version,
+        builtin_input,
         builtin_encoding,
         req_ty,
         res_ty,
         client,
         custom_wrapper,
         impl_from,
+        websocket,
+        middlewares: arg_middlewares,
+        retry,
+        idempotent,
+        cache,
+        validate,
+        batch,
     } = args;
-    let prefix = prefix.unwrap_or_else(|| Literal::string(default_path));
+    middlewares.extend(arg_middlewares);
+    let prefix = prefix.unwrap_or_else(|| syn::parse_quote!(#default_path));
+    // versioned prefixes like `/api/v2`
+    let prefix = match version {
+        Some(version) => {
+            let version = version.value();
+            let version = version.trim_matches('/');
+            quote! { #prefix, "/", #version }
+        }
+        None => quote! { #prefix },
+    };
     let fn_path = fn_path.unwrap_or_else(|| Literal::string(""));
     let input_ident = match &input {
+        _ if websocket => Some("Websocket".to_string()),
         Some(Type::Path(path)) => {
             path.path.segments.last().map(|seg| seg.ident.to_string())
         }
@@ -133,7 +235,7 @@ pub fn server_macro_impl(
     };
     let input = input
         .map(|n| {
-            if builtin_encoding {
+            if builtin_encoding || builtin_input {
                 quote! { #server_fn_path::codec::#n }
             } else {
                 n.to_token_stream()
@@ -157,6 +259,14 @@ pub fn server_macro_impl(
                 #server_fn_path::codec::Json
             }
         });
+    // WebSocket server functions are not called with an HTTP request and
+    // response, so their struct is never encoded
+    let (input, output) = if websocket {
+        let websocket = quote! { #server_fn_path::websocket::Websocket };
+        (websocket.clone(), websocket)
+    } else {
+        (input, output)
+    };
     // default to PascalCase version of function name if no struct name given
     let struct_name = struct_name.unwrap_or_else(|| {
         let upper_camel_case_name = Converter::new()
@@ -208,10 +318,36 @@ pub fn server_macro_impl(
         FnArg::Receiver(_) => None,
         FnArg::Typed(t) => Some((&t.pat, &t.ty)),
     });
+    if websocket && (fn_args.len() != 1 || custom_wrapper.is_some()) {
+        return Err(syn::Error::new(
+            body.ident.span(),
+            "WebSocket server functions take exactly one argument, a \
+             `WebsocketChannel`, and cannot use a `custom` wrapper.",
+        ));
+    }
+    if matches!(
+        input_ident.as_deref(),
+        Some("Streaming") | Some("StreamingText")
+    ) && fn_args.len() != 1
+    {
+        return Err(syn::Error::new(
+            body.ident.span(),
+            "Server functions with a streaming input encoding take exactly \
+             one argument, a `ByteStream` or `TextStream`.",
+        ));
+    }
+    let batch = batch.filter(|batch| batch.value);
+    if let Some(batch) = batch.as_ref().filter(|_| websocket) {
+        return Err(syn::Error::new(
+            batch.span(),
+            "WebSocket server functions cannot be batched.",
+        ));
+    }
     let impl_from = impl_from.map(|v| v.value).unwrap_or(true);
     let from_impl = (body.inputs.len() == 1
         && first_field.is_some()
-        && impl_from)
+        && impl_from
+        && !is_generic)
         .then(|| {
             let field = first_field.unwrap();
             let (name, ty) = field;
@@ -237,12 +373,6 @@ pub fn server_macro_impl(
 
     let output_ty = output_type(&return_ty)?;
     let error_ty = err_type(&return_ty)?;
-    let error_ty =
-        error_ty.map(ToTokens::to_token_stream).unwrap_or_else(|| {
-            quote! {
-                #server_fn_path::error::NoCustomError
-            }
-        });
 
     // build server fn path
     let serde_path = server_fn_path.as_ref().map(|path| {
@@ -258,6 +388,143 @@ pub fn server_macro_impl(
         .map(|path| quote!(#path))
         .unwrap_or_else(|| quote! { server_fn });
 
+    // errors returned directly, as `Result<T, MyError>`, are sent as the
+    // custom error of a `ServerFnError<Typed<MyError>>`
+    let typed_error = match error_ty {
+        ErrorType::Typed(ty) => Some(ty),
+        _ => None,
+    };
+    let error_ty = match error_ty {
+        ErrorType::Default => quote! {
+            #server_fn_path::error::NoCustomError
+        },
+        ErrorType::Custom(ty) => ty.to_token_stream(),
+        ErrorType::Typed(ty) => quote! {
+            #server_fn_path::error::Typed<#ty>
+        },
+    };
+    let run_body_ty = if typed_error.is_some() {
+        quote! { Result<#output_ty, #server_fn_path::ServerFnError<#error_ty>> }
+    } else {
+        return_ty.to_token_stream()
+    };
+    if let (Some(_), Some((_, span))) =
+        (custom_wrapper.as_ref(), path_params.first())
+    {
+        return Err(syn::Error::new(
+            *span,
+            "server functions with a `custom` wrapper cannot have path \
+             parameters",
+        ));
+    }
+    let path_params_impl = (!path_params.is_empty()).then(|| {
+        let names = path_params.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        let idents = path_params
+            .iter()
+            .map(|(name, span)| Ident::new(name, *span))
+            .collect::<Vec<_>>();
+        quote! {
+            fn request_path(&self) -> std::borrow::Cow<'static, str> {
+                std::borrow::Cow::Owned(#server_fn_path::rest::fill(
+                    <Self as #server_fn_path::ServerFn>::PATH,
+                    &[#((#names, self.#idents.to_string())),*],
+                ))
+            }
+
+            fn extract_path_params(
+                &mut self,
+                path: &str,
+            ) -> Result<(), #server_fn_path::ServerFnError<Self::Error>> {
+                let pattern = <Self as #server_fn_path::ServerFn>::PATH;
+                let Some(params) = #server_fn_path::rest::params(pattern, path) else {
+                    return Err(#server_fn_path::ServerFnError::Args(format!(
+                        "the path {path} does not match {pattern}"
+                    )));
+                };
+                for (name, value) in params {
+                    match name {
+                        #(
+                            #names => {
+                                match value.parse() {
+                                    Ok(value) => self.#idents = value,
+                                    Err(_) => {
+                                        return Err(#server_fn_path::ServerFnError::Args(format!(
+                                            "could not parse path parameter `{name}` from {value:?}"
+                                        )))
+                                    }
+                                }
+                            }
+                        )*
+                        _ => {}
+                    }
+                }
+                Ok(())
+            }
+        }
+    });
+    let error_status = typed_error.map(|_| {
+        quote! {
+            fn error_status(err: &#server_fn_path::ServerFnError<Self::Error>) -> u16 {
+                match err {
+                    #server_fn_path::ServerFnError::WrappedServerError(err) => {
+                        #server_fn_path::error::FromServerFnError::status_code(&err.0)
+                    }
+                    err => err.status_code(),
+                }
+            }
+        }
+    });
+    let validate_impl = validate.filter(|validate| validate.value).map(|_| {
+        quote! {
+            fn validate(&self) -> Result<(), #server_fn_path::validation::ValidationErrors> {
+                <Self as #server_fn_path::validation::Validate>::validate(self)
+            }
+        }
+    });
+
+    // the arguments are hashed for traces on the server; a custom wrapper may
+    // not implement `Debug`
+    let args_fingerprint = (cfg!(feature = "ssr") && custom_wrapper.is_none())
+        .then(|| {
+            quote! {
+                fn args_fingerprint(&self) -> Option<u64> {
+                    Some(#server_fn_path::xxhash_rust::const_xxh64::xxh64(
+                        format!("{self:?}").as_bytes(),
+                        0,
+                    ))
+                }
+            }
+        });
+
+    // multipart arguments other than `MultipartData` itself, like `FileUpload`,
+    // are converted from and into it
+    let multipart_impl = match first_field {
+        Some((name, ty))
+            if input_ident.as_deref() == Some("MultipartFormData")
+                && body.inputs.len() == 1
+                && !matches!(ty.as_ref(), Type::Path(path)
+                if path.path.segments.last().is_some_and(|segment| {
+                    segment.ident == "MultipartData"
+                })) =>
+        {
+            quote! {
+                impl From<#server_fn_path::codec::MultipartData> for #struct_name {
+                    fn from(data: #server_fn_path::codec::MultipartData) -> Self {
+                        #struct_name { #name: data.into() }
+                    }
+                }
+
+                impl From<#struct_name> for #server_fn_path::codec::MultipartData {
+                    fn from(value: #struct_name) -> Self {
+                        let #struct_name { #name } = value;
+                        #name.into()
+                    }
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
     let key_env_var = match option_env!("SERVER_FN_OVERRIDE_KEY") {
         Some(_) => "SERVER_FN_OVERRIDE_KEY",
         None => "CARGO_MANIFEST_DIR",
@@ -279,111 +546,143 @@ pub fn server_macro_impl(
         .collect::<TokenStream2>();
 
     // auto-registration with inventory
-    let inventory = if cfg!(feature = "ssr") {
-        quote! {
-            #server_fn_path::inventory::submit! {{
-                use #server_fn_path::{ServerFn, codec::Encoding};
-                #server_fn_path::ServerFnTraitObj::new(
-                    #wrapped_struct_name_turbofish::PATH,
-                    <#wrapped_struct_name as ServerFn>::InputEncoding::METHOD,
-                    |req| {
-                        Box::pin(#wrapped_struct_name_turbofish::run_on_server(req))
-                    },
-                    #wrapped_struct_name_turbofish::middlewares
-                )
-            }}
-        }
-    } else {
-        quote! {}
-    };
-
-    // run_body in the trait implementation
-    let run_body = if cfg!(feature = "ssr") {
-        let destructure = if let Some(wrapper) = custom_wrapper.as_ref() {
-            quote! {
-                let #wrapper(#struct_name { #(#field_names),* }) = self;
-            }
-        } else {
-            quote! {
-                let #struct_name { #(#field_names),* } = self;
+    let inventory =
+        |wrapped_struct_name: &TokenStream2,
+         wrapped_struct_name_turbofish: &TokenStream2| {
+            if cfg!(feature = "ssr") {
+                quote! {
+                    #server_fn_path::inventory::submit! {{
+                        use #server_fn_path::{ServerFn, codec::Encoding};
+                        #server_fn_path::ServerFnTraitObj::new(
+                            #wrapped_struct_name_turbofish::PATH,
+                            <#wrapped_struct_name as ServerFn>::InputEncoding::METHOD,
+                            <#wrapped_struct_name as ServerFn>::InputEncoding::CONTENT_TYPE,
+                            <#wrapped_struct_name as ServerFn>::OutputEncoding::CONTENT_TYPE,
+                            |req| {
+                                Box::pin(#wrapped_struct_name_turbofish::run_on_server(req))
+                            },
+                            #wrapped_struct_name_turbofish::middlewares
+                        )
+                    }}
+                }
+            } else {
+                quote! {}
             }
         };
 
-        // using the impl Future syntax here is thanks to Actix
-        //
-        // if we use Actix types inside the function, here, it becomes !Send
-        // so we need to add SendWrapper, because Actix won't actually send it anywhere
-        // but if we used SendWrapper in an async fn, the types don't work out because it
-        // becomes impl Future<Output = SendWrapper<_>>
-        //
-        // however, SendWrapper<Future<Output = T>> impls Future<Output = T>
-        let body = quote! {
-            #destructure
-            #dummy_name(#(#field_names),*).await
-        };
-        let body = if cfg!(feature = "actix") {
+    // run_body in the trait implementation, which calls a generic server
+    // function with the types it is registered for
+    let run_body = |registration: Option<&AngleBracketedGenericArguments>| {
+        if cfg!(feature = "ssr") {
+            let destructure = if let Some(wrapper) = custom_wrapper.as_ref() {
+                quote! {
+                    let #wrapper(#struct_name { #(#field_names),* }) = self;
+                }
+            } else if is_generic {
+                quote! {
+                    let #struct_name { #(#field_names,)* .. } = self;
+                }
+            } else {
+                quote! {
+                    let #struct_name { #(#field_names),* } = self;
+                }
+            };
+
+            // using the impl Future syntax here is thanks to Actix
+            //
+            // if we use Actix types inside the function, here, it becomes !Send
+            // so we need to add SendWrapper, because Actix won't actually send it anywhere
+            // but if we used SendWrapper in an async fn, the types don't work out because it
+            // becomes impl Future<Output = SendWrapper<_>>
+            //
+            // however, SendWrapper<Future<Output = T>> impls Future<Output = T>
+            let map_err = typed_error.map(|_| {
             quote! {
-                #server_fn_path::actix::SendWrapper::new(async move {
+                .map_err(|err| #server_fn_path::ServerFnError::WrappedServerError(
+                    #server_fn_path::error::Typed(err)
+                ))
+            }
+        });
+            let dummy_fn = match registration {
+                Some(types) => quote! { #dummy_name::#types },
+                None => quote! { #dummy_name },
+            };
+            let body = quote! {
+                #destructure
+                #dummy_fn(#(#field_names),*).await #map_err
+            };
+            let body = if cfg!(feature = "actix") {
+                quote! {
+                    #server_fn_path::actix::SendWrapper::new(async move {
+                        #body
+                    })
+                }
+            } else {
+                quote! { async move {
                     #body
-                })
+                }}
+            };
+            quote! {
+                // we need this for Actix, for the SendWrapper to count as impl Future
+                // but non-Actix will have a clippy warning otherwise
+                #[allow(clippy::manual_async_fn)]
+                fn run_body(self) -> impl std::future::Future<Output = #run_body_ty> + Send {
+                    #body
+                }
             }
         } else {
-            quote! { async move {
-                #body
-            }}
-        };
-        quote! {
-            // we need this for Actix, for the SendWrapper to count as impl Future
-            // but non-Actix will have a clippy warning otherwise
-            #[allow(clippy::manual_async_fn)]
-            fn run_body(self) -> impl std::future::Future<Output = #return_ty> + Send {
-                #body
-            }
-        }
-    } else {
-        quote! {
-            #[allow(unused_variables)]
-            async fn run_body(self) -> #return_ty {
-                unreachable!()
+            quote! {
+                #[allow(unused_variables)]
+                async fn run_body(self) -> #run_body_ty {
+                    unreachable!()
+                }
             }
         }
     };
 
-    // the actual function definition
-    let func = if cfg!(feature = "ssr") {
-        quote! {
-            #docs
-            #(#attrs)*
-            #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
-                #dummy_name(#(#field_names),*).await
-            }
-        }
-    } else {
-        let restructure = if let Some(custom_wrapper) = custom_wrapper.as_ref()
-        {
-            quote! {
-                let data = #custom_wrapper(#struct_name { #(#field_names),* });
-            }
-        } else {
-            quote! {
-                let data = #struct_name { #(#field_names),* };
-            }
-        };
-        quote! {
-            #docs
-            #(#attrs)*
-            #[allow(unused_variables)]
-            #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
-                use #server_fn_path::ServerFn;
-                #restructure
-                data.run_on_client().await
-            }
+    // WebSocket server functions upgrade the request and run with the channel,
+    // and generate a `connect()` function for the client to open it
+    let (run_on_server, connect) = match first_field.filter(|_| websocket) {
+        Some((name, ty)) => {
+            let run_on_server = cfg!(feature = "ssr").then(|| {
+                quote! {
+                    fn run_on_server(
+                        req: Self::ServerRequest,
+                    ) -> impl std::future::Future<Output = Self::ServerResponse> + Send {
+                        #server_fn_path::websocket::run_on_server(
+                            req,
+                            |#name| #struct_name { #name },
+                        )
+                    }
+                }
+            });
+            let connect_docs = format!(
+                "Opens a connection to the [`{fn_name_as_str}`] server \
+                 function, returning the client's end of its channel."
+            );
+            let connect = quote! {
+                impl #struct_name {
+                    #[doc = #connect_docs]
+                    pub async fn connect() -> Result<
+                        <#ty as #server_fn_path::websocket::ServerChannel>::Client,
+                        #server_fn_path::ServerFnError<#error_ty>,
+                    > {
+                        #server_fn_path::websocket::connect(
+                            <Self as #server_fn_path::ServerFn>::PATH,
+                        )
+                        .await
+                    }
+                }
+            };
+            (run_on_server, Some(connect))
         }
+        None => (None, None),
     };
 
     enum PathInfo {
         Serde,
         Rkyv,
+        Protobuf,
         None,
     }
 
@@ -396,7 +695,14 @@ pub fn server_macro_impl(
         ),
         Some("MultipartFormData")
         | Some("Streaming")
-        | Some("StreamingText") => (PathInfo::None, quote! {}),
+        | Some("StreamingText")
+        | Some("Websocket") => (PathInfo::None, quote! {}),
+        Some("Protobuf") if input_derive.is_none() => (
+            PathInfo::Protobuf,
+            quote! {
+                Clone, #server_fn_path::prost::Message
+            },
+        ),
         Some("SerdeLite") => (
             PathInfo::Serde,
             quote! {
@@ -426,11 +732,223 @@ pub fn server_macro_impl(
                 #[archive(crate = #rkyv_path, check_bytes)]
             }
         }
+        // the struct already derives `Debug`
+        PathInfo::Protobuf => quote! {
+            #[prost(skip_debug)]
+        },
         PathInfo::None => quote! {},
     };
 
+    // the struct of a generic server function only knows its types through a
+    // marker, and has its own `serde` derives
+    if is_generic
+        && (custom_wrapper.is_some()
+            || websocket
+            || !matches!(path, PathInfo::Serde)
+            || input_ident.as_deref() == Some("SerdeLite"))
+    {
+        return Err(syn::Error::new(
+            body.ident.span(),
+            "generic server functions cannot use a `custom` wrapper, and \
+             their arguments must be serialized with `serde`",
+        ));
+    }
+
+    // OpenAPI descriptions, which are only registered for server functions
+    // whose arguments are serialized with `serde`
+    let output_ident = match output_ty {
+        GenericArgument::Type(Type::Path(path)) => {
+            path.path.segments.last().map(|seg| seg.ident.to_string())
+        }
+        _ => None,
+    };
+    let openapi = (cfg!(feature = "openapi")
+        && cfg!(feature = "ssr")
+        && !is_generic
+        && matches!(path, PathInfo::Serde)
+        && input_ident.as_deref() != Some("SerdeLite"))
+    .then(|| {
+        let schemars_path = format!("{server_fn_path}::schemars");
+        let docs = body
+            .docs
+            .iter()
+            .map(|(doc, _)| doc.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let name = struct_name.to_string();
+        let output_schema = match output_ident.as_deref() {
+            Some("ByteStream") | Some("TextStream") => quote! { None },
+            _ => quote! {
+                Some(#server_fn_path::schemars::gen::SchemaGenerator::subschema_for::<#output_ty>)
+            },
+        };
+        let derive = quote! {
+            #[derive(#server_fn_path::schemars::JsonSchema)]
+            #[schemars(crate = #schemars_path)]
+        };
+        let submit = quote! {
+            #server_fn_path::inventory::submit! {{
+                use #server_fn_path::{ServerFn, codec::Encoding};
+                #server_fn_path::openapi::ServerFnSchema::new(
+                    #wrapped_struct_name_turbofish::PATH,
+                    <#wrapped_struct_name as ServerFn>::InputEncoding::METHOD,
+                    <#wrapped_struct_name as ServerFn>::InputEncoding::CONTENT_TYPE,
+                    <#wrapped_struct_name as ServerFn>::OutputEncoding::CONTENT_TYPE,
+                    #name,
+                    #docs,
+                    <#struct_name as #server_fn_path::schemars::JsonSchema>::json_schema,
+                    #output_schema,
+                )
+            }}
+        };
+        (derive, submit)
+    });
+    let (openapi_derive, openapi_submit) = openapi.unzip();
+
+    // failed calls are retried by making the request again with a clone of
+    // the arguments, so only server functions whose struct is `Clone` can be
+    // retried
+    let retries = custom_wrapper.is_none()
+        && derives.clone().into_iter().any(
+            |token| matches!(token, TokenTree::Ident(ident) if ident == "Clone"),
+        );
+    if let Some(retry) = retry.as_ref().filter(|_| !retries) {
+        return Err(syn::Error::new(
+            retry.span(),
+            "only server functions whose arguments can be cloned, and that do \
+             not use a `custom` wrapper, can have a `retry` policy",
+        ));
+    }
+    // cached calls are also keyed by their serialized arguments
+    if let Some(cache) = cache.as_ref().filter(|_| !retries) {
+        return Err(syn::Error::new(
+            cache.span(),
+            "only server functions whose arguments can be cloned, and that do \
+             not use a `custom` wrapper, can be cached",
+        ));
+    }
+    let cache_ttl = cache.as_ref().map(|ttl| {
+        quote! {
+            fn cache_ttl() -> Option<std::time::Duration> {
+                Some(#ttl)
+            }
+        }
+    });
+    let retry_policy = match (
+        retry,
+        idempotent.map(|idempotent| idempotent.value),
+    ) {
+        (Some(retry), _) => Some(quote! { Some(#retry) }),
+        (None, Some(true)) => {
+            Some(quote! { #server_fn_path::retry::retry_policy() })
+        }
+        (None, Some(false)) => Some(quote! { None }),
+        (None, None) => None,
+    }
+    .map(|policy| {
+        quote! {
+            fn retry_policy() -> Option<#server_fn_path::retry::RetryPolicy> {
+                #policy
+            }
+        }
+    });
+
+    // a generic server function can only be called with the types it is
+    // registered for
+    let (_, ty_generics, _) = body.generics.split_for_impl();
+    let mut fn_generics = body.generics.clone();
+    if is_generic {
+        fn_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! {
+                #struct_name #ty_generics: #server_fn_path::ServerFn<
+                Output = #output_ty,
+                Error = #error_ty,
+            >
+            });
+    }
+    let (fn_impl_generics, _, fn_where_clause) = fn_generics.split_for_impl();
+    let turbofish = is_generic.then(|| quote! { ::#ty_generics });
+
+    let restructure = if let Some(custom_wrapper) = custom_wrapper.as_ref() {
+        quote! {
+            let data = #custom_wrapper(#struct_name { #(#field_names),* });
+        }
+    } else if is_generic {
+        quote! {
+            let data = #struct_name #turbofish {
+                #(#field_names,)*
+                _marker: ::std::marker::PhantomData,
+            };
+        }
+    } else {
+        quote! {
+            let data = #struct_name { #(#field_names),* };
+        }
+    };
+    let map_typed_error = typed_error.is_some().then(|| {
+        quote! { .map_err(#server_fn_path::ServerFnError::into_typed) }
+    });
+
+    // a mock registered in a test replaces the server function, wherever it
+    // is called from
+    let server_fn_ty = if is_generic {
+        quote! { #struct_name #ty_generics }
+    } else {
+        wrapped_struct_name.clone()
+    };
+    let mock = quote! {
+        if let Some(mock) = #server_fn_path::mock::get::<#server_fn_ty>() {
+            #restructure
+            return mock(data).await #map_typed_error;
+        }
+    };
+
+    // the actual function definition
+    let func = if cfg!(feature = "ssr") {
+        quote! {
+            #docs
+            #(#attrs)*
+            #vis async fn #fn_name #fn_impl_generics (#(#fn_args),*) #output_arrow #return_ty #fn_where_clause {
+                #mock
+                #dummy_name #turbofish (#(#field_names),*).await
+            }
+        }
+    } else {
+        let call = if cache.is_some() {
+            quote! { #server_fn_path::cache::run_cached(data).await }
+        } else if retries {
+            quote! { data.run_on_client_with_retries().await }
+        } else {
+            quote! { data.run_on_client().await }
+        };
+        quote! {
+            #docs
+            #(#attrs)*
+            #[allow(unused_variables)]
+            #vis async fn #fn_name #fn_impl_generics (#(#fn_args),*) #output_arrow #return_ty #fn_where_clause {
+                use #server_fn_path::ServerFn;
+                #mock
+                #restructure
+                #call #map_typed_error
+            }
+        }
+    };
+
+    // multipart form data and websockets can only be sent by the default
+    // client of the target
     let client = if let Some(client) = client {
         client.to_token_stream()
+    } else if cfg!(feature = "dynamic-client")
+        && !matches!(
+            input_ident.as_deref(),
+            Some("MultipartFormData") | Some("Websocket")
+        )
+    {
+        quote! {
+            #server_fn_path::client::transport::DynClient
+        }
     } else if cfg!(feature = "reqwest") {
         quote! {
             #server_fn_path::client::reqwest::ReqwestClient
@@ -440,11 +958,20 @@ pub fn server_macro_impl(
             #server_fn_path::client::browser::BrowserClient
         }
     };
+    let client = if batch.is_some() {
+        quote! { #server_fn_path::batch::BatchClient<#client> }
+    } else {
+        client
+    };
 
     let req = if !cfg!(feature = "ssr") {
         quote! {
             #server_fn_path::request::BrowserMockReq
         }
+    } else if let Some(req_ty) = req_ty {
+        // an explicit type wins over the framework features, which may all be
+        // enabled at once when several integrations are built together
+        req_ty.to_token_stream()
     } else if cfg!(feature = "axum") {
         quote! {
             #server_fn_path::axum_export::http::Request<#server_fn_path::axum_export::body::Body>
@@ -453,8 +980,6 @@ pub fn server_macro_impl(
         quote! {
             #server_fn_path::request::actix::ActixRequest
         }
-    } else if let Some(req_ty) = req_ty {
-        req_ty.to_token_stream()
     } else if let Some(req_ty) = preset_req {
         req_ty.to_token_stream()
     } else {
@@ -469,6 +994,8 @@ pub fn server_macro_impl(
         quote! {
             #server_fn_path::response::BrowserMockRes
         }
+    } else if let Some(res_ty) = res_ty {
+        res_ty.to_token_stream()
     } else if cfg!(feature = "axum") {
         quote! {
             #server_fn_path::axum_export::http::Response<#server_fn_path::axum_export::body::Body>
@@ -477,8 +1004,6 @@ pub fn server_macro_impl(
         quote! {
             #server_fn_path::response::actix::ActixResponse
         }
-    } else if let Some(res_ty) = res_ty {
-        res_ty.to_token_stream()
     } else if let Some(res_ty) = preset_res {
         res_ty.to_token_stream()
     } else {
@@ -507,22 +1032,39 @@ pub fn server_macro_impl(
     } else {
         quote! { concat!("/", #fn_path) }
     };
-    let path = quote! {
-        if #fn_path.is_empty() {
-            #server_fn_path::const_format::concatcp!(
-                #prefix,
-                "/",
-                #fn_name_as_str,
-                #server_fn_path::xxhash_rust::const_xxh64::xxh64(
-                    concat!(env!(#key_env_var), ":", file!(), ":", line!(), ":", column!()).as_bytes(),
-                    0
+    // each type that a generic server function is registered for gets its
+    // own endpoint, ending in the name of the type
+    let path_for = |registration: Option<&AngleBracketedGenericArguments>| {
+        let suffix = registration.map(|types| {
+            let name = types
+                .args
+                .to_token_stream()
+                .to_string()
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join("_");
+            quote! { , "/", #name }
+        });
+        quote! {
+            if #fn_path.is_empty() {
+                #server_fn_path::const_format::concatcp!(
+                    #prefix,
+                    "/",
+                    #fn_name_as_str,
+                    #server_fn_path::xxhash_rust::const_xxh64::xxh64(
+                        concat!(env!(#key_env_var), ":", file!(), ":", line!(), ":", column!()).as_bytes(),
+                        0
+                    )
+                    #suffix
                 )
-            )
-        } else {
-            #server_fn_path::const_format::concatcp!(
-                #prefix,
-                #fn_path
-            )
+            } else {
+                #server_fn_path::const_format::concatcp!(
+                    #prefix,
+                    #fn_path
+                    #suffix
+                )
+            }
         }
     };
 
@@ -532,7 +1074,7 @@ pub fn server_macro_impl(
         quote! {
             vec![
                 #(
-                    std::sync::Arc::new(#middlewares),
+                    std::sync::Arc::new(#middlewares)
                 ),*
             ]
         }
@@ -540,36 +1082,145 @@ pub fn server_macro_impl(
         quote! { vec![] }
     };
 
-    Ok(quote::quote! {
-        #args_docs
-        #docs
-        #[derive(Debug, #derives)]
-        #addl_path
-        pub struct #struct_name {
-            #(#fields),*
+    // the struct of a generic server function is generic over the same types,
+    // which are only used by a marker, so `Clone` and `Debug` are implemented
+    // without requiring them of the types
+    let struct_def = if is_generic {
+        let (impl_generics, ty_generics, where_clause) =
+            body.generics.split_for_impl();
+        let generics = &body.generics;
+        let struct_name_str = struct_name.to_string();
+        let field_name_strs = field_names
+            .iter()
+            .map(|name| name.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        let new_docs = format!(
+            "Creates the arguments for the [`{fn_name_as_str}`] server \
+             function."
+        );
+        quote! {
+            #args_docs
+            #docs
+            #[derive(#server_fn_path::serde::Serialize, #server_fn_path::serde::Deserialize)]
+            #addl_path
+            pub struct #struct_name #generics #where_clause {
+                #(#fields,)*
+                #[serde(skip)]
+                _marker: ::std::marker::PhantomData<fn() -> (#(#type_params,)*)>,
+            }
+
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #[doc = #new_docs]
+                pub fn new(#(#fn_args),*) -> Self {
+                    Self {
+                        #(#field_names,)*
+                        _marker: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #impl_generics Clone for #struct_name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    Self {
+                        #(#field_names: self.#field_names.clone(),)*
+                        _marker: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl #impl_generics ::std::fmt::Debug for #struct_name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.debug_struct(#struct_name_str)
+                        #(.field(#field_name_strs, &self.#field_names))*
+                        .finish()
+                }
+            }
+        }
+    } else {
+        quote! {
+            #args_docs
+            #docs
+            #[derive(Debug, #derives)]
+            #addl_path
+            #openapi_derive
+            pub struct #struct_name {
+                #(#fields),*
+            }
         }
+    };
 
-        #from_impl
+    let instances = if is_generic {
+        registrations
+            .iter()
+            .map(|types| {
+                (
+                    quote! { #struct_name #types },
+                    quote! { #struct_name::#types },
+                    Some(types),
+                )
+            })
+            .collect::<Vec<_>>()
+    } else {
+        vec![(wrapped_struct_name, wrapped_struct_name_turbofish, None)]
+    };
+    // one implementation of `ServerFn` for each set of types that a generic
+    // server function is registered for
+    let impls = instances.iter().map(|instance| {
+        let (wrapped_struct_name, wrapped_struct_name_turbofish, registration) =
+            instance;
+        let path = path_for(*registration);
+        let run_body = run_body(*registration);
+        let inventory =
+            inventory(wrapped_struct_name, wrapped_struct_name_turbofish);
+        quote! {
+            impl #server_fn_path::ServerFn for #wrapped_struct_name {
+                const PATH: &'static str = #path;
+
+                type Client = #client;
+                type ServerRequest = #req;
+                type ServerResponse = #res;
+                type Output = #output_ty;
+                type InputEncoding = #input;
+                type OutputEncoding = #output;
+                type Error = #error_ty;
+
+                fn middlewares() -> Vec<std::sync::Arc<dyn #server_fn_path::middleware::Layer<#req, #res>>> {
+                    #middlewares
+                }
+
+                #retry_policy
+
+                #cache_ttl
+
+                #error_status
+
+                #path_params_impl
+
+                #validate_impl
 
-        impl #server_fn_path::ServerFn for #wrapped_struct_name {
-            const PATH: &'static str = #path;
+                #args_fingerprint
 
-            type Client = #client;
-            type ServerRequest = #req;
-            type ServerResponse = #res;
-            type Output = #output_ty;
-            type InputEncoding = #input;
-            type OutputEncoding = #output;
-            type Error = #error_ty;
+                #run_body
 
-            fn middlewares() -> Vec<std::sync::Arc<dyn #server_fn_path::middleware::Layer<#req, #res>>> {
-                #middlewares
+                #run_on_server
             }
 
-            #run_body
+            #inventory
         }
+    });
+
+    Ok(quote::quote! {
+        #struct_def
+
+        #from_impl
+
+        #multipart_impl
 
-        #inventory
+        #(#impls)*
+
+        #connect
+
+        #openapi_submit
 
         #func
 
@@ -577,6 +1228,19 @@ pub fn server_macro_impl(
     })
 }
 
+// the names of the `{name}` segments of an endpoint
+fn path_param_names(fn_path: &Literal) -> Result<Vec<(String, Span)>> {
+    let fn_path = syn::parse2::<LitStr>(fn_path.to_token_stream())?;
+    Ok(fn_path
+        .value()
+        .split('/')
+        .filter_map(|segment| {
+            segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'))
+        })
+        .map(|name| (name.to_string(), fn_path.span()))
+        .collect())
+}
+
 fn type_from_ident(ident: Ident) -> Type {
     let mut segments = Punctuated::new();
     segments.push(PathSegment {
@@ -633,7 +1297,16 @@ fn output_type(return_ty: &Type) -> Result<&GenericArgument> {
     ))
 }
 
-fn err_type(return_ty: &Type) -> Result<Option<&GenericArgument>> {
+enum ErrorType<'a> {
+    // Result<T> or Result<T, ServerFnError>
+    Default,
+    // Result<T, ServerFnError<E>>
+    Custom(&'a GenericArgument),
+    // Result<T, E>, where E: FromServerFnError
+    Typed(&'a Type),
+}
+
+fn err_type(return_ty: &Type) -> Result<ErrorType<'_>> {
     if let syn::Type::Path(pat) = &return_ty {
         if pat.path.segments[0].ident == "Result" {
             if let PathArguments::AngleBracketed(args) =
@@ -641,10 +1314,10 @@ fn err_type(return_ty: &Type) -> Result<Option<&GenericArgument>> {
             {
                 // Result<T>
                 if args.args.len() == 1 {
-                    return Ok(None);
+                    return Ok(ErrorType::Default);
                 }
                 // Result<T, _>
-                else if let GenericArgument::Type(Type::Path(pat)) =
+                else if let GenericArgument::Type(ty @ Type::Path(pat)) =
                     &args.args[1]
                 {
                     if let Some(segment) = pat.path.segments.last() {
@@ -652,15 +1325,22 @@ fn err_type(return_ty: &Type) -> Result<Option<&GenericArgument>> {
                             let args = &segment.arguments;
                             match args {
                                 // Result<T, ServerFnError>
-                                PathArguments::None => return Ok(None),
+                                PathArguments::None => {
+                                    return Ok(ErrorType::Default)
+                                }
                                 // Result<T, ServerFnError<E>>
-                                PathArguments::AngleBracketed(args) => {
-                                    if args.args.len() == 1 {
-                                        return Ok(Some(&args.args[0]));
-                                    }
+                                PathArguments::AngleBracketed(args)
+                                    if args.args.len() == 1 =>
+                                {
+                                    return Ok(ErrorType::Custom(
+                                        &args.args[0],
+                                    ));
                                 }
                                 _ => {}
                             }
+                        } else {
+                            // Result<T, E>
+                            return Ok(ErrorType::Typed(ty));
                         }
                     }
                 }
@@ -670,34 +1350,45 @@ fn err_type(return_ty: &Type) -> Result<Option<&GenericArgument>> {
 
     Err(syn::Error::new(
         return_ty.span(),
-        "server functions should return Result<T, ServerFnError> or Result<T, \
-         ServerFnError<E>>",
+        "server functions should return Result<T, ServerFnError>, Result<T, \
+         ServerFnError<E>>, or Result<T, E> where E: FromServerFnError",
     ))
 }
 
 #[derive(Debug)]
 struct ServerFnArgs {
     struct_name: Option<Ident>,
-    prefix: Option<Literal>,
+    prefix: Option<syn::Expr>,
     input: Option<Type>,
     input_derive: Option<ExprTuple>,
     output: Option<Type>,
     fn_path: Option<Literal>,
+    version: Option<LitStr>,
+    builtin_input: bool,
     req_ty: Option<Type>,
     res_ty: Option<Type>,
     client: Option<Type>,
     custom_wrapper: Option<Path>,
     builtin_encoding: bool,
     impl_from: Option<LitBool>,
+    websocket: bool,
+    middlewares: Vec<Middleware>,
+    retry: Option<syn::Expr>,
+    idempotent: Option<LitBool>,
+    cache: Option<syn::Expr>,
+    validate: Option<LitBool>,
+    batch: Option<LitBool>,
 }
 
 impl Parse for ServerFnArgs {
     fn parse(stream: ParseStream) -> syn::Result<Self> {
         // legacy 4-part arguments
         let mut struct_name: Option<Ident> = None;
-        let mut prefix: Option<Literal> = None;
+        let mut prefix: Option<syn::Expr> = None;
         let mut encoding: Option<Literal> = None;
         let mut fn_path: Option<Literal> = None;
+        let mut version: Option<LitStr> = None;
+        let mut method: Option<LitStr> = None;
 
         // new arguments: can only be keyed by name
         let mut input: Option<Type> = None;
@@ -708,6 +1399,13 @@ impl Parse for ServerFnArgs {
         let mut client: Option<Type> = None;
         let mut custom_wrapper: Option<Path> = None;
         let mut impl_from: Option<LitBool> = None;
+        let mut protocol: Option<LitStr> = None;
+        let mut middlewares: Option<Vec<Middleware>> = None;
+        let mut retry: Option<syn::Expr> = None;
+        let mut idempotent: Option<LitBool> = None;
+        let mut cache: Option<syn::Expr> = None;
+        let mut validate: Option<LitBool> = None;
+        let mut batch: Option<LitBool> = None;
 
         let mut use_key_and_value = false;
         let mut arg_pos = 0;
@@ -755,6 +1453,22 @@ impl Parse for ServerFnArgs {
                             ));
                         }
                         fn_path = Some(stream.parse()?);
+                    } else if key == "version" {
+                        if version.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `version`",
+                            ));
+                        }
+                        version = Some(stream.parse()?);
+                    } else if key == "method" {
+                        if method.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `method`",
+                            ));
+                        }
+                        method = Some(stream.parse()?);
                     } else if key == "input" {
                         if encoding.is_some() {
                             return Err(syn::Error::new(
@@ -831,6 +1545,70 @@ impl Parse for ServerFnArgs {
                             ));
                         }
                         impl_from = Some(stream.parse()?);
+                    } else if key == "protocol" {
+                        if protocol.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `protocol`",
+                            ));
+                        }
+                        protocol = Some(stream.parse()?);
+                    } else if key == "middleware" {
+                        if middlewares.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `middleware`",
+                            ));
+                        }
+                        // either a single layer, or an array of them
+                        middlewares = Some(match stream.parse()? {
+                            syn::Expr::Array(array) => array
+                                .elems
+                                .into_iter()
+                                .map(|expr| Middleware { expr })
+                                .collect(),
+                            expr => vec![Middleware { expr }],
+                        });
+                    } else if key == "retry" {
+                        if retry.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `retry`",
+                            ));
+                        }
+                        retry = Some(stream.parse()?);
+                    } else if key == "idempotent" {
+                        if idempotent.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `idempotent`",
+                            ));
+                        }
+                        idempotent = Some(stream.parse()?);
+                    } else if key == "validate" {
+                        if validate.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `validate`",
+                            ));
+                        }
+                        validate = Some(stream.parse()?);
+                    } else if key == "batch" {
+                        if batch.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `batch`",
+                            ));
+                        }
+                        batch = Some(stream.parse()?);
+                    } else if key == "cache" {
+                        if cache.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `cache`",
+                            ));
+                        }
+                        cache = Some(stream.parse()?);
                     } else {
                         return Err(lookahead.error());
                     }
@@ -863,7 +1641,7 @@ impl Parse for ServerFnArgs {
                 }
                 match arg_pos {
                     1 => return Err(lookahead.error()),
-                    2 => prefix = Some(value),
+                    2 => prefix = Some(syn::parse_quote!(#value)),
                     3 => encoding = Some(value),
                     4 => fn_path = Some(value),
                     _ => {
@@ -915,6 +1693,65 @@ impl Parse for ServerFnArgs {
             }
         }
 
+        // the HTTP method chooses the input encoding
+        let mut builtin_input = false;
+        if let Some(method) = method {
+            if input.is_some() || builtin_encoding || protocol.is_some() {
+                return Err(syn::Error::new(
+                    method.span(),
+                    "`method` should not be specified along with `input`, \
+                     `encoding`, or `protocol`",
+                ));
+            }
+            let encoding =
+                match method.value().to_uppercase().as_str() {
+                    "GET" => "GetUrl",
+                    "POST" => "PostUrl",
+                    "PUT" => "PutJson",
+                    "PATCH" => "PatchJson",
+                    "DELETE" => "DeleteUrl",
+                    _ => return Err(syn::Error::new(
+                        method.span(),
+                        "Method not found. Expected one of \"GET\", \"POST\", \
+                         \"PUT\", \"PATCH\", or \"DELETE\".",
+                    )),
+                };
+            input = Some(type_from_ident(Ident::new(encoding, method.span())));
+            builtin_input = true;
+        }
+
+        let websocket = match protocol {
+            None => false,
+            Some(protocol) if protocol.value() == "websocket" => {
+                if input.is_some() || output.is_some() {
+                    return Err(syn::Error::new(
+                        protocol.span(),
+                        "WebSocket server functions do not have an input or \
+                         output encoding",
+                    ));
+                }
+                true
+            }
+            Some(protocol) => {
+                return Err(syn::Error::new(
+                    protocol.span(),
+                    "Protocol not found.",
+                ))
+            }
+        };
+
+        // a retry policy opts the server function in to being retried, so it
+        // cannot be combined with declaring it non-idempotent
+        if let (Some(retry), Some(idempotent)) = (&retry, &idempotent) {
+            if !idempotent.value {
+                return Err(syn::Error::new(
+                    retry.span(),
+                    "server functions with `idempotent = false` cannot have \
+                     a `retry` policy",
+                ));
+            }
+        }
+
         Ok(Self {
             struct_name,
             prefix,
@@ -922,12 +1759,21 @@ impl Parse for ServerFnArgs {
             input_derive,
             output,
             fn_path,
+            version,
+            builtin_input,
             builtin_encoding,
             req_ty,
             res_ty,
             client,
             custom_wrapper,
             impl_from,
+            websocket,
+            middlewares: middlewares.unwrap_or_default(),
+            retry,
+            idempotent,
+            cache,
+            validate,
+            batch,
         })
     }
 }
@@ -966,6 +1812,8 @@ impl Parse for ServerFnBody {
 
         let output_arrow = input.parse()?;
         let return_ty = input.parse()?;
+        let mut generics = generics;
+        generics.where_clause = input.parse()?;
 
         let block = input.parse()?;
 
@@ -1033,10 +1881,11 @@ impl ServerFnBody {
             block,
             ..
         } = &self;
+        let where_clause = &generics.where_clause;
         quote! {
             #[doc(hidden)]
             #(#attrs)*
-            #vis #async_token #fn_token #ident #generics ( #inputs ) #output_arrow #return_ty
+            #vis #async_token #fn_token #ident #generics ( #inputs ) #output_arrow #return_ty #where_clause
             #block
         }
     }